@@ -0,0 +1,125 @@
+// Coffee Shop Order System - Compression Benchmark
+//
+// How much smaller each on-disk format gets with each codec, and what that
+// costs in CPU when writing and when opening the file.
+//
+// Run: cargo bench --features gzip,zstd [-- --days N]
+//
+// Codecs that are not compiled in are listed as skipped. The numbers depend
+// on the machine; the ratios between rows are what matters.
+
+use coffee_shop_solid::adapters::{BinaryOrderRepository, Compression, JsonOrderRepository};
+use coffee_shop_solid::domain::Order;
+use coffee_shop_solid::ports::{OrderRepository, RepositoryError};
+use coffee_shop_solid::seed::{SeedConfig, SeedGenerator};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Each measurement is the best of this many runs
+const RUNS: u32 = 5;
+
+struct Measurement {
+    bytes: u64,
+    write: Duration,
+    open: Duration,
+}
+
+fn main() {
+    let days = days_arg();
+    let orders = SeedGenerator::new(SeedConfig::new(days)).generate();
+    println!(
+        "{} orders ({} days of demo data), best of {} runs\n",
+        orders.len(),
+        days,
+        RUNS
+    );
+    println!(
+        "{:<8} {:<6} {:>12} {:>7} {:>10} {:>10}",
+        "format", "codec", "size", "ratio", "write", "open"
+    );
+
+    for format in ["json", "bin"] {
+        let mut plain_bytes = None;
+        for codec in Compression::ALL {
+            if !codec.is_available() {
+                println!(
+                    "{:<8} {:<6} {:>12}",
+                    format,
+                    codec.name(),
+                    "(skipped, feature off)"
+                );
+                continue;
+            }
+
+            let m = measure(format, codec, &orders).expect("benchmark run failed");
+            let plain = *plain_bytes.get_or_insert(m.bytes);
+            println!(
+                "{:<8} {:<6} {:>12} {:>6.1}x {:>8.1}ms {:>8.1}ms",
+                format,
+                codec.name(),
+                m.bytes,
+                plain as f64 / m.bytes as f64,
+                m.write.as_secs_f64() * 1000.0,
+                m.open.as_secs_f64() * 1000.0
+            );
+        }
+    }
+}
+
+fn measure(
+    format: &str,
+    codec: Compression,
+    orders: &[Order],
+) -> Result<Measurement, RepositoryError> {
+    let path = env::temp_dir().join(format!(
+        "bench-{}-{}.{}",
+        std::process::id(),
+        codec.name(),
+        format
+    ));
+    let mut best = Measurement {
+        bytes: 0,
+        write: Duration::MAX,
+        open: Duration::MAX,
+    };
+
+    for _ in 0..RUNS {
+        let _ = fs::remove_file(&path);
+        let started = Instant::now();
+        open(format, &path, codec)?.save_batch(orders)?;
+        best.write = best.write.min(started.elapsed());
+
+        let started = Instant::now();
+        let reopened = open(format, &path, codec)?;
+        best.open = best.open.min(started.elapsed());
+        assert_eq!(reopened.list_all()?.len(), orders.len());
+    }
+
+    best.bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or_default();
+    let _ = fs::remove_file(&path);
+    Ok(best)
+}
+
+fn open(
+    format: &str,
+    path: &Path,
+    codec: Compression,
+) -> Result<Box<dyn OrderRepository>, RepositoryError> {
+    let path = PathBuf::from(path);
+    Ok(match format {
+        "bin" => Box::new(BinaryOrderRepository::new(path)?.with_compression(codec)),
+        _ => Box::new(JsonOrderRepository::new(path)?.with_compression(codec)),
+    })
+}
+
+/// `-- --days N` (default 30); cargo also passes `--bench`, which is ignored
+fn days_arg() -> u32 {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--days")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30)
+}
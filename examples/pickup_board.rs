@@ -0,0 +1,165 @@
+// Coffee Shop Order System - Live Pickup Board
+//
+// A simulated shop places, finishes and sometimes cancels orders on the
+// message bus; a PickupBoard follows them, and every browser that opens the
+// page sees codes move from "Preparing" to "Ready" as it happens.
+//
+//   GET /         the board page
+//   GET /events   text/event-stream: the whole board, then every change
+//
+// The HTTP side is a few lines of std::net, one thread per connection: enough
+// for the screens above the counter, not a web framework.
+//
+// Run: cargo run --example pickup_board [-- --port 8080], then open
+//      http://127.0.0.1:8080 in one or more browser tabs
+
+use coffee_shop_solid::bus::MessageBus;
+use coffee_shop_solid::presentation::{board_event, render_pickup_board};
+use coffee_shop_solid::services::PickupBoard;
+use coffee_shop_solid::prelude::*;
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The board, and one channel per connected screen
+struct Screens {
+    board: PickupBoard,
+    clients: Vec<Sender<String>>,
+}
+
+fn main() -> io::Result<()> {
+    let port = port_arg();
+    let bus = MessageBus::new();
+    let screens = Arc::new(Mutex::new(Screens {
+        board: PickupBoard::subscribe(&bus),
+        clients: Vec::new(),
+    }));
+
+    thread::spawn(move || run_shop(bus));
+    {
+        let screens = screens.clone();
+        thread::spawn(move || relay(&screens));
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("📺 Pickup board on http://127.0.0.1:{} (Ctrl+C to stop)", port);
+    for stream in listener.incoming().flatten() {
+        let screens = screens.clone();
+        thread::spawn(move || {
+            if let Err(e) = serve(stream, &screens) {
+                eprintln!("📺 Screen disconnected: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Place an order every 2 seconds; each is ready 3 rounds later, 1 in 7 is cancelled
+fn run_shop(bus: MessageBus) {
+    let mut service = OrderService::new(
+        MemoryOrderRepository::new(),
+        CashPayment,
+        ConsoleNotifier::with_writer(io::sink()),
+    )
+    .with_message_bus(bus);
+    let mut preparing = Vec::new();
+
+    for n in 1.. {
+        let customer = Customer::named(&format!("Customer {}", n));
+        let beverages: Vec<Box<dyn Beverage>> =
+            vec![Box::new(Coffee::medium().with_extra_shots((n % 3) as u8))];
+        if let Ok(order) = service.place_order(customer, beverages) {
+            preparing.push(order.id);
+        }
+
+        if preparing.len() > 3 {
+            let id = preparing.remove(0);
+            let _ = if n % 7 == 0 {
+                service.cancel_order(id)
+            } else {
+                service.mark_order_preparing(id).and_then(|()| service.mark_order_ready(id))
+            };
+        }
+        thread::sleep(Duration::from_secs(2));
+    }
+}
+
+/// Push every board change to every screen; forget screens that went away
+fn relay(screens: &Mutex<Screens>) {
+    loop {
+        {
+            let mut screens = screens.lock().unwrap();
+            let events: Vec<String> = screens.board.poll().iter().map(board_event).collect();
+            for event in events {
+                screens.clients.retain(|client| client.send(event.clone()).is_ok());
+            }
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
+
+fn serve(mut stream: TcpStream, screens: &Mutex<Screens>) -> io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+
+    match path {
+        "/" => {
+            let page = render_pickup_board("Pickup Board", "/events");
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            )
+        }
+        "/events" => stream_events(stream, screens),
+        _ => write!(
+            stream,
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        ),
+    }
+}
+
+/// The current board, then changes until the screen disconnects
+fn stream_events(mut stream: TcpStream, screens: &Mutex<Screens>) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n"
+    )?;
+
+    // Snapshot and subscription under one lock: no change is missed or sent twice
+    let (sender, receiver) = mpsc::channel();
+    {
+        let mut screens = screens.lock().unwrap();
+        for change in screens.board.snapshot() {
+            stream.write_all(board_event(change).as_bytes())?;
+        }
+        screens.clients.push(sender);
+    }
+
+    loop {
+        match receiver.recv_timeout(Duration::from_secs(15)) {
+            Ok(event) => stream.write_all(event.as_bytes())?,
+            // A comment line: keeps proxies from closing an idle stream, and
+            // fails (ending this thread) once the browser is gone
+            Err(RecvTimeoutError::Timeout) => stream.write_all(b": ping\n\n")?,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        stream.flush()?;
+    }
+}
+
+fn port_arg() -> u16 {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--port")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080)
+}
@@ -0,0 +1,189 @@
+// SOLID: AnnouncerNotifier - say "order ready" out loud through a TTS program
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    A Notifier like the others. It only speaks for orders that are ready
+//    (who wants the speaker to read every new order?) and sends nothing else.
+//
+// 2. OPEN-CLOSED PRINCIPLE (OCP):
+//    The speech engine is any program that takes the text as an argument:
+//    `espeak`, `say` on macOS, `spd-say`, or a script calling a cloud voice.
+//    Changing engine is configuration, not code.
+//
+// An external process can be missing, fail, or hang. Each case becomes a
+// NotificationError within `timeout`, and a hung program is killed, so a
+// broken speaker never holds up the counter. The text is passed as one
+// argument, never through a shell, so a customer's name can't run commands.
+
+use crate::domain::{OrderCancelledNotice, OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice};
+use crate::ports::{NotificationError, Notifier};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Placeholder replaced by the announcement in the arguments
+pub const TEXT_PLACEHOLDER: &str = "{text}";
+
+/// Notifier that runs a text-to-speech command for ready orders
+pub struct AnnouncerNotifier {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl AnnouncerNotifier {
+    /// Run `program <text>`, e.g. `AnnouncerNotifier::new("espeak")`
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            args: vec![TEXT_PLACEHOLDER.to_string()],
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Arguments of the program; `{text}` marks where the announcement goes
+    ///
+    /// Without `{text}` in them, the announcement is added as the last argument.
+    pub fn with_args(mut self, args: &[&str]) -> Self {
+        self.args = args.iter().map(|arg| arg.to_string()).collect();
+        if !self.args.iter().any(|arg| arg.contains(TEXT_PLACEHOLDER)) {
+            self.args.push(TEXT_PLACEHOLDER.to_string());
+        }
+        self
+    }
+
+    /// Give up (and kill the program) after this long (default 10s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// "Order 3 F A 8 for Ada is ready": the code spelled out, first name only
+    pub fn announcement(notice: &OrderReadyNotice) -> String {
+        let code: Vec<String> = notice.order_id.simple().to_string()[..4]
+            .to_uppercase()
+            .chars()
+            .map(String::from)
+            .collect();
+        let first_name = notice.recipient.name.split_whitespace().next().unwrap_or_default();
+        match first_name {
+            "" => format!("Order {} is ready", code.join(" ")),
+            name => format!("Order {} for {} is ready", code.join(" "), name),
+        }
+    }
+
+    fn speak(&self, text: &str) -> Result<(), NotificationError> {
+        let args = self.args.iter().map(|arg| arg.replace(TEXT_PLACEHOLDER, text));
+        let mut child = Command::new(&self.program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                NotificationError::SendFailed(format!("Cannot run '{}': {}", self.program, e))
+            })?;
+
+        let started = Instant::now();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if started.elapsed() >= self.timeout => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(NotificationError::SendFailed(format!(
+                        "'{}' did not finish within {:?}",
+                        self.program, self.timeout
+                    )));
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(20)),
+                Err(e) => return Err(NotificationError::SendFailed(e.to_string())),
+            }
+        };
+
+        if status.success() {
+            return Ok(());
+        }
+        let mut stderr = String::new();
+        if let Some(mut pipe) = child.stderr.take() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        Err(NotificationError::SendFailed(format!(
+            "'{}' failed ({}): {}",
+            self.program,
+            status,
+            stderr.lines().next().unwrap_or_default()
+        )))
+    }
+}
+
+impl Notifier for AnnouncerNotifier {
+    fn notify_order_placed(&self, _notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        Ok(())
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        self.speak(&Self::announcement(notice))
+    }
+
+    fn notify_order_cancelled(
+        &self,
+        _notice: &OrderCancelledNotice,
+    ) -> Result<(), NotificationError> {
+        Ok(())
+    }
+
+    fn notify_order_no_show(&self, _notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::domain::NoticeRecipient;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn ready(name: &str) -> OrderReadyNotice {
+        OrderReadyNotice {
+            order_id: Uuid::parse_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+            recipient: NoticeRecipient {
+                name: name.to_string(),
+                email: "ada@example.com".to_string(),
+            },
+            ready_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_speaks_ready_orders_and_reports_failures() {
+        let spoken = std::env::temp_dir().join(format!("spoken-{}.txt", Uuid::new_v4()));
+        let script = format!("printf '%s' \"$1\" > '{}'", spoken.display());
+        let speaker = AnnouncerNotifier::new("sh").with_args(&["-c", &script, "tts"]);
+
+        assert_eq!(
+            AnnouncerNotifier::announcement(&ready("Ada Lovelace")),
+            "Order 3 F A 8 for Ada is ready"
+        );
+        // A name that would be a command inside a shell string stays text
+        speaker.notify_order_ready(&ready("$(false)")).unwrap();
+        let text = std::fs::read_to_string(&spoken).unwrap();
+        std::fs::remove_file(&spoken).unwrap();
+        assert_eq!(text, "Order 3 F A 8 for $(false) is ready");
+
+        let missing = AnnouncerNotifier::new("no-such-tts-program");
+        assert!(missing.notify_order_ready(&ready("Ada")).is_err());
+        let failing = AnnouncerNotifier::new("sh").with_args(&["-c", "echo no voice >&2; exit 3"]);
+        let error = failing.notify_order_ready(&ready("Ada")).unwrap_err().to_string();
+        assert!(error.contains("no voice"), "{}", error);
+        let hanging = AnnouncerNotifier::new("sh")
+            .with_args(&["-c", "sleep 5"])
+            .with_timeout(Duration::from_millis(100));
+        let started = Instant::now();
+        assert!(hanging.notify_order_ready(&ready("Ada")).is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,279 @@
+// SOLID: BinaryOrderRepository - compact binary file storage adapter
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// A third file backend, added without touching OrderRepository, OrderService
+// or JsonOrderRepository.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same contract and same behavior as the JSON repository; only the bytes on
+// disk differ. Pretty-printed JSON is the format to read by hand, this one is
+// several times smaller and faster to load.
+//
+// FILE FORMAT (versioned framing):
+//
+//   bytes 0..4   magic "CSOB" (Coffee Shop Order Binary)
+//   bytes 4..6   format version, u16 little-endian
+//   bytes 6..    bincode encoding of Vec<Order>
+//
+// bincode has no field names: an Order written with one layout cannot be read
+// with another. So the version is bumped whenever Order changes shape, and a
+// file with any other version is refused with a clear error instead of being
+// misread. Convert it through JSON with the migration tool
+// (`coffee-shop-solid migrate --from old.bin --to orders.json`).
+//
+// The whole file may additionally be gzip/zstd compressed (see
+// adapters::compression); the framing above is what is inside.
+
+use crate::adapters::compression::Compression;
+use crate::adapters::json_storage::refuse_card_numbers;
+use crate::adapters::memory_storage::first_conflict;
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAGIC: &[u8; 4] = b"CSOB";
+
+/// Layout of Order this code reads and writes
+pub const BINARY_FORMAT_VERSION: u16 = 12;
+
+/// Binary file-based order repository
+///
+/// USE CASE:
+/// Perfect for:
+/// - Large order histories (smaller files, faster start-up than JSON)
+/// - Archives nobody needs to read by hand
+pub struct BinaryOrderRepository {
+    file_path: PathBuf,
+    orders: HashMap<Uuid, Arc<Order>>,
+    compression: Compression,
+}
+
+impl BinaryOrderRepository {
+    /// Open the file at `file_path` (starts empty if it doesn't exist)
+    ///
+    /// A compressed file is read as such and stays compressed.
+    pub fn new(file_path: PathBuf) -> Result<Self, RepositoryError> {
+        let (orders, compression) = if file_path.exists() {
+            let bytes = fs::read(&file_path).map_err(|e| {
+                RepositoryError::LoadFailed(format!("Failed to read file: {}", e))
+            })?;
+            let compression = Compression::detect(&bytes);
+            let orders = decode(&Compression::decompress(bytes)?)?
+                .into_iter()
+                .map(|order| (order.id, Arc::new(order)))
+                .collect();
+            (orders, compression)
+        } else {
+            (HashMap::new(), Compression::None)
+        };
+
+        Ok(Self { file_path, orders, compression })
+    }
+
+    /// Compress the file from the next write on
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    fn save_to_file(&self) -> Result<(), RepositoryError> {
+        let orders: Vec<&Order> = self.orders.values().map(|order| order.as_ref()).collect();
+        let bytes = self.compression.compress(encode(&orders)?)?;
+        fs::write(&self.file_path, bytes).map_err(|e| {
+            RepositoryError::SaveFailed(format!("Failed to write file: {}", e))
+        })
+    }
+}
+
+/// Magic, version, then the orders
+fn encode(orders: &[&Order]) -> Result<Vec<u8>, RepositoryError> {
+    let payload = bincode::serialize(orders).map_err(|e| {
+        RepositoryError::SaveFailed(format!("Failed to serialize orders: {}", e))
+    })?;
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 2 + payload.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&BINARY_FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+fn decode(bytes: &[u8]) -> Result<Vec<Order>, RepositoryError> {
+    if bytes.len() < MAGIC.len() + 2 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(RepositoryError::LoadFailed(
+            "Not a binary order file (bad magic)".to_string(),
+        ));
+    }
+
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != BINARY_FORMAT_VERSION {
+        return Err(RepositoryError::LoadFailed(format!(
+            "Binary order file has format version {}, this build reads version {}",
+            version, BINARY_FORMAT_VERSION
+        )));
+    }
+
+    bincode::deserialize(&bytes[6..])
+        .map_err(|e| RepositoryError::LoadFailed(format!("Failed to decode orders: {}", e)))
+}
+
+impl OrderRepository for BinaryOrderRepository {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        if self.orders.contains_key(&order.id) {
+            return Err(RepositoryError::AlreadyExists(format!(
+                "Order {} already exists",
+                order.id
+            )));
+        }
+        refuse_card_numbers(order)?;
+
+        self.orders.insert(order.id, Arc::new(order.clone()));
+        if let Err(e) = self.save_to_file() {
+            self.orders.remove(&order.id);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        Ok(self.orders.get(&id).map(|order| Order::clone(order)))
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        Ok(self
+            .find_by_customer_email_shared(email)?
+            .iter()
+            .map(|order| Order::clone(order))
+            .collect())
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        Ok(self.orders.values().map(|order| Order::clone(order)).collect())
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        if !self.orders.contains_key(&order.id) {
+            return Err(RepositoryError::NotFound(format!(
+                "Order {} not found",
+                order.id
+            )));
+        }
+        refuse_card_numbers(order)?;
+
+        let previous = self.orders.insert(order.id, Arc::new(order.clone()));
+        if let Err(e) = self.save_to_file() {
+            if let Some(previous) = previous {
+                self.orders.insert(order.id, previous);
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        let Some(previous) = self.orders.remove(&id) else {
+            return Ok(false);
+        };
+
+        if let Err(e) = self.save_to_file() {
+            self.orders.insert(id, previous);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        if let Some(id) = first_conflict(orders, |id| self.orders.contains_key(&id)) {
+            return Err(RepositoryError::AlreadyExists(format!("Order {} already exists", id)));
+        }
+        orders.iter().try_for_each(refuse_card_numbers)?;
+
+        for order in orders {
+            self.orders.insert(order.id, Arc::new(order.clone()));
+        }
+
+        // One file write for the whole batch; undo the inserts if it fails
+        if let Err(e) = self.save_to_file() {
+            for order in orders {
+                self.orders.remove(&order.id);
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self.orders.values().cloned().collect())
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self
+            .orders
+            .values()
+            .filter(|order| order.customer.email == email)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Every write rewrites the whole file, so a batch costs the same as one order
+impl RepositoryCapabilities for BinaryOrderRepository {
+    fn supports_transactions(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::JsonOrderRepository;
+    use crate::seed::{SeedConfig, SeedGenerator};
+
+    fn temp_path(extension: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("orders-{}.{}", Uuid::new_v4(), extension))
+    }
+
+    #[test]
+    fn test_round_trip_is_smaller_than_json() {
+        let (bin, json) = (temp_path("bin"), temp_path("json"));
+        let mut config = SeedConfig::new(3);
+        config.orders_per_day = 40;
+        let orders = SeedGenerator::new(config).generate();
+
+        BinaryOrderRepository::new(bin.clone()).unwrap().save_batch(&orders).unwrap();
+        JsonOrderRepository::new(json.clone()).unwrap().save_batch(&orders).unwrap();
+
+        let reloaded = BinaryOrderRepository::new(bin.clone()).unwrap();
+        let original = &orders[0];
+        let copy = reloaded.find_by_id(original.id).unwrap().unwrap();
+        assert_eq!(reloaded.list_all().unwrap().len(), orders.len());
+        assert_eq!(copy.created_at, original.created_at);
+        assert_eq!(copy.status_history.len(), original.status_history.len());
+        assert!(fs::metadata(&bin).unwrap().len() < fs::metadata(&json).unwrap().len() / 2);
+
+        let _ = fs::remove_file(bin);
+        let _ = fs::remove_file(json);
+    }
+
+    #[test]
+    fn test_refuses_other_versions_and_foreign_files() {
+        let path = temp_path("bin");
+        let mut bytes = encode(&[]).unwrap();
+        bytes[4] = 99;
+        fs::write(&path, &bytes).unwrap();
+
+        let error = BinaryOrderRepository::new(path.clone()).err().unwrap();
+        assert!(error.to_string().contains("format version 99"));
+
+        fs::write(&path, b"[]").unwrap();
+        assert!(BinaryOrderRepository::new(path.clone()).is_err());
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,143 @@
+// SOLID: Campaign outboxes (in memory, JSON file)
+//
+// Both implement CampaignOutbox with the same contract (LSP). Only the JSON
+// one survives a crash, which is the point of an outbox: use the memory one
+// in tests and demos.
+
+use crate::domain::{DeliveryStatus, OutboxEntry};
+use crate::ports::{CampaignOutbox, OutboxError};
+use std::fs;
+use std::path::PathBuf;
+
+/// Entries kept in a Vec (tests, demos)
+#[derive(Debug, Default)]
+pub struct MemoryCampaignOutbox {
+    entries: Vec<OutboxEntry>,
+}
+
+impl MemoryCampaignOutbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CampaignOutbox for MemoryCampaignOutbox {
+    fn enqueue(&mut self, entry: OutboxEntry) -> Result<bool, OutboxError> {
+        if self.entries.iter().any(|e| e.is_for(&entry.campaign_id, &entry.recipient.email)) {
+            return Ok(false);
+        }
+        self.entries.push(entry);
+        Ok(true)
+    }
+
+    fn entries(&self, campaign_id: &str) -> Result<Vec<OutboxEntry>, OutboxError> {
+        Ok(self.entries.iter().filter(|e| e.campaign_id == campaign_id).cloned().collect())
+    }
+
+    fn mark(
+        &mut self,
+        campaign_id: &str,
+        email: &str,
+        status: DeliveryStatus,
+    ) -> Result<(), OutboxError> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.is_for(campaign_id, email))
+            .ok_or_else(|| OutboxError::UnknownRecipient(email.to_string()))?;
+        entry.status = status;
+        entry.attempts += 1;
+        Ok(())
+    }
+}
+
+/// Entries persisted to a JSON file (rewritten on every change)
+pub struct JsonCampaignOutbox {
+    file_path: PathBuf,
+    inner: MemoryCampaignOutbox,
+}
+
+impl JsonCampaignOutbox {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, OutboxError> {
+        let entries = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| OutboxError::StorageFailed(format!("Failed to read file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| OutboxError::StorageFailed(format!("Failed to parse JSON: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryCampaignOutbox { entries },
+        })
+    }
+
+    /// Write to a temporary file, then rename it over the old one: a crash
+    /// mid-write leaves the previous outbox, never half of one
+    fn write(&self) -> Result<(), OutboxError> {
+        let json = serde_json::to_string_pretty(&self.inner.entries)
+            .map_err(|e| OutboxError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, &self.file_path))
+            .map_err(|e| OutboxError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl CampaignOutbox for JsonCampaignOutbox {
+    fn enqueue(&mut self, entry: OutboxEntry) -> Result<bool, OutboxError> {
+        let added = self.inner.enqueue(entry)?;
+        if added {
+            self.write()?;
+        }
+        Ok(added)
+    }
+
+    fn entries(&self, campaign_id: &str) -> Result<Vec<OutboxEntry>, OutboxError> {
+        self.inner.entries(campaign_id)
+    }
+
+    fn mark(
+        &mut self,
+        campaign_id: &str,
+        email: &str,
+        status: DeliveryStatus,
+    ) -> Result<(), OutboxError> {
+        self.inner.mark(campaign_id, email, status)?;
+        self.write()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Customer;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_json_outbox_persists_statuses() {
+        let path = std::env::temp_dir().join(format!("outbox-{}.json", Uuid::new_v4()));
+        let mut outbox = JsonCampaignOutbox::new(path.clone()).unwrap();
+        let ada = Customer::named("Ada");
+
+        assert!(outbox.enqueue(OutboxEntry::new("autumn", ada.clone())).unwrap());
+        assert!(!outbox.enqueue(OutboxEntry::new("autumn", ada.clone())).unwrap());
+        assert!(outbox.enqueue(OutboxEntry::new("winter", ada)).unwrap());
+        let sent = DeliveryStatus::Sent { at: Utc::now() };
+        outbox.mark("autumn", "ADA@example.com", sent.clone()).unwrap();
+        assert!(matches!(
+            outbox.mark("autumn", "bob@example.com", sent.clone()),
+            Err(OutboxError::UnknownRecipient(_))
+        ));
+
+        let reloaded = JsonCampaignOutbox::new(path.clone()).unwrap();
+        let autumn = reloaded.entries("autumn").unwrap();
+        assert_eq!((autumn.len(), &autumn[0].status, autumn[0].attempts), (1, &sent, 1));
+        assert_eq!(reloaded.entries("winter").unwrap()[0].status, DeliveryStatus::Pending);
+        fs::remove_file(path).unwrap();
+    }
+}
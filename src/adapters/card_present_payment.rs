@@ -0,0 +1,156 @@
+// SOLID: CardPresentPayment - card terminal attached to a kiosk or register
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Another PaymentProcessor, added without touching OrderService,
+// CashPayment or CreditCardPayment.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// The customer taps or inserts a card on the terminal, so there are no card
+// details to send over the network, but the contract is the same: Ok(payment_id)
+// means the money was taken, and the same idempotency key never charges twice.
+//
+// Payment IDs carry the terminal ID, so a disputed charge can be traced back
+// to the machine that took it. The terminal reports the card read as a
+// MaskedPan; the number itself never leaves it.
+
+use super::randomness::SystemRandomness;
+use crate::domain::{Currency, MaskedPan, Money};
+use crate::ports::{PaymentError, PaymentProcessor, PaymentRequest, Randomness};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Above this many cents, contactless is refused and the card must be inserted (chip and PIN)
+const CONTACTLESS_LIMIT: i64 = 10_000;
+
+/// Card terminal payment processor
+pub struct CardPresentPayment {
+    terminal_id: String,
+    // Idempotency key -> payment ID of charges already made
+    charges: Mutex<HashMap<String, String>>,
+    // Where payment IDs come from (a real terminal gets them from the acquirer)
+    randomness: Box<dyn Randomness>,
+    // The card the simulated terminal reads
+    card: Option<MaskedPan>,
+}
+
+impl CardPresentPayment {
+    pub fn new(terminal_id: &str) -> Self {
+        Self {
+            terminal_id: terminal_id.to_string(),
+            charges: Mutex::new(HashMap::new()),
+            randomness: Box::new(SystemRandomness),
+            card: None,
+        }
+    }
+
+    /// Same terminal, payment IDs drawn from `randomness`
+    pub fn with_randomness(mut self, randomness: impl Randomness + 'static) -> Self {
+        self.randomness = Box::new(randomness);
+        self
+    }
+
+    /// The card presented to the simulated terminal, shown on approvals
+    pub fn with_card(mut self, card: MaskedPan) -> Self {
+        self.card = Some(card);
+        self
+    }
+
+    pub fn terminal_id(&self) -> &str {
+        &self.terminal_id
+    }
+}
+
+impl PaymentProcessor for CardPresentPayment {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        if amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Amount must be positive".to_string(),
+            ));
+        }
+
+        let entry = if amount.cents > CONTACTLESS_LIMIT { "Insert" } else { "Tap or insert" };
+        println!("📟 {} card on terminal {} ({})", entry, self.terminal_id, amount);
+
+        // In a real system the terminal SDK waits for the card, reads the
+        // chip, asks for a PIN if needed and returns the acquirer's reference
+        let payment_id = format!("CP-{}-{}", self.terminal_id, self.randomness.uuid());
+        match &self.card {
+            Some(card) => println!("✓ Card payment approved: {} ({})", payment_id, card),
+            None => println!("✓ Card payment approved: {}", payment_id),
+        }
+
+        Ok(payment_id)
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        if request.amount.currency != Currency::USD {
+            return Err(PaymentError::ProcessingFailed(format!(
+                "Unsupported currency: {}",
+                request.amount.currency
+            )));
+        }
+
+        let mut charges = self.charges.lock().unwrap();
+        if let Some(payment_id) = charges.get(&request.idempotency_key) {
+            return Ok(payment_id.clone());
+        }
+
+        let payment_id = self.process_payment(request.amount)?;
+        charges.insert(request.idempotency_key.clone(), payment_id.clone());
+
+        Ok(payment_id)
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        if amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Refund amount must be positive".to_string(),
+            ));
+        }
+
+        // Referenced refund: the card doesn't need to be presented again
+        println!(
+            "📟 Refunding {} for {} via terminal {}",
+            amount, payment_id, self.terminal_id
+        );
+        Ok(format!("CP-REFUND-{}-{}", self.terminal_id, self.randomness.uuid()))
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "Card (terminal)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn request(key: &str) -> PaymentRequest {
+        PaymentRequest {
+            amount: Money::usd(4.20),
+            order_id: Uuid::new_v4(),
+            customer_ref: "guest".to_string(),
+            customer_email: "guest@kiosk.invalid".to_string(),
+            idempotency_key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_payment_id_names_the_terminal() {
+        let terminal = CardPresentPayment::new("KIOSK-1");
+
+        let first = terminal.process_request(&request("order-1")).unwrap();
+        let retry = terminal.process_request(&request("order-1")).unwrap();
+
+        assert!(first.starts_with("CP-KIOSK-1-"));
+        assert_eq!(first, retry);
+    }
+
+    #[test]
+    fn test_rejects_zero_amount() {
+        let terminal = CardPresentPayment::new("KIOSK-1");
+
+        assert!(terminal.process_payment(Money::cents(0)).is_err());
+    }
+}
@@ -11,9 +11,12 @@
 //    OrderService shouldn't care if it's Cash, CreditCard, or Bitcoin
 // 
 // 3. DEPENDENCY INVERSION PRINCIPLE (DIP):
-//    This adapter depends on the PaymentProcessor trait
+//    This adapter depends on the PaymentProcessor trait. SeededCashPayment,
+//    the same drawer for reproducible runs, also takes its payment IDs from
+//    the Randomness port
 
-use crate::ports::{PaymentError, PaymentProcessor};
+use crate::domain::Money;
+use crate::ports::{PaymentError, PaymentProcessor, Randomness};
 use uuid::Uuid;
 
 /// Cash payment processor
@@ -39,21 +42,43 @@ use uuid::Uuid;
 pub struct CashPayment;
 
 impl PaymentProcessor for CashPayment {
-    fn process_payment(&self, amount: f64) -> Result<String, PaymentError> {
-        // Simulate cash payment processing
-        println!("💵 Processing cash payment of ${:.2}", amount);
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        take_cash(amount, Uuid::new_v4())
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        give_back_cash(payment_id, amount, Uuid::new_v4())
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "Cash"
+    }
+}
 
-        // In a real system, this might:
-        // - Record in a payment ledger
-        // - Generate a receipt number
-        // - Update cash register balance
-        // For this demo, we just generate a payment ID
+/// Cash payments whose IDs come from a Randomness
+///
+/// The same drawer as CashPayment, for examples and transcripts: with a
+/// SeededRandomness, every run prints the same payment and refund IDs.
+/// CashPayment itself stays a unit struct, usable as the value `CashPayment`.
+pub struct SeededCashPayment {
+    randomness: Box<dyn Randomness>,
+}
 
-        // LSP: Return Ok with a unique payment ID (the contract)
-        let payment_id = format!("CASH-{}", Uuid::new_v4());
-        println!("✓ Cash payment successful: {}", payment_id);
+impl SeededCashPayment {
+    pub fn new(randomness: impl Randomness + 'static) -> Self {
+        Self {
+            randomness: Box::new(randomness),
+        }
+    }
+}
 
-        Ok(payment_id)
+impl PaymentProcessor for SeededCashPayment {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        take_cash(amount, self.randomness.uuid())
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        give_back_cash(payment_id, amount, self.randomness.uuid())
     }
 
     fn payment_method_name(&self) -> &str {
@@ -61,6 +86,29 @@ impl PaymentProcessor for CashPayment {
     }
 }
 
+fn take_cash(amount: Money, id: Uuid) -> Result<String, PaymentError> {
+    // Simulate cash payment processing
+    println!("💵 Processing cash payment of {}", amount);
+
+    // In a real system, this might:
+    // - Record in a payment ledger
+    // - Generate a receipt number
+    // - Update cash register balance
+    // For this demo, we just generate a payment ID
+
+    // LSP: Return Ok with a unique payment ID (the contract)
+    let payment_id = format!("CASH-{}", id);
+    println!("✓ Cash payment successful: {}", payment_id);
+
+    Ok(payment_id)
+}
+
+fn give_back_cash(payment_id: &str, amount: Money, id: Uuid) -> Result<String, PaymentError> {
+    // Cash back from the drawer
+    println!("💵 Refunding {} in cash for {}", amount, payment_id);
+    Ok(format!("CASH-REFUND-{}", id))
+}
+
 // ============================================================================
 // OCP IN ACTION: Adding New Payment Methods
 // 
@@ -71,8 +119,8 @@ impl PaymentProcessor for CashPayment {
 // }
 // 
 // impl PaymentProcessor for CreditCardPayment {
-//     fn process_payment(&self, amount: f64) -> Result<String, PaymentError> {
-//         println!("💳 Processing credit card payment of ${:.2}", amount);
+//     fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+//         println!("💳 Processing credit card payment of {}", amount);
 //         
 //         // Call external payment gateway
 //         let result = self.card_processor.charge(amount)?;
@@ -99,14 +147,14 @@ impl PaymentProcessor for CashPayment {
 // 
 // fn charge_customer<P: PaymentProcessor>(
 //     processor: &P,
-//     amount: f64,
+//     amount: Money,
 // ) -> Result<String, PaymentError> {
 //     processor.process_payment(amount)
 // }
 // 
-// charge_customer(&CashPayment, 10.00);
-// charge_customer(&CreditCardPayment::new(...), 10.00);
-// charge_customer(&BitcoinPayment::new(...), 10.00);
+// charge_customer(&CashPayment, Money::usd(10.00));
+// charge_customer(&CreditCardPayment::new(...), Money::usd(10.00));
+// charge_customer(&BitcoinPayment::new(...), Money::usd(10.00));
 // 
 // All should work identically from the caller's perspective.
 // Different implementations, same interface, consistent behavior. That's LSP.
@@ -115,11 +163,12 @@ impl PaymentProcessor for CashPayment {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::SeededRandomness;
 
     #[test]
     fn test_cash_payment_success() {
         let payment = CashPayment;
-        let result = payment.process_payment(10.50);
+        let result = payment.process_payment(Money::usd(10.50));
 
         assert!(result.is_ok());
         let payment_id = result.unwrap();
@@ -131,4 +180,14 @@ mod tests {
         let payment = CashPayment;
         assert_eq!(payment.payment_method_name(), "Cash");
     }
+
+    #[test]
+    fn test_seeded_payment_ids_repeat() {
+        let payment_id = || {
+            SeededCashPayment::new(SeededRandomness::new(42))
+                .process_payment(Money::usd(3.50))
+                .unwrap()
+        };
+        assert_eq!(payment_id(), payment_id());
+    }
 }
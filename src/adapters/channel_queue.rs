@@ -0,0 +1,170 @@
+// SOLID: In-process order queue adapter (std::sync::mpsc)
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Honors the same contract a Redis or Kafka adapter would:
+// each order ID goes to exactly one consumer, and Ok(None) means "closed and drained".
+//
+// The consumer half is cloneable so several worker threads can share one queue.
+//
+// BACKPRESSURE:
+// `channel_order_queue()` never fills up: if workers fall behind, the backlog
+// grows until memory runs out. `bounded_order_queue()` holds at most
+// `capacity` orders, and its FullQueuePolicy decides what intake does when
+// that's reached: wait for a worker (Block) or refuse the order right away
+// with QueueError::Full (Shed), which intake turns into a "try again later".
+
+use crate::ports::{OrderQueueConsumer, OrderQueueProducer, QueueError};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Create a connected producer/consumer pair
+///
+/// The queue closes when every producer has been dropped.
+pub fn channel_order_queue() -> (ChannelQueueProducer, ChannelQueueConsumer) {
+    let (sender, receiver) = mpsc::channel();
+
+    (
+        ChannelQueueProducer {
+            sender: Sending::Unbounded(sender),
+        },
+        ChannelQueueConsumer {
+            receiver: Arc::new(Mutex::new(receiver)),
+        },
+    )
+}
+
+/// What `enqueue` does when a bounded queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullQueuePolicy {
+    /// Wait until a worker takes an order (intake slows down to the workers' pace)
+    Block,
+    /// Fail with QueueError::Full at once (intake stays fast, the customer retries)
+    Shed,
+}
+
+/// Create a connected pair holding at most `capacity` orders (at least 1)
+pub fn bounded_order_queue(
+    capacity: usize,
+    policy: FullQueuePolicy,
+) -> (ChannelQueueProducer, ChannelQueueConsumer) {
+    let capacity = capacity.max(1);
+    let (sender, receiver) = mpsc::sync_channel(capacity);
+
+    (
+        ChannelQueueProducer {
+            sender: Sending::Bounded {
+                sender,
+                capacity,
+                policy,
+            },
+        },
+        ChannelQueueConsumer {
+            receiver: Arc::new(Mutex::new(receiver)),
+        },
+    )
+}
+
+#[derive(Clone)]
+enum Sending {
+    Unbounded(Sender<Uuid>),
+    Bounded {
+        sender: SyncSender<Uuid>,
+        capacity: usize,
+        policy: FullQueuePolicy,
+    },
+}
+
+/// Intake half of an in-process queue
+#[derive(Clone)]
+pub struct ChannelQueueProducer {
+    sender: Sending,
+}
+
+impl OrderQueueProducer for ChannelQueueProducer {
+    fn enqueue(&self, order_id: Uuid) -> Result<(), QueueError> {
+        match &self.sender {
+            Sending::Unbounded(sender) => sender.send(order_id).map_err(|_| QueueError::Closed),
+            Sending::Bounded {
+                sender,
+                policy: FullQueuePolicy::Block,
+                ..
+            } => sender.send(order_id).map_err(|_| QueueError::Closed),
+            Sending::Bounded {
+                sender,
+                capacity,
+                policy: FullQueuePolicy::Shed,
+            } => sender.try_send(order_id).map_err(|e| match e {
+                TrySendError::Full(_) => {
+                    QueueError::Full(format!("{} order(s) already waiting", capacity))
+                }
+                TrySendError::Disconnected(_) => QueueError::Closed,
+            }),
+        }
+    }
+}
+
+/// Worker half of an in-process queue
+#[derive(Clone)]
+pub struct ChannelQueueConsumer {
+    receiver: Arc<Mutex<Receiver<Uuid>>>,
+}
+
+impl OrderQueueConsumer for ChannelQueueConsumer {
+    fn dequeue(&self) -> Result<Option<Uuid>, QueueError> {
+        let receiver = self
+            .receiver
+            .lock()
+            .map_err(|e| QueueError::BrokerError(e.to_string()))?;
+
+        // recv() only fails once all producers are gone and the queue is empty
+        Ok(receiver.recv().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_then_closed() {
+        let (producer, consumer) = channel_order_queue();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        producer.enqueue(first).unwrap();
+        producer.enqueue(second).unwrap();
+        drop(producer);
+
+        assert_eq!(consumer.dequeue().unwrap(), Some(first));
+        assert_eq!(consumer.dequeue().unwrap(), Some(second));
+        assert_eq!(consumer.dequeue().unwrap(), None);
+    }
+
+    #[test]
+    fn test_bounded_queue_sheds_or_blocks_when_full() {
+        let (producer, consumer) = bounded_order_queue(1, FullQueuePolicy::Shed);
+        producer.enqueue(Uuid::new_v4()).unwrap();
+
+        assert!(matches!(producer.enqueue(Uuid::new_v4()), Err(QueueError::Full(_))));
+        assert!(consumer.dequeue().unwrap().is_some());
+        producer.enqueue(Uuid::new_v4()).unwrap();
+
+        // A blocked producer goes through as soon as a worker makes room
+        let (producer, consumer) = bounded_order_queue(1, FullQueuePolicy::Block);
+        producer.enqueue(Uuid::new_v4()).unwrap();
+        let blocked = std::thread::spawn(move || producer.enqueue(Uuid::new_v4()));
+        assert!(consumer.dequeue().unwrap().is_some());
+        assert!(blocked.join().unwrap().is_ok());
+        assert!(consumer.dequeue().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_enqueue_without_consumer_fails() {
+        let (producer, consumer) = channel_order_queue();
+        drop(consumer);
+
+        let result = producer.enqueue(Uuid::new_v4());
+        assert!(matches!(result, Err(QueueError::Closed)));
+    }
+}
@@ -0,0 +1,453 @@
+// SOLID: ChatNotifier - post order notifications to a Slack or Discord channel
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    It's a Notifier like ConsoleNotifier. OrderService can't tell the difference.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    The HTTP POST goes through the WebhookTransport port (same one webhooks use),
+//    so tests use RecordingWebhookTransport and production uses HttpWebhookTransport.
+//
+// 3. OPEN-CLOSED PRINCIPLE (OCP):
+//    Slack and Discord differ only in the JSON they expect (blocks vs embeds).
+//    Adding Teams or Mattermost is one more ChatPlatform arm.
+//
+// Managers rarely want every event: `with_events` picks which ones reach the channel.
+// `with_events(&[ChatEvent::OpsAlert])` makes it the ops channel AlertEngine reports to.
+
+use crate::domain::{
+    AlertState, NoticeItem, OpsAlertNotice, OrderCancelledNotice, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, RefundPendingNotice, SlaBreachNotice,
+};
+use crate::ports::{NotificationError, Notifier, WebhookError, WebhookTransport};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Which chat service the incoming-webhook URL belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatPlatform {
+    Slack,
+    Discord,
+}
+
+/// Order events a channel can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatEvent {
+    Placed,
+    Ready,
+    Cancelled,
+    NoShow,
+    /// An order stuck past its SLA (see SlaWatchdogJob)
+    SlaBreach,
+    /// A refund waiting for a manager's approval (see RefundService)
+    RefundPending,
+    /// An alert rule fired or resolved (see AlertEngine)
+    OpsAlert,
+}
+
+impl ChatEvent {
+    pub const ALL: [ChatEvent; 7] = [
+        ChatEvent::Placed,
+        ChatEvent::Ready,
+        ChatEvent::Cancelled,
+        ChatEvent::NoShow,
+        ChatEvent::SlaBreach,
+        ChatEvent::RefundPending,
+        ChatEvent::OpsAlert,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            ChatEvent::Placed => "🎉 New order",
+            ChatEvent::Ready => "☕ Order ready",
+            ChatEvent::Cancelled => "❌ Order cancelled",
+            ChatEvent::NoShow => "⌛ Order not picked up",
+            ChatEvent::SlaBreach => "🚨 Order past SLA",
+            ChatEvent::RefundPending => "💸 Refund needs approval",
+            ChatEvent::OpsAlert => "🔥 Alert",
+        }
+    }
+
+    /// Discord embed colour
+    fn color(&self) -> u32 {
+        match self {
+            ChatEvent::Placed => 0x2e_cc_71,
+            ChatEvent::Ready => 0xa0_52_2d,
+            ChatEvent::Cancelled => 0xe7_4c_3c,
+            ChatEvent::NoShow => 0x95_a5_a6,
+            ChatEvent::SlaBreach => 0xf3_9c_12,
+            ChatEvent::RefundPending => 0x9b_59_b6,
+            ChatEvent::OpsAlert => 0xc0_39_2b,
+        }
+    }
+}
+
+/// Posts rich order messages to a team channel
+pub struct ChatNotifier<T: WebhookTransport> {
+    platform: ChatPlatform,
+    webhook_url: String,
+    events: Vec<ChatEvent>,
+    transport: T,
+}
+
+impl<T: WebhookTransport> ChatNotifier<T> {
+    /// Post every event to the channel behind `webhook_url`
+    pub fn new(platform: ChatPlatform, webhook_url: String, transport: T) -> Self {
+        Self {
+            platform,
+            webhook_url,
+            events: ChatEvent::ALL.to_vec(),
+            transport,
+        }
+    }
+
+    /// Only post these events (others are silently skipped)
+    pub fn with_events(mut self, events: &[ChatEvent]) -> Self {
+        self.events = events.to_vec();
+        self
+    }
+
+    fn post(&self, event: ChatEvent, content: ChatContent) -> Result<(), NotificationError> {
+        if !self.events.contains(&event) {
+            return Ok(());
+        }
+
+        let payload = match self.platform {
+            ChatPlatform::Slack => slack_message(event, &content),
+            ChatPlatform::Discord => discord_message(event, &content),
+        };
+        self.send(&payload)
+    }
+
+    fn send(&self, payload: &Value) -> Result<(), NotificationError> {
+        self.transport
+            .post(
+                &self.webhook_url,
+                &[("Content-Type", "application/json".to_string())],
+                &payload.to_string(),
+            )
+            .map(|_| ())
+            .map_err(|e| match e {
+                WebhookError::DeliveryFailed(msg) => NotificationError::NetworkError(msg),
+                WebhookError::InvalidUrl(msg) => NotificationError::InvalidRecipient(msg),
+                other => NotificationError::SendFailed(other.to_string()),
+            })
+    }
+}
+
+impl<T: WebhookTransport> Notifier for ChatNotifier<T> {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        let content = ChatContent {
+            order_id: notice.order_id,
+            customer: notice.recipient.name.clone(),
+            items: Some(item_list(&notice.items)),
+            total: Some(notice.total_price.to_major()),
+            waiting: None,
+            refund: None,
+            at: notice.placed_at,
+        };
+        self.post(ChatEvent::Placed, content)
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        let content = ChatContent {
+            order_id: notice.order_id,
+            customer: notice.recipient.name.clone(),
+            items: None,
+            total: None,
+            waiting: None,
+            refund: None,
+            at: notice.ready_at,
+        };
+        self.post(ChatEvent::Ready, content)
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        let content = ChatContent {
+            order_id: notice.order_id,
+            customer: notice.recipient.name.clone(),
+            items: None,
+            total: Some(notice.total_price.to_major()),
+            waiting: None,
+            refund: None,
+            at: notice.cancelled_at,
+        };
+        self.post(ChatEvent::Cancelled, content)
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        let content = ChatContent {
+            order_id: notice.order_id,
+            customer: notice.recipient.name.clone(),
+            items: None,
+            total: None,
+            waiting: None,
+            refund: None,
+            at: notice.flagged_at,
+        };
+        self.post(ChatEvent::NoShow, content)
+    }
+
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        let content = ChatContent {
+            order_id: notice.order_id,
+            customer: notice.recipient.name.clone(),
+            items: Some(item_list(&notice.items)),
+            total: None,
+            waiting: Some(format!("{} for {} min", notice.stuck_in, notice.waited_minutes)),
+            refund: None,
+            at: notice.flagged_at,
+        };
+        self.post(ChatEvent::SlaBreach, content)
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        let content = ChatContent {
+            order_id: notice.order_id,
+            customer: notice.recipient.name.clone(),
+            items: None,
+            total: None,
+            waiting: None,
+            refund: Some(format!(
+                "${:.2} asked by {}: {}",
+                notice.amount.to_major(),
+                notice.requested_by,
+                notice.reason
+            )),
+            at: notice.requested_at,
+        };
+        self.post(ChatEvent::RefundPending, content)
+    }
+
+    /// Not about an order: a title and the rule's summary
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        if !self.events.contains(&ChatEvent::OpsAlert) {
+            return Ok(());
+        }
+        self.send(&alert_message(self.platform, notice))
+    }
+}
+
+/// What a channel message shows, whatever the platform
+///
+/// The team channel gets the customer's name, never their email.
+struct ChatContent {
+    order_id: Uuid,
+    customer: String,
+    items: Option<String>,
+    total: Option<f64>,
+    /// "Preparing for 12 min"
+    waiting: Option<String>,
+    /// "$42.00 asked by Sam: wrong order"
+    refund: Option<String>,
+    at: DateTime<Utc>,
+}
+
+impl ChatContent {
+    /// "1a2b3c4d"
+    fn short_id(&self) -> String {
+        self.order_id.simple().to_string()[..8].to_string()
+    }
+
+    /// (label, value) pairs shown as fields
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("Order", format!("#{}", self.short_id())),
+            ("Customer", self.customer.clone()),
+        ];
+        if let Some(items) = &self.items {
+            fields.push(("Items", items.clone()));
+        }
+        if let Some(total) = self.total {
+            fields.push(("Total", format!("${:.2}", total)));
+        }
+        if let Some(waiting) = &self.waiting {
+            fields.push(("Waiting", waiting.clone()));
+        }
+        if let Some(refund) = &self.refund {
+            fields.push(("Refund", refund.clone()));
+        }
+        fields
+    }
+}
+
+/// "2 × Coffee (Medium), Green Tea (Large)"
+fn item_list(items: &[NoticeItem]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            if item.quantity > 1 {
+                format!("{} × {}", item.quantity, item.description)
+            } else {
+                item.description.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Slack Block Kit message (`text` is the fallback for notifications)
+fn slack_message(event: ChatEvent, content: &ChatContent) -> Value {
+    let summary = format!("{} #{} for {}", event.title(), content.short_id(), content.customer);
+    let fields: Vec<Value> = content
+        .fields()
+        .into_iter()
+        .map(|(label, value)| json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", label, value) }))
+        .collect();
+
+    json!({
+        "text": summary,
+        "blocks": [
+            { "type": "header", "text": { "type": "plain_text", "text": event.title() } },
+            { "type": "section", "fields": fields },
+            {
+                "type": "context",
+                "elements": [ { "type": "mrkdwn", "text": content.at.format("%Y-%m-%d %H:%M UTC").to_string() } ]
+            }
+        ]
+    })
+}
+
+/// Discord embed message
+fn discord_message(event: ChatEvent, content: &ChatContent) -> Value {
+    let fields: Vec<Value> = content
+        .fields()
+        .into_iter()
+        .skip(1) // the order number is already in the title
+        .map(|(label, value)| json!({ "name": label, "value": value, "inline": label != "Items" }))
+        .collect();
+
+    json!({
+        "embeds": [{
+            "title": format!("{} #{}", event.title(), content.short_id()),
+            "color": event.color(),
+            "fields": fields,
+            "timestamp": content.at.to_rfc3339()
+        }]
+    })
+}
+
+/// An ops alert, firing in red or resolved in green
+fn alert_message(platform: ChatPlatform, notice: &OpsAlertNotice) -> Value {
+    let (title, color) = match notice.state {
+        AlertState::Firing => (format!("{}: {}", ChatEvent::OpsAlert.title(), notice.rule), None),
+        AlertState::Resolved => (format!("✅ Resolved: {}", notice.rule), Some(0x2e_cc_71)),
+    };
+    let at = notice.at.format("%Y-%m-%d %H:%M UTC").to_string();
+    match platform {
+        ChatPlatform::Slack => json!({
+            "text": format!("{} ({})", title, notice.summary),
+            "blocks": [
+                { "type": "header", "text": { "type": "plain_text", "text": title } },
+                { "type": "section", "text": { "type": "mrkdwn", "text": notice.summary } },
+                { "type": "context", "elements": [ { "type": "mrkdwn", "text": at } ] }
+            ]
+        }),
+        ChatPlatform::Discord => json!({
+            "embeds": [{
+                "title": title,
+                "description": notice.summary,
+                "color": color.unwrap_or(ChatEvent::OpsAlert.color()),
+                "timestamp": notice.at.to_rfc3339()
+            }]
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::RecordingWebhookTransport;
+    use crate::domain::{Coffee, Customer, Order, OrderItem};
+
+    fn make_order() -> Order {
+        let items = vec![OrderItem { quantity: 2, ..OrderItem::of(&Coffee::medium()) }];
+        Order::new(Customer::named("Ada"), items)
+    }
+
+    fn posted_json(transport: &RecordingWebhookTransport) -> Value {
+        serde_json::from_str(&transport.requests()[0].body).unwrap()
+    }
+
+    #[test]
+    fn test_slack_blocks() {
+        let transport = RecordingWebhookTransport::new();
+        let notifier = ChatNotifier::new(
+            ChatPlatform::Slack,
+            "https://hooks.slack.com/services/T/B/X".to_string(),
+            transport.clone(),
+        );
+
+        notifier.notify_order_placed(&OrderPlacedNotice::from_order(&make_order())).unwrap();
+
+        let message = posted_json(&transport);
+        assert!(message["text"].as_str().unwrap().contains("Ada"));
+        assert_eq!(message["blocks"][0]["type"], "header");
+        assert!(message["blocks"][1]["fields"][2]["text"]
+            .as_str()
+            .unwrap()
+            .contains("2 × Coffee (Medium)"));
+    }
+
+    #[test]
+    fn test_discord_embed_and_event_selection() {
+        let transport = RecordingWebhookTransport::new();
+        let notifier = ChatNotifier::new(
+            ChatPlatform::Discord,
+            "https://discord.com/api/webhooks/1/abc".to_string(),
+            transport.clone(),
+        )
+        .with_events(&[ChatEvent::Cancelled]);
+
+        // Not selected: nothing posted
+        notifier.notify_order_placed(&OrderPlacedNotice::from_order(&make_order())).unwrap();
+        assert!(transport.requests().is_empty());
+
+        notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&make_order())).unwrap();
+        let message = posted_json(&transport);
+        assert_eq!(message["embeds"][0]["color"], 0xe74c3c);
+        assert_eq!(message["embeds"][0]["fields"][1]["value"], "$7.00");
+    }
+
+    #[test]
+    fn test_ops_alert_message() {
+        let transport = RecordingWebhookTransport::new();
+        let notifier = ChatNotifier::new(
+            ChatPlatform::Discord,
+            "https://discord.com/api/webhooks/1/abc".to_string(),
+            transport.clone(),
+        )
+        .with_events(&[ChatEvent::OpsAlert]);
+
+        notifier
+            .notify_ops_alert(&OpsAlertNotice {
+                alert_id: Uuid::new_v4(),
+                rule: "queue-depth".to_string(),
+                state: AlertState::Resolved,
+                summary: "9 orders queued <= 12".to_string(),
+                value: 9.0,
+                threshold: 12.0,
+                at: Utc::now(),
+            })
+            .unwrap();
+        let message = posted_json(&transport);
+        assert_eq!(message["embeds"][0]["title"], "✅ Resolved: queue-depth");
+        assert_eq!(message["embeds"][0]["description"], "9 orders queued <= 12");
+    }
+
+    #[test]
+    fn test_rejected_post_is_an_error() {
+        let notifier = ChatNotifier::new(
+            ChatPlatform::Slack,
+            "https://hooks.slack.com/services/T/B/X".to_string(),
+            RecordingWebhookTransport::answering(404),
+        );
+
+        assert!(matches!(
+            notifier.notify_order_ready(&OrderReadyNotice::from_order(&make_order())),
+            Err(NotificationError::SendFailed(_))
+        ));
+    }
+}
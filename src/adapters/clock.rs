@@ -0,0 +1,79 @@
+// SOLID: Clock adapters
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// SystemClock and FixedClock are interchangeable. A service that works
+// with one works with the other - only the source of "now" differs.
+
+use crate::ports::Clock;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Mutex;
+
+/// Real wall-clock time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when we tell it to
+///
+/// USE CASE:
+/// Perfect for:
+/// - Testing time-based rules (no sleeping in tests)
+/// - Demos ("let's pretend 20 minutes went by")
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// Create a clock frozen at the given time
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Move the clock to a specific time
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Move the clock forward (or backward, with a negative duration)
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock_advance() {
+        let start = Utc::now();
+        let clock = FixedClock::new(start);
+
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::minutes(20));
+        assert_eq!(clock.now(), start + Duration::minutes(20));
+    }
+
+    #[test]
+    fn test_fixed_clock_set() {
+        let clock = FixedClock::new(Utc::now());
+        let later = Utc::now() + Duration::days(1);
+
+        clock.set(later);
+        assert_eq!(clock.now(), later);
+    }
+}
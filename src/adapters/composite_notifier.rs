@@ -0,0 +1,212 @@
+// SOLID: CompositeNotifier - one Notifier that fans out to several
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. OPEN-CLOSED PRINCIPLE (OCP):
+//    Console AND file AND chat, without a line changed in OrderService or in
+//    any of the channels: the composite is just one more Notifier.
+//
+// 2. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    Same contract as the channels it holds. What a failing channel does to
+//    the others is the FailurePolicy:
+//    - BestEffort (default): every channel is tried anyway
+//    - FailFast: the channels after the failing one are skipped
+//    Either way the error is NotificationError::ChannelsFailed, naming each
+//    channel that failed and why.
+
+use crate::domain::{
+    FeedbackRequestNotice, OpsAlertNotice, OrderCancelledNotice, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, RefundPendingNotice, RewardGrantedNotice,
+    SlaBreachNotice,
+};
+use crate::ports::{NotificationError, Notifier};
+
+/// What a failing channel means for the channels after it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Try every channel, then report the ones that failed
+    #[default]
+    BestEffort,
+    /// Stop at the first channel that fails
+    FailFast,
+}
+
+/// Sends every notification to every channel, in the order they were added
+#[derive(Default)]
+pub struct CompositeNotifier {
+    // Channel name and notifier
+    notifiers: Vec<(String, Box<dyn Notifier>)>,
+    policy: FailurePolicy,
+}
+
+impl CompositeNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a channel, named by its position ("channel 1", "channel 2"...)
+    pub fn with(self, notifier: impl Notifier + 'static) -> Self {
+        let name = format!("channel {}", self.notifiers.len() + 1);
+        self.with_channel(&name, notifier)
+    }
+
+    /// Add a channel under a name, the one its failures are reported with
+    pub fn with_channel(mut self, name: &str, notifier: impl Notifier + 'static) -> Self {
+        self.notifiers.push((name.to_string(), Box::new(notifier)));
+        self
+    }
+
+    pub fn with_policy(mut self, policy: FailurePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.notifiers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notifiers.is_empty()
+    }
+
+    fn each(
+        &self,
+        send: impl Fn(&dyn Notifier) -> Result<(), NotificationError>,
+    ) -> Result<(), NotificationError> {
+        let mut failures = Vec::new();
+        for (name, notifier) in &self.notifiers {
+            if let Err(e) = send(notifier.as_ref()) {
+                failures.push((name.clone(), e));
+                if self.policy == FailurePolicy::FailFast {
+                    break;
+                }
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(NotificationError::ChannelsFailed(failures))
+        }
+    }
+}
+
+impl Notifier for CompositeNotifier {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_order_placed(notice))
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_order_ready(notice))
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_order_cancelled(notice))
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_order_no_show(notice))
+    }
+
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_sla_breach(notice))
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_refund_pending(notice))
+    }
+
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_ops_alert(notice))
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_feedback_requested(notice))
+    }
+
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        self.each(|notifier| notifier.notify_reward_granted(notice))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{read_notification_records, FileNotifier};
+    use crate::domain::{Customer, Money, Order, OrderItem};
+    use uuid::Uuid;
+
+    /// Fails every call
+    struct Broken;
+
+    impl Notifier for Broken {
+        fn notify_order_placed(&self, _: &OrderPlacedNotice) -> Result<(), NotificationError> {
+            Err(NotificationError::SendFailed("offline".to_string()))
+        }
+        fn notify_order_ready(&self, _: &OrderReadyNotice) -> Result<(), NotificationError> {
+            Ok(())
+        }
+        fn notify_order_cancelled(
+            &self,
+            _: &OrderCancelledNotice,
+        ) -> Result<(), NotificationError> {
+            Ok(())
+        }
+        fn notify_order_no_show(&self, _: &OrderNoShowNotice) -> Result<(), NotificationError> {
+            Ok(())
+        }
+    }
+
+    fn placed_notice() -> OrderPlacedNotice {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let order = Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: "Coffee".to_string(),
+                beverage_description: "Medium Coffee".to_string(),
+                price: Money::usd(3.50),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        OrderPlacedNotice::from_order(&order)
+    }
+
+    #[test]
+    fn test_failing_channel_does_not_stop_the_others() {
+        let log = std::env::temp_dir().join(format!("composite-{}.jsonl", Uuid::new_v4()));
+        let composite = CompositeNotifier::new()
+            .with_channel("sms", Broken)
+            .with(FileNotifier::new(log.clone()))
+            .with(Broken);
+
+        let result = composite.notify_order_placed(&placed_notice());
+
+        let Err(NotificationError::ChannelsFailed(failures)) = result else {
+            panic!("expected the failing channels, got {:?}", result);
+        };
+        let channels: Vec<&str> = failures.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(channels, vec!["sms", "channel 3"]);
+        assert!(matches!(failures[0].1, NotificationError::SendFailed(_)));
+        assert_eq!(read_notification_records(&log).unwrap().len(), 1);
+        let _ = std::fs::remove_file(log);
+    }
+
+    #[test]
+    fn test_fail_fast_skips_the_channels_after_a_failure() {
+        let log = std::env::temp_dir().join(format!("composite-{}.jsonl", Uuid::new_v4()));
+        let composite = CompositeNotifier::new()
+            .with_channel("sms", Broken)
+            .with_channel("file", FileNotifier::new(log.clone()))
+            .with_policy(FailurePolicy::FailFast);
+
+        let error = composite.notify_order_placed(&placed_notice()).unwrap_err();
+
+        assert_eq!(error.to_string(), "Channels failed: sms: Send failed: offline");
+        assert!(!log.exists());
+    }
+}
@@ -0,0 +1,182 @@
+// SOLID: Compression - optional gzip/zstd layer under the file repositories
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// JsonOrderRepository and BinaryOrderRepository still produce the same bytes;
+// this module only squeezes them on the way to disk and back. Neither the
+// OrderRepository trait nor the formats changed.
+//
+// TRANSPARENT ON READ:
+// A compressed stream names itself in its first bytes (gzip: 1f 8b,
+// zstd: 28 b5 2f fd). Plain JSON starts with '[' and a binary file with
+// "CSOB", so a repository never has to be told how a file was written - only
+// how to write it. An opened file keeps its compression unless asked to
+// change it.
+//
+// TRADE-OFF (see benches/compression.rs):
+// Order files are very repetitive, so both codecs shrink them several times.
+// gzip is everywhere (zcat can read the file); zstd compresses about as well
+// and decompresses faster. Each codec is behind its own feature (`gzip`,
+// `zstd`); without it, such a file is refused with a message saying which
+// feature to build with.
+
+use crate::ports::RepositoryError;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// How a repository file is compressed on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Every codec, compiled in or not
+    pub const ALL: [Compression; 3] = [Compression::None, Compression::Gzip, Compression::Zstd];
+
+    /// `none`, `gzip` or `zstd`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|codec| codec.name() == name)
+    }
+
+    /// Whether this build can read and write it
+    pub fn is_available(&self) -> bool {
+        match self {
+            Compression::None => true,
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    /// The compression of file contents, from their first bytes
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    pub fn compress(&self, bytes: Vec<u8>) -> Result<Vec<u8>, RepositoryError> {
+        let fail = |e: String| RepositoryError::SaveFailed(format!("Failed to compress: {}", e));
+        match self {
+            Compression::None => Ok(bytes),
+            Compression::Gzip => gzip::compress(&bytes).map_err(fail),
+            Compression::Zstd => zstd::compress(&bytes).map_err(fail),
+        }
+    }
+
+    /// Undo whatever compression the contents were written with
+    pub fn decompress(bytes: Vec<u8>) -> Result<Vec<u8>, RepositoryError> {
+        let fail = |e: String| RepositoryError::LoadFailed(format!("Failed to decompress: {}", e));
+        match Self::detect(&bytes) {
+            Compression::None => Ok(bytes),
+            Compression::Gzip => gzip::decompress(&bytes).map_err(fail),
+            Compression::Zstd => zstd::decompress(&bytes).map_err(fail),
+        }
+    }
+
+    #[cfg_attr(all(feature = "gzip", feature = "zstd"), allow(dead_code))]
+    fn missing(&self) -> String {
+        let name = self.name();
+        format!("{} support is not compiled in (build with --features {})", name, name)
+    }
+}
+
+#[cfg(feature = "gzip")]
+mod gzip {
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use std::io::{Read, Write};
+
+    pub fn compress(bytes: &[u8]) -> Result<Vec<u8>, String> {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).map_err(|e| e.to_string())?;
+        encoder.finish().map_err(|e| e.to_string())
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>, String> {
+        let mut plain = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut plain).map_err(|e| e.to_string())?;
+        Ok(plain)
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+mod gzip {
+    use super::Compression;
+
+    pub fn compress(_: &[u8]) -> Result<Vec<u8>, String> {
+        Err(Compression::Gzip.missing())
+    }
+
+    pub fn decompress(_: &[u8]) -> Result<Vec<u8>, String> {
+        Err(Compression::Gzip.missing())
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    /// zstd's own default; higher levels cost a lot more CPU for little gain
+    const LEVEL: i32 = 3;
+
+    pub fn compress(bytes: &[u8]) -> Result<Vec<u8>, String> {
+        ::zstd::encode_all(bytes, LEVEL).map_err(|e| e.to_string())
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>, String> {
+        ::zstd::decode_all(bytes).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(feature = "zstd"))]
+mod zstd {
+    use super::Compression;
+
+    pub fn compress(_: &[u8]) -> Result<Vec<u8>, String> {
+        Err(Compression::Zstd.missing())
+    }
+
+    pub fn decompress(_: &[u8]) -> Result<Vec<u8>, String> {
+        Err(Compression::Zstd.missing())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_or_clear_refusal() {
+        let plain = br#"[{"id":"a"},{"id":"b"},{"id":"c"},{"id":"d"}]"#.repeat(50);
+
+        for codec in Compression::ALL {
+            match codec.compress(plain.clone()) {
+                Ok(packed) => {
+                    assert_eq!(Compression::detect(&packed), codec);
+                    assert_eq!(Compression::decompress(packed).unwrap(), plain);
+                    assert!(codec.is_available());
+                }
+                Err(e) => {
+                    assert!(!codec.is_available());
+                    assert!(e.to_string().contains(&format!("--features {}", codec.name())));
+                }
+            }
+        }
+        assert_eq!(Compression::from_name("zstd"), Some(Compression::Zstd));
+        assert_eq!(Compression::from_name("lz4"), None);
+    }
+}
@@ -12,11 +12,32 @@
 // 3. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
 //    This has ONE job: send notifications to the console
 //    It doesn't handle order creation, payment, or storage
+//
+// Under the plain output style (`--plain`, see OutputStyle) it prints no
+// emoji and no color, for screen readers and log scrapers.
 
-use crate::domain::Order;
+use crate::domain::{
+    AlertState, FeedbackRequestNotice, NoticeItem, NoticeRecipient, OpsAlertNotice,
+    OrderCancelledNotice, OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice, OutputStyle,
+    RefundPendingNotice, RewardGrantedNotice, SlaBreachNotice,
+};
 use crate::ports::{NotificationError, Notifier};
+use std::io::{self, Stdout, Write};
+use std::sync::Mutex;
+use uuid::Uuid;
 
-/// Console notifier - prints notifications to stdout
+/// How much a ConsoleNotifier prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// One line per notification
+    Quiet,
+    /// The classic multi-line messages
+    Normal,
+    /// Normal plus every item and the customer's contact details
+    Verbose,
+}
+
+/// Console notifier - prints notifications to stdout (or any `Write` sink)
 /// 
 /// SOLID PRINCIPLE: Interface Segregation Principle (ISP)
 /// 
@@ -42,62 +63,234 @@ use crate::ports::{NotificationError, Notifier};
 /// 
 /// Each is independent and has its own single responsibility.
 /// 
+/// SOLID PRINCIPLE: Dependency Inversion Principle (DIP)
+/// 
+/// The sink is a type parameter, not a hard-coded `println!`.
+/// `ConsoleNotifier::new()` writes to stdout; `ConsoleNotifier::with_writer(Vec::new())`
+/// writes to a buffer that a test (or a TUI pane) can read back.
+/// 
 /// USE CASE:
 /// Perfect for:
 /// - Development (immediate feedback)
 /// - Demos (visual confirmation)
 /// - Testing (easy to verify output)
-pub struct ConsoleNotifier;
+pub struct ConsoleNotifier<W: Write = Stdout> {
+    // Notifier methods take &self, the Mutex gives us the &mut W that Write needs
+    out: Mutex<W>,
+    color: bool,
+    verbosity: Verbosity,
+    style: OutputStyle,
+}
+
+impl ConsoleNotifier<Stdout> {
+    /// Print to stdout, no color, normal verbosity
+    pub fn new() -> Self {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl Default for ConsoleNotifier<Stdout> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> ConsoleNotifier<W> {
+    /// Print to `out` instead of stdout
+    pub fn with_writer(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+            color: false,
+            verbosity: Verbosity::Normal,
+            style: OutputStyle::current(),
+        }
+    }
+
+    /// Highlight headers with ANSI colors (never in the plain style)
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Draw notifications in `style` instead of the process-wide one
+    pub fn with_style(mut self, style: OutputStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Get the sink back (e.g. the buffer a test wrote into)
+    pub fn into_inner(self) -> W {
+        self.out.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
 
-impl Notifier for ConsoleNotifier {
-    fn notify_order_placed(&self, order: &Order) -> Result<(), NotificationError> {
+    /// Print one notification
+    ///
+    /// `header` is the first line, `body` the normal-mode details.
+    /// Verbose mode adds the contact address and `items` (if any).
+    fn emit(
+        &self,
+        header: &str,
+        body: &str,
+        order_id: Uuid,
+        recipient: &NoticeRecipient,
+        items: &[NoticeItem],
+    ) -> Result<(), NotificationError> {
+        let mut details = format!("Contact: {}\n", recipient.email);
+        for item in items {
+            details.push_str(&format!("  - {} x {}\n", item.quantity, item.description));
+        }
+        self.print(header, body, order_id, &details)
+    }
+
+    /// Print one notification with `details` shown in verbose mode only
+    fn print(
+        &self,
+        header: &str,
+        body: &str,
+        id: Uuid,
+        details: &str,
+    ) -> Result<(), NotificationError> {
+        let header = if self.color && self.style == OutputStyle::Rich {
+            // Bold yellow
+            format!("\x1b[1;33m{}\x1b[0m", header)
+        } else {
+            header.to_string()
+        };
+
+        let message = match self.verbosity {
+            Verbosity::Quiet => format!("{} {}", header, id),
+            Verbosity::Normal => format!("\n{}\n{}\n", header, body),
+            Verbosity::Verbose => format!("\n{}\n{}\n{}", header, body, details),
+        };
+
+        let mut out = self
+            .out
+            .lock()
+            .map_err(|_| NotificationError::SendFailed("Console writer poisoned".to_string()))?;
+        writeln!(out, "{}", self.style.apply(&message))
+            .and_then(|_| out.flush())
+            .map_err(|e| NotificationError::SendFailed(format!("Failed to write: {}", e)))
+    }
+}
+
+impl<W: Write> Notifier for ConsoleNotifier<W> {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
         // Format notification message
-        let message = format!(
-            "🎉 Order Placed!\n\
-             Order ID: {}\n\
+        let body = format!(
+            "Order ID: {}\n\
              Customer: {} ({})\n\
              Items: {}\n\
-             Total: ${:.2}\n\
-             Status: {:?}",
-            order.id,
-            order.customer.name,
-            order.customer.email,
-            order.items.len(),
-            order.total_price,
-            order.status
+             Total: ${:.2}",
+            notice.order_id,
+            notice.recipient.name,
+            notice.recipient.email,
+            notice.items.len(),
+            notice.total_price.to_major()
         );
 
-        // Print to console
-        println!("\n{}\n", message);
-
-        Ok(())
+        self.emit("🎉 Order Placed!", &body, notice.order_id, &notice.recipient, &notice.items)
     }
 
-    fn notify_order_ready(&self, order: &Order) -> Result<(), NotificationError> {
-        let message = format!(
-            "☕ Order Ready for Pickup!\n\
-             Order ID: {}\n\
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        let body = format!(
+            "Order ID: {}\n\
              Customer: {}\n\
              Please come to the counter!",
-            order.id, order.customer.name
+            notice.order_id, notice.recipient.name
         );
 
-        println!("\n{}\n", message);
+        self.emit("☕ Order Ready for Pickup!", &body, notice.order_id, &notice.recipient, &[])
+    }
 
-        Ok(())
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        let body = format!(
+            "Order ID: {}\n\
+             Customer: {}",
+            notice.order_id, notice.recipient.name
+        );
+
+        self.emit("❌ Order Cancelled", &body, notice.order_id, &notice.recipient, &[])
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        let body = format!(
+            "Order ID: {}\n\
+             Customer: {}\n\
+             Your order was ready but nobody came to collect it.",
+            notice.order_id, notice.recipient.name
+        );
+
+        self.emit("⌛ Order Not Picked Up", &body, notice.order_id, &notice.recipient, &[])
     }
 
-    fn notify_order_cancelled(&self, order: &Order) -> Result<(), NotificationError> {
-        let message = format!(
-            "❌ Order Cancelled\n\
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        let body = format!(
+            "Order ID: {}\n\
+             Customer: {}\n\
+             Stuck in {} for {} minutes.",
+            notice.order_id, notice.recipient.name, notice.stuck_in, notice.waited_minutes
+        );
+
+        self.emit("🚨 SLA Breach", &body, notice.order_id, &notice.recipient, &notice.items)
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        let body = format!(
+            "Refund ID: {}\n\
              Order ID: {}\n\
-             Customer: {}",
-            order.id, order.customer.name
+             Amount: ${:.2}\n\
+             Requested by {}: {}",
+            notice.refund_id,
+            notice.order_id,
+            notice.amount.to_major(),
+            notice.requested_by,
+            notice.reason
         );
 
-        println!("\n{}\n", message);
+        self.emit("💸 Refund Awaiting Approval", &body, notice.order_id, &notice.recipient, &[])
+    }
 
-        Ok(())
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        let header = match notice.state {
+            AlertState::Firing => format!("🔥 Alert: {}", notice.rule),
+            AlertState::Resolved => format!("✅ Resolved: {}", notice.rule),
+        };
+        let details = format!("Value: {} (threshold {})\n", notice.value, notice.threshold);
+        self.print(&header, &notice.summary, notice.alert_id, &details)
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        let body = format!(
+            "Order ID: {}\n\
+             Customer: {}\n\
+             How was it? Rate your order: {}",
+            notice.order_id, notice.recipient.name, notice.link
+        );
+
+        self.emit("⭐ Tell Us How We Did", &body, notice.order_id, &notice.recipient, &[])
+    }
+
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        let body = format!(
+            "{}, {}!\n\
+             Here is {:.0}% off your next order: {}\n\
+             Valid until {}",
+            notice.occasion,
+            notice.recipient.name,
+            notice.percent_off,
+            notice.code,
+            notice.expires_at.format("%Y-%m-%d")
+        );
+
+        self.emit("🎁 A Treat On Us", &body, notice.coupon_id, &notice.recipient, &[])
     }
 }
 
@@ -109,9 +302,9 @@ impl Notifier for ConsoleNotifier {
 // BAD (Fat Interface):
 // trait OrderManager {
 //     fn save_order(&mut self, order: &Order) -> Result<()>;
-//     fn process_payment(&self, amount: f64) -> Result<String>;
+//     fn process_payment(&self, amount: Money) -> Result<String>;
 //     fn notify_customer(&self, order: &Order) -> Result<()>;
-//     fn calculate_price(&self, items: &[Item]) -> f64;
+//     fn calculate_price(&self, items: &[Item]) -> Money;
 //     fn generate_report(&self) -> Report;
 // }
 // 
@@ -119,13 +312,13 @@ impl Notifier for ConsoleNotifier {
 //     fn save_order(&mut self, order: &Order) -> Result<()> {
 //         unimplemented!() // Not needed!
 //     }
-//     fn process_payment(&self, amount: f64) -> Result<String> {
+//     fn process_payment(&self, amount: Money) -> Result<String> {
 //         unimplemented!() // Not needed!
 //     }
 //     fn notify_customer(&self, order: &Order) -> Result<()> {
 //         // Only this is needed
 //     }
-//     fn calculate_price(&self, items: &[Item]) -> f64 {
+//     fn calculate_price(&self, items: &[Item]) -> Money {
 //         unimplemented!() // Not needed!
 //     }
 //     fn generate_report(&self) -> Report {
@@ -159,14 +352,14 @@ impl Notifier for ConsoleNotifier {
 // }
 // 
 // impl Notifier for EmailNotifier {
-//     fn notify_order_placed(&self, order: &Order) -> Result<(), NotificationError> {
+//     fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
 //         let email = format!(
 //             "To: {}\n\
 //              Subject: Order Confirmation\n\
 //              \n\
 //              Your order {} has been placed!",
-//             order.customer.email,
-//             order.id
+//             notice.recipient.email,
+//             notice.order_id
 //         );
 //         
 //         // Send via SMTP
@@ -187,17 +380,18 @@ impl Notifier for ConsoleNotifier {
 // ============================================================================
 // COMPOSITE PATTERN: Multiple Notification Channels
 // 
-// Want to send notifications to multiple channels? Use a composite:
+// Want to send notifications to multiple channels? Use a composite
+// (adapters/composite_notifier.rs is the real one):
 // 
 // pub struct CompositeNotifier {
 //     notifiers: Vec<Box<dyn Notifier>>,
 // }
 // 
 // impl Notifier for CompositeNotifier {
-//     fn notify_order_placed(&self, order: &Order) -> Result<(), NotificationError> {
+//     fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
 //         for notifier in &self.notifiers {
 //             // Try all notifiers, ignore individual failures
-//             let _ = notifier.notify_order_placed(order);
+//             let _ = notifier.notify_order_placed(notice);
 //         }
 //         Ok(())
 //     }
@@ -205,7 +399,7 @@ impl Notifier for ConsoleNotifier {
 // }
 // 
 // let notifiers = vec![
-//     Box::new(ConsoleNotifier) as Box<dyn Notifier>,
+//     Box::new(ConsoleNotifier::new()) as Box<dyn Notifier>,
 //     Box::new(EmailNotifier::new(...)),
 //     Box::new(SmsNotifier::new(...)),
 // ];
@@ -220,7 +414,7 @@ impl Notifier for ConsoleNotifier {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Customer, OrderItem};
+    use crate::domain::{Customer, Money, Order, OrderItem};
 
     fn make_test_order() -> Order {
         let customer = Customer::new(
@@ -232,8 +426,11 @@ mod tests {
         let items = vec![OrderItem {
             beverage_name: "Coffee".to_string(),
             beverage_description: "Medium Coffee".to_string(),
-            price: 3.50,
+            price: Money::usd(3.50),
             quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
         }];
 
         Order::new(customer, items)
@@ -241,32 +438,93 @@ mod tests {
 
     #[test]
     fn test_notify_order_placed() {
-        let notifier = ConsoleNotifier;
+        let notifier = ConsoleNotifier::new();
         let order = make_test_order();
 
-        let result = notifier.notify_order_placed(&order);
+        let result = notifier.notify_order_placed(&OrderPlacedNotice::from_order(&order));
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_notify_order_ready() {
-        let notifier = ConsoleNotifier;
+        let notifier = ConsoleNotifier::new();
         let mut order = make_test_order();
-        order.mark_as_paid("TEST-123".to_string());
-        order.mark_as_preparing();
-        order.mark_as_ready();
+        order.mark_as_paid("TEST-123".to_string()).unwrap();
+        order.mark_as_preparing().unwrap();
+        order.mark_as_ready().unwrap();
 
-        let result = notifier.notify_order_ready(&order);
+        let result = notifier.notify_order_ready(&OrderReadyNotice::from_order(&order));
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_notify_order_cancelled() {
-        let notifier = ConsoleNotifier;
+        let notifier = ConsoleNotifier::new();
         let mut order = make_test_order();
-        order.cancel();
+        order.cancel().unwrap();
+
+        let result = notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&order));
+        assert!(result.is_ok());
+    }
 
-        let result = notifier.notify_order_cancelled(&order);
+    #[test]
+    fn test_notify_order_no_show() {
+        let notifier = ConsoleNotifier::new();
+        let order = make_test_order();
+
+        let result = notifier.notify_order_no_show(&OrderNoShowNotice::from_order(&order));
         assert!(result.is_ok());
     }
+
+    fn output(notifier: ConsoleNotifier<Vec<u8>>) -> String {
+        String::from_utf8(notifier.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_normal_output_is_captured() {
+        let notifier = ConsoleNotifier::with_writer(Vec::new());
+        notifier.notify_order_ready(&OrderReadyNotice::from_order(&make_test_order())).unwrap();
+
+        let text = output(notifier);
+        assert!(text.contains("☕ Order Ready for Pickup!"));
+        assert!(text.contains("Please come to the counter!"));
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_quiet_is_one_line_and_verbose_lists_items() {
+        let order = make_test_order();
+
+        let quiet = ConsoleNotifier::with_writer(Vec::new()).with_verbosity(Verbosity::Quiet);
+        quiet.notify_order_placed(&OrderPlacedNotice::from_order(&order)).unwrap();
+        assert_eq!(output(quiet), format!("🎉 Order Placed! {}\n", order.id));
+
+        let verbose = ConsoleNotifier::with_writer(Vec::new()).with_verbosity(Verbosity::Verbose);
+        verbose.notify_order_placed(&OrderPlacedNotice::from_order(&order)).unwrap();
+        let text = output(verbose);
+        assert!(text.contains("Contact: test@example.com"));
+        assert!(text.contains("1 x Medium Coffee"));
+    }
+
+    #[test]
+    fn test_color_wraps_header() {
+        let notifier = ConsoleNotifier::with_writer(Vec::new())
+            .with_color(true)
+            .with_verbosity(Verbosity::Quiet);
+        notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&make_test_order())).unwrap();
+
+        assert!(output(notifier).starts_with("\x1b[1;33m❌ Order Cancelled\x1b[0m"));
+    }
+
+    #[test]
+    fn test_plain_style_drops_emoji_and_color() {
+        let notifier = ConsoleNotifier::with_writer(Vec::new())
+            .with_color(true)
+            .with_style(OutputStyle::Plain)
+            .with_verbosity(Verbosity::Quiet);
+        let order = make_test_order();
+        notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&order)).unwrap();
+
+        assert_eq!(output(notifier), format!("[error] Order Cancelled {}\n", order.id));
+    }
 }
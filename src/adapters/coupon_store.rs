@@ -0,0 +1,113 @@
+// SOLID: Coupon stores (in memory, JSON file)
+//
+// Both implement CouponStore with the same contract (LSP): the `rewards`
+// CLI and RewardService can't tell them apart.
+
+use crate::domain::Coupon;
+use crate::ports::{CouponError, CouponStore};
+use std::fs;
+use std::path::PathBuf;
+
+/// Coupons kept in a Vec (tests, demos)
+#[derive(Debug, Default)]
+pub struct MemoryCouponStore {
+    coupons: Vec<Coupon>,
+}
+
+impl MemoryCouponStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CouponStore for MemoryCouponStore {
+    fn save(&mut self, coupon: Coupon) -> Result<(), CouponError> {
+        match self.coupons.iter_mut().find(|c| c.code == coupon.code) {
+            Some(existing) => *existing = coupon,
+            None => self.coupons.push(coupon),
+        }
+        Ok(())
+    }
+
+    fn find(&self, code: &str) -> Result<Option<Coupon>, CouponError> {
+        Ok(self.coupons.iter().find(|c| c.code.eq_ignore_ascii_case(code)).cloned())
+    }
+
+    fn for_customer(&self, email: &str) -> Result<Vec<Coupon>, CouponError> {
+        let email = email.to_lowercase();
+        Ok(self.coupons.iter().filter(|c| c.customer_email == email).cloned().collect())
+    }
+}
+
+/// Coupons persisted to a JSON file (rewritten on every change)
+pub struct JsonCouponStore {
+    file_path: PathBuf,
+    inner: MemoryCouponStore,
+}
+
+impl JsonCouponStore {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, CouponError> {
+        let coupons = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| CouponError::StorageFailed(format!("Failed to read file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| CouponError::StorageFailed(format!("Failed to parse JSON: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryCouponStore { coupons },
+        })
+    }
+
+    fn write(&self) -> Result<(), CouponError> {
+        let json = serde_json::to_string_pretty(&self.inner.coupons)
+            .map_err(|e| CouponError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| CouponError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl CouponStore for JsonCouponStore {
+    fn save(&mut self, coupon: Coupon) -> Result<(), CouponError> {
+        self.inner.save(coupon)?;
+        self.write()
+    }
+
+    fn find(&self, code: &str) -> Result<Option<Coupon>, CouponError> {
+        self.inner.find(code)
+    }
+
+    fn for_customer(&self, email: &str) -> Result<Vec<Coupon>, CouponError> {
+        self.inner.for_customer(email)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Occasion;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_json_store_persists_coupons() {
+        let path = std::env::temp_dir().join(format!("coupons-{}.json", Uuid::new_v4()));
+        let now = Utc::now();
+        let mut coupon =
+            Coupon::new("Ada@example.com", Occasion::Birthday, 50.0, now, Duration::days(30));
+
+        let mut store = JsonCouponStore::new(path.clone()).unwrap();
+        store.save(coupon.clone()).unwrap();
+        assert!(coupon.redeem(now));
+        store.save(coupon.clone()).unwrap();
+
+        let reloaded = JsonCouponStore::new(path.clone()).unwrap();
+        assert_eq!(reloaded.for_customer("ada@EXAMPLE.com").unwrap(), vec![coupon.clone()]);
+        assert_eq!(reloaded.find(&coupon.code.to_lowercase()).unwrap(), Some(coupon));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -7,9 +7,16 @@
 // - CashPayment (it's independent)
 // 
 // This demonstrates how OCP enables extension without modification.
+//
+// Card numbers never reach this adapter's state: `tokenize` stands in for the
+// gateway's hosted card form, which takes the number and hands back a
+// CardToken. Charges name the card by its MaskedPan ("**** 4242") only.
 
-use crate::ports::{PaymentError, PaymentProcessor};
-use uuid::Uuid;
+use super::randomness::SystemRandomness;
+use crate::domain::{CardToken, Currency, MaskedPan, Money};
+use crate::ports::{PaymentError, PaymentProcessor, PaymentRequest, Randomness};
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 /// Credit card payment processor
 /// 
@@ -33,6 +40,15 @@ pub struct CreditCardPayment {
     // - API credentials
     // - Configuration
     _gateway_url: String,
+    // Idempotency key -> payment ID of charges already made
+    // (a real gateway keeps this on its side)
+    charges: Mutex<HashMap<String, String>>,
+    // Where payment IDs come from (a real gateway assigns them)
+    randomness: Box<dyn Randomness>,
+    // Token -> the card it stands for (a real gateway keeps the card itself)
+    vault: Mutex<HashMap<CardToken, MaskedPan>>,
+    // Payment ID -> the card charged
+    cards: Mutex<HashMap<String, MaskedPan>>,
 }
 
 impl CreditCardPayment {
@@ -40,14 +56,60 @@ impl CreditCardPayment {
     pub fn new(gateway_url: String) -> Self {
         Self {
             _gateway_url: gateway_url,
+            charges: Mutex::new(HashMap::new()),
+            randomness: Box::new(SystemRandomness),
+            vault: Mutex::new(HashMap::new()),
+            cards: Mutex::new(HashMap::new()),
         }
     }
+
+    /// Draw payment IDs from `randomness` (a SeededRandomness for reproducible runs)
+    pub fn with_randomness(mut self, randomness: impl Randomness + 'static) -> Self {
+        self.randomness = Box::new(randomness);
+        self
+    }
+
+    /// Exchange a card number for a token, as the gateway's card form does
+    ///
+    /// Only the last four digits are kept; the number is refused unless it
+    /// passes the Luhn check.
+    pub fn tokenize(&self, pan: &str) -> Result<CardToken, PaymentError> {
+        let card = MaskedPan::from_pan(pan).map_err(|_| PaymentError::InvalidCard)?;
+        let token = CardToken::new(&format!("tok_{}", self.randomness.uuid().simple()))
+            .map_err(|e| PaymentError::ProcessingFailed(e.to_string()))?;
+        self.vault.lock().unwrap().insert(token.clone(), card);
+        Ok(token)
+    }
+
+    /// Charge the card behind `token` (same idempotency as `process_request`)
+    pub fn charge_card(
+        &self,
+        request: &PaymentRequest,
+        token: &CardToken,
+    ) -> Result<String, PaymentError> {
+        let card = self
+            .vault
+            .lock()
+            .unwrap()
+            .get(token)
+            .cloned()
+            .ok_or(PaymentError::InvalidCard)?;
+        println!("💳 Charging card {}", card);
+        let payment_id = self.process_request(request)?;
+        self.cards.lock().unwrap().insert(payment_id.clone(), card);
+        Ok(payment_id)
+    }
+
+    /// The card a charge was made on, for receipts and disputes
+    pub fn card_for(&self, payment_id: &str) -> Option<MaskedPan> {
+        self.cards.lock().unwrap().get(payment_id).cloned()
+    }
 }
 
 impl PaymentProcessor for CreditCardPayment {
-    fn process_payment(&self, amount: f64) -> Result<String, PaymentError> {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
         // Simulate credit card payment processing
-        println!("💳 Processing credit card payment of ${:.2}", amount);
+        println!("💳 Processing credit card payment of {}", amount);
 
         // In a real system, this would:
         // 1. Validate card details
@@ -57,7 +119,7 @@ impl PaymentProcessor for CreditCardPayment {
         // 5. Return transaction ID
 
         // Simulate validation
-        if amount < 0.0 {
+        if amount.is_negative() {
             return Err(PaymentError::ProcessingFailed(
                 "Amount cannot be negative".to_string(),
             ));
@@ -65,19 +127,52 @@ impl PaymentProcessor for CreditCardPayment {
 
         // Simulate occasional failures (for demo purposes)
         // In real code, failures would come from the payment gateway
-        if amount > 1000.0 {
+        if amount > Money::new(100_000, amount.currency) {
             return Err(PaymentError::ProcessingFailed(
                 "Amount exceeds card limit".to_string(),
             ));
         }
 
         // LSP: Return Ok with a unique payment ID (honoring the contract)
-        let payment_id = format!("CC-{}", Uuid::new_v4());
+        let payment_id = format!("CC-{}", self.randomness.uuid());
         println!("✓ Credit card payment successful: {}", payment_id);
 
         Ok(payment_id)
     }
 
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        if request.amount.currency != Currency::USD {
+            return Err(PaymentError::ProcessingFailed(format!(
+                "Unsupported currency: {}",
+                request.amount.currency
+            )));
+        }
+
+        // A retried request returns the original charge instead of charging again
+        let mut charges = self.charges.lock().unwrap();
+        if let Some(payment_id) = charges.get(&request.idempotency_key) {
+            return Ok(payment_id.clone());
+        }
+
+        println!("🧾 Card charge for order {}", request.order_id);
+        let payment_id = self.process_payment(request.amount)?;
+        charges.insert(request.idempotency_key.clone(), payment_id.clone());
+
+        Ok(payment_id)
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        if amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Refund amount must be positive".to_string(),
+            ));
+        }
+
+        // In a real system: POST /refunds with the original charge ID
+        println!("💳 Refunding {} to the card charged in {}", amount, payment_id);
+        Ok(format!("CC-REFUND-{}", self.randomness.uuid()))
+    }
+
     fn payment_method_name(&self) -> &str {
         "Credit Card"
     }
@@ -94,8 +189,8 @@ impl PaymentProcessor for CreditCardPayment {
 // }
 // 
 // impl PaymentProcessor for MobilePayment {
-//     fn process_payment(&self, amount: f64) -> Result<String, PaymentError> {
-//         println!("📱 Processing {} payment of ${:.2}", self.provider, amount);
+//     fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+//         println!("📱 Processing {} payment of {}", self.provider, amount);
 //         Ok(format!("MOBILE-{}", Uuid::new_v4()))
 //     }
 //     
@@ -146,11 +241,13 @@ impl PaymentProcessor for CreditCardPayment {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::SeededRandomness;
+    use uuid::Uuid;
 
     #[test]
     fn test_credit_card_payment_success() {
         let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
-        let result = payment.process_payment(50.00);
+        let result = payment.process_payment(Money::usd(50.00));
 
         assert!(result.is_ok());
         let payment_id = result.unwrap();
@@ -160,7 +257,7 @@ mod tests {
     #[test]
     fn test_credit_card_payment_negative_amount() {
         let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
-        let result = payment.process_payment(-10.00);
+        let result = payment.process_payment(Money::usd(-10.00));
 
         assert!(result.is_err());
     }
@@ -168,7 +265,7 @@ mod tests {
     #[test]
     fn test_credit_card_payment_exceeds_limit() {
         let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
-        let result = payment.process_payment(1500.00);
+        let result = payment.process_payment(Money::usd(1500.00));
 
         assert!(result.is_err());
     }
@@ -178,4 +275,65 @@ mod tests {
         let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
         assert_eq!(payment.payment_method_name(), "Credit Card");
     }
+
+    fn request(key: &str) -> PaymentRequest {
+        PaymentRequest {
+            amount: Money::usd(12.50),
+            order_id: Uuid::new_v4(),
+            customer_ref: "customer-1".to_string(),
+            customer_email: "customer-1@example.com".to_string(),
+            idempotency_key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_same_idempotency_key_charges_once() {
+        let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
+
+        let first = payment.process_request(&request("order-1")).unwrap();
+        let retry = payment.process_request(&request("order-1")).unwrap();
+        let other = payment.process_request(&request("order-2")).unwrap();
+
+        assert_eq!(first, retry);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_seeded_payment_ids_repeat() {
+        let payment_id = || {
+            CreditCardPayment::new("https://payment-gateway.example.com".to_string())
+                .with_randomness(SeededRandomness::new(42))
+                .process_payment(Money::usd(12.50))
+                .unwrap()
+        };
+
+        assert_eq!(payment_id(), payment_id());
+    }
+
+    #[test]
+    fn test_cards_are_charged_by_token() {
+        let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
+        assert!(matches!(
+            payment.tokenize("4242 4242 4242 4241"),
+            Err(PaymentError::InvalidCard)
+        ));
+
+        let token = payment.tokenize("4242 4242 4242 4242").unwrap();
+        assert!(token.expose().starts_with("tok_"));
+        let payment_id = payment.charge_card(&request("order-1"), &token).unwrap();
+        assert_eq!(payment.card_for(&payment_id).unwrap().to_string(), "**** 4242");
+
+        let forged = CardToken::new("tok_unknown").unwrap();
+        assert!(payment.charge_card(&request("order-2"), &forged).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_currency() {
+        let payment = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
+        let mut euros = request("order-1");
+        euros.amount = Money::new(1250, Currency::EUR);
+        let result = payment.process_request(&euros);
+
+        assert!(result.is_err());
+    }
 }
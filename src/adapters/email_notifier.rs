@@ -0,0 +1,340 @@
+// SOLID: EmailNotifier - order notices in the customer's inbox (feature `email`)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    A Notifier like ConsoleNotifier: OrderService can't tell an email from
+//    a console line. A bad address is InvalidRecipient, a server that can't
+//    be reached (or answers "try later") NetworkError, so RetryPolicy
+//    retries the right failures.
+//
+// 2. OPEN-CLOSED PRINCIPLE (OCP):
+//    Wording is configuration: each event (placed, ready, cancelled) has an
+//    EmailTemplate, replaced with `with_template` without touching the code.
+//    Placeholders: {name}, {order} (short ID), {total}, {items}.
+//
+// Unlike SmtpMailer (plain SMTP to a local relay, for staff mail), this one
+// talks to a provider directly through lettre: STARTTLS and a login.
+// `dry_run` builds the same messages and keeps them instead of sending, for
+// tests and demos.
+//
+// Customer-facing only: no-shows, SLA breaches and refunds awaiting approval
+// send nothing.
+
+use crate::domain::{
+    NoticeItem, OrderCancelledNotice, OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice,
+};
+use crate::ports::{NotificationError, Notifier};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// The events that send an email
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmailEvent {
+    Placed,
+    Ready,
+    Cancelled,
+}
+
+/// Subject and plain-text body, with {name}, {order}, {total} and {items} placeholders
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailTemplate {
+    pub subject: String,
+    pub body: String,
+}
+
+impl EmailTemplate {
+    pub fn new(subject: &str, body: &str) -> Self {
+        Self {
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    /// The shop's wording, used until `with_template` replaces it
+    pub fn default_for(event: EmailEvent) -> Self {
+        match event {
+            EmailEvent::Placed => Self::new(
+                "Your order {order} is confirmed",
+                "Hi {name},\n\nThanks for your order:\n{items}\nTotal: ${total}\n\n\
+                 We'll write again when it's ready.",
+            ),
+            EmailEvent::Ready => Self::new(
+                "Your order {order} is ready",
+                "Hi {name},\n\nYour order is waiting for you at the counter.",
+            ),
+            EmailEvent::Cancelled => Self::new(
+                "Your order {order} was cancelled",
+                "Hi {name},\n\nYour order was cancelled. If you paid for it, \
+                 ${total} is on its way back to you.",
+            ),
+        }
+    }
+
+    fn render(&self, fields: &Fields) -> (String, String) {
+        let fill = |text: &str| {
+            text.replace("{name}", &fields.name)
+                .replace("{order}", &fields.order)
+                .replace("{total}", &fields.total)
+                .replace("{items}", &fields.items)
+        };
+        (fill(&self.subject), fill(&self.body))
+    }
+}
+
+/// What the placeholders are replaced with
+struct Fields {
+    name: String,
+    order: String,
+    total: String,
+    items: String,
+}
+
+impl Fields {
+    fn new(name: &str, order_id: Uuid, total: Option<f64>, items: &[NoticeItem]) -> Self {
+        Self {
+            name: name.to_string(),
+            order: order_id.simple().to_string()[..8].to_string(),
+            total: total.map(|total| format!("{:.2}", total)).unwrap_or_default(),
+            items: items
+                .iter()
+                .map(|item| format!("- {} x {}\n", item.quantity, item.description))
+                .collect(),
+        }
+    }
+}
+
+/// Where and how to reach the SMTP server
+#[derive(Clone)]
+pub struct SmtpSettings {
+    host: String,
+    port: u16,
+    starttls: bool,
+    credentials: Option<Credentials>,
+    timeout: Duration,
+}
+
+impl SmtpSettings {
+    /// A provider's submission port (587), upgraded to TLS before anything is sent
+    pub fn starttls(host: &str) -> Self {
+        Self {
+            host: host.to_string(),
+            port: 587,
+            starttls: true,
+            credentials: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// No TLS: a local relay or a mail sink while testing (MailHog, port 1025)
+    pub fn plain(host: &str, port: u16) -> Self {
+        Self {
+            port,
+            starttls: false,
+            ..Self::starttls(host)
+        }
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Log in before sending
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.credentials = Some(Credentials::new(username.to_string(), password.to_string()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn transport(&self) -> Result<SmtpTransport, NotificationError> {
+        let builder = if self.starttls {
+            SmtpTransport::starttls_relay(&self.host)
+                .map_err(|e| NotificationError::SendFailed(e.to_string()))?
+        } else {
+            SmtpTransport::builder_dangerous(&self.host)
+        };
+        let builder = builder.port(self.port).timeout(Some(self.timeout));
+        Ok(match &self.credentials {
+            Some(credentials) => builder.credentials(credentials.clone()).build(),
+            None => builder.build(),
+        })
+    }
+}
+
+/// One email as it was (or would have been) sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Emails the customer when their order is placed, ready or cancelled
+pub struct EmailNotifier {
+    from: Mailbox,
+    // None in dry-run mode
+    transport: Option<SmtpTransport>,
+    templates: HashMap<EmailEvent, EmailTemplate>,
+    dry_run_log: Mutex<Vec<SentEmail>>,
+}
+
+impl EmailNotifier {
+    /// Send through `smtp`, from `from` ("Coffee Shop <orders@shop.example>")
+    pub fn new(from: &str, smtp: &SmtpSettings) -> Result<Self, NotificationError> {
+        Ok(Self {
+            transport: Some(smtp.transport()?),
+            ..Self::dry_run(from)?
+        })
+    }
+
+    /// Build every email, send none: `sent()` lists them
+    pub fn dry_run(from: &str) -> Result<Self, NotificationError> {
+        let from = from
+            .parse()
+            .map_err(|e| NotificationError::InvalidRecipient(format!("sender '{}': {}", from, e)))?;
+        Ok(Self {
+            from,
+            transport: None,
+            templates: HashMap::new(),
+            dry_run_log: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Word `event`'s email differently
+    pub fn with_template(mut self, event: EmailEvent, template: EmailTemplate) -> Self {
+        self.templates.insert(event, template);
+        self
+    }
+
+    /// The emails a dry run kept (always empty when sending for real)
+    pub fn sent(&self) -> Vec<SentEmail> {
+        self.dry_run_log.lock().unwrap().clone()
+    }
+
+    fn send(&self, event: EmailEvent, to: &str, fields: Fields) -> Result<(), NotificationError> {
+        let template = self
+            .templates
+            .get(&event)
+            .cloned()
+            .unwrap_or_else(|| EmailTemplate::default_for(event));
+        let (subject, body) = template.render(&fields);
+
+        let mailbox: Mailbox = format!("{} <{}>", fields.name.replace(['<', '>', '"'], ""), to)
+            .parse()
+            .map_err(|e| NotificationError::InvalidRecipient(format!("'{}': {}", to, e)))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(mailbox)
+            .subject(subject.clone())
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.clone())
+            .map_err(|e| NotificationError::SendFailed(e.to_string()))?;
+
+        match &self.transport {
+            // Refused for good (5xx) or a bad message: SendFailed. The rest
+            // (4xx "try later", timeouts, no connection) is worth a retry.
+            Some(transport) => transport.send(&message).map(|_| ()).map_err(|e| {
+                if e.is_permanent() || e.is_client() {
+                    NotificationError::SendFailed(e.to_string())
+                } else {
+                    NotificationError::NetworkError(e.to_string())
+                }
+            }),
+            None => {
+                self.dry_run_log.lock().unwrap().push(SentEmail {
+                    to: to.to_string(),
+                    subject,
+                    body,
+                });
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Notifier for EmailNotifier {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        let recipient = &notice.recipient;
+        let total = Some(notice.total_price.to_major());
+        let fields = Fields::new(&recipient.name, notice.order_id, total, &notice.items);
+        self.send(EmailEvent::Placed, &recipient.email, fields)
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        let fields = Fields::new(&notice.recipient.name, notice.order_id, None, &[]);
+        self.send(EmailEvent::Ready, &notice.recipient.email, fields)
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        let recipient = &notice.recipient;
+        let total = Some(notice.total_price.to_major());
+        let fields = Fields::new(&recipient.name, notice.order_id, total, &[]);
+        self.send(EmailEvent::Cancelled, &recipient.email, fields)
+    }
+
+    /// Not emailed: the shop keeps the order, the customer hears nothing new
+    fn notify_order_no_show(&self, _notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Coffee, Customer, Order};
+
+    fn order() -> Order {
+        Order::single(Customer::named("Ada"), Coffee::medium())
+    }
+
+    #[test]
+    fn test_dry_run_renders_each_event() {
+        let notifier = EmailNotifier::dry_run("Coffee Shop <orders@shop.example>")
+            .unwrap()
+            .with_template(
+                EmailEvent::Ready,
+                EmailTemplate::new("☕ {name}, order {order} is up", "See you at the counter."),
+            );
+        let order = order();
+
+        notifier.notify_order_placed(&OrderPlacedNotice::from_order(&order)).unwrap();
+        notifier.notify_order_ready(&OrderReadyNotice::from_order(&order)).unwrap();
+        notifier.notify_order_no_show(&OrderNoShowNotice::from_order(&order)).unwrap();
+
+        let sent = notifier.sent();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].to, "ada@example.com");
+        assert_eq!(sent[0].subject, format!("Your order {} is confirmed", order.short_id()));
+        assert!(sent[0].body.contains("- 1 x Coffee (Medium)\n"), "{}", sent[0].body);
+        assert!(sent[0].body.contains("Total: $3.50"));
+        assert_eq!(sent[1].subject, format!("☕ Ada, order {} is up", order.short_id()));
+    }
+
+    #[test]
+    fn test_bad_addresses_are_refused() {
+        assert!(matches!(
+            EmailNotifier::dry_run("not an address"),
+            Err(NotificationError::InvalidRecipient(_))
+        ));
+
+        let notifier = EmailNotifier::dry_run("orders@shop.example").unwrap();
+        let mut order = order();
+        order.customer.email = "ada-at-example.com".to_string();
+        assert!(matches!(
+            notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&order)),
+            Err(NotificationError::InvalidRecipient(_))
+        ));
+        assert!(notifier.sent().is_empty());
+    }
+}
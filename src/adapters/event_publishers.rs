@@ -0,0 +1,116 @@
+// SOLID: EventPublisher adapters - console and in-memory
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    Both honor the same contract; OrderService can't tell which one (or
+//    which broker publisher, later) it was given.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    ConsoleEventPublisher writes one JSON object per line, for `jq` or a log
+//    shipper to pick up. InMemoryEventPublisher keeps the events for a test,
+//    or for an in-process consumer that reads them back.
+//
+// Clones of an InMemoryEventPublisher share the same events: keep one
+// handle, give the other to the service.
+
+use crate::domain::DomainEvent;
+use crate::ports::{EventPublisher, PublishError};
+use std::io::{self, Stdout, Write};
+use std::sync::{Arc, Mutex};
+
+/// Writes each event as a line of JSON to stdout (or any `Write` sink)
+pub struct ConsoleEventPublisher<W: Write = Stdout> {
+    // publish takes &self, the Mutex gives us the &mut W that Write needs
+    out: Mutex<W>,
+}
+
+impl ConsoleEventPublisher<Stdout> {
+    pub fn new() -> Self {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl Default for ConsoleEventPublisher<Stdout> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> ConsoleEventPublisher<W> {
+    pub fn with_writer(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+        }
+    }
+
+    /// The sink back, with everything written to it
+    pub fn into_inner(self) -> W {
+        self.out.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<W: Write + Send> EventPublisher for ConsoleEventPublisher<W> {
+    fn publish(&self, event: &DomainEvent) -> Result<(), PublishError> {
+        let line = serde_json::to_string(event)
+            .map_err(|e| PublishError::Unavailable(format!("Failed to serialize: {}", e)))?;
+        let mut out = self.out.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        writeln!(out, "{}", line).map_err(|e| PublishError::Unavailable(e.to_string()))
+    }
+}
+
+/// Every event published, in order
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryEventPublisher {
+    events: Arc<Mutex<Vec<DomainEvent>>>,
+}
+
+impl InMemoryEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<DomainEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// The topic names so far, in order: ["order.placed", "order.paid", ...]
+    pub fn names(&self) -> Vec<&'static str> {
+        self.events.lock().unwrap().iter().map(DomainEvent::name).collect()
+    }
+}
+
+impl EventPublisher for InMemoryEventPublisher {
+    fn publish(&self, event: &DomainEvent) -> Result<(), PublishError> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_console_writes_json_lines_and_memory_keeps_events() {
+        let ready = DomainEvent::OrderReady {
+            order_id: Uuid::new_v4(),
+            at: Utc::now(),
+        };
+        let console = ConsoleEventPublisher::with_writer(Vec::new());
+        let memory = InMemoryEventPublisher::new();
+        let handle = memory.clone();
+
+        console.publish(&ready).unwrap();
+        console.publish(&ready).unwrap();
+        memory.publish(&ready).unwrap();
+
+        let text = String::from_utf8(console.into_inner()).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        let line: DomainEvent = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(line, ready);
+        assert_eq!(handle.events(), vec![ready]);
+    }
+}
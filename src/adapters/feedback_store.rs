@@ -0,0 +1,115 @@
+// SOLID: Feedback repositories (in memory, JSON file)
+//
+// Both implement FeedbackRepository with the same contract (LSP): the
+// `feedback` CLI and FeedbackService can't tell them apart.
+
+use crate::domain::Feedback;
+use crate::ports::{FeedbackError, FeedbackRepository};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Ratings kept in a Vec (tests, demos)
+#[derive(Debug, Default)]
+pub struct MemoryFeedbackRepository {
+    ratings: Vec<Feedback>,
+}
+
+impl MemoryFeedbackRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FeedbackRepository for MemoryFeedbackRepository {
+    fn save(&mut self, feedback: Feedback) -> Result<(), FeedbackError> {
+        match self.ratings.iter_mut().find(|f| f.order_id == feedback.order_id) {
+            Some(existing) => *existing = feedback,
+            None => self.ratings.push(feedback),
+        }
+        Ok(())
+    }
+
+    fn find(&self, order_id: Uuid) -> Result<Option<Feedback>, FeedbackError> {
+        Ok(self.ratings.iter().find(|f| f.order_id == order_id).cloned())
+    }
+
+    fn list(&self) -> Result<Vec<Feedback>, FeedbackError> {
+        Ok(self.ratings.clone())
+    }
+}
+
+/// Ratings persisted to a JSON file (rewritten on every change)
+pub struct JsonFeedbackRepository {
+    file_path: PathBuf,
+    inner: MemoryFeedbackRepository,
+}
+
+impl JsonFeedbackRepository {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, FeedbackError> {
+        let ratings = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| FeedbackError::StorageFailed(format!("Failed to read file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| FeedbackError::StorageFailed(format!("Failed to parse JSON: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryFeedbackRepository { ratings },
+        })
+    }
+
+    fn write(&self) -> Result<(), FeedbackError> {
+        let json = serde_json::to_string_pretty(&self.inner.ratings)
+            .map_err(|e| FeedbackError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| FeedbackError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl FeedbackRepository for JsonFeedbackRepository {
+    fn save(&mut self, feedback: Feedback) -> Result<(), FeedbackError> {
+        self.inner.save(feedback)?;
+        self.write()
+    }
+
+    fn find(&self, order_id: Uuid) -> Result<Option<Feedback>, FeedbackError> {
+        self.inner.find(order_id)
+    }
+
+    fn list(&self) -> Result<Vec<Feedback>, FeedbackError> {
+        self.inner.list()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_json_repository_persists_one_rating_per_order() {
+        let path = std::env::temp_dir().join(format!("feedback-{}.json", Uuid::new_v4()));
+        let mut feedback = Feedback {
+            order_id: Uuid::new_v4(),
+            rating: 2,
+            comment: None,
+            submitted_at: Utc::now(),
+        };
+
+        let mut repository = JsonFeedbackRepository::new(path.clone()).unwrap();
+        repository.save(feedback.clone()).unwrap();
+        feedback.rating = 5;
+        feedback.comment = Some("Better second time".to_string());
+        repository.save(feedback.clone()).unwrap();
+
+        let reloaded = JsonFeedbackRepository::new(path.clone()).unwrap();
+        assert_eq!(reloaded.list().unwrap(), vec![feedback.clone()]);
+        assert_eq!(reloaded.find(feedback.order_id).unwrap(), Some(feedback));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+// SOLID: FileLock - DistributedLock on a shared directory
+//
+// For instances that share a disk (one host, or a network share) but nothing
+// else. Each lock is two files in the directory:
+//
+//   <name>.lease   {"holder": "...", "expires_at": "..."}, the actual lease
+//   <name>.lock    held with an OS file lock while the lease is read and
+//                  rewritten, so two instances never decide at the same time
+//
+// The lease file is written to a temporary file and renamed over the old
+// one, so a crash mid-write leaves the previous lease, never half of one.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same contract as MemoryLock and RedisLock. Only where leases live differs.
+
+use crate::adapters::clock::SystemClock;
+use crate::ports::{Clock, DistributedLock, LockError};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Lease {
+    holder: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Leases stored as files in one directory
+pub struct FileLock {
+    dir: PathBuf,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl FileLock {
+    /// The directory is created on first use
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Expire leases by `clock` instead of the wall clock (tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Run `critical` while holding the OS lock of `name`, with its lease file path
+    fn exclusively<T>(
+        &self,
+        name: &str,
+        critical: impl FnOnce(&Path) -> Result<T, LockError>,
+    ) -> Result<T, LockError> {
+        let file_name: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        fs::create_dir_all(&self.dir).map_err(unavailable)?;
+
+        let guard = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.dir.join(format!("{}.lock", file_name)))
+            .map_err(unavailable)?;
+        guard.lock().map_err(unavailable)?;
+        let result = critical(&self.dir.join(format!("{}.lease", file_name)));
+        let _ = guard.unlock();
+        result
+    }
+}
+
+fn unavailable(e: std::io::Error) -> LockError {
+    LockError::Unavailable(e.to_string())
+}
+
+fn read_lease(path: &Path) -> Result<Option<Lease>, LockError> {
+    match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text).map(Some).map_err(|e| {
+            LockError::Unavailable(format!("Corrupt lease {}: {}", path.display(), e))
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(unavailable(e)),
+    }
+}
+
+fn write_lease(path: &Path, lease: &Lease) -> Result<(), LockError> {
+    let json = serde_json::to_string(lease).map_err(|e| LockError::Unavailable(e.to_string()))?;
+    let temp = path.with_extension("lease.tmp");
+    fs::write(&temp, json).map_err(unavailable)?;
+    File::open(&temp).and_then(|file| file.sync_all()).map_err(unavailable)?;
+    fs::rename(&temp, path).map_err(unavailable)
+}
+
+impl DistributedLock for FileLock {
+    fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool, LockError> {
+        let now = self.clock.now();
+        self.exclusively(name, |path| {
+            if let Some(lease) = read_lease(path)?
+                && lease.holder != holder
+                && lease.expires_at > now
+            {
+                return Ok(false);
+            }
+            let lease = Lease {
+                holder: holder.to_string(),
+                expires_at: now + ttl,
+            };
+            write_lease(path, &lease)?;
+            Ok(true)
+        })
+    }
+
+    fn release(&self, name: &str, holder: &str) -> Result<bool, LockError> {
+        self.exclusively(name, |path| match read_lease(path)? {
+            Some(lease) if lease.holder == holder => {
+                fs::remove_file(path).map_err(unavailable)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::FixedClock;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_two_instances_share_the_directory() {
+        let dir = std::env::temp_dir().join(format!("locks-{}", Uuid::new_v4()));
+        let clock = Arc::new(FixedClock::new(Utc::now()));
+        let a = FileLock::new(dir.clone()).with_clock(clock.clone());
+        let b = FileLock::new(dir.clone()).with_clock(clock.clone());
+        let ttl = Duration::minutes(10);
+
+        assert!(a.try_acquire("day-close:2024-03-04", "server-a", ttl).unwrap());
+        assert!(!b.try_acquire("day-close:2024-03-04", "server-b", ttl).unwrap());
+        assert!(b.try_acquire("day-close:2024-03-05", "server-b", ttl).unwrap());
+
+        clock.advance(Duration::minutes(11));
+        assert!(b.try_acquire("day-close:2024-03-04", "server-b", ttl).unwrap());
+        assert!(!a.release("day-close:2024-03-04", "server-a").unwrap());
+        assert!(b.release("day-close:2024-03-04", "server-b").unwrap());
+        assert!(a.try_acquire("day-close:2024-03-04", "server-a", ttl).unwrap());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,188 @@
+// SOLID: FileNotifier - append notification records to a JSON Lines file
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    Same Notifier contract as ConsoleNotifier, so it can replace it anywhere.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    It records WHICH notification fired for WHICH order. No wording, no emoji.
+//
+// USE CASE:
+// Black-box tests run the app (or the worker binary) with a FileNotifier,
+// then read the file back and assert exactly which notifications were sent,
+// without capturing stdout. One JSON object per line, so appends never
+// rewrite earlier records and `read_notification_records` can parse the file incrementally.
+
+use crate::domain::{
+    FeedbackRequestNotice, OpsAlertNotice, OrderCancelledNotice, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, RefundPendingNotice, RewardGrantedNotice, SlaBreachNotice,
+};
+use crate::ports::{NotificationError, Notifier};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// One line of the notification file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationRecord {
+    /// Same names as the bus topics: "order.placed", "order.ready", ...
+    pub event: String,
+    pub order_id: Uuid,
+    /// Empty for ops alerts
+    pub customer_email: String,
+    pub recorded_at: DateTime<Utc>,
+    /// The notice exactly as the notifier received it
+    pub notice: Value,
+}
+
+/// Notifier that appends a NotificationRecord per notification
+pub struct FileNotifier {
+    file_path: PathBuf,
+}
+
+impl FileNotifier {
+    /// The file is created on the first notification
+    pub fn new(file_path: PathBuf) -> Self {
+        Self { file_path }
+    }
+
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    fn append<N: Serialize>(
+        &self,
+        event: &str,
+        order_id: Uuid,
+        customer_email: &str,
+        notice: &N,
+    ) -> Result<(), NotificationError> {
+        let serialize_error =
+            |e: serde_json::Error| NotificationError::SendFailed(format!("Failed to serialize: {}", e));
+        let record = NotificationRecord {
+            event: event.to_string(),
+            order_id,
+            customer_email: customer_email.to_string(),
+            recorded_at: Utc::now(),
+            notice: serde_json::to_value(notice).map_err(serialize_error)?,
+        };
+        let line = serde_json::to_string(&record).map_err(serialize_error)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .map_err(|e| NotificationError::SendFailed(format!("Failed to open file: {}", e)))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| NotificationError::SendFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl Notifier for FileNotifier {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        self.append("order.placed", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        self.append("order.ready", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        self.append("order.cancelled", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        self.append("order.no_show", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        self.append("order.sla_breach", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        self.append("refund.pending", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        self.append("feedback.requested", notice.order_id, &notice.recipient.email, notice)
+    }
+
+    /// Recorded under the coupon's ID (there is no order)
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        self.append("reward.granted", notice.coupon_id, &notice.recipient.email, notice)
+    }
+
+    /// Recorded under the alert's ID, with no customer
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        self.append("ops.alert", notice.alert_id, "", notice)
+    }
+}
+
+/// Read back every record written so far (a missing file means none)
+pub fn read_notification_records(file_path: &Path) -> Result<Vec<NotificationRecord>, NotificationError> {
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(file_path)
+        .map_err(|e| NotificationError::SendFailed(format!("Failed to read file: {}", e)))?;
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| NotificationError::SendFailed(format!("Failed to parse record: {}", e)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Customer, Order, Size, Tea, TeaVariety};
+
+    fn make_order() -> Order {
+        let tea = Tea { size: Size::Small, variety: TeaVariety::Green };
+        Order::single(Customer::named("Ada"), tea)
+    }
+
+    #[test]
+    fn test_records_are_appended_in_order() {
+        let path = std::env::temp_dir().join(format!("notifications-{}.jsonl", Uuid::new_v4()));
+        let notifier = FileNotifier::new(path.clone());
+        let order = make_order();
+
+        assert!(read_notification_records(&path).unwrap().is_empty());
+        notifier.notify_order_placed(&OrderPlacedNotice::from_order(&order)).unwrap();
+        notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&order)).unwrap();
+
+        let records = read_notification_records(&path).unwrap();
+        let events: Vec<&str> = records.iter().map(|r| r.event.as_str()).collect();
+        assert_eq!(events, vec!["order.placed", "order.cancelled"]);
+        assert_eq!(records[0].order_id, order.id);
+        assert_eq!(records[0].customer_email, "ada@example.com");
+        assert_eq!(records[0].notice["total_price"]["cents"], 200);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_unwritable_path_is_an_error() {
+        let path = std::env::temp_dir()
+            .join(format!("missing-{}", Uuid::new_v4()))
+            .join("notifications.jsonl");
+        let notifier = FileNotifier::new(path);
+
+        assert!(matches!(
+            notifier.notify_order_ready(&OrderReadyNotice::from_order(&make_order())),
+            Err(NotificationError::SendFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+// SOLID: Fiscal receipt counters (in memory, in a file)
+//
+// - MemoryFiscalNumbers: for tests and demos; the sequence restarts with
+//   the process, which a real shop is not allowed to do
+// - FileFiscalNumbers: the last number issued, in a text file rewritten
+//   (write then rename) before the number is handed out, so a crash can
+//   lose a sale's receipt but never reuse its number
+//
+// One counter per register: two processes sharing a file would need the
+// file locked around next_number (see adapters::file_lock).
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same FiscalNumberGenerator contract, only where the counter lives differs.
+
+use crate::ports::{FiscalError, FiscalNumberGenerator};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Counter kept in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFiscalNumbers {
+    last: u64,
+}
+
+impl MemoryFiscalNumbers {
+    /// First number issued: 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Continue a sequence: the next number issued is `last + 1`
+    pub fn continuing_from(last: u64) -> Self {
+        Self { last }
+    }
+}
+
+impl FiscalNumberGenerator for MemoryFiscalNumbers {
+    fn next_number(&mut self) -> Result<u64, FiscalError> {
+        self.last = self
+            .last
+            .checked_add(1)
+            .ok_or_else(|| FiscalError::CounterFailed("Sequence exhausted".to_string()))?;
+        Ok(self.last)
+    }
+}
+
+/// Last number issued, in a file
+pub struct FileFiscalNumbers {
+    file_path: PathBuf,
+}
+
+impl FileFiscalNumbers {
+    /// A missing file starts the sequence at 1
+    pub fn new(file_path: PathBuf) -> Self {
+        Self { file_path }
+    }
+
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// Last number issued (0 before the first one)
+    pub fn last_issued(&self) -> Result<u64, FiscalError> {
+        match fs::read_to_string(&self.file_path) {
+            Ok(text) => text.trim().parse().map_err(|_| {
+                FiscalError::CounterFailed(format!(
+                    "{} does not hold a receipt number",
+                    self.file_path.display()
+                ))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(FiscalError::CounterFailed(e.to_string())),
+        }
+    }
+}
+
+impl FiscalNumberGenerator for FileFiscalNumbers {
+    fn next_number(&mut self) -> Result<u64, FiscalError> {
+        let failed = |e: std::io::Error| FiscalError::CounterFailed(e.to_string());
+        let next = MemoryFiscalNumbers::continuing_from(self.last_issued()?).next_number()?;
+
+        let temp = self.file_path.with_extension("tmp");
+        fs::write(&temp, format!("{}\n", next)).map_err(failed)?;
+        fs::rename(&temp, &self.file_path).map_err(failed)?;
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_file_counter_survives_a_restart() {
+        let path = std::env::temp_dir().join(format!("fiscal-{}.txt", Uuid::new_v4()));
+
+        let mut numbers = FileFiscalNumbers::new(path.clone());
+        assert_eq!(numbers.next_number(), Ok(1));
+        assert_eq!(numbers.next_number(), Ok(2));
+
+        let mut reopened = FileFiscalNumbers::new(path.clone());
+        assert_eq!(reopened.last_issued(), Ok(2));
+        assert_eq!(reopened.next_number(), Ok(3));
+
+        fs::write(&path, "not a number").unwrap();
+        assert!(matches!(reopened.next_number(), Err(FiscalError::CounterFailed(_))));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_memory_counter_continues_a_sequence() {
+        let mut numbers = MemoryFiscalNumbers::continuing_from(41);
+        assert_eq!(numbers.next_number(), Ok(42));
+        assert!(MemoryFiscalNumbers::continuing_from(u64::MAX).next_number().is_err());
+    }
+}
@@ -0,0 +1,116 @@
+// SOLID: HashedExperiment - bucket customers by a hash of their email
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    Any Experiment implementation (a hosted service, a fixed assignment in
+//    tests) can replace it; PromotionEngine only calls the trait.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    It assigns variants. What a variant means (a discount, a new price)
+//    is configured in PromotionEngine.
+//
+// The bucket is SHA-256("<experiment>:<email>") modulo the total weight:
+// no state to store, stable across runs and machines, and independent
+// between experiments (being in B for one says nothing about the next).
+// Emails are compared case-insensitively.
+
+use crate::ports::Experiment;
+use sha2::{Digest, Sha256};
+
+/// Weighted variants, assigned deterministically per customer
+#[derive(Debug, Clone)]
+pub struct HashedExperiment {
+    name: String,
+    /// (variant, weight), control first
+    variants: Vec<(String, u32)>,
+}
+
+impl HashedExperiment {
+    /// `variants` are (name, weight) pairs, control first; zero weights are dropped
+    ///
+    /// With no positive weight left, everyone is in a single "control" variant.
+    pub fn new(name: &str, variants: &[(&str, u32)]) -> Self {
+        let mut variants: Vec<(String, u32)> = variants
+            .iter()
+            .filter(|(_, weight)| *weight > 0)
+            .map(|(variant, weight)| (variant.to_string(), *weight))
+            .collect();
+        if variants.is_empty() {
+            variants.push(("control".to_string(), 1));
+        }
+
+        Self {
+            name: name.to_string(),
+            variants,
+        }
+    }
+
+    /// 50/50 between "control" and `variant`
+    pub fn ab(name: &str, variant: &str) -> Self {
+        Self::new(name, &[("control", 1), (variant, 1)])
+    }
+
+    fn bucket(&self, customer_email: &str) -> u64 {
+        let digest = Sha256::digest(format!("{}:{}", self.name, customer_email.to_lowercase()));
+        let mut first = [0u8; 8];
+        first.copy_from_slice(&digest[..8]);
+        let total: u64 = self.variants.iter().map(|(_, weight)| *weight as u64).sum();
+        u64::from_be_bytes(first) % total
+    }
+}
+
+impl Experiment for HashedExperiment {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn variants(&self) -> Vec<String> {
+        self.variants.iter().map(|(variant, _)| variant.clone()).collect()
+    }
+
+    fn variant_for(&self, customer_email: &str) -> String {
+        let mut bucket = self.bucket(customer_email);
+        for (variant, weight) in &self.variants {
+            if bucket < *weight as u64 {
+                return variant.clone();
+            }
+            bucket -= *weight as u64;
+        }
+        // Unreachable: the bucket is below the total weight
+        self.variants[0].0.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assignment_is_deterministic_and_case_insensitive() {
+        let experiment = HashedExperiment::ab("tea-promo", "ten-off");
+        let again = HashedExperiment::ab("tea-promo", "ten-off");
+
+        for i in 0..20 {
+            let email = format!("customer{}@example.com", i);
+            assert_eq!(experiment.variant_for(&email), again.variant_for(&email));
+            assert_eq!(
+                experiment.variant_for(&email),
+                experiment.variant_for(&email.to_uppercase())
+            );
+        }
+    }
+
+    #[test]
+    fn test_weights_split_the_population() {
+        let experiment =
+            HashedExperiment::new("price-test", &[("control", 3), ("higher", 1), ("off", 0)]);
+
+        let higher = (0..1000)
+            .filter(|i| experiment.variant_for(&format!("c{}@example.com", i)) == "higher")
+            .count();
+
+        assert_eq!(experiment.variants(), vec!["control", "higher"]);
+        assert!((200..300).contains(&higher), "{} of 1000 in 'higher'", higher);
+    }
+}
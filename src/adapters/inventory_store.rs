@@ -0,0 +1,120 @@
+// SOLID: Inventory repositories (in memory, JSON file)
+//
+// Both implement InventoryRepository with the same contract (LSP):
+// InventoryService can't tell them apart.
+
+use crate::domain::StockLevel;
+use crate::ports::{InventoryError, InventoryRepository};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Stock levels kept in a map by ingredient (tests, demos)
+#[derive(Debug, Default)]
+pub struct MemoryInventoryRepository {
+    levels: BTreeMap<String, StockLevel>,
+}
+
+impl MemoryInventoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with `levels` on the shelves
+    pub fn with_levels(levels: impl IntoIterator<Item = StockLevel>) -> Self {
+        Self {
+            levels: levels
+                .into_iter()
+                .map(|level| (level.ingredient.clone(), level))
+                .collect(),
+        }
+    }
+}
+
+impl InventoryRepository for MemoryInventoryRepository {
+    fn find(&self, ingredient: &str) -> Result<Option<StockLevel>, InventoryError> {
+        Ok(self.levels.get(ingredient).cloned())
+    }
+
+    fn save(&mut self, level: StockLevel) -> Result<(), InventoryError> {
+        self.levels.insert(level.ingredient.clone(), level);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<StockLevel>, InventoryError> {
+        Ok(self.levels.values().cloned().collect())
+    }
+}
+
+/// Stock levels persisted to a JSON file (rewritten on every change)
+pub struct JsonInventoryRepository {
+    file_path: PathBuf,
+    inner: MemoryInventoryRepository,
+}
+
+impl JsonInventoryRepository {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, InventoryError> {
+        let levels: Vec<StockLevel> = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path).map_err(|e| {
+                InventoryError::StorageFailed(format!("Failed to read file: {}", e))
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                InventoryError::StorageFailed(format!("Failed to parse JSON: {}", e))
+            })?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryInventoryRepository::with_levels(levels),
+        })
+    }
+
+    fn write(&self) -> Result<(), InventoryError> {
+        let json = serde_json::to_string_pretty(&self.inner.list()?)
+            .map_err(|e| InventoryError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| InventoryError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl InventoryRepository for JsonInventoryRepository {
+    fn find(&self, ingredient: &str) -> Result<Option<StockLevel>, InventoryError> {
+        self.inner.find(ingredient)
+    }
+
+    fn save(&mut self, level: StockLevel) -> Result<(), InventoryError> {
+        self.inner.save(level)?;
+        self.write()
+    }
+
+    fn list(&self) -> Result<Vec<StockLevel>, InventoryError> {
+        self.inner.list()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_json_repository_persists_one_level_per_ingredient() {
+        let path = std::env::temp_dir().join(format!("inventory-{}.json", Uuid::new_v4()));
+        let mut repository = JsonInventoryRepository::new(path.clone()).unwrap();
+        repository.save(StockLevel::new("milk (100 ml)", 40.0)).unwrap();
+        repository.save(StockLevel::new("espresso shot", 200.0).with_reorder_at(50.0)).unwrap();
+        repository.save(StockLevel::new("milk (100 ml)", 38.0)).unwrap();
+
+        let reloaded = JsonInventoryRepository::new(path.clone()).unwrap();
+        let levels = reloaded.list().unwrap();
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].ingredient, "espresso shot");
+        assert_eq!(levels[0].reorder_at, 50.0);
+        assert_eq!(reloaded.find("milk (100 ml)").unwrap().unwrap().on_hand, 38.0);
+        assert_eq!(reloaded.find("tea bag").unwrap(), None);
+        fs::remove_file(path).unwrap();
+    }
+}
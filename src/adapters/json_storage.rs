@@ -17,9 +17,12 @@
 //    This adapter depends on the OrderRepository trait (abstraction)
 //    It implements the interface defined by the high-level layer
 
-use crate::domain::Order;
-use crate::ports::{OrderRepository, RepositoryError};
+use crate::domain::{contains_pan, Order};
+use crate::adapters::compression::Compression;
+use crate::adapters::memory_storage::first_conflict;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::fs;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -47,7 +50,8 @@ use uuid::Uuid;
 /// - Development (easy to debug - just look at the JSON file)
 pub struct JsonOrderRepository {
     file_path: PathBuf,
-    orders: HashMap<Uuid, Order>,
+    orders: HashMap<Uuid, Arc<Order>>,
+    compression: Compression,
 }
 
 impl JsonOrderRepository {
@@ -55,45 +59,57 @@ impl JsonOrderRepository {
     /// 
     /// The repository will store orders in a JSON file at the given path.
     /// If the file exists, orders are loaded. If not, starts empty.
+    /// A compressed file is read as such and stays compressed.
     pub fn new(file_path: PathBuf) -> Result<Self, RepositoryError> {
-        let orders = if file_path.exists() {
+        let (orders, compression) = if file_path.exists() {
             // Load existing orders
             Self::load_from_file(&file_path)?
         } else {
             // Start with empty repository
-            HashMap::new()
+            (HashMap::new(), Compression::None)
         };
 
-        Ok(Self { file_path, orders })
+        Ok(Self { file_path, orders, compression })
+    }
+
+    /// Compress the file from the next write on (see adapters::compression)
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
     }
 
     /// Load orders from JSON file
-    fn load_from_file(path: &PathBuf) -> Result<HashMap<Uuid, Order>, RepositoryError> {
-        let contents = fs::read_to_string(path).map_err(|e| {
+    fn load_from_file(
+        path: &PathBuf,
+    ) -> Result<(HashMap<Uuid, Arc<Order>>, Compression), RepositoryError> {
+        let contents = fs::read(path).map_err(|e| {
             RepositoryError::LoadFailed(format!("Failed to read file: {}", e))
         })?;
+        let compression = Compression::detect(&contents);
+        let contents = Compression::decompress(contents)?;
 
-        let orders: Vec<Order> = serde_json::from_str(&contents).map_err(|e| {
+        let orders: Vec<Order> = serde_json::from_slice(&contents).map_err(|e| {
             RepositoryError::LoadFailed(format!("Failed to parse JSON: {}", e))
         })?;
 
         let mut map = HashMap::new();
         for order in orders {
-            map.insert(order.id, order);
+            map.insert(order.id, Arc::new(order));
         }
 
-        Ok(map)
+        Ok((map, compression))
     }
 
     /// Save orders to JSON file
     fn save_to_file(&self) -> Result<(), RepositoryError> {
-        let orders: Vec<&Order> = self.orders.values().collect();
+        let orders: Vec<&Order> = self.orders.values().map(|order| order.as_ref()).collect();
 
         let json = serde_json::to_string_pretty(&orders).map_err(|e| {
             RepositoryError::SaveFailed(format!("Failed to serialize orders: {}", e))
         })?;
 
-        fs::write(&self.file_path, json).map_err(|e| {
+        let bytes = self.compression.compress(json.into_bytes())?;
+        fs::write(&self.file_path, bytes).map_err(|e| {
             RepositoryError::SaveFailed(format!("Failed to write file: {}", e))
         })?;
 
@@ -101,6 +117,21 @@ impl JsonOrderRepository {
     }
 }
 
+/// Refuse to write an order with a card number anywhere in it (typed into a
+/// customer name or a note): files must never hold one (PCI DSS)
+pub(crate) fn refuse_card_numbers(order: &Order) -> Result<(), RepositoryError> {
+    let text = serde_json::to_string(order).map_err(|e| {
+        RepositoryError::SaveFailed(format!("Failed to serialize order: {}", e))
+    })?;
+    if contains_pan(&text) {
+        return Err(RepositoryError::SaveFailed(format!(
+            "Order {} holds a card number; refusing to write it",
+            order.id
+        )));
+    }
+    Ok(())
+}
+
 /// SOLID PRINCIPLE: Liskov Substitution Principle (LSP)
 /// 
 /// This implementation MUST behave exactly like MemoryOrderRepository
@@ -131,8 +162,9 @@ impl OrderRepository for JsonOrderRepository {
                 order.id
             )));
         }
+        refuse_card_numbers(order)?;
 
-        self.orders.insert(order.id, order.clone());
+        self.orders.insert(order.id, Arc::new(order.clone()));
 
         // Persist to file after every save
         // (In a real system, you might batch writes for performance)
@@ -143,7 +175,7 @@ impl OrderRepository for JsonOrderRepository {
 
     fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
         // LSP: Exact same behavior as MemoryOrderRepository
-        Ok(self.orders.get(&id).cloned())
+        Ok(self.orders.get(&id).map(|order| Order::clone(order)))
     }
 
     fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
@@ -152,7 +184,7 @@ impl OrderRepository for JsonOrderRepository {
             .orders
             .values()
             .filter(|order| order.customer.email == email)
-            .cloned()
+            .map(|order| Order::clone(order))
             .collect();
 
         Ok(orders)
@@ -160,7 +192,7 @@ impl OrderRepository for JsonOrderRepository {
 
     fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
         // LSP: Exact same behavior as MemoryOrderRepository
-        Ok(self.orders.values().cloned().collect())
+        Ok(self.orders.values().map(|order| Order::clone(order)).collect())
     }
 
     fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
@@ -171,8 +203,9 @@ impl OrderRepository for JsonOrderRepository {
                 order.id
             )));
         }
+        refuse_card_numbers(order)?;
 
-        self.orders.insert(order.id, order.clone());
+        self.orders.insert(order.id, Arc::new(order.clone()));
 
         // Persist to file
         self.save_to_file()?;
@@ -191,6 +224,51 @@ impl OrderRepository for JsonOrderRepository {
 
         Ok(existed)
     }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        if let Some(id) = first_conflict(orders, |id| self.orders.contains_key(&id)) {
+            return Err(RepositoryError::AlreadyExists(format!("Order {} already exists", id)));
+        }
+        orders.iter().try_for_each(refuse_card_numbers)?;
+
+        for order in orders {
+            self.orders.insert(order.id, Arc::new(order.clone()));
+        }
+
+        // One file write for the whole batch; undo the inserts if it fails
+        if let Err(e) = self.save_to_file() {
+            for order in orders {
+                self.orders.remove(&order.id);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        // LSP: Exact same behavior as MemoryOrderRepository
+        Ok(self.orders.values().cloned().collect())
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self
+            .orders
+            .values()
+            .filter(|order| order.customer.email == email)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Every write rewrites the whole file, so a batch costs the same as one order
+impl RepositoryCapabilities for JsonOrderRepository {
+    fn supports_transactions(&self) -> bool {
+        true
+    }
 }
 
 // ============================================================================
@@ -240,7 +318,7 @@ impl OrderRepository for JsonOrderRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Customer, OrderItem};
+    use crate::domain::{Customer, Money, OrderItem};
     use std::env;
 
     fn make_test_order() -> Order {
@@ -253,8 +331,11 @@ mod tests {
         let items = vec![OrderItem {
             beverage_name: "Coffee".to_string(),
             beverage_description: "Medium Coffee".to_string(),
-            price: 3.50,
+            price: Money::usd(3.50),
             quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
         }];
 
         Order::new(customer, items)
@@ -314,4 +395,36 @@ mod tests {
         // Cleanup
         let _ = fs::remove_file(temp_file);
     }
+
+    #[test]
+    fn test_card_numbers_are_never_written() {
+        let temp_file = env::temp_dir().join(format!("test_pan-{}.json", Uuid::new_v4()));
+        let mut repo = JsonOrderRepository::new(temp_file.clone()).unwrap();
+
+        let mut order = make_test_order();
+        order.customer.name = "Ada 4111 1111 1111 1111".to_string();
+        assert!(matches!(repo.save(&order), Err(RepositoryError::SaveFailed(_))));
+        assert!(repo.list_all().unwrap().is_empty());
+        assert!(!temp_file.exists());
+
+        let _ = fs::remove_file(temp_file);
+    }
+
+    #[test]
+    fn test_files_with_dollar_amounts_still_load() {
+        let temp_file = env::temp_dir().join(format!("test_dollars-{}.json", Uuid::new_v4()));
+        let order = make_test_order();
+        // Files written before Money held bare dollar amounts
+        let mut json = serde_json::to_value(&order).unwrap();
+        json["total_price"] = serde_json::json!(3.5);
+        json["items"][0]["price"] = serde_json::json!(3.5);
+        fs::write(&temp_file, serde_json::to_vec(&[json]).unwrap()).unwrap();
+
+        let repo = JsonOrderRepository::new(temp_file.clone()).unwrap();
+        let loaded = repo.find_by_id(order.id).unwrap().unwrap();
+        assert_eq!(loaded.total_price, Money::usd(3.50));
+        assert_eq!(loaded.items[0].price, Money::usd(3.50));
+
+        let _ = fs::remove_file(temp_file);
+    }
 }
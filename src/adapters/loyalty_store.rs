@@ -0,0 +1,156 @@
+// SOLID: Loyalty account repositories (in memory, JSON file, shared handle)
+//
+// - MemoryLoyaltyRepository: for tests, demos and a single process
+// - JsonLoyaltyRepository: loyalty.json, rewritten on every change
+// - SharedLoyaltyRepository: a cloneable handle over either, so the
+//   OrderService's LoyaltyService and the register's PointsPayment see one
+//   balance
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same LoyaltyRepository contract for all three. Emails are compared
+// case-insensitively, like store credit wallets.
+
+use crate::domain::LoyaltyAccount;
+use crate::ports::{LoyaltyError, LoyaltyRepository};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Accounts kept in a map by lowercased email
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLoyaltyRepository {
+    accounts: BTreeMap<String, LoyaltyAccount>,
+}
+
+impl MemoryLoyaltyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LoyaltyRepository for MemoryLoyaltyRepository {
+    fn find(&self, customer_email: &str) -> Result<Option<LoyaltyAccount>, LoyaltyError> {
+        Ok(self.accounts.get(&customer_email.to_lowercase()).cloned())
+    }
+
+    fn save(&mut self, mut account: LoyaltyAccount) -> Result<(), LoyaltyError> {
+        account.customer_email = account.customer_email.to_lowercase();
+        self.accounts.insert(account.customer_email.clone(), account);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<LoyaltyAccount>, LoyaltyError> {
+        Ok(self.accounts.values().cloned().collect())
+    }
+}
+
+/// Accounts persisted to a JSON file (rewritten on every change)
+pub struct JsonLoyaltyRepository {
+    file_path: PathBuf,
+    inner: MemoryLoyaltyRepository,
+}
+
+impl JsonLoyaltyRepository {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, LoyaltyError> {
+        let mut inner = MemoryLoyaltyRepository::new();
+        if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| LoyaltyError::StorageFailed(format!("Failed to read file: {}", e)))?;
+            let accounts: Vec<LoyaltyAccount> = serde_json::from_str(&contents)
+                .map_err(|e| LoyaltyError::StorageFailed(format!("Failed to parse JSON: {}", e)))?;
+            for account in accounts {
+                inner.save(account)?;
+            }
+        }
+        Ok(Self { file_path, inner })
+    }
+
+    fn write(&self) -> Result<(), LoyaltyError> {
+        let json = serde_json::to_string_pretty(&self.inner.list()?)
+            .map_err(|e| LoyaltyError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| LoyaltyError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl LoyaltyRepository for JsonLoyaltyRepository {
+    fn find(&self, customer_email: &str) -> Result<Option<LoyaltyAccount>, LoyaltyError> {
+        self.inner.find(customer_email)
+    }
+
+    fn save(&mut self, account: LoyaltyAccount) -> Result<(), LoyaltyError> {
+        self.inner.save(account)?;
+        self.write()
+    }
+
+    fn list(&self) -> Result<Vec<LoyaltyAccount>, LoyaltyError> {
+        self.inner.list()
+    }
+}
+
+/// One set of accounts shared by several owners (clones see the same points)
+pub struct SharedLoyaltyRepository<L: LoyaltyRepository> {
+    inner: Arc<Mutex<L>>,
+}
+
+impl<L: LoyaltyRepository> SharedLoyaltyRepository<L> {
+    pub fn new(inner: L) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, L>, LoyaltyError> {
+        self.inner
+            .lock()
+            .map_err(|e| LoyaltyError::StorageFailed(format!("Loyalty lock poisoned: {}", e)))
+    }
+}
+
+// Manual impl: cloning the handle must not require L: Clone
+impl<L: LoyaltyRepository> Clone for SharedLoyaltyRepository<L> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<L: LoyaltyRepository> LoyaltyRepository for SharedLoyaltyRepository<L> {
+    fn find(&self, customer_email: &str) -> Result<Option<LoyaltyAccount>, LoyaltyError> {
+        self.lock()?.find(customer_email)
+    }
+
+    fn save(&mut self, account: LoyaltyAccount) -> Result<(), LoyaltyError> {
+        self.lock()?.save(account)
+    }
+
+    fn list(&self) -> Result<Vec<LoyaltyAccount>, LoyaltyError> {
+        self.lock()?.list()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_json_repository_persists_one_account_per_email() {
+        let path = std::env::temp_dir().join(format!("loyalty-{}.json", Uuid::new_v4()));
+        let mut repository = JsonLoyaltyRepository::new(path.clone()).unwrap();
+        let mut ada = LoyaltyAccount::new("Ada@Example.com");
+        ada.earn(35);
+        repository.save(ada.clone()).unwrap();
+        ada.spend(20);
+        repository.save(ada).unwrap();
+
+        let reloaded = JsonLoyaltyRepository::new(path.clone()).unwrap();
+        let account = reloaded.find("ADA@example.com").unwrap().unwrap();
+        assert_eq!((account.points, account.orders, account.lifetime_points), (15, 1, 35));
+        assert_eq!(reloaded.list().unwrap().len(), 1);
+        fs::remove_file(path).unwrap();
+    }
+}
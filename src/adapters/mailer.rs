@@ -0,0 +1,230 @@
+// SOLID: Mailers (how a message actually leaves)
+//
+// - RecordingMailer: keeps messages in memory (tests, demos, dry runs)
+// - SmtpMailer: plain SMTP to a relay (the shop's Postfix, a provider's
+//   submission port behind a local relay, a mail sink while testing)
+//
+// SmtpMailer speaks the protocol directly over TCP, like RedisLock does RESP:
+// EHLO, MAIL FROM, RCPT TO, DATA, QUIT. No TLS or login: those belong to the
+// relay, which is the usual way to send from a server anyway.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Both accept the same EmailMessage and fail the same ways.
+
+use crate::ports::{EmailMessage, MailError, Mailer};
+use chrono::Utc;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Records every message and accepts it
+///
+/// Clones share the same log, so a test can keep a handle after
+/// moving the mailer into a job.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingMailer {
+    sent: Arc<Mutex<Vec<EmailMessage>>>,
+}
+
+impl RecordingMailer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sent(&self) -> Vec<EmailMessage> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl Mailer for RecordingMailer {
+    fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+        self.sent.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+/// Sends through an SMTP relay
+pub struct SmtpMailer {
+    address: String,
+    from: String,
+    timeout: Duration,
+}
+
+impl SmtpMailer {
+    /// `address` is `host:port` (25, or 2525/1025 for local sinks); `from` the sender
+    pub fn new(address: &str, from: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            from: from.to_string(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// The whole message: headers, then a text and an HTML part
+    fn compose(&self, message: &EmailMessage) -> String {
+        let boundary = format!("coffee-shop-{}", Uuid::new_v4().simple());
+        let host = self.from.rsplit('@').next().unwrap_or("localhost");
+        let mut data = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@{}>\r\n\
+             MIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+            self.from,
+            message.to.join(", "),
+            message.subject.replace(['\r', '\n'], " "),
+            Utc::now().to_rfc2822(),
+            Uuid::new_v4(),
+            host,
+            boundary
+        );
+        for (mime, body) in [("text/plain", &message.text), ("text/html", &message.html)] {
+            data.push_str(&format!(
+                "--{}\r\nContent-Type: {}; charset=utf-8\r\n\
+                 Content-Transfer-Encoding: 8bit\r\n\r\n",
+                boundary, mime
+            ));
+            data.push_str(&dot_stuffed(body));
+            data.push_str("\r\n");
+        }
+        data.push_str(&format!("--{}--\r\n", boundary));
+        data
+    }
+}
+
+/// CRLF line endings, and a leading "." doubled so it can't end the DATA early
+fn dot_stuffed(body: &str) -> String {
+    body.lines()
+        .map(|line| match line.strip_prefix('.') {
+            Some(_) => format!(".{}\r\n", line),
+            None => format!("{}\r\n", line),
+        })
+        .collect()
+}
+
+/// One SMTP conversation: send a line, check the reply code
+struct Session {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Session {
+    fn open(address: &str, timeout: Duration) -> Result<Self, MailError> {
+        let stream = TcpStream::connect(address).map_err(unavailable)?;
+        stream.set_read_timeout(Some(timeout)).map_err(unavailable)?;
+        stream.set_write_timeout(Some(timeout)).map_err(unavailable)?;
+        let reader = BufReader::new(stream.try_clone().map_err(unavailable)?);
+        let mut session = Self { stream, reader };
+        session.expect(220)?;
+        Ok(session)
+    }
+
+    /// Read a (possibly multi-line) reply: an error unless its code is `code`
+    fn expect(&mut self, code: u16) -> Result<(), MailError> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).map_err(unavailable)? == 0 {
+                return Err(MailError::Unavailable("Connection closed".to_string()));
+            }
+            // "250-..." continues, "250 ..." is the last line
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+            return match line.get(..3).and_then(|c| c.parse::<u16>().ok()) {
+                Some(got) if got == code => Ok(()),
+                _ => Err(MailError::Rejected(line.trim_end().to_string())),
+            };
+        }
+    }
+
+    fn command(&mut self, line: &str, code: u16) -> Result<(), MailError> {
+        self.stream
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .map_err(unavailable)?;
+        self.expect(code)
+    }
+}
+
+fn unavailable(e: std::io::Error) -> MailError {
+    MailError::Unavailable(e.to_string())
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+        let mut session = Session::open(&self.address, self.timeout)?;
+        session.command("EHLO coffee-shop-solid", 250)?;
+        session.command(&format!("MAIL FROM:<{}>", self.from), 250)?;
+        for to in &message.to {
+            session.command(&format!("RCPT TO:<{}>", to), 250)?;
+        }
+        session.command("DATA", 354)?;
+        session.command(&format!("{}.", self.compose(message)), 250)?;
+        // The message is accepted at this point; a failed goodbye changes nothing
+        let _ = session.command("QUIT", 221);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_speaks_smtp_to_the_relay() {
+        // A one-shot stand-in for a relay: scripted replies, records what it got
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut received = String::new();
+            stream.write_all(b"220 sink ready\r\n").unwrap();
+            let mut in_data = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                received.push_str(&line);
+                let reply: &[u8] = match line.trim_end() {
+                    "." if in_data => {
+                        in_data = false;
+                        b"250 queued\r\n"
+                    }
+                    _ if in_data => continue,
+                    "EHLO coffee-shop-solid" => b"250-sink\r\n250 8BITMIME\r\n",
+                    "DATA" => {
+                        in_data = true;
+                        b"354 go ahead\r\n"
+                    }
+                    "QUIT" => b"221 bye\r\n",
+                    _ => b"250 ok\r\n",
+                };
+                stream.write_all(reply).unwrap();
+            }
+            received
+        });
+
+        let mailer = SmtpMailer::new(&address, "shop@example.com");
+        let message = EmailMessage {
+            to: vec!["owner@example.com".to_string()],
+            subject: "Daily digest".to_string(),
+            html: "<p>Revenue</p>".to_string(),
+            text: "Revenue\n.hidden line".to_string(),
+        };
+        mailer.send(&message).unwrap();
+
+        let received = server.join().unwrap();
+        assert!(received.starts_with("EHLO coffee-shop-solid\r\nMAIL FROM:<shop@example.com>\r\n"));
+        assert!(received.contains("RCPT TO:<owner@example.com>\r\nDATA\r\n"));
+        assert!(received.contains("Subject: Daily digest\r\n"));
+        assert!(received.contains("\r\n..hidden line\r\n"));
+        assert!(received.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(received.ends_with(".\r\nQUIT\r\n"));
+        assert!(matches!(
+            SmtpMailer::new("127.0.0.1:1", "shop@example.com").send(&message),
+            Err(MailError::Unavailable(_))
+        ));
+    }
+}
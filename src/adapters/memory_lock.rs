@@ -0,0 +1,107 @@
+// SOLID: MemoryLock - DistributedLock for instances in one process
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same lease contract as FileLock and RedisLock, kept in a map. Clones share
+// the map, so two schedulers holding clones behave like two servers sharing
+// a Redis: that is how the singleton behavior is tested without either.
+
+use crate::adapters::clock::SystemClock;
+use crate::ports::{Clock, DistributedLock, LockError};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Lock name -> (holder, lease end)
+type Leases = HashMap<String, (String, DateTime<Utc>)>;
+
+/// In-process leases; clone it to hand the same locks to another "instance"
+#[derive(Clone)]
+pub struct MemoryLock {
+    leases: Arc<Mutex<Leases>>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl MemoryLock {
+    pub fn new() -> Self {
+        Self {
+            leases: Arc::new(Mutex::new(HashMap::new())),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Expire leases by `clock` instead of the wall clock (tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Who holds `name` right now, if anyone
+    pub fn holder(&self, name: &str) -> Option<String> {
+        let now = self.clock.now();
+        self.leases
+            .lock()
+            .unwrap()
+            .get(name)
+            .filter(|(_, until)| *until > now)
+            .map(|(holder, _)| holder.clone())
+    }
+}
+
+impl Default for MemoryLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DistributedLock for MemoryLock {
+    fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool, LockError> {
+        let now = self.clock.now();
+        let mut leases = self.leases.lock().unwrap();
+        match leases.get(name) {
+            Some((current, until)) if current != holder && *until > now => Ok(false),
+            _ => {
+                leases.insert(name.to_string(), (holder.to_string(), now + ttl));
+                Ok(true)
+            }
+        }
+    }
+
+    fn release(&self, name: &str, holder: &str) -> Result<bool, LockError> {
+        let mut leases = self.leases.lock().unwrap();
+        if leases.get(name).is_some_and(|(current, _)| current == holder) {
+            leases.remove(name);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::FixedClock;
+
+    #[test]
+    fn test_lease_is_exclusive_renewable_and_expires() {
+        let clock = Arc::new(FixedClock::new(Utc::now()));
+        let lock = MemoryLock::new().with_clock(clock.clone());
+        let other = lock.clone();
+        let ttl = Duration::minutes(1);
+
+        assert!(lock.try_acquire("close", "a", ttl).unwrap());
+        assert!(!other.try_acquire("close", "b", ttl).unwrap());
+        assert!(!other.release("close", "b").unwrap());
+
+        // Renewing moves the end of the lease
+        clock.advance(Duration::seconds(45));
+        assert!(lock.try_acquire("close", "a", ttl).unwrap());
+        clock.advance(Duration::seconds(45));
+        assert_eq!(other.holder("close").as_deref(), Some("a"));
+
+        // "a" crashed: its lease runs out
+        clock.advance(Duration::seconds(30));
+        assert!(other.try_acquire("close", "b", ttl).unwrap());
+        assert!(other.release("close", "b").unwrap());
+        assert_eq!(lock.holder("close"), None);
+    }
+}
@@ -0,0 +1,73 @@
+// SOLID: InMemoryMetrics - keep every observation in memory
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    Any MetricsRecorder backend can replace it; OrderService can't tell.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    It stores raw samples. Percentiles are computed by whoever reads them
+//    (LatencySummary::from_samples).
+//
+// Clones share the same samples: keep one handle, give the other to the service.
+
+use crate::ports::MetricsRecorder;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Observations per metric, in arrival order
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryMetrics {
+    samples: Arc<Mutex<BTreeMap<&'static str, Vec<f64>>>>,
+}
+
+impl InMemoryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything recorded for `metric` (empty if never observed)
+    pub fn samples(&self, metric: &str) -> Vec<f64> {
+        self.samples
+            .lock()
+            .unwrap()
+            .get(metric)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Names of the metrics observed so far, sorted
+    pub fn metric_names(&self) -> Vec<&'static str> {
+        self.samples.lock().unwrap().keys().copied().collect()
+    }
+}
+
+impl MetricsRecorder for InMemoryMetrics {
+    fn observe(&self, metric: &'static str, value: f64) {
+        self.samples
+            .lock()
+            .unwrap()
+            .entry(metric)
+            .or_default()
+            .push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::{ORDER_PLACEMENT_SECONDS, ORDER_PREP_SECONDS};
+
+    #[test]
+    fn test_clones_share_samples() {
+        let metrics = InMemoryMetrics::new();
+        let handle = metrics.clone();
+
+        metrics.observe(ORDER_PREP_SECONDS, 90.0);
+        metrics.observe(ORDER_PREP_SECONDS, 120.0);
+
+        assert_eq!(handle.samples(ORDER_PREP_SECONDS), vec![90.0, 120.0]);
+        assert!(handle.samples(ORDER_PLACEMENT_SECONDS).is_empty());
+        assert_eq!(handle.metric_names(), vec![ORDER_PREP_SECONDS]);
+    }
+}
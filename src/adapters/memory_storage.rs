@@ -17,8 +17,9 @@
 //    The system was open for this extension
 
 use crate::domain::Order;
-use crate::ports::{OrderRepository, RepositoryError};
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// In-memory order repository
@@ -39,7 +40,7 @@ use uuid::Uuid;
 /// - Development (fast, no setup required)
 /// - Demos (no persistence between runs)
 pub struct MemoryOrderRepository {
-    orders: HashMap<Uuid, Order>,
+    orders: HashMap<Uuid, Arc<Order>>,
 }
 
 impl MemoryOrderRepository {
@@ -94,7 +95,7 @@ impl OrderRepository for MemoryOrderRepository {
         }
 
         // Save the order
-        self.orders.insert(order.id, order.clone());
+        self.orders.insert(order.id, Arc::new(order.clone()));
 
         // LSP CONTRACT: Return Ok(()) on success
         Ok(())
@@ -102,7 +103,7 @@ impl OrderRepository for MemoryOrderRepository {
 
     fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
         // LSP CONTRACT: Return Ok(Some(order)) if found, Ok(None) if not found
-        Ok(self.orders.get(&id).cloned())
+        Ok(self.orders.get(&id).map(|order| Order::clone(order)))
     }
 
     fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
@@ -111,7 +112,7 @@ impl OrderRepository for MemoryOrderRepository {
             .orders
             .values()
             .filter(|order| order.customer.email == email)
-            .cloned()
+            .map(|order| Order::clone(order))
             .collect();
 
         Ok(orders)
@@ -119,7 +120,7 @@ impl OrderRepository for MemoryOrderRepository {
 
     fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
         // LSP CONTRACT: Return all orders (can be empty vec)
-        Ok(self.orders.values().cloned().collect())
+        Ok(self.orders.values().map(|order| Order::clone(order)).collect())
     }
 
     fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
@@ -132,7 +133,7 @@ impl OrderRepository for MemoryOrderRepository {
         }
 
         // Update the order
-        self.orders.insert(order.id, order.clone());
+        self.orders.insert(order.id, Arc::new(order.clone()));
 
         // LSP CONTRACT: Return Ok(()) on success
         Ok(())
@@ -142,6 +143,50 @@ impl OrderRepository for MemoryOrderRepository {
         // LSP CONTRACT: Return true if existed and deleted, false if didn't exist
         Ok(self.orders.remove(&id).is_some())
     }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        // All-or-nothing: check every ID before inserting any
+        if let Some(id) = first_conflict(orders, |id| self.orders.contains_key(&id)) {
+            return Err(RepositoryError::AlreadyExists(format!("Order {} already exists", id)));
+        }
+
+        for order in orders {
+            self.orders.insert(order.id, Arc::new(order.clone()));
+        }
+        Ok(())
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        // Handles to the stored orders: no Order is copied
+        Ok(self.orders.values().cloned().collect())
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self
+            .orders
+            .values()
+            .filter(|order| order.customer.email == email)
+            .cloned()
+            .collect())
+    }
+}
+
+impl RepositoryCapabilities for MemoryOrderRepository {
+    fn supports_transactions(&self) -> bool {
+        true
+    }
+}
+
+/// First ID in `orders` that already exists or appears twice
+pub(crate) fn first_conflict(orders: &[Order], exists: impl Fn(Uuid) -> bool) -> Option<Uuid> {
+    let mut seen = std::collections::HashSet::new();
+    orders
+        .iter()
+        .map(|order| order.id)
+        .find(|&id| exists(id) || !seen.insert(id))
 }
 
 // ============================================================================
@@ -174,7 +219,7 @@ impl OrderRepository for MemoryOrderRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Customer, OrderItem, OrderStatus};
+    use crate::domain::{Customer, Money, OrderItem, OrderStatus};
 
     fn make_test_order() -> Order {
         let customer = Customer::new(
@@ -186,8 +231,11 @@ mod tests {
         let items = vec![OrderItem {
             beverage_name: "Coffee".to_string(),
             beverage_description: "Medium Coffee".to_string(),
-            price: 3.50,
+            price: Money::usd(3.50),
             quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
         }];
 
         Order::new(customer, items)
@@ -250,7 +298,7 @@ mod tests {
         repo.save(&order).unwrap();
 
         // Update status
-        order.mark_as_paid("PAY-123".to_string());
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
         repo.update(&order).unwrap();
 
         // Verify
@@ -287,4 +335,35 @@ mod tests {
         let all = repo.list_all().unwrap();
         assert_eq!(all.len(), 2);
     }
+
+    #[test]
+    fn test_save_batch_is_all_or_nothing() {
+        let mut repo = MemoryOrderRepository::new();
+        let existing = make_test_order();
+        repo.save(&existing).unwrap();
+
+        let batch = vec![make_test_order(), existing.clone()];
+        assert!(matches!(repo.save_batch(&batch), Err(RepositoryError::AlreadyExists(_))));
+        assert_eq!(repo.count(), 1);
+
+        repo.save_batch(&[make_test_order(), make_test_order()]).unwrap();
+        assert_eq!(repo.count(), 3);
+    }
+
+    #[test]
+    fn test_shared_listing_copies_nothing() {
+        let mut repo = MemoryOrderRepository::new();
+        let mut order = make_test_order();
+        repo.save(&order).unwrap();
+
+        let first = repo.list_all_shared().unwrap();
+        let again = repo.find_by_customer_email_shared(&order.customer.email).unwrap();
+        assert!(Arc::ptr_eq(&first[0], &again[0]));
+
+        // Handles already given out keep the order as it was
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        repo.update(&order).unwrap();
+        assert!(first[0].payment_id.is_none());
+        assert!(repo.list_all_shared().unwrap()[0].payment_id.is_some());
+    }
 }
@@ -23,9 +23,36 @@
 //    A notifier doesn't implement storage or payment interfaces.
 // 
 // STRUCTURE:
-// - Storage adapters: MemoryOrderRepository, JsonOrderRepository
-// - Payment adapters: CashPayment, CreditCardPayment
-// - Notification adapters: ConsoleNotifier
+// - Storage adapters: MemoryOrderRepository, JsonOrderRepository, BinaryOrderRepository,
+//   SharedOrderRepository, Compression (gzip/zstd, features `gzip` and `zstd`),
+//   SqliteOrderRepository (feature `sqlite`)
+// - Payment adapters: CashPayment (SeededCashPayment for reproducible IDs), CreditCardPayment,
+//   CardPresentPayment (terminal),
+//   WalletPayment (store credit first, then any of the others),
+//   PointsPayment (loyalty points first, the same way),
+//   RedirectPayment (PayPal-style: approved on the provider's page, then confirmed),
+//   PaymentRouter (picks one of several by amount, preference, availability; fails over)
+// - Notification adapters: ConsoleNotifier, ChatNotifier (Slack/Discord), FileNotifier,
+//   CompositeNotifier (several channels, best-effort or fail-fast), AnnouncerNotifier (TTS),
+//   EmailNotifier (SMTP through lettre, feature `email`)
+// - Clock adapters: SystemClock, FixedClock
+// - Randomness adapters: SystemRandomness, SeededRandomness
+// - Lock adapters: MemoryLock, FileLock (shared directory), RedisLock
+// - Queue adapters: channel_order_queue, bounded_order_queue (in-process)
+// - Metrics adapters: InMemoryMetrics
+// - Event publishers: ConsoleEventPublisher (JSON lines), InMemoryEventPublisher
+// - Mail adapters: SmtpMailer (plain SMTP to a relay), RecordingMailer,
+//   MemorySentMailLog, FileSentMailLog
+// - Experiment adapters: HashedExperiment
+// - Coupon adapters: MemoryCouponStore, JsonCouponStore
+// - Fiscal numbering adapters: MemoryFiscalNumbers, FileFiscalNumbers
+// - Feedback adapters: MemoryFeedbackRepository, JsonFeedbackRepository
+// - Inventory adapters: MemoryInventoryRepository, JsonInventoryRepository
+// - Loyalty adapters: MemoryLoyaltyRepository, JsonLoyaltyRepository, SharedLoyaltyRepository
+// - Store credit adapters: MemoryStoreCredit, JsonStoreCredit, SharedStoreCredit
+// - Webhook adapters: MemoryWebhookStore, JsonWebhookStore,
+//   RecordingWebhookTransport, HttpWebhookTransport (feature `http-client`)
+// - Payload serializers: JsonPayload, XmlPayload, CloudEventsPayload
 // 
 // ADDING NEW ADAPTERS:
 // Want to add PostgreSQL storage? Create postgres_storage.rs and implement OrderRepository.
@@ -40,15 +67,102 @@
 // 
 // That's SOLID in action!
 
+pub mod announcer_notifier;
+pub mod binary_storage;
+pub mod campaign_outbox;
+pub mod card_present_payment;
 pub mod cash_payment;
+pub mod channel_queue;
+pub mod chat_notifier;
+pub mod clock;
+pub mod compression;
+pub mod composite_notifier;
 pub mod console_notifier;
+pub mod coupon_store;
 pub mod credit_card_payment;
+#[cfg(feature = "email")]
+pub mod email_notifier;
+pub mod event_publishers;
+pub mod feedback_store;
+pub mod file_lock;
+pub mod fiscal_numbers;
+pub mod file_notifier;
+pub mod hashed_experiment;
+pub mod inventory_store;
 pub mod json_storage;
+pub mod loyalty_store;
+pub mod mailer;
+pub mod memory_lock;
+pub mod memory_metrics;
 pub mod memory_storage;
+pub mod notification_outbox;
+pub mod payload_serializers;
+pub mod payment_router;
+pub mod points_payment;
+pub mod randomness;
+pub mod redirect_payment;
+pub mod redis_lock;
+pub mod sent_mail_log;
+pub mod shared_storage;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_storage;
+pub mod store_credit;
+#[cfg(feature = "stripe")]
+pub mod stripe_payment;
+pub mod wallet_payment;
+pub mod webhook_store;
+pub mod webhook_transport;
 
 // Re-export for convenience
-pub use cash_payment::CashPayment;
-pub use console_notifier::ConsoleNotifier;
+pub use announcer_notifier::AnnouncerNotifier;
+pub use binary_storage::{BinaryOrderRepository, BINARY_FORMAT_VERSION};
+pub use campaign_outbox::{JsonCampaignOutbox, MemoryCampaignOutbox};
+pub use card_present_payment::CardPresentPayment;
+pub use cash_payment::{CashPayment, SeededCashPayment};
+pub use chat_notifier::{ChatEvent, ChatNotifier, ChatPlatform};
+pub use channel_queue::{
+    bounded_order_queue, channel_order_queue, ChannelQueueConsumer, ChannelQueueProducer,
+    FullQueuePolicy,
+};
+pub use clock::{FixedClock, SystemClock};
+pub use compression::Compression;
+pub use composite_notifier::{CompositeNotifier, FailurePolicy};
+pub use console_notifier::{ConsoleNotifier, Verbosity};
+pub use coupon_store::{JsonCouponStore, MemoryCouponStore};
 pub use credit_card_payment::CreditCardPayment;
+#[cfg(feature = "email")]
+pub use email_notifier::{EmailEvent, EmailNotifier, EmailTemplate, SentEmail, SmtpSettings};
+pub use event_publishers::{ConsoleEventPublisher, InMemoryEventPublisher};
+pub use feedback_store::{JsonFeedbackRepository, MemoryFeedbackRepository};
+pub use file_lock::FileLock;
+pub use fiscal_numbers::{FileFiscalNumbers, MemoryFiscalNumbers};
+pub use file_notifier::{read_notification_records, FileNotifier, NotificationRecord};
+pub use hashed_experiment::HashedExperiment;
+pub use inventory_store::{JsonInventoryRepository, MemoryInventoryRepository};
 pub use json_storage::JsonOrderRepository;
+pub use loyalty_store::{JsonLoyaltyRepository, MemoryLoyaltyRepository, SharedLoyaltyRepository};
+pub use mailer::{RecordingMailer, SmtpMailer};
+pub use memory_lock::MemoryLock;
+pub use memory_metrics::InMemoryMetrics;
 pub use memory_storage::MemoryOrderRepository;
+pub use notification_outbox::{
+    JsonNotificationOutbox, MemoryNotificationOutbox, SharedNotificationOutbox,
+};
+pub use payload_serializers::{CloudEventsPayload, JsonPayload, XmlPayload};
+pub use payment_router::{PaymentRouter, Route, RouteCount};
+pub use points_payment::PointsPayment;
+pub use randomness::{SeededRandomness, SystemRandomness};
+pub use redirect_payment::RedirectPayment;
+pub use redis_lock::RedisLock;
+pub use sent_mail_log::{FileSentMailLog, MemorySentMailLog};
+pub use shared_storage::SharedOrderRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_storage::SqliteOrderRepository;
+pub use store_credit::{JsonStoreCredit, MemoryStoreCredit, SharedStoreCredit};
+#[cfg(feature = "stripe")]
+pub use stripe_payment::StripePayment;
+pub use wallet_payment::WalletPayment;
+pub use webhook_store::{JsonWebhookStore, MemoryWebhookStore};
+pub use webhook_transport::{RecordedRequest, RecordingWebhookTransport};
+#[cfg(feature = "http-client")]
+pub use webhook_transport::HttpWebhookTransport;
@@ -0,0 +1,191 @@
+// SOLID: Notification outboxes (where quiet hours park customer notices)
+//
+// - MemoryNotificationOutbox: for one process (tests, demos)
+// - JsonNotificationOutbox: a JSON file, so notices deferred overnight
+//   survive a restart
+// - SharedNotificationOutbox: a cloneable handle over either, so the
+//   QuietHoursNotifier inside OrderService and the job releasing the notices
+//   see the same outbox
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same NotificationOutbox contract for all three.
+
+use crate::domain::DeferredNotification;
+use crate::ports::{NotificationOutbox, OutboxError};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Notifications kept in a Vec
+#[derive(Debug, Default)]
+pub struct MemoryNotificationOutbox {
+    notifications: Vec<DeferredNotification>,
+}
+
+impl MemoryNotificationOutbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NotificationOutbox for MemoryNotificationOutbox {
+    fn defer(&mut self, notification: DeferredNotification) -> Result<(), OutboxError> {
+        self.notifications.push(notification);
+        Ok(())
+    }
+
+    fn due(&self, now: DateTime<Utc>) -> Result<Vec<DeferredNotification>, OutboxError> {
+        let mut due: Vec<DeferredNotification> =
+            self.notifications.iter().filter(|n| n.is_due(now)).cloned().collect();
+        due.sort_by_key(|n| n.deliver_at);
+        Ok(due)
+    }
+
+    fn remove(&mut self, id: Uuid) -> Result<bool, OutboxError> {
+        let before = self.notifications.len();
+        self.notifications.retain(|n| n.id != id);
+        Ok(self.notifications.len() < before)
+    }
+
+    fn pending(&self) -> Result<Vec<DeferredNotification>, OutboxError> {
+        Ok(self.notifications.clone())
+    }
+}
+
+/// Notifications persisted to a JSON file (rewritten on every change)
+pub struct JsonNotificationOutbox {
+    file_path: PathBuf,
+    inner: MemoryNotificationOutbox,
+}
+
+impl JsonNotificationOutbox {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, OutboxError> {
+        let notifications = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| OutboxError::StorageFailed(format!("Failed to read file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| OutboxError::StorageFailed(format!("Failed to parse JSON: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryNotificationOutbox { notifications },
+        })
+    }
+
+    /// Temporary file then rename, like the campaign outbox
+    fn write(&self) -> Result<(), OutboxError> {
+        let json = serde_json::to_string_pretty(&self.inner.notifications)
+            .map_err(|e| OutboxError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, &self.file_path))
+            .map_err(|e| OutboxError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl NotificationOutbox for JsonNotificationOutbox {
+    fn defer(&mut self, notification: DeferredNotification) -> Result<(), OutboxError> {
+        self.inner.defer(notification)?;
+        self.write()
+    }
+
+    fn due(&self, now: DateTime<Utc>) -> Result<Vec<DeferredNotification>, OutboxError> {
+        self.inner.due(now)
+    }
+
+    fn remove(&mut self, id: Uuid) -> Result<bool, OutboxError> {
+        let removed = self.inner.remove(id)?;
+        if removed {
+            self.write()?;
+        }
+        Ok(removed)
+    }
+
+    fn pending(&self) -> Result<Vec<DeferredNotification>, OutboxError> {
+        self.inner.pending()
+    }
+}
+
+/// Thread-safe, cloneable wrapper around any NotificationOutbox
+pub struct SharedNotificationOutbox<O: NotificationOutbox> {
+    inner: Arc<Mutex<O>>,
+}
+
+impl<O: NotificationOutbox> SharedNotificationOutbox<O> {
+    pub fn new(inner: O) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, O>, OutboxError> {
+        self.inner
+            .lock()
+            .map_err(|e| OutboxError::StorageFailed(format!("Outbox lock poisoned: {}", e)))
+    }
+}
+
+// Manual impl: cloning the handle must not require O: Clone
+impl<O: NotificationOutbox> Clone for SharedNotificationOutbox<O> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<O: NotificationOutbox> NotificationOutbox for SharedNotificationOutbox<O> {
+    fn defer(&mut self, notification: DeferredNotification) -> Result<(), OutboxError> {
+        self.lock()?.defer(notification)
+    }
+
+    fn due(&self, now: DateTime<Utc>) -> Result<Vec<DeferredNotification>, OutboxError> {
+        self.lock()?.due(now)
+    }
+
+    fn remove(&mut self, id: Uuid) -> Result<bool, OutboxError> {
+        self.lock()?.remove(id)
+    }
+
+    fn pending(&self) -> Result<Vec<DeferredNotification>, OutboxError> {
+        self.lock()?.pending()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Coffee, Customer, DeferredNotice, Order, OrderReadyNotice};
+    use chrono::Duration;
+
+    #[test]
+    fn test_json_outbox_keeps_notices_until_removed() {
+        let path = std::env::temp_dir().join(format!("deferred-{}.json", Uuid::new_v4()));
+        let outbox = JsonNotificationOutbox::new(path.clone()).unwrap();
+        let mut shared = SharedNotificationOutbox::new(outbox);
+        let mut releaser = shared.clone();
+        let now = Utc::now();
+        let order = Order::single(Customer::named("Ada"), Coffee::medium());
+        let notice = DeferredNotice::OrderReady(OrderReadyNotice::from_order(&order));
+        let later = DeferredNotification::new(notice.clone(), now, now + Duration::hours(8));
+        let sooner = DeferredNotification::new(notice, now, now + Duration::hours(1));
+        shared.defer(later.clone()).unwrap();
+        shared.defer(sooner.clone()).unwrap();
+
+        assert!(releaser.due(now).unwrap().is_empty());
+        let due = releaser.due(now + Duration::hours(9)).unwrap();
+        assert_eq!(due, vec![sooner.clone(), later.clone()]);
+        assert!(releaser.remove(sooner.id).unwrap());
+        assert!(!releaser.remove(sooner.id).unwrap());
+
+        let reloaded = JsonNotificationOutbox::new(path.clone()).unwrap();
+        assert_eq!(reloaded.pending().unwrap(), vec![later]);
+        fs::remove_file(path).unwrap();
+    }
+}
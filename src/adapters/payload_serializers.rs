@@ -0,0 +1,181 @@
+// SOLID: Payload serializers (JSON, XML, CloudEvents)
+//
+// - JsonPayload: the event as it always was, `{"id", "topic", "occurred_at", "data"}`
+// - XmlPayload: the same fields as elements, for systems that only take XML
+// - CloudEventsPayload: a CloudEvents 1.0 envelope in structured JSON mode,
+//   which serverless platforms and event routers accept as is
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same event in, one string out: WebhookDispatcher signs and sends whatever
+// it gets, with the serializer's Content-Type.
+
+use crate::domain::{CloudEvent, WebhookEvent};
+use crate::ports::{PayloadSerializer, SerializationError};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Plain JSON (the default format)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonPayload;
+
+impl PayloadSerializer for JsonPayload {
+    fn format(&self) -> &str {
+        "json"
+    }
+
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+
+    fn serialize(&self, event: &WebhookEvent) -> Result<String, SerializationError> {
+        serde_json::to_string(event).map_err(|e| SerializationError::Failed(e.to_string()))
+    }
+}
+
+/// `<event>` with one element per field; arrays repeat an `<item>` element
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XmlPayload;
+
+impl PayloadSerializer for XmlPayload {
+    fn format(&self) -> &str {
+        "xml"
+    }
+
+    fn content_type(&self) -> &str {
+        "application/xml"
+    }
+
+    fn serialize(&self, event: &WebhookEvent) -> Result<String, SerializationError> {
+        let mut text = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        // Writing to a String cannot fail
+        let _ = write!(
+            text,
+            "<event><id>{}</id><topic>{}</topic><occurred_at>{}</occurred_at>",
+            event.id,
+            escape(&event.topic),
+            event.occurred_at.to_rfc3339()
+        );
+        write_element(&mut text, "data", &event.data);
+        text.push_str("</event>\n");
+        Ok(text)
+    }
+}
+
+fn write_element(text: &mut String, name: &str, value: &Value) {
+    let _ = write!(text, "<{}>", name);
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => text.push_str(&b.to_string()),
+        Value::Number(n) => text.push_str(&n.to_string()),
+        Value::String(s) => text.push_str(&escape(s)),
+        Value::Array(items) => {
+            for item in items {
+                write_element(text, "item", item);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                write_element(text, &element_name(key), field);
+            }
+        }
+    }
+    let _ = write!(text, "</{}>", name);
+}
+
+/// Serde field names are already valid XML names; anything else becomes `_`
+fn element_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => name,
+        _ => format!("_{}", name),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// CloudEvents 1.0, structured content mode (see domain::CloudEvent)
+///
+/// `source` identifies this shop (a URI reference, e.g. "/shops/geneva");
+/// the event's topic is its `type`. Receivers read it back with `CloudEvent::parse`.
+#[derive(Debug, Clone)]
+pub struct CloudEventsPayload {
+    source: String,
+}
+
+impl CloudEventsPayload {
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+        }
+    }
+}
+
+impl PayloadSerializer for CloudEventsPayload {
+    fn format(&self) -> &str {
+        "cloudevents"
+    }
+
+    fn content_type(&self) -> &str {
+        "application/cloudevents+json"
+    }
+
+    fn serialize(&self, event: &WebhookEvent) -> Result<String, SerializationError> {
+        CloudEvent::from_webhook_event(event, &self.source)
+            .to_json()
+            .map_err(|e| SerializationError::Failed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn event() -> WebhookEvent {
+        WebhookEvent {
+            id: Uuid::nil(),
+            topic: "order.placed".to_string(),
+            occurred_at: Utc.with_ymd_and_hms(2026, 3, 4, 8, 30, 0).unwrap(),
+            data: json!({ "customer": "Ada & Bob", "items": ["Coffee", "Tea"], "total": 6.0 }),
+        }
+    }
+
+    #[test]
+    fn test_xml_elements_and_escaping() {
+        let xml = XmlPayload.serialize(&event()).unwrap();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\""));
+        assert!(xml.contains("<topic>order.placed</topic>"));
+        assert!(xml.contains("<customer>Ada &amp; Bob</customer>"));
+        assert!(xml.contains("<items><item>Coffee</item><item>Tea</item></items>"));
+        assert!(xml.contains("<total>6.0</total>"));
+        assert_eq!(element_name("2nd total"), "_2nd_total");
+    }
+
+    #[test]
+    fn test_cloudevents_envelope() {
+        let body = CloudEventsPayload::new("/shops/geneva").serialize(&event()).unwrap();
+        let envelope: Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(envelope["specversion"], "1.0");
+        assert_eq!(envelope["type"], "order.placed");
+        assert_eq!(envelope["source"], "/shops/geneva");
+        assert_eq!(envelope["time"], "2026-03-04T08:30:00Z");
+        assert_eq!(envelope["data"]["items"][1], "Tea");
+        let parsed = CloudEvent::parse(&body).unwrap();
+        assert_eq!(parsed.id, Uuid::nil().to_string());
+
+        let plain = JsonPayload.serialize(&event()).unwrap();
+        assert_eq!(serde_json::from_str::<WebhookEvent>(&plain).unwrap(), event());
+    }
+}
@@ -0,0 +1,432 @@
+// SOLID: PaymentRouter - picks a payment processor per order, by rules
+//
+// COMPOSITE over PaymentProcessors (OCP):
+// OrderService still sees one PaymentProcessor. The router holds several
+// routes (card gateway, terminal, cash...) and charges each request on the
+// first one its rules allow:
+//
+// - amount thresholds: a route only takes amounts within its min/max
+//   (no card charge under $1, say)
+// - customer preference: a customer's preferred route is tried first
+// - availability: a route switched off (gateway outage, terminal offline)
+//   is skipped until switched on again, without rebuilding the router
+// - failover: a route that declines (or can't take the request) hands it to
+//   the next one, unless failover is turned off. A NetworkError is not a
+//   decline: the charge may have gone through, so it is returned as it is
+//   and the caller retries with the same key instead of charging elsewhere
+//
+// Observability: per-route counters (`counts`), and one PAYMENT_ROUTE_ATTEMPTS
+// observation per routed payment (1 = the first choice took it) on the
+// MetricsRecorder given with `with_metrics`.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Idempotent like any processor: a retried request returns the first charge,
+// whichever route took it. Refunds go back through the route that charged,
+// remembered in memory. A payment it doesn't remember (charged before a
+// restart) goes to the route whose payment ID prefix it carries
+// (`with_payment_prefix`), and is refused when no route claims it: most
+// processors refund any ID they are given, so asking each in turn would pay
+// a card charge back out of the cash drawer.
+//
+// Its payment_method_name is "Routed": payment rules and cash rounding key
+// on the method before the charge, and the route is only known after it.
+
+use crate::domain::Money;
+use crate::ports::{
+    MetricsRecorder, PaymentError, PaymentProcessor, PaymentRequest, PAYMENT_ROUTE_ATTEMPTS,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// One processor the router can charge, and when it may
+pub struct Route {
+    name: String,
+    processor: Box<dyn PaymentProcessor>,
+    min_amount: Option<Money>,
+    max_amount: Option<Money>,
+    payment_prefix: Option<String>,
+    available: AtomicBool,
+}
+
+impl Route {
+    pub fn new(name: &str, processor: impl PaymentProcessor + 'static) -> Self {
+        Self {
+            name: name.to_string(),
+            processor: Box::new(processor),
+            min_amount: None,
+            max_amount: None,
+            payment_prefix: None,
+            available: AtomicBool::new(true),
+        }
+    }
+
+    /// Smallest amount this route takes (amounts in other currencies aren't limited)
+    pub fn with_min_amount(mut self, amount: Money) -> Self {
+        self.min_amount = Some(amount);
+        self
+    }
+
+    /// Largest amount this route takes (amounts in other currencies aren't limited)
+    pub fn with_max_amount(mut self, amount: Money) -> Self {
+        self.max_amount = Some(amount);
+        self
+    }
+
+    /// Payment IDs this route issues start with `prefix` ("CC-", "CASH-")
+    ///
+    /// How a payment charged before a restart finds its way back for a refund.
+    pub fn with_payment_prefix(mut self, prefix: &str) -> Self {
+        self.payment_prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn accepts(&self, amount: Money) -> bool {
+        self.available.load(Ordering::Relaxed)
+            && self.min_amount.is_none_or(|min| min.currency != amount.currency || amount >= min)
+            && self.max_amount.is_none_or(|max| max.currency != amount.currency || amount <= max)
+    }
+}
+
+/// What one route did so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouteCount {
+    /// Charges sent to the route
+    pub attempts: u64,
+    pub approved: u64,
+    /// Declined or failed (declines are handed to the next route under failover)
+    pub declined: u64,
+}
+
+/// Charges each request on the first route its rules allow
+pub struct PaymentRouter {
+    routes: Vec<Route>,
+    // Customer email -> preferred route name
+    preferences: HashMap<String, String>,
+    failover: bool,
+    metrics: Option<Box<dyn MetricsRecorder>>,
+    counts: Mutex<HashMap<String, RouteCount>>,
+    // Idempotency key -> payment ID of payments already made
+    payments: Mutex<HashMap<String, String>>,
+    // Payment ID -> index of the route that charged it
+    charged_by: Mutex<HashMap<String, usize>>,
+}
+
+impl Default for PaymentRouter {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            preferences: HashMap::new(),
+            failover: true,
+            metrics: None,
+            counts: Mutex::new(HashMap::new()),
+            payments: Mutex::new(HashMap::new()),
+            charged_by: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl PaymentRouter {
+    /// No routes yet, failover on
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a route; routes are tried in the order they were added
+    pub fn with_route(mut self, route: Route) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Try `route` first for this customer (if it takes the amount)
+    pub fn with_preference(mut self, customer_email: &str, route: &str) -> Self {
+        self.preferences.insert(customer_email.to_string(), route.to_string());
+        self
+    }
+
+    /// A declined charge is final: don't try the next route
+    pub fn without_failover(mut self) -> Self {
+        self.failover = false;
+        self
+    }
+
+    /// Record how many routes each payment took (PAYMENT_ROUTE_ATTEMPTS)
+    pub fn with_metrics(mut self, metrics: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
+
+    /// Switch a route off or back on; false if there is no such route
+    pub fn set_available(&self, route: &str, available: bool) -> bool {
+        match self.routes.iter().find(|r| r.name == route) {
+            Some(route) => {
+                route.available.store(available, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Counters for one route (zero if never tried)
+    pub fn counts(&self, route: &str) -> RouteCount {
+        self.counts.lock().unwrap().get(route).copied().unwrap_or_default()
+    }
+
+    /// The route that charged `payment_id`
+    pub fn route_of(&self, payment_id: &str) -> Option<&str> {
+        let index = *self.charged_by.lock().unwrap().get(payment_id)?;
+        Some(self.routes[index].name())
+    }
+
+    /// Routes allowed for this amount: the customer's preference first, then in order
+    fn candidates(&self, amount: Money, customer_email: Option<&str>) -> Vec<usize> {
+        let preferred = customer_email
+            .and_then(|email| self.preferences.get(email))
+            .and_then(|name| self.routes.iter().position(|r| &r.name == name));
+        let mut order: Vec<usize> = preferred.into_iter().collect();
+        order.extend((0..self.routes.len()).filter(|&i| Some(i) != preferred));
+        order.retain(|&i| self.routes[i].accepts(amount));
+        order
+    }
+
+    /// Charge with `charge` on each candidate in turn until one approves
+    fn route(
+        &self,
+        amount: Money,
+        customer_email: Option<&str>,
+        charge: impl Fn(&dyn PaymentProcessor) -> Result<String, PaymentError>,
+    ) -> Result<String, PaymentError> {
+        let candidates = self.candidates(amount, customer_email);
+        let mut last_error =
+            PaymentError::ProcessingFailed(format!("No payment route takes {}", amount));
+
+        for (tried, &index) in candidates.iter().enumerate() {
+            let route = &self.routes[index];
+            let result = charge(route.processor.as_ref());
+            self.count(&route.name, result.is_ok());
+            match result {
+                Ok(payment_id) => {
+                    self.charged_by.lock().unwrap().insert(payment_id.clone(), index);
+                    if let Some(metrics) = &self.metrics {
+                        metrics.observe(PAYMENT_ROUTE_ATTEMPTS, (tried + 1) as f64);
+                    }
+                    return Ok(payment_id);
+                }
+                Err(e) => {
+                    println!("↪ Route {} declined: {}", route.name, e);
+                    // The outcome of a network error is unknown: never charge twice
+                    let definite = !matches!(e, PaymentError::NetworkError(_));
+                    last_error = e;
+                    if !self.failover || !definite {
+                        break;
+                    }
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    fn count(&self, route: &str, approved: bool) {
+        let mut counts = self.counts.lock().unwrap();
+        let count = counts.entry(route.to_string()).or_default();
+        count.attempts += 1;
+        if approved {
+            count.approved += 1;
+        } else {
+            count.declined += 1;
+        }
+    }
+}
+
+impl PaymentProcessor for PaymentRouter {
+    /// No customer and no idempotency key: routed by amount only
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        self.route(amount, None, |processor| processor.process_payment(amount))
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        // Held while routing, so a concurrent retry can't charge a second route
+        let mut payments = self.payments.lock().unwrap();
+        if let Some(payment_id) = payments.get(&request.idempotency_key) {
+            return Ok(payment_id.clone());
+        }
+
+        let payment_id = self.route(request.amount, Some(&request.customer_email), |processor| {
+            processor.process_request(request)
+        })?;
+        payments.insert(request.idempotency_key.clone(), payment_id.clone());
+        Ok(payment_id)
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        self.refund_through(payment_id, |processor| processor.refund(payment_id, amount))
+    }
+
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        self.refund_through(payment_id, |processor| {
+            processor.refund_with_key(payment_id, amount, idempotency_key)
+        })
+    }
+
+    fn paid_with_points(&self, payment_id: &str) -> Option<Money> {
+        let index = self.charged_by.lock().unwrap().get(payment_id).copied()?;
+        self.routes[index].processor.paid_with_points(payment_id)
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "Routed"
+    }
+}
+
+impl PaymentRouter {
+    /// Refund on the route that took `payment_id`
+    fn refund_through(
+        &self,
+        payment_id: &str,
+        refund: impl Fn(&dyn PaymentProcessor) -> Result<String, PaymentError>,
+    ) -> Result<String, PaymentError> {
+        let index = self.charged_by.lock().unwrap().get(payment_id).copied();
+        if let Some(index) = index {
+            return refund(self.routes[index].processor.as_ref());
+        }
+
+        // Not charged since this router started: the route whose IDs look like it
+        let index = self
+            .routes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, route)| {
+                let prefix = route.payment_prefix.as_deref()?;
+                payment_id.starts_with(prefix).then_some((index, prefix.len()))
+            })
+            .max_by_key(|&(_, length)| length)
+            .map(|(index, _)| index)
+            .ok_or_else(|| {
+                PaymentError::ProcessingFailed(format!("No payment route issued {}", payment_id))
+            })?;
+        let refund_id = refund(self.routes[index].processor.as_ref())?;
+        self.charged_by.lock().unwrap().insert(payment_id.to_string(), index);
+        Ok(refund_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CardPresentPayment, CashPayment, CreditCardPayment, InMemoryMetrics};
+    use uuid::Uuid;
+
+    fn request(amount: f64, email: &str) -> PaymentRequest {
+        PaymentRequest {
+            amount: Money::usd(amount),
+            order_id: Uuid::new_v4(),
+            customer_ref: "customer-1".to_string(),
+            customer_email: email.to_string(),
+            idempotency_key: format!("order-{}", Uuid::new_v4()),
+        }
+    }
+
+    fn shop_router() -> PaymentRouter {
+        PaymentRouter::new()
+            .with_route(
+                Route::new("gateway", CreditCardPayment::new("https://gw.example".to_string()))
+                    .with_min_amount(Money::usd(1.0))
+                    .with_payment_prefix("CC-"),
+            )
+            .with_route(
+                Route::new("terminal", CardPresentPayment::new("KIOSK-1"))
+                    .with_payment_prefix("CP-KIOSK-1-"),
+            )
+            .with_route(
+                Route::new("cash", CashPayment)
+                    .with_max_amount(Money::usd(100.0))
+                    .with_payment_prefix("CASH-"),
+            )
+            .with_preference("ada@example.com", "cash")
+    }
+
+    #[test]
+    fn test_routes_by_amount_preference_and_availability() {
+        let router = shop_router();
+
+        let charge = |amount, email| router.process_request(&request(amount, email)).unwrap();
+        assert!(charge(4.20, "grace@example.com").starts_with("CC-"));
+        assert!(charge(0.50, "grace@example.com").starts_with("CP-KIOSK-1-"));
+        assert!(charge(4.20, "ada@example.com").starts_with("CASH-"));
+        // Ada's preference doesn't take amounts over its maximum
+        assert!(charge(250.0, "ada@example.com").starts_with("CC-"));
+
+        assert!(router.set_available("gateway", false));
+        assert!(charge(4.20, "grace@example.com").starts_with("CP-"));
+        assert!(!router.set_available("bitcoin", true));
+
+        let retried = request(4.20, "grace@example.com");
+        let first = router.process_request(&retried).unwrap();
+        assert_eq!(router.process_request(&retried).unwrap(), first);
+        assert_eq!(router.route_of(&first), Some("terminal"));
+        assert!(router.refund(&first, Money::usd(4.20)).unwrap().starts_with("CP-REFUND-KIOSK-1-"));
+        assert!(PaymentRouter::new().refund("CC-unknown", Money::usd(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_fails_over_on_decline_and_reports_it() {
+        let metrics = InMemoryMetrics::new();
+        let router = shop_router().with_metrics(metrics.clone());
+
+        // The gateway refuses amounts over its card limit; the terminal takes it
+        let payment_id = router.process_request(&request(1500.0, "grace@example.com")).unwrap();
+        assert_eq!(router.route_of(&payment_id), Some("terminal"));
+        assert_eq!(router.counts("gateway"), RouteCount { attempts: 1, approved: 0, declined: 1 });
+        assert_eq!(router.counts("terminal").approved, 1);
+        assert_eq!(metrics.samples(PAYMENT_ROUTE_ATTEMPTS), vec![2.0]);
+
+        let strict = shop_router().without_failover();
+        assert!(strict.process_request(&request(1500.0, "grace@example.com")).is_err());
+        assert!(PaymentRouter::new().process_payment(Money::usd(4.20)).is_err());
+    }
+
+    #[test]
+    fn test_network_errors_stop_the_routing() {
+        struct TimingOut;
+
+        impl PaymentProcessor for TimingOut {
+            fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+                Err(PaymentError::NetworkError("timed out".to_string()))
+            }
+        }
+
+        // The first route may have charged: the next one is not tried
+        let router = PaymentRouter::new()
+            .with_route(Route::new("gateway", TimingOut))
+            .with_route(Route::new("cash", CashPayment));
+        let result = router.process_request(&request(4.20, "grace@example.com"));
+        assert!(matches!(result, Err(PaymentError::NetworkError(_))));
+        assert_eq!(router.counts("cash").attempts, 0);
+    }
+
+    #[test]
+    fn test_payments_from_before_a_restart_are_refunded_by_their_route() {
+        let card = shop_router().process_request(&request(4.20, "grace@example.com")).unwrap();
+        let cash = shop_router().process_request(&request(4.20, "ada@example.com")).unwrap();
+
+        let restarted = shop_router();
+        assert!(restarted.refund(&card, Money::usd(4.20)).unwrap().starts_with("CC-REFUND-"));
+        assert_eq!(restarted.route_of(&card), Some("gateway"));
+        assert!(restarted.refund(&cash, Money::usd(4.20)).unwrap().starts_with("CASH-REFUND-"));
+        assert_eq!(restarted.route_of(&cash), Some("cash"));
+
+        // No route claims it: refused, not paid out of the first route that says yes
+        let unprefixed = PaymentRouter::new().with_route(Route::new("cash", CashPayment));
+        assert!(unprefixed.refund(&card, Money::usd(4.20)).is_err());
+        assert!(restarted.refund("pi_3Mt", Money::usd(4.20)).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+// SOLID: PointsPayment - loyalty points first, then another payment method
+//
+// DECORATOR over any PaymentProcessor (OCP), like WalletPayment:
+// OrderService still sees one PaymentProcessor. This one pays what it can
+// with the customer's loyalty points (each worth a fixed amount, a cent by
+// default) and charges only the rest to the wrapped processor.
+//
+// Payment IDs:
+// - paid entirely with points: `POINTS-...`
+// - split: the wrapped processor's ID; the points part is remembered with it
+//
+// A frozen account (one of the customer's payments is disputed, see
+// DisputeService) spends nothing: all of it goes to the wrapped processor.
+//
+// Refunds give money back where it came from: the wrapped processor's part
+// first, then points. Which payment used how many points is kept in memory:
+// after a restart, a split is refunded through the wrapped processor only.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Idempotent like any processor: the same idempotency key is charged once.
+
+use crate::domain::{LoyaltyAccount, Money};
+use crate::ports::{
+    LoyaltyError, LoyaltyRepository, PaymentError, PaymentProcessor, PaymentRequest,
+};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Points a payment used (for refunds)
+#[derive(Debug, Clone)]
+struct Redemption {
+    customer_email: String,
+    points: u64,
+    /// Cents charged to the fallback
+    charged: i64,
+    /// What the points paid of the amount due
+    paid: Money,
+}
+
+/// Pays with loyalty points before falling back to `fallback`
+pub struct PointsPayment<L: LoyaltyRepository, P: PaymentProcessor> {
+    accounts: Mutex<L>,
+    fallback: P,
+    cents_per_point: i64,
+    // Idempotency key -> payment ID of payments already made
+    payments: Mutex<HashMap<String, String>>,
+    // Payment ID -> the points part of that payment
+    redemptions: Mutex<HashMap<String, Redemption>>,
+}
+
+impl<L: LoyaltyRepository, P: PaymentProcessor> PointsPayment<L, P> {
+    /// `accounts` is usually a SharedLoyaltyRepository, so accrual adds to it meanwhile
+    pub fn new(accounts: L, fallback: P) -> Self {
+        Self {
+            accounts: Mutex::new(accounts),
+            fallback,
+            cents_per_point: 1,
+            payments: Mutex::new(HashMap::new()),
+            redemptions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// What one point pays, in cents (1 by default: 100 points are $1.00)
+    pub fn with_point_value(mut self, cents: i64) -> Self {
+        self.cents_per_point = cents.max(1);
+        self
+    }
+
+    fn accounts(&self) -> Result<MutexGuard<'_, L>, PaymentError> {
+        self.accounts.lock().map_err(|_| poisoned("Loyalty"))
+    }
+
+    fn payments(&self) -> Result<MutexGuard<'_, HashMap<String, String>>, PaymentError> {
+        self.payments.lock().map_err(|_| poisoned("Payments"))
+    }
+
+    fn redemptions(&self) -> Result<MutexGuard<'_, HashMap<String, Redemption>>, PaymentError> {
+        self.redemptions.lock().map_err(|_| poisoned("Redemptions"))
+    }
+
+    /// Refund `amount`: the fallback part through `fallback_refund`, the rest in points
+    fn give_back(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        fallback_refund: impl Fn(Money) -> Result<String, PaymentError>,
+    ) -> Result<String, PaymentError> {
+        let redemption = self.redemptions()?.get(payment_id).cloned();
+        let Some(redemption) = redemption else {
+            if payment_id.starts_with("POINTS-") {
+                return Err(PaymentError::ProcessingFailed(format!(
+                    "No points payment {}",
+                    payment_id
+                )));
+            }
+            return fallback_refund(amount);
+        };
+
+        let currency = amount.currency;
+        let amount = amount.cents;
+        let to_fallback = amount.min(redemption.charged);
+        let refund_id = if to_fallback > 0 {
+            fallback_refund(Money::new(to_fallback, currency))?
+        } else {
+            format!("POINTS-REFUND-{}", Uuid::new_v4().simple())
+        };
+        let points = ((amount - to_fallback) / self.cents_per_point) as u64;
+        let points = points.min(redemption.points);
+        if points > 0 {
+            let mut accounts = self.accounts()?;
+            let mut account = accounts
+                .find(&redemption.customer_email)
+                .map_err(loyalty_failed)?
+                .unwrap_or_else(|| LoyaltyAccount::new(&redemption.customer_email));
+            account.restore(points);
+            accounts.save(account).map_err(loyalty_failed)?;
+        }
+        Ok(refund_id)
+    }
+}
+
+fn poisoned(lock: &str) -> PaymentError {
+    PaymentError::ProcessingFailed(format!("{} lock poisoned", lock))
+}
+
+fn loyalty_failed(e: LoyaltyError) -> PaymentError {
+    PaymentError::ProcessingFailed(e.to_string())
+}
+
+impl<L: LoyaltyRepository, P: PaymentProcessor> PaymentProcessor for PointsPayment<L, P> {
+    /// No customer to look up: all of it goes to the fallback
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        self.fallback.process_payment(amount)
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        if let Some(payment_id) = self.payments()?.get(&request.idempotency_key) {
+            return Ok(payment_id.clone());
+        }
+
+        // Held until the points are spent, so the balance can't change in between
+        let mut accounts = self.accounts()?;
+        let Some(mut account) = accounts.find(&request.customer_email).map_err(loyalty_failed)?
+        else {
+            return self.fallback.process_request(request);
+        };
+        let due = request.amount.cents;
+        // Enough points to cover the total, rounded up, or all of them
+        let wanted = (due + self.cents_per_point - 1) / self.cents_per_point;
+        let points = account.spend(wanted.max(0) as u64);
+        if points == 0 {
+            return self.fallback.process_request(request);
+        }
+
+        let rest = (due - points as i64 * self.cents_per_point).max(0);
+        let charged = if rest > 0 {
+            let rest_request = PaymentRequest {
+                amount: Money::new(rest, request.amount.currency),
+                idempotency_key: format!("{}-rest", request.idempotency_key),
+                ..request.clone()
+            };
+            Some(self.fallback.process_request(&rest_request)?)
+        } else {
+            None
+        };
+
+        if let Err(e) = accounts.save(account) {
+            // The rest was charged but the points weren't taken: give the rest back
+            if let Some(payment_id) = &charged {
+                let rest = Money::new(rest, request.amount.currency);
+                let _ = self.fallback.refund(payment_id, rest);
+            }
+            return Err(loyalty_failed(e));
+        }
+
+        let payment_id = charged.unwrap_or_else(|| format!("POINTS-{}", Uuid::new_v4().simple()));
+        let redemption = Redemption {
+            customer_email: request.customer_email.clone(),
+            points,
+            charged: rest,
+            paid: Money::new(due - rest, request.amount.currency),
+        };
+        self.redemptions()?.insert(payment_id.clone(), redemption);
+        self.payments()?.insert(request.idempotency_key.clone(), payment_id.clone());
+        Ok(payment_id)
+    }
+
+    /// Money back where it came from: the fallback part first, then points
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        self.give_back(payment_id, amount, |amount| self.fallback.refund(payment_id, amount))
+    }
+
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        self.give_back(payment_id, amount, |amount| {
+            self.fallback.refund_with_key(payment_id, amount, idempotency_key)
+        })
+    }
+
+    fn paid_with_points(&self, payment_id: &str) -> Option<Money> {
+        let redemption = self.redemptions.lock().ok()?.get(payment_id).cloned();
+        redemption.map(|redemption| redemption.paid)
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "Loyalty Points"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, MemoryLoyaltyRepository, SharedLoyaltyRepository};
+
+    fn request(amount: f64) -> PaymentRequest {
+        PaymentRequest {
+            amount: Money::usd(amount),
+            order_id: Uuid::new_v4(),
+            customer_ref: "customer-1".to_string(),
+            customer_email: "ada@example.com".to_string(),
+            idempotency_key: format!("order-{}", Uuid::new_v4()),
+        }
+    }
+
+    #[test]
+    fn test_points_are_used_before_the_fallback() {
+        let mut accounts = SharedLoyaltyRepository::new(MemoryLoyaltyRepository::new());
+        let mut ada = LoyaltyAccount::new("ada@example.com");
+        ada.earn(500);
+        accounts.save(ada).unwrap();
+        let points = |accounts: &SharedLoyaltyRepository<_>| {
+            accounts.find("ada@example.com").unwrap().unwrap().points
+        };
+        let payment = PointsPayment::new(accounts.clone(), CashPayment);
+
+        // $3.50 is 350 points
+        let small = request(3.5);
+        let paid = payment.process_request(&small).unwrap();
+        assert!(paid.starts_with("POINTS-"));
+        assert_eq!(payment.process_request(&small).unwrap(), paid);
+        assert_eq!(points(&accounts), 150);
+
+        // 150 points, the other $5.00 in cash
+        let split = payment.process_request(&request(6.5)).unwrap();
+        assert!(split.starts_with("CASH-"));
+        assert_eq!(points(&accounts), 0);
+
+        // Refund $6.00 of it: $5.00 in cash, 100 points back
+        payment.refund(&split, Money::usd(6.0)).unwrap();
+        assert_eq!(points(&accounts), 100);
+        payment.refund(&paid, Money::usd(3.5)).unwrap();
+        assert_eq!(points(&accounts), 450);
+    }
+}
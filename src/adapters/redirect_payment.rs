@@ -0,0 +1,191 @@
+// SOLID: RedirectPayment - PayPal-style checkout, approved on the provider's page
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// A two-step payment method, added through the PaymentProcessor port's
+// `initiate`/`confirm` pair without changing any other processor.
+//
+// The flow:
+//   1. `initiate` registers the amount with the provider and returns a token
+//      and the URL to send the customer to; nothing is charged
+//   2. the customer approves on the provider's page (`approve` stands in for
+//      that click, as a sandbox would)
+//   3. the provider sends the customer back with the token, and `confirm`
+//      takes the money
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// `process_payment` can't take money without the customer's approval, so it
+// refuses instead of pretending; OrderService uses `initiate_order` and
+// `confirm_payment` for this processor.
+
+use super::randomness::SystemRandomness;
+use crate::domain::Money;
+use crate::ports::{PaymentError, PaymentProcessor, PaymentRequest, PendingPayment, Randomness};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// A payment the provider knows about
+struct Checkout {
+    idempotency_key: String,
+    approved: bool,
+    /// Set once confirmed
+    payment_id: Option<String>,
+}
+
+/// Redirect/confirm payment processor
+pub struct RedirectPayment {
+    checkout_url: String,
+    // Token -> checkout (a real provider keeps these on its side)
+    checkouts: Mutex<HashMap<String, Checkout>>,
+    randomness: Box<dyn Randomness>,
+}
+
+impl RedirectPayment {
+    /// `checkout_url` is the provider's approval page, e.g.
+    /// "https://www.sandbox.paypal.com/checkoutnow"
+    pub fn new(checkout_url: &str) -> Self {
+        Self {
+            checkout_url: checkout_url.to_string(),
+            checkouts: Mutex::new(HashMap::new()),
+            randomness: Box::new(SystemRandomness),
+        }
+    }
+
+    /// Draw tokens and payment IDs from `randomness` (a SeededRandomness for reproducible runs)
+    pub fn with_randomness(mut self, randomness: impl Randomness + 'static) -> Self {
+        self.randomness = Box::new(randomness);
+        self
+    }
+
+    /// The customer approved `token` on the provider's page
+    pub fn approve(&self, token: &str) -> Result<(), PaymentError> {
+        let mut checkouts = self.lock()?;
+        let checkout = checkouts
+            .get_mut(token)
+            .ok_or_else(|| PaymentError::ProcessingFailed(format!("Unknown token {}", token)))?;
+        checkout.approved = true;
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<String, Checkout>>, PaymentError> {
+        self.checkouts
+            .lock()
+            .map_err(|e| PaymentError::ProcessingFailed(format!("Checkout lock poisoned: {}", e)))
+    }
+
+    fn pending(&self, token: &str) -> PendingPayment {
+        PendingPayment {
+            token: token.to_string(),
+            approval_url: format!("{}?token={}", self.checkout_url, token),
+        }
+    }
+}
+
+impl PaymentProcessor for RedirectPayment {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        Err(PaymentError::ProcessingFailed(format!(
+            "{} needs the customer's approval to take {}: initiate, then confirm",
+            self.payment_method_name(),
+            amount
+        )))
+    }
+
+    fn initiate(&self, request: &PaymentRequest) -> Result<PendingPayment, PaymentError> {
+        if request.amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Amount must be positive".to_string(),
+            ));
+        }
+
+        // A retried request gets the checkout it already started
+        let mut checkouts = self.lock()?;
+        if let Some(token) = checkouts
+            .iter()
+            .find(|(_, checkout)| checkout.idempotency_key == request.idempotency_key)
+            .map(|(token, _)| token.clone())
+        {
+            return Ok(self.pending(&token));
+        }
+
+        let token = format!("EC-{}", self.randomness.uuid().simple());
+        println!(
+            "🔗 Checkout of {} for order {}: {}",
+            request.amount,
+            request.order_id,
+            token
+        );
+        checkouts.insert(
+            token.clone(),
+            Checkout {
+                idempotency_key: request.idempotency_key.clone(),
+                approved: false,
+                payment_id: None,
+            },
+        );
+        Ok(self.pending(&token))
+    }
+
+    fn confirm(&self, token: &str) -> Result<String, PaymentError> {
+        let mut checkouts = self.lock()?;
+        let checkout = checkouts
+            .get_mut(token)
+            .ok_or_else(|| PaymentError::ProcessingFailed(format!("Unknown token {}", token)))?;
+        if let Some(payment_id) = &checkout.payment_id {
+            return Ok(payment_id.clone());
+        }
+        if !checkout.approved {
+            return Err(PaymentError::ProcessingFailed(format!(
+                "The customer has not approved {} yet",
+                token
+            )));
+        }
+
+        let payment_id = format!("PAYPAL-{}", self.randomness.uuid());
+        println!("✓ Redirect payment confirmed: {}", payment_id);
+        checkout.payment_id = Some(payment_id.clone());
+        Ok(payment_id)
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        if amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Refund amount must be positive".to_string(),
+            ));
+        }
+
+        println!("🔗 Refunding {} on {}", amount, payment_id);
+        Ok(format!("PAYPAL-REFUND-{}", self.randomness.uuid()))
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "PayPal"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_confirm_needs_the_customers_approval() {
+        let paypal = RedirectPayment::new("https://www.sandbox.paypal.com/checkoutnow");
+        let request = PaymentRequest {
+            amount: Money::usd(4.20),
+            order_id: Uuid::new_v4(),
+            customer_ref: "guest".to_string(),
+            customer_email: "guest@example.com".to_string(),
+            idempotency_key: "order-1".to_string(),
+        };
+
+        let pending = paypal.initiate(&request).unwrap();
+        assert_eq!(paypal.initiate(&request).unwrap(), pending);
+        assert!(pending.approval_url.ends_with(&format!("checkoutnow?token={}", pending.token)));
+        assert!(paypal.confirm(&pending.token).is_err());
+        assert!(paypal.process_payment(Money::usd(4.20)).is_err());
+
+        paypal.approve(&pending.token).unwrap();
+        let payment_id = paypal.confirm(&pending.token).unwrap();
+        assert!(payment_id.starts_with("PAYPAL-"));
+        assert_eq!(paypal.confirm(&pending.token).unwrap(), payment_id);
+    }
+}
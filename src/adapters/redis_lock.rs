@@ -0,0 +1,138 @@
+// SOLID: RedisLock - DistributedLock on a Redis server
+//
+// For instances on different hosts. Each lock is one Redis key holding the
+// holder's name, with a Redis expiry as the lease: the server's clock decides,
+// so instances with drifting clocks still agree.
+//
+// Acquire and release must check "is it mine?" and act in one step, or a
+// lease could expire and be taken by someone else in between. Both are
+// therefore tiny Lua scripts, which Redis runs atomically.
+//
+// No client library: the few commands needed are spoken directly over TCP in
+// RESP, Redis' text protocol. One connection per call keeps it simple; locks
+// are taken a few times a minute, not per request.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same contract as MemoryLock and FileLock.
+
+use crate::ports::{DistributedLock, LockError};
+use chrono::Duration;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+/// Take the key if it is free, renew it if it is ours: 1 if held after the call
+const ACQUIRE: &str = "\
+if redis.call('get', KEYS[1]) == ARGV[1] then \
+  return redis.call('pexpire', KEYS[1], ARGV[2]) \
+end \
+if redis.call('set', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then return 1 end \
+return 0";
+
+/// Delete the key only if it is ours: 1 if it was
+const RELEASE: &str = "\
+if redis.call('get', KEYS[1]) == ARGV[1] then return redis.call('del', KEYS[1]) end \
+return 0";
+
+/// Leases stored as expiring Redis keys
+pub struct RedisLock {
+    address: String,
+    key_prefix: String,
+    timeout: std::time::Duration,
+}
+
+impl RedisLock {
+    /// `address` is `host:port` (Redis listens on 6379 by default)
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            key_prefix: "coffee-shop:lock:".to_string(),
+            timeout: std::time::Duration::from_secs(2),
+        }
+    }
+
+    /// Namespace of the keys (several shops on one Redis)
+    pub fn with_key_prefix(mut self, prefix: &str) -> Self {
+        self.key_prefix = prefix.to_string();
+        self
+    }
+
+    /// Run one Lua script and return its integer reply
+    fn eval(&self, script: &str, name: &str, args: &[&str]) -> Result<i64, LockError> {
+        let key = format!("{}{}", self.key_prefix, name);
+        let mut command = vec!["EVAL", script, "1", &key];
+        command.extend_from_slice(args);
+
+        let fail = |e: std::io::Error| LockError::Unavailable(format!("{}: {}", self.address, e));
+        let stream = TcpStream::connect(&self.address).map_err(fail)?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(fail)?;
+        stream.set_write_timeout(Some(self.timeout)).map_err(fail)?;
+
+        (&stream).write_all(&encode(&command)).map_err(fail)?;
+        let mut reply = String::new();
+        BufReader::new(&stream).read_line(&mut reply).map_err(fail)?;
+        parse_integer(&reply)
+    }
+}
+
+/// A command as a RESP array of bulk strings
+fn encode(parts: &[&str]) -> Vec<u8> {
+    let mut bytes = format!("*{}\r\n", parts.len()).into_bytes();
+    for part in parts {
+        bytes.extend_from_slice(format!("${}\r\n{}\r\n", part.len(), part).as_bytes());
+    }
+    bytes
+}
+
+/// `:1\r\n` -> 1; an error reply (`-ERR ...`) or anything else is an error
+fn parse_integer(reply: &str) -> Result<i64, LockError> {
+    let reply = reply.trim_end();
+    match reply.split_at_checked(1) {
+        Some((":", number)) => number
+            .parse()
+            .map_err(|_| LockError::Unavailable(format!("Unexpected reply '{}'", reply))),
+        Some(("-", message)) => Err(LockError::Unavailable(message.to_string())),
+        _ => Err(LockError::Unavailable(format!("Unexpected reply '{}'", reply))),
+    }
+}
+
+impl DistributedLock for RedisLock {
+    fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool, LockError> {
+        let millis = ttl.num_milliseconds().max(1).to_string();
+        Ok(self.eval(ACQUIRE, name, &[holder, &millis])? == 1)
+    }
+
+    fn release(&self, name: &str, holder: &str) -> Result<bool, LockError> {
+        Ok(self.eval(RELEASE, name, &[holder])? == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_speaks_resp_to_the_server() {
+        // A one-shot stand-in for Redis: records the request, answers `:1`
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0u8; 4096];
+            let read = stream.read(&mut request).unwrap();
+            stream.write_all(b":1\r\n").unwrap();
+            String::from_utf8_lossy(&request[..read]).to_string()
+        });
+
+        let lock = RedisLock::new(&address).with_key_prefix("test:");
+        assert!(lock.try_acquire("close", "server-a", Duration::seconds(30)).unwrap());
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("*6\r\n$4\r\nEVAL\r\n"));
+        assert!(request.ends_with("$10\r\ntest:close\r\n$8\r\nserver-a\r\n$5\r\n30000\r\n"));
+        assert!(matches!(parse_integer("-ERR unknown command"), Err(LockError::Unavailable(_))));
+        assert!(lock.release("close", "server-a").is_err());
+    }
+}
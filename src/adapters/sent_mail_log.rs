@@ -0,0 +1,98 @@
+// SOLID: Sent-mail logs (what already went out)
+//
+// - MemorySentMailLog: for one process (tests, a long-running worker)
+// - FileSentMailLog: one key per line in a text file, so a rerun from cron,
+//   after a crash or by hand, finds what the previous run sent
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same SentMailLog contract, only the lifetime of the memory differs.
+
+use crate::ports::{MailError, SentMailLog};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Keys kept in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemorySentMailLog {
+    keys: HashSet<String>,
+}
+
+impl MemorySentMailLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SentMailLog for MemorySentMailLog {
+    fn contains(&self, key: &str) -> Result<bool, MailError> {
+        Ok(self.keys.contains(key))
+    }
+
+    fn record(&mut self, key: &str) -> Result<(), MailError> {
+        self.keys.insert(key.to_string());
+        Ok(())
+    }
+}
+
+/// Keys appended to a file, one per line
+pub struct FileSentMailLog {
+    file_path: PathBuf,
+    keys: HashSet<String>,
+}
+
+impl FileSentMailLog {
+    /// Reads the keys already in the file (none if it doesn't exist yet)
+    pub fn new(file_path: PathBuf) -> Result<Self, MailError> {
+        let keys = match fs::read_to_string(&file_path) {
+            Ok(text) => text.lines().map(str::to_string).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(e) => return Err(MailError::LogFailed(e.to_string())),
+        };
+        Ok(Self { file_path, keys })
+    }
+
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+}
+
+impl SentMailLog for FileSentMailLog {
+    fn contains(&self, key: &str) -> Result<bool, MailError> {
+        Ok(self.keys.contains(key))
+    }
+
+    fn record(&mut self, key: &str) -> Result<(), MailError> {
+        let failed = |e: std::io::Error| MailError::LogFailed(e.to_string());
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .map_err(failed)?;
+        writeln!(file, "{}", key).map_err(failed)?;
+        file.sync_data().map_err(failed)?;
+        self.keys.insert(key.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_file_log_survives_a_restart() {
+        let path = std::env::temp_dir().join(format!("sent-{}.log", Uuid::new_v4()));
+
+        let mut log = FileSentMailLog::new(path.clone()).unwrap();
+        assert!(!log.contains("digest:2024-03-04:owner@example.com").unwrap());
+        log.record("digest:2024-03-04:owner@example.com").unwrap();
+
+        let reopened = FileSentMailLog::new(path.clone()).unwrap();
+        assert!(reopened.contains("digest:2024-03-04:owner@example.com").unwrap());
+        assert!(!reopened.contains("digest:2024-03-05:owner@example.com").unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+// SOLID: SharedOrderRepository - a cloneable handle over any repository
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Memory and JSON repositories are single-owner. Rather than teaching each
+// of them about threads, this wrapper adds sharing to ANY OrderRepository.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// It is an OrderRepository itself, so OrderService can't tell the difference.
+// Every clone sees the same orders - which is what lets an intake service
+// and several worker services cooperate on one store.
+
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Thread-safe, cloneable wrapper around any OrderRepository
+pub struct SharedOrderRepository<R: OrderRepository> {
+    inner: Arc<Mutex<R>>,
+}
+
+impl<R: OrderRepository> SharedOrderRepository<R> {
+    /// Wrap a repository so it can be shared
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, R>, RepositoryError> {
+        self.inner
+            .lock()
+            .map_err(|e| RepositoryError::LoadFailed(format!("Repository lock poisoned: {}", e)))
+    }
+}
+
+// Manual impl: cloning the handle must not require R: Clone
+impl<R: OrderRepository> Clone for SharedOrderRepository<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<R: OrderRepository> OrderRepository for SharedOrderRepository<R> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.lock()?.save(order)
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        self.lock()?.find_by_id(id)
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.lock()?.find_by_customer_email(email)
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.lock()?.list_all()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.lock()?.update(order)
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        self.lock()?.delete(id)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        self.lock()?.save_batch(orders)
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.lock()?.list_all_shared()
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.lock()?.find_by_customer_email_shared(email)
+    }
+}
+
+// A poisoned lock answers with the conservative defaults
+impl<R: OrderRepository + RepositoryCapabilities> RepositoryCapabilities for SharedOrderRepository<R> {
+    fn supports_transactions(&self) -> bool {
+        self.lock().map(|inner| inner.supports_transactions()).unwrap_or(false)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.lock().map(|inner| inner.supports_streaming()).unwrap_or(false)
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        self.lock().map(|inner| inner.max_batch_size()).unwrap_or(Some(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::Customer;
+
+    #[test]
+    fn test_clones_share_orders() {
+        let mut writer = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let reader = writer.clone();
+
+        let customer = Customer::new(
+            "Test User".to_string(),
+            "test@example.com".to_string(),
+            None,
+        );
+        let order = Order::new(customer, vec![]);
+        writer.save(&order).unwrap();
+
+        assert!(reader.find_by_id(order.id).unwrap().is_some());
+    }
+}
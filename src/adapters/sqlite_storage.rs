@@ -0,0 +1,390 @@
+// SOLID: SqliteOrderRepository - SQLite database storage adapter (feature `sqlite`)
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// The database backend the other storage adapters promised: added without
+// touching OrderRepository, OrderService or the file repositories.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same contract and same behavior as MemoryOrderRepository (the tests below
+// run both side by side). Unlike the file backends, a write touches one row
+// instead of rewriting every order, and nothing is held in memory.
+//
+// SCHEMA:
+//
+//   orders(id TEXT PRIMARY KEY, customer_email TEXT, status TEXT,
+//          created_at TEXT, body TEXT)
+//
+// `body` is the order as JSON, so a field added to Order with a serde default
+// needs no migration; the other columns are copies of it for lookups (the
+// email is indexed). Schema changes are the numbered MIGRATIONS below, applied
+// on open and counted in SQLite's `user_version`: a database written by an
+// older build is brought up to date, one from a newer build is refused.
+//
+// FULL-TEXT SEARCH:
+//
+//   orders_fts(customer_name, items)   FTS5, rowid = the order's rowid
+//
+// Triggers keep it in step with `orders` from the JSON body (item names and
+// descriptions), so every write path, and any other SQLite client, updates
+// it. `full_text_search` turns each word of the query into a prefix term and
+// ranks the matches with FTS5's bm25.
+
+use crate::adapters::json_storage::refuse_card_numbers;
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Schema steps, in order: the database's `user_version` is how many ran
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE orders (
+        id TEXT PRIMARY KEY NOT NULL,
+        customer_email TEXT NOT NULL,
+        status TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        body TEXT NOT NULL
+    )",
+    "CREATE INDEX idx_orders_customer_email ON orders (customer_email)",
+    "CREATE VIRTUAL TABLE orders_fts USING fts5(
+        customer_name, items, tokenize = 'unicode61 remove_diacritics 2'
+    );
+    CREATE TRIGGER orders_fts_insert AFTER INSERT ON orders BEGIN
+        INSERT INTO orders_fts (rowid, customer_name, items)
+        VALUES (new.rowid, json_extract(new.body, '$.customer.name'), (
+            SELECT group_concat(json_extract(value, '$.beverage_name') || ' '
+                || json_extract(value, '$.beverage_description'), ' ')
+            FROM json_each(new.body, '$.items')
+        ));
+    END;
+    CREATE TRIGGER orders_fts_delete AFTER DELETE ON orders BEGIN
+        DELETE FROM orders_fts WHERE rowid = old.rowid;
+    END;
+    CREATE TRIGGER orders_fts_update AFTER UPDATE ON orders BEGIN
+        DELETE FROM orders_fts WHERE rowid = old.rowid;
+        INSERT INTO orders_fts (rowid, customer_name, items)
+        VALUES (new.rowid, json_extract(new.body, '$.customer.name'), (
+            SELECT group_concat(json_extract(value, '$.beverage_name') || ' '
+                || json_extract(value, '$.beverage_description'), ' ')
+            FROM json_each(new.body, '$.items')
+        ));
+    END;
+    INSERT INTO orders_fts (rowid, customer_name, items)
+    SELECT rowid, json_extract(body, '$.customer.name'), (
+        SELECT group_concat(json_extract(value, '$.beverage_name') || ' '
+            || json_extract(value, '$.beverage_description'), ' ')
+        FROM json_each(orders.body, '$.items')
+    )
+    FROM orders",
+];
+
+/// SQLite order repository
+///
+/// USE CASE:
+/// Perfect for:
+/// - One shop's history over years (writes don't grow with the file)
+/// - Ad hoc queries with any SQLite client
+pub struct SqliteOrderRepository {
+    connection: Connection,
+}
+
+impl SqliteOrderRepository {
+    /// Open (or create) the database at `path` and migrate it
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        let connection = Connection::open(path.as_ref()).map_err(|e| {
+            RepositoryError::LoadFailed(format!("Failed to open database: {}", e))
+        })?;
+        Self::with_connection(connection)
+    }
+
+    /// A private database that disappears with the repository (tests, demos)
+    pub fn in_memory() -> Result<Self, RepositoryError> {
+        let connection = Connection::open_in_memory().map_err(|e| {
+            RepositoryError::LoadFailed(format!("Failed to open database: {}", e))
+        })?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(mut connection: Connection) -> Result<Self, RepositoryError> {
+        migrate(&mut connection)?;
+        Ok(Self { connection })
+    }
+
+    /// Schema version of the open database (the number of migrations applied)
+    pub fn schema_version(&self) -> Result<usize, RepositoryError> {
+        user_version(&self.connection)
+    }
+
+    fn query_orders(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Order>, RepositoryError> {
+        let mut statement = self.connection.prepare(sql).map_err(load_failed)?;
+        let bodies = statement
+            .query_map(params, |row| row.get::<_, String>(0))
+            .map_err(load_failed)?;
+        bodies
+            .map(|body| decode(&body.map_err(load_failed)?))
+            .collect()
+    }
+}
+
+/// Bring the schema up to the last migration, all steps in one transaction
+fn migrate(connection: &mut Connection) -> Result<(), RepositoryError> {
+    let applied = user_version(connection)?;
+    if applied > MIGRATIONS.len() {
+        return Err(RepositoryError::LoadFailed(format!(
+            "Database schema version {} is newer than this build knows ({})",
+            applied,
+            MIGRATIONS.len()
+        )));
+    }
+
+    let transaction = connection.transaction().map_err(migration_failed)?;
+    for step in &MIGRATIONS[applied..] {
+        transaction.execute_batch(step).map_err(migration_failed)?;
+    }
+    transaction
+        .pragma_update(None, "user_version", MIGRATIONS.len() as i64)
+        .map_err(migration_failed)?;
+    transaction.commit().map_err(migration_failed)
+}
+
+fn user_version(connection: &Connection) -> Result<usize, RepositoryError> {
+    connection
+        .pragma_query_value(None, "user_version", |row| row.get::<_, i64>(0))
+        .map(|version| version as usize)
+        .map_err(load_failed)
+}
+
+fn insert(connection: &Connection, order: &Order) -> Result<(), RepositoryError> {
+    refuse_card_numbers(order)?;
+    let result = connection.execute(
+        "INSERT INTO orders (id, customer_email, status, created_at, body)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            order.id.to_string(),
+            order.customer.email,
+            order.status.to_string(),
+            order.created_at.to_rfc3339(),
+            encode(order)?,
+        ],
+    );
+    match result {
+        Ok(_) => Ok(()),
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == ErrorCode::ConstraintViolation => {
+            Err(RepositoryError::AlreadyExists(format!("Order {} already exists", order.id)))
+        }
+        Err(e) => Err(save_failed(e)),
+    }
+}
+
+fn encode(order: &Order) -> Result<String, RepositoryError> {
+    serde_json::to_string(order).map_err(|e| {
+        RepositoryError::SaveFailed(format!("Failed to serialize order: {}", e))
+    })
+}
+
+fn decode(body: &str) -> Result<Order, RepositoryError> {
+    serde_json::from_str(body)
+        .map_err(|e| RepositoryError::LoadFailed(format!("Failed to decode order: {}", e)))
+}
+
+fn load_failed(e: rusqlite::Error) -> RepositoryError {
+    RepositoryError::LoadFailed(format!("Database query failed: {}", e))
+}
+
+fn save_failed(e: rusqlite::Error) -> RepositoryError {
+    RepositoryError::SaveFailed(format!("Database write failed: {}", e))
+}
+
+fn migration_failed(e: rusqlite::Error) -> RepositoryError {
+    RepositoryError::LoadFailed(format!("Database migration failed: {}", e))
+}
+
+impl OrderRepository for SqliteOrderRepository {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        insert(&self.connection, order)
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        self.connection
+            .query_row("SELECT body FROM orders WHERE id = ?1", [id.to_string()], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()
+            .map_err(load_failed)?
+            .map(|body| decode(&body))
+            .transpose()
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.query_orders("SELECT body FROM orders WHERE customer_email = ?1", [email])
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.query_orders("SELECT body FROM orders", [])
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        refuse_card_numbers(order)?;
+        let changed = self
+            .connection
+            .execute(
+                "UPDATE orders SET customer_email = ?2, status = ?3, created_at = ?4, body = ?5
+                 WHERE id = ?1",
+                params![
+                    order.id.to_string(),
+                    order.customer.email,
+                    order.status.to_string(),
+                    order.created_at.to_rfc3339(),
+                    encode(order)?,
+                ],
+            )
+            .map_err(save_failed)?;
+        if changed == 0 {
+            return Err(RepositoryError::NotFound(format!("Order {} not found", order.id)));
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        let deleted = self
+            .connection
+            .execute("DELETE FROM orders WHERE id = ?1", [id.to_string()])
+            .map_err(save_failed)?;
+        Ok(deleted > 0)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        // Dropping the transaction on an error rolls the batch back
+        let transaction = self.connection.transaction().map_err(save_failed)?;
+        for order in orders {
+            insert(&transaction, order)?;
+        }
+        transaction.commit().map_err(save_failed)
+    }
+}
+
+/// Batches run in one SQLite transaction; text search uses the FTS5 index
+impl RepositoryCapabilities for SqliteOrderRepository {
+    fn supports_transactions(&self) -> bool {
+        true
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        // "ada lat" -> "ada"* "lat"*: the words the tokenizer would see, quoted,
+        // so nothing typed is read as FTS5 syntax
+        let terms: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{}\"*", word))
+            .collect();
+        if terms.is_empty() {
+            return Some(Ok(Vec::new()));
+        }
+        Some(self.query_orders(
+            "SELECT orders.body FROM orders_fts JOIN orders ON orders.rowid = orders_fts.rowid
+             WHERE orders_fts MATCH ?1 ORDER BY orders_fts.rank",
+            [terms.join(" ")],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer, OrderStatus};
+    use std::fs;
+
+    /// Every call's outcome, as text, so two backends can be compared
+    fn exercise(repository: &mut dyn OrderRepository, orders: &[Order]) -> Vec<String> {
+        let sorted = |mut found: Vec<Order>| {
+            found.sort_by_key(|o| o.id);
+            found.iter().map(|o| format!("{}:{}", o.id, o.status)).collect::<Vec<_>>()
+        };
+        let mut outcomes = vec![
+            format!("{:?}", repository.save(&orders[0]).is_ok()),
+            format!("{:?}", repository.save(&orders[0]).err().map(|e| e.to_string())),
+            format!("{:?}", repository.find_by_id(orders[0].id).unwrap().map(|o| o.id)),
+            format!("{:?}", repository.find_by_id(Uuid::nil()).unwrap()),
+        ];
+
+        let mut paid = orders[0].clone();
+        paid.mark_as_paid("CC-1".to_string()).unwrap();
+        outcomes.push(format!("{:?}", repository.update(&paid).is_ok()));
+        let stored = repository.find_by_id(paid.id).unwrap().unwrap();
+        outcomes.push(format!("{} {:?}", stored.status, stored.payment_id));
+        let missing = repository.update(&orders[1]).err().map(|e| e.to_string());
+        outcomes.push(format!("{:?}", missing));
+
+        // A duplicate inside the batch: nothing of it is saved
+        let bad_batch = [orders[1].clone(), orders[0].clone()];
+        outcomes.push(format!("{:?}", repository.save_batch(&bad_batch).is_err()));
+        outcomes.push(format!("{:?}", repository.save_batch(&orders[1..]).is_ok()));
+
+        outcomes.extend(sorted(repository.find_by_customer_email("ada@example.com").unwrap()));
+        outcomes.extend(sorted(repository.find_by_customer_email("nobody@example.com").unwrap()));
+        outcomes.push(format!("{:?}", repository.delete(orders[1].id).unwrap()));
+        outcomes.push(format!("{:?}", repository.delete(orders[1].id).unwrap()));
+        outcomes.extend(sorted(repository.list_all().unwrap()));
+        outcomes
+    }
+
+    #[test]
+    fn test_behaves_like_memory_repository() {
+        let orders = vec![
+            Order::single(Customer::named("Ada"), Coffee::medium()),
+            Order::single(Customer::named("Ada"), Coffee::small()),
+            Order::single(Customer::named("Grace"), Coffee::medium()),
+        ];
+
+        let mut memory = MemoryOrderRepository::new();
+        let mut sqlite = SqliteOrderRepository::in_memory().unwrap();
+        let expected = exercise(&mut memory, &orders);
+        assert_eq!(exercise(&mut sqlite, &orders), expected);
+        assert!(expected.iter().any(|line| line.contains(&OrderStatus::Paid.to_string())));
+        assert!(sqlite.supports_transactions());
+    }
+
+    #[test]
+    fn test_migrates_on_open_and_indexes_email() {
+        let path = std::env::temp_dir().join(format!("orders-{}.db", Uuid::new_v4()));
+        let order = Order::single(Customer::named("Ada"), Coffee::medium());
+        {
+            let mut repository = SqliteOrderRepository::open(&path).unwrap();
+            assert_eq!(repository.schema_version().unwrap(), MIGRATIONS.len());
+            repository.save(&order).unwrap();
+        }
+
+        let repository = SqliteOrderRepository::open(&path).unwrap();
+        assert_eq!(repository.find_by_id(order.id).unwrap().unwrap().id, order.id);
+        let plan: String = repository
+            .connection
+            .query_row(
+                "EXPLAIN QUERY PLAN SELECT body FROM orders WHERE customer_email = ?1",
+                ["ada@example.com"],
+                |row| row.get(3),
+            )
+            .unwrap();
+        assert!(plan.contains("idx_orders_customer_email"), "{}", plan);
+
+        // Orders saved before the index existed are indexed by the migration
+        let rollback = "DROP TABLE orders_fts; DROP TRIGGER orders_fts_insert;
+            DROP TRIGGER orders_fts_delete; DROP TRIGGER orders_fts_update;";
+        repository.connection.execute_batch(rollback).unwrap();
+        repository.connection.pragma_update(None, "user_version", 2).unwrap();
+        drop(repository);
+        let repository = SqliteOrderRepository::open(&path).unwrap();
+        let found = repository.full_text_search("ada").unwrap().unwrap();
+        assert_eq!(found.iter().map(|o| o.id).collect::<Vec<_>>(), [order.id]);
+
+        // A database from a newer build is refused, not misread
+        repository.connection.pragma_update(None, "user_version", 99).unwrap();
+        drop(repository);
+        assert!(SqliteOrderRepository::open(&path).is_err());
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,289 @@
+// SOLID: Store credit wallets (in memory, JSON file, shared handle)
+//
+// - MemoryStoreCredit: for tests, demos and a single process
+// - JsonStoreCredit: wallets.json, rewritten on every change
+// - SharedStoreCredit: a cloneable handle over either, so the register's
+//   WalletPayment and the IssueService granting remedies see one balance
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Same StoreCredit contract for all three. Emails are compared
+// case-insensitively, like the digest's sent-mail keys.
+
+use crate::domain::{Money, Wallet};
+use crate::ports::{CreditError, StoreCredit};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Wallets kept in a Vec
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStoreCredit {
+    wallets: Vec<Wallet>,
+}
+
+impl MemoryStoreCredit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn wallet(&self, customer_email: &str) -> Option<&Wallet> {
+        let email = customer_email.to_lowercase();
+        self.wallets.iter().find(|w| w.customer_email == email)
+    }
+
+    fn wallet_mut(&mut self, customer_email: &str) -> &mut Wallet {
+        let email = customer_email.to_lowercase();
+        match self.wallets.iter().position(|w| w.customer_email == email) {
+            Some(index) => &mut self.wallets[index],
+            None => {
+                self.wallets.push(Wallet::new(&email));
+                self.wallets.last_mut().expect("just pushed")
+            }
+        }
+    }
+}
+
+impl StoreCredit for MemoryStoreCredit {
+    fn grant(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        reason: &str,
+    ) -> Result<String, CreditError> {
+        let wallet = self.wallet_mut(customer_email);
+        check_amount(wallet, amount)?;
+        Ok(wallet.grant(amount, reason).reference.clone())
+    }
+
+    fn balance(&self, customer_email: &str) -> Result<Money, CreditError> {
+        Ok(self.wallet(customer_email).map(Wallet::spendable).unwrap_or_default())
+    }
+
+    fn spend(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        order_id: Uuid,
+    ) -> Result<String, CreditError> {
+        if self.wallet(customer_email).is_none() {
+            let balance = Money::zero(amount.currency);
+            return Err(CreditError::InsufficientCredit { balance });
+        }
+        let wallet = self.wallet_mut(customer_email);
+        check_amount(wallet, amount)?;
+        wallet
+            .spend(amount, order_id)
+            .map(|entry| entry.reference.clone())
+            .map_err(|balance| CreditError::InsufficientCredit { balance })
+    }
+
+    fn restore(&mut self, spend_reference: &str, amount: Money) -> Result<String, CreditError> {
+        let unknown = || CreditError::UnknownReference(spend_reference.to_string());
+        let wallet = self
+            .wallets
+            .iter_mut()
+            .find(|wallet| wallet.entries.iter().any(|e| e.reference == spend_reference))
+            .ok_or_else(unknown)?;
+        check_amount(wallet, amount)?;
+        wallet
+            .restore(spend_reference, amount)
+            .map(|entry| entry.reference.clone())
+            .ok_or_else(unknown)
+    }
+
+    fn freeze(&mut self, customer_email: &str, reason: &str) -> Result<(), CreditError> {
+        self.wallet_mut(customer_email).freeze(reason);
+        Ok(())
+    }
+
+    fn wallets(&self) -> Result<Vec<Wallet>, CreditError> {
+        Ok(self.wallets.clone())
+    }
+}
+
+/// Positive, and in the wallet's currency once it has one
+fn check_amount(wallet: &Wallet, amount: Money) -> Result<(), CreditError> {
+    if amount.cents <= 0 {
+        return Err(CreditError::InvalidAmount(amount.to_string()));
+    }
+    match wallet.currency() {
+        Some(currency) if currency != amount.currency => Err(CreditError::InvalidAmount(format!(
+            "{} for credit held in {}",
+            amount, currency
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Wallets persisted to a JSON file (rewritten on every change)
+pub struct JsonStoreCredit {
+    file_path: PathBuf,
+    inner: MemoryStoreCredit,
+}
+
+impl JsonStoreCredit {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, CreditError> {
+        let wallets = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| CreditError::StorageFailed(format!("Failed to read file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| CreditError::StorageFailed(format!("Failed to parse JSON: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryStoreCredit { wallets },
+        })
+    }
+
+    fn save(&self) -> Result<(), CreditError> {
+        let json = serde_json::to_string_pretty(&self.inner.wallets)
+            .map_err(|e| CreditError::StorageFailed(format!("Failed to serialize: {}", e)))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| CreditError::StorageFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl StoreCredit for JsonStoreCredit {
+    fn grant(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        reason: &str,
+    ) -> Result<String, CreditError> {
+        let reference = self.inner.grant(customer_email, amount, reason)?;
+        self.save()?;
+        Ok(reference)
+    }
+
+    fn balance(&self, customer_email: &str) -> Result<Money, CreditError> {
+        self.inner.balance(customer_email)
+    }
+
+    fn spend(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        order_id: Uuid,
+    ) -> Result<String, CreditError> {
+        let reference = self.inner.spend(customer_email, amount, order_id)?;
+        self.save()?;
+        Ok(reference)
+    }
+
+    fn restore(&mut self, spend_reference: &str, amount: Money) -> Result<String, CreditError> {
+        let reference = self.inner.restore(spend_reference, amount)?;
+        self.save()?;
+        Ok(reference)
+    }
+
+    fn freeze(&mut self, customer_email: &str, reason: &str) -> Result<(), CreditError> {
+        self.inner.freeze(customer_email, reason)?;
+        self.save()
+    }
+
+    fn wallets(&self) -> Result<Vec<Wallet>, CreditError> {
+        self.inner.wallets()
+    }
+}
+
+/// Thread-safe, cloneable wrapper around any StoreCredit
+pub struct SharedStoreCredit<C: StoreCredit> {
+    inner: Arc<Mutex<C>>,
+}
+
+impl<C: StoreCredit> SharedStoreCredit<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, C>, CreditError> {
+        self.inner
+            .lock()
+            .map_err(|e| CreditError::StorageFailed(format!("Store credit lock poisoned: {}", e)))
+    }
+}
+
+// Manual impl: cloning the handle must not require C: Clone
+impl<C: StoreCredit> Clone for SharedStoreCredit<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<C: StoreCredit> StoreCredit for SharedStoreCredit<C> {
+    fn grant(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        reason: &str,
+    ) -> Result<String, CreditError> {
+        self.lock()?.grant(customer_email, amount, reason)
+    }
+
+    fn balance(&self, customer_email: &str) -> Result<Money, CreditError> {
+        self.lock()?.balance(customer_email)
+    }
+
+    fn spend(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        order_id: Uuid,
+    ) -> Result<String, CreditError> {
+        self.lock()?.spend(customer_email, amount, order_id)
+    }
+
+    fn restore(&mut self, spend_reference: &str, amount: Money) -> Result<String, CreditError> {
+        self.lock()?.restore(spend_reference, amount)
+    }
+
+    fn freeze(&mut self, customer_email: &str, reason: &str) -> Result<(), CreditError> {
+        self.lock()?.freeze(customer_email, reason)
+    }
+
+    fn wallets(&self) -> Result<Vec<Wallet>, CreditError> {
+        self.lock()?.wallets()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Currency;
+
+    #[test]
+    fn test_json_store_credit_persists_and_shares() {
+        let path = std::env::temp_dir().join(format!("wallets-{}.json", Uuid::new_v4()));
+        let mut shared = SharedStoreCredit::new(JsonStoreCredit::new(path.clone()).unwrap());
+        let mut register = shared.clone();
+
+        shared.grant("Ada@Example.com", Money::usd(5.0), "Cold drink").unwrap();
+        let refused = shared.grant("ada@example.com", Money::usd(-1.0), "x");
+        assert!(matches!(refused, Err(CreditError::InvalidAmount(_))));
+        let francs = shared.grant("ada@example.com", Money::new(500, Currency::CHF), "x");
+        assert!(matches!(francs, Err(CreditError::InvalidAmount(_))));
+        let spend = register.spend("ada@example.com", Money::usd(2.0), Uuid::new_v4()).unwrap();
+        assert_eq!(
+            register.spend("ada@example.com", Money::usd(4.0), Uuid::new_v4()),
+            Err(CreditError::InsufficientCredit {
+                balance: Money::usd(3.0)
+            })
+        );
+
+        let mut reloaded = JsonStoreCredit::new(path.clone()).unwrap();
+        assert_eq!(reloaded.balance("ADA@example.com").unwrap(), Money::usd(3.0));
+        reloaded.restore(&spend, Money::usd(2.0)).unwrap();
+        assert_eq!(reloaded.balance("ada@example.com").unwrap(), Money::usd(5.0));
+        assert_eq!(reloaded.wallets().unwrap()[0].entries.len(), 3);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,459 @@
+// SOLID: StripePayment - card payments through the Stripe API (feature `stripe`)
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// A real gateway behind the same PaymentProcessor port as the simulated
+// CreditCardPayment; OrderService, RefundService and PaymentRouter take it
+// unchanged.
+//
+// A charge is a PaymentIntent created and confirmed in one call
+// (POST /v1/payment_intents, confirm=true) on the shop's PaymentMethod; a
+// refund is POST /v1/refunds. Amounts go in minor units (350 for $3.50) and
+// each request carries an `Idempotency-Key` that a retry sends again, so
+// Stripe answers it with the first result instead of moving money twice:
+// the PaymentRequest's key for a charge, the key `refund_with_key` gets for a
+// refund (a retry repeats it, a second refund of the same amount has its
+// own). A bare `process_payment` or `refund` has nothing to key on and is
+// refused.
+// Only the order ID and the opaque customer reference go to Stripe, never
+// the customer's email.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Stripe's errors become the port's PaymentErrors: a decline for lack of
+// funds is InsufficientFunds, any other card error InvalidCard, rate limits,
+// Stripe outages and transport failures NetworkError (worth a retry), the
+// rest ProcessingFailed with Stripe's message and code.
+//
+// Modes:
+// - `new(secret_key)`: api.stripe.com. A `sk_test_` key is Stripe's sandbox,
+//   where no money moves and the test PaymentMethods (pm_card_visa...) work
+// - `with_base_url`: any server speaking the API, such as stripe-mock
+// - `mock()`: no network. An in-process stand-in that answers the test
+//   PaymentMethods the way the sandbox does, for tests and demos
+
+use crate::domain::Money;
+use crate::ports::{PaymentError, PaymentProcessor, PaymentRequest};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const STRIPE_API: &str = "https://api.stripe.com";
+
+/// Stripe's test card that always succeeds
+const TEST_PAYMENT_METHOD: &str = "pm_card_visa";
+
+/// Card payments through Stripe PaymentIntents
+pub struct StripePayment {
+    backend: Backend,
+    /// The PaymentMethod charged: the card reader's or the saved card's
+    payment_method: String,
+}
+
+enum Backend {
+    Api {
+        agent: ureq::Agent,
+        base_url: String,
+        secret_key: String,
+    },
+    Mock(Mutex<MockStripe>),
+}
+
+impl StripePayment {
+    /// Talk to api.stripe.com with `secret_key` (sk_live_... or sk_test_...)
+    pub fn new(secret_key: &str) -> Self {
+        Self {
+            backend: Backend::Api {
+                agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+                base_url: STRIPE_API.to_string(),
+                secret_key: secret_key.to_string(),
+            },
+            payment_method: TEST_PAYMENT_METHOD.to_string(),
+        }
+    }
+
+    /// Answer in-process, as Stripe's test mode would; nothing leaves the machine
+    pub fn mock() -> Self {
+        Self {
+            backend: Backend::Mock(Mutex::new(MockStripe::default())),
+            payment_method: TEST_PAYMENT_METHOD.to_string(),
+        }
+    }
+
+    /// Send requests to `base_url` instead (stripe-mock: "http://localhost:12111")
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        if let Backend::Api { base_url, .. } = &mut self.backend {
+            *base_url = url.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    /// Charge this PaymentMethod (default: the `pm_card_visa` test card)
+    pub fn with_payment_method(mut self, payment_method: &str) -> Self {
+        self.payment_method = payment_method.to_string();
+        self
+    }
+
+    /// Create and confirm a PaymentIntent; its ID is the payment ID
+    fn charge(
+        &self,
+        amount: Money,
+        mut form: Vec<(&'static str, String)>,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        if amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Amount must be positive".to_string(),
+            ));
+        }
+        form.splice(
+            0..0,
+            [
+                ("amount", amount.cents.to_string()),
+                ("currency", amount.currency.code().to_lowercase()),
+                ("payment_method", self.payment_method.clone()),
+                ("confirm", "true".to_string()),
+            ],
+        );
+
+        let intent = self.post("/v1/payment_intents", &form, idempotency_key)?;
+        let id = intent["id"].as_str().unwrap_or_default().to_string();
+        match intent["status"].as_str() {
+            Some("succeeded") if !id.is_empty() => Ok(id),
+            Some("requires_action") => Err(PaymentError::ProcessingFailed(format!(
+                "Payment {} needs the cardholder to authenticate (3-D Secure)",
+                id
+            ))),
+            status => Err(PaymentError::ProcessingFailed(format!(
+                "Payment {} is {}",
+                id,
+                status.unwrap_or("in an unknown state")
+            ))),
+        }
+    }
+
+    fn post(
+        &self,
+        path: &str,
+        form: &[(&str, String)],
+        idempotency_key: &str,
+    ) -> Result<Value, PaymentError> {
+        let (status, body) = match &self.backend {
+            Backend::Api {
+                agent,
+                base_url,
+                secret_key,
+            } => {
+                let form: Vec<(&str, &str)> =
+                    form.iter().map(|(name, value)| (*name, value.as_str())).collect();
+                let response = match agent
+                    .post(&format!("{}{}", base_url, path))
+                    .set("Authorization", &format!("Bearer {}", secret_key))
+                    .set("Idempotency-Key", idempotency_key)
+                    .send_form(&form)
+                {
+                    Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+                    Err(e) => return Err(PaymentError::NetworkError(e.to_string())),
+                };
+                let status = response.status();
+                let text = response
+                    .into_string()
+                    .map_err(|e| PaymentError::NetworkError(e.to_string()))?;
+                let body = serde_json::from_str(&text).map_err(|e| {
+                    PaymentError::NetworkError(format!("Unreadable Stripe response: {}", e))
+                })?;
+                (status, body)
+            }
+            Backend::Mock(stripe) => stripe
+                .lock()
+                .map_err(|e| PaymentError::ProcessingFailed(format!("Mock poisoned: {}", e)))?
+                .post(path, form, idempotency_key),
+        };
+
+        if (200..300).contains(&status) {
+            Ok(body)
+        } else {
+            Err(payment_error(status, &body))
+        }
+    }
+}
+
+/// Stripe's `{"error": {...}}` answer as the port's error
+fn payment_error(status: u16, body: &Value) -> PaymentError {
+    let error = &body["error"];
+    let message = error["message"].as_str().unwrap_or("no message");
+    let code = error["code"].as_str();
+
+    if code == Some("insufficient_funds") || error["decline_code"] == "insufficient_funds" {
+        return PaymentError::InsufficientFunds;
+    }
+    match (status, error["type"].as_str()) {
+        (_, Some("card_error")) => PaymentError::InvalidCard,
+        (429, _) | (500.., _) | (_, Some("api_error")) => {
+            PaymentError::NetworkError(format!("Stripe answered {}: {}", status, message))
+        }
+        (401, _) => PaymentError::ProcessingFailed("Stripe refused the API key".to_string()),
+        _ => PaymentError::ProcessingFailed(match code {
+            Some(code) => format!("Stripe: {} ({})", message, code),
+            None => format!("Stripe: {}", message),
+        }),
+    }
+}
+
+impl PaymentProcessor for StripePayment {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        // No order to key on: a retry would be a second charge
+        Err(PaymentError::ProcessingFailed(format!(
+            "Stripe charges go through process_request, with the order's key ({} not charged)",
+            amount
+        )))
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        let order_id = request.order_id.to_string();
+        let form = vec![
+            ("description", format!("Order {}", &order_id[..8])),
+            ("metadata[order_id]", order_id),
+            ("metadata[customer_ref]", request.customer_ref.clone()),
+        ];
+        self.charge(request.amount, form, &request.idempotency_key)
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        // No key: a retry would be a second refund
+        Err(PaymentError::ProcessingFailed(format!(
+            "Stripe refunds go through refund_with_key ({} not refunded on {})",
+            amount, payment_id
+        )))
+    }
+
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        if amount.cents <= 0 {
+            return Err(PaymentError::ProcessingFailed(
+                "Refund amount must be positive".to_string(),
+            ));
+        }
+        let form = [
+            ("payment_intent", payment_id.to_string()),
+            ("amount", amount.cents.to_string()),
+        ];
+        let refund = self.post("/v1/refunds", &form, idempotency_key)?;
+        refund["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| PaymentError::ProcessingFailed("Refund without an ID".to_string()))
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "Stripe"
+    }
+}
+
+/// A request's parameters and the (status, body) answered to it
+type Reply = (Vec<(String, String)>, (u16, Value));
+
+/// What Stripe's test mode does with its test PaymentMethods, kept in memory
+#[derive(Default)]
+struct MockStripe {
+    /// Idempotency key -> the request's parameters and the answer given
+    replies: HashMap<String, Reply>,
+    /// PaymentIntent ID -> (amount, amount refunded), in cents
+    intents: HashMap<String, (i64, i64)>,
+    next_id: u32,
+}
+
+impl MockStripe {
+    fn post(&mut self, path: &str, form: &[(&str, String)], key: &str) -> (u16, Value) {
+        let params: Vec<(String, String)> =
+            form.iter().map(|(name, value)| (name.to_string(), value.clone())).collect();
+        if let Some((seen, reply)) = self.replies.get(key) {
+            if *seen == params {
+                return reply.clone();
+            }
+            return stripe_error(
+                400,
+                "idempotency_error",
+                None,
+                "Keys for idempotent requests can only be used with the same parameters",
+            );
+        }
+
+        let reply = match path {
+            "/v1/payment_intents" => self.create_intent(&params),
+            "/v1/refunds" => self.create_refund(&params),
+            _ => stripe_error(404, "invalid_request_error", None, "Unrecognized request URL"),
+        };
+        self.replies.insert(key.to_string(), (params, reply.clone()));
+        reply
+    }
+
+    fn create_intent(&mut self, params: &[(String, String)]) -> (u16, Value) {
+        let amount: i64 = param(params, "amount").and_then(|a| a.parse().ok()).unwrap_or(0);
+        if amount < 50 {
+            let message = "Amount must be at least $0.50 usd";
+            return stripe_error(400, "invalid_request_error", Some("amount_too_small"), message);
+        }
+
+        let status = match param(params, "payment_method").unwrap_or_default() {
+            "pm_card_visa" | "pm_card_mastercard" | "pm_card_amex" => "succeeded",
+            "pm_card_authenticationRequired" => "requires_action",
+            "pm_card_visa_chargeDeclinedInsufficientFunds" => {
+                return card_declined("card_declined", Some("insufficient_funds"));
+            }
+            "pm_card_chargeDeclinedExpiredCard" => return card_declined("expired_card", None),
+            "pm_card_visa_chargeDeclined" => {
+                return card_declined("card_declined", Some("generic_decline"));
+            }
+            other => {
+                let message = format!("No such PaymentMethod: '{}'", other);
+                let code = Some("resource_missing");
+                return stripe_error(400, "invalid_request_error", code, &message);
+            }
+        };
+
+        self.next_id += 1;
+        let id = format!("pi_mock_{:06}", self.next_id);
+        if status == "succeeded" {
+            self.intents.insert(id.clone(), (amount, 0));
+        }
+        let currency = param(params, "currency").unwrap_or("usd");
+        let intent = json!({
+            "id": id, "object": "payment_intent", "amount": amount,
+            "currency": currency, "status": status,
+        });
+        (200, intent)
+    }
+
+    fn create_refund(&mut self, params: &[(String, String)]) -> (u16, Value) {
+        let intent = param(params, "payment_intent").unwrap_or_default();
+        let Some((charged, refunded)) = self.intents.get_mut(intent) else {
+            let message = format!("No such payment_intent: '{}'", intent);
+            return stripe_error(400, "invalid_request_error", Some("resource_missing"), &message);
+        };
+        let amount: i64 = param(params, "amount")
+            .and_then(|a| a.parse().ok())
+            .unwrap_or(*charged - *refunded);
+        if *refunded + amount > *charged {
+            let message = format!(
+                "Refund amount ({}) is greater than unrefunded amount ({})",
+                amount,
+                *charged - *refunded
+            );
+            return stripe_error(400, "invalid_request_error", None, &message);
+        }
+        *refunded += amount;
+
+        self.next_id += 1;
+        let refund = json!({
+            "id": format!("re_mock_{:06}", self.next_id), "object": "refund",
+            "amount": amount, "payment_intent": intent, "status": "succeeded",
+        });
+        (200, refund)
+    }
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+}
+
+fn stripe_error(status: u16, kind: &str, code: Option<&str>, message: &str) -> (u16, Value) {
+    (status, json!({ "error": { "type": kind, "code": code, "message": message } }))
+}
+
+fn card_declined(code: &str, decline_code: Option<&str>) -> (u16, Value) {
+    let error = json!({ "error": {
+        "type": "card_error", "code": code, "decline_code": decline_code,
+        "message": "Your card was declined.",
+    } });
+    (402, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Currency;
+    use uuid::Uuid;
+
+    fn request() -> PaymentRequest {
+        PaymentRequest {
+            amount: Money::usd(3.50),
+            order_id: Uuid::new_v4(),
+            customer_ref: Uuid::new_v4().to_string(),
+            customer_email: "ada@example.com".to_string(),
+            idempotency_key: format!("order-{}", Uuid::new_v4()),
+        }
+    }
+
+    #[test]
+    fn test_mock_charges_once_per_key_and_refunds() {
+        let stripe = StripePayment::mock();
+        let request = request();
+
+        let payment_id = stripe.process_request(&request).unwrap();
+        assert!(payment_id.starts_with("pi_mock_"));
+        assert_eq!(stripe.process_request(&request).unwrap(), payment_id);
+
+        let refund = |amount: f64, key: &str| {
+            stripe.refund_with_key(&payment_id, Money::usd(amount), key)
+        };
+        let refund_id = refund(1.00, "refund-1").unwrap();
+        assert!(refund_id.starts_with("re_mock_"));
+        // A retried refund is the same refund, not a second one
+        assert_eq!(refund(1.00, "refund-1").unwrap(), refund_id);
+        // Another refund of the same amount is one
+        let second = refund(1.00, "refund-2").unwrap();
+        assert_ne!(second, refund_id);
+        assert!(matches!(
+            refund(1.60, "refund-3"),
+            Err(PaymentError::ProcessingFailed(message)) if message.contains("greater than")
+        ));
+        assert!(refund(1.50, "refund-4").is_ok());
+        assert!(stripe.refund(&payment_id, Money::usd(0.10)).is_err());
+    }
+
+    #[test]
+    fn test_stripe_errors_map_to_payment_errors() {
+        let declined = |payment_method: &str| {
+            StripePayment::mock()
+                .with_payment_method(payment_method)
+                .process_request(&request())
+                .unwrap_err()
+        };
+
+        assert!(matches!(
+            declined("pm_card_visa_chargeDeclinedInsufficientFunds"),
+            PaymentError::InsufficientFunds
+        ));
+        assert!(matches!(declined("pm_card_chargeDeclinedExpiredCard"), PaymentError::InvalidCard));
+        assert!(matches!(
+            declined("pm_card_authenticationRequired"),
+            PaymentError::ProcessingFailed(message) if message.contains("3-D Secure")
+        ));
+        let mut small = request();
+        small.amount = Money::usd(0.30);
+        assert!(matches!(
+            StripePayment::mock().process_request(&small),
+            Err(PaymentError::ProcessingFailed(message)) if message.contains("amount_too_small")
+        ));
+        assert!(StripePayment::mock().process_payment(Money::usd(3.50)).is_err());
+
+        let stripe = StripePayment::mock();
+        let request = request();
+        stripe.process_request(&request).unwrap();
+        let mut changed = request.clone();
+        changed.amount = Money::new(350, Currency::EUR);
+        assert!(matches!(
+            stripe.process_request(&changed),
+            Err(PaymentError::ProcessingFailed(message)) if message.contains("same parameters")
+        ));
+
+        let outage = json!({ "error": { "type": "api_error", "message": "Try again" } });
+        assert!(matches!(payment_error(503, &outage), PaymentError::NetworkError(_)));
+        let limited = json!({ "error": { "type": "invalid_request_error", "code": "rate_limit" } });
+        assert!(matches!(payment_error(429, &limited), PaymentError::NetworkError(_)));
+    }
+}
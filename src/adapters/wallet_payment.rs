@@ -0,0 +1,228 @@
+// SOLID: WalletPayment - store credit first, then another payment method
+//
+// DECORATOR over any PaymentProcessor (OCP):
+// OrderService still sees one PaymentProcessor. This one takes what it can
+// from the customer's store credit and charges only the rest to the wrapped
+// processor (cash, card...). Neither OrderService nor the wrapped processor
+// changes.
+//
+// Credit held in another currency than the order's is left alone: all of it
+// goes to the wrapped processor.
+//
+// Payment IDs:
+// - paid entirely with credit: the wallet's `WALLET-...` spend reference
+// - split: the wrapped processor's ID (what the drawer or card statement
+//   shows); the credit part is in the wallet, tied to the order
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// Idempotent like any processor: the same idempotency key is charged once.
+
+use crate::domain::Money;
+use crate::ports::{CreditError, PaymentError, PaymentProcessor, PaymentRequest, StoreCredit};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// How a split payment was paid (for refunds)
+#[derive(Debug, Clone)]
+struct Split {
+    spend_reference: String,
+    charged: Money,
+}
+
+/// Pays from store credit before falling back to `fallback`
+pub struct WalletPayment<C: StoreCredit, P: PaymentProcessor> {
+    credit: Mutex<C>,
+    fallback: P,
+    // Idempotency key -> payment ID of payments already made
+    payments: Mutex<HashMap<String, String>>,
+    // Fallback payment ID -> the credit part of that payment
+    // (in memory: after a restart, a split is refunded through the fallback only)
+    splits: Mutex<HashMap<String, Split>>,
+}
+
+impl<C: StoreCredit, P: PaymentProcessor> WalletPayment<C, P> {
+    /// `credit` is usually a SharedStoreCredit, so remedies can add to it meanwhile
+    pub fn new(credit: C, fallback: P) -> Self {
+        Self {
+            credit: Mutex::new(credit),
+            fallback,
+            payments: Mutex::new(HashMap::new()),
+            splits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn credit(&self) -> Result<MutexGuard<'_, C>, PaymentError> {
+        self.credit
+            .lock()
+            .map_err(|_| PaymentError::ProcessingFailed("Store credit lock poisoned".to_string()))
+    }
+
+    /// Refund `amount`: the fallback part through `fallback_refund`, the rest to credit
+    fn give_back(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        fallback_refund: impl Fn(Money) -> Result<String, PaymentError>,
+    ) -> Result<String, PaymentError> {
+        if payment_id.starts_with("WALLET-") {
+            let restored = self.credit()?.restore(payment_id, amount);
+            return restored.map_err(credit_failed);
+        }
+
+        let split = self.splits.lock().unwrap().get(payment_id).cloned();
+        let Some(split) = split else {
+            return fallback_refund(amount);
+        };
+        let to_fallback = amount.min(split.charged);
+        let refund_id = fallback_refund(to_fallback)?;
+        let to_credit = amount - to_fallback;
+        if to_credit.cents > 0 {
+            self.credit()?
+                .restore(&split.spend_reference, to_credit)
+                .map_err(credit_failed)?;
+        }
+        Ok(refund_id)
+    }
+}
+
+fn credit_failed(e: CreditError) -> PaymentError {
+    PaymentError::ProcessingFailed(e.to_string())
+}
+
+impl<C: StoreCredit, P: PaymentProcessor> PaymentProcessor for WalletPayment<C, P> {
+    /// No customer to look up: all of it goes to the fallback
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        self.fallback.process_payment(amount)
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        if let Some(payment_id) = self.payments.lock().unwrap().get(&request.idempotency_key) {
+            return Ok(payment_id.clone());
+        }
+
+        // Held until the spend is recorded, so the balance can't change in between
+        let mut credit = self.credit()?;
+        let balance = credit.balance(&request.customer_email).map_err(credit_failed)?;
+        let due = request.amount;
+        if balance.currency != due.currency || balance.cents <= 0 || due.cents <= 0 {
+            return self.fallback.process_request(request);
+        }
+        let from_credit = balance.min(due);
+
+        let rest = due - from_credit;
+        let charged = if rest.cents > 0 {
+            let rest_request = PaymentRequest {
+                amount: rest,
+                idempotency_key: format!("{}-rest", request.idempotency_key),
+                ..request.clone()
+            };
+            Some(self.fallback.process_request(&rest_request)?)
+        } else {
+            None
+        };
+
+        let spent = credit.spend(&request.customer_email, from_credit, request.order_id);
+        let spend_reference = match spent {
+            Ok(reference) => reference,
+            Err(e) => {
+                // The rest was charged but the credit part failed: give the rest back
+                if let Some(payment_id) = &charged {
+                    let _ = self.fallback.refund(payment_id, rest);
+                }
+                return Err(credit_failed(e));
+            }
+        };
+
+        let payment_id = match charged {
+            Some(payment_id) => {
+                let split = Split {
+                    spend_reference,
+                    charged: rest,
+                };
+                self.splits.lock().unwrap().insert(payment_id.clone(), split);
+                payment_id
+            }
+            None => spend_reference,
+        };
+        self.payments
+            .lock()
+            .unwrap()
+            .insert(request.idempotency_key.clone(), payment_id.clone());
+        Ok(payment_id)
+    }
+
+    /// Money back where it came from: the fallback part first, then credit
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        self.give_back(payment_id, amount, |amount| self.fallback.refund(payment_id, amount))
+    }
+
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        self.give_back(payment_id, amount, |amount| {
+            self.fallback.refund_with_key(payment_id, amount, idempotency_key)
+        })
+    }
+
+    fn paid_with_points(&self, payment_id: &str) -> Option<Money> {
+        self.fallback.paid_with_points(payment_id)
+    }
+
+    fn payment_method_name(&self) -> &str {
+        "Store Credit"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, MemoryStoreCredit, SharedStoreCredit};
+    use crate::domain::Currency;
+    use uuid::Uuid;
+
+    fn request(amount: f64) -> PaymentRequest {
+        PaymentRequest {
+            amount: Money::usd(amount),
+            order_id: Uuid::new_v4(),
+            customer_ref: "customer-1".to_string(),
+            customer_email: "ada@example.com".to_string(),
+            idempotency_key: format!("order-{}", Uuid::new_v4()),
+        }
+    }
+
+    #[test]
+    fn test_credit_is_used_before_the_fallback() {
+        let mut credit = SharedStoreCredit::new(MemoryStoreCredit::new());
+        credit.grant("Ada@example.com", Money::usd(5.0), "Cold drink").unwrap();
+        let wallet = WalletPayment::new(credit.clone(), CashPayment);
+
+        // All of it from credit
+        let small = request(3.0);
+        let paid = wallet.process_request(&small).unwrap();
+        assert!(paid.starts_with("WALLET-"));
+        assert_eq!(wallet.process_request(&small).unwrap(), paid);
+        assert_eq!(credit.balance("ada@example.com").unwrap(), Money::usd(2.0));
+
+        // 2.00 from credit, the other 4.50 in cash
+        let split = wallet.process_request(&request(6.5)).unwrap();
+        assert!(split.starts_with("CASH-"));
+        assert_eq!(credit.balance("ada@example.com").unwrap(), Money::cents(0));
+
+        // Refund 5.00 of it: 4.50 in cash, 0.50 back to the wallet
+        wallet.refund(&split, Money::usd(5.0)).unwrap();
+        assert_eq!(credit.balance("ada@example.com").unwrap(), Money::usd(0.5));
+        wallet.refund(&paid, Money::usd(3.0)).unwrap();
+        assert_eq!(credit.balance("ada@example.com").unwrap(), Money::usd(3.5));
+
+        // Dollars of credit don't pay for an order in francs
+        let francs = PaymentRequest {
+            amount: Money::new(300, Currency::CHF),
+            ..request(0.0)
+        };
+        assert!(wallet.process_request(&francs).unwrap().starts_with("CASH-"));
+        assert_eq!(credit.balance("ada@example.com").unwrap(), Money::usd(3.5));
+    }
+}
@@ -0,0 +1,160 @@
+// SOLID: Webhook subscription stores (in memory, JSON file)
+//
+// Both implement WebhookSubscriptionStore with the same contract (LSP):
+// the admin CLI and the dispatcher can't tell them apart.
+
+use crate::domain::WebhookSubscription;
+use crate::ports::{WebhookError, WebhookSubscriptionStore};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Subscriptions kept in a Vec (tests, demos)
+#[derive(Debug, Default)]
+pub struct MemoryWebhookStore {
+    subscriptions: Vec<WebhookSubscription>,
+}
+
+impl MemoryWebhookStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WebhookSubscriptionStore for MemoryWebhookStore {
+    fn add(&mut self, subscription: WebhookSubscription) -> Result<(), WebhookError> {
+        if self.subscriptions.iter().any(|s| s.id == subscription.id) {
+            return Err(WebhookError::StoreFailed(format!(
+                "Subscription {} already exists",
+                subscription.id
+            )));
+        }
+        self.subscriptions.push(subscription);
+        Ok(())
+    }
+
+    fn update(&mut self, subscription: WebhookSubscription) -> Result<(), WebhookError> {
+        let existing = self
+            .subscriptions
+            .iter_mut()
+            .find(|s| s.id == subscription.id)
+            .ok_or(WebhookError::NotFound(subscription.id))?;
+        *existing = subscription;
+        Ok(())
+    }
+
+    fn remove(&mut self, id: Uuid) -> Result<bool, WebhookError> {
+        let before = self.subscriptions.len();
+        self.subscriptions.retain(|s| s.id != id);
+        Ok(self.subscriptions.len() != before)
+    }
+
+    fn find(&self, id: Uuid) -> Result<Option<WebhookSubscription>, WebhookError> {
+        Ok(self.subscriptions.iter().find(|s| s.id == id).cloned())
+    }
+
+    fn list(&self) -> Result<Vec<WebhookSubscription>, WebhookError> {
+        Ok(self.subscriptions.clone())
+    }
+}
+
+/// Subscriptions persisted to a JSON file (rewritten on every change)
+pub struct JsonWebhookStore {
+    file_path: PathBuf,
+    inner: MemoryWebhookStore,
+}
+
+impl JsonWebhookStore {
+    /// Load the file if it exists, otherwise start empty
+    pub fn new(file_path: PathBuf) -> Result<Self, WebhookError> {
+        let subscriptions = if file_path.exists() {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| WebhookError::StoreFailed(format!("Failed to read file: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| WebhookError::StoreFailed(format!("Failed to parse JSON: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            inner: MemoryWebhookStore { subscriptions },
+        })
+    }
+
+    fn save(&self) -> Result<(), WebhookError> {
+        let json = serde_json::to_string_pretty(&self.inner.subscriptions)
+            .map_err(|e| WebhookError::StoreFailed(format!("Failed to serialize: {}", e)))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| WebhookError::StoreFailed(format!("Failed to write file: {}", e)))
+    }
+}
+
+impl WebhookSubscriptionStore for JsonWebhookStore {
+    fn add(&mut self, subscription: WebhookSubscription) -> Result<(), WebhookError> {
+        self.inner.add(subscription)?;
+        self.save()
+    }
+
+    fn update(&mut self, subscription: WebhookSubscription) -> Result<(), WebhookError> {
+        self.inner.update(subscription)?;
+        self.save()
+    }
+
+    fn remove(&mut self, id: Uuid) -> Result<bool, WebhookError> {
+        let removed = self.inner.remove(id)?;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    fn find(&self, id: Uuid) -> Result<Option<WebhookSubscription>, WebhookError> {
+        self.inner.find(id)
+    }
+
+    fn list(&self) -> Result<Vec<WebhookSubscription>, WebhookError> {
+        self.inner.list()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription() -> WebhookSubscription {
+        WebhookSubscription::new(
+            "https://example.com/hook".to_string(),
+            "s3cret".to_string(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_memory_store_crud() {
+        let mut store = MemoryWebhookStore::new();
+        let mut sub = subscription();
+        store.add(sub.clone()).unwrap();
+        assert!(store.add(sub.clone()).is_err());
+
+        sub.active = false;
+        store.update(sub.clone()).unwrap();
+        assert!(!store.find(sub.id).unwrap().unwrap().active);
+
+        assert!(store.remove(sub.id).unwrap());
+        assert!(!store.remove(sub.id).unwrap());
+        assert_eq!(store.update(sub.clone()), Err(WebhookError::NotFound(sub.id)));
+    }
+
+    #[test]
+    fn test_json_store_persists() {
+        let path = std::env::temp_dir().join(format!("webhooks-{}.json", Uuid::new_v4()));
+        let sub = subscription();
+
+        JsonWebhookStore::new(path.clone()).unwrap().add(sub.clone()).unwrap();
+        let reloaded = JsonWebhookStore::new(path.clone()).unwrap();
+
+        assert_eq!(reloaded.list().unwrap(), vec![sub]);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,138 @@
+// SOLID: Webhook transports (how the HTTP POST is actually sent)
+//
+// - RecordingWebhookTransport: keeps requests in memory (tests, demos, dry runs)
+// - HttpWebhookTransport: real HTTP via `ureq`, behind the `http-client` feature
+//
+// The dispatcher only sees WebhookTransport, so the default build needs no HTTP crate.
+
+use crate::ports::{WebhookError, WebhookTransport};
+use std::sync::{Arc, Mutex};
+
+/// One request captured by RecordingWebhookTransport
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Records every request and answers with a fixed status
+///
+/// Clones share the same log, so a test can keep a handle after
+/// moving the transport into a dispatcher.
+#[derive(Debug, Clone)]
+pub struct RecordingWebhookTransport {
+    status: u16,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl RecordingWebhookTransport {
+    /// Answers 200 to everything
+    pub fn new() -> Self {
+        Self::answering(200)
+    }
+
+    /// Answers `status` to everything
+    pub fn answering(status: u16) -> Self {
+        Self {
+            status,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Default for RecordingWebhookTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookTransport for RecordingWebhookTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, WebhookError> {
+        self.requests.lock().unwrap().push(RecordedRequest {
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+            body: body.to_string(),
+        });
+
+        if (200..300).contains(&self.status) {
+            Ok(self.status)
+        } else {
+            Err(WebhookError::Rejected(self.status))
+        }
+    }
+}
+
+/// Real HTTP(S) POSTs
+#[cfg(feature = "http-client")]
+pub struct HttpWebhookTransport {
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "http-client")]
+impl HttpWebhookTransport {
+    /// Requests give up after `timeout`
+    pub fn new(timeout: std::time::Duration) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+        }
+    }
+}
+
+#[cfg(feature = "http-client")]
+impl WebhookTransport for HttpWebhookTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, WebhookError> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(WebhookError::InvalidUrl(url.to_string()));
+        }
+
+        let mut request = self.agent.post(url);
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+
+        match request.send_string(body) {
+            Ok(response) => Ok(response.status()),
+            Err(ureq::Error::Status(status, _)) => Err(WebhookError::Rejected(status)),
+            Err(e) => Err(WebhookError::DeliveryFailed(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_transport() {
+        let transport = RecordingWebhookTransport::answering(503);
+        let handle = transport.clone();
+
+        let result = transport.post(
+            "https://example.com/hook",
+            &[("Content-Type", "application/json".to_string())],
+            "{}",
+        );
+
+        assert_eq!(result, Err(WebhookError::Rejected(503)));
+        let requests = handle.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("content-type"), Some("application/json"));
+    }
+}
@@ -0,0 +1,235 @@
+// SOLID: GodOrderManager vs OrderService, measured
+//
+// Two kinds of numbers:
+//
+// 1. Coupling, read from the source code itself (comments and tests left out):
+//    - hard-wired: infrastructure the code calls directly (files, JSON, stdout,
+//      payment method and drink names as strings)
+//    - seams: port traits the design is generic over, i.e. what a caller can
+//      replace without editing the code
+//
+// 2. Test-setup cost, measured by placing the same orders with each design:
+//    files the setup leaves on disk, and the time it takes. OrderService runs
+//    on memory, a silent fake processor and a console writing nowhere; the
+//    God class has no such option, so its test needs a real file and prints.
+
+use super::GodOrderManager;
+use crate::adapters::{ConsoleNotifier, MemoryOrderRepository};
+use crate::domain::{Beverage, Coffee, Customer, Money, Size};
+use crate::ports::{PaymentError, PaymentProcessor};
+use crate::services::OrderService;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const GOD_SOURCE: &str = include_str!("god_order_manager.rs");
+const SOLID_SOURCE: &str = include_str!("../services/order_service.rs");
+
+/// Marker in the code -> what it ties the design to
+const INFRASTRUCTURE: [(&str, &str); 5] = [
+    ("fs::", "filesystem"),
+    ("serde_json::", "JSON file format"),
+    ("println!", "stdout"),
+    ("\"cash\"", "payment methods (by name)"),
+    ("\"coffee\"", "menu (by name)"),
+];
+
+const PORTS: [&str; 3] = ["OrderRepository", "PaymentProcessor", "Notifier"];
+
+/// One design, measured
+#[derive(Debug, Clone)]
+pub struct DesignProfile {
+    pub design: &'static str,
+    pub hard_wired: Vec<&'static str>,
+    pub seams: usize,
+    /// Files left behind by placing the orders
+    pub setup_files: usize,
+    pub elapsed: Duration,
+}
+
+/// Both designs, placing the same orders
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub orders: usize,
+    pub god: DesignProfile,
+    pub solid: DesignProfile,
+}
+
+/// Place `orders` medium coffees with each design and measure both
+pub fn compare(orders: usize) -> Result<Comparison, String> {
+    Ok(Comparison {
+        orders,
+        god: measure_god(orders)?,
+        solid: measure_solid(orders)?,
+    })
+}
+
+fn measure_god(orders: usize) -> Result<DesignProfile, String> {
+    let dir = std::env::temp_dir().join(format!("god-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let started = Instant::now();
+    let result = (|| {
+        let mut manager = GodOrderManager::new(dir.join("orders.json"), "cash")?;
+        for _ in 0..orders {
+            manager.place_order("Ada", "ada@example.com", "coffee", "M", 0)?;
+        }
+        Ok::<_, String>(())
+    })();
+    let elapsed = started.elapsed();
+    let setup_files = fs::read_dir(&dir).map(|entries| entries.count()).unwrap_or(0);
+    let _ = fs::remove_dir_all(&dir);
+    result?;
+
+    Ok(DesignProfile {
+        design: "GodOrderManager",
+        hard_wired: hard_wired(GOD_SOURCE),
+        seams: seams(GOD_SOURCE),
+        setup_files,
+        elapsed,
+    })
+}
+
+/// A test double: five lines, no output
+struct SilentPayment;
+
+impl PaymentProcessor for SilentPayment {
+    fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+        Ok(format!("TEST-{}", Uuid::new_v4()))
+    }
+}
+
+fn measure_solid(orders: usize) -> Result<DesignProfile, String> {
+    let started = Instant::now();
+    let notifier = ConsoleNotifier::with_writer(io::sink());
+    let mut service = OrderService::new(MemoryOrderRepository::new(), SilentPayment, notifier);
+    for _ in 0..orders {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let coffee: Box<dyn Beverage> = Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        });
+        service.place_order(customer, vec![coffee]).map_err(|e| e.to_string())?;
+    }
+
+    Ok(DesignProfile {
+        design: "OrderService",
+        hard_wired: hard_wired(SOLID_SOURCE),
+        seams: seams(SOLID_SOURCE),
+        setup_files: 0,
+        elapsed: started.elapsed(),
+    })
+}
+
+/// The source without comments and without its test module
+fn code(source: &str) -> String {
+    let source = source.split("#[cfg(test)]").next().unwrap_or_default();
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn hard_wired(source: &str) -> Vec<&'static str> {
+    let code = code(source);
+    INFRASTRUCTURE
+        .iter()
+        .filter(|(marker, _)| {
+            // `eprintln!` (a warning to the operator) is not `println!`
+            code.match_indices(marker).any(|(at, _)| {
+                !code[..at].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+            })
+        })
+        .map(|&(_, what)| what)
+        .collect()
+}
+
+fn seams(source: &str) -> usize {
+    let code = code(source);
+    PORTS
+        .iter()
+        .filter(|port| code.contains(&format!(": {}", port)))
+        .count()
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<28} {:<20} {}", "", self.god.design, self.solid.design)?;
+        let row = |f: &mut fmt::Formatter, label: &str, god: String, solid: String| {
+            writeln!(f, "{:<28} {:<20} {}", label, god, solid)
+        };
+        row(
+            f,
+            "Hard-wired infrastructure",
+            self.god.hard_wired.len().to_string(),
+            self.solid.hard_wired.len().to_string(),
+        )?;
+        row(
+            f,
+            "Replaceable parts (seams)",
+            self.god.seams.to_string(),
+            self.solid.seams.to_string(),
+        )?;
+        row(
+            f,
+            "Files left by the test",
+            self.god.setup_files.to_string(),
+            self.solid.setup_files.to_string(),
+        )?;
+        row(
+            f,
+            &format!("Time for {} order(s)", self.orders),
+            format!("{:.2?}", self.god.elapsed),
+            format!("{:.2?}", self.solid.elapsed),
+        )?;
+        writeln!(f, "\nGodOrderManager is tied to: {}", self.god.hard_wired.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coupling_read_from_the_source() {
+        let god = hard_wired(GOD_SOURCE);
+        assert!(god.contains(&"filesystem"));
+        assert!(god.contains(&"stdout"));
+        assert_eq!(god.len(), INFRASTRUCTURE.len());
+        assert!(hard_wired(SOLID_SOURCE).is_empty());
+        assert_eq!((seams(GOD_SOURCE), seams(SOLID_SOURCE)), (0, 3));
+    }
+
+    #[test]
+    fn test_god_class_needs_a_file_to_be_tested() {
+        let comparison = compare(3).unwrap();
+
+        assert_eq!(comparison.god.setup_files, 1);
+        assert_eq!(comparison.solid.setup_files, 0);
+        assert!(comparison.to_string().contains("Replaceable parts (seams)"));
+    }
+
+    #[test]
+    fn test_god_class_behaves_like_the_solid_design() {
+        let path = std::env::temp_dir().join(format!("god-{}.json", Uuid::new_v4()));
+        let mut manager = GodOrderManager::new(path.clone(), "card").unwrap();
+        let order = manager.place_order("Ada", "ada@example.com", "coffee", "L", 1).unwrap();
+        manager.mark_ready(order.id).unwrap();
+
+        // Same price as Coffee { Large, 1 shot } through OrderService
+        let coffee = Coffee {
+            size: Size::Large,
+            extra_shots: 1,
+        };
+        assert_eq!(order.total_price, coffee.price());
+        assert!(order.payment_id.as_deref().unwrap().starts_with("CC-"));
+        let reloaded = GodOrderManager::new(path.clone(), "card").unwrap();
+        assert_eq!(reloaded.orders().len(), 1);
+        assert!(reloaded.receipt(order.id).unwrap().contains("TOTAL"));
+        assert!(manager.place_order("Bob", "bob@example.com", "latte", "M", 0).is_err());
+        let _ = fs::remove_file(path);
+    }
+}
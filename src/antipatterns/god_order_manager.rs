@@ -0,0 +1,159 @@
+// SOLID: ANTI-PATTERN - GodOrderManager (read it, don't copy it)
+//
+// Everything OrderService, PricingCalculator, the payment processors, the
+// JSON repository, the console notifier, the receipt renderer and the daily
+// report do, in ONE struct. It works. It's even shorter. Now try to change it:
+//
+// - SRP broken: pricing, payment, storage, notification, receipts and
+//   reporting all live here. A new tax rule, a new email template and a new
+//   file format are three reasons to edit the same file.
+// - OCP broken: a new drink or payment method is a new `match` arm, in code
+//   every order goes through.
+// - LSP impossible: there is no abstraction to substitute. Storage IS a JSON
+//   file, payment IS a string comparison, notifications ARE println!.
+// - ISP broken: a report only needs orders, but has to construct the whole
+//   manager (and so needs a writable file).
+// - DIP broken: the high-level policy (place an order) calls the low-level
+//   details (fs::write, println!) directly.
+//
+// The comparison module measures what this costs (antipatterns/comparison.rs).
+
+use crate::domain::{Customer, Money, Order, OrderItem, OrderStatus};
+use chrono::NaiveDate;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Takes orders, prices them, charges them, saves them, tells the customer
+pub struct GodOrderManager {
+    file_path: PathBuf,
+    /// "cash" or "card"; anything else fails at the first order
+    payment_method: String,
+    orders: Vec<Order>,
+}
+
+impl GodOrderManager {
+    /// Reads `file_path` if it exists: there is no way to start without a file
+    pub fn new(file_path: PathBuf, payment_method: &str) -> Result<Self, String> {
+        let orders = if file_path.exists() {
+            let text = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+            serde_json::from_str(&text).map_err(|e| e.to_string())?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            file_path,
+            payment_method: payment_method.to_string(),
+            orders,
+        })
+    }
+
+    /// Price, charge, save and announce one drink
+    ///
+    /// `drink` is "coffee", "tea" or "smoothie"; `size` is "S", "M" or "L".
+    pub fn place_order(
+        &mut self,
+        name: &str,
+        email: &str,
+        drink: &str,
+        size: &str,
+        extra_shots: u8,
+    ) -> Result<Order, String> {
+        // Pricing (PricingCalculator's and each Beverage's job)
+        let base = match drink {
+            "coffee" => 3.50 + 0.75 * extra_shots as f64,
+            "tea" => 2.50,
+            "smoothie" => 5.00,
+            other => return Err(format!("we don't sell {}", other)),
+        };
+        let multiplier = match size {
+            "S" => 0.8,
+            "M" => 1.0,
+            "L" => 1.2,
+            other => return Err(format!("unknown size {}", other)),
+        };
+        let price = base * multiplier;
+
+        // Payment (PaymentProcessor's job)
+        let payment_id = if self.payment_method == "cash" {
+            println!("💵 Processing cash payment of ${:.2}", price);
+            format!("CASH-{}", Uuid::new_v4())
+        } else if self.payment_method == "card" {
+            if price > 1000.0 {
+                return Err("Amount exceeds card limit".to_string());
+            }
+            println!("💳 Processing credit card payment of ${:.2}", price);
+            format!("CC-{}", Uuid::new_v4())
+        } else {
+            return Err(format!("unknown payment method {}", self.payment_method));
+        };
+
+        let customer = Customer::new(name.to_string(), email.to_string(), None);
+        let mut order = Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: drink.to_string(),
+                beverage_description: format!("{} ({})", drink, size),
+                price: Money::usd(price),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        order.mark_as_paid(payment_id).map_err(|e| e.to_string())?;
+
+        // Storage (OrderRepository's job)
+        self.orders.push(order.clone());
+        self.save()?;
+
+        // Notification (Notifier's job)
+        println!("📧 Dear {}, your {} is paid: ${:.2}", name, drink, price);
+        Ok(order)
+    }
+
+    /// The barista finished the drink
+    pub fn mark_ready(&mut self, id: Uuid) -> Result<(), String> {
+        let order = self
+            .orders
+            .iter_mut()
+            .find(|o| o.id == id)
+            .ok_or_else(|| format!("no order {}", id))?;
+        order.mark_as_preparing().map_err(|e| e.to_string())?;
+        order.mark_as_ready().map_err(|e| e.to_string())?;
+        let name = order.customer.name.clone();
+        self.save()?;
+        println!("☕ {}, your order is ready!", name);
+        Ok(())
+    }
+
+    /// Revenue of one day (ReportingService's job)
+    pub fn daily_revenue(&self, date: NaiveDate) -> f64 {
+        self.orders
+            .iter()
+            .filter(|o| o.created_at.date_naive() == date && o.status != OrderStatus::Cancelled)
+            .fold(0.0, |sum, o| sum + o.total_price.to_major())
+    }
+
+    /// A receipt (the presentation layer's job)
+    pub fn receipt(&self, id: Uuid) -> Option<String> {
+        let order = self.orders.iter().find(|o| o.id == id)?;
+        let mut text = format!("Coffee Shop\n{}\n", order.customer.name);
+        for item in &order.items {
+            let price = item.price.to_major();
+            text.push_str(&format!("{:<24}${:>6.2}\n", item.beverage_description, price));
+        }
+        text.push_str(&format!("{:<24}${:>6.2}\n", "TOTAL", order.total_price.to_major()));
+        Some(text)
+    }
+
+    pub fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+
+    /// The whole file, rewritten on every change
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.orders).map_err(|e| e.to_string())?;
+        fs::write(&self.file_path, json).map_err(|e| e.to_string())
+    }
+}
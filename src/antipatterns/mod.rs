@@ -0,0 +1,20 @@
+// SOLID: Anti-patterns (feature `antipatterns`)
+//
+// The rest of the crate shows the SOLID design. This module shows the
+// design it replaces, so the two can be compared side by side:
+//
+// - GodOrderManager: one struct that prices, charges, stores, notifies and
+//   reports. It works, and it is what most first versions look like.
+// - comparison: coupling (read from both source files) and test-setup cost
+//   (measured by running both) for GodOrderManager vs OrderService.
+//
+// Off by default, so nobody reaches for GodOrderManager by accident:
+//
+//     cargo run --features antipatterns --bin antipatterns
+//     cargo test --features antipatterns antipatterns
+
+pub mod comparison;
+pub mod god_order_manager;
+
+pub use comparison::{compare, Comparison, DesignProfile};
+pub use god_order_manager::GodOrderManager;
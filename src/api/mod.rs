@@ -0,0 +1,365 @@
+// SOLID: The API Layer - OrderService over HTTP (feature `http`)
+//
+//   POST /orders                    place and pay an order       201, the order
+//   GET  /orders/{id}               one order                    200 / 404
+//   GET  /customers/{email}/orders  a customer's orders          200, a list
+//   POST /orders/{id}/cancel        cancel (refund if paid)      200, the order / 409
+//
+// PRINCIPLES:
+//
+// 1. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    OrderApi wraps an OrderService generic over the same three ports, so
+//    the HTTP service stores, charges and notifies with whatever adapters
+//    it is given: `cargo run --features http -- serve --file orders.json`
+//    and a test with MemoryOrderRepository run the same routes.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    This layer translates: JSON into beverages and customers, service
+//    errors into status codes. Prices, payments and status rules stay in
+//    OrderService; a request can't name a price.
+//
+// Routing is a plain function from (method, path, body) to a response
+// (`OrderApi::handle`, on `&self`), with no web framework in it; `serve` puts
+// it behind a minimal HTTP/1.1 server on std's TcpListener, with a fixed
+// pool of workers (a full queue is answered 429). The service is a
+// SharedOrderService, so requests run side by side and placements take
+// turns; `with_reads` lets lookups skip the line. A framework front end
+// only has to call `handle` from its catch-all route, from as many workers
+// as it likes.
+//
+// Errors are `{"error": "..."}` with the service's message.
+
+mod server;
+
+pub use server::{respond, serve, serve_with_limits, ServerLimits};
+
+use crate::domain::{Beverage, BeverageError, Coffee, Customer, OrderLine, Size, Smoothie, Tea};
+use crate::ports::{Notifier, OrderRepository, PaymentProcessor};
+use crate::services::{OrderService, OrderServiceError, SharedOrderService};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// A status code and a JSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    fn json(status: u16, body: &impl Serialize) -> Self {
+        match serde_json::to_value(body) {
+            Ok(body) => Self { status, body },
+            Err(e) => Self::error(500, &format!("Failed to serialize: {}", e)),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+}
+
+/// `POST /orders`
+///
+/// `{"customer": {"name": "Ada", "email": "ada@example.com"},
+///   "items": [{"beverage": "coffee", "size": "Large", "extra_shots": 1, "quantity": 2}]}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaceOrderRequest {
+    pub customer: CustomerInput,
+    pub items: Vec<ItemInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomerInput {
+    pub name: String,
+    pub email: String,
+    #[serde(default)]
+    pub phone: Option<String>,
+}
+
+/// One line: a beverage from the menu, its size (Medium) and quantity (1)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemInput {
+    #[serde(flatten)]
+    pub beverage: BeverageInput,
+    #[serde(default = "medium")]
+    pub size: Size,
+    #[serde(default = "one")]
+    pub quantity: u8,
+}
+
+fn medium() -> Size {
+    Size::Medium
+}
+
+fn one() -> u8 {
+    1
+}
+
+/// What the beverage is made of, by `"beverage"` name
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "beverage", rename_all = "lowercase")]
+pub enum BeverageInput {
+    Coffee {
+        #[serde(default)]
+        extra_shots: u8,
+    },
+    Tea {
+        variety: String,
+    },
+    Smoothie {
+        fruits: Vec<String>,
+    },
+}
+
+impl ItemInput {
+    /// Built like the menu builds them: the same limits, the same errors
+    fn line(&self) -> Result<OrderLine, BeverageError> {
+        let beverage: Box<dyn Beverage> = match &self.beverage {
+            BeverageInput::Coffee { extra_shots } => Box::new(
+                Coffee::builder().size(self.size).extra_shots(*extra_shots).build()?,
+            ),
+            BeverageInput::Tea { variety } => {
+                Box::new(Tea::builder().size(self.size).variety(variety.parse()?).build()?)
+            }
+            BeverageInput::Smoothie { fruits } => {
+                let builder = fruits
+                    .iter()
+                    .fold(Smoothie::builder().size(self.size), |builder, fruit| {
+                        builder.fruit(fruit)
+                    });
+                Box::new(builder.build()?)
+            }
+        };
+        Ok(OrderLine::new(beverage, self.quantity))
+    }
+}
+
+/// The routes, over one OrderService shared by every request
+pub struct OrderApi<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    service: SharedOrderService<R, P, N>,
+}
+
+impl<R, P, N> OrderApi<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    pub fn new(service: OrderService<R, P, N>) -> Self {
+        Self {
+            service: SharedOrderService::new(service),
+        }
+    }
+
+    /// Serve lookups from `repository` instead of queueing behind placements
+    /// (see SharedOrderService::with_reads)
+    pub fn with_reads(mut self, repository: impl OrderRepository + Sync + 'static) -> Self {
+        self.service = self.service.with_reads(repository);
+        self
+    }
+
+    /// The service behind the routes, for a worker thread to share
+    pub fn service(&self) -> &SharedOrderService<R, P, N> {
+        &self.service
+    }
+
+    /// Answer one request; `path` may carry a query string (ignored)
+    pub fn handle(&self, method: &str, path: &str, body: &str) -> ApiResponse {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            ("POST", ["orders"]) => self.place_order(body),
+            ("GET", ["orders", id]) => match parse_id(id) {
+                Ok(id) => self.service.get_order(id).map_or_else(
+                    |e| service_error(&e),
+                    |order| ApiResponse::json(200, &order),
+                ),
+                Err(response) => response,
+            },
+            ("POST", ["orders", id, "cancel"]) => match parse_id(id) {
+                Ok(id) => self.cancel_order(id),
+                Err(response) => response,
+            },
+            ("GET", ["customers", email, "orders"]) => {
+                let email = percent_decode(email);
+                self.service.list_customer_orders(&email).map_or_else(
+                    |e| service_error(&e),
+                    |orders| ApiResponse::json(200, &orders),
+                )
+            }
+            (_, ["orders"] | ["orders", _] | ["orders", _, "cancel"])
+            | (_, ["customers", _, "orders"]) => {
+                ApiResponse::error(405, &format!("{} is not allowed on {}", method, path))
+            }
+            _ => ApiResponse::error(404, &format!("No route for {}", path)),
+        }
+    }
+
+    fn place_order(&self, body: &str) -> ApiResponse {
+        let request: PlaceOrderRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid order request: {}", e)),
+        };
+        let lines = match request.items.iter().map(ItemInput::line).collect() {
+            Ok(lines) => lines,
+            Err(e) => return ApiResponse::error(422, &e.to_string()),
+        };
+        let input = request.customer;
+        let customer = Customer::new(input.name, input.email, input.phone);
+
+        self.service
+            .place_order_lines(customer, lines)
+            .map_or_else(|e| service_error(&e), |order| ApiResponse::json(201, &order))
+    }
+
+    fn cancel_order(&self, id: Uuid) -> ApiResponse {
+        match self.service.cancel_order(id).and_then(|()| self.service.get_order(id)) {
+            Ok(order) => ApiResponse::json(200, &order),
+            Err(e) => service_error(&e),
+        }
+    }
+}
+
+// Manual impl: a clone answers from the same service
+impl<R, P, N> Clone for OrderApi<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+        }
+    }
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ApiResponse> {
+    Uuid::parse_str(id).map_err(|_| ApiResponse::error(400, &format!("Not an order ID: {}", id)))
+}
+
+/// The status code a service error answers with
+fn service_error(error: &OrderServiceError) -> ApiResponse {
+    let status = match error {
+        OrderServiceError::OrderNotFound => 404,
+        OrderServiceError::PaymentFailed(_) => 402,
+        OrderServiceError::InvalidTransition(_)
+        | OrderServiceError::StockUnavailable(_)
+        | OrderServiceError::RefundElsewhere(_) => 409,
+        OrderServiceError::InvalidOrder(_) | OrderServiceError::PaymentMethodRefused(_) => 422,
+        OrderServiceError::StorageFailed(_)
+        | OrderServiceError::NotificationFailed(_)
+        | OrderServiceError::FiscalNumberingFailed(_) => 500,
+        OrderServiceError::ServicePoisoned(_) => 503,
+    };
+    ApiResponse::error(status, &error.to_string())
+}
+
+/// "ada%40example.com" -> "ada@example.com" (invalid escapes are kept as they are)
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, MemoryOrderRepository};
+    use std::io::Sink;
+
+    fn api() -> OrderApi<MemoryOrderRepository, CashPayment, ConsoleNotifier<Sink>> {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        OrderApi::new(OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier))
+    }
+
+    const ORDER: &str = r#"{
+        "customer": {"name": "Ada", "email": "ada@example.com"},
+        "items": [
+            {"beverage": "coffee", "size": "Large", "extra_shots": 1, "quantity": 2},
+            {"beverage": "tea", "variety": "green"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_place_get_list_and_cancel() {
+        let api = api();
+
+        let placed = api.handle("POST", "/orders", ORDER);
+        assert_eq!(placed.status, 201, "{}", placed.body);
+        assert_eq!(placed.body["items"][0]["quantity"], 2);
+        assert_eq!(placed.body["items"][1]["beverage_name"], "Green Tea");
+        let id = placed.body["id"].as_str().unwrap().to_string();
+
+        let found = api.handle("GET", &format!("/orders/{}", id), "");
+        assert_eq!((found.status, &found.body["status"]), (200, &json!("Paid")));
+        let listed = api.handle("GET", "/customers/ada%40example.com/orders", "");
+        assert_eq!(listed.body.as_array().unwrap().len(), 1);
+
+        let cancelled = api.handle("POST", &format!("/orders/{}/cancel", id), "");
+        assert_eq!((cancelled.status, &cancelled.body["status"]), (200, &json!("Refunded")));
+        let again = api.handle("POST", &format!("/orders/{}/cancel", id), "");
+        assert_eq!(again.status, 409);
+    }
+
+    #[test]
+    fn test_bad_requests_get_their_status_codes() {
+        let api = api();
+        let unknown = format!("/orders/{}", Uuid::new_v4());
+        let five_shots = ORDER.replace("\"extra_shots\": 1", "\"extra_shots\": 5");
+
+        assert_eq!(api.handle("GET", &unknown, "").status, 404);
+        assert_eq!(api.handle("GET", "/orders/not-an-id", "").status, 400);
+        assert_eq!(api.handle("POST", "/orders", "{").status, 400);
+        assert_eq!(api.handle("POST", "/orders", &five_shots).status, 422);
+        assert_eq!(api.handle("DELETE", "/orders", "").status, 405);
+        assert_eq!(api.handle("GET", "/menu", "").status, 404);
+        assert!(api.handle("GET", &unknown, "").body["error"].is_string());
+    }
+
+    #[test]
+    fn test_clones_answer_concurrently_from_one_service() {
+        let api = api();
+        let clients: Vec<_> = (0..4)
+            .map(|_| {
+                let api = api.clone();
+                std::thread::spawn(move || {
+                    (0..10).all(|_| api.handle("POST", "/orders", ORDER).status == 201)
+                })
+            })
+            .collect();
+
+        assert!(clients.into_iter().all(|client| client.join().unwrap()));
+        let listed = api.handle("GET", "/customers/ada@example.com/orders", "");
+        assert_eq!(listed.body.as_array().unwrap().len(), 40);
+        assert_eq!(api.service().list_all_orders().unwrap().len(), 40);
+    }
+}
@@ -0,0 +1,243 @@
+// SOLID: A minimal HTTP/1.1 front end for OrderApi
+//
+// Reads one request per connection (request line, headers, a body of
+// Content-Length bytes), answers it with JSON and closes the connection.
+// A fixed pool of worker threads, each with a clone of the OrderApi, answers
+// the connections, so a slow client doesn't hold up the others. Connections
+// wait in a bounded queue for a free worker; when it is full they are
+// answered 429 at once instead of piling up threads (ServerLimits).
+// No keep-alive, no chunked bodies, no TLS: put a reverse proxy in front
+// for anything facing the internet.
+
+use super::{ApiResponse, OrderApi};
+use crate::ports::{Notifier, OrderRepository, PaymentProcessor};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Largest request body accepted (an order is a few hundred bytes)
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// How many connections are answered at once, and how many may wait for a worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerLimits {
+    pub workers: usize,
+    pub queued: usize,
+}
+
+impl Default for ServerLimits {
+    /// 8 workers, 64 connections waiting
+    fn default() -> Self {
+        Self {
+            workers: 8,
+            queued: 64,
+        }
+    }
+}
+
+/// Answer connections with the default limits until the listener fails
+pub fn serve<R, P, N>(listener: TcpListener, api: &OrderApi<R, P, N>) -> io::Result<()>
+where
+    R: OrderRepository + 'static,
+    P: PaymentProcessor + Send + 'static,
+    N: Notifier + Send + 'static,
+{
+    serve_with_limits(listener, api, ServerLimits::default())
+}
+
+/// Answer connections until the listener fails; a broken connection only logs a warning
+pub fn serve_with_limits<R, P, N>(
+    listener: TcpListener,
+    api: &OrderApi<R, P, N>,
+    limits: ServerLimits,
+) -> io::Result<()>
+where
+    R: OrderRepository + 'static,
+    P: PaymentProcessor + Send + 'static,
+    N: Notifier + Send + 'static,
+{
+    let (waiting, queue) = mpsc::sync_channel(limits.queued.max(1));
+    let queue = Arc::new(Mutex::new(queue));
+    for _ in 0..limits.workers.max(1) {
+        let (queue, api) = (Arc::clone(&queue), api.clone());
+        thread::spawn(move || work(&queue, &api));
+    }
+    for stream in listener.incoming() {
+        hand_over(&waiting, stream?)?;
+    }
+    Ok(())
+}
+
+/// A worker: answer queued connections until the queue closes
+fn work<R, P, N>(queue: &Mutex<Receiver<TcpStream>>, api: &OrderApi<R, P, N>)
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    loop {
+        // Held only while waiting, not while answering
+        let next = match queue.lock() {
+            Ok(queue) => queue.recv(),
+            Err(_) => return,
+        };
+        let Ok(stream) = next else {
+            return;
+        };
+        if let Err(e) = respond(stream, api) {
+            eprintln!("Warning: Failed to answer a request: {}", e);
+        }
+    }
+}
+
+/// Queue a connection for the workers, or answer 429 when too many are waiting
+fn hand_over(waiting: &SyncSender<TcpStream>, stream: TcpStream) -> io::Result<()> {
+    match waiting.try_send(stream) {
+        Ok(()) => Ok(()),
+        Err(TrySendError::Full(stream)) => {
+            stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+            let busy = ApiResponse::error(429, "Too many requests waiting, try again later");
+            if let Err(e) = write_response(&stream, &busy) {
+                eprintln!("Warning: Failed to turn a request away: {}", e);
+            }
+            Ok(())
+        }
+        Err(TrySendError::Disconnected(_)) => Err(io::Error::other("Every worker stopped")),
+    }
+}
+
+/// Read one request from `stream` and write the answer
+pub fn respond<R, P, N>(stream: TcpStream, api: &OrderApi<R, P, N>) -> io::Result<()>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let response = if method.is_empty() || path.is_empty() {
+        ApiResponse::error(400, "Malformed request line")
+    } else if content_length > MAX_BODY_BYTES {
+        ApiResponse::error(413, &format!("Body over {} bytes", MAX_BODY_BYTES))
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        api.handle(method, path, &String::from_utf8_lossy(&body))
+    };
+
+    write_response(&stream, &response)
+}
+
+fn write_response(mut stream: &TcpStream, response: &ApiResponse) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        402 => "Payment Required",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, MemoryOrderRepository};
+    use crate::services::OrderService;
+
+    #[test]
+    fn test_one_request_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let api =
+            OrderApi::new(OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier));
+
+        let client = thread::spawn(move || {
+            let body = r#"{"customer": {"name": "Ada", "email": "ada@example.com"},
+                           "items": [{"beverage": "coffee"}]}"#;
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "POST /orders HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            let mut answer = String::new();
+            stream.read_to_string(&mut answer).unwrap();
+            answer
+        });
+        let (stream, _) = listener.accept().unwrap();
+        respond(stream, &api).unwrap();
+
+        let answer = client.join().unwrap();
+        assert!(answer.starts_with("HTTP/1.1 201 Created\r\n"), "{}", answer);
+        assert!(answer.contains(r#""total_price":{"cents":350,"currency":"USD"}"#), "{}", answer);
+    }
+
+    #[test]
+    fn test_a_full_queue_is_answered_429() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        // No worker takes from it: room for one connection
+        let (waiting, _queue) = mpsc::sync_channel(1);
+
+        let client = thread::spawn(move || {
+            let _queued = TcpStream::connect(address).unwrap();
+            let mut turned_away = TcpStream::connect(address).unwrap();
+            let mut answer = String::new();
+            turned_away.read_to_string(&mut answer).unwrap();
+            answer
+        });
+        for _ in 0..2 {
+            let (stream, _) = listener.accept().unwrap();
+            hand_over(&waiting, stream).unwrap();
+        }
+
+        let answer = client.join().unwrap();
+        assert!(answer.starts_with("HTTP/1.1 429 Too Many Requests\r\n"), "{}", answer);
+    }
+}
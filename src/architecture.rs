@@ -0,0 +1,182 @@
+// SOLID: Architecture conformance (tests only)
+//
+// The layer rules from lib.rs, checked against the source on every
+// `cargo test` instead of being taken on trust:
+//
+// - domain depends on nothing else in the crate
+// - services never name an adapter (not the module, not one of its types)
+// - adapters only reach for ports and domain
+//
+// The check reads src/ and looks at every `crate::<module>` path and every
+// adapter type name outside comments and the `#[cfg(test)]` module: tests are
+// the composition root of their file and may wire in whatever they like.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Layer -> the crate modules its code may name (besides itself)
+const LAYER_RULES: [(&str, &[&str]); 3] = [
+    ("domain", &[]),
+    ("services", &["domain", "ports", "bus"]),
+    ("adapters", &["domain", "ports"]),
+];
+
+fn src_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+/// Every .rs file of one layer, with its code
+fn layer_files(layer: &str) -> Vec<(String, String)> {
+    let mut files: Vec<_> = fs::read_dir(src_dir().join(layer))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| {
+            let name = format!("{}/{}", layer, path.file_name().unwrap().to_string_lossy());
+            (name, code(&fs::read_to_string(&path).unwrap()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// The source without comments and without its test module
+fn code(source: &str) -> String {
+    let source = source.split("#[cfg(test)]").next().unwrap_or_default();
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The top-level modules named as `crate::<module>`, `crate::{<module>::...}`
+fn crate_modules(code: &str) -> Vec<String> {
+    let mut modules = Vec::new();
+    for (at, _) in code.match_indices("crate::") {
+        if code[..at].ends_with(is_ident) {
+            continue;
+        }
+        let rest = &code[at + "crate::".len()..];
+        let group = match rest.strip_prefix('{') {
+            Some(group) => top_level_items(group),
+            None => vec![rest],
+        };
+        for path in group {
+            let module: String = path.chars().take_while(|&c| is_ident(c)).collect();
+            if !module.is_empty() && !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+    }
+    modules
+}
+
+/// The items of a `{a::{X, Y}, b}` group (without its opening brace): `a::...`, `b`
+fn top_level_items(group: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in group.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                items.push(group[start..i].trim_start());
+                break;
+            }
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(group[start..i].trim_start());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+/// `word` appears in `code` as a whole identifier
+fn names(code: &str, word: &str) -> bool {
+    code.match_indices(word).any(|(at, _)| {
+        !code[..at].ends_with(is_ident) && !code[at + word.len()..].starts_with(is_ident)
+    })
+}
+
+/// The `pub struct`s and `pub enum`s the adapters layer defines
+fn adapter_types() -> Vec<String> {
+    let mut types = Vec::new();
+    for (_, code) in layer_files("adapters") {
+        for line in code.lines() {
+            let line = line.trim_start();
+            let rest = line
+                .strip_prefix("pub struct ")
+                .or_else(|| line.strip_prefix("pub enum "));
+            if let Some(rest) = rest {
+                types.push(rest.chars().take_while(|&c| is_ident(c)).collect());
+            }
+        }
+    }
+    types
+}
+
+/// What `code` of `layer` names that the layer rules forbid
+fn violations(layer: &str, code: &str) -> Vec<String> {
+    let allowed = LAYER_RULES
+        .iter()
+        .find(|(name, _)| *name == layer)
+        .map(|(_, allowed)| *allowed)
+        .unwrap_or_default();
+    crate_modules(code)
+        .into_iter()
+        .filter(|module| module != layer && !allowed.contains(&module.as_str()))
+        .map(|module| format!("crate::{}", module))
+        .collect()
+}
+
+#[test]
+fn test_domain_has_no_crate_dependencies() {
+    for (file, code) in layer_files("domain") {
+        let found = violations("domain", &code);
+        assert!(found.is_empty(), "{} depends on {}", file, found.join(", "));
+    }
+}
+
+#[test]
+fn test_services_never_name_adapters() {
+    let adapters = adapter_types();
+    assert!(adapters.iter().any(|name| name == "JsonOrderRepository"));
+    for (file, code) in layer_files("services") {
+        let mut found = violations("services", &code);
+        found.extend(adapters.iter().filter(|name| names(&code, name)).cloned());
+        assert!(found.is_empty(), "{} names {}", file, found.join(", "));
+    }
+}
+
+#[test]
+fn test_adapters_only_import_ports_and_domain() {
+    for (file, code) in layer_files("adapters") {
+        let found = violations("adapters", &code);
+        assert!(found.is_empty(), "{} depends on {}", file, found.join(", "));
+    }
+}
+
+#[test]
+fn test_checker_catches_violations() {
+    let code = code(
+        "use crate::ports::Notifier;\n\
+         // use crate::cli::run;\n\
+         use crate::{adapters::{JsonOrderRepository, Stored}, domain::Order};\n\
+         fn f() { crate::services::OrderService::new; }\n\
+         #[cfg(test)]\n\
+         use crate::presentation::render;\n",
+    );
+
+    assert_eq!(crate_modules(&code), ["ports", "adapters", "domain", "services"]);
+    assert_eq!(violations("adapters", &code), ["crate::services"]);
+    assert_eq!(violations("domain", &code).len(), 3);
+    assert!(names(&code, "JsonOrderRepository"));
+    assert!(!names(&code, "Json"));
+}
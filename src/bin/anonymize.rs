@@ -0,0 +1,70 @@
+// Coffee Shop Order System - Dataset Anonymizer
+//
+// Copies an orders file with every name, email, phone and payment reference
+// replaced by fake data. Totals and timestamps are kept, so the result can be
+// shared with students and still produce realistic reports.
+//
+// Run: cargo run --bin anonymize -- orders.json demo_orders.json [--salt N]
+//
+// SOLID (DIP): DataAnonymizer only sees two OrderRepository values.
+// Both happen to be JSON files here.
+
+use coffee_shop_solid::adapters::JsonOrderRepository;
+use coffee_shop_solid::services::DataAnonymizer;
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let (source_path, target_path, salt) = parse_args();
+
+    if source_path == target_path {
+        eprintln!("❌ Refusing to overwrite the source file");
+        std::process::exit(2);
+    }
+
+    let result = JsonOrderRepository::new(source_path.clone()).and_then(|source| {
+        let mut target = JsonOrderRepository::new(target_path.clone())?;
+        DataAnonymizer::new(salt).export(&source, &mut target)
+    });
+
+    match result {
+        Ok(count) => println!(
+            "✅ Exported {} anonymized order(s) from {} to {}",
+            count,
+            source_path.display(),
+            target_path.display()
+        ),
+        Err(e) => {
+            eprintln!("❌ Export failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `<source> <target> [--salt N]`
+fn parse_args() -> (PathBuf, PathBuf, u64) {
+    let mut paths = Vec::new();
+    let mut salt = 0;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--salt" {
+            match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => salt = v,
+                None => usage(),
+            }
+        } else {
+            paths.push(PathBuf::from(arg));
+        }
+    }
+
+    match <[PathBuf; 2]>::try_from(paths) {
+        Ok([source, target]) => (source, target, salt),
+        Err(_) => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: anonymize <source.json> <target.json> [--salt N]");
+    std::process::exit(2);
+}
@@ -0,0 +1,30 @@
+// Coffee Shop Order System - GodOrderManager vs OrderService
+//
+// Places the same orders with the monolithic GodOrderManager and with the
+// SOLID OrderService, then prints how coupled each one is and what it cost
+// to set up. The payment and "email" lines printed first come from the God
+// class: it has no way to be silenced, which is half the lesson.
+//
+// Run: cargo run --features antipatterns --bin antipatterns -- [ORDERS]
+
+use coffee_shop_solid::antipatterns::compare;
+use std::env;
+
+fn main() {
+    let orders = match env::args().nth(1).map(|arg| arg.parse::<usize>()) {
+        None => 20,
+        Some(Ok(orders)) => orders,
+        Some(Err(_)) => {
+            eprintln!("Usage: antipatterns [ORDERS]");
+            std::process::exit(2);
+        }
+    };
+
+    match compare(orders) {
+        Ok(comparison) => println!("\n{}", comparison),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+// Coffee Shop Order System - Asynchronous Fulfillment Worker
+//
+// This binary decouples order INTAKE from order FULFILLMENT:
+//
+//   intake ──submit_order()──> repository (Pending)
+//      │
+//      └──enqueue(id)──> OrderQueue ──dequeue()──> worker(s)
+//                                                     │
+//                         process_submitted_order() <─┘
+//                         (payment, notification, update)
+//
+// Intake answers customers immediately; slow payment gateways and
+// notification channels only slow down the workers.
+//
+// With --capacity N the queue holds at most N orders and intake refuses the
+// rest (HTTP 429 in a real API) instead of letting the backlog grow.
+//
+// Several worker processes can run for high availability. They all fulfill
+// orders, but scheduled jobs (no-show expiry...) run on the elected leader
+// only. With --lock-dir, processes sharing that directory elect one leader
+// between them; without it, this process is the only candidate.
+//
+// Run: cargo run --bin worker -- --orders 10 --workers 3 [--capacity 4]
+//                                [--lock-dir DIR] [--instance NAME]
+//
+// SOLID (DIP): intake and workers only know the OrderQueueProducer /
+// OrderQueueConsumer traits. The in-process channel used here can be
+// replaced by a broker-backed adapter without touching this workflow.
+
+use coffee_shop_solid::adapters::{
+    bounded_order_queue, channel_order_queue, CashPayment, ConsoleNotifier, FileLock,
+    FullQueuePolicy, MemoryLock, MemoryOrderRepository, SharedOrderRepository, SystemClock,
+};
+use coffee_shop_solid::domain::OrderStatus;
+use coffee_shop_solid::ports::{DistributedLock, OrderQueueConsumer};
+use coffee_shop_solid::services::{
+    JobScheduler, LeaderElection, NoShowExpiryJob, NoShowPolicy, OrderIntake, Role, Schedule,
+};
+use coffee_shop_solid::*;
+use std::env;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+struct Options {
+    orders: usize,
+    workers: usize,
+    capacity: Option<usize>,
+    lock_dir: Option<PathBuf>,
+    instance: String,
+}
+
+fn main() {
+    let options = parse_args();
+    let (order_count, worker_count, capacity) =
+        (options.orders, options.workers, options.capacity);
+
+    println!("🏭 Fulfillment worker demo");
+    println!("  - Orders to submit: {}", order_count);
+    println!("  - Worker threads: {}", worker_count);
+    match capacity {
+        Some(capacity) => println!("  - Queue capacity: {} (then refuse)\n", capacity),
+        None => println!("  - Queue capacity: unbounded\n"),
+    }
+
+    // One store, shared by intake and every worker
+    let repository = SharedOrderRepository::new(MemoryOrderRepository::new());
+    let (producer, consumer) = match capacity {
+        Some(capacity) => bounded_order_queue(capacity, FullQueuePolicy::Shed),
+        None => channel_order_queue(),
+    };
+
+    // Workers: each owns its own OrderService over the shared store
+    let workers: Vec<_> = (1..=worker_count)
+        .map(|n| {
+            let consumer = consumer.clone();
+            let repository = repository.clone();
+            thread::spawn(move || run_worker(n, consumer, repository))
+        })
+        .collect();
+    drop(consumer);
+
+    // Scheduled jobs: only while this process is the leader
+    let done = Arc::new(AtomicBool::new(false));
+    let lock: Box<dyn DistributedLock> = match &options.lock_dir {
+        Some(dir) => Box::new(FileLock::new(dir.clone())),
+        None => Box::new(MemoryLock::new()),
+    };
+    let election = LeaderElection::new(lock, "worker", &options.instance)
+        .with_lease(chrono::Duration::seconds(3));
+    let leader = {
+        let (repository, done) = (repository.clone(), done.clone());
+        thread::spawn(move || run_leader_duties(election, repository, &done))
+    };
+
+    // Intake: accept orders and hand their IDs to the queue
+    let service = OrderService::new(repository.clone(), CashPayment, ConsoleNotifier::new());
+    let mut intake = OrderIntake::new(service, producer);
+    for n in 1..=order_count {
+        let customer = Customer::named(&format!("Customer {}", n));
+        let beverages: Vec<Box<dyn Beverage>> =
+            vec![Box::new(Coffee::medium().with_extra_shots((n % 3) as u8))];
+
+        match intake.accept(customer, beverages) {
+            Ok(order) => println!("📥 Accepted order {}", order.id),
+            Err(e) => eprintln!("❌ {} ({})", e, e.status_code()),
+        }
+    }
+
+    // Closing the queue lets workers finish once it is drained
+    let intake = intake.close();
+
+    let processed: usize = workers
+        .into_iter()
+        .map(|handle| handle.join().unwrap_or(0))
+        .sum();
+    done.store(true, Ordering::SeqCst);
+    let _ = leader.join();
+
+    let paid = intake
+        .list_all_orders()
+        .map(|orders| {
+            orders
+                .iter()
+                .filter(|o| o.status == OrderStatus::Paid)
+                .count()
+        })
+        .unwrap_or(0);
+
+    println!("\n✅ Workers processed {} order(s); {} now paid", processed, paid);
+}
+
+/// Process queued orders until the queue is closed and drained
+fn run_worker(
+    n: usize,
+    consumer: impl OrderQueueConsumer,
+    repository: SharedOrderRepository<MemoryOrderRepository>,
+) -> usize {
+    let mut service = OrderService::new(repository, CashPayment, ConsoleNotifier::new());
+    let mut processed = 0;
+
+    loop {
+        match consumer.dequeue() {
+            Ok(Some(id)) => match service.process_submitted_order(id) {
+                Ok(order) => {
+                    processed += 1;
+                    println!("👷 Worker {} fulfilled order {}", n, order.id);
+                }
+                Err(e) => eprintln!("👷 Worker {} failed on {}: {}", n, id, e),
+            },
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("👷 Worker {} stopped: {}", n, e);
+                break;
+            }
+        }
+    }
+
+    processed
+}
+
+/// Keep the election going; run the scheduler on every tick we lead
+fn run_leader_duties(
+    mut election: LeaderElection,
+    repository: SharedOrderRepository<MemoryOrderRepository>,
+    done: &AtomicBool,
+) {
+    let mut service = OrderService::new(repository, CashPayment, ConsoleNotifier::new());
+    let mut scheduler = JobScheduler::new();
+    scheduler.register(
+        "no-show expiry",
+        Schedule::Every(chrono::Duration::seconds(30)),
+        NoShowExpiryJob::new(NoShowPolicy::default()),
+    );
+    let mut role = Role::Follower;
+
+    // At least one round, even if the orders were all fulfilled already
+    loop {
+        let now = match election.tick(&SystemClock) {
+            Ok(now) => now,
+            Err(e) => {
+                eprintln!("👑 Election failed, acting as follower: {}", e);
+                Role::Follower
+            }
+        };
+        if now != role {
+            role = now;
+            match role {
+                Role::Leader => println!("👑 This instance leads: running scheduled jobs"),
+                Role::Follower => println!("👑 Another instance leads: not running jobs"),
+            }
+        }
+
+        if election.is_leader(&SystemClock) {
+            for run in scheduler.run_pending(&mut service, &SystemClock) {
+                match run.outcome {
+                    Ok(message) => println!("⏰ {}: {}", run.name, message),
+                    Err(e) => eprintln!("⏰ {} failed: {}", run.name, e),
+                }
+            }
+        }
+
+        if done.load(Ordering::SeqCst) {
+            break;
+        }
+        // Renewing more often than renew_every() is harmless, and the demo is short
+        thread::sleep(Duration::from_millis(200));
+    }
+
+    if let Err(e) = election.step_down() {
+        eprintln!("👑 Could not hand over leadership: {}", e);
+    }
+}
+
+/// `--orders N`, `--workers N`, `--capacity N`, `--lock-dir DIR` and
+/// `--instance NAME`, with small defaults
+fn parse_args() -> Options {
+    let mut options = Options {
+        orders: 5,
+        workers: 2,
+        capacity: None,
+        lock_dir: None,
+        instance: format!("worker-{}", std::process::id()),
+    };
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        let number = value.as_deref().and_then(|v| v.parse().ok());
+        match (arg.as_str(), value, number) {
+            ("--orders", _, Some(v)) => options.orders = v,
+            ("--workers", _, Some(v)) => options.workers = v,
+            ("--capacity", _, Some(v)) => options.capacity = Some(v),
+            ("--lock-dir", Some(v), _) => options.lock_dir = Some(PathBuf::from(v)),
+            ("--instance", Some(v), _) => options.instance = v,
+            _ => {
+                eprintln!(
+                    "Usage: worker [--orders N] [--workers N] [--capacity N] \
+                     [--lock-dir DIR] [--instance NAME]"
+                );
+                std::process::exit(2);
+            }
+        }
+    }
+
+    options.workers = options.workers.max(1);
+    options
+}
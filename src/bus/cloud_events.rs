@@ -0,0 +1,56 @@
+// SOLID: Bus messages in and out of CloudEvents envelopes
+//
+// The envelope (domain::CloudEvent) knows nothing of the bus; this is where
+// a message's TOPIC becomes the event's `type`, and where a consumer gets
+// its typed message back, so a misrouted event fails loudly instead of
+// deserializing into the wrong struct.
+//
+// let event = CloudEvent::from_message(&OrderReady { .. }, "/shops/geneva");
+// let ready: OrderReady = CloudEvent::parse(&body)?.to_message()?;
+
+use super::message_bus::Message;
+use crate::domain::{CloudEvent, CloudEventError};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+impl CloudEvent {
+    /// Wrap a bus message emitted now by `source`
+    pub fn from_message<M: Message + Serialize>(message: &M, source: &str) -> Self {
+        let data = serde_json::to_value(message).unwrap_or(serde_json::Value::Null);
+        Self::new(Uuid::new_v4().to_string(), source, M::TOPIC, data).with_time(Utc::now())
+    }
+
+    /// The message inside, if this event is an `M`
+    pub fn to_message<M: Message + DeserializeOwned>(&self) -> Result<M, CloudEventError> {
+        self.data_as(M::TOPIC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{OrderCancelled, OrderReady};
+
+    #[test]
+    fn test_message_round_trip() {
+        let ready = OrderReady {
+            order_id: Uuid::new_v4(),
+            ready_at: Utc::now(),
+        };
+
+        let body = CloudEvent::from_message(&ready, "/shops/geneva").to_json().unwrap();
+        let event = CloudEvent::parse(&body).unwrap();
+
+        assert_eq!(event.event_type, "order.ready");
+        assert_eq!(event.to_message::<OrderReady>().unwrap(), ready);
+        assert_eq!(
+            event.to_message::<OrderCancelled>(),
+            Err(CloudEventError::UnexpectedType {
+                expected: "order.cancelled".to_string(),
+                found: "order.ready".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,167 @@
+// SOLID: MessageBus - typed topics over std channels
+//
+// Each message type gets its own topic (keyed by TypeId).
+// Each subscriber gets its own channel, so subscribers never steal
+// messages from each other: every subscriber sees every message.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+
+/// Anything that can travel on the bus
+///
+/// `TOPIC` is a human-readable name (for logs and debugging);
+/// routing itself is done by type.
+pub trait Message: Clone + Send + 'static {
+    const TOPIC: &'static str;
+}
+
+/// Fan-out list for one message type
+struct Topic<M: Message> {
+    subscribers: Vec<Sender<M>>,
+}
+
+/// In-process publish/subscribe bus
+///
+/// Cloning the bus gives another handle to the SAME topics,
+/// so it can be handed to every service that publishes or subscribes.
+#[derive(Clone, Default)]
+pub struct MessageBus {
+    topics: Arc<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>>,
+}
+
+impl MessageBus {
+    /// Create a bus with no topics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start receiving every message of type `M` published from now on
+    pub fn subscribe<M: Message>(&self) -> Subscription<M> {
+        let (sender, receiver) = mpsc::channel();
+        let mut topics = self.topics.lock().unwrap();
+
+        topics
+            .entry(TypeId::of::<M>())
+            .or_insert_with(|| {
+                Box::new(Topic::<M> {
+                    subscribers: Vec::new(),
+                })
+            })
+            .downcast_mut::<Topic<M>>()
+            .expect("topic registered with a different type")
+            .subscribers
+            .push(sender);
+
+        Subscription { receiver }
+    }
+
+    /// Deliver a message to every current subscriber of its type
+    ///
+    /// Returns how many subscribers received it. Dropped subscriptions are
+    /// cleaned up here, so publishing never fails.
+    pub fn publish<M: Message>(&self, message: M) -> usize {
+        let mut topics = self.topics.lock().unwrap();
+
+        let Some(topic) = topics
+            .get_mut(&TypeId::of::<M>())
+            .and_then(|topic| topic.downcast_mut::<Topic<M>>())
+        else {
+            return 0;
+        };
+
+        topic
+            .subscribers
+            .retain(|subscriber| subscriber.send(message.clone()).is_ok());
+        topic.subscribers.len()
+    }
+
+    /// Number of live subscribers for a message type (as of the last publish)
+    pub fn subscriber_count<M: Message>(&self) -> usize {
+        let topics = self.topics.lock().unwrap();
+        topics
+            .get(&TypeId::of::<M>())
+            .and_then(|topic| topic.downcast_ref::<Topic<M>>())
+            .map(|topic| topic.subscribers.len())
+            .unwrap_or(0)
+    }
+}
+
+/// Receiving end for one message type
+pub struct Subscription<M: Message> {
+    receiver: Receiver<M>,
+}
+
+impl<M: Message> Subscription<M> {
+    /// Next message if one is waiting, without blocking
+    pub fn try_next(&self) -> Option<M> {
+        match self.receiver.try_recv() {
+            Ok(message) => Some(message),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Block until the next message (None once the bus is gone)
+    pub fn next(&self) -> Option<M> {
+        self.receiver.recv().ok()
+    }
+
+    /// Take every message currently waiting
+    pub fn drain(&self) -> Vec<M> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Ping(u32);
+
+    impl Message for Ping {
+        const TOPIC: &'static str = "test.ping";
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Pong;
+
+    impl Message for Pong {
+        const TOPIC: &'static str = "test.pong";
+    }
+
+    #[test]
+    fn test_every_subscriber_gets_every_message() {
+        let bus = MessageBus::new();
+        let first = bus.subscribe::<Ping>();
+        let second = bus.subscribe::<Ping>();
+
+        assert_eq!(bus.publish(Ping(1)), 2);
+        assert_eq!(bus.publish(Ping(2)), 2);
+
+        assert_eq!(first.drain(), vec![Ping(1), Ping(2)]);
+        assert_eq!(second.drain(), vec![Ping(1), Ping(2)]);
+    }
+
+    #[test]
+    fn test_topics_are_typed() {
+        let bus = MessageBus::new();
+        let pings = bus.subscribe::<Ping>();
+
+        // Nobody listens to Pong
+        assert_eq!(bus.publish(Pong), 0);
+        assert!(pings.try_next().is_none());
+    }
+
+    #[test]
+    fn test_dropped_subscribers_are_pruned() {
+        let bus = MessageBus::new();
+        let kept = bus.subscribe::<Ping>();
+        drop(bus.subscribe::<Ping>());
+
+        assert_eq!(bus.publish(Ping(7)), 1);
+        assert_eq!(bus.subscriber_count::<Ping>(), 1);
+        assert_eq!(kept.next(), Some(Ping(7)));
+    }
+}
@@ -0,0 +1,133 @@
+// SOLID: Order lifecycle messages
+//
+// These are the CONTRACT between publishers and subscribers.
+// They carry plain data (no Order reference), so a subscriber can't
+// accidentally mutate the order or depend on its internal layout.
+
+use super::message_bus::Message;
+use crate::domain::{Money, Order};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An order was paid and saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OrderPlaced {
+    pub order_id: Uuid,
+    pub customer_email: String,
+    /// (beverage description, quantity) per line
+    pub items: Vec<(String, u8)>,
+    pub total_price: Money,
+    /// Estimated time to make the whole order
+    pub prep_seconds: u32,
+    pub placed_at: DateTime<Utc>,
+}
+
+impl OrderPlaced {
+    pub fn from_order(order: &Order) -> Self {
+        Self {
+            order_id: order.id,
+            customer_email: order.customer.email.clone(),
+            items: order
+                .items
+                .iter()
+                .map(|item| (item.beverage_description.clone(), item.quantity))
+                .collect(),
+            total_price: order.total_price,
+            prep_seconds: order.prep_time_estimate().num_seconds() as u32,
+            placed_at: order.status_since(),
+        }
+    }
+}
+
+impl Message for OrderPlaced {
+    const TOPIC: &'static str = "order.placed";
+}
+
+/// An order is waiting at the counter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OrderReady {
+    pub order_id: Uuid,
+    pub ready_at: DateTime<Utc>,
+}
+
+impl Message for OrderReady {
+    const TOPIC: &'static str = "order.ready";
+}
+
+/// A customer collected their order
+///
+/// Carries who to ask for feedback, like OrderPlaced carries who ordered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OrderCompleted {
+    pub order_id: Uuid,
+    pub customer_name: String,
+    pub customer_email: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl Message for OrderCompleted {
+    const TOPIC: &'static str = "order.completed";
+}
+
+/// An order was cancelled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OrderCancelled {
+    pub order_id: Uuid,
+    pub cancelled_at: DateTime<Utc>,
+}
+
+impl Message for OrderCancelled {
+    const TOPIC: &'static str = "order.cancelled";
+}
+
+/// A ready order was never picked up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OrderNoShow {
+    pub order_id: Uuid,
+    pub flagged_at: DateTime<Utc>,
+}
+
+impl Message for OrderNoShow {
+    const TOPIC: &'static str = "order.no_show";
+}
+
+/// A payment was declined or failed, so no order was placed
+///
+/// Nothing is saved for it: this message is the only trace, which is what
+/// LiveMetrics counts failure rates from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PaymentFailed {
+    pub order_id: Uuid,
+    pub customer_email: String,
+    pub amount: Money,
+    pub reason: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl Message for PaymentFailed {
+    const TOPIC: &'static str = "payment.failed";
+}
+
+/// A customer was shown one variant of an experiment
+///
+/// Published by PromotionEngine each time it consults an experiment,
+/// so conversion can later be compared per variant (ExperimentReport).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExperimentExposure {
+    pub experiment: String,
+    pub variant: String,
+    pub customer_email: String,
+    pub exposed_at: DateTime<Utc>,
+}
+
+impl Message for ExperimentExposure {
+    const TOPIC: &'static str = "experiment.exposure";
+}
@@ -0,0 +1,39 @@
+// SOLID: The Message Bus (in-process, typed pub/sub)
+//
+// Instead of OrderService calling LoyaltyService, InventoryService and the
+// kitchen display directly, it PUBLISHES a message and whoever cares SUBSCRIBES.
+//
+// PRINCIPLES:
+//
+// 1. OPEN-CLOSED PRINCIPLE (OCP):
+//    A new reaction to "order placed" is a new subscriber.
+//    The publisher doesn't change.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    Publishers and subscribers both depend on the message TYPES in
+//    `messages.rs`, not on each other.
+//
+// 3. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    The bus only routes. It doesn't know what an order is.
+//
+// Topics are TYPES: subscribing to `OrderPlaced` gives a `Subscription<OrderPlaced>`,
+// so a subscriber can never receive a message it didn't ask for (the compiler checks it).
+//
+// Leaving the process, a message travels as a CloudEvent (`cloud_events.rs`)
+// and comes back typed the same way: `CloudEvent::to_message::<OrderReady>()`.
+//
+// ```text
+// OrderService ──publish(OrderPlaced)──> MessageBus ──> KitchenDisplay
+//                                              └──────> (any other subscriber)
+// ```
+
+pub mod cloud_events;
+pub mod message_bus;
+pub mod messages;
+
+// Re-export for convenience
+pub use message_bus::{Message, MessageBus, Subscription};
+pub use messages::{
+    ExperimentExposure, OrderCancelled, OrderCompleted, OrderNoShow, OrderPlaced, OrderReady,
+    PaymentFailed,
+};
@@ -0,0 +1,127 @@
+// SOLID: ParsedArgs - `--key value` options and `--flag` switches
+//
+// A deliberately small parser: commands ask for the options they understand,
+// and anything left over is reported as a usage error.
+
+use super::CliError;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Options that never take a value
+const FLAGS: [&str; 4] = ["--demo", "--dry-run", "--members-only", "--skip-existing"];
+
+/// Positional arguments, options and flags of one command
+#[derive(Debug, Default)]
+pub struct ParsedArgs {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl ParsedArgs {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut parsed = Self::default();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if FLAGS.contains(&arg.as_str()) {
+                parsed.flags.push(arg.clone());
+            } else if let Some(name) = arg.strip_prefix("--") {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| CliError::Usage(format!("Missing value for --{}", name)))?;
+                parsed.options.insert(name.to_string(), value.clone());
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// N-th positional argument
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positional.get(index).map(String::as_str)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    /// `--name YYYY-MM-DD`, if given
+    pub fn date(&self, name: &str) -> Result<Option<NaiveDate>, CliError> {
+        self.option(name)
+            .map(|value| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                    CliError::Usage(format!("--{} expects YYYY-MM-DD, got '{}'", name, value))
+                })
+            })
+            .transpose()
+    }
+
+    /// `--name "YYYY-MM-DD HH:MM[:SS]"` (UTC) or RFC 3339, if given
+    pub fn timestamp(&self, name: &str) -> Result<Option<DateTime<Utc>>, CliError> {
+        let Some(value) = self.option(name) else {
+            return Ok(None);
+        };
+        if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+            return Ok(Some(at.with_timezone(&Utc)));
+        }
+        ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(|at| Some(at.and_utc()))
+            .ok_or_else(|| {
+                CliError::Usage(format!(
+                    "--{} expects \"YYYY-MM-DD HH:MM\" (UTC), got '{}'",
+                    name, value
+                ))
+            })
+    }
+
+    /// `--file PATH`, defaulting to the interactive demo's file name
+    pub fn file(&self) -> PathBuf {
+        PathBuf::from(self.option("file").unwrap_or("orders.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options_and_flags() {
+        let parsed =
+            ParsedArgs::parse(&strings(&["daily", "--date", "2024-03-04", "--demo"])).unwrap();
+
+        assert_eq!(parsed.positional(0), Some("daily"));
+        assert!(parsed.flag("--demo"));
+        assert_eq!(
+            parsed.date("date").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 4)
+        );
+        assert_eq!(parsed.file(), PathBuf::from("orders.json"));
+
+        let parsed = ParsedArgs::parse(&strings(&["--at", "2024-03-04 09:05"])).unwrap();
+        let at: DateTime<Utc> = "2024-03-04T09:05:00Z".parse().unwrap();
+        assert_eq!(parsed.timestamp("at").unwrap(), Some(at));
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert!(ParsedArgs::parse(&strings(&["--format"])).is_err());
+
+        let parsed = ParsedArgs::parse(&strings(&["--date", "March 4th"])).unwrap();
+        assert!(parsed.date("date").is_err());
+        assert!(parsed.timestamp("date").is_err());
+    }
+}
@@ -0,0 +1,200 @@
+// SOLID: `campaign` subcommand (an announcement to many customers)
+//
+// campaign send <ID> --subject TEXT --body PATH --smtp HOST:PORT
+//        [--from ADDRESS] [--since DATE] [--members-only]
+//        [--batch N] [--per-minute N] [--outbox PATH] [--dry-run]
+// campaign status <ID> [--outbox PATH]
+//
+// `send` mails the body (a text file, `{{name}}` and `{{email}}` filled in)
+// to every customer who ordered since --since (default: ever), or only to
+// loyalty members. Each recipient's status is kept in the outbox (default:
+// campaign-outbox.json), so after a crash or a mail server outage the same
+// command carries on where it stopped. --dry-run prints the messages and
+// leaves the outbox alone.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::adapters::{JsonCampaignOutbox, MemoryCampaignOutbox, RecordingMailer, SmtpMailer};
+use crate::domain::{Campaign, Customer, Order};
+use crate::ports::{CampaignOutbox, Mailer};
+use crate::presentation::render_campaign;
+use crate::services::CampaignService;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Entry point for `campaign ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let outbox_path = PathBuf::from(parsed.option("outbox").unwrap_or("campaign-outbox.json"));
+
+    match (parsed.positional(0), parsed.positional(1)) {
+        (Some("send"), Some(id)) => send(&parsed, id, outbox_path, out),
+        (Some("status"), Some(id)) => {
+            let service = CampaignService::new(
+                RecordingMailer::new(),
+                open_outbox(outbox_path)?,
+                render_campaign,
+            );
+            let progress = service.progress(id).map_err(failed)?;
+            writeln!(
+                out,
+                "{}: {} sent, {} pending, {} failed",
+                id, progress.sent, progress.pending, progress.failed
+            )?;
+            for entry in service.outbox().entries(id).map_err(failed)? {
+                writeln!(
+                    out,
+                    "  {:<32} {} ({} attempt(s))",
+                    entry.recipient.email, entry.status, entry.attempts
+                )?;
+            }
+            Ok(())
+        }
+        (Some(action @ ("send" | "status")), None) => {
+            Err(CliError::Usage(format!("campaign {} needs a campaign ID", action)))
+        }
+        (Some(other), _) => Err(CliError::Usage(format!("Unknown campaign action '{}'", other))),
+        (None, _) => Err(CliError::Usage("campaign needs send or status".to_string())),
+    }
+}
+
+fn send(
+    parsed: &ParsedArgs,
+    id: &str,
+    outbox_path: PathBuf,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let subject = parsed
+        .option("subject")
+        .ok_or_else(|| CliError::Usage("campaign send needs --subject TEXT".to_string()))?;
+    let body_path = parsed
+        .option("body")
+        .ok_or_else(|| CliError::Usage("campaign send needs --body PATH".to_string()))?;
+    let body = fs::read_to_string(body_path)
+        .map_err(|e| CliError::CommandFailed(format!("{}: {}", body_path, e)))?;
+    let campaign = Campaign::new(id, subject, &body);
+
+    let (repository, _) = open_repository(parsed)?;
+    let since = parsed.date("since")?;
+    let orders: Vec<_> = repository
+        .list_all()?
+        .into_iter()
+        .filter(|order| since.is_none_or(|since| order.created_at.date_naive() >= since))
+        .collect();
+    let members_only = parsed.flag("--members-only");
+    let filter = |customer: &Customer| !members_only || customer.member_since.is_some();
+
+    if parsed.flag("--dry-run") {
+        let mailer = RecordingMailer::new();
+        let mut service =
+            CampaignService::new(mailer.clone(), MemoryCampaignOutbox::new(), render_campaign);
+        service.run(&campaign, &orders, filter).map_err(failed)?;
+        for message in mailer.sent() {
+            let to = message.to.join(", ");
+            writeln!(out, "To: {}\nSubject: {}\n\n{}\n", to, message.subject, message.text)?;
+        }
+        return Ok(());
+    }
+
+    let address = parsed.option("smtp").ok_or_else(|| {
+        CliError::Usage("campaign send needs --smtp HOST:PORT (or --dry-run)".to_string())
+    })?;
+    let from = parsed.option("from").unwrap_or("coffee-shop@localhost");
+    let mut service = CampaignService::new(
+        SmtpMailer::new(address, from),
+        open_outbox(outbox_path)?,
+        render_campaign,
+    );
+    if let Some(batch) = number(parsed, "batch")? {
+        service = service.with_batch_size(batch as usize);
+    }
+    if let Some(per_minute) = number(parsed, "per-minute")? {
+        service = service.with_rate_limit(per_minute);
+    }
+    deliver(service, &campaign, &orders, filter, out)
+}
+
+fn deliver<M: Mailer, O: CampaignOutbox>(
+    mut service: CampaignService<M, O>,
+    campaign: &Campaign,
+    orders: &[Order],
+    filter: impl Fn(&Customer) -> bool,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let delivery = service.run(campaign, orders, filter).map_err(failed)?;
+    for recipient in &delivery.sent {
+        writeln!(out, "📧 {} sent to {}", campaign.id, recipient)?;
+    }
+    for (recipient, e) in &delivery.failed {
+        writeln!(out, "❌ {}: {}", recipient, e)?;
+    }
+    for recipient in &delivery.given_up {
+        writeln!(out, "⛔ {}: gave up after repeated failures", recipient)?;
+    }
+    writeln!(
+        out,
+        "{} sent in {} batch(es), {} already had it",
+        delivery.sent.len(),
+        delivery.batches,
+        delivery.skipped
+    )?;
+    match delivery.failed.len() {
+        0 => Ok(()),
+        failed => Err(CliError::CommandFailed(format!(
+            "{} message(s) not sent; run again to retry them",
+            failed
+        ))),
+    }
+}
+
+fn open_outbox(path: PathBuf) -> Result<JsonCampaignOutbox, CliError> {
+    JsonCampaignOutbox::new(path).map_err(failed)
+}
+
+fn failed(e: impl std::fmt::Display) -> CliError {
+    CliError::CommandFailed(e.to_string())
+}
+
+/// `--name N`, a positive whole number, if given
+fn number(parsed: &ParsedArgs, name: &str) -> Result<Option<u32>, CliError> {
+    parsed
+        .option(name)
+        .map(|value| {
+            value.parse::<u32>().ok().filter(|&n| n > 0).ok_or_else(|| {
+                CliError::Usage(format!("--{} expects a positive number, got '{}'", name, value))
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn run_with(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_dry_run_prints_one_message_per_customer() {
+        let body = std::env::temp_dir().join(format!("campaign-{}.txt", Uuid::new_v4()));
+        fs::write(&body, "Hello {{name}},\n\nThe autumn menu is here.").unwrap();
+        let body_path = body.display().to_string();
+
+        let printed = run_with(&[
+            "send", "autumn", "--demo", "--dry-run", "--subject", "New menu", "--body", &body_path,
+        ])
+        .unwrap();
+        assert!(printed.matches("Subject: New menu").count() > 1);
+        assert!(printed.contains("The autumn menu is here."));
+        assert!(!printed.contains("{{name}}"));
+        fs::remove_file(body).unwrap();
+
+        assert!(matches!(run_with(&["send", "autumn", "--demo"]), Err(CliError::Usage(_))));
+        assert!(matches!(run_with(&["status"]), Err(CliError::Usage(_))));
+    }
+}
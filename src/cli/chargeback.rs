@@ -0,0 +1,159 @@
+// SOLID: `chargeback` subcommand (gateway disputes)
+//
+// chargeback <PAYLOAD> [--wallets wallets.json] [--coupons coupons.json]
+//                      [--loyalty loyalty.json]
+//
+// PAYLOAD is the body of the gateway's chargeback webhook, a JSON file (or
+// `-` for stdin, so a webhook receiver can pipe it through):
+//
+//   {"case_id": "CB-1", "payment_id": "CC-...", "amount": 3.50, "reason": "fraudulent"}
+//
+// The order paid with payment_id (in --file) becomes Disputed. The
+// customer's store credit, unused coupons and loyalty points are frozen
+// when the wallets, coupons and loyalty files exist (or are given). A
+// redelivered case changes nothing.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::adapters::{JsonCouponStore, JsonLoyaltyRepository, JsonStoreCredit};
+use crate::domain::Chargeback;
+use crate::services::{DisputeError, DisputeService};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Entry point for `chargeback ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let source = parsed
+        .positional(0)
+        .ok_or_else(|| CliError::Usage("chargeback needs the webhook payload".to_string()))?;
+    let body = if source == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        std::fs::read_to_string(source)
+    }
+    .map_err(|e| CliError::InvalidConfig(format!("Could not read {}: {}", source, e)))?;
+    let chargeback: Chargeback = serde_json::from_str(&body)
+        .map_err(|e| CliError::InvalidConfig(format!("Not a chargeback payload: {}", e)))?;
+
+    let mut disputes = DisputeService::new();
+    if let Some(path) = store_path(&parsed, "wallets", "wallets.json") {
+        let credit = JsonStoreCredit::new(path).map_err(invalid)?;
+        disputes = disputes.with_store_credit(credit);
+    }
+    if let Some(path) = store_path(&parsed, "coupons", "coupons.json") {
+        let coupons = JsonCouponStore::new(path).map_err(invalid)?;
+        disputes = disputes.with_coupons(coupons);
+    }
+    if let Some(path) = store_path(&parsed, "loyalty", "loyalty.json") {
+        let accounts = JsonLoyaltyRepository::new(path).map_err(invalid)?;
+        disputes = disputes.with_loyalty(accounts);
+    }
+
+    let (mut repository, _) = open_repository(&parsed)?;
+    let registered = disputes
+        .register(repository.as_mut(), chargeback)
+        .map_err(dispute_error)?;
+    let order = &registered.order;
+    let Some(chargeback) = &order.chargeback else {
+        return Err(CliError::CommandFailed("Dispute not recorded".to_string()));
+    };
+
+    if registered.duplicate {
+        writeln!(
+            out,
+            "Case {} is already registered on order {}",
+            chargeback.case_id,
+            order.short_id()
+        )?;
+        return Ok(());
+    }
+    writeln!(
+        out,
+        "⚠ Order {} disputed: case {}, {} ({})",
+        order.short_id(),
+        chargeback.case_id,
+        chargeback.amount,
+        if chargeback.reason.is_empty() { "no reason given" } else { &chargeback.reason }
+    )?;
+    if registered.credit_frozen {
+        writeln!(out, "  Store credit of {} frozen", order.customer.email)?;
+    }
+    if registered.coupons_frozen > 0 {
+        writeln!(out, "  {} coupon(s) on hold", registered.coupons_frozen)?;
+    }
+    if registered.loyalty_frozen {
+        writeln!(out, "  Loyalty points of {} frozen", order.customer.email)?;
+    }
+    Ok(())
+}
+
+/// `--NAME PATH`, or the default file if it exists
+fn store_path(parsed: &ParsedArgs, name: &str, default: &str) -> Option<PathBuf> {
+    match parsed.option(name) {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Path::new(default).exists().then(|| PathBuf::from(default)),
+    }
+}
+
+fn invalid(e: impl std::error::Error) -> CliError {
+    CliError::InvalidConfig(e.to_string())
+}
+
+fn dispute_error(e: DisputeError) -> CliError {
+    match e {
+        DisputeError::StorageFailed(e) => CliError::StorageFailed(e),
+        DisputeError::InvalidChargeback(_) => CliError::InvalidConfig(e.to_string()),
+        other => CliError::CommandFailed(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::JsonOrderRepository;
+    use crate::domain::{Coffee, Customer, Order, OrderStatus};
+    use crate::ports::{OrderRepository, StoreCredit};
+    use std::fs;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_webhook_payload_disputes_the_order() {
+        let dir = std::env::temp_dir();
+        let orders = dir.join(format!("orders-{}.json", Uuid::new_v4()));
+        let wallets = dir.join(format!("wallets-{}.json", Uuid::new_v4()));
+        let payload = dir.join(format!("chargeback-{}.json", Uuid::new_v4()));
+        let mut order = Order::single(Customer::named("Ada"), Coffee::medium());
+        order.mark_as_paid("CC-42".to_string()).unwrap();
+        JsonOrderRepository::new(orders.clone()).unwrap().save(&order).unwrap();
+        fs::write(&payload, r#"{"case_id": "CB-1", "payment_id": "CC-42", "amount": 3.5}"#)
+            .unwrap();
+
+        let args: Vec<String> = [
+            payload.to_str().unwrap(),
+            "--file",
+            orders.to_str().unwrap(),
+            "--wallets",
+            wallets.to_str().unwrap(),
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("disputed: case CB-1, 3.50 USD (no reason given)"));
+        assert!(text.contains("Store credit of ada@example.com frozen"));
+        let stored = JsonOrderRepository::new(orders.clone()).unwrap().list_all().unwrap();
+        assert_eq!(stored[0].status, OrderStatus::Disputed);
+        let credit = JsonStoreCredit::new(wallets.clone()).unwrap();
+        assert!(credit.wallets().unwrap()[0].frozen.is_some());
+
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("already registered"));
+        for path in [orders, wallets, payload] {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// SOLID: `dashboard` subcommand
+//
+// dashboard [--output dashboard.html] [--date YYYY-MM-DD]
+//
+// Collects the reports and writes them as one self-contained HTML file,
+// so the owner can look at the day without running anything else.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::presentation::{render_dashboard, DashboardData};
+use crate::services::{DateRange, ReportingService};
+use chrono::{Duration, Utc};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Entry point for `dashboard ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let output = PathBuf::from(parsed.option("output").unwrap_or("dashboard.html"));
+
+    let (repository, today) = open_repository(&parsed)?;
+    let date = parsed.date("date")?.unwrap_or(today);
+    let service = ReportingService::new(repository);
+    // Mixes cover the 7 days ending on the dashboard date
+    let range = DateRange::new(date - Duration::days(6), date);
+
+    let data = DashboardData {
+        generated_at: Utc::now(),
+        daily: service.daily(date)?,
+        weekly: service.weekly(date)?,
+        product_mix: service.product_mix(range)?,
+        payment_mix: service.payment_mix(range)?,
+    };
+
+    fs::write(&output, render_dashboard(&data))?;
+    writeln!(out, "✅ Dashboard written to {}", output.display())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_html_file() {
+        let path = std::env::temp_dir().join(format!("dashboard-{}.html", uuid::Uuid::new_v4()));
+        let args: Vec<String> = ["--demo", "--output", path.to_str().unwrap()]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        let html = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(html.contains("Coffee Shop Dashboard"));
+        assert!(String::from_utf8(out).unwrap().contains("Dashboard written"));
+    }
+}
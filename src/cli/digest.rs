@@ -0,0 +1,105 @@
+// SOLID: `digest` subcommand
+//
+// digest --to owner@example.com[,manager@example.com] --smtp HOST:PORT
+//        [--from ADDRESS] [--date DATE] [--sent-log PATH] [--dry-run]
+//
+// Emails the daily report (DailyDigestJob) to each recipient. Which copies
+// went out is noted in the sent log (default: digests-sent.log), so running
+// it again, from cron or by hand after a failure, only sends what's missing.
+// --dry-run prints the messages instead and leaves the log alone.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::adapters::{FileSentMailLog, MemorySentMailLog, RecordingMailer, SmtpMailer};
+use crate::ports::{Mailer, SentMailLog};
+use crate::presentation::render_daily_digest;
+use crate::services::{DailyDigestJob, DailyReport, ReportingService};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Entry point for `digest ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let recipients: Vec<String> = parsed
+        .option("to")
+        .ok_or_else(|| CliError::Usage("digest needs --to ADDRESS[,ADDRESS...]".to_string()))?
+        .split(',')
+        .map(|address| address.trim().to_string())
+        .filter(|address| !address.is_empty())
+        .collect();
+
+    let (repository, today) = open_repository(&parsed)?;
+    let date = parsed.date("date")?.unwrap_or(today);
+    let report = ReportingService::new(repository).daily(date)?;
+
+    if parsed.flag("--dry-run") {
+        let mailer = RecordingMailer::new();
+        let mut job = DailyDigestJob::new(
+            mailer.clone(),
+            MemorySentMailLog::new(),
+            recipients,
+            render_daily_digest,
+        );
+        job.deliver(&report);
+        for message in mailer.sent() {
+            let to = message.to.join(", ");
+            writeln!(out, "To: {}\nSubject: {}\n\n{}", to, message.subject, message.text)?;
+        }
+        return Ok(());
+    }
+
+    let address = parsed.option("smtp").ok_or_else(|| {
+        CliError::Usage("digest needs --smtp HOST:PORT (or --dry-run)".to_string())
+    })?;
+    let from = parsed.option("from").unwrap_or("coffee-shop@localhost");
+    let log_path = PathBuf::from(parsed.option("sent-log").unwrap_or("digests-sent.log"));
+    let log = FileSentMailLog::new(log_path).map_err(|e| CliError::CommandFailed(e.to_string()))?;
+    let mailer = SmtpMailer::new(address, from);
+    send(DailyDigestJob::new(mailer, log, recipients, render_daily_digest), &report, out)
+}
+
+fn send<M: Mailer, L: SentMailLog>(
+    mut job: DailyDigestJob<M, L>,
+    report: &DailyReport,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let delivery = job.deliver(report);
+    for recipient in &delivery.sent {
+        writeln!(out, "📧 Digest for {} sent to {}", report.date, recipient)?;
+    }
+    for recipient in &delivery.skipped {
+        writeln!(out, "⏭  {} already has the digest for {}", recipient, report.date)?;
+    }
+    for (recipient, e) in &delivery.failed {
+        writeln!(out, "❌ {}: {}", recipient, e)?;
+    }
+    match delivery.failed.len() {
+        0 => Ok(()),
+        failed => Err(CliError::CommandFailed(format!(
+            "{} digest(s) not sent; run again to retry them",
+            failed
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dry_run_prints_one_message_per_recipient() {
+        let to = "owner@example.com, bob@example.com";
+        let args: Vec<String> = ["--demo", "--dry-run", "--to", to]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        let printed = String::from_utf8(out).unwrap();
+        assert_eq!(printed.matches("Subject: Daily digest").count(), 2);
+        assert!(printed.contains("To: bob@example.com\n"));
+
+        let missing = run(&["--demo".to_string()], &mut Vec::new());
+        assert!(matches!(missing, Err(CliError::Usage(_))));
+    }
+}
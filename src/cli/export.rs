@@ -0,0 +1,160 @@
+// SOLID: `export` subcommand (accounting files)
+//
+// export --format ledger|qif|ofx [--from DATE] [--to DATE]
+//        [--accounts mapping.json] [--wallets wallets.json] [--output PATH]
+//
+// Sales become journal entries (services/accounting.rs), then one of the
+// AccountingExporter formats writes them. Without --output, the file goes to stdout.
+// With --wallets, store credit is booked too, and the outstanding liability
+// is reported (a comment at the end of a ledger file, or a line after --output).
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::adapters::JsonStoreCredit;
+use crate::ports::StoreCredit;
+use crate::presentation::{AccountingExporter, LedgerExporter, OfxExporter, QifExporter};
+use crate::services::{
+    apply_store_credit, credit_liability, journal_entries, store_credit_entries, AccountMapping,
+    CreditLiability, DateRange,
+};
+use chrono::Duration;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Entry point for `export ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+
+    let exporter: Box<dyn AccountingExporter> = match parsed.option("format").unwrap_or("ledger") {
+        "ledger" => Box::new(LedgerExporter),
+        "qif" => Box::new(QifExporter),
+        "ofx" => Box::new(OfxExporter),
+        other => return Err(CliError::Usage(format!("Unknown export format '{}'", other))),
+    };
+
+    let mapping = match parsed.option("accounts") {
+        Some(path) => load_mapping(path)?,
+        None => AccountMapping::default(),
+    };
+
+    let (repository, today) = open_repository(&parsed)?;
+    // Default: the last 30 days
+    let to = parsed.date("to")?.unwrap_or(today);
+    let from = parsed.date("from")?.unwrap_or(to - Duration::days(29));
+    let range = DateRange::new(from, to);
+
+    let orders: Vec<_> = repository
+        .list_all()?
+        .into_iter()
+        .filter(|order| range.contains(order.created_at.date_naive()))
+        .collect();
+    let mut entries = journal_entries(&orders, &mapping);
+
+    let mut liabilities = Vec::new();
+    if let Some(path) = parsed.option("wallets") {
+        let wallets = JsonStoreCredit::new(PathBuf::from(path))
+            .and_then(|credit| credit.wallets())
+            .map_err(|e| CliError::InvalidConfig(e.to_string()))?;
+        apply_store_credit(&mut entries, &wallets, &mapping);
+        entries.extend(
+            store_credit_entries(&wallets, &mapping)
+                .into_iter()
+                .filter(|entry| range.contains(entry.date)),
+        );
+        entries.sort_by_key(|entry| entry.date);
+        liabilities = credit_liability(&wallets, to);
+    }
+    let mut file = exporter.export(&entries, &mapping);
+
+    match parsed.option("output") {
+        Some(path) => {
+            fs::write(path, file)?;
+            writeln!(
+                out,
+                "✅ Exported {} transaction(s) ({} to {}) to {}",
+                entries.len(),
+                from,
+                to,
+                path
+            )?;
+            for liability in &liabilities {
+                writeln!(out, "{}", liability_line(liability, &mapping))?;
+            }
+        }
+        None => {
+            if exporter.extension() == "ledger" {
+                for liability in &liabilities {
+                    file.push_str(&format!("; {}\n", liability_line(liability, &mapping)));
+                }
+            }
+            write!(out, "{}", file)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn liability_line(liability: &CreditLiability, mapping: &AccountMapping) -> String {
+    format!(
+        "{} on {}: {} outstanding \
+         ({} customer(s); granted {}, spent {}, restored {})",
+        mapping.store_credit,
+        liability.as_of,
+        liability.outstanding,
+        liability.customers,
+        liability.granted,
+        liability.spent,
+        liability.restored
+    )
+}
+
+fn load_mapping(path: &str) -> Result<AccountMapping, CliError> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| CliError::InvalidConfig(format!("Can't read {}: {}", path, e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| CliError::InvalidConfig(format!("Invalid account mapping in {}: {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Money;
+
+    fn run_to_string(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_export_demo_data() {
+        let ledger = run_to_string(&["--demo"]).unwrap();
+        assert!(ledger.contains("Income:Sales:Coffee"));
+
+        let qif = run_to_string(&["--demo", "--format", "qif"]).unwrap();
+        assert!(qif.starts_with("!Account\n"));
+    }
+
+    #[test]
+    fn test_wallets_add_credit_entries_and_liability() {
+        let path = std::env::temp_dir().join(format!("wallets-{}.json", uuid::Uuid::new_v4()));
+        let mut credit = JsonStoreCredit::new(path.clone()).unwrap();
+        credit.grant("ada@example.com", Money::usd(5.0), "Cold drink").unwrap();
+
+        // Demo data ends yesterday; the credit was granted today
+        let wallets = path.display().to_string();
+        let today = chrono::Utc::now().date_naive().to_string();
+        let ledger = run_to_string(&["--demo", "--wallets", &wallets, "--to", &today]).unwrap();
+        assert!(ledger.contains("Expenses:Store Credit Granted"));
+        assert!(ledger.contains("; Liabilities:Store Credit on "));
+        assert!(ledger.contains(": 5.00 USD outstanding (1 customer(s)"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_bad_mapping_file() {
+        let result = run_to_string(&["--demo", "--accounts", "/no/such/mapping.json"]);
+        assert!(matches!(result, Err(CliError::InvalidConfig(_))));
+    }
+}
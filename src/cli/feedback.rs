@@ -0,0 +1,129 @@
+// SOLID: `feedback` subcommand
+//
+// feedback link <ORDER_ID> --secret SECRET [--link-base URL]
+// feedback submit <TOKEN> --rating 1-5 [--comment TEXT] --secret SECRET
+// feedback report [--from DATE] [--to DATE]
+//
+// Ratings live in --store (default: feedback.json). `link` prints what the
+// customer would have been sent, `submit` is what the rating page calls,
+// `report` adds up the answers (average and NPS).
+
+use super::{CliError, ParsedArgs};
+use crate::adapters::{ConsoleNotifier, JsonFeedbackRepository, SystemClock};
+use crate::services::{DateRange, FeedbackService};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const DEFAULT_LINK_BASE: &str = "http://localhost:8080/feedback";
+
+type Service = FeedbackService<JsonFeedbackRepository, ConsoleNotifier<io::Sink>>;
+
+/// Entry point for `feedback ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    match parsed.positional(0) {
+        Some("link") => {
+            let order_id = parsed
+                .positional(1)
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .ok_or_else(|| CliError::Usage("feedback link needs an order ID".to_string()))?;
+            writeln!(out, "{}", open(&parsed)?.link_for(order_id))?;
+            Ok(())
+        }
+        Some("submit") => {
+            let token = parsed
+                .positional(1)
+                .ok_or_else(|| CliError::Usage("feedback submit needs a TOKEN".to_string()))?;
+            let rating = parsed
+                .option("rating")
+                .and_then(|rating| rating.parse::<u8>().ok())
+                .ok_or_else(|| CliError::Usage("feedback submit needs --rating 1-5".to_string()))?;
+
+            let feedback = open(&parsed)?
+                .submit(token, rating, parsed.option("comment"), &SystemClock)
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+            writeln!(out, "⭐ Thanks! Order {} rated {}/5", feedback.order_id, feedback.rating)?;
+            Ok(())
+        }
+        Some("report") | None => {
+            let range = match (parsed.date("from")?, parsed.date("to")?) {
+                (None, None) => None,
+                (from, to) => Some(DateRange::new(
+                    from.unwrap_or(chrono::NaiveDate::MIN),
+                    to.unwrap_or(chrono::NaiveDate::MAX),
+                )),
+            };
+            let report = open(&parsed)?
+                .report(range)
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+
+            writeln!(out, "Responses: {}", report.responses)?;
+            let Some(nps) = report.nps else {
+                return Ok(());
+            };
+            writeln!(out, "Average:   {:.2} / 5", report.average_rating)?;
+            writeln!(
+                out,
+                "NPS:       {:+} ({} promoters, {} passives, {} detractors)",
+                nps, report.promoters, report.passives, report.detractors
+            )?;
+            for (rating, count) in report.distribution.iter().rev() {
+                writeln!(out, "  {} {:<20} {}", rating, "★".repeat(*count.min(&20)), count)?;
+            }
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown feedback action '{}'", other))),
+    }
+}
+
+/// The service over --store; links and tokens need --secret, reports don't
+fn open(parsed: &ParsedArgs) -> Result<Service, CliError> {
+    let path = PathBuf::from(parsed.option("store").unwrap_or("feedback.json"));
+    let repository =
+        JsonFeedbackRepository::new(path).map_err(|e| CliError::InvalidConfig(e.to_string()))?;
+    let needs_secret = matches!(parsed.positional(0), Some("link" | "submit"));
+    let secret = match parsed.option("secret") {
+        Some(secret) => secret,
+        None if needs_secret => {
+            return Err(CliError::Usage("feedback link/submit needs --secret".to_string()));
+        }
+        None => "",
+    };
+    let link_base = parsed.option("link-base").unwrap_or(DEFAULT_LINK_BASE);
+    let notifier = ConsoleNotifier::with_writer(io::sink());
+    Ok(FeedbackService::new(repository, notifier, secret, link_base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_in(store: &std::path::Path, args: &[&str]) -> Result<String, CliError> {
+        let mut args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        args.extend(["--store".to_string(), store.display().to_string()]);
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_link_submit_report() {
+        let store = std::env::temp_dir().join(format!("feedback-{}.json", Uuid::new_v4()));
+        let order_id = Uuid::new_v4().to_string();
+
+        let link = run_in(&store, &["link", &order_id, "--secret", "s"]).unwrap();
+        let token = link.trim().rsplit('/').next().unwrap().to_string();
+        let rated = run_in(&store, &["submit", &token, "--rating", "5", "--secret", "s"]).unwrap();
+        assert!(rated.contains("rated 5/5"));
+
+        let twice = run_in(&store, &["submit", &token, "--rating", "1", "--secret", "s"]);
+        assert!(matches!(twice, Err(CliError::CommandFailed(_))));
+        let wrong = run_in(&store, &["submit", &token, "--rating", "5", "--secret", "x"]);
+        assert!(matches!(wrong, Err(CliError::CommandFailed(_))));
+
+        let report = run_in(&store, &["report"]).unwrap();
+        assert!(report.contains("Responses: 1") && report.contains("NPS:       +100"));
+        std::fs::remove_file(store).unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+// SOLID: `import` subcommand
+//
+// import <PATH> [--format csv|json] [--dry-run] [--file orders.json]
+//
+// Loads historical or catered orders into the orders file (OrderImporter).
+// The format comes from the extension unless --format says otherwise.
+// Rejected rows are listed with their number and reason; the rest is imported.
+// With --dry-run, nothing is written.
+
+use super::{CliError, ParsedArgs};
+use crate::adapters::{JsonOrderRepository, SystemClock};
+use crate::services::{ImportError, ImportFormat, OrderImporter};
+use std::fs;
+use std::io::Write;
+
+/// Entry point for `import ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let path = parsed
+        .positional(0)
+        .ok_or_else(|| CliError::Usage("import needs a file to read".to_string()))?;
+    let format = match parsed.option("format") {
+        Some("csv") => ImportFormat::Csv,
+        Some("json") => ImportFormat::Json,
+        Some(other) => return Err(CliError::Usage(format!("Unknown import format '{}'", other))),
+        None => ImportFormat::from_path(path).ok_or_else(|| {
+            CliError::Usage(format!("Can't tell the format of '{}', use --format", path))
+        })?,
+    };
+    let dry_run = parsed.flag("--dry-run");
+
+    let text = fs::read_to_string(path)
+        .map_err(|e| CliError::InvalidConfig(format!("Could not read {}: {}", path, e)))?;
+    let mut repository = JsonOrderRepository::new(parsed.file())?;
+    let report = OrderImporter::new()
+        .dry_run(dry_run)
+        .import_str(format, &text, &mut repository, &SystemClock)
+        .map_err(import_error)?;
+
+    for rejected in &report.rejected {
+        writeln!(out, "  row {}: {}", rejected.row, rejected.message)?;
+    }
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    writeln!(
+        out,
+        "✅ {} {} order(s), rejected {}",
+        verb,
+        report.imported,
+        report.rejected.len()
+    )?;
+    Ok(())
+}
+
+fn import_error(e: ImportError) -> CliError {
+    match e {
+        ImportError::StorageFailed(e) => CliError::StorageFailed(e),
+        other => CliError::InvalidConfig(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::OrderRepository;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_import_csv_into_orders_file() {
+        let source = std::env::temp_dir().join(format!("import-{}.csv", Uuid::new_v4()));
+        let target = std::env::temp_dir().join(format!("orders-{}.json", Uuid::new_v4()));
+        fs::write(
+            &source,
+            "created_at,customer_name,customer_email,item,price\n\
+             2026-01-05,Ada,ada@example.com,Coffee,3.50\n\
+             2026-01-05,Bob,bob@example.com,Coffee,-1\n",
+        )
+        .unwrap();
+        let args: Vec<String> = [source.to_str().unwrap(), "--file", target.to_str().unwrap()]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("row 3: price -1 for Coffee is not valid"));
+        assert!(text.contains("Imported 1 order(s), rejected 1"));
+        let imported = JsonOrderRepository::new(target.clone()).unwrap().list_all().unwrap();
+        assert_eq!(imported.len(), 1);
+        let _ = fs::remove_file(source);
+        let _ = fs::remove_file(target);
+    }
+}
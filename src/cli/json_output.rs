@@ -0,0 +1,145 @@
+// SOLID: `--output json` - one JSON result on stdout, for scripts and CI
+//
+// Any command takes `--output json`, anywhere on the command line. Its usual
+// human text then goes to stderr, and stdout gets exactly one JSON object,
+// the same shape for every command and whatever happened:
+//
+//   {"command":"report","ok":true,"exit_code":0,"data":{...},"error":null}
+//   {"command":"wallet","ok":false,"exit_code":2,"data":null,"error":"Unknown wallet action 'x'"}
+//
+// `data` is the command's result where it has one in JSON (reports, as with
+// `--format json`), null otherwise. Commands stay unaware of it (OCP): they
+// write to the writer they are given, and this module decides where it goes.
+//
+// `--output PATH` keeps meaning a file for the commands that write one; only
+// the values `json` and `text` are taken here (write a file named json as ./json).
+
+use super::{dispatch, CliError};
+use crate::presentation::StyledWriter;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+
+/// Commands whose output is their JSON result (when asked `--format json`)
+const JSON_RESULT_COMMANDS: [&str; 1] = ["report"];
+
+/// Human text, or one JSON result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// The format asked for, taking `--output json|text` out of `args`
+pub fn take_output_format(args: &mut Vec<String>) -> OutputFormat {
+    let mut format = OutputFormat::Text;
+    let mut i = 0;
+    while i + 1 < args.len() {
+        let value = match (args[i].as_str(), args[i + 1].as_str()) {
+            ("--output", "json") => OutputFormat::Json,
+            ("--output", "text") => OutputFormat::Text,
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        format = value;
+        args.drain(i..i + 2);
+    }
+    format
+}
+
+/// What stdout gets under `--output json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonResult {
+    /// The first argument: "report", "wallet"...
+    pub command: String,
+    pub ok: bool,
+    /// What the process exits with
+    pub exit_code: i32,
+    pub data: Value,
+    pub error: Option<String>,
+}
+
+/// Run one command; its human text goes to `err`, the JSON result to `out`
+pub fn run_json(
+    mut args: Vec<String>,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> Result<(), CliError> {
+    let command = args.first().cloned().unwrap_or_default();
+    let has_json_result = JSON_RESULT_COMMANDS.contains(&command.as_str());
+    if has_json_result && !args.iter().any(|arg| arg == "--format") {
+        args.extend(["--format".to_string(), "json".to_string()]);
+    }
+
+    let mut captured = Vec::new();
+    let result = if has_json_result {
+        dispatch(&args, &mut captured)
+    } else {
+        dispatch(&args, &mut StyledWriter::new(&mut *err))
+    };
+    // A report in CSV or text is still text for people
+    let data = serde_json::from_slice(&captured).unwrap_or_else(|_| {
+        let _ = err.write_all(&captured);
+        Value::Null
+    });
+
+    let json = JsonResult {
+        command,
+        ok: result.is_ok(),
+        exit_code: result.as_ref().err().map_or(0, CliError::exit_code),
+        data,
+        error: result.as_ref().err().map(|e| match e {
+            // Without the usage text appended to it
+            CliError::Usage(msg) => msg.clone(),
+            e => e.to_string(),
+        }),
+    };
+    let line = serde_json::to_string(&json)
+        .map_err(|e| CliError::CommandFailed(format!("Failed to serialize: {}", e)))?;
+    writeln!(out, "{}", line)?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_with(args: &[&str]) -> (Value, String, Result<(), CliError>) {
+        let mut args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        assert_eq!(take_output_format(&mut args), OutputFormat::Json);
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let result = run_json(args, &mut out, &mut err);
+        let json = serde_json::from_slice(&out).unwrap();
+        (json, String::from_utf8(err).unwrap(), result)
+    }
+
+    #[test]
+    fn test_report_data_on_stdout() {
+        let (json, err, result) = run_with(&["report", "daily", "--demo", "--output", "json"]);
+
+        assert!(result.is_ok());
+        assert_eq!(json["command"], "report");
+        assert_eq!((&json["ok"], &json["exit_code"]), (&true.into(), &0.into()));
+        assert!(json["data"]["orders"].is_number(), "{}", json);
+        assert!(json["error"].is_null());
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn test_text_goes_to_stderr_and_errors_are_results() {
+        let (json, err, _) = run_with(&["--output", "json", "verify"]);
+        assert!(json["data"].is_null());
+        assert!(err.contains("exercise-1"), "{}", err);
+
+        let (json, _, result) = run_with(&["wallet", "--output", "json", "frobnicate"]);
+        assert_eq!(result.unwrap_err().exit_code(), 2);
+        assert_eq!((&json["ok"], &json["exit_code"]), (&false.into(), &2.into()));
+        assert_eq!(json["error"], "Unknown wallet action 'frobnicate'");
+
+        let mut args = vec!["dashboard".to_string(), "--output".to_string(), "x.html".to_string()];
+        assert_eq!(take_output_format(&mut args), OutputFormat::Text);
+        assert_eq!(args.len(), 3);
+    }
+}
@@ -0,0 +1,64 @@
+// SOLID: `menu` subcommand
+//
+// menu [--locale fr-CH] [--translations translations.json]
+//
+// Prints the kiosk menu with its prices, named in the configured locale
+// (see `configured_locale`): --locale, else COFFEE_SHOP_LOCALE, else the
+// system's LANG. A name with no translation for the locale or its language
+// is printed in English.
+
+use super::{configured_locale, kiosk_menu, CliError, ParsedArgs};
+use crate::domain::Size;
+use std::io::Write;
+
+/// Entry point for `menu ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let locale = configured_locale(&parsed);
+
+    writeln!(out, "Menu ({})", locale)?;
+    for item in kiosk_menu(&parsed)? {
+        writeln!(
+            out,
+            "  {:<36} {:>6.2} {:>6.2} {:>6.2}",
+            item.name,
+            item.price(Size::Small).to_major(),
+            item.price(Size::Medium).to_major(),
+            item.price(Size::Large).to_major()
+        )?;
+        if let Some(description) = &item.description {
+            writeln!(out, "    {}", description)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_menu_in_a_locale_with_english_fallback() {
+        let path = std::env::temp_dir().join(format!("translations-{}.json", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{ "Coffee": { "fr": { "name": "Café", "description": "Torréfié à Genève" } } }"#,
+        )
+        .unwrap();
+        let args: Vec<String> = ["--locale", "fr_CH", "--translations", path.to_str().unwrap()]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut out = Vec::new();
+
+        run(&args, &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("Menu (fr-ch)\n"));
+        assert!(text.contains("  Café "), "{}", text);
+        assert!(text.contains("    Torréfié à Genève\n"));
+        assert!(text.contains("  Green Tea "));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+// SOLID: `metrics` subcommand
+//
+// metrics [--from DATE] [--to DATE] [--output PATH] [--at "DATE HH:MM"] [--window MINUTES]
+//
+// Prints placement and preparation percentiles in the Prometheus text
+// format. Run it from cron with `--output` pointing at node_exporter's
+// textfile directory and the numbers show up in Grafana.
+//
+// Then the live gauges: orders and revenue per minute over the last
+// `--window` minutes (default 5) before `--at` (default now), replayed from
+// stored orders through LiveMetrics. Failed payments leave no order behind,
+// so from a file the failure ratio is 0: only a running shop's LiveMetrics,
+// fed by the bus, sees them.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::domain::OrderStatus;
+use crate::presentation::{render_live_prometheus, render_prometheus};
+use crate::services::{DateRange, LiveMetrics, ReportingService};
+use chrono::{Duration, Utc};
+use std::fs;
+use std::io::Write;
+
+/// Entry point for `metrics ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+
+    let window = match parsed.option("window") {
+        Some(minutes) => minutes.parse::<i64>().ok().filter(|&m| m > 0).ok_or_else(|| {
+            CliError::Usage(format!("--window expects a number of minutes, got '{}'", minutes))
+        })?,
+        None => 5,
+    };
+    let at = parsed.timestamp("at")?.unwrap_or_else(Utc::now);
+
+    let (repository, today) = open_repository(&parsed)?;
+    let to = parsed.date("to")?.unwrap_or(today);
+    let from = parsed.date("from")?.unwrap_or(to - Duration::days(6));
+
+    let mut live = LiveMetrics::new().with_window(Duration::minutes(window));
+    for order in repository.list_all()? {
+        if let Some(paid_at) = order.entered_at(&OrderStatus::Paid) {
+            live.record_sale(paid_at, order.total_price);
+        }
+    }
+
+    let report = ReportingService::new(repository).latency(DateRange::new(from, to))?;
+    let mut text = render_prometheus(&report);
+    text.push_str(&render_live_prometheus(&live.stats(at)));
+
+    match parsed.option("output") {
+        Some(path) => {
+            fs::write(path, text)?;
+            writeln!(out, "✅ Metrics written to {}", path)?;
+        }
+        None => write!(out, "{}", text)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::JsonOrderRepository;
+    use crate::domain::{Coffee, Customer, Order};
+    use crate::ports::OrderRepository;
+
+    #[test]
+    fn test_prints_summaries_for_demo_data() {
+        let args = vec!["--demo".to_string()];
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("# TYPE coffee_order_prep_seconds summary"));
+        assert!(!text.contains("coffee_order_prep_seconds_count 0\n"));
+        assert!(text.contains("# TYPE coffee_orders_per_minute gauge"));
+    }
+
+    #[test]
+    fn test_live_gauges_over_a_past_window() {
+        let path = std::env::temp_dir().join(format!("metrics-{}.json", uuid::Uuid::new_v4()));
+        let mut repository = JsonOrderRepository::new(path.clone()).unwrap();
+        for name in ["Ada", "Grace"] {
+            let mut order = Order::single(Customer::named(name), Coffee::medium());
+            order.mark_as_paid("CASH-1".to_string()).unwrap();
+            order.status_history[1].at = "2026-03-02T08:58:30Z".parse().unwrap();
+            repository.save(&order).unwrap();
+        }
+
+        let file = path.display().to_string();
+        let args: Vec<String> = ["--file", &file, "--at", "2026-03-02 09:00", "--window", "2"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("coffee_orders_per_minute 1\n"), "{}", text);
+        assert!(text.contains("coffee_revenue_per_minute 3.5\n"));
+        assert!(text.contains("coffee_payment_failure_ratio 0\n"));
+    }
+}
@@ -0,0 +1,167 @@
+// SOLID: `migrate` subcommand
+//
+// migrate --to PATH [--skip-existing] [--compress gzip|zstd|none]
+//         [--file orders.json | --demo]
+//
+// Copies every order from the data source into another file with
+// RepositoryMigrator. The extension picks the backend on both sides, so this
+// converts between formats: `--file orders.json --to orders.bin` and back,
+// or into a SQLite database (`--to orders.db`, feature `sqlite`).
+// An order already in the target aborts the copy (nothing is written)
+// unless --skip-existing is given.
+//
+// --compress sets how the target is written (see adapters::compression);
+// without it, an existing target keeps its compression and a new one has none.
+
+use super::{is_binary_file, is_sqlite_file, open_repository, CliError, ParsedArgs};
+use crate::adapters::{BinaryOrderRepository, Compression, JsonOrderRepository};
+use crate::ports::OrderRepository;
+use crate::services::{MigrationReport, RepositoryMigrator};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Entry point for `migrate ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let target = PathBuf::from(
+        parsed
+            .option("to")
+            .ok_or_else(|| CliError::Usage("migrate needs --to PATH".to_string()))?,
+    );
+    if !parsed.flag("--demo") && parsed.file() == target {
+        return Err(CliError::Usage("--to must differ from the source file".to_string()));
+    }
+
+    let compression = compression(&parsed)?;
+
+    let (source, _) = open_repository(&parsed)?;
+    let migrator = RepositoryMigrator::new().skip_existing(parsed.flag("--skip-existing"));
+    let report = if is_sqlite_file(&target) {
+        migrate_to_sqlite(&migrator, &source, &target)?
+    } else if is_binary_file(&target) {
+        let mut repository = BinaryOrderRepository::new(target.clone())?;
+        if let Some(compression) = compression {
+            repository = repository.with_compression(compression);
+        }
+        migrator.migrate(&source, &mut repository)?
+    } else {
+        let mut repository = JsonOrderRepository::new(target.clone())?;
+        if let Some(compression) = compression {
+            repository = repository.with_compression(compression);
+        }
+        migrator.migrate(&source, &mut repository)?
+    };
+
+    writeln!(
+        out,
+        "✅ Copied {} order(s) to {} ({} skipped)",
+        report.copied,
+        target.display(),
+        report.skipped
+    )?;
+    Ok(())
+}
+
+/// A database has no compression: --compress doesn't apply
+#[cfg(feature = "sqlite")]
+fn migrate_to_sqlite(
+    migrator: &RepositoryMigrator,
+    source: &impl OrderRepository,
+    target: &Path,
+) -> Result<MigrationReport, CliError> {
+    let mut repository = crate::adapters::SqliteOrderRepository::open(target)?;
+    Ok(migrator.migrate(source, &mut repository)?)
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn migrate_to_sqlite(
+    _: &RepositoryMigrator,
+    _: &impl OrderRepository,
+    target: &Path,
+) -> Result<MigrationReport, CliError> {
+    Err(CliError::InvalidConfig(format!(
+        "{} is a SQLite database: rebuild with --features sqlite",
+        target.display()
+    )))
+}
+
+/// `--compress NAME`, if given and compiled in
+fn compression(parsed: &ParsedArgs) -> Result<Option<Compression>, CliError> {
+    let Some(name) = parsed.option("compress") else {
+        return Ok(None);
+    };
+    let compression = Compression::from_name(name)
+        .ok_or_else(|| CliError::Usage(format!("Unknown compression '{}'", name)))?;
+    if !compression.is_available() {
+        return Err(CliError::Usage(format!(
+            "This build has no {} support (rebuild with --features {})",
+            name, name
+        )));
+    }
+    Ok(Some(compression))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::OrderRepository;
+    use std::fs;
+    use uuid::Uuid;
+
+    fn migrate(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_json_to_binary_and_back() {
+        let dir = std::env::temp_dir();
+        let bin = dir.join(format!("orders-{}.bin", Uuid::new_v4()));
+        let json = dir.join(format!("orders-{}.json", Uuid::new_v4()));
+        let (bin_arg, json_arg) = (bin.to_str().unwrap(), json.to_str().unwrap());
+
+        let output = migrate(&["--demo", "--to", bin_arg]).unwrap();
+        let count = BinaryOrderRepository::new(bin.clone()).unwrap().list_all().unwrap().len();
+        assert!(output.contains(&format!("Copied {} order(s)", count)));
+
+        migrate(&["--file", bin_arg, "--to", json_arg]).unwrap();
+        let back = JsonOrderRepository::new(json.clone()).unwrap();
+        assert_eq!(back.list_all().unwrap().len(), count);
+
+        // Everything is already there
+        assert!(matches!(
+            migrate(&["--file", bin_arg, "--to", json_arg]),
+            Err(CliError::StorageFailed(_))
+        ));
+        let output = migrate(&["--file", bin_arg, "--to", json_arg, "--skip-existing"]).unwrap();
+        assert!(output.contains(&format!("({} skipped)", count)));
+
+        let _ = fs::remove_file(bin);
+        let _ = fs::remove_file(json);
+    }
+
+    #[test]
+    fn test_compressed_target_reads_back_transparently() {
+        let bin = std::env::temp_dir().join(format!("orders-{}.bin.gz", Uuid::new_v4()));
+        let bin_arg = bin.to_str().unwrap();
+        assert!(is_binary_file(&bin));
+        assert!(matches!(
+            migrate(&["--demo", "--to", bin_arg, "--compress", "lz4"]),
+            Err(CliError::Usage(_))
+        ));
+
+        let result = migrate(&["--demo", "--to", bin_arg, "--compress", "gzip"]);
+        if !Compression::Gzip.is_available() {
+            assert!(matches!(result, Err(CliError::Usage(_))));
+            return;
+        }
+        result.unwrap();
+        assert_eq!(Compression::detect(&fs::read(&bin).unwrap()), Compression::Gzip);
+        let parsed = ParsedArgs::parse(&["--file".to_string(), bin_arg.to_string()]).unwrap();
+        let (reopened, _) = open_repository(&parsed).unwrap();
+        assert!(!reopened.list_all().unwrap().is_empty());
+        let _ = fs::remove_file(bin);
+    }
+}
@@ -0,0 +1,412 @@
+// SOLID: The CLI Layer (non-interactive subcommands)
+//
+// `cargo run` with no arguments starts the interactive menu.
+// `cargo run -- <command> ...` runs one command and exits, which is what
+// scripts and cron jobs need.
+//
+// PRINCIPLES:
+//
+// 1. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    The CLI parses arguments, wires dependencies and prints.
+//    Numbers come from services, layouts come from the presentation layer.
+//
+// 2. OPEN-CLOSED PRINCIPLE (OCP):
+//    Each command lives in its own module. Adding one is a new module
+//    plus one line in `run()`.
+//
+// Commands write to any `io::Write`, so tests can capture their output.
+// `run` hands them a StyledWriter: with `--plain` (see `output_style`) their
+// emoji, box drawing and color become plain text without them knowing. With
+// `--output json` their text goes to stderr and stdout gets one JSON result
+// (see json_output).
+
+pub mod args;
+pub mod campaign;
+pub mod chargeback;
+pub mod close_day;
+pub mod dashboard;
+pub mod digest;
+pub mod export;
+pub mod feedback;
+pub mod import;
+pub mod json_output;
+pub mod menu;
+pub mod metrics;
+pub mod migrate;
+pub mod prices;
+pub mod report;
+pub mod rewards;
+pub mod schema;
+pub mod serve;
+pub mod scenario;
+pub mod shell;
+pub mod status_board;
+pub mod tutorial;
+pub mod verify;
+pub mod wallet;
+pub mod webhooks;
+
+use crate::adapters::{
+    BinaryOrderRepository, CardPresentPayment, ConsoleNotifier, JsonOrderRepository,
+    MemoryOrderRepository,
+};
+use crate::domain::{Beverage, Locale, MenuTranslations, OutputStyle, Size};
+use crate::presentation::StyledWriter;
+use crate::ports::{OrderRepository, RepositoryError};
+use crate::seed::{SeedConfig, SeedGenerator};
+use crate::services::{KioskService, MenuItem, OrderService};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub use args::ParsedArgs;
+
+/// Why a command failed
+#[derive(Debug)]
+pub enum CliError {
+    /// Bad command line; the message says what was expected
+    Usage(String),
+    /// A configuration file is missing or malformed
+    InvalidConfig(String),
+    StorageFailed(RepositoryError),
+    OutputFailed(io::Error),
+    /// The command ran but could not finish
+    CommandFailed(String),
+}
+
+impl CliError {
+    /// Process exit code (2 for usage errors, like most Unix tools)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(msg) => write!(f, "{}\n\n{}", msg, USAGE),
+            CliError::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
+            CliError::StorageFailed(e) => write!(f, "Storage failed: {}", e),
+            CliError::OutputFailed(e) => write!(f, "Could not write output: {}", e),
+            CliError::CommandFailed(msg) => write!(f, "Command failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<RepositoryError> for CliError {
+    fn from(e: RepositoryError) -> Self {
+        CliError::StorageFailed(e)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::OutputFailed(e)
+    }
+}
+
+pub const USAGE: &str = "\
+Usage: coffee-shop-solid [COMMAND] [OPTIONS]
+
+Without a command, starts the interactive demo.
+
+Commands:
+  report daily        Sales of one day        [--date YYYY-MM-DD]
+  report weekly       Sales of one week       [--date YYYY-MM-DD]
+  report product-mix  Items sold per product  [--from DATE] [--to DATE]
+  report payment-mix  Orders per payment type [--from DATE] [--to DATE]
+  report latency      Placement/prep p50-p99  [--from DATE] [--to DATE]
+  report profitability
+                      Cost of goods, margins  [--from DATE] [--to DATE] [--costs costs.json]
+  report as-of --at \"DATE HH:MM\"
+                      Orders as they stood then (UTC): open ones, or [--status STATUS]
+  dashboard           Write an HTML dashboard [--date DATE] [--output PATH]
+  digest --to ADDRS   Email the daily report  [--date DATE] [--smtp HOST:PORT] [--from ADDR]
+                      once per recipient      [--sent-log PATH] [--dry-run]
+  campaign send|status
+                      Announcement to customers [--outbox campaign-outbox.json]
+                      send <ID> --subject TEXT --body PATH [--smtp HOST:PORT] [--from ADDR]
+                      [--since DATE] [--members-only] [--batch N] [--per-minute N] [--dry-run]
+  chargeback <PAYLOAD> Register a gateway's chargeback webhook (JSON file, - for stdin):
+                      the order becomes Disputed, the customer's credit, coupons and
+                      points are frozen    [--wallets wallets.json] [--coupons coupons.json]
+                      [--loyalty loyalty.json]
+  feedback link|submit|report
+                      Ratings after pickup    [--store feedback.json] [--from DATE] [--to DATE]
+                      link <ORDER_ID> --secret S, submit <TOKEN> --rating 1-5 --secret S
+                                              [--comment TEXT]
+  status-board        Public preparing/ready page, codes only [--output PATH] [--every SECS]
+  metrics             Prometheus latency text [--from DATE] [--to DATE] [--output PATH]
+                      and live gauges         [--window MINUTES] [--at \"DATE HH:MM\"]
+  export              Accounting file         [--format ledger|qif|ofx] [--from DATE] [--to DATE]
+                                              [--accounts mapping.json] [--output PATH]
+                                              [--wallets wallets.json]  (store credit)
+  import <PATH>       Load orders from a file [--format csv|json] [--dry-run]
+  menu                Kiosk menu and prices   [--locale TAG] [--translations translations.json]
+  close-day           End-of-day close        [--date DATE] [--counted-cash AMOUNT]
+                                              [--currency CODE]  (default: USD)
+                                              [--archive DIR]  (default: archive/)
+                                              [--lock-dir DIR | --lock-redis HOST:PORT]
+  migrate --to PATH   Copy all orders         [--skip-existing] [--compress gzip|zstd|none]
+                      to a .json or .bin (binary) file; the source is --file
+  prices show|publish|audit
+                      Effective-dated price books [--books price_books.json] [--date DATE]
+                      publish --from DATE FAMILY=PRICE..., audit re-prices --file
+  rewards run|redeem  Birthday/anniversary   [--coupons coupons.json] [--date DATE]
+                      coupons; redeem <CODE> <EMAIL>
+  scenario run <PATH> Replay a scenario file (TOML): place orders, advance the
+                      clock, mark orders, then check the report against `expect` steps
+  schema list|dump    JSON Schema of orders, events and API types (--features schema)
+                      dump [TYPE] [--output DIR]
+  serve               REST API over the orders [--addr 127.0.0.1:8080] (--features http):
+                      POST /orders, GET /orders/ID, GET /customers/EMAIL/orders,
+                      POST /orders/ID/cancel
+  shell               Prompt for ordering and every command below, with history
+                      and completion   [--history PATH] [--script PATH]
+                      [--clock now|HH:MM]: a demo clock (also --demo) to `clock advance`
+  tutorial            Guided SOLID lesson: swap adapters live, then a quiz [--lesson N]
+  verify [exercise-N] List the exercises, or check your solution to one
+  wallet balance|grant|liability
+                      Store credit            [--wallets wallets.json] [--date DATE]
+                      balance <EMAIL>, grant <EMAIL> <AMOUNT> [--reason TEXT]
+  webhooks list|add|enable|disable|remove
+                      Manage webhook subscriptions [--store webhooks.json]
+                      add <URL> --secret SECRET [--events order.placed,order.*]
+                      [--format json|xml|cloudevents]
+
+Data source (all commands):
+  --file PATH         Orders file (default: orders.json; a .bin file is read as binary,
+                      a gzip/zstd compressed one is decompressed, a .db or .sqlite
+                      file is a SQLite database with --features sqlite)
+  --demo              Use generated demo data instead of a file
+
+Locale (menu, and the interactive demo's receipts and notices):
+  --locale TAG        fr-CH, de... (default: $COFFEE_SHOP_LOCALE, else $LANG);
+                      untranslated names fall back to the language, then English
+
+Output (report):
+  --format text|json|csv   (default: text)
+
+Output (all commands, and the interactive demo's notices and receipts):
+  --plain             No emoji, box drawing or color, for screen readers and log
+                      scraping (also COFFEE_SHOP_OUTPUT=plain)
+  --output json       One JSON result on stdout: {command, ok, exit_code, data, error};
+                      the usual text goes to stderr (data: a report's numbers, else null)";
+
+/// Days of generated data behind `--demo`
+const DEMO_DAYS: u32 = 14;
+
+/// The repository selected by `--file PATH` / `--demo`, and what "today" means for it
+///
+/// Demo data ends yesterday, so its "today" is yesterday.
+pub fn open_repository(
+    parsed: &ParsedArgs,
+) -> Result<(Box<dyn OrderRepository>, NaiveDate), CliError> {
+    if parsed.flag("--demo") {
+        let mut repository = MemoryOrderRepository::new();
+        SeedGenerator::new(SeedConfig::new(DEMO_DAYS)).populate(&mut repository)?;
+        Ok((Box::new(repository), Utc::now().date_naive() - Duration::days(1)))
+    } else {
+        Ok((open_orders_file(parsed.file())?, Utc::now().date_naive()))
+    }
+}
+
+/// The locale beverages are named in: `--locale`, else $COFFEE_SHOP_LOCALE, else $LANG
+pub fn configured_locale(parsed: &ParsedArgs) -> Locale {
+    match parsed.option("locale") {
+        Some(tag) => Locale::parse(tag),
+        None => std::env::var("COFFEE_SHOP_LOCALE")
+            .or_else(|_| std::env::var("LANG"))
+            .map(|tag| Locale::parse(&tag))
+            .unwrap_or_default(),
+    }
+}
+
+/// Menu translations from `--translations PATH` (default: translations.json)
+///
+/// Without the option, a missing file means nothing is translated.
+pub fn load_translations(parsed: &ParsedArgs) -> Result<MenuTranslations, CliError> {
+    let path = parsed.option("translations").unwrap_or("translations.json");
+    if parsed.option("translations").is_none() && !Path::new(path).exists() {
+        return Ok(MenuTranslations::new());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| CliError::InvalidConfig(format!("Can't read {}: {}", path, e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| CliError::InvalidConfig(format!("Invalid translations in {}: {}", path, e)))
+}
+
+/// The kiosk menu, named in the configured locale with the configured translations
+pub fn kiosk_menu(parsed: &ParsedArgs) -> Result<Vec<MenuItem>, CliError> {
+    // Only the menu is read: nothing is stored, charged or announced
+    let kiosk = KioskService::new(OrderService::new(
+        MemoryOrderRepository::new(),
+        CardPresentPayment::new("MENU"),
+        ConsoleNotifier::with_writer(io::sink()),
+    ))
+    .with_translations(load_translations(parsed)?, configured_locale(parsed));
+    Ok(kiosk.menu().to_vec())
+}
+
+/// A menu item in a size, from "coffee" (Medium) or "green-tea:large"
+pub fn menu_beverage(menu: &[MenuItem], spec: &str) -> Result<Box<dyn Beverage>, CliError> {
+    let (id, size) = spec.split_once(':').unwrap_or((spec, "medium"));
+    let size = match size.to_lowercase().as_str() {
+        "small" | "s" => Size::Small,
+        "medium" | "m" => Size::Medium,
+        "large" | "l" => Size::Large,
+        other => return Err(CliError::Usage(format!("Unknown size '{}'", other))),
+    };
+    let item = menu
+        .iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| CliError::Usage(format!("'{}' is not on the menu (see `menu`)", id)))?;
+    Ok(item.beverage(size))
+}
+
+/// "45s", "10m", "2h", "1h30m"
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let amount: i64 = digits.parse().ok()?;
+        total += match c {
+            'h' => Duration::hours(amount),
+            'm' => Duration::minutes(amount),
+            's' => Duration::seconds(amount),
+            _ => return None,
+        };
+        digits.clear();
+    }
+    (digits.is_empty() && total > Duration::zero()).then_some(total)
+}
+
+/// "17:30" (on `day`), "2026-10-15 17:30" or RFC 3339; UTC like the job schedules
+pub fn parse_moment(text: &str, day: NaiveDate) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Some(at.with_timezone(&Utc));
+    }
+    if let Some(time) = ["%H:%M:%S", "%H:%M"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(text, format).ok())
+    {
+        return Some(day.and_time(time).and_utc());
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|at| at.and_utc())
+}
+
+/// `.bin` files are BinaryOrderRepository, anything else JsonOrderRepository
+///
+/// A compression suffix is looked through: `orders.bin.zst` is binary too.
+pub fn is_binary_file(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let name = name.trim_end_matches(".gz").trim_end_matches(".zst");
+    name.ends_with(".bin")
+}
+
+/// `.db` / `.sqlite` files are SqliteOrderRepository (feature `sqlite`)
+pub fn is_sqlite_file(path: &Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()), Some("db" | "sqlite"))
+}
+
+fn open_orders_file(path: PathBuf) -> Result<Box<dyn OrderRepository>, CliError> {
+    if is_sqlite_file(&path) {
+        #[cfg(feature = "sqlite")]
+        return Ok(Box::new(crate::adapters::SqliteOrderRepository::open(path)?));
+        #[cfg(not(feature = "sqlite"))]
+        return Err(CliError::InvalidConfig(format!(
+            "{} is a SQLite database: rebuild with --features sqlite",
+            path.display()
+        )));
+    }
+    if is_binary_file(&path) {
+        Ok(Box::new(BinaryOrderRepository::new(path)?))
+    } else {
+        Ok(Box::new(JsonOrderRepository::new(path)?))
+    }
+}
+
+/// The output style asked for, taking `--plain` out of `args`
+///
+/// `--plain` may go anywhere on the command line; COFFEE_SHOP_OUTPUT=plain
+/// does the same. The caller makes it global (`OutputStyle::set_global`).
+pub fn output_style(args: &mut Vec<String>) -> OutputStyle {
+    let flagged = args.iter().any(|arg| arg == "--plain");
+    args.retain(|arg| arg != "--plain");
+    let from_env = std::env::var("COFFEE_SHOP_OUTPUT").is_ok_and(|style| style == "plain");
+    if flagged || from_env {
+        OutputStyle::Plain
+    } else {
+        OutputStyle::Rich
+    }
+}
+
+/// Run one command, its output drawn in the process-wide OutputStyle
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    run_to(args, out, &mut io::stderr())
+}
+
+/// Like `run`, with `err` getting the human text under `--output json`
+pub fn run_to(args: &[String], out: &mut dyn Write, err: &mut dyn Write) -> Result<(), CliError> {
+    let mut args = args.to_vec();
+    match json_output::take_output_format(&mut args) {
+        json_output::OutputFormat::Text => dispatch(&args, &mut StyledWriter::new(out)),
+        json_output::OutputFormat::Json => json_output::run_json(args, out, err),
+    }
+}
+
+/// Hand `args` to their command
+/// Every command `dispatch` runs, for the shell's completion
+pub const COMMANDS: [&str; 24] = [
+    "report", "dashboard", "digest", "campaign", "chargeback", "feedback", "status-board",
+    "export", "metrics", "import", "menu", "close-day", "migrate", "tutorial", "prices", "rewards",
+    "scenario", "schema", "serve", "shell", "verify", "wallet", "webhooks", "help",
+];
+
+fn dispatch(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    match args.first().map(String::as_str) {
+        Some("report") => report::run(&args[1..], out),
+        Some("dashboard") => dashboard::run(&args[1..], out),
+        Some("digest") => digest::run(&args[1..], out),
+        Some("campaign") => campaign::run(&args[1..], out),
+        Some("chargeback") => chargeback::run(&args[1..], out),
+        Some("feedback") => feedback::run(&args[1..], out),
+        Some("status-board") => status_board::run(&args[1..], out),
+        Some("export") => export::run(&args[1..], out),
+        Some("metrics") => metrics::run(&args[1..], out),
+        Some("import") => import::run(&args[1..], out),
+        Some("menu") => menu::run(&args[1..], out),
+        Some("close-day") => close_day::run(&args[1..], out),
+        Some("migrate") => migrate::run(&args[1..], out),
+        Some("tutorial") => tutorial::run(&args[1..], out),
+        Some("prices") => prices::run(&args[1..], out),
+        Some("rewards") => rewards::run(&args[1..], out),
+        Some("scenario") => scenario::run(&args[1..], out),
+        Some("schema") => schema::run(&args[1..], out),
+        Some("serve") => serve::run(&args[1..], out),
+        Some("shell") => shell::run(&args[1..], out),
+        Some("verify") => verify::run(&args[1..], out),
+        Some("wallet") => wallet::run(&args[1..], out),
+        Some("webhooks") => webhooks::run(&args[1..], out),
+        Some("help" | "--help" | "-h") => {
+            writeln!(out, "{}", USAGE)?;
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown command '{}'", other))),
+        None => Err(CliError::Usage("Missing command".to_string())),
+    }
+}
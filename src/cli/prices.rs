@@ -0,0 +1,210 @@
+// SOLID: `prices` subcommand (effective-dated price books)
+//
+// prices show [--date DATE]
+// prices publish --from DATE FAMILY=PRICE...
+// prices audit
+//
+// The books live in --books (default: price_books.json). `publish` adds the
+// next version, effective from midnight (UTC) on --from; a version can't be
+// slipped in before the latest one. `audit` re-prices every order in --file
+// against the book effective when it was placed and lists the ones that
+// don't match.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::services::{PriceBook, PriceBooks};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Differences below half a cent are rounding
+const TOLERANCE: f64 = 0.005;
+
+/// Entry point for `prices ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let path = parsed.option("books").unwrap_or("price_books.json");
+    let mut books = load_books(path)?;
+
+    match parsed.positional(0) {
+        Some("show") | None => {
+            let date = parsed.date("date")?.unwrap_or_else(|| Utc::now().date_naive());
+            let at = date.and_hms_opt(23, 59, 59).unwrap_or_default().and_utc();
+            for book in books.versions() {
+                let marker = match books.effective_at(at) {
+                    Some(effective) if effective.version == book.version => "▶",
+                    _ => " ",
+                };
+                writeln!(out, "{} {}", marker, describe(book))?;
+            }
+            if books.effective_at(at).is_none() {
+                writeln!(out, "No price book in effect on {}: menu prices apply", date)?;
+            }
+            Ok(())
+        }
+        Some("publish") => {
+            let from = parsed
+                .date("from")?
+                .ok_or_else(|| CliError::Usage("prices publish needs --from DATE".to_string()))?;
+            let prices = parse_prices(&parsed)?;
+            let effective_from = from.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+            let version = books
+                .publish(effective_from, prices)
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+            save_books(path, &books)?;
+            writeln!(out, "✅ Price book v{} takes effect on {}", version, from)?;
+            Ok(())
+        }
+        Some("audit") => {
+            let (repository, _) = open_repository(&parsed)?;
+            let orders = repository.list_all()?;
+            let mut mismatched = 0;
+            for order in &orders {
+                let repricing = books.reprice(order);
+                if repricing.is_consistent(TOLERANCE) {
+                    continue;
+                }
+                mismatched += 1;
+                writeln!(
+                    out,
+                    "⚠️  {} ({}): book {}, recorded {}",
+                    order.id,
+                    order.created_at.format("%Y-%m-%d %H:%M"),
+                    version_label(repricing.book_version),
+                    version_label(repricing.recorded_version)
+                )?;
+                for line in repricing.differences(TOLERANCE) {
+                    writeln!(
+                        out,
+                        "    {}: charged {}, book says {}",
+                        line.beverage_name,
+                        line.charged,
+                        line.expected.unwrap_or_default()
+                    )?;
+                }
+            }
+            writeln!(
+                out,
+                "{} order(s) re-priced, {} not matching their price book",
+                orders.len(),
+                mismatched
+            )?;
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown prices action '{}'", other))),
+    }
+}
+
+fn describe(book: &PriceBook) -> String {
+    let prices: Vec<String> = book
+        .base_prices
+        .iter()
+        .map(|(family, price)| format!("{} ${:.2}", family, price))
+        .collect();
+    format!(
+        "v{} from {}: {}",
+        book.version,
+        book.effective_from.format("%Y-%m-%d %H:%M"),
+        prices.join(", ")
+    )
+}
+
+fn version_label(version: Option<u32>) -> String {
+    version.map_or("none".to_string(), |v| format!("v{}", v))
+}
+
+/// `Coffee=3.80 Tea=2.60 ...` after the action
+fn parse_prices(parsed: &ParsedArgs) -> Result<BTreeMap<String, f64>, CliError> {
+    let mut prices = BTreeMap::new();
+    let mut index = 1;
+    while let Some(entry) = parsed.positional(index) {
+        let (family, price) = entry
+            .split_once('=')
+            .and_then(|(family, price)| Some((family, price.parse::<f64>().ok()?)))
+            .ok_or_else(|| CliError::Usage(format!("Expected FAMILY=PRICE, got '{}'", entry)))?;
+        prices.insert(family.to_string(), price);
+        index += 1;
+    }
+    if prices.is_empty() {
+        return Err(CliError::Usage("prices publish needs FAMILY=PRICE entries".to_string()));
+    }
+    Ok(prices)
+}
+
+/// No file yet means no price book yet
+fn load_books(path: &str) -> Result<PriceBooks, CliError> {
+    if !Path::new(path).exists() {
+        return Ok(PriceBooks::new());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| CliError::InvalidConfig(format!("Can't read {}: {}", path, e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| CliError::InvalidConfig(format!("Invalid price books in {}: {}", path, e)))
+}
+
+fn save_books(path: &str, books: &PriceBooks) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(books)
+        .map_err(|e| CliError::CommandFailed(format!("Failed to serialize: {}", e)))?;
+    fs::write(path, json).map_err(|e| CliError::CommandFailed(format!("Can't write {}: {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, JsonOrderRepository};
+    use crate::domain::{Coffee, Customer, Money, Size};
+    use crate::ports::OrderRepository;
+    use crate::services::OrderService;
+    use chrono::Duration;
+    use std::io;
+    use uuid::Uuid;
+
+    fn run_with(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_publish_show_and_audit() {
+        let dir = std::env::temp_dir();
+        let books = dir.join(format!("price-books-{}.json", Uuid::new_v4()));
+        let orders = dir.join(format!("price-orders-{}.json", Uuid::new_v4()));
+        let books_path = books.display().to_string();
+        let orders_path = orders.display().to_string();
+
+        let yesterday = (Utc::now().date_naive() - Duration::days(1)).to_string();
+        let publish = ["publish", "--from", &yesterday, "Coffee=4.00", "--books", &books_path];
+        assert!(run_with(&publish).unwrap().contains("Price book v1 takes effect"));
+        assert!(matches!(run_with(&publish), Err(CliError::CommandFailed(_))));
+        let shown = run_with(&["show", "--books", &books_path]).unwrap();
+        assert!(shown.contains("▶ v1 from"), "{}", shown);
+        assert!(shown.contains("Coffee $4.00"), "{}", shown);
+
+        let mut service = OrderService::new(
+            JsonOrderRepository::new(orders.clone()).unwrap(),
+            CashPayment,
+            ConsoleNotifier::with_writer(io::sink()),
+        )
+        .with_price_books(load_books(&books_path).unwrap());
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let coffee = Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        };
+        let mut order = service.place_order(customer, vec![Box::new(coffee)]).unwrap();
+        let audit = ["audit", "--file", &orders_path, "--books", &books_path];
+        assert!(run_with(&audit).unwrap().contains("1 order(s) re-priced, 0 not matching"));
+
+        // Hand-edited price
+        order.items[0].price = Money::usd(3.50);
+        JsonOrderRepository::new(orders.clone()).unwrap().update(&order).unwrap();
+        let report = run_with(&audit).unwrap();
+        assert!(report.contains("Coffee: charged 3.50 USD, book says 4.00 USD"), "{}", report);
+        assert!(report.contains("1 not matching"), "{}", report);
+        fs::remove_file(books).unwrap();
+        fs::remove_file(orders).unwrap();
+    }
+}
@@ -0,0 +1,533 @@
+// SOLID: `report` subcommands
+//
+// report daily | weekly | product-mix | payment-mix | latency | profitability | as-of
+//
+// The command picks a data source, asks ReportingService for the numbers
+// and renders them as terminal charts, JSON or CSV.
+// ReportingService never knows which format was requested (SRP).
+// `as-of` asks OrderQueryService instead: the orders as they stood at a past
+// moment (`--at "2026-10-15 09:05"`), the open ones or those in `--status`.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::domain::{Order, OrderStatus};
+use crate::ports::OrderRepository;
+use crate::presentation::{bar_chart, sparkline};
+use crate::services::{
+    CostModel, DailyReport, DateRange, LatencyReport, LatencySummary, MixShare, OrderBook,
+    OrderQueryService, ProfitabilityReport, ReportingService, WeeklyReport,
+};
+use chrono::{Duration, NaiveDate};
+use serde::Serialize;
+use std::io::Write;
+
+const CHART_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Daily,
+    Weekly,
+    ProductMix,
+    PaymentMix,
+    Latency,
+    Profitability,
+}
+
+/// Entry point for `report ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+
+    let kind = match parsed.positional(0) {
+        Some("daily") => Kind::Daily,
+        Some("weekly") => Kind::Weekly,
+        Some("product-mix") => Kind::ProductMix,
+        Some("payment-mix") => Kind::PaymentMix,
+        Some("latency") => Kind::Latency,
+        Some("profitability") => Kind::Profitability,
+        Some("as-of") => return as_of(&parsed, out),
+        Some(other) => return Err(CliError::Usage(format!("Unknown report '{}'", other))),
+        None => return Err(CliError::Usage("Missing report name".to_string())),
+    };
+
+    let format = format_of(&parsed)?;
+    let (repository, today) = open_repository(&parsed)?;
+    render(&ReportingService::new(repository), kind, format, &parsed, today, out)
+}
+
+fn format_of(parsed: &ParsedArgs) -> Result<Format, CliError> {
+    match parsed.option("format").unwrap_or("text") {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        other => Err(CliError::Usage(format!("Unknown format '{}'", other))),
+    }
+}
+
+/// `as-of --at TIME [--status STATUS]`: without a status, the orders still open then
+fn as_of(parsed: &ParsedArgs, out: &mut dyn Write) -> Result<(), CliError> {
+    let format = format_of(parsed)?;
+    let at = parsed
+        .timestamp("at")?
+        .ok_or_else(|| CliError::Usage("report as-of needs --at \"YYYY-MM-DD HH:MM\"".into()))?;
+    let status = match parsed.option("status") {
+        Some(status) => Some(status.parse::<OrderStatus>().map_err(CliError::Usage)?),
+        None => None,
+    };
+
+    let (repository, _) = open_repository(parsed)?;
+    let book = OrderQueryService::new(repository).as_of(at)?;
+    let orders = match &status {
+        Some(status) => book.with_status(status),
+        None => book.open(),
+    };
+    match format {
+        Format::Text => write_as_of(&book, &orders, out),
+        Format::Json => write_json(&orders, out),
+        Format::Csv => {
+            let rows = orders.iter().map(|order| {
+                format!(
+                    "{},{},{},{},{:.2}",
+                    order.id,
+                    csv_field(&order.customer.name),
+                    order.status,
+                    order.status_since().to_rfc3339(),
+                    order.total_price.to_major()
+                )
+            });
+            write_csv("id,customer,status,since,total", rows, out)
+        }
+    }
+}
+
+fn render<R: OrderRepository>(
+    service: &ReportingService<R>,
+    kind: Kind,
+    format: Format,
+    parsed: &ParsedArgs,
+    today: NaiveDate,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let date = parsed.date("date")?.unwrap_or(today);
+    // Mix, latency and profitability reports default to the last 7 days
+    let to = parsed.date("to")?.unwrap_or(today);
+    let from = parsed.date("from")?.unwrap_or(to - Duration::days(6));
+    let range = DateRange::new(from, to);
+
+    match kind {
+        Kind::Daily => {
+            let report = service.daily(date)?;
+            match format {
+                Format::Text => write_daily(&report, out),
+                Format::Json => write_json(&report, out),
+                Format::Csv => {
+                    let rows = report
+                        .hourly
+                        .iter()
+                        .map(|(hour, orders)| format!("{},{},{}", report.date, hour, orders));
+                    write_csv("date,hour,orders", rows, out)
+                }
+            }
+        }
+        Kind::Weekly => {
+            let report = service.weekly(date)?;
+            match format {
+                Format::Text => write_weekly(&report, out),
+                Format::Json => write_json(&report, out),
+                Format::Csv => {
+                    let rows = report
+                        .days
+                        .iter()
+                        .map(|day| format!("{},{},{:.2}", day.date, day.orders, day.revenue));
+                    write_csv("date,orders,revenue", rows, out)
+                }
+            }
+        }
+        Kind::Latency => {
+            let report = service.latency(range)?;
+            match format {
+                Format::Text => write_latency(&report, out),
+                Format::Json => write_json(&report, out),
+                Format::Csv => {
+                    let rows = [("placement", &report.placement), ("preparation", &report.preparation)]
+                        .into_iter()
+                        .map(|(stage, s)| {
+                            format!("{},{},{},{},{},{}", stage, s.samples, s.p50, s.p95, s.p99, s.max)
+                        });
+                    write_csv("stage,samples,p50,p95,p99,max", rows, out)
+                }
+            }
+        }
+        Kind::Profitability => {
+            let costs = match parsed.option("costs") {
+                Some(path) => load_costs(path)?,
+                None => CostModel::default(),
+            };
+            let report = service.profitability(range, &costs)?;
+            match format {
+                Format::Text => write_profitability(&report, out),
+                Format::Json => write_json(&report, out),
+                Format::Csv => {
+                    let rows = report.beverages.iter().map(|line| {
+                        format!(
+                            "{},{},{:.2},{:.2},{:.2},{:.4}",
+                            csv_field(&line.name),
+                            line.items,
+                            line.revenue,
+                            line.cost,
+                            line.margin,
+                            line.margin_rate
+                        )
+                    });
+                    write_csv("beverage,items,revenue,cost,margin,margin_rate", rows, out)
+                }
+            }
+        }
+        Kind::ProductMix | Kind::PaymentMix => {
+            let (title, shares) = if kind == Kind::ProductMix {
+                ("Product mix", service.product_mix(range)?)
+            } else {
+                ("Payment mix", service.payment_mix(range)?)
+            };
+            match format {
+                Format::Text => write_mix(title, range, &shares, out),
+                Format::Json => write_json(&shares, out),
+                Format::Csv => {
+                    let rows = shares.iter().map(|share| {
+                        format!(
+                            "{},{},{:.2},{:.4}",
+                            csv_field(&share.name),
+                            share.count,
+                            share.revenue,
+                            share.share
+                        )
+                    });
+                    write_csv("name,count,revenue,share", rows, out)
+                }
+            }
+        }
+    }
+}
+
+fn write_daily(report: &DailyReport, out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "📊 Daily report - {}", report.date)?;
+    writeln!(
+        out,
+        "Orders: {}   Cancelled: {}   Past SLA: {}   Revenue: ${:.2}   Average ticket: ${:.2}\n",
+        report.orders, report.cancelled, report.sla_breaches, report.revenue, report.average_ticket
+    )?;
+
+    if report.hourly.is_empty() {
+        writeln!(out, "No sales.")?;
+        return Ok(());
+    }
+
+    let rows: Vec<(String, f64)> = report
+        .hourly
+        .iter()
+        .map(|(hour, orders)| (format!("{:02}:00", hour), *orders as f64))
+        .collect();
+    writeln!(out, "Orders per hour:")?;
+    writeln!(out, "{}", bar_chart(&rows, CHART_WIDTH))?;
+    Ok(())
+}
+
+fn write_weekly(report: &WeeklyReport, out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "📊 Weekly report - {} to {}", report.from, report.to)?;
+    writeln!(
+        out,
+        "Orders: {}   Revenue: ${:.2}\n",
+        report.orders, report.revenue
+    )?;
+
+    let rows: Vec<(String, f64)> = report
+        .days
+        .iter()
+        .map(|day| (day.date.format("%a %m-%d").to_string(), day.revenue))
+        .collect();
+    let trend: Vec<f64> = report.days.iter().map(|day| day.revenue).collect();
+
+    writeln!(out, "Revenue per day:")?;
+    writeln!(out, "{}", bar_chart(&rows, CHART_WIDTH))?;
+    writeln!(out, "\nTrend: {}", sparkline(&trend))?;
+    Ok(())
+}
+
+fn write_mix(
+    title: &str,
+    range: DateRange,
+    shares: &[MixShare],
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    writeln!(out, "📊 {} - {} to {}\n", title, range.from, range.to)?;
+
+    if shares.is_empty() {
+        writeln!(out, "No sales.")?;
+        return Ok(());
+    }
+
+    let rows: Vec<(String, f64)> = shares
+        .iter()
+        .map(|share| {
+            (
+                format!("{} ({}, {:.0}%)", share.name, share.count, share.share * 100.0),
+                share.revenue,
+            )
+        })
+        .collect();
+    writeln!(out, "{}", bar_chart(&rows, CHART_WIDTH))?;
+    Ok(())
+}
+
+fn write_latency(report: &LatencyReport, out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "⏱️  Latency report - {} to {}\n", report.from, report.to)?;
+    writeln!(
+        out,
+        "{:<12} {:>7} {:>8} {:>8} {:>8} {:>8}",
+        "", "orders", "p50", "p95", "p99", "max"
+    )?;
+    for (stage, summary) in [("Placement", &report.placement), ("Preparation", &report.preparation)] {
+        writeln!(out, "{:<12} {}", stage, latency_row(summary))?;
+    }
+    Ok(())
+}
+
+/// "     12    1m05s ..." (columns aligned with the header)
+fn latency_row(summary: &LatencySummary) -> String {
+    let values = [summary.p50, summary.p95, summary.p99, summary.max];
+    let cells: Vec<String> = values
+        .iter()
+        .map(|seconds| format!("{:>8}", minutes_seconds(*seconds)))
+        .collect();
+    format!("{:>7} {}", summary.samples, cells.join(" "))
+}
+
+/// 65.0 -> "1m05s"
+fn minutes_seconds(seconds: f64) -> String {
+    let whole = seconds.round() as i64;
+    format!("{}m{:02}s", whole / 60, whole % 60)
+}
+
+fn write_profitability(report: &ProfitabilityReport, out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "💰 Profitability - {} to {}", report.from, report.to)?;
+    writeln!(
+        out,
+        "Revenue: ${:.2}   Cost of goods: ${:.2}   Margin: ${:.2} ({:.0}%)\n",
+        report.revenue,
+        report.cost,
+        report.margin,
+        report.margin_rate * 100.0
+    )?;
+
+    if report.beverages.is_empty() {
+        writeln!(out, "No sales.")?;
+        return Ok(());
+    }
+
+    writeln!(
+        out,
+        "{:<28} {:>5} {:>9} {:>8} {:>9} {:>5}",
+        "", "sold", "revenue", "cost", "margin", "%"
+    )?;
+    for line in &report.beverages {
+        writeln!(
+            out,
+            "{:<28} {:>5} {:>9.2} {:>8.2} {:>9.2} {:>4.0}%",
+            line.name,
+            line.items,
+            line.revenue,
+            line.cost,
+            line.margin,
+            line.margin_rate * 100.0
+        )?;
+    }
+
+    let rows: Vec<(String, f64)> = report
+        .days
+        .iter()
+        .map(|day| (day.date.format("%a %m-%d").to_string(), day.margin))
+        .collect();
+    writeln!(out, "\nMargin per day:")?;
+    writeln!(out, "{}", bar_chart(&rows, CHART_WIDTH))?;
+    if report.uncosted_items > 0 {
+        writeln!(
+            out,
+            "\n⚠️  {} item(s) sold without a recipe, counted at zero cost",
+            report.uncosted_items
+        )?;
+    }
+    Ok(())
+}
+
+/// Ingredient costs and recipes from JSON; every ingredient used must have a price
+fn load_costs(path: &str) -> Result<CostModel, CliError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| CliError::InvalidConfig(format!("Can't read {}: {}", path, e)))?;
+    let costs: CostModel = serde_json::from_str(&contents)
+        .map_err(|e| CliError::InvalidConfig(format!("Invalid cost model in {}: {}", path, e)))?;
+    let unknown = costs.unknown_ingredients();
+    if !unknown.is_empty() {
+        return Err(CliError::InvalidConfig(format!(
+            "No cost for {} in {}",
+            unknown.join(", "),
+            path
+        )));
+    }
+    Ok(costs)
+}
+
+fn write_as_of(book: &OrderBook, orders: &[&Order], out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "🕘 Orders as of {} UTC", book.at.format("%Y-%m-%d %H:%M:%S"))?;
+    for (status, count) in book.counts() {
+        writeln!(out, "{:<18}{:>4}", status.to_string(), count)?;
+    }
+    writeln!(out)?;
+    for order in orders {
+        writeln!(
+            out,
+            "{}  {:<20} {:<18} since {}  ${:.2}",
+            order.short_id(),
+            order.customer.name,
+            order.status.to_string(),
+            order.status_since().format("%H:%M:%S"),
+            order.total_price.to_major()
+        )?;
+    }
+    if orders.is_empty() {
+        writeln!(out, "(no orders)")?;
+    }
+    Ok(())
+}
+
+fn write_json<T: Serialize>(value: &T, out: &mut dyn Write) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| CliError::OutputFailed(std::io::Error::other(e)))?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+fn write_csv(
+    header: &str,
+    rows: impl Iterator<Item = String>,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    writeln!(out, "{}", header)?;
+    for row in rows {
+        writeln!(out, "{}", row)?;
+    }
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a separator or a quote
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Coffee, Customer};
+
+    fn run_to_string(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_weekly_text_report_on_demo_data() {
+        let output = run_to_string(&["weekly", "--demo"]).unwrap();
+
+        assert!(output.contains("Weekly report"));
+        assert!(output.contains("Trend: "));
+        assert!(output.contains('█'));
+    }
+
+    #[test]
+    fn test_mix_csv_and_json() {
+        let csv = run_to_string(&["product-mix", "--demo", "--format", "csv"]).unwrap();
+        assert!(csv.starts_with("name,count,revenue,share\n"));
+        assert!(csv.contains("\nCoffee,"));
+
+        let json = run_to_string(&["payment-mix", "--demo", "--format", "json"]).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(parsed.is_array());
+    }
+
+    #[test]
+    fn test_latency_report_on_demo_data() {
+        let output = run_to_string(&["latency", "--demo"]).unwrap();
+        assert!(output.contains("Latency report"));
+        assert!(output.contains("Preparation"));
+
+        let csv = run_to_string(&["latency", "--demo", "--format", "csv"]).unwrap();
+        assert!(csv.starts_with("stage,samples,p50,p95,p99,max\nplacement,"));
+    }
+
+    #[test]
+    fn test_profitability_report_on_demo_data() {
+        let output = run_to_string(&["profitability", "--demo"]).unwrap();
+        assert!(output.contains("Cost of goods: $"), "{}", output);
+        assert!(output.contains("Margin per day:"));
+
+        let csv = run_to_string(&["profitability", "--demo", "--format", "csv"]).unwrap();
+        assert!(csv.starts_with("beverage,items,revenue,cost,margin,margin_rate\n"));
+
+        let path = std::env::temp_dir().join(format!("costs-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, r#"{ "recipes": { "Tea": { "tea bag": 1, "honey": 1 } } }"#)
+            .unwrap();
+        let costs = path.display().to_string();
+        assert!(matches!(
+            run_to_string(&["profitability", "--demo", "--costs", &costs]),
+            Err(CliError::InvalidConfig(msg)) if msg.starts_with("No cost for honey")
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_as_of_report_on_a_file() {
+        let path = std::env::temp_dir().join(format!("as-of-{}.json", uuid::Uuid::new_v4()));
+        let file = path.display().to_string();
+        let mut order = Order::single(Customer::named("Ada"), Coffee::medium());
+        order.created_at = "2026-03-02T09:00:00Z".parse().unwrap();
+        order.status_history[0].at = order.created_at;
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        let mut repository = crate::adapters::JsonOrderRepository::new(path.clone()).unwrap();
+        repository.save(&order).unwrap();
+
+        let text = run_to_string(&["as-of", "--file", &file, "--at", "2026-03-02 09:05"]).unwrap();
+        assert!(text.starts_with("🕘 Orders as of 2026-03-02 09:05:00 UTC\nPending"), "{}", text);
+        assert!(text.contains(&format!("{}  Ada", order.short_id())));
+
+        let csv = run_to_string(&[
+            "as-of", "--file", &file, "--at", "2026-03-02 09:05", "--status", "paid", "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert_eq!(csv, "id,customer,status,since,total\n");
+        assert!(matches!(
+            run_to_string(&["as-of", "--file", &file, "--status", "lost", "--at", "2026-03-02"]),
+            Err(CliError::Usage(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert!(matches!(run_to_string(&[]), Err(CliError::Usage(_))));
+        assert!(matches!(
+            run_to_string(&["daily", "--format", "xml"]),
+            Err(CliError::Usage(_))
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+// SOLID: `rewards` subcommand (birthday and anniversary coupons)
+//
+// rewards run [--date DATE]
+// rewards redeem <CODE> <EMAIL>
+//
+// Coupons live in --coupons (default: coupons.json). `run` is what cron
+// calls every morning: it grants the coupons due that day (RewardService,
+// once per occasion per year) and lists them. `redeem` checks a coupon at
+// the counter and uses it up.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::adapters::{ConsoleNotifier, FixedClock, JsonCouponStore, SystemClock};
+use crate::services::{Promotion, RewardService};
+use chrono::Utc;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+type Service = RewardService<JsonCouponStore, ConsoleNotifier<io::Sink>>;
+
+/// Entry point for `rewards ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let mut rewards = open(&parsed)?;
+
+    match parsed.positional(0) {
+        Some("run") | None => {
+            let (repository, today) = open_repository(&parsed)?;
+            let date = parsed.date("date")?.unwrap_or(today);
+            let clock = FixedClock::new(date.and_time(Utc::now().time()).and_utc());
+            let run = rewards
+                .grant_due(&repository.list_all()?, &clock)
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+
+            for coupon in &run.granted {
+                writeln!(
+                    out,
+                    "🎁 {} for {}: {}, {:.0}% off until {}",
+                    coupon.code,
+                    coupon.customer_email,
+                    coupon.occasion.label(),
+                    coupon.percent_off,
+                    coupon.expires_at.format("%Y-%m-%d")
+                )?;
+            }
+            writeln!(
+                out,
+                "{} coupon(s) granted for {}, {} already granted",
+                run.granted.len(),
+                date,
+                run.already_granted
+            )?;
+            Ok(())
+        }
+        Some("redeem") => {
+            let (Some(code), Some(email)) = (parsed.positional(1), parsed.positional(2)) else {
+                return Err(CliError::Usage("rewards redeem needs a CODE and an EMAIL".to_string()));
+            };
+            let offer = rewards
+                .redeem(code, email, &SystemClock)
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+            writeln!(out, "✅ {} applies to this order", offer.label())?;
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown rewards action '{}'", other))),
+    }
+}
+
+/// The service over --coupons; the customer hears nothing from the CLI
+fn open(parsed: &ParsedArgs) -> Result<Service, CliError> {
+    let path = PathBuf::from(parsed.option("coupons").unwrap_or("coupons.json"));
+    let coupons = JsonCouponStore::new(path).map_err(|e| CliError::InvalidConfig(e.to_string()))?;
+    Ok(RewardService::new(coupons, ConsoleNotifier::with_writer(io::sink())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::JsonOrderRepository;
+    use crate::domain::{Customer, Money, Order, OrderItem};
+    use crate::ports::OrderRepository;
+    use chrono::{Datelike, NaiveDate};
+    use uuid::Uuid;
+
+    fn run_with(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_run_grants_once_then_redeem() {
+        let dir = std::env::temp_dir();
+        let orders = dir.join(format!("rewards-orders-{}.json", Uuid::new_v4()));
+        let coupons = dir.join(format!("rewards-coupons-{}.json", Uuid::new_v4()));
+        // Born today (in a leap year, in case today is February 29)
+        let today = Utc::now().date_naive();
+        let birthday = NaiveDate::from_ymd_opt(2000, today.month(), today.day()).unwrap();
+        let ada = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None)
+            .with_birthday(birthday);
+        let item = OrderItem {
+            beverage_name: "Coffee".to_string(),
+            beverage_description: "Large latte".to_string(),
+            price: Money::usd(4.5),
+            quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        };
+        JsonOrderRepository::new(orders.clone())
+            .unwrap()
+            .save(&Order::new(ada, vec![item]))
+            .unwrap();
+
+        let orders_path = orders.display().to_string();
+        let coupons_path = coupons.display().to_string();
+        let args = ["run", "--file", &orders_path, "--coupons", &coupons_path];
+        let granted = run_with(&args).unwrap();
+        assert!(granted.contains("1 coupon(s) granted"), "{}", granted);
+        let again = run_with(&args).unwrap();
+        assert!(again.contains("0 coupon(s) granted"), "{}", again);
+
+        let code = granted.split_whitespace().nth(1).unwrap();
+        let redeem = ["redeem", code, "ada@example.com", "--coupons", &coupons_path];
+        assert!(run_with(&redeem).unwrap().contains("Happy birthday -50% applies"));
+        assert!(matches!(run_with(&redeem), Err(CliError::CommandFailed(_))));
+        std::fs::remove_file(orders).unwrap();
+        std::fs::remove_file(coupons).unwrap();
+    }
+}
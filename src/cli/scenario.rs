@@ -0,0 +1,637 @@
+// SOLID: `scenario run` - a shop day written down, replayed and checked
+//
+// scenario run <PATH>
+//
+// A scenario is a small TOML file: a name, then steps run top to bottom
+// against a fresh in-memory shop whose clock only moves when told to.
+//
+//   name = "Morning rush"
+//
+//   [[step]]
+//   place = "coffee:large, green-tea"   # order #1 (ITEM[:SIZE], as in the shell)
+//   customer = "Ada"
+//
+//   [[step]]
+//   place = "coffee"
+//   times = 2                           # orders #2 and #3
+//
+//   [[step]]
+//   advance = "10m"                     # 45s, 2h, 1h30m
+//
+//   [[step]]
+//   mark = "preparing"                  # preparing | ready | pickup | cancel
+//   order = 2
+//
+//   [[step]]
+//   check = "sla"                       # sla | no-shows, with the default policies
+//
+//   [[step]]
+//   expect = "report.revenue"           # orders | report.FIELD | order.N.FIELD
+//   equals = 13.7
+//
+// `expect` paths walk the JSON of today's DailyReport or of order #N
+// (`order.1.items.count` counts an array). Numbers match to the cent; an
+// amount (`order.1.total_price`) matches a number in its own currency.
+// A failed expectation is reported and the run goes on, so one run lists
+// every mismatch; the command then fails. A step that can't run (unknown
+// item, order #9 of 3) stops the scenario.
+//
+// Only the part of TOML scenarios need is read: `key = value` pairs with
+// strings, integers, floats and booleans, `[[step]]` tables and comments.
+//
+// The steps go through the same services as the other commands (OrderService,
+// ReportingService); the scenario only says what to do and what to expect.
+
+use super::{kiosk_menu, menu_beverage, parse_duration, CliError, ParsedArgs};
+use crate::adapters::{
+    CashPayment, ConsoleNotifier, FixedClock, MemoryOrderRepository, SharedOrderRepository,
+};
+use crate::domain::{Customer, Money};
+use crate::services::{MenuItem, NoShowPolicy, OrderService, ReportingService, SlaPolicy};
+use chrono::{Duration, NaiveDate, Utc};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io::{self, Sink, Write};
+use uuid::Uuid;
+
+/// Step kinds, one of which each `[[step]]` has, with the keys it allows
+const STEP_KEYS: [(&str, &[&str]); 5] = [
+    ("place", &["customer", "times"]),
+    ("advance", &[]),
+    ("mark", &["order"]),
+    ("check", &[]),
+    ("expect", &["equals"]),
+];
+
+/// Entry point for `scenario ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    match parsed.positional(0) {
+        Some("run") => {}
+        Some(other) => return Err(CliError::Usage(format!("Unknown scenario command '{}'", other))),
+        None => return Err(CliError::Usage("Missing scenario command".to_string())),
+    }
+    let path = parsed
+        .positional(1)
+        .ok_or_else(|| CliError::Usage("Usage: scenario run <PATH>".to_string()))?;
+    let text = fs::read_to_string(path)
+        .map_err(|e| CliError::CommandFailed(format!("Can't read scenario {}: {}", path, e)))?;
+
+    let scenario = Scenario::parse(&text)
+        .map_err(|e| CliError::InvalidConfig(format!("{}: {}", path, e)))?;
+    Replay::new(kiosk_menu(&parsed)?).run(&scenario, out)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Transition {
+    Preparing,
+    Ready,
+    Pickup,
+    Cancel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Check {
+    Sla,
+    NoShows,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Place {
+        items: Vec<String>,
+        customer: Option<String>,
+        times: usize,
+    },
+    Advance(Duration),
+    Mark { transition: Transition, order: usize },
+    Check(Check),
+    Expect { path: String, equals: Value },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    /// Line of the `[[step]]` header, for messages
+    line: usize,
+    action: Action,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Scenario {
+    name: Option<String>,
+    description: Option<String>,
+    steps: Vec<Step>,
+}
+
+/// A `key = value` of a step, with its line
+type Entry = (String, usize, Value);
+
+/// What's wrong with a scenario file, and where
+#[derive(Debug, Clone, PartialEq)]
+struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+fn at(line: usize, message: impl Into<String>) -> ParseError {
+    ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+impl Scenario {
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut scenario = Scenario::default();
+        // Header line and entries of each [[step]] so far
+        let mut tables: Vec<(usize, Vec<Entry>)> = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let number = index + 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                let header = line.split('#').next().unwrap_or_default().trim();
+                if header != "[[step]]" {
+                    return Err(at(number, format!("Unknown table {} (only [[step]])", header)));
+                }
+                tables.push((number, Vec::new()));
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| at(number, "Expected `key = value`"))?;
+            let key = key.trim();
+            if key.is_empty()
+                || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(at(number, format!("Invalid key '{}'", key)));
+            }
+            let value = parse_value(value.trim()).map_err(|message| at(number, message))?;
+
+            match tables.last_mut() {
+                Some((_, keys)) => {
+                    if keys.iter().any(|(seen, _, _)| seen == key) {
+                        return Err(at(number, format!("Duplicate key '{}'", key)));
+                    }
+                    keys.push((key.to_string(), number, value));
+                }
+                None => {
+                    let slot = match key {
+                        "name" => &mut scenario.name,
+                        "description" => &mut scenario.description,
+                        other => return Err(at(number, format!("Unknown key '{}'", other))),
+                    };
+                    if slot.is_some() {
+                        return Err(at(number, format!("Duplicate key '{}'", key)));
+                    }
+                    *slot = Some(text_of(&value).map_err(|message| at(number, message))?);
+                }
+            }
+        }
+
+        for (line, keys) in tables {
+            scenario.steps.push(Step {
+                line,
+                action: action(line, keys)?,
+            });
+        }
+        Ok(scenario)
+    }
+}
+
+/// The action of one `[[step]]` table
+fn action(line: usize, keys: Vec<Entry>) -> Result<Action, ParseError> {
+    let kinds: Vec<&(&str, &[&str])> = STEP_KEYS
+        .iter()
+        .filter(|(kind, _)| keys.iter().any(|(key, _, _)| key == kind))
+        .collect();
+    let (kind, allowed) = match kinds.as_slice() {
+        [one] => **one,
+        [] => return Err(at(line, "Step without place, advance, mark, check or expect")),
+        _ => return Err(at(line, "Step with more than one of place, advance, mark, check...")),
+    };
+    if let Some((key, number, _)) =
+        keys.iter().find(|(key, _, _)| key != kind && !allowed.contains(&key.as_str()))
+    {
+        return Err(at(*number, format!("Unknown key '{}' in a `{}` step", key, kind)));
+    }
+
+    let get = |name: &str| keys.iter().find(|(key, _, _)| key == name);
+    let text = |name: &str| -> Result<Option<String>, ParseError> {
+        get(name)
+            .map(|(_, number, value)| text_of(value).map_err(|message| at(*number, message)))
+            .transpose()
+    };
+    let count = |name: &str| -> Result<Option<usize>, ParseError> {
+        get(name)
+            .map(|(_, number, value)| {
+                value
+                    .as_u64()
+                    .filter(|n| *n > 0)
+                    .map(|n| n as usize)
+                    .ok_or_else(|| at(*number, format!("`{}` must be a positive integer", name)))
+            })
+            .transpose()
+    };
+    let (_, number, _) = get(kind).expect("the step's kind key is present");
+    let value = text(kind)?.unwrap_or_default();
+
+    Ok(match kind {
+        "place" => {
+            let items: Vec<String> = value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect();
+            if items.is_empty() {
+                return Err(at(*number, "`place` needs at least one item"));
+            }
+            Action::Place {
+                items,
+                customer: text("customer")?,
+                times: count("times")?.unwrap_or(1),
+            }
+        }
+        "advance" => Action::Advance(
+            parse_duration(&value)
+                .ok_or_else(|| at(*number, format!("Invalid duration '{}' (10m, 1h30m)", value)))?,
+        ),
+        "mark" => Action::Mark {
+            transition: match value.as_str() {
+                "preparing" => Transition::Preparing,
+                "ready" => Transition::Ready,
+                "pickup" => Transition::Pickup,
+                "cancel" => Transition::Cancel,
+                other => {
+                    let message =
+                        format!("Unknown mark '{}' (preparing|ready|pickup|cancel)", other);
+                    return Err(at(*number, message));
+                }
+            },
+            order: count("order")?.ok_or_else(|| at(line, "`mark` needs `order = N`"))?,
+        },
+        "check" => Action::Check(match value.as_str() {
+            "sla" => Check::Sla,
+            "no-shows" => Check::NoShows,
+            other => return Err(at(*number, format!("Unknown check '{}' (sla|no-shows)", other))),
+        }),
+        _ => Action::Expect {
+            path: value,
+            equals: get("equals")
+                .map(|(_, _, value)| value.clone())
+                .ok_or_else(|| at(line, "`expect` needs `equals = VALUE`"))?,
+        },
+    })
+}
+
+fn text_of(value: &Value) -> Result<String, String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("Expected a string, got {}", value))
+}
+
+/// A TOML value: "string", 42, 4.2, true / false, then an optional comment
+fn parse_value(text: &str) -> Result<Value, String> {
+    if let Some(rest) = text.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    let after = rest[index + 1..].trim_start();
+                    if !after.is_empty() && !after.starts_with('#') {
+                        return Err(format!("Unexpected '{}' after the string", after));
+                    }
+                    return Ok(Value::String(value));
+                }
+                '\\' => value.push(match chars.next().map(|(_, c)| c) {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    other => return Err(format!("Unknown escape \\{}", other.unwrap_or(' '))),
+                }),
+                c => value.push(c),
+            }
+        }
+        return Err("Unterminated string".to_string());
+    }
+
+    let text = text.split('#').next().unwrap_or_default().trim();
+    match text {
+        "" => Err("Missing value".to_string()),
+        "true" => Ok(Value::Bool(true)),
+        "false" => Ok(Value::Bool(false)),
+        _ => {
+            if let Ok(integer) = text.parse::<i64>() {
+                Ok(Value::from(integer))
+            } else if let Ok(float) = text.parse::<f64>()
+                && float.is_finite()
+            {
+                Ok(Value::from(float))
+            } else {
+                Err(format!("Can't read value '{}'", text))
+            }
+        }
+    }
+}
+
+type Repository = SharedOrderRepository<MemoryOrderRepository>;
+
+/// A fresh shop a scenario plays out in
+struct Replay {
+    service: OrderService<Repository, CashPayment, ConsoleNotifier<Sink>>,
+    reporting: ReportingService<Repository>,
+    clock: FixedClock,
+    menu: Vec<MenuItem>,
+    /// Order IDs by number: #1 is `orders[0]`
+    orders: Vec<Uuid>,
+    today: NaiveDate,
+}
+
+impl Replay {
+    fn new(menu: Vec<MenuItem>) -> Self {
+        let repository = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let now = Utc::now();
+        Self {
+            service: OrderService::new(
+                repository.clone(),
+                CashPayment,
+                ConsoleNotifier::with_writer(io::sink()),
+            ),
+            reporting: ReportingService::new(repository),
+            clock: FixedClock::new(now),
+            menu,
+            orders: Vec::new(),
+            today: now.date_naive(),
+        }
+    }
+
+    fn run(&mut self, scenario: &Scenario, out: &mut dyn Write) -> Result<(), CliError> {
+        writeln!(out, "Scenario: {}", scenario.name.as_deref().unwrap_or("(unnamed)"))?;
+        if let Some(description) = &scenario.description {
+            writeln!(out, "  {}", description)?;
+        }
+
+        let (mut passed, mut failed) = (0, 0);
+        for (index, step) in scenario.steps.iter().enumerate() {
+            // Buffered, so the payment adapter's own lines don't split the step's line
+            let mut line = Vec::new();
+            let result = self.step(&step.action, &mut line);
+            write!(out, "{:>3}. ", index + 1)?;
+            out.write_all(&line)?;
+            match result {
+                Ok(Some(true)) => passed += 1,
+                Ok(Some(false)) => failed += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    writeln!(out, "❌ Stopped")?;
+                    return Err(CliError::CommandFailed(format!(
+                        "Step {} (line {}): {}",
+                        index + 1,
+                        step.line,
+                        e
+                    )));
+                }
+            }
+        }
+
+        writeln!(out, "{} of {} expectations passed", passed, passed + failed)?;
+        if failed > 0 {
+            return Err(CliError::CommandFailed(format!("{} expectations failed", failed)));
+        }
+        Ok(())
+    }
+
+    /// Run one step: Some(passed) for an expectation, None for the others
+    fn step(&mut self, action: &Action, out: &mut dyn Write) -> Result<Option<bool>, String> {
+        let failed = |e: &dyn fmt::Display| e.to_string();
+        match action {
+            Action::Place {
+                items,
+                customer,
+                times,
+            } => {
+                let customer = customer.as_deref().map_or_else(Customer::guest, Customer::named);
+                let mut lines = Vec::new();
+                for _ in 0..*times {
+                    let beverages = items
+                        .iter()
+                        .map(|item| menu_beverage(&self.menu, item))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| failed(&e))?;
+                    let order = self
+                        .service
+                        .place_order(customer.clone(), beverages)
+                        .map_err(|e| failed(&e))?;
+                    self.orders.push(order.id);
+                    lines.push(format!("#{} {}", self.orders.len(), order.summary()));
+                }
+                writeln!(out, "{}", lines.join("\n     ")).map_err(|e| failed(&e))?;
+            }
+            Action::Advance(by) => {
+                self.clock.advance(*by);
+                writeln!(out, "Clock +{} min", by.num_minutes()).map_err(|e| failed(&e))?;
+            }
+            Action::Mark { transition, order } => {
+                let id = self.order_id(*order)?;
+                match transition {
+                    Transition::Preparing => self.service.mark_order_preparing(id),
+                    Transition::Ready => self.service.mark_order_ready(id),
+                    Transition::Pickup => self.service.complete_order(id),
+                    Transition::Cancel => self.service.cancel_order(id),
+                }
+                .map_err(|e| failed(&e))?;
+                let order_now = self.service.get_order(id).map_err(|e| failed(&e))?;
+                writeln!(out, "#{} {:?}", order, order_now.status).map_err(|e| failed(&e))?;
+            }
+            Action::Check(check) => {
+                let (what, flagged) = match check {
+                    Check::Sla => (
+                        "past the SLA",
+                        self.service.flag_sla_breaches(&SlaPolicy::default(), &self.clock),
+                    ),
+                    Check::NoShows => (
+                        "not picked up",
+                        self.service.expire_no_shows(&NoShowPolicy::default(), &self.clock),
+                    ),
+                };
+                let flagged = flagged.map_err(|e| failed(&e))?;
+                writeln!(out, "{} orders {}", flagged.len(), what).map_err(|e| failed(&e))?;
+            }
+            Action::Expect { path, equals } => {
+                let actual = self.lookup(path)?;
+                let passed = matches(&actual, equals);
+                if passed {
+                    writeln!(out, "✅ {} = {}", path, actual)
+                } else {
+                    writeln!(out, "❌ {}: expected {}, got {}", path, equals, actual)
+                }
+                .map_err(|e| failed(&e))?;
+                return Ok(Some(passed));
+            }
+        }
+        Ok(None)
+    }
+
+    fn order_id(&self, number: usize) -> Result<Uuid, String> {
+        number
+            .checked_sub(1)
+            .and_then(|index| self.orders.get(index))
+            .copied()
+            .ok_or_else(|| format!("No order #{} ({} placed so far)", number, self.orders.len()))
+    }
+
+    /// The value at `orders`, `report.FIELD...` or `order.N.FIELD...`
+    fn lookup(&self, path: &str) -> Result<Value, String> {
+        let mut segments = path.split('.');
+        let (mut value, walked) = match segments.next() {
+            Some("orders") => {
+                let orders = self.service.list_all_orders().map_err(|e| e.to_string())?;
+                (Value::from(orders.len()), "orders".to_string())
+            }
+            Some("report") => {
+                let report = self.reporting.daily(self.today).map_err(|e| e.to_string())?;
+                (serde_json::to_value(report).map_err(|e| e.to_string())?, "report".to_string())
+            }
+            Some("order") => {
+                let number = segments.next().unwrap_or_default();
+                let id = number
+                    .parse()
+                    .map_err(|_| format!("Expected order.N.FIELD, got '{}'", path))
+                    .and_then(|number| self.order_id(number))?;
+                let order = self.service.get_order(id).map_err(|e| e.to_string())?;
+                let value = serde_json::to_value(order).map_err(|e| e.to_string())?;
+                (value, format!("order.{}", number))
+            }
+            _ => return Err(format!("Unknown path '{}' (orders, report..., order.N...)", path)),
+        };
+
+        let mut walked = walked;
+        for segment in segments {
+            value = match (&value, segment) {
+                (Value::Array(items), "count") => Value::from(items.len()),
+                (Value::Object(fields), field) => fields.get(field).cloned().ok_or_else(|| {
+                    let known: Vec<&str> = fields.keys().map(String::as_str).collect();
+                    format!("No '{}' in {} (has {})", field, walked, known.join(", "))
+                })?,
+                _ => return Err(format!("{} has no '{}'", walked, segment)),
+            };
+            walked = format!("{}.{}", walked, segment);
+        }
+        Ok(value)
+    }
+}
+
+/// Equal, with numbers (and amounts, in major units) compared to the cent
+fn matches(actual: &Value, expected: &Value) -> bool {
+    let amount = serde_json::from_value::<Money>(actual.clone()).ok();
+    match (amount.map(Money::to_major).or(actual.as_f64()), expected.as_f64()) {
+        (Some(actual), Some(expected)) => (actual - expected).abs() < 0.005,
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu() -> Vec<MenuItem> {
+        kiosk_menu(&ParsedArgs::parse(&[]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_scenario_places_moves_and_checks_orders() {
+        let scenario = Scenario::parse(
+            r#"
+name = "Morning rush"  # a comment
+
+[[step]]
+place = "coffee:large, green-tea"
+customer = "Ada"
+
+[[step]]
+place = "coffee"
+times = 2
+
+[[step]]
+advance = "1h30m"
+
+[[step]]
+mark = "preparing"
+order = 2
+
+[[step]]
+mark = "ready"
+order = 2
+
+[[step]]
+check = "sla"
+
+[[step]]
+expect = "orders"
+equals = 3
+
+[[step]]
+expect = "order.2.status"
+equals = "Ready"
+
+[[step]]
+expect = "report.sla_breaches"
+equals = 2
+
+[[step]]
+expect = "report.revenue"
+equals = 13.7
+
+[[step]]
+expect = "order.1.items.count"
+equals = 2
+
+[[step]]
+expect = "order.3.total_price"
+equals = 3.5
+"#,
+        )
+        .unwrap();
+        assert_eq!(scenario.name.as_deref(), Some("Morning rush"));
+        assert_eq!(scenario.steps[2].action, Action::Advance(Duration::minutes(90)));
+
+        let mut out = Vec::new();
+        Replay::new(menu()).run(&scenario, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("#1 "), "{}", text);
+        assert!(text.contains("2 orders past the SLA"), "{}", text);
+        assert!(text.contains("6 of 6 expectations passed"), "{}", text);
+    }
+
+    #[test]
+    fn test_failed_expectations_and_bad_files_are_reported() {
+        let scenario = Scenario::parse(
+            "[[step]]\nplace = \"coffee\"\n\n[[step]]\nexpect = \"report.orders\"\nequals = 5\n",
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        let result = Replay::new(menu()).run(&scenario, &mut out);
+
+        assert!(matches!(result, Err(CliError::CommandFailed(_))));
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("❌ report.orders: expected 5, got 1"), "{}", text);
+
+        let error = Scenario::parse("[[step]]\nplace = \"coffee\"\nwhen = 3\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: Unknown key 'when' in a `place` step");
+        assert_eq!(Scenario::parse("name = \"x\nnope").unwrap_err().line, 1);
+    }
+}
@@ -0,0 +1,113 @@
+// SOLID: `schema` subcommand (JSON Schema of the wire types)
+//
+// schema list
+// schema dump [TYPE] [--output DIR]
+//
+// `dump` prints every schema as one JSON object keyed by type name, or only
+// TYPE's; with --output, writes one `<Type>.schema.json` per type into DIR
+// instead, which is what client generators usually take.
+//
+// The schemas come from crate::schema, only built with `--features schema`;
+// without it the command says so instead of disappearing from the usage.
+
+use super::{CliError, ParsedArgs};
+use std::io::Write;
+
+/// Entry point for `schema ...`
+#[cfg(feature = "schema")]
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    use crate::schema::schemas;
+    use std::fs;
+    use std::path::Path;
+
+    let parsed = ParsedArgs::parse(args)?;
+    let pretty = |value: &serde_json::Value| {
+        serde_json::to_string_pretty(value)
+            .map_err(|e| CliError::CommandFailed(format!("Failed to serialize: {}", e)))
+    };
+
+    match parsed.positional(0) {
+        Some("list") | None => {
+            for (name, _) in schemas() {
+                writeln!(out, "{}", name)?;
+            }
+            Ok(())
+        }
+        Some("dump") => {
+            let selected = match parsed.positional(1) {
+                Some(name) => {
+                    let found = schemas()
+                        .into_iter()
+                        .find(|(type_name, _)| type_name.eq_ignore_ascii_case(name))
+                        .ok_or_else(|| {
+                            CliError::Usage(format!("No schema for '{}' (see `schema list`)", name))
+                        })?;
+                    vec![found]
+                }
+                None => schemas(),
+            };
+
+            match parsed.option("output") {
+                Some(dir) => {
+                    let failed =
+                        |e: std::io::Error| CliError::CommandFailed(format!("{}: {}", dir, e));
+                    fs::create_dir_all(dir).map_err(failed)?;
+                    for (name, schema) in &selected {
+                        let path = Path::new(dir).join(format!("{}.schema.json", name));
+                        fs::write(&path, pretty(schema)?).map_err(failed)?;
+                    }
+                    writeln!(out, "✅ Wrote {} schema(s) to {}", selected.len(), dir)?;
+                }
+                None if selected.len() == 1 => writeln!(out, "{}", pretty(&selected[0].1)?)?,
+                None => {
+                    let all: serde_json::Map<String, serde_json::Value> = selected
+                        .into_iter()
+                        .map(|(name, schema)| (name.to_string(), schema))
+                        .collect();
+                    writeln!(out, "{}", pretty(&serde_json::Value::Object(all))?)?;
+                }
+            }
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown schema action '{}'", other))),
+    }
+}
+
+/// Entry point for `schema ...` in builds without the schemas
+#[cfg(not(feature = "schema"))]
+pub fn run(args: &[String], _out: &mut dyn Write) -> Result<(), CliError> {
+    ParsedArgs::parse(args)?;
+    Err(CliError::CommandFailed(
+        "This build has no schemas: rebuild with `--features schema`".to_string(),
+    ))
+}
+
+#[cfg(all(test, feature = "schema"))]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn run_with(args: &[&str]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_list_and_dump() {
+        assert!(run_with(&["list"]).unwrap().contains("CloudEvent\n"));
+
+        let all: serde_json::Value = serde_json::from_str(&run_with(&["dump"]).unwrap()).unwrap();
+        assert!(all["Order"]["properties"]["items"].is_object());
+        let customer = run_with(&["dump", "customer"]).unwrap();
+        assert!(customer.contains("\"email\""));
+        assert!(matches!(run_with(&["dump", "Beverage"]), Err(CliError::Usage(_))));
+
+        let dir = std::env::temp_dir().join(format!("schemas-{}", Uuid::new_v4()));
+        let dir_path = dir.display().to_string();
+        run_with(&["dump", "order", "--output", &dir_path]).unwrap();
+        assert!(dir.join("Order.schema.json").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+// SOLID: `serve` subcommand (the REST API, feature `http`)
+//
+// serve [--addr 127.0.0.1:8080]
+//
+// Runs the shop as a service: the routes of crate::api over an
+// OrderService backed by the orders in --file (or --demo data), paid in
+// cash and announced on the console. Stop it with Ctrl+C.
+//
+// Without the feature the command says how to get it, like `schema`.
+
+use super::{CliError, ParsedArgs};
+use std::io::Write;
+
+/// Entry point for `serve ...`
+#[cfg(feature = "http")]
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    use super::open_repository;
+    use crate::adapters::{CashPayment, ConsoleNotifier, SharedOrderRepository};
+    use crate::api::{serve, OrderApi};
+    use crate::services::OrderService;
+    use std::net::TcpListener;
+
+    let parsed = ParsedArgs::parse(args)?;
+    let addr = parsed.option("addr").unwrap_or("127.0.0.1:8080");
+    let (repository, _) = open_repository(&parsed)?;
+    let repository = SharedOrderRepository::new(repository);
+    let listener = TcpListener::bind(addr)
+        .map_err(|e| CliError::CommandFailed(format!("Can't listen on {}: {}", addr, e)))?;
+
+    let service = OrderService::new(repository.clone(), CashPayment, ConsoleNotifier::new());
+    writeln!(out, "✅ Listening on http://{}", addr)?;
+    out.flush()?;
+    serve(listener, &OrderApi::new(service).with_reads(repository))
+        .map_err(|e| CliError::CommandFailed(format!("Server stopped: {}", e)))
+}
+
+/// Entry point for `serve ...` in builds without the API
+#[cfg(not(feature = "http"))]
+pub fn run(args: &[String], _out: &mut dyn Write) -> Result<(), CliError> {
+    ParsedArgs::parse(args)?;
+    Err(CliError::CommandFailed(
+        "This build has no API server: rebuild with `--features http`".to_string(),
+    ))
+}
@@ -0,0 +1,645 @@
+// SOLID: `shell` subcommand - a prompt for the whole shop
+//
+// shell [--file orders.json | --demo] [--clock TIME] [--history PATH] [--script PATH]
+//
+// The friendlier front end to the numbered menu of the interactive demo:
+// order from the menu by name, move orders along by (short) ID, and run any
+// other command as if it were typed after `coffee-shop-solid`:
+//
+//   coffee> order coffee:large green-tea --name Ada
+//   coffee> orders ada@example.com ; ready 1a2b
+//   coffee> report daily
+//   coffee> complete order gr          (lists green-tea)
+//   coffee> !2                         (runs history entry 2 again)
+//
+// `;` separates commands on a line and `#` starts a comment. History is kept
+// across sessions, one line per command, in --history (default:
+// $COFFEE_SHOP_HISTORY, else ~/.coffee_shop_history). A script, from
+// --script or piped in, runs line by line and stops at the first failure.
+//
+// Completion: `complete TEXT` lists what could follow TEXT (commands, menu
+// items and sizes after `order`, order IDs after show/prepare/ready/pickup/
+// cancel). Tab completion needs a line editor owning the terminal, which
+// std doesn't offer; `completions` is what one would call.
+//
+// Time travel: with --demo or --clock TIME ("now", "17:30", "2026-10-15
+// 17:30", UTC) the shell keeps a FixedClock that only `clock advance 20m` and
+// `clock set 23:00` move. The jobs the interactive demo runs (no-show expiry
+// and the SLA watchdog every minute, the no-show report at 23:00) run on it
+// each time it moves, so a no-show or an SLA breach shows up without waiting.
+// Orders are placed and moved along on it too, and priced with the price book
+// in effect then (happy hour). `clock set` refuses to go back before the
+// latest order change.
+//
+// The shell only parses lines: orders go through OrderService (cash, the
+// notices kept quiet), other commands through the CLI's own dispatch.
+
+use super::{
+    dispatch, kiosk_menu, menu_beverage, open_repository, parse_duration, parse_moment, CliError,
+    ParsedArgs, COMMANDS, USAGE,
+};
+use crate::adapters::{CashPayment, ConsoleNotifier, FixedClock};
+use crate::domain::{Beverage, Customer, Order};
+use crate::ports::{Clock, OrderRepository};
+use crate::services::{
+    JobScheduler, MenuItem, NoShowExpiryJob, NoShowPolicy, NoShowReportJob, OrderService,
+    Schedule, SlaPolicy, SlaWatchdogJob,
+};
+use chrono::{Duration, NaiveTime, Utc};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, IsTerminal, Sink, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const HELP: &str = "\
+Shell commands:
+  order ITEM[:SIZE]... [--name NAME]   Place and pay (cash); SIZE small|medium|large
+  orders [EMAIL]                       All orders, or one customer's
+  show ID                              One order (ID: its first digits are enough)
+  prepare|ready|pickup|cancel ID       Move an order along
+  clock [advance 20m | set 17:30]      The demo clock (--demo, --clock); moving it runs the jobs
+  history, !N                          Past commands, run entry N again
+  complete TEXT                        What could follow TEXT
+  help cli                             The other commands (report, export...)
+  exit
+Separate commands with `;`, comment with `#`.";
+
+/// Words after which an order ID comes
+const ID_COMMANDS: [&str; 5] = ["show", "prepare", "ready", "pickup", "cancel"];
+
+const SHELL_COMMANDS: [&str; 12] = [
+    "order", "orders", "show", "prepare", "ready", "pickup", "cancel", "clock", "history",
+    "complete", "help", "exit",
+];
+
+/// History lines kept in memory and offered to `!N`
+const HISTORY_LIMIT: usize = 1000;
+
+/// Entry point for `shell ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let mut shell = Shell::open(&parsed, args)?;
+    shell.run_jobs(out)?;
+
+    let script = match parsed.option("script") {
+        Some(path) => Some(fs::read_to_string(path).map_err(|e| {
+            CliError::CommandFailed(format!("Can't read script {}: {}", path, e))
+        })?),
+        None if !io::stdin().is_terminal() => Some(io::read_to_string(io::stdin())?),
+        None => None,
+    };
+    match script {
+        Some(script) => shell.run_script(&script, out),
+        None => {
+            shell.keep_history(history_path(&parsed))?;
+            shell.interact(&mut io::stdin().lock(), out)
+        }
+    }
+}
+
+/// `--history`, else $COFFEE_SHOP_HISTORY, else ~/.coffee_shop_history
+fn history_path(parsed: &ParsedArgs) -> Option<PathBuf> {
+    parsed
+        .option("history")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("COFFEE_SHOP_HISTORY").map(PathBuf::from))
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".coffee_shop_history"))
+        })
+}
+
+#[derive(Debug, PartialEq)]
+enum Flow {
+    Continue,
+    Exit,
+}
+
+type ShellService = OrderService<Box<dyn OrderRepository>, CashPayment, ConsoleNotifier<Sink>>;
+
+struct Shell {
+    service: ShellService,
+    menu: Vec<MenuItem>,
+    /// Time travel (--demo, --clock): the clock `clock` moves and the jobs run on
+    clock: Option<Arc<FixedClock>>,
+    jobs: JobScheduler<ShellService>,
+    /// `--file PATH` / `--demo`, handed on to CLI commands that don't name a source
+    source: Vec<String>,
+    history: Vec<String>,
+    history_file: Option<PathBuf>,
+}
+
+impl Shell {
+    fn open(parsed: &ParsedArgs, args: &[String]) -> Result<Self, CliError> {
+        let (repository, _) = open_repository(parsed)?;
+        let service =
+            OrderService::new(repository, CashPayment, ConsoleNotifier::with_writer(io::sink()));
+
+        let source = match args.iter().position(|arg| arg == "--file") {
+            Some(at) => args[at..].iter().take(2).cloned().collect(),
+            None if parsed.flag("--demo") => vec!["--demo".to_string()],
+            None => Vec::new(),
+        };
+        let clock = match parsed.option("clock") {
+            Some("now") => Some(Utc::now()),
+            Some(time) => Some(parse_moment(time, Utc::now().date_naive()).ok_or_else(|| {
+                CliError::Usage(format!("--clock expects now, HH:MM or a date and time: {}", time))
+            })?),
+            None if parsed.flag("--demo") => Some(Utc::now()),
+            None => None,
+        };
+        let clock = clock.map(|now| Arc::new(FixedClock::new(now)));
+        let service = match &clock {
+            Some(clock) => service.with_clock(Arc::clone(clock)),
+            None => service,
+        };
+
+        let mut jobs = JobScheduler::new();
+        jobs.register(
+            "no-show expiry",
+            Schedule::Every(Duration::minutes(1)),
+            NoShowExpiryJob::new(NoShowPolicy::default()),
+        )
+        .register(
+            "SLA watchdog",
+            Schedule::Every(Duration::minutes(1)),
+            // The demo's "manager channel" is the console, as in the interactive demo
+            SlaWatchdogJob::new(SlaPolicy::default(), ConsoleNotifier::new()),
+        )
+        .register(
+            "daily no-show report",
+            Schedule::DailyAt(NaiveTime::from_hms_opt(23, 0, 0).unwrap_or_default()),
+            NoShowReportJob,
+        );
+        Ok(Self {
+            service,
+            menu: kiosk_menu(parsed)?,
+            clock,
+            jobs,
+            source,
+            history: Vec::new(),
+            history_file: None,
+        })
+    }
+
+    /// Load the history in `path` and append each new line to it
+    fn keep_history(&mut self, path: Option<PathBuf>) -> Result<(), CliError> {
+        if let Some(path) = &path
+            && let Ok(text) = fs::read_to_string(path)
+        {
+            let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+            let skip = lines.len().saturating_sub(HISTORY_LIMIT);
+            self.history = lines[skip..].iter().map(|line| line.to_string()).collect();
+        }
+        self.history_file = path;
+        Ok(())
+    }
+
+    fn interact(&mut self, input: &mut dyn BufRead, out: &mut dyn Write) -> Result<(), CliError> {
+        writeln!(out, "☕ Coffee shop shell: `help` for commands, `exit` to leave")?;
+        loop {
+            write!(out, "coffee> ")?;
+            out.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                return Ok(());
+            }
+            match self.run_line(line.trim_end_matches(['\n', '\r']), out, true) {
+                Ok(Flow::Exit) => return Ok(()),
+                Ok(Flow::Continue) => {}
+                Err(e) => writeln!(out, "❌ {}", message(&e))?,
+            }
+        }
+    }
+
+    fn run_script(&mut self, script: &str, out: &mut dyn Write) -> Result<(), CliError> {
+        for (number, line) in script.lines().enumerate() {
+            match self.run_line(line, out, false) {
+                Ok(Flow::Exit) => break,
+                Ok(Flow::Continue) => {}
+                Err(e) => {
+                    writeln!(out, "❌ Script stopped at line {}: {}", number + 1, message(&e))?;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_line(
+        &mut self,
+        line: &str,
+        out: &mut dyn Write,
+        record: bool,
+    ) -> Result<Flow, CliError> {
+        let mut line = line.trim_start().to_string();
+        if let Some(entry) = line.strip_prefix('!') {
+            line = entry
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| self.history.get(n.checked_sub(1)?))
+                .cloned()
+                .ok_or_else(|| CliError::Usage(format!("No history entry !{}", entry.trim())))?;
+            writeln!(out, "{}", line)?;
+        }
+        if record && !line.trim().is_empty() {
+            self.remember(&line)?;
+        }
+
+        for command in commands(&line) {
+            if self.run_command(command, out)? == Flow::Exit {
+                return Ok(Flow::Exit);
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn remember(&mut self, line: &str) -> Result<(), CliError> {
+        if self.history.len() == HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.history.push(line.to_string());
+        if let Some(path) = &self.history_file {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    fn run_command(&mut self, command: &str, out: &mut dyn Write) -> Result<Flow, CliError> {
+        let words = words(command);
+        let Some(first) = words.first() else {
+            return Ok(Flow::Continue);
+        };
+        let id = || {
+            words
+                .get(1)
+                .map(String::as_str)
+                .ok_or_else(|| CliError::Usage(format!("Usage: {} ID", first)))
+        };
+
+        match first.as_str() {
+            "exit" | "quit" => return Ok(Flow::Exit),
+            "help" if words.get(1).is_some_and(|topic| topic == "cli") => {
+                writeln!(out, "{}", USAGE)?
+            }
+            "help" => writeln!(out, "{}", HELP)?,
+            "history" => {
+                for (number, line) in self.history.iter().enumerate() {
+                    writeln!(out, "{:>4}  {}", number + 1, line)?;
+                }
+            }
+            "complete" => {
+                let text = command.trim_start()["complete".len()..].trim_start_matches(' ');
+                for candidate in self.completions(text)? {
+                    writeln!(out, "{}", candidate)?;
+                }
+            }
+            "clock" => self.clock(&words[1..], out)?,
+            "order" => self.order(&words[1..], out)?,
+            "orders" => {
+                let orders = match words.get(1) {
+                    Some(email) => self.service.list_customer_orders(email),
+                    None => self.service.list_all_orders(),
+                }
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+                if orders.is_empty() {
+                    writeln!(out, "No orders")?;
+                }
+                for order in orders {
+                    writeln!(out, "  {}", order.summary())?;
+                }
+            }
+            "show" => {
+                let order = self.find_order(id()?)?;
+                show(&order, out)?;
+            }
+            "prepare" | "ready" | "pickup" | "cancel" => {
+                let order = self.find_order(id()?)?;
+                let moved = match first.as_str() {
+                    "prepare" => self.service.mark_order_preparing(order.id),
+                    "ready" => self.service.mark_order_ready(order.id),
+                    "pickup" => self.service.complete_order(order.id),
+                    _ => self.service.cancel_order(order.id),
+                };
+                moved.map_err(|e| CliError::CommandFailed(e.to_string()))?;
+                let order = self.find_order(&order.id.to_string())?;
+                writeln!(out, "✅ {}", order.summary())?;
+            }
+            "shell" => return Err(CliError::Usage("Already in the shell".to_string())),
+            _ => {
+                let mut args = words.clone();
+                if !args.iter().any(|arg| arg == "--file" || arg == "--demo") {
+                    args.extend(self.source.iter().cloned());
+                }
+                dispatch(&args, out)?;
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// `clock`, `clock advance DURATION`, `clock set TIME`
+    fn clock(&mut self, args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+        let Some(clock) = &self.clock else {
+            if !args.is_empty() {
+                return Err(CliError::Usage(
+                    "The clock is the system's: start the shell with --demo or --clock TIME"
+                        .to_string(),
+                ));
+            }
+            let now = Utc::now().format("%Y-%m-%d %H:%M:%S");
+            writeln!(out, "🕒 {} UTC (system clock)", now)?;
+            return Ok(());
+        };
+
+        let value = args.get(1).map(String::as_str).unwrap_or_default();
+        match args.first().map(String::as_str) {
+            None => {}
+            Some("advance") => clock.advance(parse_duration(value).ok_or_else(|| {
+                CliError::Usage(format!("Usage: clock advance 10m|1h30m, not '{}'", value))
+            })?),
+            Some("set") => {
+                let at = parse_moment(value, clock.now().date_naive()).ok_or_else(|| {
+                    CliError::Usage(format!("Usage: clock set HH:MM or DATE HH:MM, not {}", value))
+                })?;
+                // Going back would date new orders before ones already placed
+                let orders = self
+                    .service
+                    .list_all_orders()
+                    .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+                if let Some(latest) = orders.iter().map(Order::status_since).max()
+                    && at < latest
+                {
+                    return Err(CliError::CommandFailed(format!(
+                        "The clock can't go back before the latest order change ({} UTC)",
+                        latest.format("%Y-%m-%d %H:%M:%S")
+                    )));
+                }
+                clock.set(at);
+            }
+            Some(other) => {
+                return Err(CliError::Usage(format!("Unknown clock command '{}'", other)));
+            }
+        }
+        writeln!(out, "🕒 {} UTC (demo clock)", clock.now().format("%Y-%m-%d %H:%M:%S"))?;
+        if !args.is_empty() {
+            self.run_jobs(out)?;
+        }
+        Ok(())
+    }
+
+    /// Run the jobs due on the demo clock, if there is one
+    fn run_jobs(&mut self, out: &mut dyn Write) -> Result<(), CliError> {
+        let Some(clock) = &self.clock else {
+            return Ok(());
+        };
+        for run in self.jobs.run_pending(&mut self.service, clock) {
+            match run.outcome {
+                Ok(summary) => writeln!(out, "⏰ [{}] {}", run.name, summary)?,
+                Err(e) => writeln!(out, "⏰ [{}] {}", run.name, e)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn order(&mut self, args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+        let parsed = ParsedArgs::parse(args)?;
+        let mut beverages: Vec<Box<dyn Beverage>> = Vec::new();
+        while let Some(spec) = parsed.positional(beverages.len()) {
+            beverages.push(menu_beverage(&self.menu, spec)?);
+        }
+        if beverages.is_empty() {
+            return Err(CliError::Usage("Usage: order ITEM[:SIZE]... [--name NAME]".to_string()));
+        }
+        let customer = parsed.option("name").map_or_else(Customer::guest, Customer::named);
+
+        let order = self
+            .service
+            .place_order(customer, beverages)
+            .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+        writeln!(out, "✅ {}", order.summary())?;
+        Ok(())
+    }
+
+    /// The order whose ID starts with `prefix` (dashes optional)
+    fn find_order(&self, prefix: &str) -> Result<Order, CliError> {
+        let prefix = prefix.replace('-', "").to_lowercase();
+        let orders = self
+            .service
+            .list_all_orders()
+            .map_err(|e| CliError::CommandFailed(e.to_string()))?;
+        let mut matches = orders
+            .into_iter()
+            .filter(|order| order.id.simple().to_string().starts_with(&prefix));
+        match (matches.next(), matches.next()) {
+            (Some(order), None) if !prefix.is_empty() => Ok(order),
+            (Some(_), _) => Err(CliError::Usage(format!("Several orders start with {}", prefix))),
+            (None, _) => Err(CliError::CommandFailed(format!("No order {}", prefix))),
+        }
+    }
+
+    /// What could come after `text`: the word it ends with, completed
+    fn completions(&self, text: &str) -> Result<Vec<String>, CliError> {
+        let mut words = words(text);
+        let partial = if text.is_empty() || text.ends_with(char::is_whitespace) {
+            String::new()
+        } else {
+            words.pop().unwrap_or_default()
+        };
+
+        let candidates: Vec<String> = match words.first().map(String::as_str) {
+            None => SHELL_COMMANDS.iter().chain(COMMANDS.iter()).map(|c| c.to_string()).collect(),
+            Some("order") => match partial.split_once(':') {
+                Some((id, _)) => ["small", "medium", "large"]
+                    .iter()
+                    .map(|size| format!("{}:{}", id, size))
+                    .collect(),
+                None => self.menu.iter().map(|item| item.id.to_string()).collect(),
+            },
+            Some(command) if ID_COMMANDS.contains(&command) && words.len() == 1 => self
+                .service
+                .list_all_orders()
+                .map_err(|e| CliError::CommandFailed(e.to_string()))?
+                .iter()
+                .map(|order| order.id.to_string())
+                .collect(),
+            Some("help") if words.len() == 1 => vec!["cli".to_string()],
+            Some("clock") if words.len() == 1 => vec!["advance".to_string(), "set".to_string()],
+            Some(_) => Vec::new(),
+        };
+
+        let mut candidates: Vec<String> =
+            candidates.into_iter().filter(|c| c.starts_with(&partial)).collect();
+        candidates.sort();
+        candidates.dedup();
+        Ok(candidates)
+    }
+}
+
+fn show(order: &Order, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "Order {}", order.id)?;
+    writeln!(out, "  {} <{}>, {}", order.customer.name, order.customer.email, order.status)?;
+    for item in &order.items {
+        writeln!(
+            out,
+            "  {} x {:<40} {:>6.2}",
+            item.quantity,
+            item.beverage_description,
+            item.price.to_major()
+        )?;
+    }
+    writeln!(out, "  Total {:>6.2}", order.amount_charged().to_major())
+}
+
+/// A message for the prompt: usage errors without the whole USAGE after them
+fn message(error: &CliError) -> String {
+    match error {
+        CliError::Usage(msg) => msg.clone(),
+        e => e.to_string(),
+    }
+}
+
+/// The `;`-separated commands of a line, up to a `#` comment (quotes respected)
+fn commands(line: &str) -> Vec<&str> {
+    let mut commands = Vec::new();
+    let (mut start, mut quote) = (0, None);
+    for (at, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            (';', None) => {
+                commands.push(&line[start..at]);
+                start = at + 1;
+            }
+            ('#', None) if line[..at].ends_with(char::is_whitespace) || at == start => {
+                commands.push(&line[start..at]);
+                return commands;
+            }
+            _ => {}
+        }
+    }
+    commands.push(&line[start..]);
+    commands
+}
+
+/// Whitespace-separated words; "double quotes" or 'single quotes' keep spaces
+fn words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let (mut word, mut quote, mut in_word) = (String::new(), None, false);
+    for c in command.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (c, Some(open)) if c == open => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (c, _) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn shell() -> (Shell, PathBuf) {
+        let path = std::env::temp_dir().join(format!("shell-{}.json", Uuid::new_v4()));
+        let args = vec!["--file".to_string(), path.to_str().unwrap().to_string()];
+        let shell = Shell::open(&ParsedArgs::parse(&args).unwrap(), &args).unwrap();
+        (shell, path)
+    }
+
+    #[test]
+    fn test_script_orders_and_moves_them_along() {
+        let (mut shell, path) = shell();
+        let mut out = Vec::new();
+        shell
+            .run_script(
+                "order coffee:large green-tea --name \"Ada Lovelace\" # two drinks\n\
+                 order tropical-smoothie:s ; orders ada.lovelace@example.com",
+                &mut out,
+            )
+            .unwrap();
+        let id = shell.completions("ready ").unwrap()[0].clone();
+        shell.run_script(&format!("prepare {}\nready {}", &id[..4], id), &mut out).unwrap();
+        let failed = shell.run_script("order unicorn-latte\norder coffee", &mut out);
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Ada Lovelace  2 items  6.70 USD  Paid"), "{}", text);
+        assert!(text.contains("Ready"), "{}", text);
+        assert!(failed.is_err());
+        assert!(text.contains("Script stopped at line 1: 'unicorn-latte' is not on the menu"));
+        assert_eq!(shell.service.list_all_orders().unwrap().len(), 2);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_history_is_kept_and_replayed_and_completion_offers_the_menu() {
+        let (mut shell, path) = shell();
+        let history = std::env::temp_dir().join(format!("history-{}", Uuid::new_v4()));
+        fs::write(&history, "orders\n").unwrap();
+        shell.keep_history(Some(history.clone())).unwrap();
+
+        let mut out = Vec::new();
+        let input = "order coffee\n!2\nhistory\nexit\norder coffee\n";
+        shell.interact(&mut input.as_bytes(), &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("   3  order coffee"), "{}", text);
+        assert_eq!(shell.service.list_all_orders().unwrap().len(), 2);
+        let saved = fs::read_to_string(&history).unwrap();
+        assert_eq!(saved, "orders\norder coffee\norder coffee\nhistory\nexit\n");
+
+        assert_eq!(shell.completions("ord").unwrap(), ["order", "orders"]);
+        assert_eq!(shell.completions("order coffee gr").unwrap(), ["green-tea"]);
+        assert_eq!(shell.completions("order coffee:l").unwrap(), ["coffee:large"]);
+        assert!(shell.completions("").unwrap().contains(&"report".to_string()));
+        let _ = (fs::remove_file(history), fs::remove_file(path));
+    }
+
+    #[test]
+    fn test_demo_clock_moves_and_runs_the_jobs() {
+        let (mut system, path) = shell();
+        assert!(system.run_script("clock advance 20m", &mut Vec::new()).is_err());
+
+        let args: Vec<String> = ["--file", path.to_str().unwrap(), "--clock", "now"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let mut shell = Shell::open(&ParsedArgs::parse(&args).unwrap(), &args).unwrap();
+        let mut out = Vec::new();
+        shell.run_jobs(&mut out).unwrap();
+        shell.run_script("order coffee --name Ada", &mut out).unwrap();
+        let id = shell.completions("ready ").unwrap()[0].clone();
+        shell
+            .run_script(&format!("prepare {}\nready {}\nclock advance 20m", id, id), &mut out)
+            .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("⏰ [no-show expiry] 1 order(s) flagged as no-show"), "{}", text);
+        assert!(matches!(
+            shell.find_order(&id).unwrap().status,
+            crate::domain::OrderStatus::NoShow
+        ));
+        assert!(matches!(
+            shell.run_script("clock set 2000-01-01T00:00", &mut Vec::new()),
+            Err(CliError::CommandFailed(_))
+        ));
+        let _ = fs::remove_file(path);
+    }
+}
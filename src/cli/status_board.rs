@@ -0,0 +1,103 @@
+// SOLID: `status-board` subcommand
+//
+// status-board [--output status.html] [--every SECS]
+//
+// Writes the customers' "Now preparing / Ready" board as a static HTML page
+// that reloads itself. Only pickup codes are shown, never a name or email,
+// so the file can go on a public screen or a static host as it is.
+//
+// Without --every it is written once (run it from cron); with --every the
+// command keeps rewriting it, re-reading the orders each time.
+
+use super::{open_repository, CliError, ParsedArgs};
+use crate::presentation::render_status_page;
+use crate::services::board_from_orders;
+use chrono::Utc;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// How often the page reloads when written once
+const DEFAULT_REFRESH_SECONDS: u32 = 30;
+
+/// Entry point for `status-board ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let output = PathBuf::from(parsed.option("output").unwrap_or("status.html"));
+    let every = match parsed.option("every") {
+        Some(secs) => Some(secs.parse::<u32>().ok().filter(|&secs| secs > 0).ok_or_else(|| {
+            CliError::Usage(format!("--every expects a number of seconds, got '{}'", secs))
+        })?),
+        None => None,
+    };
+
+    let refresh = every.unwrap_or(DEFAULT_REFRESH_SECONDS);
+    let shown = write_board(&parsed, &output, refresh)?;
+    writeln!(out, "✅ Status board written to {} ({} order(s))", output.display(), shown)?;
+
+    if let Some(secs) = every {
+        writeln!(out, "🔁 Rewriting it every {}s (Ctrl+C to stop)", secs)?;
+        loop {
+            thread::sleep(Duration::from_secs(secs as u64));
+            write_board(&parsed, &output, refresh)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read the orders again and write the page; returns how many are shown
+fn write_board(parsed: &ParsedArgs, output: &Path, refresh: u32) -> Result<usize, CliError> {
+    let (repository, _) = open_repository(parsed)?;
+    let entries = board_from_orders(&repository.list_all()?);
+    let html = render_status_page("Pickup Board", &entries, Utc::now(), refresh);
+
+    // Written aside and renamed: a screen reloading mid-write never shows half a page
+    let temp = output.with_extension("html.tmp");
+    fs::write(&temp, html)?;
+    fs::rename(&temp, output)?;
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, JsonOrderRepository};
+    use crate::domain::{Coffee, Customer, Size};
+    use crate::services::OrderService;
+    use std::io;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_writes_codes_without_personal_data() {
+        let dir = std::env::temp_dir();
+        let orders = dir.join(format!("status-{}.json", Uuid::new_v4()));
+        let page = dir.join(format!("status-{}.html", Uuid::new_v4()));
+
+        let repository = JsonOrderRepository::new(orders.clone()).unwrap();
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let mut service = OrderService::new(repository, CashPayment, notifier);
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let coffee = Coffee {
+            size: Size::Small,
+            extra_shots: 0,
+        };
+        let order = service.place_order(customer, vec![Box::new(coffee)]).unwrap();
+
+        let args: Vec<String> = ["--file", orders.to_str().unwrap(), "--output"]
+            .iter()
+            .map(|s| s.to_string())
+            .chain([page.to_string_lossy().to_string()])
+            .collect();
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        let html = fs::read_to_string(&page).unwrap();
+        let _ = fs::remove_file(&orders);
+        fs::remove_file(&page).unwrap();
+        assert!(html.contains(&order.id.simple().to_string()[..4].to_uppercase()));
+        assert!(!html.contains("Ada") && !html.contains("ada@example.com"));
+        assert!(String::from_utf8(out).unwrap().contains("(1 order(s))"));
+    }
+}
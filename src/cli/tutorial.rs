@@ -0,0 +1,318 @@
+// SOLID: `tutorial` subcommand (a guided lesson on the running code)
+//
+// tutorial [--lesson N]
+//
+// The interactive demo's "Demonstrate OCP/LSP/DIP" options only print text.
+// Here each lesson actually swaps one adapter, runs the SAME check against
+// the new OrderService, shows that it still passes, then asks one question:
+//
+//   1. Baseline               memory + cash + console
+//   2. Storage                memory   -> JSON file        (LSP, DIP)
+//   3. Payment                cash     -> credit card      (OCP)
+//   4. Notifications          console  -> composite        (OCP, ISP)
+//
+// `exercise()` is generic over R, P and N and never changes between lessons:
+// that is the point being taught. Files go to a temporary folder, removed at
+// the end. Answers are read one line at a time; end of input skips the rest
+// of the quiz, so `tutorial < /dev/null` just runs the lessons.
+
+use super::{CliError, ParsedArgs};
+use crate::adapters::{
+    read_notification_records, CashPayment, CompositeNotifier, ConsoleNotifier, CreditCardPayment,
+    FileNotifier, JsonOrderRepository, MemoryOrderRepository,
+};
+use crate::domain::{Coffee, Customer, Order, OrderStatus};
+use crate::ports::{Notifier, OrderRepository, PaymentProcessor};
+use crate::services::OrderService;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// One multiple-choice question
+struct Quiz {
+    question: &'static str,
+    choices: [&'static str; 3],
+    /// Index into `choices`
+    answer: usize,
+    why: &'static str,
+}
+
+/// One adapter swap, with its check and its question
+struct Lesson {
+    title: &'static str,
+    principles: &'static str,
+    /// The line of wiring that changes
+    wiring: &'static str,
+    explanation: &'static str,
+    /// Builds the service for this lesson and runs the checks; Ok = what was verified
+    run: fn(&Path) -> Result<Vec<String>, String>,
+    quiz: Quiz,
+}
+
+const LESSONS: [Lesson; 4] = [
+    Lesson {
+        title: "Baseline",
+        principles: "DIP",
+        wiring: "OrderService::new(MemoryOrderRepository::new(), CashPayment, console)",
+        explanation: "OrderService<R, P, N> only knows three traits. main.rs picks the adapters.",
+        run: baseline,
+        quiz: Quiz {
+            question: "Which type does OrderService name in its own code?",
+            choices: ["MemoryOrderRepository", "The OrderRepository trait", "Both"],
+            answer: 1,
+            why: "It is generic over R: OrderRepository; adapters are chosen by the caller.",
+        },
+    },
+    Lesson {
+        title: "Storage: memory -> JSON file",
+        principles: "LSP, DIP",
+        wiring: "OrderService::new(JsonOrderRepository::new(path)?, CashPayment, ...)",
+        explanation: "Same contract, different backend: the check below is unchanged, and the \
+                      order is still there when the file is opened again.",
+        run: json_storage,
+        quiz: Quiz {
+            question: "What had to change in OrderService to store orders in a file?",
+            choices: ["A new save_to_file method", "Nothing", "A storage flag"],
+            answer: 1,
+            why: "JsonOrderRepository honors the OrderRepository contract (LSP).",
+        },
+    },
+    Lesson {
+        title: "Payment: cash -> credit card",
+        principles: "OCP",
+        wiring: "OrderService::new(repository, CreditCardPayment::new(url), ...)",
+        explanation: "A new payment method is a new type implementing PaymentProcessor. \
+                      Existing code is closed for modification.",
+        run: credit_card,
+        quiz: Quiz {
+            question: "How would you add mobile payments?",
+            choices: [
+                "Add a `Mobile` case to OrderService",
+                "Write MobilePayment: PaymentProcessor",
+                "Subclass CashPayment",
+            ],
+            answer: 1,
+            why: "Open for extension (a new impl), closed for modification (no edits).",
+        },
+    },
+    Lesson {
+        title: "Notifications: console -> composite",
+        principles: "OCP, ISP",
+        wiring: "OrderService::new(..., CompositeNotifier::new().with(console).with(file))",
+        explanation: "A composite is one more Notifier: it fans out to several channels, and \
+                      neither OrderService nor the channels know about it.",
+        run: composite,
+        quiz: Quiz {
+            question: "Why can FileNotifier be used without a payment or storage method?",
+            choices: [
+                "Notifier only has notification methods",
+                "It panics on the others",
+                "Rust ignores missing methods",
+            ],
+            answer: 0,
+            why: "The ports are segregated: a notifier implements Notifier and nothing else (ISP).",
+        },
+    },
+];
+
+/// Entry point for `tutorial ...` (answers come from stdin)
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    run_with_input(args, &mut io::stdin().lock(), out)
+}
+
+/// Same as `run`, with the learner's answers read from `input`
+pub fn run_with_input(
+    args: &[String],
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let first = match parsed.option("lesson") {
+        Some(value) => value
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=LESSONS.len()).contains(n))
+            .ok_or_else(|| {
+                CliError::Usage(format!("--lesson expects 1-{}, got '{}'", LESSONS.len(), value))
+            })?,
+        None => 1,
+    };
+
+    let workspace = std::env::temp_dir().join(format!("tutorial-{}", Uuid::new_v4()));
+    fs::create_dir_all(&workspace)?;
+    let result = teach(&LESSONS[first - 1..], first, &workspace, input, out);
+    let _ = fs::remove_dir_all(&workspace);
+    result
+}
+
+fn teach(
+    lessons: &[Lesson],
+    first: usize,
+    workspace: &Path,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    writeln!(out, "🎓 SOLID tutorial: swap one adapter at a time, run the same check")?;
+    let mut score = 0;
+    let mut asked = 0;
+    let mut answering = true;
+
+    for (index, lesson) in lessons.iter().enumerate() {
+        let number = first + index;
+        writeln!(out, "\n=== Lesson {}: {} ({}) ===", number, lesson.title, lesson.principles)?;
+        writeln!(out, "  {}", lesson.wiring)?;
+        writeln!(out, "{}", lesson.explanation)?;
+
+        match (lesson.run)(workspace) {
+            Ok(checks) => {
+                for check in checks {
+                    writeln!(out, "  ✅ {}", check)?;
+                }
+            }
+            Err(e) => {
+                writeln!(out, "  ❌ {}", e)?;
+                return Err(CliError::CommandFailed(format!("lesson {}: {}", number, e)));
+            }
+        }
+
+        if !answering {
+            continue;
+        }
+        let quiz = &lesson.quiz;
+        writeln!(out, "\n❓ {}", quiz.question)?;
+        for (i, choice) in quiz.choices.iter().enumerate() {
+            writeln!(out, "   {}. {}", i + 1, choice)?;
+        }
+        write!(out, "Your answer (1-3): ")?;
+        out.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out, "\n(no more answers, skipping the rest of the quiz)")?;
+            answering = false;
+            continue;
+        }
+        asked += 1;
+        if line.trim().parse::<usize>().ok() == Some(quiz.answer + 1) {
+            score += 1;
+            writeln!(out, "✔ Right. {}", quiz.why)?;
+        } else {
+            writeln!(out, "✘ It's {}. {}", quiz.answer + 1, quiz.why)?;
+        }
+    }
+
+    writeln!(out, "\n🎉 Done: {} lesson(s), quiz {}/{}", lessons.len(), score, asked)?;
+    Ok(())
+}
+
+/// The check every lesson runs, whatever the adapters
+fn exercise<R, P, N>(service: &mut OrderService<R, P, N>) -> Result<Order, String>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    let customer = Customer::named("Learner");
+    let coffee = Coffee::medium().with_extra_shots(1);
+    let placed = service
+        .place_order(customer, vec![Box::new(coffee)])
+        .map_err(|e| e.to_string())?;
+    let stored = service.get_order(placed.id).map_err(|e| e.to_string())?;
+    if stored.status != OrderStatus::Paid {
+        return Err(format!("expected a paid order, got {}", stored.status));
+    }
+    Ok(stored)
+}
+
+fn placed(order: &Order) -> String {
+    format!("order placed, paid (${:.2}) and found again by id", order.total_price.to_major())
+}
+
+fn quiet_console() -> ConsoleNotifier<io::Sink> {
+    ConsoleNotifier::with_writer(io::sink())
+}
+
+fn baseline(_: &Path) -> Result<Vec<String>, String> {
+    let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, quiet_console());
+    let order = exercise(&mut service)?;
+    Ok(vec![placed(&order)])
+}
+
+fn json_storage(workspace: &Path) -> Result<Vec<String>, String> {
+    let path: PathBuf = workspace.join("orders.json");
+    let repository = JsonOrderRepository::new(path.clone()).map_err(|e| e.to_string())?;
+    let mut service = OrderService::new(repository, CashPayment, quiet_console());
+    let order = exercise(&mut service)?;
+
+    let reopened = JsonOrderRepository::new(path.clone()).map_err(|e| e.to_string())?;
+    match reopened.find_by_id(order.id).map_err(|e| e.to_string())? {
+        Some(_) => Ok(vec![
+            placed(&order),
+            format!("still there after reopening {}", path.display()),
+        ]),
+        None => Err("the order was not persisted".to_string()),
+    }
+}
+
+fn credit_card(_: &Path) -> Result<Vec<String>, String> {
+    let payment = CreditCardPayment::new("https://gateway.example.com".to_string());
+    let mut service = OrderService::new(MemoryOrderRepository::new(), payment, quiet_console());
+    let order = exercise(&mut service)?;
+    let payment_id = order.payment_id.clone().unwrap_or_default();
+    if !payment_id.starts_with("CC-") {
+        return Err(format!("expected a card payment, got '{}'", payment_id));
+    }
+    Ok(vec![placed(&order), format!("charged by card ({})", payment_id)])
+}
+
+fn composite(workspace: &Path) -> Result<Vec<String>, String> {
+    let log = workspace.join("notifications.jsonl");
+    let notifier = CompositeNotifier::new()
+        .with(quiet_console())
+        .with(FileNotifier::new(log.clone()));
+    let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier);
+    let order = exercise(&mut service)?;
+
+    let records = read_notification_records(&log).map_err(|e| e.to_string())?;
+    if !records.iter().any(|r| r.event == "order.placed" && r.order_id == order.id) {
+        return Err("the file channel missed the notification".to_string());
+    }
+    Ok(vec![
+        placed(&order),
+        "console and file channels both notified".to_string(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tutorial(args: &[&str], answers: &str) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        run_with_input(&args, &mut answers.as_bytes(), &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_every_swap_passes_and_answers_are_scored() {
+        let output = tutorial(&[], "2\n2\n1\n1\n").unwrap();
+
+        assert_eq!(output.matches("❌").count(), 0);
+        assert!(output.contains("still there after reopening"));
+        assert!(output.contains("charged by card (CC-"));
+        assert!(output.contains("console and file channels both notified"));
+        assert!(output.contains("quiz 3/4"));
+    }
+
+    #[test]
+    fn test_start_lesson_and_no_answers() {
+        let output = tutorial(&["--lesson", "3"], "").unwrap();
+
+        assert!(!output.contains("Lesson 2"));
+        assert!(output.contains("Lesson 4"));
+        assert!(output.contains("Done: 2 lesson(s), quiz 0/0"));
+        assert!(matches!(tutorial(&["--lesson", "9"], ""), Err(CliError::Usage(_))));
+    }
+}
@@ -0,0 +1,99 @@
+// SOLID: `verify` subcommand (student exercises)
+//
+// verify              list the exercises and where each one stands
+// verify exercise-N   run the exercise's conformance checks (or `verify N`)
+//
+// The exercises and their checks live in src/exercises/. A failing or
+// unstarted exercise is a failed command, so `cargo run -- verify 2 && ...`
+// works in a grading script.
+
+use super::{CliError, ParsedArgs};
+use crate::exercises::{self, Exercise, Verdict};
+use std::io::Write;
+
+/// Entry point for `verify ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    match parsed.positional(0) {
+        None | Some("list") => list(out),
+        Some(id) => {
+            let exercise = exercises::find(id)
+                .ok_or_else(|| CliError::Usage(format!("Unknown exercise '{}'", id)))?;
+            verify(exercise, out)
+        }
+    }
+}
+
+fn list(out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "Exercises (write your code in src/exercises/solutions.rs):")?;
+    for exercise in exercises::all() {
+        let status = match exercise.verify() {
+            Verdict::NotStarted => "not started".to_string(),
+            Verdict::Checked(checks) => {
+                let passed = checks.iter().filter(|c| c.passed()).count();
+                format!("{}/{} checks pass", passed, checks.len())
+            }
+        };
+        writeln!(
+            out,
+            "  {}  {:<32} {:<10} {}",
+            exercise.id, exercise.title, exercise.principles, status
+        )?;
+    }
+    writeln!(out, "\nRun `verify exercise-N` for the details of one exercise.")?;
+    Ok(())
+}
+
+fn verify(exercise: &Exercise, out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "{}: {} ({})", exercise.id, exercise.title, exercise.principles)?;
+    match exercise.verify() {
+        Verdict::NotStarted => {
+            writeln!(out, "\nNot started yet. {}", exercise.instructions)?;
+            Err(CliError::CommandFailed(format!("{} is not started", exercise.id)))
+        }
+        Verdict::Checked(checks) => {
+            for check in &checks {
+                match &check.outcome {
+                    Ok(()) => writeln!(out, "  ✅ {}", check.name)?,
+                    Err(reason) => writeln!(out, "  ❌ {}: {}", check.name, reason)?,
+                }
+            }
+            let failed = checks.iter().filter(|c| !c.passed()).count();
+            if failed == 0 {
+                writeln!(out, "\n🎉 {} done!", exercise.id)?;
+                Ok(())
+            } else {
+                Err(CliError::CommandFailed(format!(
+                    "{} of {} check(s) failed",
+                    failed,
+                    checks.len()
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify_to_string(args: &[&str]) -> (Result<(), CliError>, String) {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        let result = run(&args, &mut out);
+        (result, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_list_and_unstarted_exercise() {
+        let (result, output) = verify_to_string(&[]);
+        assert!(result.is_ok());
+        assert!(output.contains("exercise-1  Add a Latte"));
+        assert!(output.contains("not started"));
+
+        let (result, output) = verify_to_string(&["exercise-2"]);
+        assert!(matches!(result, Err(CliError::CommandFailed(_))));
+        assert!(output.contains("Not started yet. Create a `MobilePayment`"));
+        assert!(matches!(verify_to_string(&["7"]).0, Err(CliError::Usage(_))));
+    }
+}
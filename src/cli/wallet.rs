@@ -0,0 +1,118 @@
+// SOLID: `wallet` subcommand (store credit)
+//
+// wallet balance <EMAIL>
+// wallet grant <EMAIL> <AMOUNT> [--reason TEXT]
+// wallet liability [--date DATE]
+//
+// Wallets live in --wallets (default: wallets.json), the file WalletPayment
+// and the complaint remedies use. `grant` is for promotions and goodwill
+// handed out by hand; `liability` is what the shop owes on a day.
+
+use super::{CliError, ParsedArgs};
+use crate::adapters::JsonStoreCredit;
+use crate::domain::Money;
+use crate::ports::{CreditError, StoreCredit};
+use crate::services::credit_liability;
+use chrono::Utc;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Entry point for `wallet ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let path = PathBuf::from(parsed.option("wallets").unwrap_or("wallets.json"));
+    let mut credit = JsonStoreCredit::new(path).map_err(credit_error)?;
+
+    match parsed.positional(0) {
+        Some("balance") => {
+            let email = email(&parsed)?;
+            let balance = credit.balance(email).map_err(credit_error)?;
+            writeln!(out, "💳 {}: {} of store credit", email, balance)?;
+            Ok(())
+        }
+        Some("grant") => {
+            let email = email(&parsed)?;
+            let amount = parsed
+                .positional(2)
+                .and_then(|amount| amount.parse::<f64>().ok())
+                .ok_or_else(|| CliError::Usage("wallet grant needs an AMOUNT".to_string()))?;
+            // In the wallet's currency (US dollars for a new one)
+            let currency = credit.balance(email).map_err(credit_error)?.currency;
+            let amount = Money::from_major(amount, currency);
+            let reason = parsed.option("reason").unwrap_or("Promotion");
+            let reference = credit.grant(email, amount, reason).map_err(credit_error)?;
+            let balance = credit.balance(email).map_err(credit_error)?;
+            writeln!(
+                out,
+                "✅ Granted {} to {} ({}), balance {}",
+                amount, email, reference, balance
+            )?;
+            Ok(())
+        }
+        Some("liability") | None => {
+            let as_of = parsed.date("date")?.unwrap_or_else(|| Utc::now().date_naive());
+            let wallets = credit.wallets().map_err(credit_error)?;
+            writeln!(out, "Store credit on {}", as_of)?;
+            let liabilities = credit_liability(&wallets, as_of);
+            if liabilities.is_empty() {
+                writeln!(out, "  None granted")?;
+            }
+            for liability in liabilities {
+                writeln!(out, "  Granted:     {}", liability.granted)?;
+                writeln!(out, "  Spent:       {}", liability.spent)?;
+                writeln!(out, "  Restored:    {}", liability.restored)?;
+                writeln!(
+                    out,
+                    "  Outstanding: {} ({} customer(s))",
+                    liability.outstanding, liability.customers
+                )?;
+            }
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown wallet action '{}'", other))),
+    }
+}
+
+fn email(parsed: &ParsedArgs) -> Result<&str, CliError> {
+    parsed
+        .positional(1)
+        .filter(|email| email.contains('@'))
+        .ok_or_else(|| CliError::Usage("Expected a customer email".to_string()))
+}
+
+fn credit_error(e: CreditError) -> CliError {
+    match e {
+        CreditError::InvalidAmount(_) => CliError::Usage(e.to_string()),
+        _ => CliError::CommandFailed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn run_in(wallets: &std::path::Path, args: &[&str]) -> Result<String, CliError> {
+        let mut args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        args.extend(["--wallets".to_string(), wallets.display().to_string()]);
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_grant_balance_liability() {
+        let wallets = std::env::temp_dir().join(format!("wallets-{}.json", Uuid::new_v4()));
+
+        let granted = run_in(&wallets, &["grant", "ada@example.com", "4.5"]).unwrap();
+        assert!(granted.contains("balance 4.50 USD"));
+        assert!(matches!(
+            run_in(&wallets, &["grant", "ada@example.com", "-2"]),
+            Err(CliError::Usage(_))
+        ));
+        assert!(run_in(&wallets, &["balance", "ADA@example.com"]).unwrap().contains("4.50 USD"));
+        let liability = run_in(&wallets, &["liability"]).unwrap();
+        assert!(liability.contains("Outstanding: 4.50 USD (1 customer(s))"));
+        std::fs::remove_file(wallets).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+// SOLID: `webhooks` subcommand (subscription management)
+//
+// webhooks list
+// webhooks add <URL> --secret SECRET [--events order.placed,order.ready] [--format xml]
+// webhooks enable <ID> | disable <ID> | remove <ID>
+//
+// This is the admin surface for webhook subscriptions. It only talks to a
+// WebhookSubscriptionStore (ISP): managing subscriptions needs no HTTP client.
+// `--format` names the PayloadSerializer the receiver expects (json, xml,
+// cloudevents); without it, deliveries use the dispatcher's default.
+
+use super::{CliError, ParsedArgs};
+use crate::adapters::JsonWebhookStore;
+use crate::domain::WebhookSubscription;
+use crate::ports::{WebhookError, WebhookSubscriptionStore};
+use std::io::Write;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Wire formats with a serializer in this crate
+const FORMATS: [&str; 3] = ["json", "xml", "cloudevents"];
+
+/// Entry point for `webhooks ...`
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let parsed = ParsedArgs::parse(args)?;
+    let path = PathBuf::from(parsed.option("store").unwrap_or("webhooks.json"));
+    let mut store = JsonWebhookStore::new(path).map_err(webhook_error)?;
+
+    match parsed.positional(0) {
+        Some("list") | None => list(&store, out),
+        Some("add") => {
+            let url = parsed
+                .positional(1)
+                .ok_or_else(|| CliError::Usage("webhooks add needs a URL".to_string()))?;
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(CliError::Usage(format!("'{}' is not an http(s) URL", url)));
+            }
+            let secret = parsed
+                .option("secret")
+                .ok_or_else(|| CliError::Usage("webhooks add needs --secret".to_string()))?;
+            let events = parsed
+                .option("events")
+                .map(|list| list.split(',').map(|e| e.trim().to_string()).collect())
+                .unwrap_or_default();
+
+            let mut subscription =
+                WebhookSubscription::new(url.to_string(), secret.to_string(), events);
+            if let Some(format) = parsed.option("format") {
+                if !FORMATS.contains(&format) {
+                    return Err(CliError::Usage(format!(
+                        "Unknown format '{}' (expected one of: {})",
+                        format,
+                        FORMATS.join(", ")
+                    )));
+                }
+                subscription = subscription.with_format(format);
+            }
+            let id = subscription.id;
+            store.add(subscription).map_err(webhook_error)?;
+            writeln!(out, "✅ Added webhook {}", id)?;
+            Ok(())
+        }
+        Some(action @ ("enable" | "disable")) => {
+            let mut subscription = find(&store, &parsed)?;
+            subscription.active = action == "enable";
+            let id = subscription.id;
+            store.update(subscription).map_err(webhook_error)?;
+            writeln!(out, "✅ Webhook {} {}d", id, action)?;
+            Ok(())
+        }
+        Some("remove") => {
+            let id = find(&store, &parsed)?.id;
+            store.remove(id).map_err(webhook_error)?;
+            writeln!(out, "✅ Removed webhook {}", id)?;
+            Ok(())
+        }
+        Some(other) => Err(CliError::Usage(format!("Unknown webhooks action '{}'", other))),
+    }
+}
+
+fn list(store: &JsonWebhookStore, out: &mut dyn Write) -> Result<(), CliError> {
+    let subscriptions = store.list().map_err(webhook_error)?;
+    if subscriptions.is_empty() {
+        writeln!(out, "No webhook subscriptions.")?;
+    }
+
+    for subscription in subscriptions {
+        let events = if subscription.events.is_empty() {
+            "all events".to_string()
+        } else {
+            subscription.events.join(", ")
+        };
+        let format = subscription
+            .format
+            .as_deref()
+            .map(|format| format!(", {}", format))
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{} {} {} ({}{})",
+            if subscription.active { "●" } else { "○" },
+            subscription.id,
+            subscription.url,
+            events,
+            format
+        )?;
+    }
+    Ok(())
+}
+
+/// The subscription named by the second positional argument
+fn find(store: &JsonWebhookStore, parsed: &ParsedArgs) -> Result<WebhookSubscription, CliError> {
+    let id = parsed
+        .positional(1)
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| CliError::Usage("Expected a webhook ID".to_string()))?;
+
+    store
+        .find(id)
+        .map_err(webhook_error)?
+        .ok_or_else(|| webhook_error(WebhookError::NotFound(id)))
+}
+
+fn webhook_error(e: WebhookError) -> CliError {
+    CliError::InvalidConfig(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_in(store: &std::path::Path, args: &[&str]) -> Result<String, CliError> {
+        let mut args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        args.extend(["--store".to_string(), store.display().to_string()]);
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_add_disable_remove() {
+        let store = std::env::temp_dir().join(format!("webhooks-{}.json", Uuid::new_v4()));
+
+        let added = run_in(&store, &["add", "https://example.com/hook", "--secret", "s", "--events", "order.ready"]).unwrap();
+        let id = added.trim().rsplit(' ').next().unwrap().to_string();
+        assert!(run_in(&store, &["list"]).unwrap().contains("● "));
+
+        run_in(&store, &["disable", &id]).unwrap();
+        assert!(run_in(&store, &["list"]).unwrap().contains("○ "));
+
+        run_in(&store, &["remove", &id]).unwrap();
+        assert!(run_in(&store, &["list"]).unwrap().contains("No webhook subscriptions."));
+        std::fs::remove_file(store).unwrap();
+    }
+
+    #[test]
+    fn test_add_requires_secret_and_url() {
+        let store = std::env::temp_dir().join(format!("webhooks-{}.json", Uuid::new_v4()));
+
+        assert!(matches!(run_in(&store, &["add", "https://example.com"]), Err(CliError::Usage(_))));
+        assert!(matches!(run_in(&store, &["add", "ftp://x", "--secret", "s"]), Err(CliError::Usage(_))));
+        assert!(!store.exists());
+    }
+
+    #[test]
+    fn test_add_with_format() {
+        let store = std::env::temp_dir().join(format!("webhooks-{}.json", Uuid::new_v4()));
+
+        let yaml = ["add", "https://example.com/hook", "--secret", "s", "--format", "yaml"];
+        assert!(matches!(run_in(&store, &yaml), Err(CliError::Usage(_))));
+        run_in(&store, &["add", "https://erp.example/in", "--secret", "s", "--format", "xml"]).unwrap();
+        assert!(run_in(&store, &["list"]).unwrap().contains("(all events, xml)"));
+        std::fs::remove_file(store).unwrap();
+    }
+}
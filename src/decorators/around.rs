@@ -0,0 +1,287 @@
+// SOLID: Decorated<T, A> - one wrapper type for every port
+//
+// A cross-cutting concern (logging, timing, retry, ...) is an `Around` policy:
+// "do something before and after a call". `Decorated` pairs a policy with
+// any port implementation and implements the port again, forwarding each
+// method through the policy.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// A Decorated<MemoryOrderRepository, Logging> IS an OrderRepository, with the
+// same contract. OrderService can't tell it is wrapped.
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// A new concern is a new Around impl. It works for all three ports without
+// touching this file, the ports or the adapters.
+
+use crate::domain::{
+    FeedbackRequestNotice, Money, OpsAlertNotice, Order, OrderCancelledNotice, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, RefundPendingNotice, RewardGrantedNotice,
+    SlaBreachNotice,
+};
+use crate::ports::{
+    NotificationError, Notifier, OrderRepository, PaymentError, PaymentProcessor, PaymentRequest,
+    PendingPayment, RepositoryCapabilities, RepositoryError,
+};
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The error side of a port call, as policies see it
+pub trait CallError: fmt::Display {
+    /// Worth trying again (a timeout, a dropped connection)?
+    ///
+    /// Contract violations like AlreadyExists or InvalidCard never are.
+    fn is_transient(&self) -> bool;
+}
+
+impl CallError for RepositoryError {
+    fn is_transient(&self) -> bool {
+        matches!(self, RepositoryError::LoadFailed(_) | RepositoryError::SaveFailed(_))
+    }
+}
+
+impl CallError for PaymentError {
+    fn is_transient(&self) -> bool {
+        matches!(self, PaymentError::NetworkError(_))
+    }
+}
+
+impl CallError for NotificationError {
+    fn is_transient(&self) -> bool {
+        matches!(self, NotificationError::NetworkError(_))
+    }
+}
+
+/// Behavior wrapped around every call of a decorated port
+pub trait Around {
+    /// Run `call` (zero, one or several times) and return its result
+    ///
+    /// `operation` names the port method, e.g. "repository.save".
+    fn around<T, E: CallError>(
+        &self,
+        operation: &'static str,
+        call: impl FnMut() -> Result<T, E>,
+    ) -> Result<T, E>;
+}
+
+/// A port implementation plus a policy applied to each of its calls
+pub struct Decorated<T, A> {
+    inner: T,
+    policy: A,
+}
+
+impl<T, A: Around> Decorated<T, A> {
+    pub fn new(inner: T, policy: A) -> Self {
+        Self { inner, policy }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn policy(&self) -> &A {
+        &self.policy
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: OrderRepository, A: Around + Send> OrderRepository for Decorated<R, A> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        let inner = &mut self.inner;
+        self.policy.around("repository.save", || inner.save(order))
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        self.policy.around("repository.find_by_id", || self.inner.find_by_id(id))
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.policy.around("repository.find_by_customer_email", || {
+            self.inner.find_by_customer_email(email)
+        })
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.policy.around("repository.list_all", || self.inner.list_all())
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        let inner = &mut self.inner;
+        self.policy.around("repository.update", || inner.update(order))
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        let inner = &mut self.inner;
+        self.policy.around("repository.delete", || inner.delete(id))
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        let inner = &mut self.inner;
+        self.policy.around("repository.save_batch", || inner.save_batch(orders))
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.policy.around("repository.list_all", || self.inner.list_all_shared())
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.policy.around("repository.find_by_customer_email", || {
+            self.inner.find_by_customer_email_shared(email)
+        })
+    }
+}
+
+impl<R: RepositoryCapabilities, A> RepositoryCapabilities for Decorated<R, A> {
+    fn supports_transactions(&self) -> bool {
+        self.inner.supports_transactions()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        self.inner.max_batch_size()
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        self.inner.full_text_search(query)
+    }
+}
+
+impl<P: PaymentProcessor, A: Around> PaymentProcessor for Decorated<P, A> {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        self.policy.around("payment.process", || self.inner.process_payment(amount))
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        self.policy.around("payment.process", || self.inner.process_request(request))
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        self.policy.around("payment.refund", || self.inner.refund(payment_id, amount))
+    }
+
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        self.policy.around("payment.refund", || {
+            self.inner.refund_with_key(payment_id, amount, idempotency_key)
+        })
+    }
+
+    fn paid_with_points(&self, payment_id: &str) -> Option<Money> {
+        self.inner.paid_with_points(payment_id)
+    }
+
+    fn initiate(&self, request: &PaymentRequest) -> Result<PendingPayment, PaymentError> {
+        self.policy.around("payment.initiate", || self.inner.initiate(request))
+    }
+
+    fn confirm(&self, token: &str) -> Result<String, PaymentError> {
+        self.policy.around("payment.confirm", || self.inner.confirm(token))
+    }
+
+    fn payment_method_name(&self) -> &str {
+        self.inner.payment_method_name()
+    }
+}
+
+impl<N: Notifier, A: Around> Notifier for Decorated<N, A> {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.order_placed", || self.inner.notify_order_placed(notice))
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.order_ready", || self.inner.notify_order_ready(notice))
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.order_cancelled", || {
+            self.inner.notify_order_cancelled(notice)
+        })
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.order_no_show", || self.inner.notify_order_no_show(notice))
+    }
+
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.sla_breach", || self.inner.notify_sla_breach(notice))
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.refund_pending", || self.inner.notify_refund_pending(notice))
+    }
+
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.ops_alert", || self.inner.notify_ops_alert(notice))
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        self.policy.around("notifier.feedback_requested", || {
+            self.inner.notify_feedback_requested(notice)
+        })
+    }
+
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        self.policy.around("notifier.reward_granted", || self.inner.notify_reward_granted(notice))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, MemoryOrderRepository};
+    use std::sync::Mutex;
+
+    /// Records which operations went through it
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<&'static str>>);
+
+    impl Around for Recorder {
+        fn around<T, E: CallError>(
+            &self,
+            operation: &'static str,
+            mut call: impl FnMut() -> Result<T, E>,
+        ) -> Result<T, E> {
+            self.0.lock().unwrap().push(operation);
+            call()
+        }
+    }
+
+    #[test]
+    fn test_every_method_goes_through_the_policy() {
+        let mut repo = Decorated::new(MemoryOrderRepository::new(), Recorder::default());
+        repo.list_all().unwrap();
+        repo.delete(Uuid::new_v4()).unwrap();
+
+        let payment = Decorated::new(CashPayment, Recorder::default());
+        payment.process_payment(Money::usd(2.0)).unwrap();
+
+        assert_eq!(*repo.policy().0.lock().unwrap(), vec!["repository.list_all", "repository.delete"]);
+        assert_eq!(*payment.policy().0.lock().unwrap(), vec!["payment.process"]);
+        assert_eq!(payment.payment_method_name(), "Cash");
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(RepositoryError::SaveFailed("disk".to_string()).is_transient());
+        assert!(!RepositoryError::AlreadyExists("x".to_string()).is_transient());
+        assert!(PaymentError::NetworkError("timeout".to_string()).is_transient());
+        assert!(!PaymentError::InvalidCard.is_transient());
+        assert!(!NotificationError::InvalidRecipient("x".to_string()).is_transient());
+    }
+}
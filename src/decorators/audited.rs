@@ -0,0 +1,177 @@
+// SOLID: AuditedRepository - who changed what on an order, update by update
+//
+// Every successful `update` is compared with the version it replaced
+// (domain::order_diff), and the changes go to an AuditLog:
+//
+//   order 1a2b3c4d: status: Paid -> Ready
+//
+// Like caching, it needs to understand the call (load the previous version
+// first), so it is a repository decorator rather than an Around policy.
+// Saves and deletes are not diffs and are not recorded; an update that
+// changes nothing is not recorded either.
+//
+// Clones of an AuditLog share the same entries, so keep one handle to read
+// them after moving the repository into a service.
+
+use crate::domain::{order_diff, FieldChange, Order};
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// The changes one update made
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub order_id: Uuid,
+    pub at: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Audit entries, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    entries: Arc<Mutex<Vec<AuditEntry>>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// The history of one order
+    pub fn for_order(&self, order_id: Uuid) -> Vec<AuditEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.order_id == order_id)
+            .cloned()
+            .collect()
+    }
+
+    fn record(&self, entry: AuditEntry) {
+        self.entries.lock().unwrap().push(entry);
+    }
+}
+
+/// Records what each update changed, in front of any repository
+pub struct AuditedRepository<R: OrderRepository> {
+    inner: R,
+    log: AuditLog,
+}
+
+impl<R: OrderRepository> AuditedRepository<R> {
+    /// Wrap `inner`, recording into `log` (a shared handle)
+    pub fn new(inner: R, log: &AuditLog) -> Self {
+        Self {
+            inner,
+            log: log.clone(),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: OrderRepository> OrderRepository for AuditedRepository<R> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.inner.save(order)
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        self.inner.find_by_id(id)
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.inner.find_by_customer_email(email)
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.inner.list_all()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        let before = self.inner.find_by_id(order.id)?;
+        self.inner.update(order)?;
+
+        let changes = before.map_or_else(Vec::new, |before| order_diff(&before, order));
+        if !changes.is_empty() {
+            self.log.record(AuditEntry {
+                order_id: order.id,
+                at: Utc::now(),
+                changes,
+            });
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        self.inner.delete(id)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        self.inner.save_batch(orders)
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.inner.list_all_shared()
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.inner.find_by_customer_email_shared(email)
+    }
+}
+
+impl<R: OrderRepository + RepositoryCapabilities> RepositoryCapabilities for AuditedRepository<R> {
+    fn supports_transactions(&self) -> bool {
+        self.inner.supports_transactions()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        self.inner.max_batch_size()
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        self.inner.full_text_search(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer, OrderStatus};
+
+    #[test]
+    fn test_updates_are_recorded_with_their_changes() {
+        let log = AuditLog::new();
+        let mut repository = AuditedRepository::new(MemoryOrderRepository::new(), &log);
+        let mut order = Order::single(Customer::named("Ada"), Coffee::medium());
+        repository.save(&order).unwrap();
+
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        repository.update(&order).unwrap();
+        repository.update(&order).unwrap();
+        let missing = Order::single(Customer::named("Bob"), Coffee::small());
+        assert!(repository.update(&missing).is_err());
+
+        let entries = log.for_order(order.id);
+        assert_eq!(log.entries().len(), 1);
+        assert_eq!(
+            entries[0].changes[0],
+            FieldChange::StatusChanged { from: OrderStatus::Pending, to: OrderStatus::Paid }
+        );
+    }
+}
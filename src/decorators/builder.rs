@@ -0,0 +1,189 @@
+// SOLID: Decorate - stack decorators without spelling out nested types
+//
+// Decorate::new(JsonOrderRepository::new(path)?)
+//     .retried(3)
+//     .logged("orders")
+//     .build()
+//
+// Layers wrap outward: the first one added sits closest to the adapter,
+// the last one added sees the call first. Above, logging sees one call per
+// operation however many retries happened underneath.
+
+use super::around::{Around, Decorated};
+use super::audited::{AuditLog, AuditedRepository};
+use super::caching::CachedRepository;
+use super::dedup::DedupNotifier;
+use super::logging::Logging;
+use super::metrics::CallMetrics;
+use super::retry::Retry;
+use super::timing::Timing;
+use super::wal::WalRepository;
+use crate::ports::{Notifier, OrderRepository, RepositoryError};
+use std::path::PathBuf;
+
+/// Builder wrapping a port implementation layer by layer
+pub struct Decorate<T> {
+    inner: T,
+}
+
+impl<T> Decorate<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Add any policy
+    pub fn with<A: Around>(self, policy: A) -> Decorate<Decorated<T, A>> {
+        Decorate {
+            inner: Decorated::new(self.inner, policy),
+        }
+    }
+
+    /// Log every call to stderr
+    pub fn logged(self, label: &str) -> Decorate<Decorated<T, Logging>> {
+        self.with(Logging::new(label))
+    }
+
+    /// Record durations into `timing` (a shared handle)
+    pub fn timed(self, timing: &Timing) -> Decorate<Decorated<T, Timing>> {
+        self.with(timing.clone())
+    }
+
+    /// Count calls and errors into `metrics` (a shared handle)
+    pub fn metered(self, metrics: &CallMetrics) -> Decorate<Decorated<T, CallMetrics>> {
+        self.with(metrics.clone())
+    }
+
+    /// Retry transient failures, up to `max_attempts` calls in total
+    pub fn retried(self, max_attempts: u32) -> Decorate<Decorated<T, Retry>> {
+        self.with(Retry::new(max_attempts))
+    }
+
+    pub fn build(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: OrderRepository> Decorate<R> {
+    /// Cache lookups by ID (repositories only)
+    pub fn cached(self) -> Decorate<CachedRepository<R>> {
+        Decorate {
+            inner: CachedRepository::new(self.inner),
+        }
+    }
+
+    /// Record what each update changes into `log` (a shared handle)
+    pub fn audited(self, log: &AuditLog) -> Decorate<AuditedRepository<R>> {
+        Decorate {
+            inner: AuditedRepository::new(self.inner, log),
+        }
+    }
+
+    /// Log every write to `log_path` before it reaches the repository
+    pub fn write_ahead(
+        self,
+        log_path: PathBuf,
+    ) -> Result<Decorate<WalRepository<R>>, RepositoryError> {
+        Ok(Decorate {
+            inner: WalRepository::new(self.inner, log_path)?,
+        })
+    }
+}
+
+impl<N: Notifier> Decorate<N> {
+    /// Send the same notice at most once per `ttl` (notifiers only)
+    pub fn deduplicated(self, ttl: chrono::Duration) -> Decorate<DedupNotifier<N>> {
+        Decorate {
+            inner: DedupNotifier::new(self.inner, ttl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{ConsoleNotifier, MemoryOrderRepository};
+    use crate::domain::{
+        Beverage, Coffee, Customer, Money, Order, OrderItem, OrderReadyNotice, Size,
+    };
+    use crate::ports::{Notifier, PaymentError, PaymentProcessor};
+    use crate::services::OrderService;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    /// Fails with a network error the first `failures` times
+    struct FlakyPayment {
+        failures: AtomicU32,
+    }
+
+    impl PaymentProcessor for FlakyPayment {
+        fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(PaymentError::NetworkError("gateway timeout".to_string()));
+            }
+            Ok("CC-1".to_string())
+        }
+    }
+
+    #[test]
+    fn test_decorated_ports_plug_into_order_service() {
+        let metrics = CallMetrics::new();
+        let timing = Timing::new();
+
+        let repository = Decorate::new(MemoryOrderRepository::new())
+            .cached()
+            .metered(&metrics)
+            .build();
+        let payment = Decorate::new(FlakyPayment { failures: AtomicU32::new(2) })
+            .metered(&metrics)
+            .with(Retry::new(3).with_backoff(Duration::ZERO))
+            .build();
+        let notifier = Decorate::new(ConsoleNotifier::with_writer(Vec::new()))
+            .timed(&timing)
+            .build();
+
+        let mut service = OrderService::new(repository, payment, notifier);
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Small,
+            extra_shots: 0,
+        })];
+        let order = service.place_order(customer, beverages).unwrap();
+        service.get_order(order.id).unwrap();
+
+        // Retry is outside the metrics layer: it saw all three attempts
+        assert_eq!(metrics.get("payment.process").calls, 3);
+        assert_eq!(metrics.get("payment.process").errors, 2);
+        assert_eq!(metrics.get("repository.save").calls, 1);
+        assert_eq!(timing.get("notifier.order_placed").unwrap().calls, 1);
+    }
+
+    #[test]
+    fn test_stacked_layers_still_reach_the_adapter() {
+        let inner = CallMetrics::new();
+        let outer = CallMetrics::new();
+        let notifier = Decorate::new(ConsoleNotifier::with_writer(Vec::new()))
+            .metered(&inner)
+            .metered(&outer)
+            .build();
+
+        let order = Order::new(
+            Customer::new("Ada".to_string(), "ada@example.com".to_string(), None),
+            vec![OrderItem {
+                beverage_name: "Tea".to_string(),
+                beverage_description: "Small Tea".to_string(),
+                price: Money::usd(2.0),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        notifier.notify_order_ready(&OrderReadyNotice::from_order(&order)).unwrap();
+
+        assert_eq!(inner.total().calls, 1);
+        assert_eq!(outer.total().calls, 1);
+        let console = notifier.into_inner().into_inner().into_inner();
+        assert!(String::from_utf8(console).unwrap().contains("Order Ready"));
+    }
+}
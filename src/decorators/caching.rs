@@ -0,0 +1,156 @@
+// SOLID: CachedRepository - remember find_by_id results
+//
+// Caching is the one concern that needs to understand the calls it wraps
+// (which are reads, which writes invalidate what), so it is a repository
+// decorator of its own rather than an Around policy. Payments and
+// notifications are never cached: repeating them is the whole point.
+//
+// Writes through this wrapper invalidate the orders they touch. Writes that
+// bypass it (another process, another handle) are not seen: wrap the
+// repository once and share the wrapper.
+
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Caches lookups by ID (misses included) in front of any repository
+pub struct CachedRepository<R: OrderRepository> {
+    inner: R,
+    by_id: Mutex<HashMap<Uuid, Option<Order>>>,
+    hits: Mutex<u64>,
+}
+
+impl<R: OrderRepository> CachedRepository<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            by_id: Mutex::new(HashMap::new()),
+            hits: Mutex::new(0),
+        }
+    }
+
+    /// Lookups answered without asking the inner repository
+    pub fn hits(&self) -> u64 {
+        *self.hits.lock().unwrap()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn forget(&self, id: Uuid) {
+        self.by_id.lock().unwrap().remove(&id);
+    }
+}
+
+impl<R: OrderRepository> OrderRepository for CachedRepository<R> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.forget(order.id);
+        self.inner.save(order)
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        if let Some(cached) = self.by_id.lock().unwrap().get(&id) {
+            *self.hits.lock().unwrap() += 1;
+            return Ok(cached.clone());
+        }
+
+        let found = self.inner.find_by_id(id)?;
+        self.by_id.lock().unwrap().insert(id, found.clone());
+        Ok(found)
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.inner.find_by_customer_email(email)
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.inner.list_all()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.forget(order.id);
+        self.inner.update(order)
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        self.forget(id);
+        self.inner.delete(id)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        for order in orders {
+            self.forget(order.id);
+        }
+        self.inner.save_batch(orders)
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.inner.list_all_shared()
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.inner.find_by_customer_email_shared(email)
+    }
+}
+
+impl<R: OrderRepository + RepositoryCapabilities> RepositoryCapabilities for CachedRepository<R> {
+    fn supports_transactions(&self) -> bool {
+        self.inner.supports_transactions()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        self.inner.max_batch_size()
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        self.inner.full_text_search(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer, OrderStatus};
+
+    fn make_order() -> Order {
+        Order::single(Customer::named("Ada"), Coffee::medium())
+    }
+
+    #[test]
+    fn test_repeated_lookups_hit_the_cache() {
+        let mut repo = CachedRepository::new(MemoryOrderRepository::new());
+        let order = make_order();
+
+        // A cached miss must not hide an order saved afterwards
+        assert!(repo.find_by_id(order.id).unwrap().is_none());
+        repo.save(&order).unwrap();
+
+        assert!(repo.find_by_id(order.id).unwrap().is_some());
+        assert!(repo.find_by_id(order.id).unwrap().is_some());
+        assert_eq!(repo.hits(), 1);
+    }
+
+    #[test]
+    fn test_update_invalidates() {
+        let mut repo = CachedRepository::new(MemoryOrderRepository::new());
+        let mut order = make_order();
+        repo.save(&order).unwrap();
+        repo.find_by_id(order.id).unwrap();
+
+        order.cancel().unwrap();
+        repo.update(&order).unwrap();
+
+        assert_eq!(repo.find_by_id(order.id).unwrap().unwrap().status, OrderStatus::Cancelled);
+    }
+}
@@ -0,0 +1,190 @@
+// SOLID: DedupNotifier - the same notice never goes out twice
+//
+// A notice is identified by (customer, event, subject): the recipient's
+// email, the event name ("order.ready", same names as FileNotifier), and the
+// order, refund or coupon it is about. Once a channel accepted one, the same
+// key is dropped, reported as sent, until `ttl` has passed:
+//
+// - a job re-run after a partial failure (the reward job, the SLA watchdog)
+// - an event replayed from the bus or the WAL after a restart
+// - two paths reaching the same channel (a composite listing it twice)
+//
+// Only successful sends are remembered, so a failed one can be retried.
+// Wrap each channel of a CompositeNotifier rather than the composite:
+// a retry after one channel failed then only reaches that channel.
+//
+// The keys are kept in memory; TTL is measured with the Clock port.
+
+use crate::adapters::SystemClock;
+use crate::domain::{
+    FeedbackRequestNotice, NoticeRecipient, OpsAlertNotice, OrderCancelledNotice,
+    OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice, RefundPendingNotice,
+    RewardGrantedNotice, SlaBreachNotice,
+};
+use crate::ports::{Clock, NotificationError, Notifier};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Drops notices already sent within the TTL
+pub struct DedupNotifier<N: Notifier> {
+    inner: N,
+    ttl: Duration,
+    sent: Mutex<HashMap<String, DateTime<Utc>>>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl<N: Notifier> DedupNotifier<N> {
+    pub fn new(inner: N, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            sent: Mutex::new(HashMap::new()),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn into_inner(self) -> N {
+        self.inner
+    }
+
+    /// Keys remembered right now (expired ones included until the next call)
+    pub fn remembered(&self) -> usize {
+        self.sent.lock().map_or(0, |sent| sent.len())
+    }
+
+    fn once(
+        &self,
+        recipient: &NoticeRecipient,
+        event: &str,
+        subject: Uuid,
+        send: impl FnOnce(&N) -> Result<(), NotificationError>,
+    ) -> Result<(), NotificationError> {
+        let key = format!("{}:{}:{}", recipient.email.to_lowercase(), event, subject);
+        let now = self.clock.now();
+        {
+            let mut sent = self.lock()?;
+            sent.retain(|_, at| now - *at < self.ttl);
+            if sent.contains_key(&key) {
+                return Ok(());
+            }
+        }
+        send(&self.inner)?;
+        self.lock()?.insert(key, now);
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<String, DateTime<Utc>>>, NotificationError> {
+        self.sent
+            .lock()
+            .map_err(|e| NotificationError::SendFailed(format!("Dedup lock poisoned: {}", e)))
+    }
+}
+
+impl<N: Notifier> Notifier for DedupNotifier<N> {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "order.placed", notice.order_id, |inner| {
+            inner.notify_order_placed(notice)
+        })
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "order.ready", notice.order_id, |inner| {
+            inner.notify_order_ready(notice)
+        })
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "order.cancelled", notice.order_id, |inner| {
+            inner.notify_order_cancelled(notice)
+        })
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "order.no_show", notice.order_id, |inner| {
+            inner.notify_order_no_show(notice)
+        })
+    }
+
+    /// One breach per status: stuck in Paid, then in Preparing, are two
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        let event = format!("order.sla_breach.{}", notice.stuck_in);
+        self.once(&notice.recipient, &event, notice.order_id, |inner| {
+            inner.notify_sla_breach(notice)
+        })
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "refund.pending", notice.refund_id, |inner| {
+            inner.notify_refund_pending(notice)
+        })
+    }
+
+    /// Forwarded as is: reminders repeat on purpose, and AlertEngine's cooldown paces them
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        self.inner.notify_ops_alert(notice)
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "feedback.requested", notice.order_id, |inner| {
+            inner.notify_feedback_requested(notice)
+        })
+    }
+
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        self.once(&notice.recipient, "reward.granted", notice.coupon_id, |inner| {
+            inner.notify_reward_granted(notice)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{read_notification_records, FileNotifier, FixedClock};
+    use crate::domain::{Coffee, Customer, Order};
+
+    #[test]
+    fn test_same_notice_is_sent_once_per_ttl() {
+        let path = std::env::temp_dir().join(format!("dedup-{}.jsonl", Uuid::new_v4()));
+        let clock = Arc::new(FixedClock::new(Utc::now()));
+        let notifier = DedupNotifier::new(FileNotifier::new(path.clone()), Duration::hours(1))
+            .with_clock(clock.clone());
+        let order = Order::single(Customer::named("Ada"), Coffee::medium());
+        let other = Order::single(Customer::named("Bob"), Coffee::small());
+
+        let ready = OrderReadyNotice::from_order(&order);
+        notifier.notify_order_ready(&ready).unwrap();
+        notifier.notify_order_ready(&ready).unwrap();
+        notifier.notify_order_placed(&OrderPlacedNotice::from_order(&order)).unwrap();
+        notifier.notify_order_ready(&OrderReadyNotice::from_order(&other)).unwrap();
+        assert_eq!(read_notification_records(&path).unwrap().len(), 3);
+
+        clock.advance(Duration::hours(1));
+        notifier.notify_order_ready(&ready).unwrap();
+        assert_eq!(read_notification_records(&path).unwrap().len(), 4);
+        assert_eq!(notifier.remembered(), 1);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_failed_sends_are_not_remembered() {
+        let missing_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let notifier =
+            DedupNotifier::new(FileNotifier::new(missing_dir.join("n.jsonl")), Duration::hours(1));
+        let order = Order::single(Customer::named("Ada"), Coffee::medium());
+        let ready = OrderReadyNotice::from_order(&order);
+
+        assert!(notifier.notify_order_ready(&ready).is_err());
+        assert_eq!(notifier.remembered(), 0);
+    }
+}
@@ -0,0 +1,87 @@
+// SOLID: Logging - one line per port call
+//
+// SRP: the wrapped adapter keeps doing its job; this only reports what
+// happened. Lines go to stderr by default, or to any sink (a test buffer,
+// a log file, a TUI pane).
+//
+// Error messages are passed through `redact_pans` first: a gateway that
+// echoes a card number in its error doesn't get it into the log.
+
+use super::around::{Around, CallError};
+use crate::domain::redact_pans;
+use std::sync::Arc;
+
+/// Where log lines go
+pub type LogSink = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Logs "[label] operation ok" / "[label] operation failed: error"
+pub struct Logging {
+    label: String,
+    sink: LogSink,
+}
+
+impl Logging {
+    /// Log to stderr
+    pub fn new(label: &str) -> Self {
+        Self::with_sink(label, |line| eprintln!("{}", line))
+    }
+
+    /// Log to `sink`
+    pub fn with_sink(label: &str, sink: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Self {
+            label: label.to_string(),
+            sink: Arc::new(sink),
+        }
+    }
+}
+
+impl Around for Logging {
+    fn around<T, E: CallError>(
+        &self,
+        operation: &'static str,
+        mut call: impl FnMut() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let result = call();
+        match &result {
+            Ok(_) => (self.sink)(&format!("[{}] {} ok", self.label, operation)),
+            Err(e) => {
+                let error = redact_pans(&e.to_string());
+                (self.sink)(&format!("[{}] {} failed: {}", self.label, operation, error))
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::RepositoryError;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_logs_success_and_failure() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let captured = lines.clone();
+        let logging = Logging::with_sink("orders", move |line| {
+            captured.lock().unwrap().push(line.to_string())
+        });
+
+        let _ = logging.around("repository.save", || Ok::<_, RepositoryError>(()));
+        let _ = logging.around("repository.update", || {
+            Err::<(), _>(RepositoryError::NotFound("Order 42".to_string()))
+        });
+        let _ = logging.around("repository.save", || {
+            Err::<(), _>(RepositoryError::SaveFailed("bad name 4111 1111 1111 1111".to_string()))
+        });
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "[orders] repository.save ok".to_string(),
+                "[orders] repository.update failed: Not found: Order 42".to_string(),
+                "[orders] repository.save failed: Save failed: bad name **** 1111".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+// SOLID: CallMetrics - call and error counters per port operation
+//
+// Like Timing, clones share the counters: keep a handle, move the
+// decorated adapter into the service, read the numbers later.
+
+use super::around::{Around, CallError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Counters for one operation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallCount {
+    pub calls: u64,
+    pub errors: u64,
+}
+
+/// Counts calls and failed calls
+#[derive(Debug, Clone, Default)]
+pub struct CallMetrics {
+    counts: Arc<Mutex<HashMap<&'static str, CallCount>>>,
+}
+
+impl CallMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counters for one operation (zero if never called)
+    pub fn get(&self, operation: &str) -> CallCount {
+        self.counts.lock().unwrap().get(operation).copied().unwrap_or_default()
+    }
+
+    /// Sum over every operation
+    pub fn total(&self) -> CallCount {
+        self.counts
+            .lock()
+            .unwrap()
+            .values()
+            .fold(CallCount::default(), |sum, count| CallCount {
+                calls: sum.calls + count.calls,
+                errors: sum.errors + count.errors,
+            })
+    }
+}
+
+impl Around for CallMetrics {
+    fn around<T, E: CallError>(
+        &self,
+        operation: &'static str,
+        mut call: impl FnMut() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let result = call();
+
+        let mut counts = self.counts.lock().unwrap();
+        let count = counts.entry(operation).or_default();
+        count.calls += 1;
+        if result.is_err() {
+            count.errors += 1;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::PaymentError;
+
+    #[test]
+    fn test_counts_calls_and_errors() {
+        let metrics = CallMetrics::new();
+
+        let _ = metrics.around("payment.process", || Ok::<_, PaymentError>("CASH-1".to_string()));
+        let _ = metrics.around("payment.process", || Err::<String, _>(PaymentError::InvalidCard));
+
+        assert_eq!(metrics.get("payment.process"), CallCount { calls: 2, errors: 1 });
+        assert_eq!(metrics.get("repository.save"), CallCount::default());
+        assert_eq!(metrics.total().calls, 2);
+    }
+}
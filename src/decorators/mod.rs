@@ -0,0 +1,67 @@
+// SOLID: Decorators - cross-cutting concerns by composition
+//
+// Logging, timing, retries, metrics and caching are needed around storage,
+// payments AND notifications. Putting them inside each adapter would give
+// every adapter several reasons to change (SRP) and duplicate the code.
+//
+// Instead each concern wraps a port and implements the same port:
+//
+// 1. OPEN-CLOSED PRINCIPLE (OCP):
+//    Adapters and OrderService are unchanged. Behavior is added by wrapping.
+//
+// 2. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    A decorated repository is still an OrderRepository with the same
+//    contract, so it plugs in wherever the bare one did.
+//
+// 3. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    Each decorator does one thing. Combine them with the Decorate builder.
+//
+// STRUCTURE:
+// - around: the Around policy trait and Decorated<T, A>, which applies a policy
+//   to OrderRepository, PaymentProcessor and Notifier alike
+// - logging, timing, metrics, retry: Around policies
+// - caching: CachedRepository (repositories only)
+// - replicated: ReplicatedRepository, writes to a primary and reads from
+//   replicas (repositories only)
+// - wal: WalRepository, a durable write-ahead log with replay (repositories only)
+// - audited: AuditedRepository, what each update changed (repositories only)
+// - dedup: DedupNotifier, the same notice sent once per TTL (notifiers only)
+// - quiet_hours: QuietHoursNotifier, customer notices held back at night
+//   (notifiers only)
+// - builder: Decorate, to stack them
+//
+// let timing = Timing::new();
+// let repository = Decorate::new(MemoryOrderRepository::new())
+//     .cached()
+//     .retried(3)
+//     .timed(&timing)
+//     .logged("orders")
+//     .build();
+// let service = OrderService::new(repository, payment, notifier);
+
+pub mod around;
+pub mod audited;
+pub mod builder;
+pub mod caching;
+pub mod dedup;
+pub mod logging;
+pub mod metrics;
+pub mod quiet_hours;
+pub mod replicated;
+pub mod retry;
+pub mod timing;
+pub mod wal;
+
+// Re-export for convenience
+pub use around::{Around, CallError, Decorated};
+pub use audited::{AuditEntry, AuditLog, AuditedRepository};
+pub use builder::Decorate;
+pub use caching::CachedRepository;
+pub use dedup::DedupNotifier;
+pub use logging::{LogSink, Logging};
+pub use metrics::{CallCount, CallMetrics};
+pub use quiet_hours::QuietHoursNotifier;
+pub use replicated::{ReadPreference, ReplicatedRepository};
+pub use retry::Retry;
+pub use timing::{OperationTiming, Timing};
+pub use wal::{ReplayMismatch, WalRepository};
@@ -0,0 +1,177 @@
+// SOLID: QuietHoursNotifier - no customer notices at night
+//
+// Wraps any Notifier. A customer notice arriving in the customer's quiet
+// hours (domain::QuietHoursPolicy, global or per customer) is written to a
+// NotificationOutbox instead of sent, with the time the window opens; the
+// DeferredNotificationJob sends it then. Outside quiet hours, and for staff
+// notices (SLA breaches, pending refunds, ops alerts) at any hour, it
+// forwards as is.
+//
+// "Now" comes from the Clock port, so tests move a FixedClock across the
+// window instead of waiting for the night.
+//
+// Like caching, it needs to understand the call (whose notice, when), so it
+// is a Notifier decorator rather than an Around policy. Give it a
+// SharedNotificationOutbox and keep a clone for the release job.
+
+use crate::adapters::SystemClock;
+use crate::domain::{
+    DeferredNotice, DeferredNotification, FeedbackRequestNotice, OpsAlertNotice,
+    OrderCancelledNotice, OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice,
+    QuietHoursPolicy, RefundPendingNotice, RewardGrantedNotice, SlaBreachNotice,
+};
+use crate::ports::{Clock, NotificationError, NotificationOutbox, Notifier};
+use std::sync::{Arc, Mutex};
+
+/// Defers customer notices that fall in quiet hours
+pub struct QuietHoursNotifier<N: Notifier, O: NotificationOutbox> {
+    inner: N,
+    policy: QuietHoursPolicy,
+    outbox: Mutex<O>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl<N: Notifier, O: NotificationOutbox> QuietHoursNotifier<N, O> {
+    pub fn new(inner: N, policy: QuietHoursPolicy, outbox: O) -> Self {
+        Self {
+            inner,
+            policy,
+            outbox: Mutex::new(outbox),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn into_inner(self) -> N {
+        self.inner
+    }
+
+    /// Send now, or park `notice` in the outbox until the window opens
+    fn customer(
+        &self,
+        notice: impl FnOnce() -> DeferredNotice,
+        email: &str,
+        send: impl FnOnce(&N) -> Result<(), NotificationError>,
+    ) -> Result<(), NotificationError> {
+        let now = self.clock.now();
+        let Some(deliver_at) = self.policy.defer_until(email, now) else {
+            return send(&self.inner);
+        };
+        let deferred = DeferredNotification::new(notice(), now, deliver_at);
+        self.outbox
+            .lock()
+            .map_err(|e| NotificationError::SendFailed(format!("Outbox lock poisoned: {}", e)))?
+            .defer(deferred)
+            .map_err(|e| NotificationError::SendFailed(format!("Could not defer: {}", e)))
+    }
+}
+
+impl<N: Notifier, O: NotificationOutbox> Notifier for QuietHoursNotifier<N, O> {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        self.customer(
+            || DeferredNotice::OrderPlaced(notice.clone()),
+            &notice.recipient.email,
+            |inner| inner.notify_order_placed(notice),
+        )
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        self.customer(
+            || DeferredNotice::OrderReady(notice.clone()),
+            &notice.recipient.email,
+            |inner| inner.notify_order_ready(notice),
+        )
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        self.customer(
+            || DeferredNotice::OrderCancelled(notice.clone()),
+            &notice.recipient.email,
+            |inner| inner.notify_order_cancelled(notice),
+        )
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        self.customer(
+            || DeferredNotice::OrderNoShow(notice.clone()),
+            &notice.recipient.email,
+            |inner| inner.notify_order_no_show(notice),
+        )
+    }
+
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        self.inner.notify_sla_breach(notice)
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        self.inner.notify_refund_pending(notice)
+    }
+
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        self.inner.notify_ops_alert(notice)
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        self.customer(
+            || DeferredNotice::FeedbackRequested(notice.clone()),
+            &notice.recipient.email,
+            |inner| inner.notify_feedback_requested(notice),
+        )
+    }
+
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        self.customer(
+            || DeferredNotice::RewardGranted(notice.clone()),
+            &notice.recipient.email,
+            |inner| inner.notify_reward_granted(notice),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        read_notification_records, FileNotifier, FixedClock, MemoryNotificationOutbox,
+        SharedNotificationOutbox,
+    };
+    use crate::domain::{Coffee, Customer, Order, QuietHours};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_customer_notices_wait_for_the_morning() {
+        let night = Utc.with_ymd_and_hms(2026, 10, 15, 23, 0, 0).unwrap();
+        let clock = Arc::new(FixedClock::new(night));
+        let outbox = SharedNotificationOutbox::new(MemoryNotificationOutbox::new());
+        let path = std::env::temp_dir().join(format!("quiet-{}.jsonl", uuid::Uuid::new_v4()));
+        let policy = QuietHoursPolicy::new().with_global(QuietHours::between(21, 8).unwrap());
+        let notifier =
+            QuietHoursNotifier::new(FileNotifier::new(path.clone()), policy, outbox.clone())
+                .with_clock(clock.clone());
+        let order = Order::single(Customer::named("Ada"), Coffee::medium());
+
+        notifier.notify_order_ready(&OrderReadyNotice::from_order(&order)).unwrap();
+        notifier.notify_sla_breach(&SlaBreachNotice::from_order(&order, night)).unwrap();
+
+        let waiting = outbox.pending().unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].deliver_at, Utc.with_ymd_and_hms(2026, 10, 16, 8, 0, 0).unwrap());
+        assert!(matches!(waiting[0].notice, DeferredNotice::OrderReady(_)));
+
+        clock.set(Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap());
+        notifier.notify_order_placed(&OrderPlacedNotice::from_order(&order)).unwrap();
+        assert_eq!(outbox.pending().unwrap().len(), 1);
+
+        let events: Vec<String> =
+            read_notification_records(&path).unwrap().into_iter().map(|r| r.event).collect();
+        assert_eq!(events, vec!["order.sla_breach", "order.placed"]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,294 @@
+// SOLID: ReplicatedRepository - one primary for writes, replicas for reads
+//
+// A primary/replica setup behind the same OrderRepository port. OrderService
+// still sees one repository; which copy answers is decided here.
+//
+// - Writes go to the primary only. Once it accepted them they are queued
+//   for every replica, the way a database ships its changes asynchronously.
+// - `catch_up` applies what is queued (call it from a timer, or use
+//   `with_synchronous_replication` to do it on every write). Until then a
+//   replica is "behind" by the number of writes it has not applied: its lag.
+// - Reads take turns over the replicas (round-robin), as the ReadPreference
+//   allows. `BoundedStaleness` only reads from a replica that is at most N
+//   writes behind, and falls back to the primary otherwise.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// With `ReadPreference::Primary` (or synchronous replication) this behaves
+// exactly like the primary. The other preferences deliberately trade
+// freshness for spreading the reads: a replica may not have an order the
+// primary just saved yet. That is the contract of a replica, made explicit.
+
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Where reads may be served from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadPreference {
+    /// Always the primary: never stale, no read is spread
+    Primary,
+    /// The next replica, however far behind it is (the primary if there is none)
+    #[default]
+    AnyReplica,
+    /// The next replica at most `max_lag` writes behind, else the primary
+    BoundedStaleness { max_lag: usize },
+}
+
+/// A write the primary accepted, waiting to reach the replicas
+#[derive(Debug, Clone)]
+enum Change {
+    Put(Box<Order>),
+    Delete(Uuid),
+}
+
+/// Routes writes to `primary` and reads to `replicas`
+pub struct ReplicatedRepository<P: OrderRepository, R: OrderRepository> {
+    primary: P,
+    replicas: Vec<R>,
+    preference: ReadPreference,
+    synchronous: bool,
+    /// Changes not yet applied by every replica
+    pending: Vec<Change>,
+    /// Per replica: how many of `pending` it has applied
+    applied: Vec<usize>,
+    next_replica: AtomicUsize,
+}
+
+impl<P: OrderRepository, R: OrderRepository> ReplicatedRepository<P, R> {
+    /// The replicas are expected to start as copies of the primary
+    pub fn new(primary: P, replicas: Vec<R>) -> Self {
+        let applied = vec![0; replicas.len()];
+        Self {
+            primary,
+            replicas,
+            preference: ReadPreference::default(),
+            synchronous: false,
+            pending: Vec::new(),
+            applied,
+            next_replica: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_read_preference(mut self, preference: ReadPreference) -> Self {
+        self.preference = preference;
+        self
+    }
+
+    /// Apply every write to the replicas before returning (no lag, slower writes)
+    pub fn with_synchronous_replication(mut self) -> Self {
+        self.synchronous = true;
+        self
+    }
+
+    /// Writes `replica` has not applied yet (None if there is no such replica)
+    pub fn lag(&self, replica: usize) -> Option<usize> {
+        self.applied.get(replica).map(|applied| self.pending.len() - applied)
+    }
+
+    /// Bring every replica up to date with the primary
+    ///
+    /// A replica that fails keeps its place and is retried on the next call;
+    /// the first error is returned once the others were served.
+    pub fn catch_up(&mut self) -> Result<(), RepositoryError> {
+        let mut first_error = None;
+        for (replica, applied) in self.replicas.iter_mut().zip(self.applied.iter_mut()) {
+            while let Some(change) = self.pending.get(*applied) {
+                if let Err(e) = apply(replica, change) {
+                    first_error.get_or_insert(e);
+                    break;
+                }
+                *applied += 1;
+            }
+        }
+
+        // Forget what every replica has
+        let done = self.applied.iter().copied().min().unwrap_or(self.pending.len());
+        self.pending.drain(..done);
+        for applied in &mut self.applied {
+            *applied -= done;
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    pub fn replicas(&self) -> &[R] {
+        &self.replicas
+    }
+
+    /// Queue what the primary accepted for the replicas
+    fn replicate(&mut self, changes: impl IntoIterator<Item = Change>) {
+        self.pending.extend(changes);
+        if self.synchronous
+            && let Err(e) = self.catch_up()
+        {
+            eprintln!("Warning: A replica is behind the primary: {}", e);
+        }
+    }
+
+    /// The repository the next read goes to
+    fn reader(&self) -> &dyn OrderRepository {
+        let max_lag = match self.preference {
+            ReadPreference::Primary => return &self.primary,
+            ReadPreference::AnyReplica => usize::MAX,
+            ReadPreference::BoundedStaleness { max_lag } => max_lag,
+        };
+
+        let count = self.replicas.len();
+        let start = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&index| self.lag(index).is_some_and(|lag| lag <= max_lag))
+            .map_or(&self.primary as &dyn OrderRepository, |index| &self.replicas[index])
+    }
+}
+
+/// Replay one change on a replica (upsert, so applying it twice is harmless)
+fn apply<R: OrderRepository>(replica: &mut R, change: &Change) -> Result<(), RepositoryError> {
+    match change {
+        Change::Put(order) if replica.find_by_id(order.id)?.is_some() => replica.update(order),
+        Change::Put(order) => replica.save(order),
+        Change::Delete(id) => replica.delete(*id).map(|_| ()),
+    }
+}
+
+impl<P: OrderRepository, R: OrderRepository> OrderRepository for ReplicatedRepository<P, R> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.primary.save(order)?;
+        self.replicate([Change::Put(Box::new(order.clone()))]);
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        self.reader().find_by_id(id)
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.reader().find_by_customer_email(email)
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.reader().list_all()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.primary.update(order)?;
+        self.replicate([Change::Put(Box::new(order.clone()))]);
+        Ok(())
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        let existed = self.primary.delete(id)?;
+        if existed {
+            self.replicate([Change::Delete(id)]);
+        }
+        Ok(existed)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        self.primary.save_batch(orders)?;
+        self.replicate(orders.iter().map(|order| Change::Put(Box::new(order.clone()))));
+        Ok(())
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.reader().list_all_shared()
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.reader().find_by_customer_email_shared(email)
+    }
+}
+
+/// What matters for writes is what the primary can do
+impl<P, R> RepositoryCapabilities for ReplicatedRepository<P, R>
+where
+    P: OrderRepository + RepositoryCapabilities,
+    R: OrderRepository,
+{
+    fn supports_transactions(&self) -> bool {
+        self.primary.supports_transactions()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.primary.supports_streaming()
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        self.primary.max_batch_size()
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        self.primary.full_text_search(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::decorators::{CallMetrics, Decorate};
+    use crate::domain::{Coffee, Customer};
+
+    fn make_order() -> Order {
+        Order::single(Customer::named("Ada"), Coffee::medium())
+    }
+
+    #[test]
+    fn test_reads_rotate_over_replicas_which_lag_until_caught_up() {
+        let metrics = [CallMetrics::new(), CallMetrics::new()];
+        let replicas = metrics
+            .iter()
+            .map(|m| Decorate::new(MemoryOrderRepository::new()).metered(m).build())
+            .collect();
+        let mut repo = ReplicatedRepository::new(MemoryOrderRepository::new(), replicas);
+        let order = make_order();
+
+        repo.save(&order).unwrap();
+        assert_eq!(repo.lag(0), Some(1));
+        assert!(repo.find_by_id(order.id).unwrap().is_none());
+        assert_eq!(repo.primary().list_all().unwrap().len(), 1);
+
+        repo.catch_up().unwrap();
+        assert_eq!(repo.lag(1), Some(0));
+        let reads = || metrics.iter().map(|m| m.get("repository.find_by_id").calls);
+        let before: Vec<u64> = reads().collect();
+        for _ in 0..4 {
+            assert!(repo.find_by_id(order.id).unwrap().is_some());
+        }
+
+        // Taking turns: two reads each
+        let after: Vec<u64> = reads().collect();
+        assert_eq!([after[0] - before[0], after[1] - before[1]], [2, 2]);
+    }
+
+    #[test]
+    fn test_bounded_staleness_falls_back_to_the_primary() {
+        let replicas = vec![MemoryOrderRepository::new()];
+        let mut repo = ReplicatedRepository::new(MemoryOrderRepository::new(), replicas)
+            .with_read_preference(ReadPreference::BoundedStaleness { max_lag: 1 });
+        let (first, second) = (make_order(), make_order());
+
+        repo.save(&first).unwrap();
+        assert!(repo.find_by_id(first.id).unwrap().is_none());
+        repo.save(&second).unwrap();
+        // Two writes behind: too stale, the primary answers
+        assert!(repo.find_by_id(first.id).unwrap().is_some());
+
+        let replicas = vec![MemoryOrderRepository::new()];
+        let mut repo = ReplicatedRepository::new(MemoryOrderRepository::new(), replicas)
+            .with_synchronous_replication();
+        repo.save(&first).unwrap();
+        repo.delete(first.id).unwrap();
+        assert_eq!(repo.lag(0), Some(0));
+        assert!(repo.replicas()[0].list_all().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+// SOLID: Retry - try again when a call fails for a transient reason
+//
+// Only errors whose `is_transient()` is true are retried: a network error
+// is, an AlreadyExists or an InvalidCard never is.
+//
+// Retrying a payment is only safe because processors must be idempotent
+// (see PaymentRequest::idempotency_key): the same request twice is one charge.
+
+use super::around::{Around, CallError};
+use std::time::Duration;
+
+/// Up to `max_attempts` calls, with exponential backoff between them
+#[derive(Debug, Clone)]
+pub struct Retry {
+    max_attempts: u32,
+    backoff: Duration,
+}
+
+impl Retry {
+    /// `max_attempts` includes the first call (1 = no retry)
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff: Duration::from_millis(50),
+        }
+    }
+
+    /// Wait `backoff` before the first retry, doubling each time
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+impl Around for Retry {
+    fn around<T, E: CallError>(
+        &self,
+        _operation: &'static str,
+        mut call: impl FnMut() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut delay = self.backoff;
+        let mut attempt = 1;
+
+        loop {
+            match call() {
+                Err(e) if e.is_transient() && attempt < self.max_attempts => {
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::{NotificationError, RepositoryError};
+
+    #[test]
+    fn test_retries_transient_errors_until_success() {
+        let retry = Retry::new(3).with_backoff(Duration::ZERO);
+        let mut calls = 0;
+
+        let result = retry.around("notifier.order_ready", || {
+            calls += 1;
+            if calls < 3 {
+                Err(NotificationError::NetworkError("timeout".to_string()))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_gives_up_and_skips_permanent_errors() {
+        let retry = Retry::new(2).with_backoff(Duration::ZERO);
+
+        let mut calls = 0;
+        let result = retry.around("repository.save", || {
+            calls += 1;
+            Err::<(), _>(RepositoryError::SaveFailed("disk full".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        let _ = retry.around("repository.save", || {
+            calls += 1;
+            Err::<(), _>(RepositoryError::AlreadyExists("Order 1".to_string()))
+        });
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,103 @@
+// SOLID: Timing - how long each port call takes
+//
+// Clones of a Timing share the same statistics, so keep one handle to read
+// them after moving the decorated adapter into a service.
+
+use super::around::{Around, CallError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Accumulated durations for one operation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OperationTiming {
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl OperationTiming {
+    pub fn average(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.calls as u32
+        }
+    }
+}
+
+/// Measures every call, failed ones included
+#[derive(Debug, Clone, Default)]
+pub struct Timing {
+    stats: Arc<Mutex<HashMap<&'static str, OperationTiming>>>,
+}
+
+impl Timing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Statistics for one operation ("repository.save", ...)
+    pub fn get(&self, operation: &str) -> Option<OperationTiming> {
+        self.stats.lock().unwrap().get(operation).copied()
+    }
+
+    /// Every operation seen so far, sorted by name
+    pub fn snapshot(&self) -> Vec<(&'static str, OperationTiming)> {
+        let mut all: Vec<_> = self
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(operation, timing)| (*operation, *timing))
+            .collect();
+        all.sort_by_key(|(operation, _)| *operation);
+        all
+    }
+}
+
+impl Around for Timing {
+    fn around<T, E: CallError>(
+        &self,
+        operation: &'static str,
+        mut call: impl FnMut() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let started = Instant::now();
+        let result = call();
+        let elapsed = started.elapsed();
+
+        let mut stats = self.stats.lock().unwrap();
+        let timing = stats.entry(operation).or_default();
+        timing.calls += 1;
+        timing.total += elapsed;
+        timing.max = timing.max.max(elapsed);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::RepositoryError;
+
+    #[test]
+    fn test_records_calls_per_operation() {
+        let timing = Timing::new();
+        let handle = timing.clone();
+
+        for _ in 0..2 {
+            let _ = timing.around("repository.save", || {
+                std::thread::sleep(Duration::from_millis(2));
+                Ok::<_, RepositoryError>(())
+            });
+        }
+        let _ = timing.around("repository.list_all", || Ok::<_, RepositoryError>(()));
+
+        let save = handle.get("repository.save").unwrap();
+        assert_eq!(save.calls, 2);
+        assert!(save.max >= Duration::from_millis(2));
+        assert!(save.average() <= save.max);
+        assert_eq!(handle.snapshot()[0].0, "repository.list_all");
+    }
+}
@@ -0,0 +1,433 @@
+// SOLID: WalRepository - write-ahead log in front of any repository
+//
+// Durability as one more layer: every save, update, delete and batch is
+// appended to a log file and flushed to disk BEFORE the inner repository sees
+// it. If the inner backend dies halfway (a crashed process, a memory store, a
+// half-written JSON file), `replay` applies the log again and the orders are
+// back, whatever the backend was.
+//
+// LOG FORMAT (JSON Lines, one record per line):
+//
+//   {"seq":1,"op":"save","order":{...}}
+//   {"seq":2,"op":"delete","id":"..."}
+//   {"seq":3,"op":"abort","of":2}
+//
+// A write the inner repository refuses (AlreadyExists, NotFound, ...) is
+// followed by an `abort` record, so replay does not apply what never
+// happened. Replay upserts, therefore running it over a backend that already
+// has some of the orders is harmless. A torn last line (crash during the
+// append itself) is ignored: that write was never acknowledged.
+//
+// The log grows with every write. Once the inner backend is known to be on
+// disk, `checkpoint` empties it.
+//
+// `verify` replays the log on paper and compares the result with the inner
+// repository (domain::order_diff): after a restore or a migration, it says
+// which logged orders the backend lost, kept after a delete, or holds in
+// another version. Orders the log never saw (before a checkpoint) are not
+// checked.
+
+use crate::domain::{order_diff, FieldChange, Order};
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// One mutation, as written to the log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Entry {
+    Save { order: Order },
+    Update { order: Order },
+    Delete { id: Uuid },
+    SaveBatch { orders: Vec<Order> },
+    /// The inner repository refused record `of`
+    Abort { of: u64 },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    seq: u64,
+    #[serde(flatten)]
+    entry: Entry,
+}
+
+/// Where the inner repository disagrees with the log
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayMismatch {
+    /// Logged, but not in the repository
+    Missing(Uuid),
+    /// Deleted in the log, still in the repository
+    NotDeleted(Uuid),
+    /// In the repository, in another version than the last one logged
+    Differs { order_id: Uuid, changes: Vec<FieldChange> },
+}
+
+/// Logs every write durably before passing it to `inner`
+pub struct WalRepository<R: OrderRepository> {
+    inner: R,
+    log_path: PathBuf,
+    log: File,
+    next_seq: u64,
+}
+
+impl<R: OrderRepository> WalRepository<R> {
+    /// Wrap `inner`, appending to the log at `log_path` (created if missing)
+    ///
+    /// Nothing is replayed here; call `replay` when `inner` may have lost
+    /// writes, or use `recover`.
+    pub fn new(inner: R, log_path: PathBuf) -> Result<Self, RepositoryError> {
+        let (records, complete) = read_log(&log_path)?;
+        let next_seq = records.last().map_or(1, |record| record.seq + 1);
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .and_then(|log| {
+                // Cut a torn last line, or the next record would be glued to it
+                log.set_len(complete)?;
+                Ok(log)
+            })
+            .map_err(|e| RepositoryError::LoadFailed(format!("Failed to open log: {}", e)))?;
+
+        Ok(Self { inner, log_path, log, next_seq })
+    }
+
+    /// `new`, then `replay`: rebuild `inner` from the log after a crash
+    pub fn recover(inner: R, log_path: PathBuf) -> Result<Self, RepositoryError> {
+        let mut wal = Self::new(inner, log_path)?;
+        wal.replay()?;
+        Ok(wal)
+    }
+
+    /// Apply every logged write that was not aborted to the inner repository
+    ///
+    /// Returns how many records were applied.
+    pub fn replay(&mut self) -> Result<usize, RepositoryError> {
+        let mut applied = 0;
+        for record in applied_records(&self.log_path)? {
+            match record.entry {
+                Entry::Save { order } | Entry::Update { order } => self.upsert(&order)?,
+                Entry::SaveBatch { orders } => {
+                    for order in &orders {
+                        self.upsert(order)?;
+                    }
+                }
+                Entry::Delete { id } => {
+                    self.inner.delete(id)?;
+                }
+                Entry::Abort { .. } => continue,
+            }
+            applied += 1;
+        }
+        Ok(applied)
+    }
+
+    /// Compare the inner repository with what replaying the log would give
+    ///
+    /// Nothing is written. An empty list means every logged order is there,
+    /// in its last logged version (changes list the repository's version
+    /// against the log's).
+    pub fn verify(&self) -> Result<Vec<ReplayMismatch>, RepositoryError> {
+        // Order ID -> its last logged version, None once deleted
+        let mut expected: BTreeMap<Uuid, Option<Order>> = BTreeMap::new();
+        for record in applied_records(&self.log_path)? {
+            match record.entry {
+                Entry::Save { order } | Entry::Update { order } => {
+                    expected.insert(order.id, Some(order));
+                }
+                Entry::SaveBatch { orders } => {
+                    for order in orders {
+                        expected.insert(order.id, Some(order));
+                    }
+                }
+                Entry::Delete { id } => {
+                    expected.insert(id, None);
+                }
+                Entry::Abort { .. } => {}
+            }
+        }
+
+        let mut mismatches = Vec::new();
+        for (id, logged) in expected {
+            match (logged, self.inner.find_by_id(id)?) {
+                (Some(_), None) => mismatches.push(ReplayMismatch::Missing(id)),
+                (None, Some(_)) => mismatches.push(ReplayMismatch::NotDeleted(id)),
+                (Some(logged), Some(stored)) => {
+                    let changes = order_diff(&logged, &stored);
+                    if !changes.is_empty() {
+                        mismatches.push(ReplayMismatch::Differs { order_id: id, changes });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Empty the log; only safe once the inner repository is durable itself
+    pub fn checkpoint(&mut self) -> Result<(), RepositoryError> {
+        self.log
+            .set_len(0)
+            .and_then(|_| self.log.sync_data())
+            .map_err(|e| RepositoryError::SaveFailed(format!("Failed to truncate log: {}", e)))
+    }
+
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn upsert(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        if self.inner.find_by_id(order.id)?.is_some() {
+            self.inner.update(order)
+        } else {
+            self.inner.save(order)
+        }
+    }
+
+    /// Append one record and wait until it is on disk
+    fn append(&mut self, entry: Entry) -> Result<u64, RepositoryError> {
+        let seq = self.next_seq;
+        let mut line = serde_json::to_string(&Record { seq, entry }).map_err(|e| {
+            RepositoryError::SaveFailed(format!("Failed to serialize log record: {}", e))
+        })?;
+        line.push('\n');
+
+        self.log
+            .write_all(line.as_bytes())
+            .and_then(|_| self.log.sync_data())
+            .map_err(|e| RepositoryError::SaveFailed(format!("Failed to write log: {}", e)))?;
+        self.next_seq += 1;
+        Ok(seq)
+    }
+
+    /// Log `entry`, run the write, and mark the record aborted if it failed
+    fn logged<T>(
+        &mut self,
+        entry: Entry,
+        write: impl FnOnce(&mut R) -> Result<T, RepositoryError>,
+    ) -> Result<T, RepositoryError> {
+        let seq = self.append(entry)?;
+        let result = write(&mut self.inner);
+        if result.is_err() {
+            // If this fails too, replay may apply a refused write: still the
+            // safer side for a durability log
+            let _ = self.append(Entry::Abort { of: seq });
+        }
+        result
+    }
+}
+
+/// The records of the log that were not aborted, in order
+fn applied_records(path: &Path) -> Result<Vec<Record>, RepositoryError> {
+    let (records, _) = read_log(path)?;
+    let aborted: HashSet<u64> = records
+        .iter()
+        .filter_map(|record| match record.entry {
+            Entry::Abort { of } => Some(of),
+            _ => None,
+        })
+        .collect();
+    Ok(records.into_iter().filter(|record| !aborted.contains(&record.seq)).collect())
+}
+
+/// Every complete record of the log (none if it doesn't exist), and the
+/// length in bytes of the lines they came from
+fn read_log(path: &Path) -> Result<(Vec<Record>, u64), RepositoryError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(e) => return Err(RepositoryError::LoadFailed(format!("Failed to read log: {}", e))),
+    };
+
+    // Only newline-terminated lines were fully written; what follows the last
+    // newline was torn by a crash during the append
+    let complete = contents.rfind('\n').map_or(0, |at| at + 1);
+    let mut records = Vec::new();
+    for (index, line) in contents[..complete].lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line).map_err(|e| {
+            RepositoryError::LoadFailed(format!("Corrupt log record on line {}: {}", index + 1, e))
+        })?;
+        records.push(record);
+    }
+    Ok((records, complete as u64))
+}
+
+impl<R: OrderRepository> OrderRepository for WalRepository<R> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.logged(Entry::Save { order: order.clone() }, |inner| inner.save(order))
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError> {
+        self.inner.find_by_id(id)
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        self.inner.find_by_customer_email(email)
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        self.inner.list_all()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.logged(Entry::Update { order: order.clone() }, |inner| inner.update(order))
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<bool, RepositoryError> {
+        self.logged(Entry::Delete { id }, |inner| inner.delete(id))
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        let entry = Entry::SaveBatch { orders: orders.to_vec() };
+        self.logged(entry, |inner| inner.save_batch(orders))
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.inner.list_all_shared()
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        self.inner.find_by_customer_email_shared(email)
+    }
+}
+
+impl<R: OrderRepository + RepositoryCapabilities> RepositoryCapabilities for WalRepository<R> {
+    fn supports_transactions(&self) -> bool {
+        self.inner.supports_transactions()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        self.inner.max_batch_size()
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        self.inner.full_text_search(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer, OrderStatus};
+
+    fn make_order() -> Order {
+        Order::single(Customer::named("Ada"), Coffee::medium())
+    }
+
+    fn temp_log() -> PathBuf {
+        std::env::temp_dir().join(format!("wal-{}.jsonl", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_replay_rebuilds_a_lost_backend() {
+        let log = temp_log();
+        let (mut kept, gone) = (make_order(), make_order());
+        let batch = vec![make_order(), make_order()];
+
+        let mut wal = WalRepository::new(MemoryOrderRepository::new(), log.clone()).unwrap();
+        wal.save(&kept).unwrap();
+        wal.save(&gone).unwrap();
+        wal.save_batch(&batch).unwrap();
+        kept.cancel().unwrap();
+        wal.update(&kept).unwrap();
+        wal.delete(gone.id).unwrap();
+        // The memory backend "crashes": everything it held is gone
+        drop(wal);
+
+        // A crash in the middle of an append leaves half a line
+        let mut file = OpenOptions::new().append(true).open(&log).unwrap();
+        file.write_all(br#"{"seq":99,"op":"sa"#).unwrap();
+
+        let wal = WalRepository::recover(MemoryOrderRepository::new(), log.clone()).unwrap();
+        assert_eq!(wal.list_all().unwrap().len(), 3);
+        assert_eq!(wal.find_by_id(kept.id).unwrap().unwrap().status, OrderStatus::Cancelled);
+        assert!(wal.find_by_id(gone.id).unwrap().is_none());
+
+        // Replaying over a backend that already has everything changes nothing
+        let mut wal = wal;
+        wal.replay().unwrap();
+        assert_eq!(wal.list_all().unwrap().len(), 3);
+
+        // The torn line was cut, so later records stay readable
+        wal.save(&make_order()).unwrap();
+        drop(wal);
+        let wal = WalRepository::recover(MemoryOrderRepository::new(), log.clone()).unwrap();
+        assert_eq!(wal.list_all().unwrap().len(), 4);
+        let _ = fs::remove_file(log);
+    }
+
+    #[test]
+    fn test_refused_writes_are_not_replayed() {
+        let log = temp_log();
+        let (order, never_saved) = (make_order(), make_order());
+
+        let mut wal = WalRepository::new(MemoryOrderRepository::new(), log.clone()).unwrap();
+        wal.save(&order).unwrap();
+        assert!(wal.save(&order).is_err());
+        assert!(wal.update(&never_saved).is_err());
+        drop(wal);
+
+        let mut wal = WalRepository::recover(MemoryOrderRepository::new(), log.clone()).unwrap();
+        assert_eq!(wal.list_all().unwrap().len(), 1);
+        assert!(wal.find_by_id(never_saved.id).unwrap().is_none());
+
+        wal.checkpoint().unwrap();
+        assert_eq!(wal.replay().unwrap(), 0);
+        let _ = fs::remove_file(log);
+    }
+
+    #[test]
+    fn test_verify_compares_the_backend_with_the_log() {
+        let log = temp_log();
+        let (mut changed, lost, deleted) = (make_order(), make_order(), make_order());
+
+        let mut wal = WalRepository::new(MemoryOrderRepository::new(), log.clone()).unwrap();
+        for order in [&changed, &lost, &deleted] {
+            wal.save(order).unwrap();
+        }
+        wal.delete(deleted.id).unwrap();
+        changed.mark_as_paid("CASH-1".to_string()).unwrap();
+        wal.update(&changed).unwrap();
+        assert!(wal.verify().unwrap().is_empty());
+
+        // A backend restored from an older backup
+        let mut restored = MemoryOrderRepository::new();
+        changed.status = OrderStatus::Pending;
+        restored.save(&changed).unwrap();
+        restored.save(&deleted).unwrap();
+        let wal = WalRepository::new(restored, log.clone()).unwrap();
+
+        let mismatches = wal.verify().unwrap();
+        assert_eq!(mismatches.len(), 3);
+        assert!(mismatches.contains(&ReplayMismatch::Missing(lost.id)));
+        assert!(mismatches.contains(&ReplayMismatch::NotDeleted(deleted.id)));
+        assert!(mismatches.contains(&ReplayMismatch::Differs {
+            order_id: changed.id,
+            changes: vec![FieldChange::StatusChanged {
+                from: OrderStatus::Paid,
+                to: OrderStatus::Pending,
+            }],
+        }));
+        let _ = fs::remove_file(log);
+    }
+}
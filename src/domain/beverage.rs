@@ -2,10 +2,19 @@
 // The Beverage trait is OPEN for extension (add new beverage types)
 // but CLOSED for modification (existing code doesn't change)
 
+use super::money::{Currency, Money};
+use chrono::Duration;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Prep time of a medium beverage that doesn't say how long it takes,
+/// also used for order lines stored before prep times were recorded
+pub const DEFAULT_PREP_SECONDS: u32 = 120;
 
 /// Size of a beverage
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Ordered by volume: Small < Medium < Large
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Size {
     Small,
     Medium,
@@ -22,6 +31,28 @@ impl Size {
             Size::Large => 1.2,
         }
     }
+
+    /// Prep time multiplier for size (bigger cups take longer to fill or blend)
+    pub fn prep_multiplier(&self) -> f64 {
+        match self {
+            Size::Small => 0.9,
+            Size::Medium => 1.0,
+            Size::Large => 1.25,
+        }
+    }
+
+    /// Scale a medium-size prep time to this size
+    pub fn scale_prep_time(&self, medium: Duration) -> Duration {
+        let millis = medium.num_milliseconds() as f64 * self.prep_multiplier();
+        Duration::milliseconds(millis.round() as i64)
+    }
+}
+
+/// One part of a beverage's price, as printed on an itemized receipt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceLine {
+    pub label: String,
+    pub amount: Money,
 }
 
 /// SOLID PRINCIPLE: Open-Closed Principle (OCP)
@@ -37,22 +68,64 @@ pub trait Beverage: std::fmt::Debug {
     /// Name of the beverage (e.g., "Espresso", "Green Tea")
     fn name(&self) -> String;
 
-    /// Base price before size adjustment
+    /// Base price before size adjustment, in major units of `currency()`
     fn base_price(&self) -> f64;
 
+    /// The part of `base_price()` due to customizations (extra shots, more fruit...)
+    ///
+    /// A price book replaces the rest, so "+2 shots" costs the same extra
+    /// whatever the book says a coffee costs.
+    fn price_adjustment(&self) -> f64 {
+        0.0
+    }
+
     /// Size of the beverage
     fn size(&self) -> Size;
 
-    /// Calculate final price including size
+    /// The currency the menu prices this beverage in
+    fn currency(&self) -> Currency {
+        Currency::default()
+    }
+
+    /// Calculate final price including size, to the cent
     /// This is a default implementation - beverages can override if needed
-    fn price(&self) -> f64 {
-        self.base_price() * self.size().price_multiplier()
+    fn price(&self) -> Money {
+        Money::from_major(self.base_price() * self.size().price_multiplier(), self.currency())
     }
 
     /// Description of the beverage
     fn description(&self) -> String {
         format!("{} ({:?})", self.name(), self.size())
     }
+
+    /// How long a medium one takes to make, before customizations
+    fn base_prep_time(&self) -> Duration {
+        Duration::seconds(DEFAULT_PREP_SECONDS as i64)
+    }
+
+    /// Extra time for customizations (extra shots, more fruit...)
+    fn prep_adjustment(&self) -> Duration {
+        Duration::zero()
+    }
+
+    /// How the price is made up, for itemized receipts
+    ///
+    /// One line by default. Pricing wrappers (see SurgePriced) append their
+    /// own line, so the customer sees exactly what was added and why.
+    fn price_lines(&self) -> Vec<PriceLine> {
+        vec![PriceLine {
+            label: self.description(),
+            amount: self.price(),
+        }]
+    }
+
+    /// Estimated time for one barista to make this beverage
+    ///
+    /// Same shape as `price()`: a base, customizations, then the size.
+    fn prep_time_estimate(&self) -> Duration {
+        self.size()
+            .scale_prep_time(self.base_prep_time() + self.prep_adjustment())
+    }
 }
 
 // ============================================================================
@@ -72,6 +145,32 @@ pub struct Coffee {
     pub extra_shots: u8,
 }
 
+impl Coffee {
+    pub fn small() -> Self {
+        Self::sized(Size::Small)
+    }
+
+    pub fn medium() -> Self {
+        Self::sized(Size::Medium)
+    }
+
+    pub fn large() -> Self {
+        Self::sized(Size::Large)
+    }
+
+    fn sized(size: Size) -> Self {
+        Self {
+            size,
+            extra_shots: 0,
+        }
+    }
+
+    pub fn with_extra_shots(mut self, extra_shots: u8) -> Self {
+        self.extra_shots = extra_shots;
+        self
+    }
+}
+
 impl Beverage for Coffee {
     fn name(&self) -> String {
         if self.extra_shots > 0 {
@@ -87,12 +186,25 @@ impl Beverage for Coffee {
 
     fn base_price(&self) -> f64 {
         // Base price + extra shots
-        3.50 + (self.extra_shots as f64 * 0.75)
+        3.50 + self.price_adjustment()
+    }
+
+    fn price_adjustment(&self) -> f64 {
+        self.extra_shots as f64 * 0.75
     }
 
     fn size(&self) -> Size {
         self.size
     }
+
+    fn base_prep_time(&self) -> Duration {
+        Duration::seconds(90)
+    }
+
+    fn prep_adjustment(&self) -> Duration {
+        // Each extra shot is another pull
+        Duration::seconds(20 * self.extra_shots as i64)
+    }
 }
 
 /// A tea beverage
@@ -103,7 +215,98 @@ impl Beverage for Coffee {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tea {
     pub size: Size,
-    pub variety: String, // "Green", "Black", "Herbal", etc.
+    pub variety: TeaVariety,
+}
+
+/// The kind of tea, which sets its name and price
+///
+/// Stored as its name ("Green", "Earl Grey"), like the free-form string it
+/// replaces: a name that isn't one of the varieties below loads as
+/// `Custom`, so no stored tea is ever refused. Parsing (`"green".parse()`)
+/// is the strict way in, for input that must be on the menu.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum TeaVariety {
+    Green,
+    Black,
+    Herbal,
+    EarlGrey,
+    Oolong,
+    /// Anything else, by name ("Rooibos")
+    Custom(String),
+}
+
+impl TeaVariety {
+    /// The varieties on the menu (every one but Custom)
+    pub const ALL: [TeaVariety; 5] = [
+        TeaVariety::Green,
+        TeaVariety::Black,
+        TeaVariety::Herbal,
+        TeaVariety::EarlGrey,
+        TeaVariety::Oolong,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            TeaVariety::Green => "Green",
+            TeaVariety::Black => "Black",
+            TeaVariety::Herbal => "Herbal",
+            TeaVariety::EarlGrey => "Earl Grey",
+            TeaVariety::Oolong => "Oolong",
+            TeaVariety::Custom(name) => name,
+        }
+    }
+
+    /// What the variety adds to the base tea price
+    pub fn premium(&self) -> f64 {
+        match self {
+            TeaVariety::Oolong => 0.50,
+            _ => 0.0,
+        }
+    }
+
+    /// One of ALL, whatever the case and spacing ("earl grey", "EarlGrey")
+    pub fn known(name: &str) -> Option<TeaVariety> {
+        let key = |name: &str| name.replace(' ', "").to_lowercase();
+        TeaVariety::ALL
+            .into_iter()
+            .find(|variety| key(variety.name()) == key(name))
+    }
+}
+
+impl fmt::Display for TeaVariety {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Lenient: a known variety, or Custom
+impl From<&str> for TeaVariety {
+    fn from(name: &str) -> Self {
+        TeaVariety::known(name).unwrap_or_else(|| TeaVariety::Custom(name.trim().to_string()))
+    }
+}
+
+impl From<String> for TeaVariety {
+    fn from(name: String) -> Self {
+        TeaVariety::from(name.as_str())
+    }
+}
+
+impl From<TeaVariety> for String {
+    fn from(variety: TeaVariety) -> Self {
+        variety.name().to_string()
+    }
+}
+
+impl Tea {
+    /// `Tea::new(TeaVariety::Green, Size::Large)`, or `Tea::new("Rooibos", Size::Small)`
+    pub fn new(variety: impl Into<TeaVariety>, size: Size) -> Self {
+        Self {
+            size,
+            variety: variety.into(),
+        }
+    }
 }
 
 impl Beverage for Tea {
@@ -112,12 +315,21 @@ impl Beverage for Tea {
     }
 
     fn base_price(&self) -> f64 {
-        2.50 // Tea is cheaper than coffee
+        2.50 + self.price_adjustment() // Tea is cheaper than coffee
+    }
+
+    fn price_adjustment(&self) -> f64 {
+        self.variety.premium()
     }
 
     fn size(&self) -> Size {
         self.size
     }
+
+    fn base_prep_time(&self) -> Duration {
+        // Mostly steeping
+        Duration::seconds(180)
+    }
 }
 
 /// A smoothie
@@ -130,6 +342,16 @@ pub struct Smoothie {
     pub fruits: Vec<String>,
 }
 
+impl Smoothie {
+    /// `Smoothie::new(&["Mango", "Banana"], Size::Medium)`
+    pub fn new(fruits: &[&str], size: Size) -> Self {
+        Self {
+            size,
+            fruits: fruits.iter().map(|fruit| fruit.to_string()).collect(),
+        }
+    }
+}
+
 impl Beverage for Smoothie {
     fn name(&self) -> String {
         format!("Smoothie ({})", self.fruits.join(", "))
@@ -137,14 +359,27 @@ impl Beverage for Smoothie {
 
     fn base_price(&self) -> f64 {
         // Base price + extra for each fruit beyond the first
-        let base = 5.00;
+        5.00 + self.price_adjustment()
+    }
+
+    fn price_adjustment(&self) -> f64 {
         let fruit_count = self.fruits.len().max(1) - 1;
-        base + (fruit_count as f64 * 0.50)
+        fruit_count as f64 * 0.50
     }
 
     fn size(&self) -> Size {
         self.size
     }
+
+    fn base_prep_time(&self) -> Duration {
+        Duration::seconds(150)
+    }
+
+    fn prep_adjustment(&self) -> Duration {
+        // Cutting each extra fruit
+        let fruit_count = self.fruits.len().max(1) - 1;
+        Duration::seconds(15 * fruit_count as i64)
+    }
 }
 
 // ============================================================================
@@ -175,7 +410,7 @@ mod tests {
         };
 
         // Base: 3.50, Medium multiplier: 1.0
-        assert_eq!(coffee.price(), 3.50);
+        assert_eq!(coffee.price(), Money::cents(350));
     }
 
     #[test]
@@ -186,18 +421,35 @@ mod tests {
         };
 
         // Base: 3.50 + (2 * 0.75) = 5.00, Medium: 1.0
-        assert!((coffee.price() - 5.00).abs() < 0.01);
+        assert_eq!(coffee.price(), Money::usd(5.00));
     }
 
     #[test]
     fn test_tea_price() {
         let tea = Tea {
             size: Size::Large,
-            variety: "Green".to_string(),
+            variety: TeaVariety::Green,
         };
 
         // Base: 2.50, Large multiplier: 1.2
-        assert!((tea.price() - 3.0).abs() < 0.01);
+        assert_eq!(tea.price(), Money::usd(3.00));
+
+        // Oolong: 2.50 + 0.50, Medium: 1.0
+        let oolong = Tea::new(TeaVariety::Oolong, Size::Medium);
+        assert_eq!(oolong.price(), Money::usd(3.00));
+        assert_eq!(oolong.description(), "Oolong Tea (Medium)");
+    }
+
+    #[test]
+    fn test_tea_variety_reads_stored_names() {
+        let stored = r#"[{"size":"Small","variety":"Earl Grey"},{"size":"Large","variety":"Rooibos"}]"#;
+        let teas: Vec<Tea> = serde_json::from_str(stored).unwrap();
+
+        assert_eq!(teas[0].variety, TeaVariety::EarlGrey);
+        assert_eq!(teas[1].variety, TeaVariety::Custom("Rooibos".to_string()));
+        assert_eq!(teas[1].name(), "Rooibos Tea");
+        assert_eq!(serde_json::to_string(&teas).unwrap(), stored);
+        assert_eq!(TeaVariety::from("earlgrey"), TeaVariety::EarlGrey);
     }
 
     #[test]
@@ -208,7 +460,7 @@ mod tests {
         };
 
         // Base: 5.00 + 0.50 for extra fruit = 5.50, Medium: 1.0
-        assert!((smoothie.price() - 5.50).abs() < 0.01);
+        assert_eq!(smoothie.price(), Money::usd(5.50));
     }
 
     #[test]
@@ -219,6 +471,19 @@ mod tests {
         };
 
         // Small: 3.50 * 0.8 = 2.80
-        assert!((coffee.price() - 2.80).abs() < 0.01);
+        assert_eq!(coffee.price(), Money::usd(2.80));
+    }
+
+    #[test]
+    fn test_prep_time_includes_customizations_and_size() {
+        let double = Coffee {
+            size: Size::Large,
+            extra_shots: 2,
+        };
+        // (90 + 2 * 20) * 1.25
+        assert_eq!(double.prep_time_estimate(), Duration::milliseconds(162_500));
+
+        let tea = Tea::new(TeaVariety::Green, Size::Medium);
+        assert_eq!(tea.prep_time_estimate(), Duration::seconds(180));
     }
 }
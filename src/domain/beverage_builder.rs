@@ -0,0 +1,217 @@
+// SOLID: This module is part of the DOMAIN layer
+// Builders for the beverages, for input that comes from outside the code
+// (the interactive menu, a form, a file): the struct literals accept any
+// number of shots, an empty fruit list or any Custom tea; a builder refuses
+// them with a BeverageError the caller can show.
+//
+// let coffee = Coffee::builder().size(Size::Large).extra_shots(2).build()?;
+// let tea = Tea::builder().variety("earl grey".parse()?).build()?;
+// let smoothie = Smoothie::builder().fruit("Mango").fruit("Banana").build()?;
+//
+// The size defaults to Medium. The beverages themselves don't change, so
+// orders stored before the rules existed still load.
+
+use super::beverage::{Coffee, Size, Smoothie, Tea, TeaVariety};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Most extra shots a coffee can have
+pub const MAX_EXTRA_SHOTS: u8 = 4;
+
+/// Why a beverage could not be built
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum BeverageError {
+    TooManyShots { requested: u8, max: u8 },
+    /// A smoothie without any (non-blank) fruit
+    NoFruit,
+    /// A tea built without choosing its variety
+    MissingVariety,
+    UnknownTeaVariety(String),
+}
+
+impl fmt::Display for BeverageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BeverageError::TooManyShots { requested, max } => {
+                write!(f, "{} extra shots requested, at most {}", requested, max)
+            }
+            BeverageError::NoFruit => write!(f, "A smoothie needs at least one fruit"),
+            BeverageError::MissingVariety => write!(f, "Choose a tea variety"),
+            BeverageError::UnknownTeaVariety(name) => write!(
+                f,
+                "Unknown tea variety '{}' (known: {})",
+                name,
+                TeaVariety::ALL.map(|v| v.to_string()).join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for BeverageError {}
+
+/// Strict: only the varieties on the menu (see `TeaVariety::known`)
+impl FromStr for TeaVariety {
+    type Err = BeverageError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        TeaVariety::known(text)
+            .ok_or_else(|| BeverageError::UnknownTeaVariety(text.trim().to_string()))
+    }
+}
+
+/// Builds a Coffee with at most MAX_EXTRA_SHOTS extra shots
+#[derive(Debug, Clone)]
+pub struct CoffeeBuilder {
+    size: Size,
+    extra_shots: u8,
+}
+
+impl Coffee {
+    pub fn builder() -> CoffeeBuilder {
+        CoffeeBuilder {
+            size: Size::Medium,
+            extra_shots: 0,
+        }
+    }
+}
+
+impl CoffeeBuilder {
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn extra_shots(mut self, extra_shots: u8) -> Self {
+        self.extra_shots = extra_shots;
+        self
+    }
+
+    pub fn build(self) -> Result<Coffee, BeverageError> {
+        if self.extra_shots > MAX_EXTRA_SHOTS {
+            return Err(BeverageError::TooManyShots {
+                requested: self.extra_shots,
+                max: MAX_EXTRA_SHOTS,
+            });
+        }
+        Ok(Coffee {
+            size: self.size,
+            extra_shots: self.extra_shots,
+        })
+    }
+}
+
+/// Builds a Tea of a variety on the menu
+#[derive(Debug, Clone)]
+pub struct TeaBuilder {
+    size: Size,
+    variety: Option<TeaVariety>,
+}
+
+impl Tea {
+    pub fn builder() -> TeaBuilder {
+        TeaBuilder {
+            size: Size::Medium,
+            variety: None,
+        }
+    }
+}
+
+impl TeaBuilder {
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn variety(mut self, variety: TeaVariety) -> Self {
+        self.variety = Some(variety);
+        self
+    }
+
+    pub fn build(self) -> Result<Tea, BeverageError> {
+        match self.variety {
+            None => Err(BeverageError::MissingVariety),
+            Some(TeaVariety::Custom(name)) => Err(BeverageError::UnknownTeaVariety(name)),
+            Some(variety) => Ok(Tea::new(variety, self.size)),
+        }
+    }
+}
+
+/// Builds a Smoothie with at least one fruit
+#[derive(Debug, Clone)]
+pub struct SmoothieBuilder {
+    size: Size,
+    fruits: Vec<String>,
+}
+
+impl Smoothie {
+    pub fn builder() -> SmoothieBuilder {
+        SmoothieBuilder {
+            size: Size::Medium,
+            fruits: Vec::new(),
+        }
+    }
+}
+
+impl SmoothieBuilder {
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Add one fruit; blank names are ignored
+    pub fn fruit(mut self, fruit: &str) -> Self {
+        let fruit = fruit.trim();
+        if !fruit.is_empty() {
+            self.fruits.push(fruit.to_string());
+        }
+        self
+    }
+
+    pub fn build(self) -> Result<Smoothie, BeverageError> {
+        if self.fruits.is_empty() {
+            return Err(BeverageError::NoFruit);
+        }
+        Ok(Smoothie {
+            size: self.size,
+            fruits: self.fruits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Beverage;
+
+    #[test]
+    fn test_builders_enforce_the_rules() {
+        let coffee = Coffee::builder().size(Size::Large).extra_shots(4).build().unwrap();
+        assert_eq!((coffee.size, coffee.extra_shots), (Size::Large, 4));
+        assert_eq!(
+            Coffee::builder().extra_shots(5).build().unwrap_err(),
+            BeverageError::TooManyShots { requested: 5, max: 4 }
+        );
+
+        let smoothie = Smoothie::builder().fruit(" Mango ").fruit("").build().unwrap();
+        assert_eq!(smoothie.fruits, vec!["Mango"]);
+        assert_eq!(Smoothie::builder().fruit("  ").build().unwrap_err(), BeverageError::NoFruit);
+
+        assert_eq!(Tea::builder().build().unwrap_err(), BeverageError::MissingVariety);
+    }
+
+    #[test]
+    fn test_tea_varieties() {
+        let variety: TeaVariety = "earlgrey".parse().unwrap();
+        let tea = Tea::builder().variety(variety).size(Size::Small).build().unwrap();
+        assert_eq!(tea.name(), "Earl Grey Tea");
+        assert_eq!(" Green ".parse::<TeaVariety>().unwrap(), TeaVariety::Green);
+
+        let error = "Coffee".parse::<TeaVariety>().unwrap_err();
+        assert_eq!(error, BeverageError::UnknownTeaVariety("Coffee".to_string()));
+        assert!(error.to_string().contains("Green, Black, Herbal, Earl Grey, Oolong"));
+        let custom = Tea::builder().variety(TeaVariety::Custom("Rooibos".to_string())).build();
+        assert!(matches!(custom, Err(BeverageError::UnknownTeaVariety(_))));
+    }
+}
@@ -0,0 +1,119 @@
+// SOLID: This module is part of the DOMAIN layer
+// An announcement mailed to many customers at once (the new seasonal menu),
+// and where each recipient's copy stands.
+//
+// The subject and body are templates: `{{name}}` and `{{email}}` become the
+// recipient's. One OutboxEntry per recipient and campaign is what lets a
+// campaign that stopped halfway (crash, mail server down) pick up where it
+// was; sending them is CampaignService's job, keeping them a CampaignOutbox's.
+
+use super::Customer;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One announcement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Campaign {
+    /// Stable across runs ("autumn-menu-2026"): recipients are tracked by it
+    pub id: String,
+    pub subject: String,
+    /// Plain text, paragraphs separated by a blank line
+    pub body: String,
+}
+
+impl Campaign {
+    pub fn new(id: &str, subject: &str, body: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    pub fn subject_for(&self, customer: &Customer) -> String {
+        fill(&self.subject, customer)
+    }
+
+    pub fn body_for(&self, customer: &Customer) -> String {
+        fill(&self.body, customer)
+    }
+}
+
+fn fill(template: &str, customer: &Customer) -> String {
+    template
+        .replace("{{name}}", &customer.name)
+        .replace("{{email}}", &customer.email)
+}
+
+/// Where one recipient's copy stands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    /// Not tried yet
+    Pending,
+    Sent { at: DateTime<Utc> },
+    /// The last attempt failed; tried again on the next run
+    Failed { error: String },
+}
+
+impl fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeliveryStatus::Pending => write!(f, "Pending"),
+            DeliveryStatus::Sent { at } => write!(f, "Sent {}", at.format("%Y-%m-%d %H:%M")),
+            DeliveryStatus::Failed { error } => write!(f, "Failed: {}", error),
+        }
+    }
+}
+
+/// One recipient of one campaign
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub campaign_id: String,
+    pub recipient: Customer,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+}
+
+impl OutboxEntry {
+    pub fn new(campaign_id: &str, recipient: Customer) -> Self {
+        Self {
+            campaign_id: campaign_id.to_string(),
+            recipient,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+        }
+    }
+
+    /// Same campaign and same address (any case)
+    pub fn is_for(&self, campaign_id: &str, email: &str) -> bool {
+        self.campaign_id == campaign_id && self.recipient.email.eq_ignore_ascii_case(email)
+    }
+
+    pub fn is_sent(&self) -> bool {
+        matches!(self.status, DeliveryStatus::Sent { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_are_filled_per_recipient() {
+        let campaign = Campaign::new(
+            "autumn",
+            "{{name}}, the autumn menu is here",
+            "Hello {{name}},\n\nPumpkin spice is back.\n\nSent to {{email}}",
+        );
+        let ada = Customer::named("Ada");
+
+        assert_eq!(campaign.subject_for(&ada), "Ada, the autumn menu is here");
+        assert!(campaign.body_for(&ada).ends_with("Sent to ada@example.com"));
+
+        let entry = OutboxEntry::new("autumn", ada);
+        assert!(entry.is_for("autumn", "ADA@example.com"));
+        assert!(!entry.is_for("winter", "ada@example.com"));
+        assert!(!entry.is_sent());
+    }
+}
@@ -0,0 +1,259 @@
+// SOLID: This module is part of the DOMAIN layer
+// Card data the shop may hold, and nothing more (PCI DSS scope).
+//
+// A card number (PAN) never lives in a value of this crate:
+//
+// - CardToken: the gateway's reference to a card it keeps. Built from the
+//   gateway's response, and refused if it looks like a card number.
+//   Debug and Serialize print "[redacted]", so neither a log nor a file
+//   can carry it, and it can't be read back from one.
+// - MaskedPan: the last four digits, what receipts and logs may show.
+//   Masking a PAN keeps those four digits and drops the rest.
+//
+// `contains_pan` and `redact_pans` catch numbers that slip in as free text
+// (a customer name, a gateway's error message): the JSON and binary
+// repositories refuse to write them, the Logging decorator masks them.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Why card data was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardDataError {
+    /// 13 to 19 digits passing the Luhn check were expected
+    NotAPan,
+    /// A token (or a masked number) that is actually a card number
+    LooksLikeAPan,
+    InvalidToken(String),
+}
+
+impl fmt::Display for CardDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardDataError::NotAPan => write!(f, "Not a card number"),
+            CardDataError::LooksLikeAPan => write!(f, "Refusing to hold a card number"),
+            CardDataError::InvalidToken(msg) => write!(f, "Invalid card token: {}", msg),
+        }
+    }
+}
+
+impl Error for CardDataError {}
+
+/// The last four digits of a card: "**** 4242"
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct MaskedPan {
+    last4: String,
+}
+
+impl MaskedPan {
+    /// Mask a card number read from a card ("4242 4242 4242 4242");
+    /// only the last four digits are kept
+    pub fn from_pan(pan: &str) -> Result<Self, CardDataError> {
+        let digits: String = pan.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+        if !is_pan(&digits) {
+            return Err(CardDataError::NotAPan);
+        }
+        Ok(Self {
+            last4: digits[digits.len() - 4..].to_string(),
+        })
+    }
+
+    /// What a gateway or terminal reports ("4242")
+    pub fn from_last4(last4: &str) -> Result<Self, CardDataError> {
+        if last4.len() != 4 || !last4.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(CardDataError::InvalidToken(format!(
+                "'{}' is not four digits",
+                redact_pans(last4)
+            )));
+        }
+        Ok(Self {
+            last4: last4.to_string(),
+        })
+    }
+
+    pub fn last4(&self) -> &str {
+        &self.last4
+    }
+}
+
+impl fmt::Display for MaskedPan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "**** {}", self.last4)
+    }
+}
+
+impl fmt::Debug for MaskedPan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MaskedPan({})", self)
+    }
+}
+
+// Stored as printed, "**** 4242"; a full number is refused when read back
+impl Serialize for MaskedPan {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MaskedPan {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let last4 = text.trim().trim_start_matches('*').trim();
+        MaskedPan::from_last4(last4).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A gateway's reference to a card it keeps ("tok_1N3x...")
+///
+/// Only `expose` gives the token back, for the call to the gateway.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CardToken(String);
+
+impl CardToken {
+    pub fn new(token: &str) -> Result<Self, CardDataError> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(CardDataError::InvalidToken("empty".to_string()));
+        }
+        if contains_pan(token) {
+            return Err(CardDataError::LooksLikeAPan);
+        }
+        Ok(Self(token.to_string()))
+    }
+
+    /// The token itself, for the gateway call and nothing else
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for CardToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CardToken([redacted])")
+    }
+}
+
+// Never written out: there is no Deserialize to read one back either
+impl Serialize for CardToken {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("[redacted]")
+    }
+}
+
+/// Does `text` hold a card number (13-19 digits, spaces or dashes between, Luhn-valid)?
+pub fn contains_pan(text: &str) -> bool {
+    !pan_spans(text).is_empty()
+}
+
+/// `text` with every card number masked to "**** 4242"
+pub fn redact_pans(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, end, last4) in pan_spans(text) {
+        redacted.push_str(&text[copied..start]);
+        redacted.push_str(&format!("**** {}", last4));
+        copied = end;
+    }
+    redacted.push_str(&text[copied..]);
+    redacted
+}
+
+/// Byte ranges of card numbers in `text`, with their last four digits
+///
+/// A candidate is a run of digits not glued to letters or a decimal point
+/// (2.2500000000000003 is a price), either unbroken or in groups of 3 to 6
+/// split by single spaces or dashes, as cards are printed (4-4-4-4, 4-6-5).
+/// UUIDs never match: their all-digit runs are too short or include a group
+/// of 8 or 12.
+fn pan_spans(text: &str) -> Vec<(usize, usize, String)> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    let glue = |b: &u8| b.is_ascii_alphanumeric() || *b == b'.';
+    while i < bytes.len() {
+        let starts_run = bytes[i].is_ascii_digit() && (i == 0 || !glue(&bytes[i - 1]));
+        if !starts_run {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut digits = String::new();
+        let mut groups = vec![0];
+        let mut end = i;
+        while i < bytes.len() {
+            if bytes[i].is_ascii_digit() {
+                digits.push(bytes[i] as char);
+                *groups.last_mut().unwrap() += 1;
+                i += 1;
+                end = i;
+            } else if matches!(bytes[i], b' ' | b'-')
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            {
+                groups.push(0);
+                i += 1;
+            } else {
+                break;
+            }
+        }
+
+        let glued = bytes.get(end).is_some_and(glue);
+        let printed_like_a_card =
+            groups.len() == 1 || groups.iter().all(|g| (3..=6).contains(g));
+        if !glued && printed_like_a_card && is_pan(&digits) {
+            spans.push((start, end, digits[digits.len() - 4..].to_string()));
+        }
+    }
+    spans
+}
+
+/// 13 to 19 digits with a valid Luhn check digit
+fn is_pan(digits: &str) -> bool {
+    if !(13..=19).contains(&digits.len()) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .map(|b| (b - b'0') as u32)
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 1 { if d * 2 > 9 { d * 2 - 9 } else { d * 2 } } else { d })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_numbers_are_masked_and_never_kept() {
+        let masked = MaskedPan::from_pan("4242 4242 4242 4242").unwrap();
+        assert_eq!(masked.to_string(), "**** 4242");
+        assert_eq!(format!("{:?}", masked), "MaskedPan(**** 4242)");
+        assert_eq!(serde_json::to_string(&masked).unwrap(), r#""**** 4242""#);
+        assert_eq!(serde_json::from_str::<MaskedPan>(r#""**** 4242""#).unwrap(), masked);
+        assert!(serde_json::from_str::<MaskedPan>(r#""4242424242424242""#).is_err());
+        assert_eq!(MaskedPan::from_pan("4242 4242 4242 4241"), Err(CardDataError::NotAPan));
+
+        assert_eq!(CardToken::new("4242-4242-4242-4242"), Err(CardDataError::LooksLikeAPan));
+        let token = CardToken::new("tok_1N3xVisa").unwrap();
+        assert_eq!(format!("{:?}", token), "CardToken([redacted])");
+        assert_eq!(serde_json::to_string(&token).unwrap(), r#""[redacted]""#);
+        assert_eq!(token.expose(), "tok_1N3xVisa");
+    }
+
+    #[test]
+    fn test_pans_are_found_in_free_text_but_ids_are_not() {
+        assert_eq!(
+            redact_pans("Declined: card 5555-5555-5555-4444 expired"),
+            "Declined: card **** 4444 expired"
+        );
+        assert!(contains_pan("Ada 4111111111111111"));
+        assert!(!contains_pan("CC-0b5f4d2e-4242-4242-4242-424242424242"));
+        assert!(!contains_pan("CC-0b5f4d2e-4a42-4242-424242424242"));
+        assert!(!contains_pan("order 12345, 2026-10-15, $3.50"));
+        assert!(!contains_pan(r#"{"price":2.2500000000000003}"#));
+    }
+}
@@ -0,0 +1,41 @@
+// SOLID: This module is part of the DOMAIN layer
+// A chargeback: the cardholder disputed a charge with their bank, and the
+// gateway took the money back pending the outcome. The gateway reports it
+// against the payment ID it issued; which order that is, and what to freeze
+// meanwhile, is DisputeService's job.
+
+use super::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A gateway's notice that a charge is disputed
+///
+/// Deserializes from the gateway's webhook body; `received_at` defaults to
+/// the moment it is read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Chargeback {
+    /// The gateway's case reference: the same case delivered twice is one dispute
+    pub case_id: String,
+    /// The disputed charge, as the gateway returned it when paying
+    pub payment_id: String,
+    /// Taken back from the shop's settlement (a bare number is US dollars)
+    pub amount: Money,
+    /// The bank's reason code or text ("fraudulent", "product_not_received")
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default = "Utc::now")]
+    pub received_at: DateTime<Utc>,
+}
+
+impl Chargeback {
+    pub fn new(case_id: &str, payment_id: &str, amount: Money, reason: &str) -> Self {
+        Self {
+            case_id: case_id.to_string(),
+            payment_id: payment_id.to_string(),
+            amount,
+            reason: reason.to_string(),
+            received_at: Utc::now(),
+        }
+    }
+}
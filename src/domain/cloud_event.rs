@@ -0,0 +1,178 @@
+// SOLID: This module is part of the DOMAIN layer
+// A CloudEvents 1.0 envelope: the vendor-neutral way to say "this happened,
+// here, at this time, and here is the data", which serverless platforms and
+// event routers consume without a custom adapter.
+//
+// Only the envelope lives here. Wrapping a bus message (and checking a
+// parsed event is the message a consumer expects) is in bus::cloud_events;
+// writing it on a webhook is CloudEventsPayload.
+//
+// Attributes follow the spec's names (`specversion`, `type`, `time`...),
+// in structured JSON mode: https://github.com/cloudevents/spec
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+use super::webhook::WebhookEvent;
+
+/// The only CloudEvents version written and accepted
+pub const CLOUD_EVENTS_SPEC_VERSION: &str = "1.0";
+
+/// Why a CloudEvent could not be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloudEventError {
+    /// Not JSON, or not an object with the required attributes
+    Malformed(String),
+    UnsupportedVersion(String),
+    /// A required attribute is present but empty
+    MissingAttribute(&'static str),
+    /// The event is of another type than the caller expected
+    UnexpectedType { expected: String, found: String },
+    /// The data doesn't fit the expected type
+    InvalidData(String),
+}
+
+impl fmt::Display for CloudEventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CloudEventError::Malformed(msg) => write!(f, "Malformed CloudEvent: {}", msg),
+            CloudEventError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported CloudEvents version '{}'", version)
+            }
+            CloudEventError::MissingAttribute(name) => {
+                write!(f, "CloudEvent attribute '{}' is empty", name)
+            }
+            CloudEventError::UnexpectedType { expected, found } => {
+                write!(f, "Expected a '{}' event, got '{}'", expected, found)
+            }
+            CloudEventError::InvalidData(msg) => write!(f, "Invalid CloudEvent data: {}", msg),
+        }
+    }
+}
+
+impl Error for CloudEventError {}
+
+/// One event in a CloudEvents 1.0 envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CloudEvent {
+    pub specversion: String,
+    /// Unique per source: consumers drop duplicates by (source, id)
+    pub id: String,
+    /// Who emitted it, a URI reference such as "/shops/geneva"
+    pub source: String,
+    /// What happened: the bus topic ("order.placed")
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub data: Value,
+}
+
+impl CloudEvent {
+    /// An event of `event_type` from `source`, with JSON data
+    pub fn new(id: String, source: &str, event_type: &str, data: Value) -> Self {
+        Self {
+            specversion: CLOUD_EVENTS_SPEC_VERSION.to_string(),
+            id,
+            source: source.to_string(),
+            event_type: event_type.to_string(),
+            time: None,
+            datacontenttype: Some("application/json".to_string()),
+            data,
+        }
+    }
+
+    pub fn with_time(mut self, time: DateTime<Utc>) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Same id, topic, time and data as the webhook event
+    pub fn from_webhook_event(event: &WebhookEvent, source: &str) -> Self {
+        Self::new(event.id.to_string(), source, &event.topic, event.data.clone())
+            .with_time(event.occurred_at)
+    }
+
+    /// Structured-mode JSON
+    pub fn to_json(&self) -> Result<String, CloudEventError> {
+        serde_json::to_string(self).map_err(|e| CloudEventError::Malformed(e.to_string()))
+    }
+
+    /// Read a structured-mode JSON event, checking the required attributes
+    pub fn parse(json: &str) -> Result<Self, CloudEventError> {
+        let event: CloudEvent =
+            serde_json::from_str(json).map_err(|e| CloudEventError::Malformed(e.to_string()))?;
+
+        if event.specversion != CLOUD_EVENTS_SPEC_VERSION {
+            return Err(CloudEventError::UnsupportedVersion(event.specversion));
+        }
+        let required = [("id", &event.id), ("source", &event.source), ("type", &event.event_type)];
+        for (name, value) in required {
+            if value.is_empty() {
+                return Err(CloudEventError::MissingAttribute(name));
+            }
+        }
+        Ok(event)
+    }
+
+    /// The data as `T`, if the event is of `event_type`
+    pub fn data_as<T: DeserializeOwned>(&self, event_type: &str) -> Result<T, CloudEventError> {
+        if self.event_type != event_type {
+            return Err(CloudEventError::UnexpectedType {
+                expected: event_type.to_string(),
+                found: self.event_type.clone(),
+            });
+        }
+        serde_json::from_value(self.data.clone())
+            .map_err(|e| CloudEventError::InvalidData(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip_and_spec_names() {
+        let data = json!({ "n": 1 });
+        let event = CloudEvent::new("42".to_string(), "/shops/geneva", "order.ready", data);
+
+        let text = event.to_json().unwrap();
+        assert!(text.contains("\"type\":\"order.ready\""));
+        assert!(!text.contains("\"time\""));
+        assert_eq!(CloudEvent::parse(&text).unwrap(), event);
+        assert_eq!(event.data_as::<Value>("order.ready").unwrap()["n"], 1);
+        assert!(matches!(
+            event.data_as::<Value>("order.placed"),
+            Err(CloudEventError::UnexpectedType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_checks_required_attributes() {
+        let parse = |text: &str| CloudEvent::parse(text).unwrap_err();
+
+        assert!(matches!(parse("not json"), CloudEventError::Malformed(_)));
+        assert!(matches!(
+            parse(r#"{"specversion":"1.0","id":"1","source":"/s"}"#),
+            CloudEventError::Malformed(_)
+        ));
+        assert_eq!(
+            parse(r#"{"specversion":"0.3","id":"1","source":"/s","type":"t"}"#),
+            CloudEventError::UnsupportedVersion("0.3".to_string())
+        );
+        assert_eq!(
+            parse(r#"{"specversion":"1.0","id":"","source":"/s","type":"t"}"#),
+            CloudEventError::MissingAttribute("id")
+        );
+    }
+}
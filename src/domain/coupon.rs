@@ -0,0 +1,145 @@
+// SOLID: This module is part of the DOMAIN layer
+// A reward coupon: a percentage off one order, for one customer, until it
+// expires. Which occasions earn one is decided here, from the customer's
+// dates; granting them on time is RewardService's job, keeping them a
+// CouponStore's.
+
+use super::Customer;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What the coupon celebrates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Occasion {
+    Birthday,
+    /// Years since the customer joined the loyalty program
+    Anniversary { years: u32 },
+}
+
+impl Occasion {
+    /// The occasions a customer has on `date`
+    ///
+    /// February 29 falls on February 28 in other years, so nobody waits
+    /// four years for their birthday coupon.
+    pub fn falling_on(customer: &Customer, date: NaiveDate) -> Vec<Occasion> {
+        let mut occasions = Vec::new();
+        if let Some(birthday) = customer.birthday
+            && same_day_of_year(birthday, date)
+        {
+            occasions.push(Occasion::Birthday);
+        }
+        if let Some(joined) = customer.member_since
+            && same_day_of_year(joined, date)
+            && date.year() > joined.year()
+        {
+            let years = (date.year() - joined.year()) as u32;
+            occasions.push(Occasion::Anniversary { years });
+        }
+        occasions
+    }
+
+    /// What the customer is told ("Happy birthday", "1 year with us")
+    pub fn label(&self) -> String {
+        match self {
+            Occasion::Birthday => "Happy birthday".to_string(),
+            Occasion::Anniversary { years: 1 } => "1 year with us".to_string(),
+            Occasion::Anniversary { years } => format!("{} years with us", years),
+        }
+    }
+}
+
+fn same_day_of_year(anniversary: NaiveDate, date: NaiveDate) -> bool {
+    let leap_day = anniversary.month() == 2 && anniversary.day() == 29;
+    if leap_day && NaiveDate::from_ymd_opt(date.year(), 2, 29).is_none() {
+        return date.month() == 2 && date.day() == 28;
+    }
+    anniversary.month() == date.month() && anniversary.day() == date.day()
+}
+
+/// A percentage off one order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coupon {
+    pub id: Uuid,
+    /// What the customer types or shows at the counter ("GIFT-1A2B3C4D")
+    pub code: String,
+    /// Lowercased, like wallets: the coupon follows the email
+    pub customer_email: String,
+    pub occasion: Occasion,
+    /// The year of the occasion: one coupon per occasion per year
+    pub year: i32,
+    pub percent_off: f64,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub redeemed_at: Option<DateTime<Utc>>,
+    /// Held back while one of the customer's payments is disputed
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+impl Coupon {
+    pub fn new(
+        customer_email: &str,
+        occasion: Occasion,
+        percent_off: f64,
+        issued_at: DateTime<Utc>,
+        valid_for: Duration,
+    ) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            code: format!("GIFT-{}", id.simple().to_string()[..8].to_uppercase()),
+            customer_email: customer_email.to_lowercase(),
+            occasion,
+            year: issued_at.year(),
+            percent_off: percent_off.clamp(0.0, 100.0),
+            issued_at,
+            expires_at: issued_at + valid_for,
+            redeemed_at: None,
+            frozen: false,
+        }
+    }
+
+    /// Not used yet, not frozen, and not expired at `now`
+    pub fn is_redeemable(&self, now: DateTime<Utc>) -> bool {
+        self.redeemed_at.is_none() && !self.frozen && now < self.expires_at
+    }
+
+    /// Use the coupon; false (and nothing changes) if it can't be used
+    pub fn redeem(&mut self, now: DateTime<Utc>) -> bool {
+        if !self.is_redeemable(now) {
+            return false;
+        }
+        self.redeemed_at = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_occasions_falling_on_a_date() {
+        let ada = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None)
+            .with_birthday(date(1990, 3, 14))
+            .with_member_since(date(2023, 3, 14));
+
+        assert_eq!(
+            Occasion::falling_on(&ada, date(2026, 3, 14)),
+            [Occasion::Birthday, Occasion::Anniversary { years: 3 }]
+        );
+        // The day they joined is not an anniversary yet
+        assert_eq!(Occasion::falling_on(&ada, date(2023, 3, 14)), [Occasion::Birthday]);
+        assert!(Occasion::falling_on(&ada, date(2026, 3, 15)).is_empty());
+
+        let leap = Customer::new("Bo".to_string(), "bo@example.com".to_string(), None)
+            .with_birthday(date(2000, 2, 29));
+        assert_eq!(Occasion::falling_on(&leap, date(2026, 2, 28)), [Occasion::Birthday]);
+        assert!(Occasion::falling_on(&leap, date(2028, 2, 28)).is_empty());
+    }
+}
@@ -3,7 +3,9 @@
 // They have NO dependencies on infrastructure (databases, HTTP, etc.)
 // This follows the Dependency Inversion Principle (DIP)
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use uuid::Uuid;
 
 /// Represents a customer in our coffee shop
@@ -14,12 +16,20 @@ use uuid::Uuid;
 /// - Save itself to a database (that's the Repository's job)
 /// - Send itself notifications (that's the Notifier's job)
 /// - Calculate discounts (that's the PricingCalculator's job)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Customer {
     pub id: Uuid,
     pub name: String,
     pub email: String,
     pub phone: Option<String>,
+    /// Only the month and day are used (birthday rewards)
+    /// (`default` keeps customers saved before these fields existed loadable)
+    #[serde(default)]
+    pub birthday: Option<NaiveDate>,
+    /// When they joined the loyalty program (anniversary rewards)
+    #[serde(default)]
+    pub member_since: Option<NaiveDate>,
 }
 
 impl Customer {
@@ -34,8 +44,66 @@ impl Customer {
             name,
             email,
             phone,
+            birthday: None,
+            member_since: None,
         }
     }
+
+    pub fn with_birthday(mut self, birthday: NaiveDate) -> Self {
+        self.birthday = Some(birthday);
+        self
+    }
+
+    pub fn with_member_since(mut self, joined: NaiveDate) -> Self {
+        self.member_since = Some(joined);
+        self
+    }
+
+    /// A customer known by name only, for examples and exercises
+    ///
+    /// The email is made from the name on the reserved example.com domain:
+    /// `Customer::named("Ada Lovelace")` is ada.lovelace@example.com.
+    pub fn named(name: &str) -> Self {
+        let mut local = String::new();
+        for c in name.trim().chars() {
+            if c.is_alphanumeric() {
+                local.extend(c.to_lowercase());
+            } else if !local.is_empty() && !local.ends_with('.') {
+                local.push('.');
+            }
+        }
+        let local = local.trim_end_matches('.');
+        let local = if local.is_empty() { "customer" } else { local };
+        Self::new(name.trim().to_string(), format!("{}@example.com", local), None)
+    }
+
+    /// An anonymous walk-up customer (kiosk orders)
+    ///
+    /// The email is unique per guest and on a reserved domain, so guest
+    /// orders never merge into one "customer" in histories or reports.
+    pub fn guest() -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            name: "Guest".to_string(),
+            email: format!("guest-{}@kiosk.invalid", &id.simple().to_string()[..8]),
+            phone: None,
+            birthday: None,
+            member_since: None,
+        }
+    }
+
+    /// True for customers created by `Customer::guest`
+    pub fn is_guest(&self) -> bool {
+        self.email.ends_with("@kiosk.invalid")
+    }
+}
+
+/// "Ada Lovelace <ada@example.com>"
+impl fmt::Display for Customer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
 }
 
 #[cfg(test)]
@@ -53,4 +121,28 @@ mod tests {
         assert_eq!(customer.name, "Alice");
         assert_eq!(customer.email, "alice@example.com");
     }
+
+    #[test]
+    fn test_guests_are_distinct() {
+        let first = Customer::guest();
+        let second = Customer::guest();
+
+        assert!(first.is_guest());
+        assert_ne!(first.email, second.email);
+        assert!(!Customer::new("Ada".to_string(), "ada@example.com".to_string(), None).is_guest());
+    }
+
+    #[test]
+    fn test_display() {
+        let customer = Customer::named("Ada Lovelace");
+        assert_eq!(customer.to_string(), "Ada Lovelace <ada.lovelace@example.com>");
+    }
+
+    #[test]
+    fn test_named_customer_email() {
+        assert_eq!(Customer::named("Alice").email, "alice@example.com");
+        assert_eq!(Customer::named(" Ada  Lovelace ").email, "ada.lovelace@example.com");
+        assert_eq!(Customer::named("Customer 12").name, "Customer 12");
+        assert_eq!(Customer::named("!").email, "customer@example.com");
+    }
 }
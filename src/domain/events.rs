@@ -0,0 +1,131 @@
+// SOLID: This module is part of the DOMAIN layer
+// Domain events: what happened to an order, as facts other systems may act on.
+//
+// They are published through the EventPublisher port, for systems outside
+// the shop's code (analytics, a kitchen screen on another machine). The
+// message bus does the same job inside the process, with one type per topic;
+// here one enum carries the whole lifecycle, so a publisher forwards all of
+// it with a single method.
+//
+// Placed and Paid are separate facts: a counter order is placed unpaid and
+// paid at pickup, a redirect payment is placed before the customer approves.
+// An order paid on the spot is placed, then paid, a moment apart.
+
+use crate::domain::{Money, Order, OrderStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A change in an order's life, with when it happened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DomainEvent {
+    /// The order was accepted and saved, paid or not
+    OrderPlaced {
+        order_id: Uuid,
+        customer_email: String,
+        total_price: Money,
+        at: DateTime<Utc>,
+    },
+    /// The payment was taken
+    OrderPaid {
+        order_id: Uuid,
+        amount: Money,
+        payment_id: String,
+        at: DateTime<Utc>,
+    },
+    /// The order is waiting at the counter
+    OrderReady { order_id: Uuid, at: DateTime<Utc> },
+    /// The order was cancelled, and refunded if it had been paid
+    OrderCancelled {
+        order_id: Uuid,
+        refunded: bool,
+        at: DateTime<Utc>,
+    },
+}
+
+impl DomainEvent {
+    /// `order` was saved for the first time
+    pub fn placed(order: &Order) -> Self {
+        DomainEvent::OrderPlaced {
+            order_id: order.id,
+            customer_email: order.customer.email.clone(),
+            total_price: order.total_price,
+            at: order.created_at,
+        }
+    }
+
+    /// `order` was just marked Paid
+    pub fn paid(order: &Order) -> Self {
+        DomainEvent::OrderPaid {
+            order_id: order.id,
+            amount: order.amount_charged(),
+            payment_id: order.payment_id.clone().unwrap_or_default(),
+            at: order.status_since(),
+        }
+    }
+
+    /// `order` was just marked Ready
+    pub fn ready(order: &Order) -> Self {
+        DomainEvent::OrderReady {
+            order_id: order.id,
+            at: order.status_since(),
+        }
+    }
+
+    /// `order` was just cancelled or refunded
+    pub fn cancelled(order: &Order) -> Self {
+        DomainEvent::OrderCancelled {
+            order_id: order.id,
+            refunded: order.status == OrderStatus::Refunded,
+            at: order.status_since(),
+        }
+    }
+
+    /// The dotted topic name, as webhooks and the bus spell it: "order.paid"
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::OrderPlaced { .. } => "order.placed",
+            DomainEvent::OrderPaid { .. } => "order.paid",
+            DomainEvent::OrderReady { .. } => "order.ready",
+            DomainEvent::OrderCancelled { .. } => "order.cancelled",
+        }
+    }
+
+    pub fn order_id(&self) -> Uuid {
+        match self {
+            DomainEvent::OrderPlaced { order_id, .. }
+            | DomainEvent::OrderPaid { order_id, .. }
+            | DomainEvent::OrderReady { order_id, .. }
+            | DomainEvent::OrderCancelled { order_id, .. } => *order_id,
+        }
+    }
+
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            DomainEvent::OrderPlaced { at, .. }
+            | DomainEvent::OrderPaid { at, .. }
+            | DomainEvent::OrderReady { at, .. }
+            | DomainEvent::OrderCancelled { at, .. } => *at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_tagged_by_variant() {
+        let event = DomainEvent::OrderReady {
+            order_id: Uuid::nil(),
+            at: DateTime::UNIX_EPOCH,
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "OrderReady");
+        assert_eq!((event.name(), event.order_id()), ("order.ready", Uuid::nil()));
+        assert_eq!(serde_json::from_value::<DomainEvent>(json).unwrap(), event);
+    }
+}
@@ -0,0 +1,20 @@
+// SOLID: This module is part of the DOMAIN layer
+// A customer's rating of one order. Which orders may be rated, and what the
+// ratings add up to, is FeedbackService's job; storing them is a repository's.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Lowest and highest rating a customer can give
+pub const RATING_RANGE: std::ops::RangeInclusive<u8> = 1..=5;
+
+/// One rating, at most one per order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Feedback {
+    pub order_id: Uuid,
+    /// 1 (awful) to 5 (loved it)
+    pub rating: u8,
+    pub comment: Option<String>,
+    pub submitted_at: DateTime<Utc>,
+}
@@ -0,0 +1,78 @@
+// SOLID: This module is part of the DOMAIN layer
+// What the shop has on its shelves: one StockLevel per ingredient, counted in
+// the units CostModel's recipes use ("espresso shot", "milk (100 ml)").
+// Which ingredients an order takes is InventoryService's job; storing the
+// levels is an InventoryRepository's.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How much of one ingredient is on hand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StockLevel {
+    pub ingredient: String,
+    pub on_hand: f64,
+    /// At or below this, the ingredient is low: time to reorder
+    #[serde(default)]
+    pub reorder_at: f64,
+}
+
+impl StockLevel {
+    pub fn new(ingredient: &str, on_hand: f64) -> Self {
+        Self {
+            ingredient: ingredient.to_string(),
+            on_hand,
+            reorder_at: 0.0,
+        }
+    }
+
+    pub fn with_reorder_at(mut self, units: f64) -> Self {
+        self.reorder_at = units;
+        self
+    }
+
+    pub fn is_low(&self) -> bool {
+        self.on_hand <= self.reorder_at
+    }
+
+    /// Take `units` out (never below zero)
+    pub fn take(&mut self, units: f64) {
+        self.on_hand = (self.on_hand - units).max(0.0);
+    }
+
+    pub fn restock(&mut self, units: f64) {
+        self.on_hand += units;
+    }
+}
+
+/// An ingredient an order needs more of than the shop has
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortage {
+    pub ingredient: String,
+    pub needed: f64,
+    pub on_hand: f64,
+}
+
+impl fmt::Display for Shortage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({} needed, {} on hand)", self.ingredient, self.needed, self.on_hand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stock_never_goes_below_zero() {
+        let mut milk = StockLevel::new("milk (100 ml)", 3.0).with_reorder_at(2.0);
+        assert!(!milk.is_low());
+
+        milk.take(1.0);
+        assert!(milk.is_low());
+        milk.take(5.0);
+        assert_eq!(milk.on_hand, 0.0);
+        milk.restock(10.0);
+        assert_eq!(milk.on_hand, 10.0);
+    }
+}
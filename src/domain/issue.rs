@@ -0,0 +1,103 @@
+// SOLID: This module is part of the DOMAIN layer
+// A complaint about one order, from the moment it's reported at the counter
+// to the remedy the customer got. Carrying the remedy out (a refund, store
+// credit) is IssueService's job.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::money::Money;
+use super::order::Order;
+use super::staff::StaffMember;
+
+/// What went wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IssueKind {
+    WrongDrink,
+    ColdDrink,
+    MissingItem,
+    LongWait,
+    Other,
+}
+
+/// What the customer got to make up for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Remedy {
+    /// The drink is made again, at no charge
+    Remake,
+    /// Money back on the order, in its currency
+    Refund(Money),
+    /// Store credit for a later order
+    Credit(Money),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IssueStatus {
+    Open,
+    Resolved,
+}
+
+/// A complaint tied to an order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Issue {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    /// Who is owed the remedy (credit is kept per customer)
+    pub customer_email: String,
+    pub kind: IssueKind,
+    pub description: String,
+    pub reported_by: String,
+    pub reported_at: DateTime<Utc>,
+    pub status: IssueStatus,
+    pub remedy: Option<Remedy>,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// The refund request or credit entry behind the remedy, if any
+    pub reference: Option<String>,
+}
+
+impl Issue {
+    pub fn new(
+        order: &Order,
+        kind: IssueKind,
+        description: &str,
+        reported_by: &StaffMember,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            order_id: order.id,
+            customer_email: order.customer.email.clone(),
+            kind,
+            description: description.to_string(),
+            reported_by: reported_by.name.clone(),
+            reported_at: Utc::now(),
+            status: IssueStatus::Open,
+            remedy: None,
+            resolved_by: None,
+            resolved_at: None,
+            reference: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == IssueStatus::Open
+    }
+
+    /// The customer got `remedy`; an issue is only resolved once
+    pub fn resolve(
+        &mut self,
+        remedy: Remedy,
+        resolved_by: &StaffMember,
+        reference: Option<String>,
+    ) {
+        if !self.is_open() {
+            return;
+        }
+        self.status = IssueStatus::Resolved;
+        self.remedy = Some(remedy);
+        self.resolved_by = Some(resolved_by.name.clone());
+        self.resolved_at = Some(Utc::now());
+        self.reference = reference;
+    }
+}
@@ -0,0 +1,69 @@
+// SOLID: This module is part of the DOMAIN layer
+// A customer's loyalty account: the paid orders counted (tiers are earned by
+// order count) and the points they can still spend. How many points an order
+// earns and what a tier is worth is LoyaltyService's business, paying with
+// points is PointsPayment's, and where accounts are kept a LoyaltyRepository's.
+
+use serde::{Deserialize, Serialize};
+
+/// Loyalty points and order count of one customer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoyaltyAccount {
+    /// Lowercased, so "Ada@Example.com" and "ada@example.com" share an account
+    pub customer_email: String,
+    /// Points the customer can spend
+    pub points: u64,
+    /// Paid orders counted since joining
+    pub orders: u32,
+    /// Every point ever earned (spending doesn't lower it)
+    pub lifetime_points: u64,
+    /// On hold while one of the customer's payments is disputed: no spending
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+impl LoyaltyAccount {
+    pub fn new(customer_email: &str) -> Self {
+        Self {
+            customer_email: customer_email.to_lowercase(),
+            points: 0,
+            orders: 0,
+            lifetime_points: 0,
+            frozen: false,
+        }
+    }
+
+    /// One more paid order, earning `points`
+    pub fn earn(&mut self, points: u64) {
+        self.orders += 1;
+        self.points += points;
+        self.lifetime_points += points;
+    }
+
+    /// Take back `points` earned on a refunded order, and the order itself
+    /// when `whole_order` (it was refunded in full)
+    ///
+    /// Points already spent can't be taken back: the balance stops at zero.
+    pub fn reverse(&mut self, points: u64, whole_order: bool) {
+        if whole_order {
+            self.orders = self.orders.saturating_sub(1);
+        }
+        self.points = self.points.saturating_sub(points);
+        self.lifetime_points = self.lifetime_points.saturating_sub(points);
+    }
+
+    /// Spend up to `points`; returns how many were actually spent (none while frozen)
+    pub fn spend(&mut self, points: u64) -> u64 {
+        if self.frozen {
+            return 0;
+        }
+        let spent = points.min(self.points);
+        self.points -= spent;
+        spent
+    }
+
+    /// Give back points spent on a refunded payment
+    pub fn restore(&mut self, points: u64) {
+        self.points += points;
+    }
+}
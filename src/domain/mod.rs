@@ -11,10 +11,61 @@
 // - Can be understood without reading any other code
 
 pub mod beverage;
+pub mod beverage_builder;
+pub mod campaign;
+pub mod card;
+pub mod chargeback;
+pub mod cloud_event;
+pub mod coupon;
 pub mod customer;
+pub mod events;
+pub mod feedback;
+pub mod inventory;
+pub mod issue;
+pub mod loyalty;
+pub mod money;
+pub mod notice;
 pub mod order;
+pub mod order_diff;
+pub mod output_style;
+pub mod quiet_hours;
+pub mod refund;
+pub mod staff;
+pub mod translation;
+pub mod wallet;
+pub mod webhook;
 
 // Re-export commonly used types for convenience
-pub use beverage::{Beverage, Coffee, Size, Smoothie, Tea};
+pub use beverage::{Beverage, Coffee, PriceLine, Size, Smoothie, Tea, TeaVariety};
+pub use beverage_builder::{
+    BeverageError, CoffeeBuilder, SmoothieBuilder, TeaBuilder, MAX_EXTRA_SHOTS,
+};
+pub use campaign::{Campaign, DeliveryStatus, OutboxEntry};
+pub use card::{contains_pan, redact_pans, CardDataError, CardToken, MaskedPan};
+pub use chargeback::Chargeback;
+pub use cloud_event::{CloudEvent, CloudEventError, CLOUD_EVENTS_SPEC_VERSION};
+pub use coupon::{Coupon, Occasion};
 pub use customer::Customer;
-pub use order::{Order, OrderItem, OrderStatus};
+pub use events::DomainEvent;
+pub use feedback::{Feedback, RATING_RANGE};
+pub use inventory::{Shortage, StockLevel};
+pub use issue::{Issue, IssueKind, IssueStatus, Remedy};
+pub use loyalty::LoyaltyAccount;
+pub use money::{Currency, Money};
+pub use notice::{
+    AlertState, FeedbackRequestNotice, NoticeItem, NoticeRecipient, OpsAlertNotice,
+    OrderCancelledNotice, OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice,
+    RefundPendingNotice, RewardGrantedNotice, SlaBreachNotice,
+};
+pub use order::{
+    InvalidTransition, LinePricing, Order, OrderItem, OrderLine, OrderPricing, OrderStatus,
+    PriceBasis, StatusChange, VatLine,
+};
+pub use order_diff::{order_diff, FieldChange};
+pub use output_style::{plain_text, OutputStyle};
+pub use quiet_hours::{DeferredNotice, DeferredNotification, QuietHours, QuietHoursPolicy};
+pub use refund::{RefundRequest, RefundStatus};
+pub use staff::{Role, StaffMember};
+pub use translation::{Locale, LocalizedText, MenuTranslations};
+pub use wallet::{Wallet, WalletEntry, WalletEntryKind};
+pub use webhook::{WebhookEvent, WebhookSubscription};
@@ -0,0 +1,334 @@
+// SOLID: This module is part of the DOMAIN layer
+// Money: an amount in whole minor units (cents) and its currency.
+//
+// f64 can't hold 0.10 exactly: adding up lines, surcharges and rounding
+// drifts by fractions of a cent (0.10 + 0.20 = 0.30000000000000004). So every
+// amount is Money, in integer cents with its currency: Beverage::price,
+// Order::total_price and the order's surcharge and rounding, OrderItem::price,
+// PaymentRequest::amount and what PaymentProcessor charges and refunds, store
+// credit and its journal postings, chargebacks, refund limits and the day
+// close figures. Factors (a size multiplier, a VAT rate, a percentage off)
+// stay f64.
+//
+// Converting from f64 rounds to the nearest cent (halves away from zero).
+// Amounts in two currencies are never added: `+` and `-` panic on a
+// mismatch, `checked_add` returns None.
+//
+// Stored as `{"cents": 350, "currency": "USD"}`. Orders saved while amounts
+// were plain numbers (3.5) still load: a bare number is read as US dollars,
+// the only currency the shop charged in then.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// ISO 4217 code, three uppercase letters ("USD")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    /// The shop's currency unless configured otherwise (PaymentRequest::for_order)
+    pub const USD: Currency = Currency(*b"USD");
+    pub const EUR: Currency = Currency(*b"EUR");
+    pub const CHF: Currency = Currency(*b"CHF");
+
+    /// None unless `code` is three ASCII letters (any case)
+    pub fn new(code: &str) -> Option<Self> {
+        let bytes: [u8; 3] = code.as_bytes().try_into().ok()?;
+        bytes
+            .iter()
+            .all(u8::is_ascii_alphabetic)
+            .then(|| Currency(bytes.map(|b| b.to_ascii_uppercase())))
+    }
+
+    pub fn code(&self) -> &str {
+        // Only ASCII letters get in (see `new`)
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::USD
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Currency::new(code.trim()).ok_or_else(|| format!("'{}' is not a currency code", code))
+    }
+}
+
+// Stored as its code, "USD"
+impl Serialize for Currency {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// An amount of money, exact to the cent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Money {
+    /// Minor units: 350 is 3.50
+    pub cents: i64,
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub currency: Currency,
+}
+
+/// `{"cents": 350, "currency": "USD"}`, or a bare 3.5 from before Money (US dollars)
+///
+/// Binary formats (bincode) can't tell a number from a struct: they only
+/// ever read the struct.
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Money")]
+        struct Stored {
+            cents: i64,
+            #[serde(default)]
+            currency: Currency,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StoredOr {
+            Stored(Stored),
+            Major(f64),
+        }
+
+        let stored = if deserializer.is_human_readable() {
+            match StoredOr::deserialize(deserializer)? {
+                StoredOr::Stored(stored) => stored,
+                StoredOr::Major(amount) => return Ok(Money::usd(amount)),
+            }
+        } else {
+            Stored::deserialize(deserializer)?
+        };
+        Ok(Money::new(stored.cents, stored.currency))
+    }
+}
+
+impl Money {
+    pub fn new(cents: i64, currency: Currency) -> Self {
+        Self { cents, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Cents in US dollars, `Money::cents(350)`
+    pub fn cents(cents: i64) -> Self {
+        Self::new(cents, Currency::USD)
+    }
+
+    /// A major-unit amount (3.5) to the nearest cent
+    pub fn from_major(amount: f64, currency: Currency) -> Self {
+        Self::new((amount * 100.0).round() as i64, currency)
+    }
+
+    /// `Money::from_major` in US dollars: `Money::usd(3.5)`
+    pub fn usd(amount: f64) -> Self {
+        Self::from_major(amount, Currency::USD)
+    }
+
+    /// Back to major units for f64 fields and signatures (3.5)
+    pub fn to_major(self) -> f64 {
+        self.cents as f64 / 100.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.cents == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.cents < 0
+    }
+
+    /// `self * factor`, to the nearest cent (a 2% surcharge, a 10% discount)
+    pub fn scaled(self, factor: f64) -> Self {
+        Self::new((self.cents as f64 * factor).round() as i64, self.currency)
+    }
+
+    /// None if the currencies differ
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        (self.currency == other.currency)
+            .then(|| Self::new(self.cents + other.cents, self.currency))
+    }
+
+    /// None if the currencies differ
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.checked_add(-other)
+    }
+
+    /// Same currency, never below zero
+    pub fn saturating_sub(self, other: Money) -> Money {
+        let difference = self - other;
+        if difference.is_negative() {
+            Money::zero(self.currency)
+        } else {
+            difference
+        }
+    }
+
+    /// One sum per currency among `amounts`, in currency order (none for no amounts)
+    pub fn sum_by_currency(amounts: impl IntoIterator<Item = Money>) -> Vec<Money> {
+        let mut sums: BTreeMap<Currency, Money> = BTreeMap::new();
+        for amount in amounts {
+            *sums.entry(amount.currency).or_insert(Money::zero(amount.currency)) += amount;
+        }
+        sums.into_values().collect()
+    }
+
+    pub fn min(self, other: Money) -> Money {
+        if other < self { other } else { self }
+    }
+
+    pub fn max(self, other: Money) -> Money {
+        if other > self { other } else { self }
+    }
+
+    fn same_currency(self, other: Money, operation: &str) -> Currency {
+        assert_eq!(
+            self.currency, other.currency,
+            "cannot {} {} and {}",
+            operation, self.currency, other.currency
+        );
+        self.currency
+    }
+}
+
+impl Default for Money {
+    fn default() -> Self {
+        Money::zero(Currency::USD)
+    }
+}
+
+/// "3.50 USD", "-0.02 CHF"
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let cents = self.cents.unsigned_abs();
+        write!(f, "{}{}.{:02} {}", sign, cents / 100, cents % 100, self.currency)
+    }
+}
+
+/// Amounts compare within one currency only
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.cents.cmp(&other.cents))
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        let currency = self.same_currency(other, "add");
+        Money::new(self.cents + other.cents, currency)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        let currency = self.same_currency(other, "subtract");
+        Money::new(self.cents - other.cents, currency)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::new(-self.cents, self.currency)
+    }
+}
+
+/// A quantity of the same price: `unit_price * 3`
+impl Mul<u32> for Money {
+    type Output = Money;
+
+    fn mul(self, quantity: u32) -> Money {
+        Money::new(self.cents * quantity as i64, self.currency)
+    }
+}
+
+/// An empty sum is zero US dollars
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.reduce(|sum, amount| sum + amount).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cents_do_not_drift() {
+        let total: Money = [0.10, 0.20, 3.40].into_iter().map(Money::usd).sum();
+        assert_eq!(total, Money::cents(370));
+        assert_eq!(total.to_major(), 3.70);
+        assert_ne!(0.10 + 0.20, 0.30);
+
+        assert_eq!(Money::usd(3.85).scaled(0.02), Money::cents(8));
+        assert_eq!(Money::usd(3.50) * 3, Money::cents(1050));
+        assert_eq!(Money::cents(-2).to_string(), "-0.02 USD");
+        assert_eq!(Money::usd(1.0).saturating_sub(Money::usd(1.5)), Money::cents(0));
+    }
+
+    #[test]
+    fn test_currencies_do_not_mix() {
+        let francs = Money::new(500, "chf".parse().unwrap());
+        assert_eq!(francs.currency, Currency::CHF);
+        assert_eq!(francs.checked_add(Money::cents(100)), None);
+        assert_eq!(francs.partial_cmp(&Money::cents(100)), None);
+        assert_eq!(
+            Money::sum_by_currency([Money::cents(100), francs, Money::cents(50)]),
+            [francs, Money::cents(150)]
+        );
+        assert!(Currency::new("US").is_none() && Currency::new("U$D").is_none());
+
+        let json = serde_json::to_string(&francs).unwrap();
+        assert_eq!(json, r#"{"cents":500,"currency":"CHF"}"#);
+        assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), francs);
+        // Amounts stored before Money were dollars
+        assert_eq!(serde_json::from_str::<Money>("3.5").unwrap(), Money::cents(350));
+        let bytes = bincode::serialize(&francs).unwrap();
+        assert_eq!(bincode::deserialize::<Money>(&bytes).unwrap(), francs);
+    }
+}
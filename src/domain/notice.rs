@@ -0,0 +1,301 @@
+// SOLID: This module is part of the DOMAIN layer
+// Notices are what a Notifier receives: just the fields needed to tell
+// someone about an order event, never the whole Order.
+//
+// INTERFACE SEGREGATION PRINCIPLE (ISP), applied to data:
+// A chat channel doesn't need the customer's phone number or payment ID,
+// so it never gets them. Less PII travels to third-party channels, and a
+// change to Order's internals doesn't ripple into every notifier.
+//
+// Notices serialize with stable field names, so adapters that log or forward
+// them (FileNotifier, webhooks) produce the same JSON release after release.
+
+use crate::domain::{
+    Coupon, Customer, Locale, MenuTranslations, Money, Order, OrderStatus, RefundRequest,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Who the notice is about (and, for customer-facing channels, who to tell)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoticeRecipient {
+    pub name: String,
+    pub email: String,
+}
+
+impl NoticeRecipient {
+    fn of(order: &Order) -> Self {
+        Self {
+            name: order.customer.name.clone(),
+            email: order.customer.email.clone(),
+        }
+    }
+}
+
+/// One line of an order, as shown to people
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoticeItem {
+    pub description: String,
+    pub quantity: u8,
+}
+
+/// An order was paid and saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderPlacedNotice {
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub items: Vec<NoticeItem>,
+    pub total_price: Money,
+    pub placed_at: DateTime<Utc>,
+}
+
+impl OrderPlacedNotice {
+    pub fn from_order(order: &Order) -> Self {
+        Self {
+            order_id: order.id,
+            recipient: NoticeRecipient::of(order),
+            items: items_of(order),
+            total_price: order.total_price,
+            placed_at: order.status_since(),
+        }
+    }
+
+    /// Items named in `locale` (see MenuTranslations), for customers who read it
+    pub fn from_order_in(order: &Order, translations: &MenuTranslations, locale: &Locale) -> Self {
+        let mut notice = Self::from_order(order);
+        for (item, line) in notice.items.iter_mut().zip(&order.items) {
+            item.description =
+                translations.describe(&line.beverage_name, &line.beverage_description, locale);
+        }
+        notice
+    }
+}
+
+/// An order is waiting at the counter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderReadyNotice {
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub ready_at: DateTime<Utc>,
+}
+
+impl OrderReadyNotice {
+    pub fn from_order(order: &Order) -> Self {
+        Self {
+            order_id: order.id,
+            recipient: NoticeRecipient::of(order),
+            ready_at: order.status_since(),
+        }
+    }
+}
+
+/// An order was cancelled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderCancelledNotice {
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    /// What the order was worth (what a refund would be)
+    pub total_price: Money,
+    pub cancelled_at: DateTime<Utc>,
+}
+
+impl OrderCancelledNotice {
+    pub fn from_order(order: &Order) -> Self {
+        Self {
+            order_id: order.id,
+            recipient: NoticeRecipient::of(order),
+            total_price: order.total_price,
+            cancelled_at: order.status_since(),
+        }
+    }
+}
+
+/// A ready order was never picked up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderNoShowNotice {
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub flagged_at: DateTime<Utc>,
+}
+
+impl OrderNoShowNotice {
+    pub fn from_order(order: &Order) -> Self {
+        Self {
+            order_id: order.id,
+            recipient: NoticeRecipient::of(order),
+            flagged_at: order.status_since(),
+        }
+    }
+}
+
+/// An order has sat in one status past its SLA (for staff, not customers)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlaBreachNotice {
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub items: Vec<NoticeItem>,
+    pub stuck_in: OrderStatus,
+    pub since: DateTime<Utc>,
+    pub waited_minutes: i64,
+    pub flagged_at: DateTime<Utc>,
+}
+
+impl SlaBreachNotice {
+    pub fn from_order(order: &Order, now: DateTime<Utc>) -> Self {
+        let since = order.status_since();
+        Self {
+            order_id: order.id,
+            recipient: NoticeRecipient::of(order),
+            items: items_of(order),
+            stuck_in: order.status.clone(),
+            since,
+            waited_minutes: (now - since).num_minutes(),
+            flagged_at: now,
+        }
+    }
+}
+
+/// A refund above the approval limit is waiting for a manager (for staff)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundPendingNotice {
+    pub refund_id: Uuid,
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub amount: Money,
+    pub reason: String,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+impl RefundPendingNotice {
+    pub fn from_request(request: &RefundRequest, order: &Order) -> Self {
+        Self {
+            refund_id: request.id,
+            order_id: order.id,
+            recipient: NoticeRecipient::of(order),
+            amount: request.amount,
+            reason: request.reason.clone(),
+            requested_by: request.requested_by.clone(),
+            requested_at: request.requested_at,
+        }
+    }
+}
+
+/// Whether an alert started (or is still) firing, or has stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// An alert rule fired or resolved (for the ops channel, not customers)
+///
+/// Not about an order: `alert_id` identifies one episode, and its firing,
+/// reminders and resolution share it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpsAlertNotice {
+    pub alert_id: Uuid,
+    /// The rule's name: "payment-failures"
+    pub rule: String,
+    pub state: AlertState,
+    /// "Payment failure rate 32.0% > 20.0%"
+    pub summary: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// A collected order: ask the customer to rate it
+///
+/// `link` carries a token only valid for this order (see FeedbackService).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackRequestNotice {
+    pub order_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub link: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// A reward coupon was granted (birthday, anniversary)
+///
+/// Not about an order: the coupon's ID is what identifies the notice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RewardGrantedNotice {
+    pub coupon_id: Uuid,
+    pub recipient: NoticeRecipient,
+    pub code: String,
+    /// "Happy birthday", "2 years with us"
+    pub occasion: String,
+    pub percent_off: f64,
+    pub expires_at: DateTime<Utc>,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl RewardGrantedNotice {
+    pub fn from_coupon(coupon: &Coupon, customer: &Customer) -> Self {
+        Self {
+            coupon_id: coupon.id,
+            recipient: NoticeRecipient {
+                name: customer.name.clone(),
+                email: customer.email.clone(),
+            },
+            code: coupon.code.clone(),
+            occasion: coupon.occasion.label(),
+            percent_off: coupon.percent_off,
+            expires_at: coupon.expires_at,
+            granted_at: coupon.issued_at,
+        }
+    }
+}
+
+fn items_of(order: &Order) -> Vec<NoticeItem> {
+    order
+        .items
+        .iter()
+        .map(|item| NoticeItem {
+            description: item.beverage_description.clone(),
+            quantity: item.quantity,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Coffee, Customer, OrderItem};
+
+    fn make_order() -> Order {
+        let customer = Customer::new(
+            "Ada".to_string(),
+            "ada@example.com".to_string(),
+            Some("555-0100".to_string()),
+        );
+        let items = vec![OrderItem { quantity: 2, ..OrderItem::of(&Coffee::medium()) }];
+        Order::new(customer, items)
+    }
+
+    #[test]
+    fn test_placed_notice_carries_only_what_channels_need() {
+        let mut order = make_order();
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+
+        let notice = OrderPlacedNotice::from_order(&order);
+        assert_eq!(notice.total_price, Money::usd(7.0));
+        assert_eq!(notice.items[0].quantity, 2);
+
+        let json = serde_json::to_string(&notice).unwrap();
+        assert!(!json.contains("555-0100"));
+        assert!(!json.contains("CASH-1"));
+    }
+
+    #[test]
+    fn test_notice_json_round_trips() {
+        let notice = OrderCancelledNotice::from_order(&make_order());
+        let json = serde_json::to_value(&notice).unwrap();
+
+        assert_eq!(json["recipient"]["email"], "ada@example.com");
+        assert_eq!(serde_json::from_value::<OrderCancelledNotice>(json).unwrap(), notice);
+    }
+}
@@ -3,21 +3,89 @@
 // It doesn't know how to save itself, send notifications, or process payments
 // Those are responsibilities of other modules (SRP)
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 
+use super::beverage::{Beverage, Size, DEFAULT_PREP_SECONDS};
+use super::chargeback::Chargeback;
 use super::customer::Customer;
+use super::money::{Currency, Money};
 
 /// Status of an order in its lifecycle
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub enum OrderStatus {
     Pending,    // Just created
+    AwaitingPayment, // Sent to a payment provider, waiting for the customer to approve
     Paid,       // Payment successful
     Preparing,  // Barista is making it
     Ready,      // Ready for pickup
     Completed,  // Customer picked it up
     Cancelled,  // Order was cancelled
+    NoShow,     // Ready, but never picked up
+    Disputed,   // The cardholder charged it back (see `chargeback`)
+    Refunded,   // Cancelled after payment, and the money given back (see `refund_id`)
+}
+
+impl OrderStatus {
+    /// Whether an order in this status may move to `next`
+    ///
+    /// Pending -> (AwaitingPayment ->) Paid -> Preparing -> Ready -> Completed,
+    /// or NoShow from Ready. Anything not handed over can be cancelled, a paid
+    /// one refunded, and a charge disputed until the order is closed.
+    /// Cancelled, Refunded and Disputed are final.
+    pub fn can_transition_to(&self, next: &OrderStatus) -> bool {
+        use OrderStatus::*;
+        matches!(
+            (self, next),
+            (Pending, AwaitingPayment | Paid | Cancelled)
+                | (AwaitingPayment, Paid | Cancelled)
+                | (Paid, Preparing | Cancelled | Refunded | Disputed)
+                | (Preparing, Ready | Cancelled | Refunded | Disputed)
+                | (Ready, Completed | NoShow | Cancelled | Refunded | Disputed)
+                | (Completed, Disputed)
+                | (NoShow, Cancelled | Disputed)
+        )
+    }
+}
+
+/// A status change the order's lifecycle doesn't allow, e.g. Pending to Ready
+///
+/// The order is left as it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub order_id: Uuid,
+    pub from: OrderStatus,
+    pub to: OrderStatus,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Order {} cannot go from {} to {}",
+            &self.order_id.simple().to_string()[..8],
+            self.from,
+            self.to
+        )
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+/// A single entry in an order's status history
+///
+/// Recording WHEN each transition happened lets time-based rules
+/// (e.g. "Ready for more than 15 minutes") work without extra fields per status.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatusChange {
+    pub status: OrderStatus,
+    pub at: DateTime<Utc>,
 }
 
 /// Represents an order in our coffee shop
@@ -34,15 +102,58 @@ pub enum OrderStatus {
 /// 
 /// Each of those is a SEPARATE responsibility handled by a SEPARATE module.
 /// This makes the code easier to maintain and test.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Order {
     pub id: Uuid,
     pub customer: Customer,
     pub items: Vec<OrderItem>,
     pub status: OrderStatus,
     pub created_at: DateTime<Utc>,
-    pub total_price: f64,
+    /// In the order's currency, like every amount on it (see `currency`)
+    pub total_price: Money,
     pub payment_id: Option<String>,
+    /// Every status the order went through, oldest first
+    /// (`default` keeps orders saved before this field existed loadable)
+    #[serde(default)]
+    pub status_history: Vec<StatusChange>,
+    /// Statuses the order got stuck in past its SLA, and when that was noticed
+    #[serde(default)]
+    pub sla_breaches: Vec<StatusChange>,
+    /// The price book the items were priced from (None: the menu's own prices)
+    #[serde(default)]
+    pub price_book_version: Option<u32>,
+    /// Added to the total for the payment method (card surcharge), before rounding
+    #[serde(default)]
+    pub surcharge: Money,
+    /// Added to the total when it was charged (cash rounding; negative = rounded down)
+    #[serde(default)]
+    pub rounding_adjustment: Money,
+    /// Redirect payments (PayPal-style): what the customer was sent to approve
+    #[serde(default)]
+    pub payment_token: Option<String>,
+    /// Sequential receipt number from the shop's fiscal counter (given once paid)
+    #[serde(default)]
+    pub fiscal_number: Option<u64>,
+    /// The shop's VAT registration when the order was paid, as printed on the receipt
+    #[serde(default)]
+    pub seller_tax_id: Option<String>,
+    /// The gateway's chargeback, once the payment is disputed
+    #[serde(default)]
+    pub chargeback: Option<Chargeback>,
+    /// The processor's reference for the refund, once a paid order is cancelled
+    #[serde(default)]
+    pub refund_id: Option<String>,
+    /// Money given back so far: partial refunds, then the rest when cancelled
+    #[serde(default)]
+    pub refunded_total: Money,
+    /// The `payment_method_name` of the processor that charged it, to refund through
+    #[serde(default)]
+    pub payment_method: Option<String>,
+    /// Part of the charge paid with loyalty points (PointsPayment): it earns no points
+    #[serde(default)]
+    pub paid_with_points: Money,
 }
 
 /// An item in an order
@@ -51,12 +162,120 @@ pub struct Order {
 /// (not a reference to a Beverage trait object, which wouldn't be serializable)
 /// This is a pragmatic choice - in a real system, you might want to store
 /// the beverage details differently.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct OrderItem {
     pub beverage_name: String,
     pub beverage_description: String,
-    pub price: f64,
+    /// Price of one
+    pub price: Money,
     pub quantity: u8,
+    /// Estimated seconds to make one (None for lines stored before estimates existed)
+    #[serde(default)]
+    pub prep_seconds: Option<u32>,
+    /// What the price was made of, to re-price the line in an audit
+    /// (None for lines stored before it was recorded, or imported)
+    #[serde(default)]
+    pub price_basis: Option<PriceBasis>,
+    /// VAT rate included in the price, e.g. 0.081 (None: VAT not recorded)
+    #[serde(default)]
+    pub vat_rate: Option<f64>,
+}
+
+/// The inputs of a line's price besides the product family's base
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PriceBasis {
+    pub size: Size,
+    /// Customizations' share of the base price (Beverage::price_adjustment)
+    pub adjustment: f64,
+}
+
+impl PriceBasis {
+    pub fn of(beverage: &dyn Beverage) -> Self {
+        Self {
+            size: beverage.size(),
+            adjustment: beverage.price_adjustment(),
+        }
+    }
+}
+
+/// The VAT included in an order's lines at one rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VatLine {
+    pub rate: f64,
+    /// Lines at this rate, VAT included (what the customer paid for them)
+    pub gross: Money,
+    pub net: Money,
+    pub vat: Money,
+}
+
+impl OrderItem {
+    /// One of `beverage`, at its own price and prep time
+    pub fn of(beverage: &dyn Beverage) -> Self {
+        Self {
+            beverage_name: beverage.name(),
+            beverage_description: beverage.description(),
+            price: beverage.price(),
+            quantity: 1,
+            prep_seconds: Some(beverage.prep_time_estimate().num_seconds() as u32),
+            price_basis: Some(PriceBasis::of(beverage)),
+            vat_rate: None,
+        }
+    }
+
+    /// Price of one, to the cent
+    pub fn unit_price(&self) -> Money {
+        self.price
+    }
+
+    /// Price of the whole line: unit price times quantity
+    pub fn line_total(&self) -> Money {
+        self.unit_price() * self.quantity as u32
+    }
+
+    /// Estimated time to make the whole line
+    pub fn prep_time(&self) -> Duration {
+        let each = self.prep_seconds.unwrap_or(DEFAULT_PREP_SECONDS);
+        Duration::seconds(each as i64 * self.quantity as i64)
+    }
+}
+
+/// A beverage and how many of it, as ordered (becomes one OrderItem)
+pub struct OrderLine {
+    pub beverage: Box<dyn Beverage>,
+    pub quantity: u8,
+}
+
+impl OrderLine {
+    pub fn new(beverage: Box<dyn Beverage>, quantity: u8) -> Self {
+        Self { beverage, quantity }
+    }
+
+    /// One of `beverage`
+    pub fn single(beverage: Box<dyn Beverage>) -> Self {
+        Self::new(beverage, 1)
+    }
+}
+
+/// How an order's total follows from its items
+///
+/// Prices include VAT and a promotion is already taken off its item's price
+/// (PromotionEngine), so the shop's total is the sum of the lines:
+/// `LinePricing`. Surcharges and cash rounding are kept apart from the total.
+pub trait OrderPricing {
+    fn total(&self, items: &[OrderItem]) -> Money;
+}
+
+/// The sum of price * quantity, added up in cents
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinePricing;
+
+impl OrderPricing for LinePricing {
+    fn total(&self, items: &[OrderItem]) -> Money {
+        items.iter().map(OrderItem::line_total).sum()
+    }
 }
 
 impl Order {
@@ -66,56 +285,432 @@ impl Order {
     /// "placing an order" (validation, payment, persistence, notification)
     /// is in OrderService, following SRP.
     pub fn new(customer: Customer, items: Vec<OrderItem>) -> Self {
-        let total_price = items.iter()
-            .map(|item| item.price * item.quantity as f64)
-            .sum();
+        Self::placed_at(customer, items, Utc::now())
+    }
+
+    /// Create a new order placed at `created_at` (OrderService's clock)
+    pub fn placed_at(
+        customer: Customer,
+        items: Vec<OrderItem>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        // Added up in cents: f64 sums drift (see Money)
+        let total_price = LinePricing.total(&items);
+        let nothing = Money::zero(total_price.currency);
 
         Self {
             id: Uuid::new_v4(),
             customer,
             items,
             status: OrderStatus::Pending,
-            created_at: Utc::now(),
+            created_at,
             total_price,
             payment_id: None,
+            status_history: vec![StatusChange {
+                status: OrderStatus::Pending,
+                at: created_at,
+            }],
+            sla_breaches: Vec::new(),
+            price_book_version: None,
+            surcharge: nothing,
+            rounding_adjustment: nothing,
+            payment_token: None,
+            fiscal_number: None,
+            seller_tax_id: None,
+            chargeback: None,
+            refund_id: None,
+            refunded_total: nothing,
+            payment_method: None,
+            paid_with_points: nothing,
+        }
+    }
+
+    /// A new order for one beverage, e.g. `Order::single(Customer::named("Ada"), Coffee::medium())`
+    ///
+    /// Not placed: nothing is charged or saved until it goes through OrderService.
+    pub fn single(customer: Customer, beverage: impl Beverage) -> Self {
+        Self::new(customer, vec![OrderItem::of(&beverage)])
+    }
+
+    /// VAT included in the total, one line per rate, lowest rate first
+    ///
+    /// Prices include VAT, so each rate's share is `gross * rate / (1 + rate)`,
+    /// rounded to the cent per rate as receipts print it. Lines without a
+    /// recorded rate are left out.
+    pub fn vat_breakdown(&self) -> Vec<VatLine> {
+        let mut rates: Vec<(f64, Money)> = Vec::new();
+        for item in &self.items {
+            let Some(rate) = item.vat_rate else { continue };
+            match rates.iter_mut().find(|(r, _)| *r == rate) {
+                Some((_, gross)) => *gross += item.line_total(),
+                None => rates.push((rate, item.line_total())),
+            }
+        }
+
+        let mut lines: Vec<VatLine> = rates
+            .into_iter()
+            .map(|(rate, gross)| {
+                let vat = gross.scaled(rate / (1.0 + rate));
+                VatLine {
+                    rate,
+                    gross,
+                    net: gross - vat,
+                    vat,
+                }
+            })
+            .collect();
+        lines.sort_by(|a, b| a.rate.total_cmp(&b.rate));
+        lines
+    }
+
+    /// The total, to the cent
+    pub fn total(&self) -> Money {
+        self.total_price
+    }
+
+    /// What the order is priced and charged in: its items' currency
+    pub fn currency(&self) -> Currency {
+        self.total_price.currency
+    }
+
+    /// What `total_price` should be for these items
+    pub fn expected_total(&self, pricing: &dyn OrderPricing) -> Money {
+        pricing.total(&self.items)
+    }
+
+    /// Set `total_price` from the items again (after a hand edit, say);
+    /// returns the total it replaced
+    pub fn recompute_total(&mut self, pricing: &dyn OrderPricing) -> Money {
+        let previous = self.total();
+        self.total_price = self.expected_total(pricing);
+        previous
+    }
+
+    /// What the customer actually paid: the total, plus any surcharge, after cash rounding
+    pub fn amount_charged(&self) -> Money {
+        self.total_price + self.surcharge + self.rounding_adjustment
+    }
+
+    /// What can still be given back: the amount charged, less the refunds so far
+    pub fn left_to_refund(&self) -> Money {
+        let charged = self.amount_charged();
+        // Orders stored before refunds were recorded default to zero dollars
+        if self.refunded_total.is_zero() {
+            return charged;
         }
+        charged.saturating_sub(self.refunded_total)
+    }
+
+    /// `amount` of this order less the part paid with points, never below zero
+    pub fn less_paid_with_points(&self, amount: Money) -> Money {
+        // Orders stored before points payments were recorded default to zero dollars
+        if self.paid_with_points.is_zero() {
+            return amount;
+        }
+        amount.saturating_sub(self.paid_with_points)
+    }
+
+    /// Add money given back on this order to `refunded_total`
+    pub fn record_refund(&mut self, amount: Money) {
+        self.refunded_total = if self.refunded_total.is_zero() {
+            amount
+        } else {
+            self.refunded_total + amount
+        };
     }
 
     /// Mark order as paid
     /// 
     /// SOLID: Notice this is just a state transition method.
     /// The actual payment processing logic is elsewhere (SRP).
-    pub fn mark_as_paid(&mut self, payment_id: String) {
-        self.status = OrderStatus::Paid;
+    pub fn mark_as_paid(&mut self, payment_id: String) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::Paid)?;
         self.payment_id = Some(payment_id);
+        Ok(())
+    }
+
+    /// Park a pending order until the customer approves the payment `token`
+    pub fn await_payment(&mut self, token: String) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::AwaitingPayment)?;
+        self.payment_token = Some(token);
+        Ok(())
+    }
+
+    /// Mark a paid order as preparing
+    pub fn mark_as_preparing(&mut self) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::Preparing)
+    }
+
+    /// Mark a preparing order as ready
+    pub fn mark_as_ready(&mut self) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::Ready)
+    }
+
+    /// Mark a ready order as completed
+    pub fn mark_as_completed(&mut self) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::Completed)
+    }
+
+    /// Mark a ready order as never picked up
+    pub fn mark_as_no_show(&mut self) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::NoShow)
+    }
+
+    /// A paid order's charge was disputed with the bank
+    pub fn dispute(&mut self, chargeback: Chargeback) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::Disputed)?;
+        self.chargeback = Some(chargeback);
+        Ok(())
+    }
+
+    /// Paid and not handed over yet: cancelling it means giving the money back
+    pub fn is_refundable(&self) -> bool {
+        matches!(self.status, OrderStatus::Paid | OrderStatus::Preparing | OrderStatus::Ready)
+            && self.payment_id.is_some()
     }
 
-    /// Mark order as preparing
-    pub fn mark_as_preparing(&mut self) {
-        if self.status == OrderStatus::Paid {
-            self.status = OrderStatus::Preparing;
+    /// A refundable order was cancelled and its payment refunded
+    ///
+    /// An order that was never charged (no `payment_id`) can't be refunded either.
+    pub fn mark_refunded(&mut self, refund_id: String) -> Result<(), InvalidTransition> {
+        if self.payment_id.is_none() {
+            return Err(self.invalid(OrderStatus::Refunded));
         }
+        self.transition_to(OrderStatus::Refunded)?;
+        self.refund_id = Some(refund_id);
+        Ok(())
+    }
+
+    /// Cancel an order that wasn't handed over (or closed) yet
+    pub fn cancel(&mut self) -> Result<(), InvalidTransition> {
+        self.transition_to(OrderStatus::Cancelled)
     }
 
-    /// Mark order as ready
-    pub fn mark_as_ready(&mut self) {
-        if self.status == OrderStatus::Preparing {
-            self.status = OrderStatus::Ready;
+    /// When the order entered its current status
+    ///
+    /// Falls back to `created_at` for orders stored without a history.
+    pub fn status_since(&self) -> DateTime<Utc> {
+        self.status_history
+            .last()
+            .map(|change| change.at)
+            .unwrap_or(self.created_at)
+    }
+
+    /// The order as it stood at `at`, rewound along its status history
+    ///
+    /// None if it didn't exist yet. The status, its history, SLA breaches and
+    /// what later steps recorded (payment, refund, chargeback) are rewound;
+    /// items and totals stay as they are now. An order stored without a
+    /// history keeps its current status.
+    pub fn as_of(&self, at: DateTime<Utc>) -> Option<Order> {
+        if self.created_at > at {
+            return None;
+        }
+        let mut order = self.clone();
+        order.status_history.retain(|change| change.at <= at);
+        order.sla_breaches.retain(|breach| breach.at <= at);
+        if let Some(change) = order.status_history.last() {
+            order.status = change.status.clone();
+        } else if !self.status_history.is_empty() {
+            order.status = OrderStatus::Pending;
         }
+
+        if matches!(order.status, OrderStatus::Pending | OrderStatus::AwaitingPayment) {
+            order.payment_id = None;
+            order.payment_method = None;
+        }
+        if order.status != OrderStatus::Refunded {
+            order.refund_id = None;
+        }
+        if order.status != OrderStatus::Disputed {
+            order.chargeback = None;
+        }
+        Some(order)
+    }
+
+    /// Estimated time for one barista to make every item
+    pub fn prep_time_estimate(&self) -> Duration {
+        self.items
+            .iter()
+            .fold(Duration::zero(), |total, item| total + item.prep_time())
+    }
+
+    /// When the order first entered `status`, if it ever did
+    pub fn entered_at(&self, status: &OrderStatus) -> Option<DateTime<Utc>> {
+        self.status_history
+            .iter()
+            .find(|change| &change.status == status)
+            .map(|change| change.at)
+    }
+
+    /// Time from creation to payment (includes any wait in the order queue)
+    pub fn placement_latency(&self) -> Option<Duration> {
+        self.entered_at(&OrderStatus::Paid)
+            .map(|paid_at| paid_at - self.created_at)
+    }
+
+    /// Time the barista spent on the order, from Preparing to Ready
+    pub fn prep_duration(&self) -> Option<Duration> {
+        let started = self.entered_at(&OrderStatus::Preparing)?;
+        self.entered_at(&OrderStatus::Ready)
+            .map(|ready_at| ready_at - started)
+    }
+
+    /// Record that the order has been in its current status for too long
+    ///
+    /// One flag per status visit: returns false (and changes nothing) if this
+    /// visit is already flagged.
+    pub fn flag_sla_breach(&mut self, at: DateTime<Utc>) -> bool {
+        if self.is_sla_breach_flagged() {
+            return false;
+        }
+        self.sla_breaches.push(StatusChange {
+            status: self.status.clone(),
+            at,
+        });
+        true
+    }
+
+    /// Is the current status visit already flagged as an SLA breach?
+    pub fn is_sla_breach_flagged(&self) -> bool {
+        let since = self.status_since();
+        self.sla_breaches
+            .iter()
+            .any(|breach| breach.status == self.status && breach.at >= since)
+    }
+
+    /// Did the order ever break its SLA?
+    pub fn breached_sla(&self) -> bool {
+        !self.sla_breaches.is_empty()
+    }
+
+    /// Change status and record the transition in the history
+    ///
+    /// Refused, leaving the order untouched, unless the current status may
+    /// move to `status` (OrderStatus::can_transition_to).
+    fn transition_to(&mut self, status: OrderStatus) -> Result<(), InvalidTransition> {
+        if !self.status.can_transition_to(&status) {
+            return Err(self.invalid(status));
+        }
+        self.status_history.push(StatusChange {
+            status: status.clone(),
+            at: Utc::now(),
+        });
+        self.status = status;
+        Ok(())
+    }
+
+    fn invalid(&self, to: OrderStatus) -> InvalidTransition {
+        InvalidTransition {
+            order_id: self.id,
+            from: self.status.clone(),
+            to,
+        }
+    }
+
+    /// Same order in substance: same customer, lines, amounts and status
+    ///
+    /// `==` compares everything; this ignores what differs between two
+    /// copies of one order placed twice, or saved and replayed: the IDs
+    /// (order, customer, payment, fiscal number) and the timestamps
+    /// (creation, status history, SLA breaches).
+    pub fn same_contents(&self, other: &Order) -> bool {
+        self.customer.name == other.customer.name
+            && self.customer.email == other.customer.email
+            && self.customer.phone == other.customer.phone
+            && self.items == other.items
+            && self.status == other.status
+            && self.total_price == other.total_price
+            && self.surcharge == other.surcharge
+            && self.rounding_adjustment == other.rounding_adjustment
+            && self.price_book_version == other.price_book_version
+            && self.seller_tax_id == other.seller_tax_id
+    }
+
+    /// First 8 hex digits of the ID, as printed on receipts and in journals
+    pub fn short_id(&self) -> String {
+        self.id.simple().to_string()[..8].to_string()
+    }
+
+    /// One line for lists and logs: "1a2b3c4d  Ada  2 items  7.00 USD  Paid"
+    pub fn summary(&self) -> String {
+        let count: u32 = self.items.iter().map(|item| item.quantity as u32).sum();
+        format!(
+            "{}  {}  {} item{}  {}  {}",
+            self.short_id(),
+            self.customer.name,
+            count,
+            if count == 1 { "" } else { "s" },
+            self.total_price,
+            self.status
+        )
+    }
+}
+
+/// "Paid", "No show"
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            OrderStatus::Pending => "Pending",
+            OrderStatus::AwaitingPayment => "Awaiting payment",
+            OrderStatus::Paid => "Paid",
+            OrderStatus::Preparing => "Preparing",
+            OrderStatus::Ready => "Ready",
+            OrderStatus::Completed => "Completed",
+            OrderStatus::Cancelled => "Cancelled",
+            OrderStatus::NoShow => "No show",
+            OrderStatus::Disputed => "Disputed",
+            OrderStatus::Refunded => "Refunded",
+        };
+        f.write_str(label)
     }
+}
 
-    /// Mark order as completed
-    pub fn mark_as_completed(&mut self) {
-        if self.status == OrderStatus::Ready {
-            self.status = OrderStatus::Completed;
+/// Case, spaces, dashes and underscores don't matter: "No show", "no-show", "NOSHOW"
+impl FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "pending" => Ok(OrderStatus::Pending),
+            "awaitingpayment" => Ok(OrderStatus::AwaitingPayment),
+            "paid" => Ok(OrderStatus::Paid),
+            "preparing" => Ok(OrderStatus::Preparing),
+            "ready" => Ok(OrderStatus::Ready),
+            "completed" => Ok(OrderStatus::Completed),
+            "cancelled" | "canceled" => Ok(OrderStatus::Cancelled),
+            "noshow" => Ok(OrderStatus::NoShow),
+            "disputed" => Ok(OrderStatus::Disputed),
+            "refunded" => Ok(OrderStatus::Refunded),
+            _ => Err(format!("status '{}' is unknown", text)),
         }
     }
+}
+
+/// "2 x Coffee (Medium)  $7.00" (the line total)
+impl fmt::Display for OrderItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} x {}  ${:.2}",
+            self.quantity,
+            self.beverage_description,
+            self.line_total().to_major()
+        )
+    }
+}
 
-    /// Cancel order
-    pub fn cancel(&mut self) {
-        if self.status != OrderStatus::Completed {
-            self.status = OrderStatus::Cancelled;
+/// Several lines: ID, customer, one line per item, total, status, creation time
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Order ID: {}", self.id)?;
+        writeln!(f, "Customer: {}", self.customer)?;
+        for item in &self.items {
+            writeln!(f, "  {}", item)?;
         }
+        writeln!(f, "Total: ${:.2}", self.total_price.to_major())?;
+        writeln!(f, "Status: {}", self.status)?;
+        write!(f, "Created: {}", self.created_at.format("%Y-%m-%d %H:%M:%S"))
     }
 }
 
@@ -136,8 +731,11 @@ mod tests {
         OrderItem {
             beverage_name: "Coffee".to_string(),
             beverage_description: "Medium Coffee".to_string(),
-            price: 3.50,
+            price: Money::usd(3.50),
             quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
         }
     }
 
@@ -149,7 +747,7 @@ mod tests {
         let order = Order::new(customer, items);
         
         assert_eq!(order.status, OrderStatus::Pending);
-        assert_eq!(order.total_price, 3.50);
+        assert_eq!(order.total_price, Money::usd(3.50));
     }
 
     #[test]
@@ -162,21 +760,116 @@ mod tests {
         assert_eq!(order.status, OrderStatus::Pending);
 
         // Mark as paid
-        order.mark_as_paid("PAY-123".to_string());
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
         assert_eq!(order.status, OrderStatus::Paid);
         assert_eq!(order.payment_id, Some("PAY-123".to_string()));
 
         // Preparing
-        order.mark_as_preparing();
+        order.mark_as_preparing().unwrap();
         assert_eq!(order.status, OrderStatus::Preparing);
 
         // Ready
-        order.mark_as_ready();
+        order.mark_as_ready().unwrap();
         assert_eq!(order.status, OrderStatus::Ready);
 
         // Completed
-        order.mark_as_completed();
+        order.mark_as_completed().unwrap();
         assert_eq!(order.status, OrderStatus::Completed);
+
+        // Every transition is recorded
+        assert_eq!(order.status_history.len(), 5);
+        assert_eq!(order.status_history[0].status, OrderStatus::Pending);
+        assert_eq!(order.status_history[4].status, OrderStatus::Completed);
+    }
+
+    #[test]
+    fn test_no_show_only_from_ready() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+
+        // Pending orders can't be no-shows
+        assert!(order.mark_as_no_show().is_err());
+        assert_eq!(order.status, OrderStatus::Pending);
+
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
+        order.mark_as_preparing().unwrap();
+        order.mark_as_ready().unwrap();
+        order.mark_as_no_show().unwrap();
+        assert_eq!(order.status, OrderStatus::NoShow);
+    }
+
+    #[test]
+    fn test_invalid_transitions_leave_the_order_as_it_was() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+
+        let err = order.mark_as_ready().unwrap_err();
+        assert_eq!((&err.from, &err.to), (&OrderStatus::Pending, &OrderStatus::Ready));
+        assert_eq!(
+            err.to_string(),
+            format!("Order {} cannot go from Pending to Ready", order.short_id())
+        );
+        assert_eq!(order.status_history.len(), 1);
+        // Never charged: nothing to refund
+        assert!(order.mark_refunded("RF-1".to_string()).is_err());
+
+        order.cancel().unwrap();
+        assert!(order.cancel().is_err());
+        assert!(order.mark_as_paid("PAY-123".to_string()).is_err());
+        assert_eq!(order.payment_id, None);
+        assert_eq!(order.status_history.len(), 2);
+    }
+
+    #[test]
+    fn test_sla_breach_flagged_once_per_status() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
+
+        assert!(order.flag_sla_breach(Utc::now()));
+        assert!(!order.flag_sla_breach(Utc::now()));
+
+        order.mark_as_preparing().unwrap();
+        assert!(!order.is_sla_breach_flagged());
+        assert!(order.breached_sla());
+    }
+
+    #[test]
+    fn test_placement_and_prep_durations() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+        assert!(order.placement_latency().is_none());
+
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
+        order.mark_as_preparing().unwrap();
+        order.mark_as_ready().unwrap();
+        let started = order.entered_at(&OrderStatus::Preparing).unwrap();
+        order.status_history.last_mut().unwrap().at = started + Duration::minutes(4);
+
+        assert!(order.placement_latency().unwrap() >= Duration::zero());
+        assert_eq!(order.prep_duration(), Some(Duration::minutes(4)));
+    }
+
+    #[test]
+    fn test_status_since_without_history() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+        order.status_history.clear();
+
+        assert_eq!(order.status_since(), order.created_at);
+    }
+
+    #[test]
+    fn test_as_of_rewinds_the_status() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
+        order.mark_as_preparing().unwrap();
+        let created = order.created_at;
+        order.status_history[1].at = created + Duration::minutes(1);
+        order.status_history[2].at = created + Duration::minutes(5);
+
+        assert!(order.as_of(created - Duration::seconds(1)).is_none());
+        let pending = order.as_of(created).unwrap();
+        assert_eq!((pending.status, pending.payment_id), (OrderStatus::Pending, None));
+        let paid = order.as_of(created + Duration::minutes(3)).unwrap();
+        assert_eq!(paid.status, OrderStatus::Paid);
+        assert_eq!(paid.status_since(), created + Duration::minutes(1));
+        assert_eq!(order.as_of(created + Duration::hours(1)).unwrap(), order);
     }
 
     #[test]
@@ -186,20 +879,120 @@ mod tests {
             OrderItem {
                 beverage_name: "Coffee".to_string(),
                 beverage_description: "Medium Coffee".to_string(),
-                price: 3.50,
+                price: Money::usd(3.50),
                 quantity: 2,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
             },
             OrderItem {
                 beverage_name: "Tea".to_string(),
                 beverage_description: "Large Green Tea".to_string(),
-                price: 3.00,
+                price: Money::usd(3.00),
                 quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
             },
         ];
         
         let order = Order::new(customer, items);
         
         // (3.50 * 2) + (3.00 * 1) = 10.00
-        assert_eq!(order.total_price, 10.00);
+        assert_eq!(order.total_price, Money::usd(10.00));
+    }
+
+    #[test]
+    fn test_recompute_total_after_a_hand_edit() {
+        let mut order = Order::new(make_test_customer(), vec![make_test_item()]);
+        let expected = order.total();
+        order.total_price = Money::usd(1.00);
+
+        assert_eq!(order.expected_total(&LinePricing), expected);
+        assert_eq!(order.recompute_total(&LinePricing), Money::usd(1.00));
+        assert_eq!(order.total(), expected);
+    }
+
+    #[test]
+    fn test_display_and_summary() {
+        let mut item = make_test_item();
+        item.quantity = 2;
+        let mut order = Order::new(make_test_customer(), vec![item]);
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+
+        let text = order.to_string();
+        assert!(text.starts_with(&format!("Order ID: {}\n", order.id)));
+        assert!(text.contains("Customer: Test User <test@example.com>\n"));
+        assert!(text.contains("\n  2 x Medium Coffee  $7.00\n"));
+        assert!(text.contains("\nStatus: Paid\n"));
+        assert_eq!(
+            order.summary(),
+            format!("{}  Test User  2 items  7.00 USD  Paid", order.short_id())
+        );
+        assert_eq!(OrderStatus::NoShow.to_string(), "No show");
+    }
+
+    #[test]
+    fn test_same_contents_ignores_ids_and_times() {
+        let order = Order::new(make_test_customer(), vec![make_test_item()]);
+        let mut twice = Order::new(make_test_customer(), vec![make_test_item()]);
+        twice.created_at = order.created_at - Duration::minutes(5);
+
+        assert!(order.same_contents(&twice));
+        assert_ne!(order, twice);
+        assert_eq!(order, order.clone());
+
+        twice.items[0].quantity = 2;
+        assert!(!order.same_contents(&twice));
+        let mut paid = order.clone();
+        paid.mark_as_paid("CASH-1".to_string()).unwrap();
+        assert!(!order.same_contents(&paid));
+
+        let statuses: std::collections::HashSet<OrderStatus> =
+            [OrderStatus::Paid, OrderStatus::Paid, OrderStatus::Ready].into_iter().collect();
+        assert_eq!(statuses.len(), 2);
+        assert!(Size::Small < Size::Large);
+    }
+
+    #[test]
+    fn test_single_beverage_order() {
+        use crate::domain::beverage::Coffee;
+
+        let order = Order::single(Customer::named("Ada"), Coffee::large().with_extra_shots(1));
+
+        assert_eq!(order.items.len(), 1);
+        assert_eq!(order.items[0].beverage_name, "Coffee (+1 shot)");
+        assert_eq!(order.total_price, Money::usd(5.10));
+        assert_eq!(order.items[0].prep_seconds, Some(137));
+        assert_eq!(order.status, OrderStatus::Pending);
+    }
+
+    #[test]
+    fn test_vat_breakdown_per_rate() {
+        let item = |price: f64, quantity: u8, vat_rate: Option<f64>| OrderItem {
+            vat_rate,
+            price: Money::usd(price),
+            quantity,
+            ..make_test_item()
+        };
+        let order = Order::new(
+            make_test_customer(),
+            vec![
+                item(5.40, 1, Some(0.081)),
+                item(3.00, 2, Some(0.026)),
+                item(2.00, 1, Some(0.081)),
+                item(1.00, 1, None),
+            ],
+        );
+
+        let breakdown = order.vat_breakdown();
+        assert_eq!(breakdown.len(), 2);
+        // 6.00 * 0.026 / 1.026 = 0.152
+        assert_eq!(breakdown[0].rate, 0.026);
+        assert_eq!(breakdown[0].vat, Money::cents(15));
+        assert_eq!(breakdown[0].net, Money::cents(585));
+        // 7.40 * 0.081 / 1.081 = 0.554
+        assert_eq!(breakdown[1].gross, Money::cents(740));
+        assert_eq!(breakdown[1].vat, Money::cents(55));
     }
 }
@@ -0,0 +1,194 @@
+// SOLID: This module is part of the DOMAIN layer
+// What changed between two versions of one order, as a list of changes an
+// audit trail can store and a person can read:
+//
+//   status: Paid -> Ready
+//   + 1 x Tea (Small)  $2.00
+//   total: $3.50 -> $5.50
+//
+// Lines are matched by what they are (name, description, price): the same
+// line with another quantity is one QuantityChanged, not a removal and an
+// addition. The status history and SLA breaches follow from the status and
+// are not listed; the order ID is not compared (diff versions of ONE order).
+
+use super::money::Money;
+use super::order::{Order, OrderItem, OrderStatus};
+use std::fmt;
+
+/// One difference between two versions of an order
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldChange {
+    StatusChanged { from: OrderStatus, to: OrderStatus },
+    ItemAdded(OrderItem),
+    ItemRemoved(OrderItem),
+    QuantityChanged { item: String, from: u8, to: u8 },
+    TotalChanged { from: Money, to: Money },
+    /// Any other field, both values as displayed ("-" for none)
+    Changed { field: &'static str, from: String, to: String },
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldChange::StatusChanged { from, to } => write!(f, "status: {} -> {}", from, to),
+            FieldChange::ItemAdded(item) => write!(f, "+ {}", item),
+            FieldChange::ItemRemoved(item) => write!(f, "- {}", item),
+            FieldChange::QuantityChanged { item, from, to } => {
+                write!(f, "quantity of {}: {} -> {}", item, from, to)
+            }
+            FieldChange::TotalChanged { from, to } => {
+                write!(f, "total: ${:.2} -> ${:.2}", from.to_major(), to.to_major())
+            }
+            FieldChange::Changed { field, from, to } => write!(f, "{}: {} -> {}", field, from, to),
+        }
+    }
+}
+
+/// Everything that changed from `before` to `after`, in field order
+pub fn order_diff(before: &Order, after: &Order) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    if before.status != after.status {
+        changes.push(FieldChange::StatusChanged {
+            from: before.status.clone(),
+            to: after.status.clone(),
+        });
+    }
+
+    changed(&mut changes, "customer", &before.customer, &after.customer);
+    diff_items(&mut changes, &before.items, &after.items);
+
+    if before.total_price != after.total_price {
+        changes.push(FieldChange::TotalChanged {
+            from: before.total_price,
+            to: after.total_price,
+        });
+    }
+
+    changed(&mut changes, "created_at", &before.created_at, &after.created_at);
+    changed(&mut changes, "payment_id", &shown(&before.payment_id), &shown(&after.payment_id));
+    changed(
+        &mut changes,
+        "payment_token",
+        &shown(&before.payment_token),
+        &shown(&after.payment_token),
+    );
+    changed(&mut changes, "surcharge", &before.surcharge, &after.surcharge);
+    changed(
+        &mut changes,
+        "rounding_adjustment",
+        &before.rounding_adjustment,
+        &after.rounding_adjustment,
+    );
+    changed(
+        &mut changes,
+        "price_book_version",
+        &shown(&before.price_book_version),
+        &shown(&after.price_book_version),
+    );
+    changed(
+        &mut changes,
+        "fiscal_number",
+        &shown(&before.fiscal_number),
+        &shown(&after.fiscal_number),
+    );
+    changed(
+        &mut changes,
+        "seller_tax_id",
+        &shown(&before.seller_tax_id),
+        &shown(&after.seller_tax_id),
+    );
+    changed(
+        &mut changes,
+        "chargeback",
+        &shown(&before.chargeback.as_ref().map(|c| &c.case_id)),
+        &shown(&after.chargeback.as_ref().map(|c| &c.case_id)),
+    );
+    changed(&mut changes, "refund_id", &shown(&before.refund_id), &shown(&after.refund_id));
+    changed(&mut changes, "refunded_total", &before.refunded_total, &after.refunded_total);
+    changed(
+        &mut changes,
+        "payment_method",
+        &shown(&before.payment_method),
+        &shown(&after.payment_method),
+    );
+    changes
+}
+
+fn changed<T: PartialEq + fmt::Display>(
+    changes: &mut Vec<FieldChange>,
+    field: &'static str,
+    from: &T,
+    to: &T,
+) {
+    if from != to {
+        changes.push(FieldChange::Changed {
+            field,
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+}
+
+fn shown<T: fmt::Display>(value: &Option<T>) -> String {
+    value.as_ref().map_or_else(|| "-".to_string(), T::to_string)
+}
+
+/// Same line, whatever the quantity
+fn same_line(a: &OrderItem, b: &OrderItem) -> bool {
+    OrderItem { quantity: b.quantity, ..a.clone() } == *b
+}
+
+fn diff_items(changes: &mut Vec<FieldChange>, before: &[OrderItem], after: &[OrderItem]) {
+    let mut unmatched: Vec<&OrderItem> = after.iter().collect();
+    for old in before {
+        match unmatched.iter().position(|new| same_line(old, new)) {
+            Some(at) => {
+                let new = unmatched.remove(at);
+                if new.quantity != old.quantity {
+                    changes.push(FieldChange::QuantityChanged {
+                        item: old.beverage_description.clone(),
+                        from: old.quantity,
+                        to: new.quantity,
+                    });
+                }
+            }
+            None => changes.push(FieldChange::ItemRemoved(old.clone())),
+        }
+    }
+    changes.extend(unmatched.into_iter().cloned().map(FieldChange::ItemAdded));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Coffee, Customer, Size, Tea, TeaVariety};
+
+    #[test]
+    fn test_diff_lists_what_changed() {
+        let before = Order::single(Customer::named("Ada"), Coffee::medium());
+        assert!(order_diff(&before, &before.clone()).is_empty());
+
+        let mut after = before.clone();
+        after.mark_as_paid("CASH-1".to_string()).unwrap();
+        after.items[0].quantity = 2;
+        after.items.push(OrderItem::of(&Tea::new(TeaVariety::Green, Size::Small)));
+        after.total_price = Money::usd(9.0);
+
+        let changes = order_diff(&before, &after);
+        let lines: Vec<String> = changes.iter().map(FieldChange::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "status: Pending -> Paid",
+                "quantity of Coffee (Medium): 1 -> 2",
+                "+ 1 x Green Tea (Small)  $2.00",
+                "total: $3.50 -> $9.00",
+                "payment_id: - -> CASH-1",
+            ]
+        );
+
+        let removed = order_diff(&after, &before);
+        assert!(removed.contains(&FieldChange::ItemRemoved(after.items[1].clone())));
+    }
+}
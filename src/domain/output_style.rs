@@ -0,0 +1,139 @@
+// SOLID: This module is part of the DOMAIN layer
+// How text meant for people is drawn: rich (emoji, box drawing, color) or plain.
+//
+// Plain output is for screen readers, which read "✅" as "white heavy check
+// mark", and for scripts scraping logs. It is one setting for the whole
+// process, chosen once at startup (`--plain`, see cli::output_style), not a
+// flag threaded through every call: the CLI's output, ConsoleNotifier and
+// receipts all ask `OutputStyle::current()`.
+//
+// `plain_text` is the conversion: emoji that carry meaning become words
+// ("[ok]", "[error]", "[warning]"), the rest are dropped; box drawing, bars
+// and bullets become ASCII; ANSI color codes are removed. Letters outside
+// ASCII ("Café") are kept.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Emoji, box drawing and color, or none of them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputStyle {
+    #[default]
+    Rich,
+    Plain,
+}
+
+impl OutputStyle {
+    /// The process-wide style (Rich until `set_global` says otherwise)
+    pub fn current() -> Self {
+        if PLAIN.load(Ordering::Relaxed) {
+            OutputStyle::Plain
+        } else {
+            OutputStyle::Rich
+        }
+    }
+
+    /// Make `self` the process-wide style; call it once, at startup
+    pub fn set_global(self) {
+        PLAIN.store(self == OutputStyle::Plain, Ordering::Relaxed);
+    }
+
+    /// `text` as this style draws it
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            OutputStyle::Rich => text.to_string(),
+            OutputStyle::Plain => plain_text(text),
+        }
+    }
+}
+
+/// `text` with only what reads well aloud or in a log
+pub fn plain_text(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    // An emoji was dropped where a word began: drop the spaces after it too
+    let mut skip_spaces = false;
+
+    while let Some(c) = chars.next() {
+        if skip_spaces && c == ' ' {
+            continue;
+        }
+        skip_spaces = false;
+
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            // ANSI escape: ESC [ parameters, up to the final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        if let Some(ascii) = ascii_for(c) {
+            plain.push_str(ascii);
+        } else if is_emoji(c) {
+            skip_spaces = plain.is_empty() || plain.ends_with(char::is_whitespace);
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// What stands in for a symbol that means something
+fn ascii_for(c: char) -> Option<&'static str> {
+    Some(match c {
+        '✅' | '✔' => "[ok]",
+        '❌' | '✘' | '⛔' => "[error]",
+        '⚠' => "[warning]",
+        '•' | '●' | '★' | '⭐' => "*",
+        '○' => "o",
+        '▶' => ">",
+        '…' => "...",
+        // Bar charts, and sparklines from lowest to highest
+        '▁' => "_",
+        '▂' => ".",
+        '▃' => ":",
+        '▄' => "-",
+        '▅' => "=",
+        '▆' => "+",
+        '▇' => "*",
+        '█' => "#",
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' => "-",
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' => "|",
+        // Corners, tees and crosses
+        '\u{2500}'..='\u{257F}' => "+",
+        _ => return None,
+    })
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2300..=0x23FF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_keeps_words_and_drops_decoration() {
+        assert_eq!(plain_text("✅ Status board written"), "[ok] Status board written");
+        assert_eq!(plain_text("⚠️  Low stock"), "[warning]  Low stock");
+        assert_eq!(plain_text("🎉 Order Placed!"), "Order Placed!");
+        assert_eq!(plain_text("\n  📊 Report ☕"), "\n  Report ");
+        assert_eq!(plain_text("\x1b[1;33mReady\x1b[0m"), "Ready");
+        assert_eq!(plain_text("Coffee ████ 12.00 ▁▄█"), "Coffee #### 12.00 _-#");
+        assert_eq!(plain_text("┌──┐\n│ Café │"), "+--+\n| Café |");
+    }
+
+    #[test]
+    fn test_rich_is_the_default_and_leaves_text_alone() {
+        assert_eq!(OutputStyle::default(), OutputStyle::Rich);
+        assert_eq!(OutputStyle::Rich.apply("✅ Done"), "✅ Done");
+        assert_eq!(OutputStyle::Plain.apply("✅ Done"), "[ok] Done");
+    }
+}
@@ -0,0 +1,188 @@
+// SOLID: This module is part of the DOMAIN layer
+// Quiet hours: when customers must not be disturbed, for the whole shop
+// (nothing before 8:00 or after 21:00) and per customer (do-not-disturb,
+// which replaces the shop's window for that customer).
+//
+// A customer notice that falls in a quiet window is not dropped: it becomes
+// a DeferredNotification, kept in an outbox until the window opens. Times
+// are UTC, like the job scheduler's; a window may cross midnight.
+//
+// Staff notices (SLA breaches, pending refunds) are not customer notices
+// and are never deferred.
+
+use super::notice::{
+    FeedbackRequestNotice, NoticeRecipient, OrderCancelledNotice, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, RewardGrantedNotice,
+};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A daily window, from `start` (included) to `end` (excluded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// Whole hours, `QuietHours::between(21, 8)`; None unless both are 0-23
+    pub fn between(start_hour: u32, end_hour: u32) -> Option<Self> {
+        Some(Self::new(
+            NaiveTime::from_hms_opt(start_hour, 0, 0)?,
+            NaiveTime::from_hms_opt(end_hour, 0, 0)?,
+        ))
+    }
+
+    /// Is `at` inside the window? (An empty window, start == end, never is)
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let time = at.time();
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// When the window that `at` is in opens again (`at` itself outside it)
+    pub fn opens_after(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        if !self.contains(at) {
+            return at;
+        }
+        let today = at.date_naive().and_time(self.end).and_utc();
+        if today > at {
+            today
+        } else {
+            today + Duration::days(1)
+        }
+    }
+}
+
+/// The shop's quiet hours, and each customer's own
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuietHoursPolicy {
+    global: Option<QuietHours>,
+    /// By lowercase email
+    customers: HashMap<String, QuietHours>,
+}
+
+impl QuietHoursPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Quiet hours for every customer without their own
+    pub fn with_global(mut self, hours: QuietHours) -> Self {
+        self.global = Some(hours);
+        self
+    }
+
+    /// One customer's do-not-disturb window (instead of the global one)
+    pub fn with_customer(mut self, email: &str, hours: QuietHours) -> Self {
+        self.customers.insert(email.to_lowercase(), hours);
+        self
+    }
+
+    /// The window that applies to this customer, if any
+    pub fn for_customer(&self, email: &str) -> Option<QuietHours> {
+        self.customers.get(&email.to_lowercase()).copied().or(self.global)
+    }
+
+    /// None to notify now, or when to notify instead
+    pub fn defer_until(&self, email: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.for_customer(email)
+            .filter(|hours| hours.contains(now))
+            .map(|hours| hours.opens_after(now))
+    }
+}
+
+/// A customer notice held back by quiet hours
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "notice")]
+pub enum DeferredNotice {
+    OrderPlaced(OrderPlacedNotice),
+    OrderReady(OrderReadyNotice),
+    OrderCancelled(OrderCancelledNotice),
+    OrderNoShow(OrderNoShowNotice),
+    FeedbackRequested(FeedbackRequestNotice),
+    RewardGranted(RewardGrantedNotice),
+}
+
+impl DeferredNotice {
+    pub fn recipient(&self) -> &NoticeRecipient {
+        match self {
+            DeferredNotice::OrderPlaced(notice) => &notice.recipient,
+            DeferredNotice::OrderReady(notice) => &notice.recipient,
+            DeferredNotice::OrderCancelled(notice) => &notice.recipient,
+            DeferredNotice::OrderNoShow(notice) => &notice.recipient,
+            DeferredNotice::FeedbackRequested(notice) => &notice.recipient,
+            DeferredNotice::RewardGranted(notice) => &notice.recipient,
+        }
+    }
+}
+
+/// A deferred notice and when it may go out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeferredNotification {
+    pub id: Uuid,
+    pub notice: DeferredNotice,
+    pub deferred_at: DateTime<Utc>,
+    pub deliver_at: DateTime<Utc>,
+}
+
+impl DeferredNotification {
+    pub fn new(notice: DeferredNotice, now: DateTime<Utc>, deliver_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            notice,
+            deferred_at: now,
+            deliver_at,
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.deliver_at <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 15, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_windows_across_midnight() {
+        let night = QuietHours::between(21, 8).unwrap();
+        assert!(night.contains(at(23, 30)) && night.contains(at(7, 59)));
+        assert!(!night.contains(at(8, 0)) && !night.contains(at(20, 59)));
+        assert_eq!(night.opens_after(at(6, 0)), at(8, 0));
+        assert_eq!(night.opens_after(at(22, 0)), at(8, 0) + Duration::days(1));
+        assert_eq!(night.opens_after(at(12, 0)), at(12, 0));
+
+        let lunch = QuietHours::between(12, 14).unwrap();
+        assert!(lunch.contains(at(13, 0)) && !lunch.contains(at(14, 0)));
+        assert!(QuietHours::between(24, 8).is_none());
+    }
+
+    #[test]
+    fn test_customer_window_replaces_the_global_one() {
+        let policy = QuietHoursPolicy::new()
+            .with_global(QuietHours::between(21, 8).unwrap())
+            .with_customer("Night.Owl@example.com", QuietHours::between(9, 17).unwrap());
+
+        let tomorrow_8 = at(8, 0) + Duration::days(1);
+        assert_eq!(policy.defer_until("ada@example.com", at(22, 0)), Some(tomorrow_8));
+        assert_eq!(policy.defer_until("night.owl@example.com", at(22, 0)), None);
+        assert_eq!(policy.defer_until("night.owl@example.com", at(10, 0)), Some(at(17, 0)));
+        assert_eq!(QuietHoursPolicy::new().defer_until("ada@example.com", at(3, 0)), None);
+    }
+}
@@ -0,0 +1,87 @@
+// SOLID: This module is part of the DOMAIN layer
+// A refund request records who asked for money back, who decided, and what
+// the payment processor returned. Deciding whether approval is needed, and
+// who may give it, is RefundService's job.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::money::Money;
+use super::order::Order;
+use super::staff::StaffMember;
+
+/// Where a refund request is in its lifecycle
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RefundStatus {
+    PendingApproval, // Above the limit, waiting for a manager
+    Refunded,        // Money returned through the payment processor
+    Rejected,        // A manager said no
+}
+
+/// Money back on a paid order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundRequest {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    /// The original charge being refunded
+    pub payment_id: String,
+    pub amount: Money,
+    pub reason: String,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub status: RefundStatus,
+    /// Who approved or rejected it (the requester, when no approval was needed)
+    pub decided_by: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    /// The payment processor's reference for the refund
+    pub refund_id: Option<String>,
+    pub rejection_reason: Option<String>,
+}
+
+impl RefundRequest {
+    /// A new request, waiting for approval
+    ///
+    /// Returns None if the order was never paid (there's nothing to refund).
+    pub fn new(
+        order: &Order,
+        amount: Money,
+        reason: &str,
+        requested_by: &StaffMember,
+    ) -> Option<Self> {
+        let payment_id = order.payment_id.clone()?;
+        Some(Self {
+            id: Uuid::new_v4(),
+            order_id: order.id,
+            payment_id,
+            amount,
+            reason: reason.to_string(),
+            requested_by: requested_by.name.clone(),
+            requested_at: Utc::now(),
+            status: RefundStatus::PendingApproval,
+            decided_by: None,
+            decided_at: None,
+            refund_id: None,
+            rejection_reason: None,
+        })
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == RefundStatus::PendingApproval
+    }
+
+    /// The money went back to the customer
+    pub fn mark_refunded(&mut self, decided_by: &StaffMember, refund_id: String) {
+        self.status = RefundStatus::Refunded;
+        self.decided_by = Some(decided_by.name.clone());
+        self.decided_at = Some(Utc::now());
+        self.refund_id = Some(refund_id);
+    }
+
+    pub fn reject(&mut self, decided_by: &StaffMember, reason: &str) {
+        self.status = RefundStatus::Rejected;
+        self.decided_by = Some(decided_by.name.clone());
+        self.decided_at = Some(Utc::now());
+        self.rejection_reason = Some(reason.to_string());
+    }
+}
@@ -0,0 +1,32 @@
+// SOLID: This module is part of the DOMAIN layer
+// A staff member is who is at the register. What each role may do is a
+// policy (services/authorization.rs), not a property of the person.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A job at the shop, from least to most trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Role {
+    Barista,
+    ShiftLead,
+    Manager,
+}
+
+/// Someone working at the shop
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaffMember {
+    pub id: Uuid,
+    pub name: String,
+    pub role: Role,
+}
+
+impl StaffMember {
+    pub fn new(name: &str, role: Role) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            role,
+        }
+    }
+}
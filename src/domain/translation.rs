@@ -0,0 +1,196 @@
+// SOLID: This module is part of the DOMAIN layer
+// Beverage names in the customer's language, with a fallback chain.
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Beverages keep their one English name: it is what orders store, what
+// price books, recipes and reports match on. A translation is configuration
+// (usually loaded from JSON) looked up at the last moment, when a name is
+// shown to someone: the kiosk menu, a receipt, a customer notice.
+//
+// A name is looked up for the locale ("fr-CH"), then its language ("fr");
+// with neither, the English name is shown. A customized name is looked up
+// whole first ("Coffee (+2 shots)"), then by what comes before the
+// parenthesis ("Coffee"), keeping the rest as it is. Sizes translate like
+// names do: { "Medium": { "fr": { "name": "Moyen" } } }.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A language, optionally narrowed to a region: "fr", "fr-ch"
+///
+/// Tags are lowercase with a dash; "fr_CH.UTF-8" (a POSIX LANG) reads as "fr-ch".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale(String);
+
+impl Locale {
+    pub fn parse(tag: &str) -> Self {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        let tag = tag.trim().replace('_', "-").to_lowercase();
+        match tag.as_str() {
+            // The POSIX defaults mean "no preference"
+            "" | "c" | "posix" => Self::default(),
+            _ => Self(tag),
+        }
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.0
+    }
+
+    /// Most specific first: "fr-ch", then "fr"
+    pub fn fallback_chain(&self) -> Vec<&str> {
+        let mut chain = vec![self.0.as_str()];
+        let mut tag = self.0.as_str();
+        while let Some((parent, _)) = tag.rsplit_once('-') {
+            chain.push(parent);
+            tag = parent;
+        }
+        chain
+    }
+}
+
+impl Default for Locale {
+    /// English, the language beverages are named in
+    fn default() -> Self {
+        Self("en".to_string())
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A beverage's name, and optionally a line about it, in one language
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalizedText {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// English name -> locale -> translation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MenuTranslations {
+    entries: BTreeMap<String, BTreeMap<String, LocalizedText>>,
+}
+
+impl MenuTranslations {
+    /// Nothing translated: every name shows in English
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_name(mut self, english: &str, locale: &str, name: &str) -> Self {
+        let text = LocalizedText {
+            name: name.to_string(),
+            description: None,
+        };
+        self.entries
+            .entry(english.to_string())
+            .or_default()
+            .insert(Locale::parse(locale).0, text);
+        self
+    }
+
+    /// A line about the beverage, for menus; its name must be translated first
+    pub fn with_description(mut self, english: &str, locale: &str, description: &str) -> Self {
+        if let Some(text) = self
+            .entries
+            .get_mut(english)
+            .and_then(|by_locale| by_locale.get_mut(Locale::parse(locale).tag()))
+        {
+            text.description = Some(description.to_string());
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The closest translation of `english` along `locale`'s fallback chain
+    pub fn text(&self, english: &str, locale: &Locale) -> Option<&LocalizedText> {
+        let by_locale = self.entries.get(english)?;
+        locale.fallback_chain().into_iter().find_map(|tag| {
+            by_locale
+                .iter()
+                .find(|(key, _)| Locale::parse(key).tag() == tag)
+                .map(|(_, text)| text)
+        })
+    }
+
+    /// "Café (+2 shots)"; the English name when nothing matches
+    pub fn name(&self, english: &str, locale: &Locale) -> String {
+        if let Some(text) = self.text(english, locale) {
+            return text.name.clone();
+        }
+        match english.split_once(" (") {
+            Some((base, rest)) => match self.text(base, locale) {
+                Some(text) => format!("{} ({}", text.name, rest),
+                None => english.to_string(),
+            },
+            None => english.to_string(),
+        }
+    }
+
+    pub fn description(&self, english: &str, locale: &Locale) -> Option<&str> {
+        self.text(english, locale)?.description.as_deref()
+    }
+
+    /// A beverage's description ("Coffee (Medium)") with its name and size translated
+    ///
+    /// Descriptions that don't start with the name are left as they are.
+    pub fn describe(&self, beverage_name: &str, description: &str, locale: &Locale) -> String {
+        let Some(rest) = description.strip_prefix(beverage_name) else {
+            return description.to_string();
+        };
+        let rest = match rest.strip_prefix(" (").and_then(|size| size.strip_suffix(')')) {
+            Some(size) => format!(" ({})", self.name(size, locale)),
+            None => rest.to_string(),
+        };
+        format!("{}{}", self.name(beverage_name, locale), rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_fallback_chain() {
+        let swiss = Locale::parse("fr_CH.UTF-8");
+        assert_eq!(swiss.tag(), "fr-ch");
+        assert_eq!(swiss.fallback_chain(), vec!["fr-ch", "fr"]);
+        assert_eq!(Locale::parse("C"), Locale::default());
+    }
+
+    #[test]
+    fn test_names_fall_back_to_language_then_english() {
+        let translations = MenuTranslations::new()
+            .with_name("Coffee", "fr", "Café")
+            .with_name("Coffee", "fr-CH", "Kafi")
+            .with_description("Coffee", "fr", "Un espresso allongé")
+            .with_name("Medium", "fr", "Moyen");
+        let french = Locale::parse("fr-FR");
+        let swiss = Locale::parse("fr-ch");
+
+        assert_eq!(translations.name("Coffee", &french), "Café");
+        assert_eq!(translations.name("Coffee", &swiss), "Kafi");
+        assert_eq!(translations.name("Coffee (+2 shots)", &french), "Café (+2 shots)");
+        assert_eq!(translations.name("Green Tea", &french), "Green Tea");
+        assert_eq!(translations.name("Coffee", &Locale::parse("de")), "Coffee");
+        assert_eq!(translations.description("Coffee", &swiss), None);
+        assert_eq!(
+            translations.describe("Coffee", "Coffee (Medium)", &french),
+            "Café (Moyen)"
+        );
+        assert_eq!(
+            translations.describe("Coffee", "Coffee (Large)", &french),
+            "Café (Large)"
+        );
+    }
+}
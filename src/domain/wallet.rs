@@ -0,0 +1,176 @@
+// SOLID: This module is part of the DOMAIN layer
+// A customer's store credit: every grant and every spend, so the balance is
+// always the sum of the entries and accounting can book each of them. Where
+// wallets are kept is a StoreCredit adapter's job, paying with one is
+// WalletPayment's.
+//
+// A wallet holds one currency, its first entry's; the StoreCredit adapters
+// refuse amounts in any other.
+
+use super::money::{Currency, Money};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What moved the balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WalletEntryKind {
+    /// Credit given (a complaint's remedy, a promotion)
+    Granted,
+    /// Credit used to pay for an order
+    Spent,
+    /// Credit given back (the order paid with it was refunded)
+    Restored,
+}
+
+/// One movement, always a positive amount (the kind says which way)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletEntry {
+    /// `CREDIT-...` for grants, `WALLET-...` for spends, `WALLET-RESTORE-...`
+    pub reference: String,
+    pub kind: WalletEntryKind,
+    pub amount: Money,
+    pub at: DateTime<Utc>,
+    /// Why credit was granted, or what was paid
+    pub note: String,
+    /// The order paid (Spent) or refunded (Restored)
+    pub order_id: Option<Uuid>,
+}
+
+impl WalletEntry {
+    /// The entry's effect on the balance
+    pub fn signed_amount(&self) -> Money {
+        match self.kind {
+            WalletEntryKind::Granted | WalletEntryKind::Restored => self.amount,
+            WalletEntryKind::Spent => -self.amount,
+        }
+    }
+}
+
+/// Store credit of one customer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wallet {
+    /// Lowercased, so "Ada@Example.com" and "ada@example.com" share a wallet
+    pub customer_email: String,
+    pub entries: Vec<WalletEntry>,
+    /// Why the credit can't be spent for now (a disputed payment), None if it can
+    #[serde(default)]
+    pub frozen: Option<String>,
+}
+
+impl Wallet {
+    pub fn new(customer_email: &str) -> Self {
+        Self {
+            customer_email: customer_email.to_lowercase(),
+            entries: Vec::new(),
+            frozen: None,
+        }
+    }
+
+    /// The currency of its entries (None before the first)
+    pub fn currency(&self) -> Option<Currency> {
+        self.entries.first().map(|e| e.amount.currency)
+    }
+
+    /// What the customer can still spend (zero US dollars for an empty wallet)
+    pub fn balance(&self) -> Money {
+        let zero = Money::zero(self.currency().unwrap_or_default());
+        self.entries.iter().fold(zero, |sum, e| sum + e.signed_amount())
+    }
+
+    /// What the customer may spend right now: nothing while frozen
+    pub fn spendable(&self) -> Money {
+        let balance = self.balance();
+        if self.frozen.is_some() { Money::zero(balance.currency) } else { balance }
+    }
+
+    /// Stop spending (grants and restores still land) until `unfreeze`
+    pub fn freeze(&mut self, reason: &str) {
+        self.frozen = Some(reason.to_string());
+    }
+
+    pub fn unfreeze(&mut self) {
+        self.frozen = None;
+    }
+
+    pub fn grant(&mut self, amount: Money, reason: &str) -> &WalletEntry {
+        self.push("CREDIT", WalletEntryKind::Granted, amount, reason, None)
+    }
+
+    /// Pay `amount` of `order_id` with credit
+    ///
+    /// Returns the spendable balance as the error if it doesn't cover `amount`
+    /// (nor does a balance in another currency).
+    pub fn spend(&mut self, amount: Money, order_id: Uuid) -> Result<&WalletEntry, Money> {
+        let balance = self.spendable();
+        if amount.currency != balance.currency || amount > balance {
+            return Err(balance);
+        }
+        let note = format!("Order {}", &order_id.simple().to_string()[..8]);
+        Ok(self.push("WALLET", WalletEntryKind::Spent, amount, &note, Some(order_id)))
+    }
+
+    /// Give back `amount` of an earlier spend (at most what it took)
+    ///
+    /// None if there is no such spend, or `amount` is in another currency.
+    pub fn restore(&mut self, spend_reference: &str, amount: Money) -> Option<&WalletEntry> {
+        let spent = self
+            .entries
+            .iter()
+            .find(|e| e.kind == WalletEntryKind::Spent && e.reference == spend_reference)
+            .filter(|e| e.amount.currency == amount.currency)?;
+        let order_id = spent.order_id;
+        let amount = amount.min(spent.amount);
+        let note = format!("Refund of {}", spend_reference);
+        Some(self.push("WALLET-RESTORE", WalletEntryKind::Restored, amount, &note, order_id))
+    }
+
+    fn push(
+        &mut self,
+        prefix: &str,
+        kind: WalletEntryKind,
+        amount: Money,
+        note: &str,
+        order_id: Option<Uuid>,
+    ) -> &WalletEntry {
+        self.entries.push(WalletEntry {
+            reference: format!("{}-{}", prefix, Uuid::new_v4()),
+            kind,
+            amount,
+            at: Utc::now(),
+            note: note.to_string(),
+            order_id,
+        });
+        self.entries.last().expect("just pushed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_follows_the_entries() {
+        let mut wallet = Wallet::new("Ada@Example.com");
+        assert_eq!(wallet.customer_email, "ada@example.com");
+        wallet.grant(Money::usd(5.0), "Cold drink");
+        let order_id = Uuid::new_v4();
+
+        assert_eq!(wallet.spend(Money::usd(6.0), order_id), Err(Money::usd(5.0)));
+        let francs = Money::new(100, Currency::CHF);
+        assert_eq!(wallet.spend(francs, order_id), Err(Money::usd(5.0)));
+        let reference = wallet.spend(Money::usd(3.5), order_id).unwrap().reference.clone();
+        assert!(reference.starts_with("WALLET-"));
+        assert_eq!(wallet.balance(), Money::usd(1.5));
+
+        // Never more back than the spend took
+        let restored = wallet.restore(&reference, Money::usd(10.0)).unwrap();
+        assert_eq!(restored.amount, Money::usd(3.5));
+        assert_eq!(wallet.balance(), Money::usd(5.0));
+        assert!(wallet.restore("WALLET-unknown", Money::usd(1.0)).is_none());
+
+        wallet.freeze("Chargeback CB-1");
+        assert_eq!((wallet.balance(), wallet.spendable()), (Money::usd(5.0), Money::cents(0)));
+        assert_eq!(wallet.spend(Money::usd(1.0), order_id), Err(Money::cents(0)));
+    }
+}
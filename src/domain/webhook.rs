@@ -0,0 +1,111 @@
+// SOLID: This module is part of the DOMAIN layer
+// A webhook subscription is plain data plus the rule deciding which events it wants.
+// Storing it, encoding events (PayloadSerializer) and sending HTTP requests
+// are someone else's job.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An external system that wants to hear about order events
+///
+/// SOLID PRINCIPLE: Single Responsibility Principle (SRP)
+/// It knows WHERE to send (url), HOW to prove it's us (secret)
+/// and WHAT it wants (event filter). Nothing about delivery.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    /// Shared secret used to sign each delivery (HMAC-SHA256)
+    pub secret: String,
+    /// Event topics ("order.placed"), family wildcards ("order.*") or "*".
+    /// Empty means every event.
+    pub events: Vec<String>,
+    /// Inactive subscriptions are kept but receive nothing
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    /// Wire format the receiver expects ("json", "xml", "cloudevents"...);
+    /// None means the dispatcher's default
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// What gets delivered: a topic plus the message as JSON
+///
+/// How it is written on the wire (plain JSON, XML, a CloudEvents envelope)
+/// is up to the subscription's PayloadSerializer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WebhookEvent {
+    /// Unique per event, lets receivers ignore duplicates
+    pub id: Uuid,
+    pub topic: String,
+    pub occurred_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl WebhookSubscription {
+    /// Create an active subscription
+    pub fn new(url: String, secret: String, events: Vec<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            url,
+            secret,
+            events,
+            active: true,
+            created_at: Utc::now(),
+            format: None,
+        }
+    }
+
+    /// Deliver in this wire format instead of the dispatcher's default
+    pub fn with_format(mut self, format: &str) -> Self {
+        self.format = Some(format.to_string());
+        self
+    }
+
+    /// Should an event with this topic be delivered here?
+    pub fn wants(&self, topic: &str) -> bool {
+        if !self.active {
+            return false;
+        }
+        if self.events.is_empty() {
+            return true;
+        }
+
+        self.events.iter().any(|filter| match filter.strip_suffix('*') {
+            Some(prefix) => topic.starts_with(prefix),
+            None => filter == topic,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(events: &[&str]) -> WebhookSubscription {
+        WebhookSubscription::new(
+            "https://example.com/hook".to_string(),
+            "s3cret".to_string(),
+            events.iter().map(|e| e.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_event_filters() {
+        assert!(subscription(&[]).wants("order.placed"));
+        assert!(subscription(&["*"]).wants("order.ready"));
+        assert!(subscription(&["order.*"]).wants("order.cancelled"));
+        assert!(subscription(&["order.ready"]).wants("order.ready"));
+        assert!(!subscription(&["order.ready"]).wants("order.placed"));
+    }
+
+    #[test]
+    fn test_inactive_wants_nothing() {
+        let mut sub = subscription(&[]);
+        sub.active = false;
+        assert!(!sub.wants("order.placed"));
+    }
+}
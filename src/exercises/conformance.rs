@@ -0,0 +1,299 @@
+// SOLID: Conformance checks behind `verify`
+//
+// The trait contracts (ports/*.rs) written as code. Every check gets a fresh
+// setup, so one failure doesn't cascade into the next, and the last check of
+// each group plugs the student's type into a real OrderService: a type that
+// passes is a drop-in replacement for the adapters the crate ships (LSP).
+
+use super::{check, CheckResult};
+use crate::adapters::{CashPayment, ConsoleNotifier, MemoryOrderRepository};
+use crate::domain::{
+    Beverage, Coffee, Customer, Money, Order, OrderCancelledNotice, OrderItem, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, OrderStatus, Size,
+};
+use crate::ports::{NotificationError, Notifier, OrderRepository, PaymentProcessor, RepositoryError};
+use crate::services::OrderService;
+use std::io;
+
+const SIZES: [Size; 3] = [Size::Small, Size::Medium, Size::Large];
+
+fn customer(email: &str) -> Customer {
+    Customer::new("Student".to_string(), email.to_string(), None)
+}
+
+fn order(email: &str) -> Order {
+    Order::new(
+        customer(email),
+        vec![OrderItem {
+            beverage_name: "Coffee".to_string(),
+            beverage_description: "Coffee (Medium)".to_string(),
+            price: Money::usd(3.50),
+            quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        }],
+    )
+}
+
+fn coffee() -> Vec<Box<dyn Beverage>> {
+    vec![Box::new(Coffee {
+        size: Size::Medium,
+        extra_shots: 0,
+    })]
+}
+
+fn quiet_console() -> ConsoleNotifier<io::Sink> {
+    ConsoleNotifier::with_writer(io::sink())
+}
+
+/// exercise-1: a new Beverage, built by `make` for each size
+pub fn beverage(make: impl Fn(Size) -> Box<dyn Beverage>, name: &str) -> Vec<CheckResult> {
+    vec![
+        check(&format!("is named \"{}\"", name), || {
+            let actual = make(Size::Medium).name();
+            if actual.contains(name) {
+                Ok(())
+            } else {
+                Err(format!("name() returned \"{}\"", actual))
+            }
+        }),
+        check("keeps the size it was given", || {
+            match SIZES.iter().find(|&&size| make(size).size() != size) {
+                Some(size) => Err(format!("asked for {:?}, got {:?}", size, make(*size).size())),
+                None => Ok(()),
+            }
+        }),
+        check("costs more when bigger (and something when small)", || {
+            let prices: Vec<Money> = SIZES.iter().map(|&size| make(size).price()).collect();
+            if prices[0].cents > 0 && prices[0] < prices[1] && prices[1] < prices[2] {
+                Ok(())
+            } else {
+                let shown: Vec<String> = prices.iter().map(Money::to_string).collect();
+                Err(format!("small/medium/large cost {}", shown.join(" / ")))
+            }
+        }),
+        check("is charged its price by OrderService", || {
+            let latte = make(Size::Large);
+            let price = latte.price();
+            let mut service =
+                OrderService::new(MemoryOrderRepository::new(), CashPayment, quiet_console());
+            let order = service
+                .place_order(customer("student@example.com"), vec![latte])
+                .map_err(|e| e.to_string())?;
+            if order.total_price == price {
+                Ok(())
+            } else {
+                Err(format!("order total {}, price() {}", order.total_price, price))
+            }
+        }),
+    ]
+}
+
+/// exercise-2: a new PaymentProcessor, `make` returns a fresh one per check
+pub fn payment(make: impl Fn() -> Box<dyn PaymentProcessor>) -> Vec<CheckResult> {
+    vec![
+        check("has its own payment_method_name", || {
+            match make().payment_method_name() {
+                "Unknown Payment Method" => Err("still the default name".to_string()),
+                "" => Err("the name is empty".to_string()),
+                _ => Ok(()),
+            }
+        }),
+        check("returns a payment id for a charge", || {
+            match make().process_payment(Money::usd(4.20)).map_err(|e| e.to_string())? {
+                id if id.trim().is_empty() => Err("the payment id is empty".to_string()),
+                _ => Ok(()),
+            }
+        }),
+        check("gives each charge its own id", || {
+            let payment = make();
+            let first = payment.process_payment(Money::usd(4.20)).map_err(|e| e.to_string())?;
+            let second = payment.process_payment(Money::usd(4.20)).map_err(|e| e.to_string())?;
+            if first != second {
+                Ok(())
+            } else {
+                Err(format!("two charges both returned '{}'", first))
+            }
+        }),
+        check("refuses a negative amount", || match make().process_payment(Money::usd(-5.0)) {
+            Ok(id) => Err(format!("charged -$5.00 as '{}'", id)),
+            Err(_) => Ok(()),
+        }),
+        check("pays for an order in OrderService", || {
+            let mut service =
+                OrderService::new(MemoryOrderRepository::new(), make(), quiet_console());
+            let order = service
+                .place_order(customer("student@example.com"), coffee())
+                .map_err(|e| e.to_string())?;
+            match (&order.status, &order.payment_id) {
+                (OrderStatus::Paid, Some(_)) => Ok(()),
+                (status, _) => Err(format!("the order ended {}", status)),
+            }
+        }),
+    ]
+}
+
+/// exercise-3: a new customer-facing Notifier, `make` returns a fresh one per check
+pub fn notifier(make: impl Fn() -> Box<dyn Notifier>) -> Vec<CheckResult> {
+    vec![
+        check("sends the four customer notices", || {
+            let notifier = make();
+            let order = order("student@example.com");
+            notifier
+                .notify_order_placed(&OrderPlacedNotice::from_order(&order))
+                .and_then(|_| notifier.notify_order_ready(&OrderReadyNotice::from_order(&order)))
+                .and_then(|_| {
+                    notifier.notify_order_cancelled(&OrderCancelledNotice::from_order(&order))
+                })
+                .and_then(|_| notifier.notify_order_no_show(&OrderNoShowNotice::from_order(&order)))
+                .map_err(|e| e.to_string())
+        }),
+        check("rejects an address without '@' as InvalidRecipient", || {
+            let notice = OrderPlacedNotice::from_order(&order("not-an-address"));
+            match make().notify_order_placed(&notice) {
+                Err(NotificationError::InvalidRecipient(_)) => Ok(()),
+                Err(e) => Err(format!("wrong error: {}", e)),
+                Ok(()) => Err("the notice was accepted".to_string()),
+            }
+        }),
+        check("notifies through OrderService", || {
+            let mut service =
+                OrderService::new(MemoryOrderRepository::new(), CashPayment, make());
+            let order = service
+                .place_order(customer("student@example.com"), coffee())
+                .map_err(|e| e.to_string())?;
+            service.cancel_order(order.id).map_err(|e| e.to_string())
+        }),
+    ]
+}
+
+/// exercise-4: a new OrderRepository, `make` returns an empty one per check
+pub fn repository(make: impl Fn() -> Box<dyn OrderRepository>) -> Vec<CheckResult> {
+    let storage = |e: RepositoryError| e.to_string();
+    vec![
+        check("starts empty", || match make().list_all().map_err(storage)?.len() {
+            0 => Ok(()),
+            n => Err(format!("a new repository lists {} order(s)", n)),
+        }),
+        check("finds a saved order by id", || {
+            let mut repository = make();
+            let order = order("student@example.com");
+            repository.save(&order).map_err(storage)?;
+            match repository.find_by_id(order.id).map_err(storage)? {
+                Some(found) if found.id == order.id => Ok(()),
+                Some(_) => Err("find_by_id returned another order".to_string()),
+                None => Err("find_by_id returned None after save".to_string()),
+            }
+        }),
+        check("returns Ok(None) for an unknown id", || {
+            match make().find_by_id(uuid::Uuid::new_v4()) {
+                Ok(None) => Ok(()),
+                Ok(Some(_)) => Err("found an order that was never saved".to_string()),
+                Err(e) => Err(format!("an unknown id is not an error: {}", e)),
+            }
+        }),
+        check("refuses to save the same id twice (AlreadyExists)", || {
+            let mut repository = make();
+            let order = order("student@example.com");
+            repository.save(&order).map_err(storage)?;
+            match repository.save(&order) {
+                Err(RepositoryError::AlreadyExists(_)) => Ok(()),
+                Err(e) => Err(format!("wrong error: {}", e)),
+                Ok(()) => Err("the duplicate was saved".to_string()),
+            }
+        }),
+        check("finds orders by customer email", || {
+            let mut repository = make();
+            repository.save(&order("ada@example.com")).map_err(storage)?;
+            repository.save(&order("ada@example.com")).map_err(storage)?;
+            repository.save(&order("bob@example.com")).map_err(storage)?;
+            match repository.find_by_customer_email("ada@example.com").map_err(storage)?.len() {
+                2 => Ok(()),
+                n => Err(format!("found {} of ada's 2 orders", n)),
+            }
+        }),
+        check("stores updates", || {
+            let mut repository = make();
+            let mut order = order("student@example.com");
+            repository.save(&order).map_err(storage)?;
+            order.mark_as_paid("PAY-1".to_string()).map_err(|e| e.to_string())?;
+            repository.update(&order).map_err(storage)?;
+            match repository.find_by_id(order.id).map_err(storage)? {
+                Some(found) if found.status == OrderStatus::Paid => Ok(()),
+                Some(found) => Err(format!("still {} after update", found.status)),
+                None => Err("the order disappeared".to_string()),
+            }
+        }),
+        check("refuses to update an unknown order (NotFound)", || {
+            match make().update(&order("student@example.com")) {
+                Err(RepositoryError::NotFound(_)) => Ok(()),
+                Err(e) => Err(format!("wrong error: {}", e)),
+                Ok(()) => Err("updated an order that was never saved".to_string()),
+            }
+        }),
+        check("deletes once (true, then false)", || {
+            let mut repository = make();
+            let order = order("student@example.com");
+            repository.save(&order).map_err(storage)?;
+            let first = repository.delete(order.id).map_err(storage)?;
+            let second = repository.delete(order.id).map_err(storage)?;
+            match (first, second) {
+                (true, false) => Ok(()),
+                other => Err(format!("delete returned {:?}", other)),
+            }
+        }),
+        check("backs an OrderService", || {
+            let mut service = OrderService::new(make(), CashPayment, quiet_console());
+            let order = service
+                .place_order(customer("student@example.com"), coffee())
+                .map_err(|e| e.to_string())?;
+            service.cancel_order(order.id).map_err(|e| e.to_string())?;
+            // Paid, so cancelling it refunds it
+            match service.get_order(order.id).map_err(|e| e.to_string())?.status {
+                OrderStatus::Refunded => Ok(()),
+                status => Err(format!("the stored order is {}", status)),
+            }
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::CreditCardPayment;
+
+    fn failed(results: &[CheckResult]) -> Vec<&str> {
+        results
+            .iter()
+            .filter(|r| !r.passed())
+            .map(|r| r.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_shipped_adapters_pass_their_contracts() {
+        let coffee = |size| -> Box<dyn Beverage> {
+            Box::new(Coffee {
+                size,
+                extra_shots: 0,
+            })
+        };
+        assert!(failed(&beverage(coffee, "Coffee")).is_empty());
+        let card = || -> Box<dyn PaymentProcessor> {
+            Box::new(CreditCardPayment::new("https://gateway.example.com".to_string()))
+        };
+        assert!(failed(&payment(card)).is_empty());
+        assert!(failed(&repository(|| Box::new(MemoryOrderRepository::new()))).is_empty());
+    }
+
+    #[test]
+    fn test_contract_breaches_are_named() {
+        // CashPayment takes any amount, the console prints to any address
+        assert_eq!(failed(&payment(|| Box::new(CashPayment))), vec!["refuses a negative amount"]);
+        assert_eq!(
+            failed(&notifier(|| Box::new(quiet_console()))),
+            vec!["rejects an address without '@' as InvalidRecipient"]
+        );
+    }
+}
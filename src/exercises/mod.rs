@@ -0,0 +1,178 @@
+// SOLID: Exercises - student tasks with a conformance check
+//
+// Each exercise asks for one extension of the system, the way the comments
+// all over this crate describe it ("to add a Latte, implement Beverage").
+// The student writes the code in `solutions.rs`, then runs
+//
+//     cargo run -- verify exercise-2
+//
+// and the exercise's conformance checks (conformance.rs, not meant to be
+// read first) run against their type. Nothing else in the crate changes,
+// which is the lesson:
+//
+// 1. OPEN-CLOSED PRINCIPLE (OCP):
+//    A Latte, a payment method, a notifier: new types, no edits elsewhere.
+//
+// 2. LISKOV SUBSTITUTION PRINCIPLE (LSP):
+//    The checks are the trait contracts written as code. A type that
+//    compiles but breaks a contract (duplicates saved twice, a negative
+//    amount charged) fails here, not in production.
+//
+// Why EmailNotifier and not SmsNotifier? Notices carry no phone number on
+// purpose (see domain/notice.rs): an SMS channel would first need a change
+// to the data the notifier receives. Discuss it in class.
+
+mod conformance;
+pub mod solutions;
+
+use crate::domain::Size;
+use std::panic::{self, AssertUnwindSafe};
+
+/// One line of a verification: what was checked, and why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    pub name: String,
+    pub outcome: Result<(), String>,
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+/// What `verify` found
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// The function in solutions.rs still returns None
+    NotStarted,
+    Checked(Vec<CheckResult>),
+}
+
+impl Verdict {
+    pub fn passed(&self) -> bool {
+        match self {
+            Verdict::NotStarted => false,
+            Verdict::Checked(checks) => checks.iter().all(CheckResult::passed),
+        }
+    }
+}
+
+/// A task, and the checks that say it's done
+pub struct Exercise {
+    /// `exercise-N`, as typed after `verify`
+    pub id: &'static str,
+    pub title: &'static str,
+    pub principles: &'static str,
+    pub instructions: &'static str,
+    verify: fn() -> Verdict,
+}
+
+impl Exercise {
+    pub fn verify(&self) -> Verdict {
+        (self.verify)()
+    }
+}
+
+const EXERCISES: [Exercise; 4] = [
+    Exercise {
+        id: "exercise-1",
+        title: "Add a Latte",
+        principles: "OCP",
+        instructions: "Create a `Latte { size: Size }` implementing Beverage (name \"Latte\", \
+                       a base price of your choice), then return it from `solutions::latte`.",
+        verify: || match solutions::latte(Size::Medium) {
+            None => Verdict::NotStarted,
+            Some(_) => Verdict::Checked(conformance::beverage(
+                |size| solutions::latte(size).expect("latte() returned None"),
+                "Latte",
+            )),
+        },
+    },
+    Exercise {
+        id: "exercise-2",
+        title: "Implement MobilePayment",
+        principles: "OCP, LSP",
+        instructions: "Create a `MobilePayment` implementing PaymentProcessor: its own \
+                       payment_method_name, a unique id per charge, an error for negative \
+                       amounts. Return it from `solutions::mobile_payment`.",
+        verify: || match solutions::mobile_payment() {
+            None => Verdict::NotStarted,
+            Some(_) => Verdict::Checked(conformance::payment(|| {
+                solutions::mobile_payment().expect("mobile_payment() returned None")
+            })),
+        },
+    },
+    Exercise {
+        id: "exercise-3",
+        title: "Implement EmailNotifier",
+        principles: "ISP, LSP",
+        instructions: "Create an `EmailNotifier` implementing Notifier (printing the email is \
+                       enough). An address without '@' must give \
+                       NotificationError::InvalidRecipient. Return it from \
+                       `solutions::email_notifier`.",
+        verify: || match solutions::email_notifier() {
+            None => Verdict::NotStarted,
+            Some(_) => Verdict::Checked(conformance::notifier(|| {
+                solutions::email_notifier().expect("email_notifier() returned None")
+            })),
+        },
+    },
+    Exercise {
+        id: "exercise-4",
+        title: "Write your own OrderRepository",
+        principles: "LSP, DIP",
+        instructions: "Implement OrderRepository on a type of your own (a Vec is fine), \
+                       following every contract in ports/repository.rs. Return a new, empty \
+                       one from `solutions::order_repository`.",
+        verify: || match solutions::order_repository() {
+            None => Verdict::NotStarted,
+            Some(_) => Verdict::Checked(conformance::repository(|| {
+                solutions::order_repository().expect("order_repository() returned None")
+            })),
+        },
+    },
+];
+
+/// Every exercise, in order
+pub fn all() -> &'static [Exercise] {
+    &EXERCISES
+}
+
+/// By id (`exercise-3`) or number (`3`)
+pub fn find(id: &str) -> Option<&'static Exercise> {
+    let id = id.trim();
+    EXERCISES
+        .iter()
+        .find(|exercise| exercise.id == id || exercise.id.strip_prefix("exercise-") == Some(id))
+}
+
+/// Run one check; a panic in the student's code fails the check instead of the run
+fn check(name: &str, run: impl FnOnce() -> Result<(), String>) -> CheckResult {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(format!("panicked: {}", message))
+    });
+    CheckResult {
+        name: name.to_string(),
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_unstarted_exercises() {
+        assert_eq!(find("3").map(|e| e.id), Some("exercise-3"));
+        assert_eq!(find("exercise-1").map(|e| e.title), Some("Add a Latte"));
+        assert!(find("exercise-9").is_none());
+        // The shipped solutions.rs is the student's blank page
+        assert!(all().iter().all(|e| e.verify() == Verdict::NotStarted));
+    }
+}
@@ -0,0 +1,41 @@
+// SOLID: Your solutions go here
+//
+// Each function below belongs to one exercise (`cargo run -- verify` lists
+// them). Write your type in this file (or anywhere in the crate), then make
+// the function return it. While a function returns None, its exercise shows
+// as "not started".
+//
+// Example, once exercise-1 is done:
+//
+//     #[derive(Debug)]
+//     pub struct Latte { pub size: Size }
+//
+//     impl Beverage for Latte { ... }
+//
+//     pub fn latte(size: Size) -> Option<Box<dyn Beverage>> {
+//         Some(Box::new(Latte { size }))
+//     }
+
+use crate::domain::{Beverage, Size};
+use crate::ports::{Notifier, OrderRepository, PaymentProcessor};
+
+/// exercise-1: a Latte of the given size
+pub fn latte(size: Size) -> Option<Box<dyn Beverage>> {
+    let _ = size;
+    None
+}
+
+/// exercise-2: your MobilePayment
+pub fn mobile_payment() -> Option<Box<dyn PaymentProcessor>> {
+    None
+}
+
+/// exercise-3: your EmailNotifier
+pub fn email_notifier() -> Option<Box<dyn Notifier>> {
+    None
+}
+
+/// exercise-4: a new, empty repository of yours (called once per check)
+pub fn order_repository() -> Option<Box<dyn OrderRepository>> {
+    None
+}
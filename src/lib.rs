@@ -55,6 +55,46 @@ pub mod services;
 // Adapters layer - concrete implementations
 pub mod adapters;
 
+// Message bus - typed in-process pub/sub between services
+pub mod bus;
+
+// Seed data - realistic demo orders for any repository
+pub mod seed;
+
+// Presentation - renders service output for humans (charts, ...)
+pub mod presentation;
+
+// Decorators - logging, timing, retry, metrics and caching around any port
+pub mod decorators;
+
+// Exercises - student tasks checked by `coffee-shop-solid verify exercise-N`
+pub mod exercises;
+
+// Anti-patterns - the monolithic design SOLID replaces, for comparison
+#[cfg(feature = "antipatterns")]
+pub mod antipatterns;
+
+// JSON Schema of the wire types, for generated clients (`schema dump`)
+#[cfg(feature = "schema")]
+pub mod schema;
+
+// API - OrderService over HTTP (`serve`): orders placed, read and cancelled as JSON
+#[cfg(feature = "http")]
+pub mod api;
+
+// Architecture - the layer rules above, checked against the source
+#[cfg(test)]
+mod architecture;
+
+// CLI - non-interactive subcommands (`coffee-shop-solid report daily`, ...)
+pub mod cli;
+
+// Stable API - what teaching material should import (`use coffee_shop_solid::v1::*`)
+pub mod v1;
+
+// Prelude - `use coffee_shop_solid::prelude::*` for examples and exercises
+pub mod prelude;
+
 // Re-export commonly used types for convenience
 pub use domain::{Beverage, Coffee, Customer, Order, OrderItem, Size, Smoothie, Tea};
 pub use ports::{Notifier, OrderRepository, PaymentProcessor};
@@ -65,29 +105,22 @@ pub use adapters::{CashPayment, ConsoleNotifier, MemoryOrderRepository};
 // QUICK START EXAMPLE
 // 
 // ```rust
-// use coffee_shop_solid::*;
+// use coffee_shop_solid::prelude::*;
 // 
 // // Create dependencies (dependency injection)
 // let repository = MemoryOrderRepository::new();
 // let payment = CashPayment;
-// let notifier = ConsoleNotifier;
+// let notifier = ConsoleNotifier::new();
 // 
 // // Create service (depends on abstractions, not concretions)
 // let mut service = OrderService::new(repository, payment, notifier);
 // 
-// // Create customer
-// let customer = Customer::new(
-//     "Alice".to_string(),
-//     "alice@example.com".to_string(),
-//     None,
-// );
+// // Create customer (alice@example.com)
+// let customer = Customer::named("Alice");
 // 
 // // Create order
 // let beverages: Vec<Box<dyn Beverage>> = vec![
-//     Box::new(Coffee {
-//         size: Size::Large,
-//         extra_shots: 1,
-//     }),
+//     Box::new(Coffee::large().with_extra_shots(1)),
 // ];
 // 
 // // Place order
@@ -3,6 +3,8 @@
 // This demo shows all SOLID principles working together in a real application.
 //
 // Run: cargo run
+// Or:  cargo run -- report weekly --demo   (one-shot CLI commands)
+// Or:  cargo run -- tutorial               (the principles below, swapped live)
 //
 // The demo allows us to:
 // 1. Create orders with different beverages
@@ -10,10 +12,35 @@
 // 3. See how components work together (demonstrating DIP - dependency inversion principle)
 // 4. Swap implementations easily (demonstrating LSP - Liskov substitution principle)
 
+use chrono::{Duration, NaiveTime};
+use coffee_shop_solid::adapters::SystemClock;
+use coffee_shop_solid::bus::MessageBus;
+use coffee_shop_solid::domain::{
+    BeverageError, Locale, MenuTranslations, Money, OrderLine, OutputStyle, PriceLine,
+    MAX_EXTRA_SHOTS,
+};
+use coffee_shop_solid::ports::Clock;
+use coffee_shop_solid::services::{
+    JobScheduler, LiveMetrics, NoShowExpiryJob, NoShowPolicy, NoShowReportJob, Schedule,
+    SlaPolicy, SlaWatchdogJob,
+};
 use coffee_shop_solid::*;
+use std::env;
 use std::io::{self, Write};
 
 fn main() {
+    // With arguments: run one CLI command and exit (see `cli::USAGE`)
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    // `--plain` (anywhere) holds for everything below: commands, notices, receipts
+    cli::output_style(&mut args).set_global();
+    if !args.is_empty() {
+        if let Err(e) = cli::run(&args, &mut io::stdout()) {
+            eprintln!("{}", OutputStyle::current().apply(&format!("❌ {}", e)));
+            std::process::exit(e.exit_code());
+        }
+        return;
+    }
+
     println!("☕ Coffee Shop Order System - SOLID Principles Demo");
     println!("====================================================\n");
 
@@ -22,26 +49,70 @@ fn main() {
     // OrderService depends on TRAITS, not these specific types
     let repository = MemoryOrderRepository::new();
     let payment = CashPayment;
-    let notifier = ConsoleNotifier;
+    let notifier = ConsoleNotifier::new();
 
     // Create the service
     // Notice: OrderService is generic over the trait bounds
     // It doesn't know it's using Memory, Cash, or Console
     // It only knows about OrderRepository, PaymentProcessor, and Notifier traits
-    let mut service = OrderService::new(repository, payment, notifier);
+    // The bus feeds the live numbers shown above the menu
+    let bus = MessageBus::new();
+    let mut live = LiveMetrics::subscribe(&bus);
+    // Beverage names in the configured locale ($COFFEE_SHOP_LOCALE, else $LANG),
+    // from translations.json when there is one; English otherwise
+    let locale = cli::configured_locale(&cli::ParsedArgs::default());
+    let translations = cli::load_translations(&cli::ParsedArgs::default()).unwrap_or_else(|e| {
+        eprintln!("⚠️  {}", e);
+        MenuTranslations::new()
+    });
+    let mut service = OrderService::new(repository, payment, notifier)
+        .with_message_bus(bus)
+        .with_translations(translations.clone(), locale.clone());
+
+    // Recurring jobs run between menu interactions (no background thread needed)
+    let clock = SystemClock;
+    let mut scheduler = JobScheduler::new();
+    scheduler
+        .register(
+            "no-show expiry",
+            Schedule::Every(Duration::minutes(1)),
+            NoShowExpiryJob::new(NoShowPolicy::default()),
+        )
+        .register(
+            "SLA watchdog",
+            Schedule::Every(Duration::minutes(1)),
+            // The demo's "manager channel" is the same console
+            SlaWatchdogJob::new(SlaPolicy::default(), ConsoleNotifier::new()),
+        )
+        .register(
+            "daily no-show report",
+            Schedule::DailyAt(NaiveTime::from_hms_opt(23, 0, 0).unwrap()),
+            NoShowReportJob,
+        );
 
     println!("📝 System initialized with:");
     println!("  - Storage: In-Memory (fast, no persistence)");
     println!("  - Payment: Cash");
     println!("  - Notifications: Console");
+    println!("  - Locale: {}", locale);
     println!("\n💡 TIP: To use different implementations, just change the initialization above!");
     println!("  Example: let repository = JsonOrderRepository::new(\"orders.json\".into())?;");
     println!("  Example: let payment = CreditCardPayment::new(...);");
+    println!("  Or run `cargo run -- tutorial` to watch each swap happen, with a quiz.");
     println!("\n");
 
     // Interactive demo loop
     loop {
+        for run in scheduler.run_pending(&mut service, &clock) {
+            match run.outcome {
+                Ok(summary) => println!("⏰ [{}] {}", run.name, summary),
+                Err(e) => println!("⏰ [{}] {}", run.name, e),
+            }
+        }
+
+        live.poll();
         println!("\n=== Main Menu ===");
+        println!("📈 {}", live.stats(clock.now()));
         println!("1. Place a new order");
         println!("2. List all orders");
         println!("3. Demonstrate OCP (Open-Closed Principle)");
@@ -55,7 +126,7 @@ fn main() {
         io::stdin().read_line(&mut input).unwrap();
 
         match input.trim() {
-            "1" => place_order_interactive(&mut service),
+            "1" => place_order_interactive(&mut service, &translations, &locale),
             "2" => list_orders(&service),
             "3" => demonstrate_ocp(),
             "4" => demonstrate_lsp(),
@@ -70,8 +141,11 @@ fn main() {
 }
 
 /// Interactive order placement
-fn place_order_interactive<R, P, N>(service: &mut OrderService<R, P, N>)
-where
+fn place_order_interactive<R, P, N>(
+    service: &mut OrderService<R, P, N>,
+    translations: &MenuTranslations,
+    locale: &Locale,
+) where
     R: OrderRepository,
     P: PaymentProcessor,
     N: Notifier,
@@ -92,7 +166,98 @@ where
 
     let customer = Customer::new(name.trim().to_string(), email.trim().to_string(), None);
 
-    // Get beverage order
+    // One line per beverage, each with its quantity, until the customer is done
+    let mut lines: Vec<OrderLine> = Vec::new();
+    loop {
+        if let Some(beverage) = choose_beverage() {
+            // Peak-hour surcharge, only in builds with `--features surge-pricing`.
+            // OrderService is unchanged: it just charges the wrapped beverage's price.
+            #[cfg(feature = "surge-pricing")]
+            let beverage = {
+                use coffee_shop_solid::domain::OrderStatus;
+                let queue_depth = service
+                    .list_all_orders()
+                    .map(|orders| {
+                        orders
+                            .iter()
+                            .filter(|o| {
+                                matches!(o.status, OrderStatus::Paid | OrderStatus::Preparing)
+                            })
+                            .count()
+                    })
+                    .unwrap_or(0);
+                services::SurgePricing::default().apply(beverage, queue_depth)
+            };
+
+            print!("Quantity (1-{}): ", u8::MAX);
+            io::stdout().flush().unwrap();
+            let mut quantity = String::new();
+            io::stdin().read_line(&mut quantity).unwrap();
+            let quantity = quantity.trim().parse().unwrap_or(1);
+            lines.push(OrderLine::new(beverage, quantity));
+        }
+
+        print!("Add another beverage? (y/n): ");
+        io::stdout().flush().unwrap();
+        let mut more = String::new();
+        io::stdin().read_line(&mut more).unwrap();
+        if more.trim().to_lowercase() != "y" {
+            break;
+        }
+    }
+    if lines.is_empty() {
+        println!("Nothing to order.");
+        return;
+    }
+
+    // Show price preview
+    println!("\n--- Order Summary ---");
+    let mut total = Money::zero(lines[0].beverage.currency());
+    for line in &lines {
+        let amount = line.beverage.price() * line.quantity as u32;
+        let description =
+            translations.describe(&line.beverage.name(), &line.beverage.description(), locale);
+        println!("{} x {}: ${:.2}", line.quantity, description, amount.to_major());
+        total += amount;
+    }
+    println!("Total: ${:.2}", total.to_major());
+    let receipt_lines: Vec<Vec<PriceLine>> =
+        lines.iter().map(|line| line.beverage.price_lines()).collect();
+
+    print!("\nConfirm order? (y/n): ");
+    io::stdout().flush().unwrap();
+    let mut confirm = String::new();
+    io::stdin().read_line(&mut confirm).unwrap();
+
+    if confirm.trim().to_lowercase() != "y" {
+        println!("Order cancelled.");
+        return;
+    }
+
+    // Place the order
+    // DIP: service.place_order_lines() works with any repository, payment, notifier
+    // It doesn't know we're using Memory, Cash, Console
+    match service.place_order_lines(customer, lines) {
+        Ok(order) => {
+            println!("\n✅ Order placed successfully!");
+            println!("Order ID: {}", order.id);
+            println!("Status: {}", order.status);
+            let receipt = presentation::render_localized_receipt(
+                &order,
+                &receipt_lines,
+                translations,
+                locale,
+            );
+            println!("\n{}", receipt);
+        }
+        Err(e) => {
+            println!("\n❌ Error placing order: {}", e);
+        }
+    }
+}
+
+/// Ask for one beverage; None (after saying why) if the shop can't make it
+fn choose_beverage() -> Option<Box<dyn Beverage>> {
     println!("\n=== Beverage Selection ===");
     println!("Available beverages:");
     println!("1. Coffee (Small: $2.80, Medium: $3.50, Large: $4.20)");
@@ -117,26 +282,31 @@ where
 
     // Create beverage
     // OCP: We can add new beverage types without modifying this code
-    let beverage: Box<dyn Beverage> = match beverage_choice.trim() {
+    // The builders refuse what the shop can't make (5 shots, no fruit...)
+    let built: Result<Box<dyn Beverage>, BeverageError> = match beverage_choice.trim() {
         "1" => {
-            print!("Extra shots? (0-3): ");
+            print!("Extra shots? (0-{}): ", MAX_EXTRA_SHOTS);
             io::stdout().flush().unwrap();
             let mut shots = String::new();
             io::stdin().read_line(&mut shots).unwrap();
             let extra_shots = shots.trim().parse().unwrap_or(0);
 
-            Box::new(Coffee { size, extra_shots })
+            Coffee::builder()
+                .size(size)
+                .extra_shots(extra_shots)
+                .build()
+                .map(|coffee| Box::new(coffee) as Box<dyn Beverage>)
         }
         "2" => {
-            print!("Tea variety (Green/Black/Herbal): ");
+            print!("Tea variety (Green/Black/Herbal/Earl Grey/Oolong): ");
             io::stdout().flush().unwrap();
             let mut variety = String::new();
             io::stdin().read_line(&mut variety).unwrap();
 
-            Box::new(Tea {
-                size,
-                variety: variety.trim().to_string(),
-            })
+            variety
+                .parse()
+                .and_then(|variety| Tea::builder().size(size).variety(variety).build())
+                .map(|tea| Box::new(tea) as Box<dyn Beverage>)
         }
         "3" => {
             print!("Fruits (comma-separated, e.g., Strawberry,Banana): ");
@@ -144,46 +314,22 @@ where
             let mut fruits_input = String::new();
             io::stdin().read_line(&mut fruits_input).unwrap();
 
-            let fruits: Vec<String> = fruits_input
-                .trim()
+            fruits_input
                 .split(',')
-                .map(|s| s.trim().to_string())
-                .collect();
-
-            Box::new(Smoothie { size, fruits })
+                .fold(Smoothie::builder().size(size), |builder, fruit| builder.fruit(fruit))
+                .build()
+                .map(|smoothie| Box::new(smoothie) as Box<dyn Beverage>)
         }
-        _ => Box::new(Coffee {
+        _ => Ok(Box::new(Coffee {
             size,
             extra_shots: 0,
-        }),
+        })),
     };
-
-    // Show price preview
-    println!("\n--- Order Summary ---");
-    println!("Beverage: {}", beverage.description());
-    println!("Price: ${:.2}", beverage.price());
-
-    print!("\nConfirm order? (y/n): ");
-    io::stdout().flush().unwrap();
-    let mut confirm = String::new();
-    io::stdin().read_line(&mut confirm).unwrap();
-
-    if confirm.trim().to_lowercase() != "y" {
-        println!("Order cancelled.");
-        return;
-    }
-
-    // Place the order
-    // DIP: service.place_order() works with any repository, payment, notifier
-    // It doesn't know we're using Memory, Cash, Console
-    match service.place_order(customer, vec![beverage]) {
-        Ok(order) => {
-            println!("\n✅ Order placed successfully!");
-            println!("Order ID: {}", order.id);
-            println!("Status: {:?}", order.status);
-        }
+    match built {
+        Ok(beverage) => Some(beverage),
         Err(e) => {
-            println!("\n❌ Error placing order: {}", e);
+            println!("\n❌ {}", e);
+            None
         }
     }
 }
@@ -204,15 +350,7 @@ where
             } else {
                 for order in orders {
                     println!("\n---------------------------");
-                    println!("Order ID: {}", order.id);
-                    println!(
-                        "Customer: {} ({})",
-                        order.customer.name, order.customer.email
-                    );
-                    println!("Items: {}", order.items.len());
-                    println!("Total: ${:.2}", order.total_price);
-                    println!("Status: {:?}", order.status);
-                    println!("Created: {}", order.created_at.format("%Y-%m-%d %H:%M:%S"));
+                    println!("{}", order);
                 }
             }
         }
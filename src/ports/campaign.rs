@@ -0,0 +1,57 @@
+// SOLID: This module defines the CampaignOutbox PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    CampaignService needs to queue recipients, list a campaign's and note
+//    how each went; nothing about orders or the mail server.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    CampaignService depends on this trait, not on a JSON file.
+//
+// The outbox is written BEFORE the mails go out: a campaign interrupted
+// halfway finds, on the next run, who still has to get it.
+
+use crate::domain::{DeliveryStatus, OutboxEntry};
+use std::error::Error;
+use std::fmt;
+
+/// Error type for outbox storage
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutboxError {
+    StorageFailed(String),
+    /// `mark` for a recipient that was never queued
+    UnknownRecipient(String),
+}
+
+impl fmt::Display for OutboxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutboxError::StorageFailed(msg) => write!(f, "Outbox storage failed: {}", msg),
+            OutboxError::UnknownRecipient(email) => write!(f, "Not in the outbox: {}", email),
+        }
+    }
+}
+
+impl Error for OutboxError {}
+
+/// One entry per campaign and recipient
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait CampaignOutbox {
+    /// CONTRACT:
+    /// - Adds the entry and returns true, unless the recipient (any email
+    ///   case) is already queued for that campaign: then changes nothing
+    ///   and returns false
+    fn enqueue(&mut self, entry: OutboxEntry) -> Result<bool, OutboxError>;
+
+    /// Every entry of one campaign, in the order they were queued
+    fn entries(&self, campaign_id: &str) -> Result<Vec<OutboxEntry>, OutboxError>;
+
+    /// Record an attempt's outcome (and count the attempt)
+    fn mark(
+        &mut self,
+        campaign_id: &str,
+        email: &str,
+        status: DeliveryStatus,
+    ) -> Result<(), OutboxError>;
+}
@@ -0,0 +1,32 @@
+// SOLID: This module defines the Clock PORT (abstraction)
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Time is infrastructure too. Business rules like "flag orders that have been
+// Ready for more than 15 minutes" should not call Utc::now() directly,
+// otherwise they can only be tested by waiting in real time.
+//
+// Services ask this trait for the current time instead:
+// - SystemClock returns the real wall-clock time (production)
+// - FixedClock returns whatever time we set (tests, demos)
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Clock trait - the single source of "now" for time-based business rules
+///
+/// SOLID PRINCIPLE: Interface Segregation Principle (ISP)
+///
+/// One method. A service that needs the time doesn't have to know
+/// whether it comes from the OS, an NTP server, or a test fixture.
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait Clock {
+    /// Current point in time (UTC)
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// One clock read by several services (a demo's FixedClock and the service it drives)
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
@@ -0,0 +1,42 @@
+// SOLID: This module defines the CouponStore PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    RewardService needs to save coupons, find one by code and list a
+//    customer's; nothing about orders, wallets or ratings.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    RewardService depends on this trait, not on a JSON file.
+
+use crate::domain::Coupon;
+use std::error::Error;
+use std::fmt;
+
+/// Error type for coupon storage
+#[derive(Debug, Clone, PartialEq)]
+pub enum CouponError {
+    StorageFailed(String),
+}
+
+impl fmt::Display for CouponError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CouponError::StorageFailed(msg) => write!(f, "Coupon storage failed: {}", msg),
+        }
+    }
+}
+
+impl Error for CouponError {}
+
+/// Where coupons live, by code
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait CouponStore {
+    /// Store a coupon, replacing any earlier one with the same code
+    fn save(&mut self, coupon: Coupon) -> Result<(), CouponError>;
+
+    fn find(&self, code: &str) -> Result<Option<Coupon>, CouponError>;
+
+    /// Every coupon of one customer (any email case), oldest first
+    fn for_customer(&self, email: &str) -> Result<Vec<Coupon>, CouponError>;
+}
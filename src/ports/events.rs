@@ -0,0 +1,47 @@
+// SOLID: This module defines the EventPublisher PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    OrderService announces "order paid" without knowing who listens: a log
+//    line, an analytics pipeline, a queue feeding the kitchen screen.
+//
+// 2. OPEN-CLOSED PRINCIPLE (OCP):
+//    A new downstream system is a new publisher given to the service
+//    (`with_event_publisher`), not a new call in the order workflow.
+//
+// 3. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    One method. Subscriptions, retries and delivery are the backend's.
+
+use crate::domain::DomainEvent;
+use std::error::Error;
+use std::fmt;
+
+/// Error type for event publishing
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublishError {
+    /// The broker or sink could not take the event
+    Unavailable(String),
+}
+
+impl fmt::Display for PublishError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PublishError::Unavailable(msg) => write!(f, "Event publishing failed: {}", msg),
+        }
+    }
+}
+
+impl Error for PublishError {}
+
+/// EventPublisher trait - somewhere to send domain events
+///
+/// CONTRACT:
+/// - Called after the change is saved: an event is never published for an
+///   order that doesn't exist
+/// - A failure is reported, but the service only logs it; the order stands
+/// - `Send`, so a service holding one can move to another thread
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait EventPublisher: Send {
+    fn publish(&self, event: &DomainEvent) -> Result<(), PublishError>;
+}
@@ -0,0 +1,24 @@
+// SOLID: This module defines the Experiment PORT (abstraction)
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// PromotionEngine asks "which variant does this customer see?" without
+// knowing whether the answer comes from a local hash (HashedExperiment)
+// or a hosted experimentation service.
+//
+// CONTRACT:
+// Assignment must be deterministic: the same customer gets the same variant
+// on every visit, at every register, after every restart. Otherwise the
+// comparison between variants measures noise.
+
+/// One A/B (or A/B/n) experiment
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait Experiment {
+    /// Stable identifier, used in exposure events and reports
+    fn name(&self) -> &str;
+
+    /// Every variant, the control group first
+    fn variants(&self) -> Vec<String>;
+
+    /// The variant this customer is bucketed into
+    fn variant_for(&self, customer_email: &str) -> String;
+}
@@ -0,0 +1,43 @@
+// SOLID: This module defines the FeedbackRepository PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Ratings are not orders: FeedbackService needs save, find and list,
+//    none of OrderRepository's queries (and OrderRepository none of these).
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    FeedbackService depends on this trait, not on a JSON file.
+
+use crate::domain::Feedback;
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Error type for feedback storage
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedbackError {
+    StorageFailed(String),
+}
+
+impl fmt::Display for FeedbackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedbackError::StorageFailed(msg) => write!(f, "Feedback storage failed: {}", msg),
+        }
+    }
+}
+
+impl Error for FeedbackError {}
+
+/// Where ratings live, at most one per order
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait FeedbackRepository {
+    /// Store a rating, replacing any earlier one for the same order
+    fn save(&mut self, feedback: Feedback) -> Result<(), FeedbackError>;
+
+    fn find(&self, order_id: Uuid) -> Result<Option<Feedback>, FeedbackError>;
+
+    /// Every rating, oldest first
+    fn list(&self) -> Result<Vec<Feedback>, FeedbackError>;
+}
@@ -0,0 +1,47 @@
+// SOLID: This module defines the FiscalNumberGenerator PORT (abstraction)
+//
+// Tax authorities want receipts numbered in one unbroken sequence: a gap
+// or a duplicate is what an inspector looks for first.
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    Order IDs are random (UUIDs) and stay that way; the fiscal number is a
+//    second, dedicated identifier handed out only to paid orders.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    OrderService asks this trait for the next number. A counter in memory,
+//    in a file, or a certified fiscal device behind an API all fit.
+
+use std::error::Error;
+use std::fmt;
+
+/// Error type for fiscal numbering
+#[derive(Debug, Clone, PartialEq)]
+pub enum FiscalError {
+    /// The counter could not be read or advanced
+    CounterFailed(String),
+}
+
+impl fmt::Display for FiscalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FiscalError::CounterFailed(msg) => write!(f, "Fiscal numbering failed: {}", msg),
+        }
+    }
+}
+
+impl Error for FiscalError {}
+
+/// Hands out sequential fiscal receipt numbers
+///
+/// Contract:
+/// - each number is the previous one plus one, starting at 1 (or where the
+///   shop's numbering started), and is never handed out twice
+/// - a number is consumed when returned: the caller only asks for one once
+///   the sale is certain (the order is paid)
+/// - `Send`, so a service holding one can move to a worker thread
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait FiscalNumberGenerator: Send {
+    fn next_number(&mut self) -> Result<u64, FiscalError>;
+}
@@ -0,0 +1,44 @@
+// SOLID: This module defines the InventoryRepository PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Stock levels are not orders: InventoryService needs find, save and
+//    list, none of OrderRepository's queries.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    InventoryService depends on this trait, not on a JSON file.
+
+use crate::domain::StockLevel;
+use std::error::Error;
+use std::fmt;
+
+/// Error type for inventory storage
+#[derive(Debug, Clone, PartialEq)]
+pub enum InventoryError {
+    StorageFailed(String),
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InventoryError::StorageFailed(msg) => write!(f, "Inventory storage failed: {}", msg),
+        }
+    }
+}
+
+impl Error for InventoryError {}
+
+/// Where stock levels live, one per ingredient
+///
+/// `Send`, so an OrderService holding one can move to another thread
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait InventoryRepository: Send {
+    fn find(&self, ingredient: &str) -> Result<Option<StockLevel>, InventoryError>;
+
+    /// Store a level, replacing the ingredient's earlier one
+    fn save(&mut self, level: StockLevel) -> Result<(), InventoryError>;
+
+    /// Every level, by ingredient name
+    fn list(&self) -> Result<Vec<StockLevel>, InventoryError>;
+}
@@ -0,0 +1,65 @@
+// SOLID: This module defines the DistributedLock PORT (abstraction)
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// When several instances share one backend, some tasks must still run once:
+// the day close, the nightly report, an outbox relay. The scheduler and the
+// day close only ask "may I?" through this trait; whether the answer comes
+// from a file on a shared disk, Redis or a database row is an adapter.
+//
+// LEASES, NOT MUTEXES:
+// A lock is taken for a time-to-live. An instance that crashes while holding
+// it does not block everyone forever: the lease simply runs out. Taking a
+// lock you already hold renews it, which is what leader election needs.
+
+use chrono::Duration;
+use std::error::Error;
+use std::fmt;
+
+/// Error type for lock operations
+#[derive(Debug, Clone)]
+pub enum LockError {
+    /// The lock backend could not be reached or answered nonsense
+    Unavailable(String),
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LockError::Unavailable(msg) => write!(f, "Lock backend unavailable: {}", msg),
+        }
+    }
+}
+
+impl Error for LockError {}
+
+/// Named leases shared by every instance
+///
+/// `Send + Sync`, so one handle can be used by a scheduler on a worker thread.
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait DistributedLock: Send + Sync {
+    /// Take `name` for `holder` during `ttl`
+    ///
+    /// CONTRACT:
+    /// - Returns Ok(true) if the lock was free, expired, or already held by
+    ///   `holder` (the lease then restarts from now)
+    /// - Returns Ok(false) if another holder has an unexpired lease
+    /// - Two instances never both get Ok(true) for the same unexpired lease
+    fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool, LockError>;
+
+    /// Give `name` back before its lease runs out
+    ///
+    /// CONTRACT:
+    /// - Returns Ok(true) if `holder` held it
+    /// - Returns Ok(false) and changes nothing if someone else does (or nobody)
+    fn release(&self, name: &str, holder: &str) -> Result<bool, LockError>;
+}
+
+impl<L: DistributedLock + ?Sized> DistributedLock for Box<L> {
+    fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool, LockError> {
+        (**self).try_acquire(name, holder, ttl)
+    }
+
+    fn release(&self, name: &str, holder: &str) -> Result<bool, LockError> {
+        (**self).release(name, holder)
+    }
+}
@@ -0,0 +1,45 @@
+// SOLID: This module defines the LoyaltyRepository PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Accounts are looked up by email, saved whole, and listed: what
+//    LoyaltyService and PointsPayment need, nothing of the order queries.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    Both depend on this trait, not on where accounts are kept.
+
+use crate::domain::LoyaltyAccount;
+use std::error::Error;
+use std::fmt;
+
+/// Error type for loyalty account storage
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoyaltyError {
+    StorageFailed(String),
+}
+
+impl fmt::Display for LoyaltyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoyaltyError::StorageFailed(msg) => write!(f, "Loyalty storage failed: {}", msg),
+        }
+    }
+}
+
+impl Error for LoyaltyError {}
+
+/// Loyalty accounts, one per email (case-insensitive)
+///
+/// `Send`, so an OrderService holding one can move to another thread
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait LoyaltyRepository: Send {
+    /// The customer's account, None if they never earned a point
+    fn find(&self, customer_email: &str) -> Result<Option<LoyaltyAccount>, LoyaltyError>;
+
+    /// Store an account, replacing the customer's earlier one
+    fn save(&mut self, account: LoyaltyAccount) -> Result<(), LoyaltyError>;
+
+    /// Every account, by email
+    fn list(&self) -> Result<Vec<LoyaltyAccount>, LoyaltyError>;
+}
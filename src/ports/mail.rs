@@ -0,0 +1,84 @@
+// SOLID: This module defines the mail PORTS (abstractions)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Sending a message and remembering what was already sent are two traits.
+//    An SMTP relay knows nothing about reruns; the log knows nothing about SMTP.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    The daily digest depends on these traits, not on a mail server.
+//
+// Notifier covers the customer's order events, one notice at a time. A mail
+// is a whole composed document (HTML and plain text) for staff or the owner,
+// which is why it has a port of its own.
+
+use std::error::Error;
+use std::fmt;
+
+/// Error type for mail operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum MailError {
+    /// The server could not be reached, or the connection broke
+    Unavailable(String),
+    /// The server answered, and refused (bad recipient, message too big...)
+    Rejected(String),
+    /// The sent-mail log could not be read or written
+    LogFailed(String),
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MailError::Unavailable(msg) => write!(f, "Mail server unavailable: {}", msg),
+            MailError::Rejected(msg) => write!(f, "Mail rejected: {}", msg),
+            MailError::LogFailed(msg) => write!(f, "Sent-mail log failed: {}", msg),
+        }
+    }
+}
+
+impl Error for MailError {}
+
+/// One message, in both HTML and plain text (for clients that show no HTML)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to: Vec<String>,
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+/// Delivers a message to every address in `to`
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait Mailer {
+    /// CONTRACT:
+    /// - Returns Ok(()) once the server accepted the message for every recipient
+    /// - Returns MailError::Rejected if it refused it, Unavailable if it never answered
+    fn send(&self, message: &EmailMessage) -> Result<(), MailError>;
+}
+
+/// Remembers which messages went out, so a rerun doesn't send them twice
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait SentMailLog {
+    /// Was the message with this key sent already?
+    fn contains(&self, key: &str) -> Result<bool, MailError>;
+
+    /// Note the message with this key as sent
+    fn record(&mut self, key: &str) -> Result<(), MailError>;
+}
+
+impl<M: Mailer + ?Sized> Mailer for Box<M> {
+    fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+        (**self).send(message)
+    }
+}
+
+impl<L: SentMailLog + ?Sized> SentMailLog for Box<L> {
+    fn contains(&self, key: &str) -> Result<bool, MailError> {
+        (**self).contains(key)
+    }
+
+    fn record(&mut self, key: &str) -> Result<(), MailError> {
+        (**self).record(key)
+    }
+}
@@ -0,0 +1,30 @@
+// SOLID: This module defines the MetricsRecorder PORT (abstraction)
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// OrderService reports "this order took 42 s to place" without knowing
+// whether the number ends up in memory, StatsD or a Prometheus registry.
+//
+// INTERFACE SEGREGATION PRINCIPLE (ISP):
+// One method. Reading, aggregating and exporting metrics are the backend's
+// business, not the business logic's.
+
+/// Time from order creation to payment, in seconds
+pub const ORDER_PLACEMENT_SECONDS: &str = "order_placement_seconds";
+
+/// Time from Preparing to Ready, in seconds
+pub const ORDER_PREP_SECONDS: &str = "order_prep_seconds";
+
+/// Routes a PaymentRouter tried for one payment (1 = the first choice took it)
+pub const PAYMENT_ROUTE_ATTEMPTS: &str = "payment_route_attempts";
+
+/// MetricsRecorder trait - somewhere to send measurements
+///
+/// CONTRACT:
+/// - Must never fail the caller: a metrics backend being down is not a
+///   reason to refuse an order, so there is no Result to handle
+/// - `Send + Sync`, so a service holding one can move to a worker thread
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait MetricsRecorder: Send + Sync {
+    /// Record one observation of `metric` (a duration in seconds, say)
+    fn observe(&self, metric: &'static str, value: f64);
+}
@@ -26,11 +26,93 @@
 // 3. Testing is easy (use mock implementations)
 // 4. Different teams can work on adapters independently
 
+pub mod campaign;
+pub mod clock;
+pub mod coupons;
+pub mod events;
+pub mod experiment;
+pub mod feedback;
+pub mod fiscal;
+pub mod inventory;
+pub mod lock;
+pub mod loyalty;
+pub mod mail;
+pub mod metrics;
+pub mod notification_outbox;
 pub mod notifier;
+pub mod order_queue;
+pub mod payload;
 pub mod payment;
+pub mod randomness;
 pub mod repository;
+pub mod store_credit;
+pub mod webhook;
 
 // Re-export for convenience
+pub use campaign::{CampaignOutbox, OutboxError};
+pub use clock::Clock;
+pub use coupons::{CouponError, CouponStore};
+pub use events::{EventPublisher, PublishError};
+pub use experiment::Experiment;
+pub use feedback::{FeedbackError, FeedbackRepository};
+pub use fiscal::{FiscalError, FiscalNumberGenerator};
+pub use inventory::{InventoryError, InventoryRepository};
+pub use lock::{DistributedLock, LockError};
+pub use loyalty::{LoyaltyError, LoyaltyRepository};
+pub use mail::{EmailMessage, MailError, Mailer, SentMailLog};
+pub use metrics::{
+    MetricsRecorder, ORDER_PLACEMENT_SECONDS, ORDER_PREP_SECONDS, PAYMENT_ROUTE_ATTEMPTS,
+};
+pub use notification_outbox::NotificationOutbox;
 pub use notifier::{NotificationError, Notifier};
-pub use payment::{PaymentError, PaymentProcessor};
-pub use repository::{OrderRepository, RepositoryError};
+pub use order_queue::{OrderQueueConsumer, OrderQueueProducer, QueueError};
+pub use payload::{PayloadSerializer, SerializationError};
+pub use payment::{PaymentError, PaymentProcessor, PaymentRequest, PendingPayment};
+pub use randomness::Randomness;
+pub use repository::{OrderRepository, RepositoryCapabilities, RepositoryError};
+pub use store_credit::{CreditError, StoreCredit};
+pub use webhook::{WebhookError, WebhookSubscriptionStore, WebhookTransport};
+
+// mockall mocks of every trait above (feature `mocks`)
+#[cfg(feature = "mocks")]
+pub use campaign::MockCampaignOutbox;
+#[cfg(feature = "mocks")]
+pub use clock::MockClock;
+#[cfg(feature = "mocks")]
+pub use coupons::MockCouponStore;
+#[cfg(feature = "mocks")]
+pub use events::MockEventPublisher;
+#[cfg(feature = "mocks")]
+pub use experiment::MockExperiment;
+#[cfg(feature = "mocks")]
+pub use feedback::MockFeedbackRepository;
+#[cfg(feature = "mocks")]
+pub use fiscal::MockFiscalNumberGenerator;
+#[cfg(feature = "mocks")]
+pub use inventory::MockInventoryRepository;
+#[cfg(feature = "mocks")]
+pub use lock::MockDistributedLock;
+#[cfg(feature = "mocks")]
+pub use loyalty::MockLoyaltyRepository;
+#[cfg(feature = "mocks")]
+pub use mail::{MockMailer, MockSentMailLog};
+#[cfg(feature = "mocks")]
+pub use metrics::MockMetricsRecorder;
+#[cfg(feature = "mocks")]
+pub use notification_outbox::MockNotificationOutbox;
+#[cfg(feature = "mocks")]
+pub use notifier::MockNotifier;
+#[cfg(feature = "mocks")]
+pub use order_queue::{MockOrderQueueConsumer, MockOrderQueueProducer};
+#[cfg(feature = "mocks")]
+pub use payload::MockPayloadSerializer;
+#[cfg(feature = "mocks")]
+pub use payment::MockPaymentProcessor;
+#[cfg(feature = "mocks")]
+pub use randomness::MockRandomness;
+#[cfg(feature = "mocks")]
+pub use repository::{MockOrderRepository, MockRepositoryCapabilities};
+#[cfg(feature = "mocks")]
+pub use store_credit::MockStoreCredit;
+#[cfg(feature = "mocks")]
+pub use webhook::{MockWebhookSubscriptionStore, MockWebhookTransport};
@@ -0,0 +1,34 @@
+// SOLID: This module defines the NotificationOutbox PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    The quiet-hours notifier only puts notices in; the release job reads
+//    the due ones and takes them out once delivered. Neither sends anything
+//    through this trait.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    Both depend on this trait, not on a JSON file.
+//
+// A notice leaves the outbox only after it was delivered: a crash in
+// between sends it again rather than never (at least once).
+
+use super::campaign::OutboxError;
+use crate::domain::DeferredNotification;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Customer notices waiting for their quiet hours to end
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait NotificationOutbox {
+    fn defer(&mut self, notification: DeferredNotification) -> Result<(), OutboxError>;
+
+    /// The notifications whose time has come, earliest first (left in the outbox)
+    fn due(&self, now: DateTime<Utc>) -> Result<Vec<DeferredNotification>, OutboxError>;
+
+    /// Take one out once delivered; false if it wasn't there
+    fn remove(&mut self, id: Uuid) -> Result<bool, OutboxError>;
+
+    /// Everything still waiting, due or not
+    fn pending(&self) -> Result<Vec<DeferredNotification>, OutboxError>;
+}
@@ -14,16 +14,23 @@
 //    Notification is a separate concern from order management, payment, etc.
 //    This trait isolates that concern
 
-use crate::domain::Order;
+use crate::domain::{
+    FeedbackRequestNotice, OpsAlertNotice, OrderCancelledNotice, OrderNoShowNotice,
+    OrderPlacedNotice, OrderReadyNotice, RefundPendingNotice, RewardGrantedNotice,
+    SlaBreachNotice,
+};
 use std::error::Error;
 use std::fmt;
 
 /// Error type for notification operations
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum NotificationError {
     SendFailed(String),
     InvalidRecipient(String),
     NetworkError(String),
+    /// Channels of a CompositeNotifier that failed: each one's name and error
+    ChannelsFailed(Vec<(String, NotificationError)>),
 }
 
 impl fmt::Display for NotificationError {
@@ -32,6 +39,13 @@ impl fmt::Display for NotificationError {
             NotificationError::SendFailed(msg) => write!(f, "Send failed: {}", msg),
             NotificationError::InvalidRecipient(msg) => write!(f, "Invalid recipient: {}", msg),
             NotificationError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            NotificationError::ChannelsFailed(failures) => {
+                let failures: Vec<String> = failures
+                    .iter()
+                    .map(|(channel, e)| format!("{}: {}", channel, e))
+                    .collect();
+                write!(f, "Channels failed: {}", failures.join("; "))
+            }
         }
     }
 }
@@ -46,6 +60,7 @@ impl Error for NotificationError {}
 ///    This is a SMALL, FOCUSED interface. It only handles notifications.
 ///    
 ///    Compare this to a "god interface":
+///    ```text
 ///    trait OrderManager {
 ///        fn save_order(...);
 ///        fn process_payment(...);
@@ -53,6 +68,7 @@ impl Error for NotificationError {}
 ///        fn generate_report(...);
 ///        fn calculate_tax(...);
 ///    }
+///    ```
 ///    
 ///    The problem with god interfaces:
 ///    - A simple console notifier would have to implement ALL methods
@@ -83,6 +99,11 @@ impl Error for NotificationError {}
 ///    - Testing with mock notifiers
 ///    - Swapping notification channels without touching business logic
 ///    - Running the app without email server (use Console instead)
+///
+/// PAYLOADS:
+///    Each method receives a notice (see domain::notice), not the Order.
+///    OrderService builds it, so adapters only see the fields they need.
+#[cfg_attr(feature = "mocks", mockall::automock)]
 pub trait Notifier {
     /// Notify customer that their order was placed successfully
     /// 
@@ -90,13 +111,100 @@ pub trait Notifier {
     /// - Should be non-blocking (don't slow down order processing)
     /// - If notification fails, log it but don't fail the order
     /// - Returns Ok(()) if sent, Err if failed
-    fn notify_order_placed(&self, order: &Order) -> Result<(), NotificationError>;
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError>;
 
     /// Notify customer that their order is ready for pickup
-    fn notify_order_ready(&self, order: &Order) -> Result<(), NotificationError>;
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError>;
 
     /// Notify customer that their order was cancelled
-    fn notify_order_cancelled(&self, order: &Order) -> Result<(), NotificationError>;
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError>;
+
+    /// Notify customer that their ready order was never picked up
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError>;
+
+    /// Escalate an order stuck past its SLA to the shop manager
+    ///
+    /// Staff-facing: customer channels (email, SMS) keep the default, which
+    /// sends nothing. Adapters used as a manager channel override it.
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        let _ = notice;
+        Ok(())
+    }
+
+    /// Ask the managers to approve or reject a refund (staff-facing, like SLA breaches)
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        let _ = notice;
+        Ok(())
+    }
+
+    /// Tell the ops channel an alert rule fired or resolved (staff-facing, see AlertEngine)
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        let _ = notice;
+        Ok(())
+    }
+
+    /// Send a collected order's customer a link to rate it
+    ///
+    /// Optional: channels that can't carry a link (a speaker, a chat room
+    /// for staff) keep the default, which sends nothing.
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        let _ = notice;
+        Ok(())
+    }
+
+    /// Give a customer their reward coupon's code
+    ///
+    /// Optional, like feedback requests: only customer-facing channels
+    /// override it.
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        let _ = notice;
+        Ok(())
+    }
+}
+
+/// A boxed notifier is a notifier (forwards the staff-facing methods too)
+impl<N: Notifier + ?Sized> Notifier for Box<N> {
+    fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
+        (**self).notify_order_placed(notice)
+    }
+
+    fn notify_order_ready(&self, notice: &OrderReadyNotice) -> Result<(), NotificationError> {
+        (**self).notify_order_ready(notice)
+    }
+
+    fn notify_order_cancelled(&self, notice: &OrderCancelledNotice) -> Result<(), NotificationError> {
+        (**self).notify_order_cancelled(notice)
+    }
+
+    fn notify_order_no_show(&self, notice: &OrderNoShowNotice) -> Result<(), NotificationError> {
+        (**self).notify_order_no_show(notice)
+    }
+
+    fn notify_sla_breach(&self, notice: &SlaBreachNotice) -> Result<(), NotificationError> {
+        (**self).notify_sla_breach(notice)
+    }
+
+    fn notify_refund_pending(&self, notice: &RefundPendingNotice) -> Result<(), NotificationError> {
+        (**self).notify_refund_pending(notice)
+    }
+
+    fn notify_ops_alert(&self, notice: &OpsAlertNotice) -> Result<(), NotificationError> {
+        (**self).notify_ops_alert(notice)
+    }
+
+    fn notify_feedback_requested(
+        &self,
+        notice: &FeedbackRequestNotice,
+    ) -> Result<(), NotificationError> {
+        (**self).notify_feedback_requested(notice)
+    }
+
+    fn notify_reward_granted(&self, notice: &RewardGrantedNotice) -> Result<(), NotificationError> {
+        (**self).notify_reward_granted(notice)
+    }
 }
 
 // ============================================================================
@@ -106,12 +214,12 @@ pub trait Notifier {
 // 
 // If we need multiple capabilities, we can compose them:
 // 
-// fn process_order<N>(notifier: &N, order: &Order)
+// fn process_order<N>(notifier: &N, notice: &OrderPlacedNotice)
 // where
 //     N: Notifier + Logger + MetricsCollector
 // {
-//     notifier.notify_order_placed(order)?;
-//     notifier.log_event("order_placed", order.id)?;
+//     notifier.notify_order_placed(notice)?;
+//     notifier.log_event("order_placed", notice.order_id)?;
 //     notifier.record_metric("orders", 1)?;
 // }
 // 
@@ -141,10 +249,10 @@ pub trait Notifier {
 // }
 // 
 // impl Notifier for CompositeNotifier {
-//     fn notify_order_placed(&self, order: &Order) -> Result<(), NotificationError> {
+//     fn notify_order_placed(&self, notice: &OrderPlacedNotice) -> Result<(), NotificationError> {
 //         for notifier in &self.notifiers {
 //             // Ignore individual failures, try all channels
-//             let _ = notifier.notify_order_placed(order);
+//             let _ = notifier.notify_order_placed(notice);
 //         }
 //         Ok(())
 //     }
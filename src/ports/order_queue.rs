@@ -0,0 +1,63 @@
+// SOLID: This module defines the order queue PORTS (abstractions)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    The intake side only ever enqueues, the worker side only ever dequeues.
+//    So there are TWO small traits instead of one "queue" trait both sides depend on.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    Intake and workers depend on these traits, not on std channels, Redis or Kafka.
+//    Moving from an in-process channel to a broker means writing a new adapter.
+//
+// The queue carries order IDs only. The order itself is already saved
+// (status Pending) by the time it is queued, so the repository stays the
+// single source of truth.
+
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Error type for queue operations
+#[derive(Debug, Clone)]
+pub enum QueueError {
+    Closed,
+    Full(String),
+    BrokerError(String),
+}
+
+impl fmt::Display for QueueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueueError::Closed => write!(f, "Queue closed"),
+            QueueError::Full(msg) => write!(f, "Queue full: {}", msg),
+            QueueError::BrokerError(msg) => write!(f, "Broker error: {}", msg),
+        }
+    }
+}
+
+impl Error for QueueError {}
+
+/// Intake side: hand an accepted order over for fulfillment
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait OrderQueueProducer {
+    /// Queue an order for processing
+    ///
+    /// CONTRACT:
+    /// - Returns Ok(()) once the order ID is accepted by the queue
+    /// - Returns QueueError::Full if the queue is at capacity and refuses to wait
+    /// - Returns QueueError::Closed if no consumer will ever read it
+    fn enqueue(&self, order_id: Uuid) -> Result<(), QueueError>;
+}
+
+/// Worker side: take the next order to fulfill
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait OrderQueueConsumer {
+    /// Wait for the next queued order
+    ///
+    /// CONTRACT:
+    /// - Returns Ok(Some(id)) for the next order
+    /// - Returns Ok(None) once the queue is closed and drained
+    /// - Each order ID is delivered to exactly one consumer
+    fn dequeue(&self) -> Result<Option<Uuid>, QueueError>;
+}
@@ -0,0 +1,44 @@
+// SOLID: This module defines the PayloadSerializer PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. OPEN-CLOSED PRINCIPLE (OCP):
+//    Receivers disagree on the wire format: one wants plain JSON, an ERP
+//    wants XML, a serverless platform wants a CloudEvents envelope. Each
+//    format is one implementation; the dispatcher never changes.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    Encoding an event is separate from choosing who receives it, signing
+//    it and sending it: the signature covers whatever bytes come out of here.
+
+use crate::domain::WebhookEvent;
+use std::error::Error;
+use std::fmt;
+
+/// Error type for payload serialization
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerializationError {
+    Failed(String),
+}
+
+impl fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerializationError::Failed(msg) => write!(f, "Failed to serialize event: {}", msg),
+        }
+    }
+}
+
+impl Error for SerializationError {}
+
+/// Writes an event in one wire format
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait PayloadSerializer {
+    /// Name subscriptions pick it by ("json", "xml", "cloudevents")
+    fn format(&self) -> &str;
+
+    /// Content-Type header of the body
+    fn content_type(&self) -> &str;
+
+    fn serialize(&self, event: &WebhookEvent) -> Result<String, SerializationError>;
+}
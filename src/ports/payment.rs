@@ -13,14 +13,17 @@
 //    This trait is focused ONLY on payment processing
 //    It doesn't handle storage, notifications, or other concerns
 
+use crate::domain::{Money, Order};
 use std::error::Error;
 use std::fmt;
+use uuid::Uuid;
 
 /// Error type for payment operations
 /// 
 /// SOLID (LSP): All payment processors must use this error type,
 /// ensuring they're substitutable and handle errors consistently.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum PaymentError {
     InsufficientFunds,
     InvalidCard,
@@ -41,6 +44,47 @@ impl fmt::Display for PaymentError {
 
 impl Error for PaymentError {}
 
+/// Everything a real gateway needs to charge for an order
+///
+/// A bare amount is enough for the cash drawer, but a card gateway wants to
+/// print the order on the receipt, link the charge to a customer, and refuse
+/// to charge twice when a request is retried.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentRequest {
+    /// In the order's currency: a gateway that doesn't take it refuses the charge
+    pub amount: Money,
+    pub order_id: Uuid,
+    /// Opaque customer reference (the customer ID, never name or email)
+    pub customer_ref: String,
+    /// Whose store credit to use (WalletPayment); in-house only, never sent to a gateway
+    pub customer_email: String,
+    /// Same order, same key: retries and redeliveries must not double-charge
+    pub idempotency_key: String,
+}
+
+impl PaymentRequest {
+    /// Charge the order's total (after any cash rounding), in the order's currency
+    pub fn for_order(order: &Order) -> Self {
+        Self {
+            amount: order.amount_charged(),
+            order_id: order.id,
+            customer_ref: order.customer.id.to_string(),
+            customer_email: order.customer.email.clone(),
+            idempotency_key: format!("order-{}", order.id),
+        }
+    }
+}
+
+/// A redirect payment started but not yet approved (PayPal-style checkout)
+///
+/// The customer is sent to `approval_url`, approves on the provider's page,
+/// and is sent back with `token`; only then can the payment be confirmed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingPayment {
+    pub token: String,
+    pub approval_url: String,
+}
+
 /// Payment processor trait
 /// 
 /// SOLID PRINCIPLES:
@@ -69,6 +113,7 @@ impl Error for PaymentError {}
 ///    - Same return type (Result<String, PaymentError>)
 ///    - Same error semantics
 ///    - Same guarantees (if Ok, payment was successful)
+#[cfg_attr(feature = "mocks", mockall::automock)]
 pub trait PaymentProcessor {
     /// Process a payment
     /// 
@@ -81,7 +126,88 @@ pub trait PaymentProcessor {
     /// 
     /// The payment_id is a unique identifier for the transaction,
     /// which can be used for refunds, auditing, etc.
-    fn process_payment(&self, amount: f64) -> Result<String, PaymentError>;
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError>;
+
+    /// Process a payment with its full context
+    ///
+    /// This is what OrderService calls. The default is a compatibility shim
+    /// that forwards the amount to `process_payment`, so existing processors
+    /// keep working unchanged. Gateways that need the order, customer or
+    /// idempotency key override this method.
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        self.process_payment(request.amount)
+    }
+
+    /// Give money back on an earlier charge
+    ///
+    /// CONTRACT:
+    /// - `payment_id` is what `process_payment` returned for the charge
+    /// - `amount` is at most what was charged (RefundService checks this)
+    /// - Returns Ok(refund_id) once the money is on its way back
+    ///
+    /// The default refuses: a processor that can't refund says so instead
+    /// of pretending it did.
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        let _ = (payment_id, amount);
+        Err(PaymentError::ProcessingFailed(format!(
+            "{} does not support refunds",
+            self.payment_method_name()
+        )))
+    }
+
+    /// Give money back, under a key naming this refund
+    ///
+    /// This is what OrderService and RefundService call. The key is the same
+    /// when the same refund is retried, and a new one for the next refund on
+    /// the payment, even of the same amount. The default forwards to `refund`;
+    /// gateways that deduplicate refunds override this method.
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        let _ = idempotency_key;
+        self.refund(payment_id, amount)
+    }
+
+    /// How much of `payment_id` was paid with loyalty points (None by default)
+    ///
+    /// Points don't earn points: OrderService records this part on the order
+    /// and leaves it out of the accrual. PointsPayment overrides it.
+    fn paid_with_points(&self, payment_id: &str) -> Option<Money> {
+        let _ = payment_id;
+        None
+    }
+
+    /// Start a payment the customer must approve on the provider's side
+    ///
+    /// CONTRACT:
+    /// - Charges nothing: returns where to send the customer, and the token
+    ///   the provider will send them back with
+    /// - Same idempotency key, same pending payment
+    ///
+    /// The default refuses, like `refund`: most processors charge at once.
+    fn initiate(&self, request: &PaymentRequest) -> Result<PendingPayment, PaymentError> {
+        let _ = request;
+        Err(PaymentError::ProcessingFailed(format!(
+            "{} does not support redirect payments",
+            self.payment_method_name()
+        )))
+    }
+
+    /// Complete a payment started with `initiate`, once the customer approved it
+    ///
+    /// CONTRACT:
+    /// - Returns Ok(payment_id), like `process_payment`
+    /// - Confirming the same token twice returns the same payment_id
+    fn confirm(&self, token: &str) -> Result<String, PaymentError> {
+        let _ = token;
+        Err(PaymentError::ProcessingFailed(format!(
+            "{} does not support redirect payments",
+            self.payment_method_name()
+        )))
+    }
 
     /// Get the name of this payment method (for display purposes)
     /// 
@@ -91,6 +217,46 @@ pub trait PaymentProcessor {
     }
 }
 
+/// A boxed processor is a processor (same idea as `Box<dyn OrderRepository>`)
+impl<P: PaymentProcessor + ?Sized> PaymentProcessor for Box<P> {
+    fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
+        (**self).process_payment(amount)
+    }
+
+    fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+        (**self).process_request(request)
+    }
+
+    fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+        (**self).refund(payment_id, amount)
+    }
+
+    fn refund_with_key(
+        &self,
+        payment_id: &str,
+        amount: Money,
+        idempotency_key: &str,
+    ) -> Result<String, PaymentError> {
+        (**self).refund_with_key(payment_id, amount, idempotency_key)
+    }
+
+    fn paid_with_points(&self, payment_id: &str) -> Option<Money> {
+        (**self).paid_with_points(payment_id)
+    }
+
+    fn initiate(&self, request: &PaymentRequest) -> Result<PendingPayment, PaymentError> {
+        (**self).initiate(request)
+    }
+
+    fn confirm(&self, token: &str) -> Result<String, PaymentError> {
+        (**self).confirm(token)
+    }
+
+    fn payment_method_name(&self) -> &str {
+        (**self).payment_method_name()
+    }
+}
+
 // ============================================================================
 // EXAMPLE: How OCP Works Here
 // 
@@ -115,7 +281,7 @@ pub trait PaymentProcessor {
 // }
 // 
 // impl PaymentProcessor for MobilePayment {
-//     fn process_payment(&self, amount: f64) -> Result<String, PaymentError> {
+//     fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
 //         // Mobile payment logic here
 //         Ok(format!("MOBILE-{}", uuid::Uuid::new_v4()))
 //     }
@@ -139,7 +305,7 @@ pub trait PaymentProcessor {
 // }
 // 
 // impl PaymentProcessor for MockPaymentProcessor {
-//     fn process_payment(&self, _amount: f64) -> Result<String, PaymentError> {
+//     fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
 //         if self.should_fail {
 //             Err(PaymentError::ProcessingFailed("Mock failure".to_string()))
 //         } else {
@@ -0,0 +1,77 @@
+// SOLID: This module defines the Randomness PORT (abstraction)
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Chance is infrastructure, like time (see ports::clock). Code that draws a
+// payment ID or picks a demo customer should not call Uuid::new_v4() or read
+// the OS entropy pool directly, otherwise two runs of the same example can
+// never print the same thing.
+//
+// Adapters that need a random value ask this trait instead:
+// - SystemRandomness draws from the OS (production)
+// - SeededRandomness replays the same sequence for the same seed (tests,
+//   examples, doctests, demo data)
+
+use uuid::{Builder, Uuid};
+
+/// Source of random numbers
+///
+/// SOLID PRINCIPLE: Interface Segregation Principle (ISP)
+///
+/// One required method; everything else is derived from it, so a new source
+/// only has to produce 64 random bits.
+///
+/// `Send + Sync`, so an adapter holding one can still move to a worker thread.
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait Randomness: Send + Sync {
+    /// 64 uniformly distributed bits
+    fn next_u64(&self) -> u64;
+
+    /// Uniform in [0, 1)
+    fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [0, bound)
+    fn below(&self, bound: u64) -> u64 {
+        self.next_u64() % bound.max(1)
+    }
+
+    /// True with the given probability (0.0 to 1.0)
+    fn chance(&self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    /// Index picked proportionally to its weight (None if every weight is 0)
+    fn weighted(&self, weights: &[f64]) -> Option<usize> {
+        let total: f64 = weights.iter().filter(|w| **w > 0.0).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = self.next_f64() * total;
+        for (index, weight) in weights.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+            if target < *weight {
+                return Some(index);
+            }
+            target -= weight;
+        }
+        weights.iter().rposition(|w| *w > 0.0)
+    }
+
+    /// A version 4 UUID built from this source's bits
+    fn uuid(&self) -> Uuid {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
+
+impl<R: Randomness + ?Sized> Randomness for Box<R> {
+    fn next_u64(&self) -> u64 {
+        (**self).next_u64()
+    }
+}
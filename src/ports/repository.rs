@@ -13,6 +13,7 @@
 use crate::domain::Order;
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
 /// Error type for repository operations
 /// 
@@ -20,6 +21,7 @@ use std::fmt;
 /// All implementations must use this error type, ensuring they're
 /// substitutable (Liskov Substitution Principle - LSP).
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum RepositoryError {
     NotFound(String),
     SaveFailed(String),
@@ -62,7 +64,10 @@ impl Error for RepositoryError {}
 ///    - Any implementation of this trait should be substitutable
 ///    - All implementations must honor the same contract (return types, error semantics)
 ///    - A user of OrderRepository shouldn't care if it's Memory, JSON, or Postgres
-pub trait OrderRepository {
+///    - `Send`, so a service holding one can be shared across threads
+///      (SharedOrderService); `&mut self` methods still get one caller at a time
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait OrderRepository: Send {
     /// Save an order
     /// 
     /// Contract: 
@@ -108,6 +113,143 @@ pub trait OrderRepository {
     /// - If order doesn't exist, return Ok(false)
     /// - If deletion fails, return RepositoryError::SaveFailed
     fn delete(&mut self, id: uuid::Uuid) -> Result<bool, RepositoryError>;
+
+    /// Save several new orders
+    ///
+    /// Contract:
+    /// - Same errors as `save`
+    /// - The default saves one order at a time, so a failure can leave the
+    ///   orders before it saved. Backends whose `supports_transactions()` is
+    ///   true save all of them or none.
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        for order in orders {
+            self.save(order)?;
+        }
+        Ok(())
+    }
+
+    /// List all orders without copying them
+    ///
+    /// Contract:
+    /// - Same orders and errors as `list_all`
+    /// - The default copies each order once (it calls `list_all`). Backends
+    ///   that keep orders in memory hand out shared handles instead, so a
+    ///   report over a large repository allocates no Order at all.
+    /// - A handle is a snapshot: a later `update` stores a new Order and
+    ///   leaves the handles already given out unchanged.
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self.list_all()?.into_iter().map(Arc::new).collect())
+    }
+
+    /// Find all orders for a customer without copying them
+    ///
+    /// Contract: same as `find_by_customer_email`, shared like `list_all_shared`
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self.find_by_customer_email(email)?.into_iter().map(Arc::new).collect())
+    }
+}
+
+/// What a storage backend can do beyond the OrderRepository basics
+///
+/// SOLID PRINCIPLE: Interface Segregation Principle (ISP)
+/// This is a separate, optional trait: OrderService never needs it, so
+/// backends aren't forced to answer. Tools that move many orders at once
+/// (RepositoryMigrator, batch imports) ask for it and adapt, instead of
+/// assuming every backend is as limited as the weakest one.
+///
+/// The defaults describe that weakest backend.
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait RepositoryCapabilities {
+    /// `save_batch` is all-or-nothing
+    fn supports_transactions(&self) -> bool {
+        false
+    }
+
+    /// Orders can be read incrementally, without loading them all in memory
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// Largest batch worth handing to `save_batch` (None = no limit)
+    fn max_batch_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Search the backend's own text index (see OrderSearchService)
+    ///
+    /// Orders whose customer name or items contain every word of `query`, as
+    /// a word or the start of one, best match first. None when the backend
+    /// has no index: the caller scans instead.
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        let _ = query;
+        None
+    }
+}
+
+/// A boxed repository is a repository
+///
+/// Lets callers pick the backend at runtime (`Box<dyn OrderRepository>`)
+/// and still hand it to generic services like `OrderService<R, ..>`.
+impl<R: OrderRepository + ?Sized> OrderRepository for Box<R> {
+    fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        (**self).save(order)
+    }
+
+    fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Order>, RepositoryError> {
+        (**self).find_by_id(id)
+    }
+
+    fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+        (**self).find_by_customer_email(email)
+    }
+
+    fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+        (**self).list_all()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        (**self).update(order)
+    }
+
+    fn delete(&mut self, id: uuid::Uuid) -> Result<bool, RepositoryError> {
+        (**self).delete(id)
+    }
+
+    fn save_batch(&mut self, orders: &[Order]) -> Result<(), RepositoryError> {
+        (**self).save_batch(orders)
+    }
+
+    fn list_all_shared(&self) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        (**self).list_all_shared()
+    }
+
+    fn find_by_customer_email_shared(
+        &self,
+        email: &str,
+    ) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        (**self).find_by_customer_email_shared(email)
+    }
+}
+
+impl<R: RepositoryCapabilities + ?Sized> RepositoryCapabilities for Box<R> {
+    fn supports_transactions(&self) -> bool {
+        (**self).supports_transactions()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        (**self).supports_streaming()
+    }
+
+    fn max_batch_size(&self) -> Option<usize> {
+        (**self).max_batch_size()
+    }
+
+    fn full_text_search(&self, query: &str) -> Option<Result<Vec<Order>, RepositoryError>> {
+        (**self).full_text_search(query)
+    }
 }
 
 // ============================================================================
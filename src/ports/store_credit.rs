@@ -0,0 +1,88 @@
+// SOLID: This module defines the StoreCredit PORT (abstraction)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    One small trait for every side of store credit: remedies and promotions
+//    grant, WalletPayment spends and restores, accounting reads the wallets.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    IssueService and WalletPayment depend on this trait, not on where
+//    wallets are kept.
+
+use crate::domain::{Money, Wallet};
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Error type for store credit operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum CreditError {
+    /// Not positive, or in another currency than the wallet's
+    InvalidAmount(String),
+    /// The wallet holds less than was asked for
+    InsufficientCredit { balance: Money },
+    /// No spend with this reference
+    UnknownReference(String),
+    StorageFailed(String),
+}
+
+impl fmt::Display for CreditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CreditError::InvalidAmount(msg) => write!(f, "Invalid credit amount: {}", msg),
+            CreditError::InsufficientCredit { balance } => {
+                write!(f, "Only {} of store credit left", balance)
+            }
+            CreditError::UnknownReference(reference) => {
+                write!(f, "No store credit payment {}", reference)
+            }
+            CreditError::StorageFailed(msg) => write!(f, "Store credit storage failed: {}", msg),
+        }
+    }
+}
+
+impl Error for CreditError {}
+
+/// Credit owed to customers, one wallet per email (case-insensitive)
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait StoreCredit {
+    /// Add `amount` to the customer's balance
+    ///
+    /// CONTRACT:
+    /// - Returns a reference for the grant (for receipts and audits)
+    /// - Returns CreditError::InvalidAmount unless the amount is positive and
+    ///   in the wallet's currency (a new wallet takes the first grant's)
+    fn grant(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        reason: &str,
+    ) -> Result<String, CreditError>;
+
+    /// What the customer can still spend, in the wallet's currency (zero for a
+    /// customer never credited, or whose credit is frozen)
+    fn balance(&self, customer_email: &str) -> Result<Money, CreditError>;
+
+    /// Pay `amount` of an order with credit; returns the spend's reference
+    ///
+    /// All or nothing: CreditError::InsufficientCredit if the balance is short.
+    fn spend(
+        &mut self,
+        customer_email: &str,
+        amount: Money,
+        order_id: Uuid,
+    ) -> Result<String, CreditError>;
+
+    /// Give back up to `amount` of an earlier spend (a refund)
+    fn restore(&mut self, spend_reference: &str, amount: Money) -> Result<String, CreditError>;
+
+    /// Stop the customer spending their credit (a disputed payment)
+    ///
+    /// What they hold is kept, and grants and restores still land; only
+    /// `spend` refuses, and `balance` is zero, until the wallet is unfrozen.
+    fn freeze(&mut self, customer_email: &str, reason: &str) -> Result<(), CreditError>;
+
+    /// Every wallet with its entries (what accounting books)
+    fn wallets(&self) -> Result<Vec<Wallet>, CreditError>;
+}
@@ -0,0 +1,76 @@
+// SOLID: This module defines the webhook PORTS (abstractions)
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Managing subscriptions (the admin side) and sending HTTP requests
+//    (the delivery side) are two different traits. The admin CLI never
+//    needs an HTTP client; a chat notifier needs the transport but no store.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    The dispatcher depends on these traits, not on a JSON file or an HTTP crate.
+
+use crate::domain::WebhookSubscription;
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Error type for webhook operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookError {
+    InvalidUrl(String),
+    /// The request never got a response (DNS, connection, timeout...)
+    DeliveryFailed(String),
+    /// The receiver answered with a non-2xx status
+    Rejected(u16),
+    StoreFailed(String),
+    NotFound(Uuid),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebhookError::InvalidUrl(msg) => write!(f, "Invalid URL: {}", msg),
+            WebhookError::DeliveryFailed(msg) => write!(f, "Delivery failed: {}", msg),
+            WebhookError::Rejected(status) => write!(f, "Rejected with HTTP {}", status),
+            WebhookError::StoreFailed(msg) => write!(f, "Subscription store failed: {}", msg),
+            WebhookError::NotFound(id) => write!(f, "No webhook subscription {}", id),
+        }
+    }
+}
+
+impl Error for WebhookError {}
+
+/// Sends one HTTP POST with a JSON body
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait WebhookTransport {
+    /// CONTRACT:
+    /// - Returns Ok(status) for a 2xx response
+    /// - Returns WebhookError::Rejected(status) for any other response
+    /// - Returns WebhookError::DeliveryFailed if there was no response at all
+    // `'a` is spelled out so `mockall` can mock the nested reference
+    #[allow(clippy::needless_lifetimes)]
+    fn post<'a>(
+        &self,
+        url: &str,
+        headers: &[(&'a str, String)],
+        body: &str,
+    ) -> Result<u16, WebhookError>;
+}
+
+/// Where webhook subscriptions live
+#[cfg_attr(feature = "mocks", mockall::automock)]
+pub trait WebhookSubscriptionStore {
+    fn add(&mut self, subscription: WebhookSubscription) -> Result<(), WebhookError>;
+
+    /// Replace an existing subscription (WebhookError::NotFound if unknown)
+    fn update(&mut self, subscription: WebhookSubscription) -> Result<(), WebhookError>;
+
+    /// Returns false if there was nothing to remove
+    fn remove(&mut self, id: Uuid) -> Result<bool, WebhookError>;
+
+    fn find(&self, id: Uuid) -> Result<Option<WebhookSubscription>, WebhookError>;
+
+    /// Every subscription, active or not, oldest first
+    fn list(&self) -> Result<Vec<WebhookSubscription>, WebhookError>;
+}
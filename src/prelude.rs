@@ -0,0 +1,11 @@
+// The prelude: one import for examples, exercises and student code
+//
+// use coffee_shop_solid::prelude::*;
+//
+// let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new());
+// let order = service.place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())])?;
+//
+// It is the stable v1 surface (see v1.rs), so code written against it keeps
+// compiling as the crate grows.
+
+pub use crate::v1::*;
@@ -0,0 +1,214 @@
+// SOLID: Accounting file formats (QIF, OFX, ledger)
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Every format implements AccountingExporter. Supporting another package
+// (e.g. IIF, CSV for a specific bank) is a new struct, nothing else changes.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// All exporters take the same balanced JournalEntry values and return the
+// whole file as a string; the caller can't tell them apart.
+
+use crate::services::{AccountMapping, JournalEntry};
+use std::collections::BTreeMap;
+
+/// Renders journal entries in one accounting file format
+pub trait AccountingExporter {
+    /// File extension, without the dot
+    fn extension(&self) -> &'static str;
+
+    fn export(&self, entries: &[JournalEntry], mapping: &AccountMapping) -> String;
+}
+
+/// Plain-text ledger (ledger-cli / hledger)
+pub struct LedgerExporter;
+
+impl AccountingExporter for LedgerExporter {
+    fn extension(&self) -> &'static str {
+        "ledger"
+    }
+
+    fn export(&self, entries: &[JournalEntry], _mapping: &AccountMapping) -> String {
+        let mut out = String::new();
+
+        for entry in entries {
+            out.push_str(&format!(
+                "{} * {}  ; payment: {}\n",
+                entry.date.format("%Y/%m/%d"),
+                entry.description,
+                entry.payment_id
+            ));
+            for posting in &entry.postings {
+                out.push_str(&format!(
+                    "    {:<40} {:>10.2} {}\n",
+                    posting.account,
+                    posting.amount.to_major(),
+                    posting.amount.currency
+                ));
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Quicken Interchange Format, one bank section per payment account
+pub struct QifExporter;
+
+impl AccountingExporter for QifExporter {
+    fn extension(&self) -> &'static str {
+        "qif"
+    }
+
+    fn export(&self, entries: &[JournalEntry], _mapping: &AccountMapping) -> String {
+        let mut out = String::new();
+
+        for (account, entries) in by_payment_account(entries) {
+            out.push_str(&format!("!Account\nN{}\nTBank\n^\n!Type:Bank\n", account));
+
+            for entry in entries {
+                out.push_str(&format!(
+                    "D{}\nT{:.2}\nN{}\nM{}\n",
+                    entry.date.format("%m/%d/%Y"),
+                    entry.payment_posting().amount.to_major(),
+                    entry.payment_id,
+                    entry.description
+                ));
+                // Splits: income accounts are credited, so they show as positive
+                // amounts received into this bank account
+                for posting in &entry.postings[1..] {
+                    let amount = -posting.amount.to_major();
+                    out.push_str(&format!("S{}\n${:.2}\n", posting.account, amount));
+                }
+                out.push_str("^\n");
+            }
+        }
+
+        out
+    }
+}
+
+/// Open Financial Exchange 2.x (XML), one statement per payment account
+pub struct OfxExporter;
+
+impl AccountingExporter for OfxExporter {
+    fn extension(&self) -> &'static str {
+        "ofx"
+    }
+
+    fn export(&self, entries: &[JournalEntry], mapping: &AccountMapping) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>\n\
+             <OFX>\n<BANKMSGSRSV1>\n",
+        );
+
+        for (account, entries) in by_payment_account(entries) {
+            let start = entries.first().map(|e| e.date).unwrap_or_default();
+            let end = entries.last().map(|e| e.date).unwrap_or_default();
+
+            out.push_str("<STMTTRNRS>\n<TRNUID>0</TRNUID>\n");
+            out.push_str("<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>\n");
+            out.push_str(&format!("<STMTRS>\n<CURDEF>{}</CURDEF>\n", xml(&mapping.currency)));
+            out.push_str(&format!(
+                "<BANKACCTFROM><BANKID>0</BANKID><ACCTID>{}</ACCTID><ACCTTYPE>CHECKING</ACCTTYPE></BANKACCTFROM>\n",
+                xml(account)
+            ));
+            out.push_str(&format!(
+                "<BANKTRANLIST>\n<DTSTART>{}</DTSTART>\n<DTEND>{}</DTEND>\n",
+                start.format("%Y%m%d"),
+                end.format("%Y%m%d")
+            ));
+
+            for entry in entries {
+                out.push_str(&format!(
+                    "<STMTTRN><TRNTYPE>CREDIT</TRNTYPE><DTPOSTED>{}</DTPOSTED><TRNAMT>{:.2}</TRNAMT><FITID>{}</FITID><NAME>{}</NAME><MEMO>{}</MEMO></STMTTRN>\n",
+                    entry.date.format("%Y%m%d"),
+                    entry.payment_posting().amount.to_major(),
+                    entry.order_id.simple(),
+                    xml(&entry.payment_id),
+                    xml(&entry.description)
+                ));
+            }
+
+            out.push_str("</BANKTRANLIST>\n</STMTRS>\n</STMTTRNRS>\n");
+        }
+
+        out.push_str("</BANKMSGSRSV1>\n</OFX>\n");
+        out
+    }
+}
+
+/// Entries grouped by the account that received the money
+fn by_payment_account(entries: &[JournalEntry]) -> BTreeMap<&str, Vec<&JournalEntry>> {
+    let mut groups: BTreeMap<&str, Vec<&JournalEntry>> = BTreeMap::new();
+    for entry in entries {
+        groups
+            .entry(entry.payment_posting().account.as_str())
+            .or_default()
+            .push(entry);
+    }
+    groups
+}
+
+fn xml(text: &str) -> String {
+    super::template::escape_html(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Money;
+    use crate::services::Posting;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    fn entry(payment_account: &str) -> JournalEntry {
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+            order_id: Uuid::new_v4(),
+            payment_id: "CASH-1".to_string(),
+            description: "Order 1a2b3c4d".to_string(),
+            postings: vec![
+                Posting {
+                    account: payment_account.to_string(),
+                    amount: Money::usd(3.50),
+                },
+                Posting {
+                    account: "Income:Sales:Coffee".to_string(),
+                    amount: Money::usd(-3.50),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_ledger_format() {
+        let text = LedgerExporter.export(&[entry("Assets:Cash Drawer")], &AccountMapping::default());
+
+        assert!(text.starts_with("2024/03/04 * Order 1a2b3c4d"));
+        assert!(text.contains("Assets:Cash Drawer"));
+        assert!(text.contains("-3.50 USD"));
+    }
+
+    #[test]
+    fn test_qif_groups_by_account() {
+        let entries = [entry("Assets:Cash Drawer"), entry("Assets:Card Clearing"), entry("Assets:Cash Drawer")];
+
+        let text = QifExporter.export(&entries, &AccountMapping::default());
+
+        assert_eq!(text.matches("!Account").count(), 2);
+        assert_eq!(text.matches("D03/04/2024").count(), 3);
+        assert!(text.contains("SIncome:Sales:Coffee\n$3.50\n^"));
+    }
+
+    #[test]
+    fn test_ofx_format() {
+        let text = OfxExporter.export(&[entry("Assets:Cash & Co")], &AccountMapping::default());
+
+        assert!(text.contains("<CURDEF>USD</CURDEF>"));
+        assert!(text.contains("<ACCTID>Assets:Cash &amp; Co</ACCTID>"));
+        assert!(text.contains("<TRNAMT>3.50</TRNAMT>"));
+        assert!(text.trim_end().ends_with("</OFX>"));
+    }
+}
@@ -0,0 +1,51 @@
+// SOLID: Campaign - an announcement's text as an HTML email
+//
+// The campaign is written as plain text; each paragraph (blank-line
+// separated) becomes a <p>, escaped, with the same inline styles as the
+// daily digest. Line breaks inside a paragraph are kept.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The text comes filled in from the Campaign, delivery from CampaignService.
+
+use super::template::{escape_html, render_template, TemplateContext};
+
+const EMAIL: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<body style="font-family: Arial, sans-serif; color: #3b2f2f; margin: 0; padding: 1rem;">
+<h2 style="margin: 0 0 1rem 0;">☕</h2>
+{{{paragraphs}}}
+</body>
+</html>
+"#;
+
+/// The email body for one recipient's text (a CampaignRenderer)
+pub fn render_campaign(text: &str) -> String {
+    let paragraphs: Vec<String> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            format!(
+                "<p style=\"margin: 0 0 1rem 0;\">{}</p>",
+                escape_html(paragraph).replace('\n', "<br>")
+            )
+        })
+        .collect();
+
+    let mut context = TemplateContext::new();
+    context.set("paragraphs", paragraphs.join("\n"));
+    render_template(EMAIL, &context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paragraphs_are_escaped() {
+        let html = render_campaign("Hello Ada,\n\nPumpkin & spice\nare <back>\n\n");
+        assert!(html.contains(">Hello Ada,</p>"));
+        assert!(html.contains(">Pumpkin &amp; spice<br>are &lt;back&gt;</p>"));
+        assert_eq!(html.matches("<p ").count(), 2);
+    }
+}
@@ -0,0 +1,80 @@
+// SOLID: Terminal charts
+//
+// Plain strings, no terminal library: the output can be printed,
+// logged or compared in a test.
+
+const BAR: char = '█';
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Horizontal bar chart, one row per (label, value)
+///
+/// The largest value gets `width` characters; the others are scaled.
+///
+/// ```text
+/// Coffee   ████████████████████ 120.50
+/// Tea      ██████                36.00
+/// ```
+pub fn bar_chart(rows: &[(String, f64)], width: usize) -> String {
+    let label_width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    let max = rows.iter().map(|(_, value)| *value).fold(0.0, f64::max);
+
+    rows.iter()
+        .map(|(label, value)| {
+            let length = if max > 0.0 {
+                ((value / max) * width as f64).round() as usize
+            } else {
+                0
+            };
+            format!(
+                "{:<label_width$} {:<width$} {:>8.2}",
+                label,
+                BAR.to_string().repeat(length),
+                value,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One-line trend, one character per value (lowest ▁ to highest █)
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if max <= min {
+                SPARKS[0]
+            } else {
+                let level = ((value - min) / (max - min) * (SPARKS.len() - 1) as f64).round();
+                SPARKS[level as usize]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_chart_scales_to_largest() {
+        let rows = vec![("Coffee".to_string(), 10.0), ("Tea".to_string(), 5.0)];
+
+        let chart = bar_chart(&rows, 10);
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].matches(BAR).count(), 10);
+        assert_eq!(lines[1].matches(BAR).count(), 5);
+        assert!(lines[1].starts_with("Tea   "));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0.0, 7.0, 3.5]), "▁█▅");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
@@ -0,0 +1,92 @@
+// SOLID: DailyDigest - the daily report as an HTML email
+//
+// Mail clients drop <style> blocks and never run scripts, so unlike the
+// dashboard every style is inline and the hourly chart is a table of bars.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The figures come from ReportingService, delivery from DailyDigestJob.
+// This file only lays them out.
+
+use super::template::{render_template, TemplateContext};
+use crate::services::DailyReport;
+
+const EMAIL: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<body style="font-family: Arial, sans-serif; color: #3b2f2f; margin: 0; padding: 1rem;">
+<h2 style="margin: 0 0 0.25rem 0;">☕ {{date}}</h2>
+<p style="color: #8a7f7f; margin: 0 0 1rem 0;">Daily digest</p>
+<table cellpadding="8" style="border-collapse: collapse; margin-bottom: 1rem;">
+  <tr style="background: #f7f1eb;">
+    <td>Orders</td><td align="right"><b>{{orders}}</b></td>
+  </tr>
+  <tr><td>Revenue</td><td align="right"><b>${{revenue}}</b></td></tr>
+  <tr style="background: #f7f1eb;">
+    <td>Average ticket</td><td align="right"><b>${{average_ticket}}</b></td>
+  </tr>
+  <tr><td>Cancelled</td><td align="right">{{cancelled}}</td></tr>
+  <tr style="background: #f7f1eb;"><td>Past SLA</td><td align="right">{{sla_breaches}}</td></tr>
+</table>
+<h3 style="margin-bottom: 0.5rem;">Orders per hour</h3>
+{{{hourly}}}
+</body>
+</html>
+"#;
+
+/// The email body for one day
+pub fn render_daily_digest(report: &DailyReport) -> String {
+    let mut context = TemplateContext::new();
+    context
+        .set("date", report.date.format("%A %Y-%m-%d").to_string())
+        .set("orders", report.orders.to_string())
+        .set("revenue", format!("{:.2}", report.revenue))
+        .set("average_ticket", format!("{:.2}", report.average_ticket))
+        .set("cancelled", report.cancelled.to_string())
+        .set("sla_breaches", report.sla_breaches.to_string())
+        .set("hourly", hourly_bars(&report.hourly));
+    render_template(EMAIL, &context)
+}
+
+fn hourly_bars(hourly: &[(u32, usize)]) -> String {
+    let Some(busiest) = hourly.iter().map(|(_, orders)| *orders).max() else {
+        return "<p>No sales.</p>".to_string();
+    };
+    let rows: String = hourly
+        .iter()
+        .map(|(hour, orders)| {
+            let width = (orders * 200 / busiest.max(1)).max(1);
+            format!(
+                "  <tr><td>{:02}:00</td><td><div style=\"background: #a0522d; height: 12px; \
+                 width: {}px;\"></div></td><td align=\"right\">{}</td></tr>\n",
+                hour, width, orders
+            )
+        })
+        .collect();
+    format!("<table cellpadding=\"3\" style=\"border-collapse: collapse;\">\n{}</table>", rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_digest_uses_inline_styles_only() {
+        let report = DailyReport {
+            date: NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+            orders: 3,
+            cancelled: 1,
+            sla_breaches: 0,
+            revenue: 12.5,
+            average_ticket: 4.1666,
+            hourly: vec![(8, 2), (9, 1)],
+            other_currencies: Vec::new(),
+        };
+
+        let html = render_daily_digest(&report);
+        assert!(html.contains("Monday 2024-03-04"));
+        assert!(html.contains("$12.50") && html.contains("$4.17"));
+        assert!(html.contains("width: 200px") && html.contains("width: 100px"));
+        assert!(!html.contains("<style") && !html.contains("<script"));
+        assert!(!html.contains("{{"));
+    }
+}
@@ -0,0 +1,221 @@
+// SOLID: HtmlDashboard - a self-contained HTML page of shop analytics
+//
+// The page has no external CSS, JS or images: it can be emailed,
+// opened from a USB stick, or dropped on any static host.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The numbers come from ReportingService. This file only lays them out,
+// using the template engine so the markup stays readable in one place.
+
+use super::template::{escape_html, render_template, TemplateContext};
+use crate::services::{DailyReport, MixShare, WeeklyReport};
+use chrono::{DateTime, Utc};
+
+/// Everything shown on the dashboard
+#[derive(Debug, Clone)]
+pub struct DashboardData {
+    pub generated_at: DateTime<Utc>,
+    pub daily: DailyReport,
+    pub weekly: WeeklyReport,
+    pub product_mix: Vec<MixShare>,
+    pub payment_mix: Vec<MixShare>,
+}
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{title}}</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 960px; color: #3b2f2f; }
+  h1 { margin-bottom: 0; }
+  .muted { color: #8a7f7f; }
+  .cards { display: flex; gap: 1rem; margin: 1.5rem 0; }
+  .card { flex: 1; background: #f7f1eb; border-radius: 8px; padding: 1rem; }
+  .card .value { font-size: 1.8rem; font-weight: bold; }
+  table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+  th, td { text-align: left; padding: 0.35rem 0.5rem; border-bottom: 1px solid #eadfd6; }
+  td.num { text-align: right; font-variant-numeric: tabular-nums; }
+  .bar { background: #a0522d; height: 0.9rem; border-radius: 3px; }
+</style>
+</head>
+<body>
+<h1>☕ {{title}}</h1>
+<p class="muted">Generated {{generated_at}}</p>
+
+<div class="cards">
+  <div class="card"><div class="muted">Orders ({{date}})</div><div class="value">{{orders}}</div></div>
+  <div class="card"><div class="muted">Revenue</div><div class="value">${{revenue}}</div></div>
+  <div class="card"><div class="muted">Average ticket</div><div class="value">${{average_ticket}}</div></div>
+  <div class="card"><div class="muted">Cancelled</div><div class="value">{{cancelled}}</div></div>
+  <div class="card"><div class="muted">Past SLA</div><div class="value">{{sla_breaches}}</div></div>
+</div>
+
+<h2>Orders per hour</h2>
+{{{hourly_table}}}
+
+<h2>Week of {{week_from}}</h2>
+{{{weekly_table}}}
+
+<h2>Product mix</h2>
+{{{product_table}}}
+
+<h2>Payment mix</h2>
+{{{payment_table}}}
+</body>
+</html>
+"#;
+
+/// Render the whole page
+pub fn render_dashboard(data: &DashboardData) -> String {
+    let daily = &data.daily;
+
+    let mut context = TemplateContext::new();
+    context
+        .set("title", "Coffee Shop Dashboard")
+        .set("generated_at", data.generated_at.format("%Y-%m-%d %H:%M UTC").to_string())
+        .set("date", daily.date.to_string())
+        .set("orders", daily.orders.to_string())
+        .set("revenue", format!("{:.2}", daily.revenue))
+        .set("average_ticket", format!("{:.2}", daily.average_ticket))
+        .set("cancelled", daily.cancelled.to_string())
+        .set("sla_breaches", daily.sla_breaches.to_string())
+        .set("week_from", data.weekly.from.to_string())
+        .set(
+            "hourly_table",
+            bar_table(
+                ["Hour", "Orders"],
+                daily
+                    .hourly
+                    .iter()
+                    .map(|(hour, orders)| (format!("{:02}:00", hour), *orders as f64, orders.to_string()))
+                    .collect(),
+            ),
+        )
+        .set(
+            "weekly_table",
+            bar_table(
+                ["Day", "Revenue"],
+                data.weekly
+                    .days
+                    .iter()
+                    .map(|day| {
+                        (
+                            day.date.format("%a %Y-%m-%d").to_string(),
+                            day.revenue,
+                            format!("${:.2}", day.revenue),
+                        )
+                    })
+                    .collect(),
+            ),
+        )
+        .set("product_table", mix_table("Product", &data.product_mix))
+        .set("payment_table", mix_table("Payment", &data.payment_mix));
+
+    render_template(PAGE, &context)
+}
+
+fn mix_table(label: &str, shares: &[MixShare]) -> String {
+    bar_table(
+        [label, "Revenue"],
+        shares
+            .iter()
+            .map(|share| {
+                (
+                    format!("{} ({})", share.name, share.count),
+                    share.revenue,
+                    format!("${:.2} ({:.0}%)", share.revenue, share.share * 100.0),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Table with a CSS bar per row: (label, value for the bar, displayed value)
+fn bar_table(headers: [&str; 2], rows: Vec<(String, f64, String)>) -> String {
+    if rows.is_empty() {
+        return "<p class=\"muted\">No data.</p>".to_string();
+    }
+
+    let max = rows.iter().map(|(_, value, _)| *value).fold(0.0, f64::max);
+    let mut html = format!(
+        "<table>\n<tr><th>{}</th><th></th><th>{}</th></tr>\n",
+        escape_html(headers[0]),
+        escape_html(headers[1])
+    );
+
+    for (label, value, shown) in &rows {
+        let percent = if max > 0.0 { value / max * 100.0 } else { 0.0 };
+        html.push_str(&format!(
+            "<tr><td>{}</td><td style=\"width:60%\"><div class=\"bar\" style=\"width:{:.1}%\"></div></td><td class=\"num\">{}</td></tr>\n",
+            escape_html(label),
+            percent,
+            escape_html(shown)
+        ));
+    }
+
+    html.push_str("</table>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::DayTotal;
+    use chrono::NaiveDate;
+
+    fn data() -> DashboardData {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        DashboardData {
+            generated_at: Utc::now(),
+            daily: DailyReport {
+                date,
+                orders: 2,
+                cancelled: 1,
+                sla_breaches: 0,
+                revenue: 6.0,
+                average_ticket: 3.0,
+                hourly: vec![(8, 2)],
+                other_currencies: Vec::new(),
+            },
+            weekly: WeeklyReport {
+                from: date,
+                to: date,
+                days: vec![DayTotal {
+                    date,
+                    orders: 2,
+                    revenue: 6.0,
+                }],
+                orders: 2,
+                revenue: 6.0,
+            },
+            product_mix: vec![MixShare {
+                name: "<script>".to_string(),
+                count: 2,
+                revenue: 6.0,
+                share: 1.0,
+            }],
+            payment_mix: vec![],
+        }
+    }
+
+    #[test]
+    fn test_dashboard_is_self_contained() {
+        let html = render_dashboard(&data());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("$6.00"));
+        assert!(html.contains("08:00"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn test_labels_are_escaped() {
+        let html = render_dashboard(&data());
+
+        assert!(html.contains("&lt;script&gt; (2)"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("No data."));
+    }
+}
@@ -0,0 +1,37 @@
+// SOLID: The Presentation Layer (rendering for humans)
+//
+// Services compute numbers; this layer turns them into something to LOOK at:
+// terminal charts, HTML pages and emails, receipts, accounting files, metrics scrapes.
+//
+// PRINCIPLES:
+//
+// 1. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    Renderers only format. They never read a repository or compute a total,
+//    so a layout change can't break a report's numbers.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    Renderers take plain values (labels, numbers, report structs).
+//    They don't know where the data came from.
+
+pub mod accounting_formats;
+pub mod campaign;
+pub mod charts;
+pub mod daily_digest;
+pub mod html_dashboard;
+pub mod pickup_board;
+pub mod prometheus;
+pub mod receipt;
+pub mod styled_writer;
+pub mod template;
+
+// Re-export for convenience
+pub use accounting_formats::{AccountingExporter, LedgerExporter, OfxExporter, QifExporter};
+pub use campaign::render_campaign;
+pub use charts::{bar_chart, sparkline};
+pub use daily_digest::render_daily_digest;
+pub use html_dashboard::{render_dashboard, DashboardData};
+pub use pickup_board::{board_event, render_pickup_board, render_status_page};
+pub use prometheus::{render_live_prometheus, render_prometheus};
+pub use receipt::{render_localized_receipt, render_receipt};
+pub use styled_writer::StyledWriter;
+pub use template::{escape_html, render_template, TemplateContext};
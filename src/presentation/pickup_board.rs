@@ -0,0 +1,176 @@
+// SOLID: PickupBoard pages - the customers' "preparing / ready" screen
+//
+// Two ways to keep the screen current, with the same look:
+//
+// - live: a few lines of script open an EventSource on the events URL and
+//   move codes between the two columns as changes arrive. Server-Sent Events
+//   are plain text over one long HTTP response, so any server that can write
+//   lines can feed it, and browsers reconnect on their own.
+// - static: the codes are in the HTML and the page reloads itself every few
+//   seconds. No script and no server: a file rewritten by `status-board`.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// PickupBoard (services) decides what changed. This file only renders the
+// page and frames each change as an SSE event.
+
+use super::template::{escape_html, render_template, TemplateContext};
+use crate::services::{BoardChange, BoardStatus};
+use chrono::{DateTime, Utc};
+
+const STYLE: &str = "<style>
+  body { font-family: system-ui, sans-serif; margin: 0; background: #3b2f2f; color: #fff; }
+  h1 { text-align: center; margin: 1rem 0; }
+  .columns { display: flex; gap: 2rem; padding: 0 2rem; }
+  .column { flex: 1; background: #4e3d3d; border-radius: 12px; padding: 1rem; min-height: 60vh; }
+  .column h2 { margin-top: 0; text-align: center; color: #f7f1eb; }
+  #ready h2 { color: #9be29b; }
+  .code { font-size: 3rem; font-weight: bold; text-align: center; font-family: monospace; }
+  #ready .code { color: #9be29b; }
+  .offline { position: fixed; bottom: 0.5rem; right: 1rem; color: #e29b9b; display: none; }
+  .updated { text-align: center; color: #8a7f7f; }
+</style>";
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{title}}</title>
+{{{style}}}
+</head>
+<body>
+<h1>☕ {{title}}</h1>
+<div class="columns">
+  <div class="column" id="preparing"><h2>Preparing</h2></div>
+  <div class="column" id="ready"><h2>Ready for pickup</h2></div>
+</div>
+<div class="offline" id="offline">Reconnecting…</div>
+<script>
+  const events = new EventSource("{{events_url}}");
+  events.addEventListener("board", (event) => {
+    const change = JSON.parse(event.data);
+    const old = document.getElementById(change.order_id);
+    if (old) old.remove();
+    if (change.status === "gone") return;
+    const tile = document.createElement("div");
+    tile.id = change.order_id;
+    tile.className = "code";
+    tile.textContent = change.code;
+    document.getElementById(change.status).appendChild(tile);
+  });
+  // On reconnect the server replays the whole board: start from empty
+  events.onopen = () => {
+    document.getElementById("offline").style.display = "none";
+    document.querySelectorAll(".code").forEach((tile) => tile.remove());
+  };
+  events.onerror = () => { document.getElementById("offline").style.display = "block"; };
+</script>
+</body>
+</html>
+"#;
+
+const STATIC_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{{refresh}}">
+<title>{{title}}</title>
+{{{style}}}
+</head>
+<body>
+<h1>☕ {{title}}</h1>
+<div class="columns">
+  <div class="column" id="preparing"><h2>Now preparing</h2>{{{preparing}}}</div>
+  <div class="column" id="ready"><h2>Ready for pickup</h2>{{{ready}}}</div>
+</div>
+<p class="updated">Updated {{generated_at}}</p>
+</body>
+</html>
+"#;
+
+/// The board page; `events_url` is where it subscribes (e.g. `/events`)
+pub fn render_pickup_board(title: &str, events_url: &str) -> String {
+    let mut context = TemplateContext::new();
+    context
+        .set("title", title)
+        .set("style", STYLE)
+        .set("events_url", events_url);
+    render_template(PAGE, &context)
+}
+
+/// The board as a page that reloads itself every `refresh_seconds`
+pub fn render_status_page(
+    title: &str,
+    entries: &[BoardChange],
+    generated_at: DateTime<Utc>,
+    refresh_seconds: u32,
+) -> String {
+    let codes = |status: BoardStatus| -> String {
+        entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .map(|entry| format!("<div class=\"code\">{}</div>\n", escape_html(&entry.code)))
+            .collect()
+    };
+
+    let mut context = TemplateContext::new();
+    context
+        .set("title", title)
+        .set("style", STYLE)
+        .set("refresh", refresh_seconds.to_string())
+        .set("preparing", codes(BoardStatus::Preparing))
+        .set("ready", codes(BoardStatus::Ready))
+        .set("generated_at", generated_at.format("%H:%M:%S UTC").to_string());
+    render_template(STATIC_PAGE, &context)
+}
+
+/// One change as a Server-Sent Event named `board`
+pub fn board_event(change: &BoardChange) -> String {
+    // JSON has no raw newlines, so the data always fits on one `data:` line
+    let json = serde_json::to_string(change).unwrap_or_default();
+    format!("event: board\ndata: {}\n\n", json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::BoardStatus;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_page_subscribes_and_events_are_framed() {
+        let html = render_pickup_board("Pickup <Board>", "/events");
+        assert!(html.contains(r#"new EventSource("/events")"#));
+        assert!(html.contains("Pickup &lt;Board&gt;"));
+        assert!(!html.contains("{{"));
+
+        let event = board_event(&BoardChange {
+            order_id: Uuid::new_v4(),
+            code: "3FA8".to_string(),
+            status: BoardStatus::Ready,
+            at: Utc::now(),
+        });
+        assert!(event.starts_with("event: board\ndata: {"));
+        assert!(event.contains(r#""status":"ready""#));
+        assert!(event.ends_with("}\n\n"));
+        assert_eq!(event.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_static_page_lists_codes_by_column() {
+        let entry = |code: &str, status| BoardChange {
+            order_id: Uuid::new_v4(),
+            code: code.to_string(),
+            status,
+            at: Utc::now(),
+        };
+        let entries = [entry("AB12", BoardStatus::Preparing), entry("CD34", BoardStatus::Ready)];
+
+        let html = render_status_page("Pickup", &entries, Utc::now(), 20);
+        assert!(html.contains(r#"<meta http-equiv="refresh" content="20">"#));
+        let ready_column = html.split(r#"id="ready""#).nth(1).unwrap();
+        assert!(ready_column.contains("CD34") && !ready_column.contains("AB12"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("{{"));
+    }
+}
@@ -0,0 +1,115 @@
+// SOLID: Prometheus exporter - latency percentiles in the text exposition format
+//
+// Each duration becomes a Prometheus summary:
+//
+// # HELP coffee_order_prep_seconds Time from Preparing to Ready
+// # TYPE coffee_order_prep_seconds summary
+// coffee_order_prep_seconds{quantile="0.5"} 180
+// coffee_order_prep_seconds_sum 5400
+// coffee_order_prep_seconds_count 30
+//
+// LiveMetrics' window becomes three gauges (render_live_prometheus):
+// coffee_orders_per_minute, coffee_revenue_per_minute and
+// coffee_payment_failure_ratio.
+//
+// Write it to a file picked up by node_exporter's textfile collector, or
+// serve it from any HTTP handler. This module only formats (SRP).
+
+use crate::ports::{ORDER_PLACEMENT_SECONDS, ORDER_PREP_SECONDS};
+use crate::services::{LatencyReport, LatencySummary, WindowStats};
+use std::fmt::Write;
+
+/// Prefix of every exported metric name
+const NAMESPACE: &str = "coffee";
+
+/// Render the latency report as Prometheus summaries
+pub fn render_prometheus(report: &LatencyReport) -> String {
+    let mut text = String::new();
+    write_summary(
+        &mut text,
+        ORDER_PLACEMENT_SECONDS,
+        "Time from order creation to payment",
+        &report.placement,
+    );
+    write_summary(
+        &mut text,
+        ORDER_PREP_SECONDS,
+        "Time from Preparing to Ready",
+        &report.preparation,
+    );
+    text
+}
+
+/// Render the live window as Prometheus gauges
+pub fn render_live_prometheus(live: &WindowStats) -> String {
+    let minutes = live.window.num_minutes();
+    let gauges = [
+        ("orders_per_minute", "Orders placed per minute", live.orders_per_minute()),
+        ("revenue_per_minute", "Revenue per minute", live.revenue_per_minute().to_major()),
+        ("payment_failure_ratio", "Share of payments that failed", live.failure_rate()),
+    ];
+    let mut text = String::new();
+    for (metric, help, value) in gauges {
+        let name = format!("{}_{}", NAMESPACE, metric);
+        let _ = writeln!(text, "# HELP {} {}, last {} minutes", name, help, minutes);
+        let _ = writeln!(text, "# TYPE {} gauge", name);
+        let _ = writeln!(text, "{} {}", name, value);
+    }
+    text
+}
+
+fn write_summary(text: &mut String, metric: &str, help: &str, summary: &LatencySummary) {
+    let name = format!("{}_{}", NAMESPACE, metric);
+    // Writing to a String cannot fail
+    let _ = writeln!(text, "# HELP {} {}", name, help);
+    let _ = writeln!(text, "# TYPE {} summary", name);
+    let quantiles = [("0.5", summary.p50), ("0.95", summary.p95), ("0.99", summary.p99)];
+    for (quantile, value) in quantiles {
+        let _ = writeln!(text, "{}{{quantile=\"{}\"}} {}", name, quantile, value);
+    }
+    let _ = writeln!(text, "{}_sum {}", name, summary.sum);
+    let _ = writeln!(text, "{}_count {}", name, summary.samples);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Money;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_summaries_in_exposition_format() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let report = LatencyReport {
+            from: date,
+            to: date,
+            placement: LatencySummary::from_samples(vec![1.5, 2.0]),
+            preparation: LatencySummary::from_samples(vec![]),
+        };
+
+        let text = render_prometheus(&report);
+
+        assert!(text.contains("# TYPE coffee_order_placement_seconds summary\n"));
+        assert!(text.contains("coffee_order_placement_seconds{quantile=\"0.5\"} 1.5\n"));
+        assert!(text.contains("coffee_order_placement_seconds_sum 3.5\n"));
+        assert!(text.contains("coffee_order_prep_seconds_count 0\n"));
+    }
+
+    #[test]
+    fn test_live_window_as_gauges() {
+        let live = WindowStats {
+            at: chrono::Utc::now(),
+            window: chrono::Duration::minutes(5),
+            orders: 10,
+            revenue: Money::usd(40.0),
+            failures: 10,
+        };
+
+        let text = render_live_prometheus(&live);
+
+        assert!(text.contains("# TYPE coffee_orders_per_minute gauge\n"));
+        assert!(text.contains("coffee_orders_per_minute 2\n"));
+        assert!(text.contains("coffee_revenue_per_minute 8\n"));
+        assert!(text.contains("coffee_payment_failure_ratio 0.5\n"));
+    }
+}
@@ -0,0 +1,238 @@
+// SOLID: Itemized receipts
+//
+// A receipt lists every part of every price (see Beverage::price_lines):
+// the drink itself, then any adjustment a pricing rule added, such as a
+// peak-hour surcharge. Nothing is folded silently into the total.
+//
+// Price lines come from the beverages, so build them before the beverages
+// are handed to OrderService::place_order:
+//
+// let lines: Vec<Vec<PriceLine>> = beverages.iter().map(|b| b.price_lines()).collect();
+// let order = service.place_order(customer, beverages)?;
+// println!("{}", render_receipt(&order, &lines));
+//
+// Groups line up with the order's items: a beverage ordered several times
+// (OrderService::place_order_lines) is printed once, "2 x ...", with its
+// amounts multiplied.
+//
+// Where the law requires it, OrderService records what a fiscal receipt
+// must show (see `with_vat_rates` and `with_fiscal_receipts`): the shop's
+// VAT registration, the receipt's sequential number, and the VAT included
+// in the total, per rate. Those rows only appear when the order has them.
+//
+// `render_localized_receipt` names the beverages in the customer's language
+// (see MenuTranslations); adjustments and totals keep their wording.
+//
+// Receipts follow the process-wide OutputStyle: plain ones carry no emoji
+// (a promotion's "☕ Happy hour" label prints as "Happy hour").
+
+use crate::domain::{Locale, MenuTranslations, Money, Order, OutputStyle, PriceLine};
+use std::fmt::Write;
+
+/// Characters per receipt line (a narrow thermal printer)
+const WIDTH: usize = 40;
+
+/// Render a plain-text receipt: one group of lines per beverage
+pub fn render_receipt(order: &Order, items: &[Vec<PriceLine>]) -> String {
+    let rule = "-".repeat(WIDTH);
+    let mut text = String::new();
+
+    // Writing to a String cannot fail
+    let _ = writeln!(text, "{:^width$}", "Coffee Shop", width = WIDTH);
+    if let Some(tax_id) = &order.seller_tax_id {
+        let _ = writeln!(text, "{:^width$}", format!("VAT No. {}", tax_id), width = WIDTH);
+    }
+    if let Some(number) = order.fiscal_number {
+        let _ = writeln!(text, "Receipt No. {:06}", number);
+    }
+    let _ = writeln!(
+        text,
+        "Order {}  {}",
+        order.short_id(),
+        order.created_at.format("%Y-%m-%d %H:%M")
+    );
+    let _ = writeln!(text, "{}", rule);
+
+    for (index, lines) in items.iter().enumerate() {
+        let quantity = order.items.get(index).map_or(1, |item| item.quantity);
+        for (i, line) in lines.iter().enumerate() {
+            // Adjustments are indented under the beverage they apply to
+            let label = match (i, quantity) {
+                (0, 1) => line.label.clone(),
+                (0, _) => format!("{} x {}", quantity, line.label),
+                _ => format!("  {}", line.label),
+            };
+            let amount = line.amount * quantity as u32;
+            let _ = writeln!(text, "{}", amount_row(&label, amount));
+        }
+    }
+
+    let _ = writeln!(text, "{}", rule);
+    let _ = writeln!(text, "{}", amount_row("Total", order.total_price));
+    if !order.surcharge.is_zero() {
+        let _ = writeln!(text, "{}", amount_row("Payment surcharge", order.surcharge));
+    }
+    if !order.rounding_adjustment.is_zero() {
+        let _ = writeln!(text, "{}", amount_row("Rounding", order.rounding_adjustment));
+    }
+    if !order.surcharge.is_zero() || !order.rounding_adjustment.is_zero() {
+        let _ = writeln!(text, "{}", amount_row("Paid", order.amount_charged()));
+    }
+    for line in order.vat_breakdown() {
+        // 0.077 * 100.0 is 7.700000000000001: print tenths of a percent
+        let percent = (line.rate * 1000.0).round() / 10.0;
+        let label = format!("  incl. VAT {}% on {:.2}", percent, line.gross.to_major());
+        let _ = writeln!(text, "{}", amount_row(&label, line.vat));
+    }
+    if let Some(payment_id) = &order.payment_id {
+        let _ = writeln!(text, "Paid: {}", payment_id);
+    }
+    OutputStyle::current().apply(&text)
+}
+
+/// Like `render_receipt`, with each beverage's line named in `locale`
+pub fn render_localized_receipt(
+    order: &Order,
+    items: &[Vec<PriceLine>],
+    translations: &MenuTranslations,
+    locale: &Locale,
+) -> String {
+    let localized: Vec<Vec<PriceLine>> = items
+        .iter()
+        .zip(&order.items)
+        .map(|(lines, item)| {
+            let mut lines = lines.clone();
+            if let Some(first) = lines.first_mut() {
+                first.label = translations.describe(&item.beverage_name, &first.label, locale);
+            }
+            lines
+        })
+        .collect();
+    render_receipt(order, &localized)
+}
+
+/// "Coffee (Medium)                     3.50"
+fn amount_row(label: &str, amount: Money) -> String {
+    let amount = format!("{:.2}", amount.to_major());
+    let room = WIDTH.saturating_sub(amount.len() + 1);
+    let label: String = label.chars().take(room).collect();
+    format!("{:<room$} {}", label, amount, room = room)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Beverage, Coffee, Customer, OrderItem};
+
+    #[test]
+    fn test_adjustments_are_listed_under_their_item() {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let mut order = Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: "Coffee".to_string(),
+                beverage_description: "Coffee (Medium)".to_string(),
+                price: Money::usd(3.85),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        let lines = vec![vec![
+            PriceLine {
+                label: "Coffee (Medium)".to_string(),
+                amount: Money::usd(3.50),
+            },
+            PriceLine {
+                label: "Peak-hour surcharge (x1.10)".to_string(),
+                amount: Money::usd(0.35),
+            },
+        ]];
+
+        let receipt = render_receipt(&order, &lines);
+
+        assert!(receipt.contains("\nCoffee (Medium)"));
+        assert!(receipt.contains("\n  Peak-hour surcharge (x1.10)"));
+        assert!(receipt.contains("0.35\n"));
+        assert!(receipt.contains(&amount_row("Total", Money::usd(3.85))));
+        assert!(receipt.lines().all(|line| line.chars().count() <= WIDTH));
+        assert!(!receipt.contains("Rounding"));
+
+        order.rounding_adjustment = Money::usd(0.05);
+        let rounded = render_receipt(&order, &lines);
+        assert!(rounded.contains(&amount_row("Rounding", Money::usd(0.05))));
+        assert!(rounded.contains(&amount_row("Paid", Money::usd(3.90))));
+        assert!(!rounded.contains("VAT"));
+
+        order.surcharge = Money::usd(0.08);
+        let surcharged = render_receipt(&order, &lines);
+        assert!(surcharged.contains(&amount_row("Payment surcharge", Money::usd(0.08))));
+        assert!(surcharged.contains(&amount_row("Paid", Money::usd(3.98))));
+    }
+
+    #[test]
+    fn test_quantities_are_printed_once_and_multiplied() {
+        let coffee = Coffee::medium();
+        let item = OrderItem {
+            quantity: 3,
+            ..OrderItem::of(&coffee)
+        };
+        let order = Order::new(Customer::named("Ada"), vec![item]);
+
+        let receipt = render_receipt(&order, &[coffee.price_lines()]);
+
+        assert!(receipt.contains(&amount_row("3 x Coffee (Medium)", Money::usd(10.50))));
+        assert!(receipt.contains(&amount_row("Total", Money::usd(10.50))));
+    }
+
+    #[test]
+    fn test_localized_receipt_names_beverages_in_the_locale() {
+        let coffee = Coffee::medium();
+        let order = Order::new(Customer::named("Ada"), vec![OrderItem::of(&coffee)]);
+        let translations = MenuTranslations::new()
+            .with_name("Coffee", "it", "Caffè")
+            .with_name("Medium", "it", "Medio");
+
+        let receipt = render_localized_receipt(
+            &order,
+            &[coffee.price_lines()],
+            &translations,
+            &Locale::parse("it-CH"),
+        );
+
+        assert!(receipt.contains(&amount_row("Caffè (Medio)", Money::usd(3.50))));
+        assert!(receipt.contains(&amount_row("Total", Money::usd(3.50))));
+    }
+
+    #[test]
+    fn test_fiscal_receipt_shows_registration_number_and_vat() {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let item = |label: &str, price: f64, vat_rate: f64| OrderItem {
+            beverage_name: label.to_string(),
+            beverage_description: label.to_string(),
+            price: Money::usd(price),
+            quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: Some(vat_rate),
+        };
+        let mut order = Order::new(
+            customer,
+            vec![item("Coffee", 4.20, 0.081), item("Smoothie (Mango)", 5.00, 0.026)],
+        );
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        order.fiscal_number = Some(42);
+        order.seller_tax_id = Some("CHE-123.456.789 MWST".to_string());
+
+        let receipt = render_receipt(&order, &[]);
+
+        assert!(receipt.contains("VAT No. CHE-123.456.789 MWST"));
+        assert!(receipt.contains("Receipt No. 000042\n"));
+        // 5.00 * 0.026 / 1.026 and 4.20 * 0.081 / 1.081
+        assert!(receipt.contains(&amount_row("  incl. VAT 2.6% on 5.00", Money::usd(0.13))));
+        assert!(receipt.contains(&amount_row("  incl. VAT 8.1% on 4.20", Money::usd(0.31))));
+        assert!(receipt.lines().all(|line| line.chars().count() <= WIDTH));
+    }
+}
@@ -0,0 +1,88 @@
+// SOLID: StyledWriter - any output, drawn in the process-wide OutputStyle
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// CLI commands print their ✅ and their bar charts as they always have; the
+// writer they are handed turns them into plain text when the style says so.
+// No command takes a `--plain` of its own, and a new command is plain
+// without doing anything.
+//
+// Text is converted a line at a time (an emoji's bytes never straddle a
+// newline), so writes may split a character anywhere.
+
+use crate::domain::OutputStyle;
+use std::io::{self, Write};
+
+/// Passes text through `OutputStyle::apply`, line by line
+pub struct StyledWriter<W: Write> {
+    inner: W,
+    style: OutputStyle,
+    // Bytes of the line not finished yet (plain style only)
+    pending: Vec<u8>,
+}
+
+impl<W: Write> StyledWriter<W> {
+    /// In the process-wide style
+    pub fn new(inner: W) -> Self {
+        Self::with_style(inner, OutputStyle::current())
+    }
+
+    pub fn with_style(inner: W, style: OutputStyle) -> Self {
+        Self {
+            inner,
+            style,
+            pending: Vec::new(),
+        }
+    }
+
+    fn write_lines(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let text = String::from_utf8_lossy(bytes);
+        self.inner.write_all(self.style.apply(&text).as_bytes())
+    }
+}
+
+impl<W: Write> Write for StyledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.style == OutputStyle::Rich {
+            return self.inner.write(buf);
+        }
+        self.pending.extend_from_slice(buf);
+        if let Some(end) = self.pending.iter().rposition(|&b| b == b'\n') {
+            let lines: Vec<u8> = self.pending.drain(..=end).collect();
+            self.write_lines(&lines)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            self.write_lines(&rest)?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for StyledWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_writer_converts_split_writes() {
+        let mut out = Vec::new();
+        {
+            let mut writer = StyledWriter::with_style(&mut out, OutputStyle::Plain);
+            let text = "✅ Saved\nCoffee ██ 2\n📊 Done".as_bytes();
+            // Split inside the check mark's bytes
+            writer.write_all(&text[..2]).unwrap();
+            writer.write_all(&text[2..]).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(out).unwrap(), "[ok] Saved\nCoffee ## 2\nDone");
+    }
+}
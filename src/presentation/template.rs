@@ -0,0 +1,105 @@
+// SOLID: A minimal text template engine
+//
+// `{{name}}`   is replaced by the HTML-escaped value
+// `{{{name}}}` is replaced by the raw value (for already-rendered fragments)
+//
+// Unknown placeholders render as nothing, so a template can be ahead of its data.
+// That's all the dashboard needs; no template crate required.
+
+use std::collections::HashMap;
+
+/// Values available to a template
+#[derive(Debug, Default)]
+pub struct TemplateContext {
+    values: HashMap<String, String>,
+}
+
+impl TemplateContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.values.insert(name.to_string(), value.into());
+        self
+    }
+
+    fn get(&self, name: &str) -> &str {
+        self.values.get(name.trim()).map(String::as_str).unwrap_or("")
+    }
+}
+
+/// Fill `template` with the values of `context`
+pub fn render_template(template: &str, context: &TemplateContext) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start..];
+
+        let (raw, open, close) = if after.starts_with("{{{") {
+            (true, "{{{", "}}}")
+        } else {
+            (false, "{{", "}}")
+        };
+
+        match after[open.len()..].find(close) {
+            Some(end) => {
+                let name = &after[open.len()..open.len() + end];
+                let value = context.get(name);
+                if raw {
+                    output.push_str(value);
+                } else {
+                    output.push_str(&escape_html(value));
+                }
+                rest = &after[open.len() + end + close.len()..];
+            }
+            None => {
+                // Unclosed placeholder: keep the text as is
+                output.push_str(after);
+                rest = "";
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Escape the characters that matter in HTML text and attributes
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaped_and_raw_placeholders() {
+        let mut context = TemplateContext::new();
+        context.set("name", "<Tom & Jerry>").set("table", "<table></table>");
+
+        let output = render_template("<h1>{{ name }}</h1>{{{table}}}{{missing}}!", &context);
+
+        assert_eq!(output, "<h1>&lt;Tom &amp; Jerry&gt;</h1><table></table>!");
+    }
+
+    #[test]
+    fn test_unclosed_placeholder_is_kept() {
+        let output = render_template("a {{b", &TemplateContext::new());
+        assert_eq!(output, "a {{b");
+    }
+}
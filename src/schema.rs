@@ -0,0 +1,80 @@
+// JSON Schema of the types other systems read or write (feature `schema`)
+//
+// Teams integrating with the shop generate their clients from these
+// instead of copying field lists from the Rust source:
+//
+// - stored orders and customers (the JSON repository, exports)
+// - events: bus messages, webhook deliveries, CloudEvents envelopes
+// - API requests: webhook subscriptions, order import records, gateway chargebacks
+//
+// Schemas are derived from the types themselves (schemars follows the serde
+// attributes), so they can't drift from what is actually written. A type
+// joins the list with `#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]`
+// and one line below.
+
+use crate::bus::{
+    ExperimentExposure, OrderCancelled, OrderCompleted, OrderNoShow, OrderPlaced, OrderReady,
+    PaymentFailed,
+};
+use crate::domain::{Chargeback, CloudEvent, Customer, Order, WebhookEvent, WebhookSubscription};
+use crate::services::ImportRecord;
+use schemars::schema_for;
+use serde_json::Value;
+
+/// Every exported schema, by type name
+pub fn schemas() -> Vec<(&'static str, Value)> {
+    let schemas = [
+        ("Order", schema_for!(Order)),
+        ("Customer", schema_for!(Customer)),
+        ("OrderPlaced", schema_for!(OrderPlaced)),
+        ("OrderReady", schema_for!(OrderReady)),
+        ("OrderCompleted", schema_for!(OrderCompleted)),
+        ("OrderCancelled", schema_for!(OrderCancelled)),
+        ("OrderNoShow", schema_for!(OrderNoShow)),
+        ("PaymentFailed", schema_for!(PaymentFailed)),
+        ("ExperimentExposure", schema_for!(ExperimentExposure)),
+        ("WebhookEvent", schema_for!(WebhookEvent)),
+        ("CloudEvent", schema_for!(CloudEvent)),
+        ("WebhookSubscription", schema_for!(WebhookSubscription)),
+        ("ImportRecord", schema_for!(ImportRecord)),
+        ("Chargeback", schema_for!(Chargeback)),
+    ];
+    schemas
+        .into_iter()
+        .map(|(name, schema)| (name, schema.to_value()))
+        .collect()
+}
+
+/// One schema by type name (case-insensitive)
+pub fn schema(name: &str) -> Option<Value> {
+    schemas()
+        .into_iter()
+        .find(|(type_name, _)| type_name.eq_ignore_ascii_case(name))
+        .map(|(_, schema)| schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schemas_follow_serde_names() {
+        let order = schema("order").unwrap();
+        let properties = &order["properties"];
+        assert!(properties["total_price"].is_object());
+        assert!(properties["customer"].is_object());
+        // `#[serde(default)]` fields are optional
+        let required: Vec<&str> = order["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert!(required.contains(&"id"));
+        assert!(!required.contains(&"fiscal_number"));
+
+        let cloud_event = schema("CloudEvent").unwrap();
+        assert!(cloud_event["properties"]["type"].is_object());
+        assert!(schema("Beverage").is_none());
+    }
+}
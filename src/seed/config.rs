@@ -0,0 +1,99 @@
+// SOLID: SeedConfig - the shape of the generated data
+//
+// Plain data with sensible defaults. Changing the distributions is
+// configuration, never a change to the generator (OCP).
+
+use chrono::{Duration, NaiveDate, Utc};
+
+/// Relative weights of each product family
+///
+/// Weights don't need to add up to 1; only their ratio matters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductMix {
+    pub coffee: f64,
+    pub tea: f64,
+    pub smoothie: f64,
+}
+
+impl Default for ProductMix {
+    fn default() -> Self {
+        // A coffee shop sells mostly coffee
+        Self {
+            coffee: 0.65,
+            tea: 0.20,
+            smoothie: 0.15,
+        }
+    }
+}
+
+/// How many orders to generate, and how they are distributed
+#[derive(Debug, Clone)]
+pub struct SeedConfig {
+    /// First day with orders
+    pub start_date: NaiveDate,
+    /// Number of consecutive days
+    pub days: u32,
+    /// Average orders per day (actual days vary by ±20%)
+    pub orders_per_day: u32,
+    /// Relative number of orders for each hour of the day (0 = closed)
+    pub hourly_weights: [f64; 24],
+    pub product_mix: ProductMix,
+    /// Share of orders cancelled before payment (0.0 to 1.0)
+    pub cancellation_rate: f64,
+    /// Share of paid orders paid by card; the rest is cash (0.0 to 1.0)
+    pub card_share: f64,
+    /// Size of the customer pool (regulars come back)
+    pub customers: usize,
+    /// Same seed, same orders
+    pub rng_seed: u64,
+}
+
+impl SeedConfig {
+    /// `days` days of orders ending yesterday, with default distributions
+    pub fn new(days: u32) -> Self {
+        Self {
+            start_date: Utc::now().date_naive() - Duration::days(days as i64),
+            days,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for SeedConfig {
+    fn default() -> Self {
+        Self {
+            start_date: Utc::now().date_naive() - Duration::days(7),
+            days: 7,
+            orders_per_day: 80,
+            hourly_weights: default_hourly_weights(),
+            product_mix: ProductMix::default(),
+            cancellation_rate: 0.05,
+            card_share: 0.6,
+            customers: 150,
+            rng_seed: 42,
+        }
+    }
+}
+
+/// Open 7:00-19:00, with morning, lunch and afternoon rushes
+fn default_hourly_weights() -> [f64; 24] {
+    let mut weights = [0.0; 24];
+    let open_hours = [
+        (7, 6.0),
+        (8, 10.0),  // morning rush
+        (9, 7.0),
+        (10, 4.0),
+        (11, 4.0),
+        (12, 8.0),  // lunch
+        (13, 7.0),
+        (14, 3.0),
+        (15, 3.0),
+        (16, 5.0),  // afternoon pick-me-up
+        (17, 4.0),
+        (18, 2.0),
+    ];
+    for (hour, weight) in open_hours {
+        weights[hour] = weight;
+    }
+    weights
+}
@@ -0,0 +1,281 @@
+// SOLID: SeedGenerator - builds orders from a SeedConfig
+//
+// Orders are built from real Beverage values, so prices always follow the
+// current pricing rules. Only the timestamps are rewritten, to spread the
+// orders over the configured days and hours.
+
+use super::config::SeedConfig;
+use crate::domain::{
+    Beverage, Coffee, Customer, Order, OrderItem, OrderStatus, PriceBasis, Size, Smoothie,
+    StatusChange, Tea, TeaVariety,
+};
+use crate::adapters::SeededRandomness;
+use crate::ports::{OrderRepository, Randomness, RepositoryError};
+use chrono::{DateTime, Duration, Utc};
+
+const FIRST_NAMES: [&str; 12] = [
+    "Alice", "Bruno", "Chloe", "Diego", "Emma", "Farid", "Grace", "Hugo", "Ines", "Jonas",
+    "Keiko", "Liam",
+];
+
+const TEA_VARIETIES: [TeaVariety; 4] =
+    [TeaVariety::Green, TeaVariety::Black, TeaVariety::Herbal, TeaVariety::EarlGrey];
+
+const FRUITS: [&str; 5] = ["Banana", "Strawberry", "Mango", "Blueberry", "Pineapple"];
+
+/// What `populate()` wrote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeedSummary {
+    pub orders: usize,
+    pub cancelled: usize,
+    /// Sum of the non-cancelled orders
+    pub revenue: f64,
+}
+
+/// Deterministic order generator
+pub struct SeedGenerator {
+    config: SeedConfig,
+    rng: Box<dyn Randomness>,
+    customers: Vec<Customer>,
+}
+
+impl SeedGenerator {
+    /// Draws from `SeededRandomness::new(config.rng_seed)`
+    pub fn new(config: SeedConfig) -> Self {
+        let rng = SeededRandomness::new(config.rng_seed);
+        Self::with_randomness(config, rng)
+    }
+
+    /// Draws from `rng` instead; `config.rng_seed` is ignored
+    pub fn with_randomness(config: SeedConfig, rng: impl Randomness + 'static) -> Self {
+        let customers = (0..config.customers.max(1))
+            .map(|n| {
+                let first = FIRST_NAMES[n % FIRST_NAMES.len()];
+                let phone = rng.chance(0.5).then(|| format!("555-01{:02}", n % 100));
+                let mut customer = Customer::new(
+                    format!("{} {}", first, n + 1),
+                    format!("{}.{}@example.com", first.to_lowercase(), n + 1),
+                    phone,
+                );
+                customer.id = rng.uuid();
+                customer
+            })
+            .collect();
+
+        Self {
+            config,
+            rng: Box::new(rng),
+            customers,
+        }
+    }
+
+    /// Every order for the configured period, oldest first
+    pub fn generate(&mut self) -> Vec<Order> {
+        let mut orders = Vec::new();
+
+        for day in 0..self.config.days {
+            let date = self.config.start_date + Duration::days(day as i64);
+            let midnight = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+            // ±20% day-to-day variation
+            let base = self.config.orders_per_day as f64;
+            let count = (base * (0.8 + 0.4 * self.rng.next_f64())).round() as usize;
+
+            let mut day_orders: Vec<Order> = (0..count)
+                .filter_map(|_| {
+                    let hour = self.rng.weighted(&self.config.hourly_weights)?;
+                    let placed_at = midnight
+                        + Duration::hours(hour as i64)
+                        + Duration::seconds(self.rng.below(3600) as i64);
+                    Some(self.generate_order(placed_at))
+                })
+                .collect();
+
+            day_orders.sort_by_key(|order| order.created_at);
+            orders.extend(day_orders);
+        }
+
+        orders
+    }
+
+    /// Generate the orders and save them into `repository`
+    pub fn populate<R: OrderRepository>(
+        &mut self,
+        repository: &mut R,
+    ) -> Result<SeedSummary, RepositoryError> {
+        let orders = self.generate();
+        let mut summary = SeedSummary {
+            orders: 0,
+            cancelled: 0,
+            revenue: 0.0,
+        };
+
+        // One batch: a JSON repository rewrites its file once, not once per order
+        repository.save_batch(&orders)?;
+
+        for order in &orders {
+            summary.orders += 1;
+            if order.status == OrderStatus::Cancelled {
+                summary.cancelled += 1;
+            } else {
+                summary.revenue += order.total_price.to_major();
+            }
+        }
+
+        Ok(summary)
+    }
+
+    fn generate_order(&mut self, placed_at: DateTime<Utc>) -> Order {
+        let customer = self.customers[self.rng.below(self.customers.len() as u64) as usize].clone();
+
+        // 1 item most of the time, sometimes 2 or 3
+        let item_count = 1 + self.rng.weighted(&[6.0, 3.0, 1.0]).unwrap_or(0);
+        let items: Vec<OrderItem> = (0..item_count)
+            .map(|_| {
+                let beverage = self.generate_beverage();
+                OrderItem {
+                    beverage_name: beverage.name(),
+                    beverage_description: beverage.description(),
+                    price: beverage.price(),
+                    quantity: 1,
+                    prep_seconds: Some(beverage.prep_time_estimate().num_seconds() as u32),
+                    price_basis: Some(PriceBasis::of(beverage.as_ref())),
+                    vat_rate: None,
+                }
+            })
+            .collect();
+
+        let mut order = Order::new(customer, items);
+        order.id = self.rng.uuid();
+        order.created_at = placed_at;
+        order.status_history = vec![StatusChange {
+            status: OrderStatus::Pending,
+            at: placed_at,
+        }];
+
+        let cancelled = self.rng.chance(self.config.cancellation_rate);
+        // Same prefixes as the real payment adapters, so payment-mix reports work
+        let method = if self.rng.chance(self.config.card_share) {
+            "CC"
+        } else {
+            "CASH"
+        };
+        let mut at = placed_at;
+        let mut step = |order: &mut Order, status: OrderStatus, max_minutes: u64| {
+            at += Duration::seconds(30 + self.rng.below(max_minutes * 60) as i64);
+            order.status = status.clone();
+            order.status_history.push(StatusChange { status, at });
+        };
+
+        if cancelled {
+            step(&mut order, OrderStatus::Cancelled, 5);
+            return order;
+        }
+
+        order.payment_id = Some(format!("{}-{}", method, order.id));
+        step(&mut order, OrderStatus::Paid, 1);
+        step(&mut order, OrderStatus::Preparing, 2);
+        step(&mut order, OrderStatus::Ready, 8);
+        step(&mut order, OrderStatus::Completed, 5);
+        order
+    }
+
+    fn generate_beverage(&mut self) -> Box<dyn Beverage> {
+        let mix = &self.config.product_mix;
+        let family = self.rng.weighted(&[mix.coffee, mix.tea, mix.smoothie]).unwrap_or(0);
+        // Medium sells best
+        let size = match self.rng.weighted(&[3.0, 5.0, 2.0]) {
+            Some(0) => Size::Small,
+            Some(2) => Size::Large,
+            _ => Size::Medium,
+        };
+
+        match family {
+            1 => Box::new(Tea {
+                size,
+                variety: TEA_VARIETIES[self.rng.below(TEA_VARIETIES.len() as u64) as usize]
+                    .clone(),
+            }),
+            2 => {
+                let count = 1 + self.rng.below(3) as usize;
+                let fruits = (0..count)
+                    .map(|_| FRUITS[self.rng.below(FRUITS.len() as u64) as usize].to_string())
+                    .collect();
+                Box::new(Smoothie { size, fruits })
+            }
+            _ => Box::new(Coffee {
+                size,
+                extra_shots: self.rng.weighted(&[7.0, 2.0, 1.0]).unwrap_or(0) as u8,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use chrono::{NaiveDate, Timelike};
+
+    fn config() -> SeedConfig {
+        SeedConfig {
+            start_date: NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+            days: 3,
+            orders_per_day: 50,
+            ..SeedConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_orders() {
+        let first = SeedGenerator::new(config()).generate();
+        let second = SeedGenerator::new(config()).generate();
+
+        assert_eq!(first.len(), second.len());
+        let totals = |orders: &[Order]| orders.iter().map(|o| o.total_price).collect::<Vec<_>>();
+        assert_eq!(totals(&first), totals(&second));
+        // IDs too, so two runs of a demo print the same thing
+        let ids = |orders: &[Order]| {
+            orders.iter().map(|o| (o.id, o.customer.id)).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&first), ids(&second));
+    }
+
+    #[test]
+    fn test_orders_follow_opening_hours() {
+        let orders = SeedGenerator::new(config()).generate();
+
+        assert!(orders.len() >= 3 * 40 && orders.len() <= 3 * 60);
+        for order in &orders {
+            let hour = order.created_at.hour();
+            assert!((7..19).contains(&hour), "order at {}h", hour);
+            assert!(order.created_at.date_naive() >= config().start_date);
+        }
+    }
+
+    #[test]
+    fn test_cancellation_rate_and_populate() {
+        let mut all_cancelled = config();
+        all_cancelled.cancellation_rate = 1.0;
+
+        let mut repo = MemoryOrderRepository::new();
+        let summary = SeedGenerator::new(all_cancelled).populate(&mut repo).unwrap();
+
+        assert_eq!(summary.orders, summary.cancelled);
+        assert_eq!(summary.revenue, 0.0);
+        assert_eq!(repo.list_all().unwrap().len(), summary.orders);
+    }
+
+    #[test]
+    fn test_completed_orders_are_consistent() {
+        let orders = SeedGenerator::new(config()).generate();
+        let completed = orders
+            .iter()
+            .find(|o| o.status == OrderStatus::Completed)
+            .unwrap();
+
+        assert!(completed.payment_id.is_some());
+        assert_eq!(completed.status_history.len(), 5);
+        assert!(completed.status_since() > completed.created_at);
+    }
+}
@@ -0,0 +1,29 @@
+// SOLID: Seed data (realistic demo orders)
+//
+// Reports, dashboards and benchmarks need weeks of orders.
+// Typing them in by hand isn't an option, so this module generates them.
+//
+// PRINCIPLES:
+//
+// 1. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    - SeedConfig: WHAT the data should look like (distributions)
+//    - SeedGenerator: HOW orders are built from those distributions
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    `populate()` writes through the OrderRepository trait,
+//    so any backend can be seeded: memory, JSON file, or a future database.
+//
+// Generation is deterministic: the same config (including `rng_seed`)
+// always produces the same orders. Handy for reproducible demos and benchmarks.
+//
+// ```text
+// SeedConfig ──> SeedGenerator ──generate()──> Vec<Order>
+//                      └──────────populate()──> any OrderRepository
+// ```
+
+pub mod config;
+pub mod generator;
+
+// Re-export for convenience
+pub use config::{ProductMix, SeedConfig};
+pub use generator::{SeedGenerator, SeedSummary};
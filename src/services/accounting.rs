@@ -0,0 +1,455 @@
+// SOLID: Accounting journal - orders turned into double-entry bookkeeping
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Owned by Accounting. This file decides WHICH accounts an order touches
+// (the business rule). Writing QIF, OFX or ledger files is the
+// presentation layer's job (presentation/accounting_formats.rs).
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// The chart of accounts is configuration (AccountMapping), usually loaded
+// from a JSON file, so each shop can match its own books without a code change.
+//
+// For each sale:
+//   debit  the payment account (cash drawer, card clearing...)  = amount charged
+//   credit one revenue account per product family               = item amounts
+//   credit the surcharge account for payment method surcharges (see PaymentRules)
+//   credit/debit the rounding account for cash rounding (see RoundingPolicy)
+//   credit/debit the adjustment account for any difference (discounts, hand edits)
+//
+// Store credit is a liability: the shop owes it until it's spent.
+//   granted:  debit the credit expense account,  credit the store credit liability
+//   spent:    the sale debits the liability instead of a payment account
+//   restored: debit adjustments (the refunded sale), credit the liability
+//
+// Every entry is in one currency, its order's (or its wallet's). The credit
+// liability is reported per currency held.
+
+use super::reporting_service::{is_sale, payment_method, product_family};
+use crate::domain::{Currency, Money, Order, Wallet, WalletEntryKind};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Which account each product family and payment method is booked to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountMapping {
+    /// Product family ("Coffee", "Tea", ...) -> income account
+    pub revenue: HashMap<String, String>,
+    pub default_revenue: String,
+    /// Payment method ("Cash", "Credit Card", ...) -> asset account
+    pub payment: HashMap<String, String>,
+    pub default_payment: String,
+    /// Receives the difference when an order total doesn't match its items
+    pub adjustments: String,
+    /// Surcharges charged for a payment method (card fees passed on)
+    pub surcharges: String,
+    /// Gains and losses from rounding cash totals
+    pub rounding: String,
+    /// Liability: store credit customers can still spend
+    pub store_credit: String,
+    /// What granting credit costs the shop (remedies, promotions)
+    pub credit_expense: String,
+    pub currency: String,
+}
+
+impl AccountMapping {
+    pub fn revenue_account(&self, product_family: &str) -> &str {
+        self.revenue
+            .get(product_family)
+            .unwrap_or(&self.default_revenue)
+    }
+
+    pub fn payment_account(&self, payment_method: &str) -> &str {
+        self.payment
+            .get(payment_method)
+            .unwrap_or(&self.default_payment)
+    }
+}
+
+impl Default for AccountMapping {
+    fn default() -> Self {
+        let revenue = [
+            ("Coffee", "Income:Sales:Coffee"),
+            ("Tea", "Income:Sales:Tea"),
+            ("Smoothie", "Income:Sales:Smoothies"),
+        ];
+        let payment = [
+            ("Cash", "Assets:Cash Drawer"),
+            ("Credit Card", "Assets:Card Clearing"),
+        ];
+
+        Self {
+            revenue: revenue
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            default_revenue: "Income:Sales:Other".to_string(),
+            payment: payment
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            default_payment: "Assets:Undeposited Funds".to_string(),
+            adjustments: "Income:Adjustments".to_string(),
+            surcharges: "Income:Payment Surcharges".to_string(),
+            rounding: "Income:Cash Rounding".to_string(),
+            store_credit: "Liabilities:Store Credit".to_string(),
+            credit_expense: "Expenses:Store Credit Granted".to_string(),
+            currency: "USD".to_string(),
+        }
+    }
+}
+
+/// One line of a journal entry (positive = debit, negative = credit)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Posting {
+    pub account: String,
+    pub amount: Money,
+}
+
+/// One balanced transaction, one per sale
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JournalEntry {
+    pub date: NaiveDate,
+    pub order_id: Uuid,
+    pub payment_id: String,
+    pub description: String,
+    pub postings: Vec<Posting>,
+}
+
+impl JournalEntry {
+    /// The debited payment account (always the first posting)
+    pub fn payment_posting(&self) -> &Posting {
+        &self.postings[0]
+    }
+
+    /// Sum of postings; zero for a balanced entry
+    pub fn balance(&self) -> Money {
+        sum_postings(&self.postings)
+    }
+}
+
+/// Journal entries for the sales among `orders`, oldest first
+pub fn journal_entries(orders: &[Order], mapping: &AccountMapping) -> Vec<JournalEntry> {
+    let mut entries: Vec<JournalEntry> = orders
+        .iter()
+        .filter(|order| is_sale(order))
+        .map(|order| journal_entry(order, mapping))
+        .collect();
+
+    entries.sort_by_key(|entry| entry.date);
+    entries
+}
+
+/// Book the store credit part of sales to the liability account
+///
+/// A sale paid partly with credit debits its payment account for the rest
+/// only; one paid entirely with credit debits the liability alone (which
+/// then is its payment posting).
+pub fn apply_store_credit(
+    entries: &mut [JournalEntry],
+    wallets: &[Wallet],
+    mapping: &AccountMapping,
+) {
+    let mut spent: HashMap<Uuid, Money> = HashMap::new();
+    for entry in wallets.iter().flat_map(|w| &w.entries) {
+        if let (WalletEntryKind::Spent, Some(order_id)) = (entry.kind, entry.order_id) {
+            *spent.entry(order_id).or_insert(Money::zero(entry.amount.currency)) += entry.amount;
+        }
+    }
+
+    for entry in entries.iter_mut() {
+        let Some(&credit) = spent.get(&entry.order_id) else {
+            continue;
+        };
+        // WalletPayment only spends credit held in the order's currency
+        let Some(rest) = entry.postings[0].amount.checked_sub(credit) else {
+            continue;
+        };
+        let liability = Posting {
+            account: mapping.store_credit.clone(),
+            amount: credit,
+        };
+        if rest.is_zero() {
+            entry.postings[0] = liability;
+        } else {
+            entry.postings[0].amount = rest;
+            entry.postings.insert(1, liability);
+        }
+    }
+}
+
+/// Journal entries for credit granted and given back, oldest first
+pub fn store_credit_entries(wallets: &[Wallet], mapping: &AccountMapping) -> Vec<JournalEntry> {
+    let mut entries: Vec<JournalEntry> = wallets
+        .iter()
+        .flat_map(|wallet| wallet.entries.iter().map(move |entry| (wallet, entry)))
+        .filter_map(|(wallet, entry)| {
+            let debit = match entry.kind {
+                WalletEntryKind::Granted => &mapping.credit_expense,
+                WalletEntryKind::Restored => &mapping.adjustments,
+                WalletEntryKind::Spent => return None,
+            };
+            let amount = entry.amount;
+            Some(JournalEntry {
+                date: entry.at.date_naive(),
+                order_id: entry.order_id.unwrap_or_default(),
+                payment_id: entry.reference.clone(),
+                description: format!("Store credit for {}: {}", wallet.customer_email, entry.note),
+                postings: vec![
+                    Posting {
+                        account: debit.clone(),
+                        amount,
+                    },
+                    Posting {
+                        account: mapping.store_credit.clone(),
+                        amount: -amount,
+                    },
+                ],
+            })
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| entry.date);
+    entries
+}
+
+/// Store credit totals up to a day in one currency (the liability on the balance sheet)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreditLiability {
+    pub as_of: NaiveDate,
+    pub granted: Money,
+    pub spent: Money,
+    pub restored: Money,
+    /// granted - spent + restored: what the shop still owes
+    pub outstanding: Money,
+    /// Customers with a balance left
+    pub customers: usize,
+}
+
+impl CreditLiability {
+    fn new(as_of: NaiveDate, currency: Currency) -> Self {
+        let zero = Money::zero(currency);
+        Self {
+            as_of,
+            granted: zero,
+            spent: zero,
+            restored: zero,
+            outstanding: zero,
+            customers: 0,
+        }
+    }
+}
+
+/// One liability per currency wallets hold, in currency order (none without credit)
+pub fn credit_liability(wallets: &[Wallet], as_of: NaiveDate) -> Vec<CreditLiability> {
+    let mut liabilities: BTreeMap<Currency, CreditLiability> = BTreeMap::new();
+
+    for wallet in wallets {
+        let Some(currency) = wallet.currency() else {
+            continue;
+        };
+        let liability =
+            liabilities.entry(currency).or_insert_with(|| CreditLiability::new(as_of, currency));
+        let mut balance = Money::zero(currency);
+        for entry in wallet.entries.iter().filter(|e| e.at.date_naive() <= as_of) {
+            match entry.kind {
+                WalletEntryKind::Granted => liability.granted += entry.amount,
+                WalletEntryKind::Spent => liability.spent += entry.amount,
+                WalletEntryKind::Restored => liability.restored += entry.amount,
+            }
+            balance += entry.signed_amount();
+        }
+        if balance.cents > 0 {
+            liability.customers += 1;
+        }
+    }
+
+    liabilities
+        .into_values()
+        .map(|mut liability| {
+            liability.outstanding = liability.granted - liability.spent + liability.restored;
+            liability
+        })
+        .collect()
+}
+
+fn journal_entry(order: &Order, mapping: &AccountMapping) -> JournalEntry {
+    let payment_id = order.payment_id.clone().unwrap_or_default();
+    let total = order.amount_charged();
+    let zero = Money::zero(total.currency);
+
+    // Credit revenue per account (BTreeMap keeps the output stable)
+    let mut revenue: BTreeMap<&str, Money> = BTreeMap::new();
+    for item in &order.items {
+        let account = mapping.revenue_account(&product_family(&item.beverage_name));
+        *revenue.entry(account).or_insert(zero) += item.line_total();
+    }
+
+    let mut postings = vec![Posting {
+        account: mapping.payment_account(&payment_method(&payment_id)).to_string(),
+        amount: total,
+    }];
+    postings.extend(revenue.into_iter().map(|(account, amount)| Posting {
+        account: account.to_string(),
+        amount: -amount,
+    }));
+
+    let surcharge = order.surcharge;
+    if !surcharge.is_zero() {
+        postings.push(Posting {
+            account: mapping.surcharges.clone(),
+            amount: -surcharge,
+        });
+    }
+
+    let rounding = order.rounding_adjustment;
+    if !rounding.is_zero() {
+        postings.push(Posting {
+            account: mapping.rounding.clone(),
+            amount: -rounding,
+        });
+    }
+
+    let difference = sum_postings(&postings);
+    if !difference.is_zero() {
+        postings.push(Posting {
+            account: mapping.adjustments.clone(),
+            amount: -difference,
+        });
+    }
+
+    JournalEntry {
+        date: order.created_at.date_naive(),
+        order_id: order.id,
+        payment_id,
+        description: format!("Order {}", order.short_id()),
+        postings,
+    }
+}
+
+fn sum_postings(postings: &[Posting]) -> Money {
+    let zero = Money::zero(postings.first().map(|p| p.amount.currency).unwrap_or_default());
+    postings.iter().fold(zero, |sum, p| sum + p.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Customer, OrderItem};
+    use chrono::Utc;
+
+    fn make_order(items: &[(&str, f64)], payment_id: &str) -> Order {
+        let customer = Customer::new("Test".to_string(), "test@example.com".to_string(), None);
+        let items = items
+            .iter()
+            .map(|(name, price)| OrderItem {
+                beverage_name: name.to_string(),
+                beverage_description: name.to_string(),
+                price: Money::usd(*price),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            })
+            .collect();
+
+        let mut order = Order::new(customer, items);
+        order.mark_as_paid(payment_id.to_string()).unwrap();
+        order
+    }
+
+    #[test]
+    fn test_entry_is_balanced_and_mapped() {
+        let order = make_order(&[("Coffee", 3.50), ("Green Tea", 2.50)], "CC-1");
+
+        let entries = journal_entries(&[order], &AccountMapping::default());
+
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.payment_posting().account, "Assets:Card Clearing");
+        assert_eq!(entry.payment_posting().amount, Money::usd(6.00));
+        assert_eq!(entry.postings.len(), 3);
+        assert!(entry.balance().is_zero());
+    }
+
+    #[test]
+    fn test_difference_goes_to_adjustments() {
+        let mut order = make_order(&[("Coffee", 3.50)], "CASH-1");
+        // e.g. a loyalty discount applied to the total
+        order.total_price = Money::usd(3.15);
+
+        let entries = journal_entries(&[order], &AccountMapping::default());
+
+        let adjustment = entries[0].postings.last().unwrap();
+        assert_eq!(adjustment.account, "Income:Adjustments");
+        assert_eq!(adjustment.amount, Money::usd(0.35));
+        assert!(entries[0].balance().is_zero());
+    }
+
+    #[test]
+    fn test_cash_rounding_has_its_own_account() {
+        let mut order = make_order(&[("Coffee", 3.50), ("Green Tea", 2.32)], "CASH-1");
+        order.rounding_adjustment = Money::usd(-0.02);
+
+        let entries = journal_entries(&[order], &AccountMapping::default());
+
+        let entry = &entries[0];
+        assert_eq!(entry.payment_posting().amount, Money::usd(5.80));
+        let rounding = entry.postings.last().unwrap();
+        assert_eq!(rounding.account, "Income:Cash Rounding");
+        assert_eq!(rounding.amount, Money::usd(0.02));
+        assert!(entry.balance().is_zero());
+    }
+
+    #[test]
+    fn test_store_credit_is_a_liability() {
+        let paid_with_credit = make_order(&[("Coffee", 3.50)], "WALLET-1");
+        let split = make_order(&[("Coffee", 3.50), ("Green Tea", 2.50)], "CASH-1");
+        let mut wallet = Wallet::new("test@example.com");
+        wallet.grant(Money::usd(5.0), "Cold drink");
+        wallet.spend(Money::usd(3.5), paid_with_credit.id).unwrap();
+        wallet.spend(Money::usd(1.5), split.id).unwrap();
+        let wallets = [wallet];
+        let mapping = AccountMapping::default();
+
+        let mut entries = journal_entries(&[paid_with_credit, split], &mapping);
+        apply_store_credit(&mut entries, &wallets, &mapping);
+        assert_eq!(entries[0].payment_posting().account, "Liabilities:Store Credit");
+        assert_eq!(entries[0].postings.len(), 2);
+        assert_eq!(entries[1].payment_posting().amount, Money::usd(4.5));
+        assert_eq!(entries[1].postings[1].account, "Liabilities:Store Credit");
+        assert!(entries.iter().all(|e| e.balance().is_zero()));
+
+        let granted = store_credit_entries(&wallets, &mapping);
+        assert_eq!(granted.len(), 1);
+        assert_eq!(granted[0].postings[0].account, "Expenses:Store Credit Granted");
+        assert_eq!(granted[0].postings[1].amount, Money::usd(-5.0));
+
+        let mut francs = Wallet::new("grace@example.com");
+        francs.grant(Money::new(800, Currency::CHF), "Promotion");
+        let liabilities = credit_liability(&[wallets[0].clone(), francs], Utc::now().date_naive());
+        let dollars = &liabilities[1];
+        assert_eq!(
+            (dollars.granted, dollars.spent, dollars.outstanding),
+            (Money::usd(5.0), Money::usd(5.0), Money::cents(0))
+        );
+        assert_eq!(dollars.customers, 0);
+        assert_eq!(liabilities[0].outstanding, Money::new(800, Currency::CHF));
+        assert_eq!(liabilities[0].customers, 1);
+    }
+
+    #[test]
+    fn test_mapping_from_json() {
+        let mapping: AccountMapping =
+            serde_json::from_str(r#"{ "payment": { "Cash": "1000 Cash" }, "currency": "EUR" }"#)
+                .unwrap();
+
+        // A given map replaces the default one; missing fields keep their defaults
+        assert_eq!(mapping.payment_account("Cash"), "1000 Cash");
+        assert_eq!(mapping.payment_account("Credit Card"), "Assets:Undeposited Funds");
+        assert_eq!(mapping.revenue_account("Coffee"), "Income:Sales:Coffee");
+        assert_eq!(mapping.currency, "EUR");
+    }
+}
@@ -0,0 +1,295 @@
+// SOLID: AlertEngine - rules over live numbers, fired and resolved on the ops channel
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// An AlertRule only says what is too much: "payment failures above 20%",
+// "more than 12 orders queued", "more than 3 repository errors since the last
+// check". AlertEngine keeps each rule's state and decides what to tell:
+// - Firing when a rule's condition starts to hold
+// - Firing again, as a reminder, once per cooldown while it still holds
+// - Resolved when it stops holding (only if the firing was told)
+// A rule that flaps, resolving and firing again within its cooldown, stays
+// quiet until the cooldown has passed.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Alerts go out through the Notifier port (`notify_ops_alert`), so the ops
+// channel is whatever the shop routes them to: a CompositeNotifier naming a
+// chat channel and a log file, say. Customer channels ignore them.
+//
+// The numbers come in as an AlertSample: LiveMetrics' window for payment
+// failures, the KitchenDisplay's tickets for the queue, a CallMetrics error
+// counter for the repository. Evaluate it from a JobScheduler job.
+//
+// A notice that fails to send is printed as a warning; a firing one is sent
+// again on the next evaluation.
+
+use crate::domain::{AlertState, OpsAlertNotice};
+use crate::ports::Notifier;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// The number a rule watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMetric {
+    /// Failed payments out of all attempts (0.0-1.0)
+    PaymentFailureRate,
+    /// Orders waiting for the kitchen
+    QueueDepth,
+    /// Repository errors since the previous evaluation
+    RepositoryErrors,
+}
+
+/// The numbers one evaluation looks at
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AlertSample {
+    /// WindowStats::failure_rate
+    pub payment_failure_rate: f64,
+    /// KitchenDisplay tickets
+    pub queue_depth: usize,
+    /// A running counter (CallCount::errors): each evaluation looks at the increase
+    pub repository_errors: u64,
+}
+
+/// Fire when a metric goes above a threshold
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    name: String,
+    metric: AlertMetric,
+    threshold: f64,
+    cooldown: Duration,
+}
+
+impl AlertRule {
+    /// "payment-failures": more than `percent`% of payments failed
+    pub fn payment_failure_rate(percent: f64) -> Self {
+        Self::new("payment-failures", AlertMetric::PaymentFailureRate, percent / 100.0)
+    }
+
+    /// "queue-depth": more than `orders` waiting for the kitchen
+    pub fn queue_depth(orders: usize) -> Self {
+        Self::new("queue-depth", AlertMetric::QueueDepth, orders as f64)
+    }
+
+    /// "repository-errors": more than `errors` new repository errors since the last check
+    pub fn repository_errors(errors: u64) -> Self {
+        Self::new("repository-errors", AlertMetric::RepositoryErrors, errors as f64)
+    }
+
+    fn new(name: &str, metric: AlertMetric, threshold: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            metric,
+            threshold,
+            cooldown: Duration::minutes(15),
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Minimum time between two firing notices (15 minutes by default)
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// "Payment failure rate 32.0% > 20.0%"
+    fn summary(&self, value: f64, state: AlertState) -> String {
+        let relation = match state {
+            AlertState::Firing => ">",
+            AlertState::Resolved => "<=",
+        };
+        match self.metric {
+            AlertMetric::PaymentFailureRate => format!(
+                "Payment failure rate {:.1}% {} {:.1}%",
+                value * 100.0,
+                relation,
+                self.threshold * 100.0
+            ),
+            AlertMetric::QueueDepth => {
+                format!("{} orders queued {} {}", value, relation, self.threshold)
+            }
+            AlertMetric::RepositoryErrors => format!(
+                "{} repository errors since the last check {} {}",
+                value, relation, self.threshold
+            ),
+        }
+    }
+}
+
+/// Where one rule stands
+#[derive(Debug, Default)]
+struct RuleState {
+    // Some while firing, shared by the episode's notices
+    alert_id: Option<Uuid>,
+    // The ops channel heard about this episode
+    told: bool,
+    last_fired: Option<DateTime<Utc>>,
+}
+
+/// Evaluates alert rules and tells the ops channel
+pub struct AlertEngine<N: Notifier> {
+    ops: N,
+    rules: Vec<(AlertRule, RuleState)>,
+    last_repository_errors: u64,
+}
+
+impl<N: Notifier> AlertEngine<N> {
+    /// No rules yet; alerts go to `ops`
+    pub fn new(ops: N) -> Self {
+        Self {
+            ops,
+            rules: Vec::new(),
+            last_repository_errors: 0,
+        }
+    }
+
+    pub fn with_rule(mut self, rule: AlertRule) -> Self {
+        self.rules.push((rule, RuleState::default()));
+        self
+    }
+
+    /// Names of the rules firing now
+    pub fn firing(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .filter(|(_, state)| state.alert_id.is_some())
+            .map(|(rule, _)| rule.name())
+            .collect()
+    }
+
+    /// Check every rule against `sample`; returns the notices the ops channel accepted
+    pub fn evaluate(&mut self, sample: &AlertSample, now: DateTime<Utc>) -> Vec<OpsAlertNotice> {
+        let new_errors = sample.repository_errors.saturating_sub(self.last_repository_errors);
+        self.last_repository_errors = sample.repository_errors;
+
+        let mut sent = Vec::new();
+        for (rule, state) in &mut self.rules {
+            let value = match rule.metric {
+                AlertMetric::PaymentFailureRate => sample.payment_failure_rate,
+                AlertMetric::QueueDepth => sample.queue_depth as f64,
+                AlertMetric::RepositoryErrors => new_errors as f64,
+            };
+            let notice = |alert_id, state| OpsAlertNotice {
+                alert_id,
+                rule: rule.name.clone(),
+                state,
+                summary: rule.summary(value, state),
+                value,
+                threshold: rule.threshold,
+                at: now,
+            };
+
+            if value > rule.threshold {
+                let alert_id = *state.alert_id.get_or_insert_with(Uuid::new_v4);
+                if state.last_fired.is_some_and(|at| now - at < rule.cooldown) {
+                    continue;
+                }
+                let firing = notice(alert_id, AlertState::Firing);
+                if tell(&self.ops, &firing) {
+                    state.told = true;
+                    state.last_fired = Some(now);
+                    sent.push(firing);
+                }
+            } else if let Some(alert_id) = state.alert_id.take() {
+                let resolved = notice(alert_id, AlertState::Resolved);
+                if std::mem::take(&mut state.told) && tell(&self.ops, &resolved) {
+                    sent.push(resolved);
+                }
+            }
+        }
+        sent
+    }
+}
+
+fn tell(ops: &impl Notifier, notice: &OpsAlertNotice) -> bool {
+    match ops.notify_ops_alert(notice) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("Warning: Failed to send alert '{}': {}", notice.rule, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{read_notification_records, CompositeNotifier, FileNotifier};
+
+    fn failing(rate: f64) -> AlertSample {
+        AlertSample {
+            payment_failure_rate: rate,
+            ..AlertSample::default()
+        }
+    }
+
+    #[test]
+    fn test_fires_reminds_and_resolves_with_a_cooldown() {
+        let start: DateTime<Utc> = "2026-10-15T09:00:00Z".parse().unwrap();
+        let at = |minutes| start + Duration::minutes(minutes);
+        let path = std::env::temp_dir().join(format!("alerts-{}.jsonl", Uuid::new_v4()));
+        let ops = CompositeNotifier::new().with_channel("ops-log", FileNotifier::new(path.clone()));
+        let mut engine = AlertEngine::new(ops).with_rule(
+            AlertRule::payment_failure_rate(20.0).with_cooldown(Duration::minutes(10)),
+        );
+
+        let fired = engine.evaluate(&failing(0.32), at(0));
+        assert_eq!(fired[0].state, AlertState::Firing);
+        assert_eq!(fired[0].summary, "Payment failure rate 32.0% > 20.0%");
+        assert_eq!(engine.firing(), vec!["payment-failures"]);
+
+        // Still failing: quiet within the cooldown, then a reminder
+        assert!(engine.evaluate(&failing(0.40), at(5)).is_empty());
+        let reminder = engine.evaluate(&failing(0.40), at(10));
+        assert_eq!(reminder[0].alert_id, fired[0].alert_id);
+
+        let resolved = engine.evaluate(&failing(0.05), at(11));
+        assert_eq!(resolved[0].state, AlertState::Resolved);
+        assert_eq!(resolved[0].summary, "Payment failure rate 5.0% <= 20.0%");
+        assert!(engine.firing().is_empty());
+
+        // Flapping back within the cooldown: firing, but not told again yet
+        assert!(engine.evaluate(&failing(0.30), at(12)).is_empty());
+        assert!(engine.evaluate(&failing(0.01), at(13)).is_empty());
+
+        let records = read_notification_records(&path).unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records.iter().all(|record| record.event == "ops.alert"));
+        assert_eq!(records[2].notice["state"], "resolved");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_queue_and_repository_rules() {
+        let now = Utc::now();
+        let mut engine = AlertEngine::new(CompositeNotifier::new())
+            .with_rule(AlertRule::queue_depth(12))
+            .with_rule(AlertRule::repository_errors(3).with_name("storage"));
+
+        let sample = AlertSample {
+            queue_depth: 14,
+            repository_errors: 5,
+            ..AlertSample::default()
+        };
+        let fired = engine.evaluate(&sample, now);
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[0].summary, "14 orders queued > 12");
+        assert_eq!(engine.firing(), vec!["queue-depth", "storage"]);
+
+        // The error counter didn't move: no new errors, so storage resolves
+        let calmer = AlertSample {
+            queue_depth: 13,
+            ..sample
+        };
+        let resolved = engine.evaluate(&calmer, now + Duration::minutes(1));
+        assert_eq!(resolved[0].rule, "storage");
+        assert_eq!(resolved[0].summary, "0 repository errors since the last check <= 3");
+        assert_eq!(engine.firing(), vec!["queue-depth"]);
+    }
+}
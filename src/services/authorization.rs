@@ -0,0 +1,90 @@
+// SOLID: AuthorizationPolicy - which role may do what
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Services ask "may this person do X?" and get a yes or no. Who is allowed
+// is decided here, in one table, so promoting shift leads to approve refunds
+// is a one-line configuration change rather than an edit to RefundService.
+//
+// Everything not granted is denied.
+
+use crate::domain::{Role, StaffMember};
+use std::collections::{HashMap, HashSet};
+
+/// Something only some staff may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    RequestRefund,
+    ApproveRefund,
+    /// Give store credit (a complaint's remedy)
+    GrantCredit,
+}
+
+/// Role -> permissions
+#[derive(Debug, Clone)]
+pub struct AuthorizationPolicy {
+    grants: HashMap<Role, HashSet<Permission>>,
+}
+
+impl AuthorizationPolicy {
+    /// Nobody may do anything
+    pub fn deny_all() -> Self {
+        Self {
+            grants: HashMap::new(),
+        }
+    }
+
+    pub fn grant(mut self, role: Role, permission: Permission) -> Self {
+        self.grants.entry(role).or_default().insert(permission);
+        self
+    }
+
+    pub fn revoke(mut self, role: Role, permission: Permission) -> Self {
+        if let Some(permissions) = self.grants.get_mut(&role) {
+            permissions.remove(&permission);
+        }
+        self
+    }
+
+    pub fn allows(&self, staff: &StaffMember, permission: Permission) -> bool {
+        self.grants
+            .get(&staff.role)
+            .is_some_and(|permissions| permissions.contains(&permission))
+    }
+}
+
+impl Default for AuthorizationPolicy {
+    /// Anyone at the register may ask for a refund, only managers approve;
+    /// shift leads and managers may give store credit
+    fn default() -> Self {
+        Self::deny_all()
+            .grant(Role::Barista, Permission::RequestRefund)
+            .grant(Role::ShiftLead, Permission::RequestRefund)
+            .grant(Role::Manager, Permission::RequestRefund)
+            .grant(Role::Manager, Permission::ApproveRefund)
+            .grant(Role::ShiftLead, Permission::GrantCredit)
+            .grant(Role::Manager, Permission::GrantCredit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_grants_and_overrides() {
+        let barista = StaffMember::new("Sam", Role::Barista);
+        let lead = StaffMember::new("Kim", Role::ShiftLead);
+        let manager = StaffMember::new("Max", Role::Manager);
+        let policy = AuthorizationPolicy::default();
+
+        assert!(policy.allows(&barista, Permission::RequestRefund));
+        assert!(!policy.allows(&lead, Permission::ApproveRefund));
+        assert!(policy.allows(&manager, Permission::ApproveRefund));
+
+        let policy = policy
+            .grant(Role::ShiftLead, Permission::ApproveRefund)
+            .revoke(Role::Barista, Permission::RequestRefund);
+        assert!(policy.allows(&lead, Permission::ApproveRefund));
+        assert!(!policy.allows(&barista, Permission::RequestRefund));
+    }
+}
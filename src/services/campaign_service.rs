@@ -0,0 +1,280 @@
+// SOLID: CampaignService - one announcement, mailed to many customers
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The Campaign fills in its templates; the presentation layer turns the text
+// into HTML (the `render_html` function handed in); the Mailer delivers and
+// the CampaignOutbox remembers. This service decides who gets it and paces
+// the sending.
+//
+// RESUMING:
+// `queue` writes every recipient to the outbox first, `deliver` then works
+// through the entries that aren't Sent, marking each one as soon as the
+// server answered. After a crash, running it again only mails the rest. A
+// crash between a send and its mark means that one customer gets it twice:
+// better than a whole campaign lost or resent.
+//
+// PACING:
+// Mails go out in batches (50 by default), with a pause between batches
+// when a rate limit is set, so a campaign to the whole customer base
+// doesn't trip the mail provider's limits. An entry that failed is tried
+// again on the next run, up to `max_attempts`.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// SMTP or a recording mailer, a JSON file or an in-memory outbox: all ports.
+
+use super::reward_service::latest_customers;
+use crate::domain::{Campaign, Customer, DeliveryStatus, Order, OutboxEntry};
+use crate::ports::{CampaignOutbox, EmailMessage, MailError, Mailer, OutboxError};
+use chrono::Utc;
+use std::thread;
+use std::time::Duration;
+
+/// Turns a campaign's filled-in text into the email's HTML
+/// (presentation::render_campaign)
+pub type CampaignRenderer = fn(&str) -> String;
+
+/// What one `deliver` run did
+#[derive(Debug, Default)]
+pub struct CampaignDelivery {
+    pub sent: Vec<String>,
+    /// Got it on an earlier run
+    pub skipped: usize,
+    /// Failed on this run (tried again on the next one)
+    pub failed: Vec<(String, MailError)>,
+    /// Failed `max_attempts` times: not tried any more
+    pub given_up: Vec<String>,
+    pub batches: usize,
+}
+
+/// How far a campaign got, from its outbox
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CampaignProgress {
+    pub pending: usize,
+    pub sent: usize,
+    pub failed: usize,
+}
+
+/// Sends campaigns to customers, in batches, resumable through the outbox
+pub struct CampaignService<M: Mailer, O: CampaignOutbox> {
+    mailer: M,
+    outbox: O,
+    render_html: CampaignRenderer,
+    batch_size: usize,
+    per_minute: Option<u32>,
+    max_attempts: u32,
+}
+
+impl<M: Mailer, O: CampaignOutbox> CampaignService<M, O> {
+    pub fn new(mailer: M, outbox: O, render_html: CampaignRenderer) -> Self {
+        Self {
+            mailer,
+            outbox,
+            render_html,
+            batch_size: 50,
+            per_minute: None,
+            max_attempts: 3,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// At most `per_minute` mails a minute, by pausing between batches
+    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
+        self.per_minute = Some(per_minute.max(1));
+        self
+    }
+
+    /// How many times a recipient is tried before giving up (3 by default)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The pause between two batches (zero without a rate limit)
+    pub fn pause(&self) -> Duration {
+        self.per_minute.map_or(Duration::ZERO, |per_minute| {
+            Duration::from_secs_f64(60.0 * self.batch_size as f64 / per_minute as f64)
+        })
+    }
+
+    pub fn outbox(&self) -> &O {
+        &self.outbox
+    }
+
+    /// Queue every customer of `orders` that `filter` accepts
+    ///
+    /// Customers come from their latest order (guests excluded), one per
+    /// email. Returns how many were new to the campaign's outbox.
+    pub fn queue(
+        &mut self,
+        campaign: &Campaign,
+        orders: &[Order],
+        filter: impl Fn(&Customer) -> bool,
+    ) -> Result<usize, OutboxError> {
+        let mut queued = 0;
+        for customer in latest_customers(orders).into_iter().filter(|c| filter(c)) {
+            if self.outbox.enqueue(OutboxEntry::new(&campaign.id, customer.clone()))? {
+                queued += 1;
+            }
+        }
+        Ok(queued)
+    }
+
+    /// Mail every recipient in the outbox that doesn't have it yet
+    ///
+    /// One message per recipient, so one bad address doesn't hold up the
+    /// others; an outbox error stops the run (nothing more could be marked).
+    pub fn deliver(&mut self, campaign: &Campaign) -> Result<CampaignDelivery, OutboxError> {
+        let mut delivery = CampaignDelivery::default();
+        let mut to_send = Vec::new();
+        for entry in self.outbox.entries(&campaign.id)? {
+            if entry.is_sent() {
+                delivery.skipped += 1;
+            } else if entry.attempts >= self.max_attempts {
+                delivery.given_up.push(entry.recipient.email);
+            } else {
+                to_send.push(entry.recipient);
+            }
+        }
+
+        let pause = self.pause();
+        for (index, batch) in to_send.chunks(self.batch_size).enumerate() {
+            if index > 0 && !pause.is_zero() {
+                thread::sleep(pause);
+            }
+            delivery.batches += 1;
+
+            for customer in batch {
+                let body = campaign.body_for(customer);
+                let message = EmailMessage {
+                    to: vec![customer.email.clone()],
+                    subject: campaign.subject_for(customer),
+                    html: (self.render_html)(&body),
+                    text: body,
+                };
+                match self.mailer.send(&message) {
+                    Ok(()) => {
+                        let sent = DeliveryStatus::Sent { at: Utc::now() };
+                        self.outbox.mark(&campaign.id, &customer.email, sent)?;
+                        delivery.sent.push(customer.email.clone());
+                    }
+                    Err(e) => {
+                        let failed = DeliveryStatus::Failed { error: e.to_string() };
+                        self.outbox.mark(&campaign.id, &customer.email, failed)?;
+                        delivery.failed.push((customer.email.clone(), e));
+                    }
+                }
+            }
+        }
+        Ok(delivery)
+    }
+
+    /// `queue` then `deliver`: running it again resumes the campaign
+    pub fn run(
+        &mut self,
+        campaign: &Campaign,
+        orders: &[Order],
+        filter: impl Fn(&Customer) -> bool,
+    ) -> Result<CampaignDelivery, OutboxError> {
+        self.queue(campaign, orders, filter)?;
+        self.deliver(campaign)
+    }
+
+    pub fn progress(&self, campaign_id: &str) -> Result<CampaignProgress, OutboxError> {
+        let mut progress = CampaignProgress::default();
+        for entry in self.outbox.entries(campaign_id)? {
+            match entry.status {
+                DeliveryStatus::Pending => progress.pending += 1,
+                DeliveryStatus::Sent { .. } => progress.sent += 1,
+                DeliveryStatus::Failed { .. } => progress.failed += 1,
+            }
+        }
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{MemoryCampaignOutbox, RecordingMailer};
+    use crate::domain::Coffee;
+
+    /// Refuses one address, records the rest
+    #[derive(Clone)]
+    struct RejectingMailer {
+        rejected: &'static str,
+        inner: RecordingMailer,
+    }
+
+    impl Mailer for RejectingMailer {
+        fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+            if message.to.iter().any(|to| to == self.rejected) {
+                return Err(MailError::Rejected("mailbox full".to_string()));
+            }
+            self.inner.send(message)
+        }
+    }
+
+    fn html(text: &str) -> String {
+        format!("<p>{}</p>", text)
+    }
+
+    fn orders(names: &[&str]) -> Vec<Order> {
+        names.iter().map(|name| Order::single(Customer::named(name), Coffee::medium())).collect()
+    }
+
+    fn campaign() -> Campaign {
+        Campaign::new("autumn", "New menu", "Hello {{name}}, pumpkin spice is back")
+    }
+
+    #[test]
+    fn test_campaign_reaches_filtered_customers_in_batches() {
+        let mailer = RecordingMailer::new();
+        let mut service = CampaignService::new(mailer.clone(), MemoryCampaignOutbox::new(), html)
+            .with_batch_size(2);
+        let orders = orders(&["Ada", "Bob", "Cy", "Dee"]);
+
+        let delivery = service.run(&campaign(), &orders, |c| c.name != "Bob").unwrap();
+
+        assert_eq!(delivery.sent, vec!["ada@example.com", "cy@example.com", "dee@example.com"]);
+        assert_eq!(delivery.batches, 2);
+        let messages = mailer.sent();
+        assert_eq!(messages[0].text, "Hello Ada, pumpkin spice is back");
+        assert_eq!(messages[0].html, "<p>Hello Ada, pumpkin spice is back</p>");
+
+        let again = service.run(&campaign(), &orders, |_| true).unwrap();
+        assert_eq!((again.sent, again.skipped), (vec!["bob@example.com".to_string()], 3));
+        assert_eq!(service.progress("autumn").unwrap().sent, 4);
+    }
+
+    #[test]
+    fn test_failed_recipients_are_retried_until_max_attempts() {
+        let recorded = RecordingMailer::new();
+        let mailer = RejectingMailer { rejected: "bob@example.com", inner: recorded.clone() };
+        let mut service =
+            CampaignService::new(mailer, MemoryCampaignOutbox::new(), html).with_max_attempts(2);
+        let orders = orders(&["Ada", "Bob"]);
+
+        let first = service.run(&campaign(), &orders, |_| true).unwrap();
+        assert_eq!((first.sent.len(), first.failed.len()), (1, 1));
+        let progress = service.progress("autumn").unwrap();
+        assert_eq!(progress, CampaignProgress { pending: 0, sent: 1, failed: 1 });
+
+        let second = service.deliver(&campaign()).unwrap();
+        assert_eq!((second.skipped, second.failed.len()), (1, 1));
+        let third = service.deliver(&campaign()).unwrap();
+        assert_eq!(third.given_up, vec!["bob@example.com"]);
+        assert_eq!(recorded.sent().len(), 1);
+    }
+
+    #[test]
+    fn test_rate_limit_sets_the_pause_between_batches() {
+        let service = CampaignService::new(RecordingMailer::new(), MemoryCampaignOutbox::new(), html)
+            .with_rate_limit(120)
+            .with_batch_size(10);
+        assert_eq!(service.pause(), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,132 @@
+// SOLID: RoundingPolicy - cash totals rounded to the coins in circulation
+//
+// Where the smallest coins were withdrawn (Switzerland, Canada, Australia,
+// the Netherlands...), a cash total is rounded to the nearest 5 or 10
+// cents; cards still pay to the cent. So the rule depends on the payment
+// method, and the increments on the country.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Menu prices and order totals don't change: the policy only says by how
+// much the charge differs from the total. OrderService records that
+// difference on the order (`rounding_adjustment`) before charging, and
+// Accounting books it to its own account, so the ledger still balances
+// against the items sold.
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Methods are matched by `PaymentProcessor::payment_method_name`, so a new
+// processor is rounded (or not) by configuration alone.
+
+use crate::domain::Money;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Rounding increment per payment method
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoundingPolicy {
+    /// Payment method name ("Cash") -> smallest amount it can pay (0.05)
+    pub increments: HashMap<String, f64>,
+}
+
+impl RoundingPolicy {
+    /// No rounding for any method
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Round totals paid with `payment_method` to multiples of `increment`
+    pub fn with_increment(mut self, payment_method: &str, increment: f64) -> Self {
+        self.increments.insert(payment_method.to_string(), increment);
+        self
+    }
+
+    /// Cash rounding of a country (ISO 3166 code); none for unknown countries
+    pub fn for_country(country: &str) -> Self {
+        let cash = match country.to_ascii_uppercase().as_str() {
+            "CH" | "CA" | "AU" | "NL" | "BE" | "IE" | "FI" => 0.05,
+            "NZ" => 0.10,
+            "DK" => 0.50,
+            "SE" => 1.00,
+            _ => return Self::new(),
+        };
+        Self::new().with_increment("Cash", cash)
+    }
+
+    /// What to add to `total` when it is paid with `payment_method`
+    ///
+    /// Rounds to the nearest increment (in the total's currency), halves up.
+    pub fn adjustment(&self, payment_method: &str, total: Money) -> Money {
+        let step = self.increments.get(payment_method).map_or(0, |i| (i * 100.0).round() as i64);
+        if step <= 0 {
+            return Money::zero(total.currency);
+        }
+        let rounded = (total.cents + step / 2).div_euclid(step) * step;
+        Money::new(rounded - total.cents, total.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, CreditCardPayment, MemoryOrderRepository};
+    use crate::domain::{Beverage, Coffee, Currency, Customer, Size};
+    use crate::services::OrderService;
+    use std::io;
+
+    #[test]
+    fn test_cash_rounds_to_the_nearest_increment() {
+        let swiss = RoundingPolicy::for_country("ch");
+
+        let chf = |amount| Money::from_major(amount, Currency::CHF);
+        assert_eq!(swiss.adjustment("Cash", chf(3.82)), chf(-0.02));
+        assert_eq!(swiss.adjustment("Cash", chf(3.83)), chf(0.02));
+        assert_eq!(swiss.adjustment("Cash", chf(4.20)), chf(0.0));
+        // Cards pay to the cent
+        assert_eq!(swiss.adjustment("Credit Card", chf(3.82)), chf(0.0));
+
+        let nz = RoundingPolicy::for_country("NZ");
+        assert_eq!(nz.adjustment("Cash", Money::usd(3.85)), Money::usd(0.05));
+        let us = RoundingPolicy::for_country("US");
+        assert_eq!(us.adjustment("Cash", Money::usd(3.82)), Money::usd(0.0));
+    }
+
+    #[test]
+    fn test_policy_from_json() {
+        let policy: RoundingPolicy =
+            serde_json::from_str(r#"{ "increments": { "Cash": 1.0 } }"#).unwrap();
+
+        assert_eq!(policy.adjustment("Cash", Money::usd(41.49)), Money::usd(-0.49));
+        assert_eq!(policy.adjustment("Cash", Money::usd(41.50)), Money::usd(0.5));
+    }
+
+    #[test]
+    fn test_order_service_records_the_rounding_it_charged() {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        // Small coffee with a shot: (3.50 + 0.75) * 0.8 = 3.40, a large one 5.10
+        let coffee = |size| -> Box<dyn Beverage> {
+            Box::new(Coffee {
+                size,
+                extra_shots: 1,
+            })
+        };
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let mut cash = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier)
+            .with_rounding(RoundingPolicy::new().with_increment("Cash", 0.25));
+
+        let order = cash.place_order(customer.clone(), vec![coffee(Size::Small)]).unwrap();
+        assert_eq!(order.rounding_adjustment, Money::usd(0.10));
+        assert_eq!(order.amount_charged(), Money::usd(3.50));
+        let stored = cash.get_order(order.id).unwrap();
+        assert_eq!(stored.rounding_adjustment, order.rounding_adjustment);
+
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let mut card = OrderService::new(
+            MemoryOrderRepository::new(),
+            CreditCardPayment::new("https://payment-gateway.example.com".to_string()),
+            notifier,
+        )
+        .with_rounding(RoundingPolicy::new().with_increment("Cash", 0.25));
+        let order = card.place_order(customer, vec![coffee(Size::Large)]).unwrap();
+        assert!(order.rounding_adjustment.is_zero());
+    }
+}
@@ -0,0 +1,196 @@
+// SOLID: CostModel - what a drink costs the shop to make (cost of goods)
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Ingredient prices change with every supplier invoice, recipes change when
+// the head barista says so; neither is a reason to touch a beverage type or
+// ReportingService. Both live here, as configuration (usually loaded from
+// JSON, like AccountMapping and PrepTimeModel).
+//
+// A recipe is looked up by the exact beverage name first ("Coffee (+2 shots)"),
+// then by product family ("Coffee"), so customizations can be costed where
+// they matter and left to the family's recipe where they don't. Quantities
+// are for a medium; other sizes scale like the price does.
+//
+// ReportingService::profitability puts these costs next to the revenue.
+
+use super::reporting_service::product_family;
+use crate::domain::{Order, OrderItem};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Ingredient unit costs and the recipes that use them
+///
+/// A JSON file may give only one of the two; the other keeps the defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostModel {
+    /// Ingredient -> cost of one unit
+    pub ingredients: BTreeMap<String, f64>,
+    /// Beverage name or product family -> ingredient -> units in a medium
+    pub recipes: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl CostModel {
+    /// No ingredients, no recipes: nothing can be costed
+    pub fn new() -> Self {
+        Self {
+            ingredients: BTreeMap::new(),
+            recipes: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_ingredient(mut self, ingredient: &str, unit_cost: f64) -> Self {
+        self.ingredients.insert(ingredient.to_string(), unit_cost);
+        self
+    }
+
+    /// A recipe for a beverage name or a product family
+    pub fn with_recipe(mut self, name: &str, ingredients: &[(&str, f64)]) -> Self {
+        let units = ingredients
+            .iter()
+            .map(|(ingredient, units)| (ingredient.to_string(), *units))
+            .collect();
+        self.recipes.insert(name.to_string(), units);
+        self
+    }
+
+    /// Ingredients used by a recipe but missing from the price list, sorted
+    pub fn unknown_ingredients(&self) -> Vec<String> {
+        let mut unknown: Vec<String> = self
+            .recipes
+            .values()
+            .flat_map(|recipe| recipe.keys())
+            .filter(|ingredient| !self.ingredients.contains_key(*ingredient))
+            .cloned()
+            .collect();
+        unknown.sort();
+        unknown.dedup();
+        unknown
+    }
+
+    /// Cost of making one of this line's beverage (None without a recipe)
+    ///
+    /// Lines stored without their size are costed as a medium.
+    pub fn unit_cost(&self, item: &OrderItem) -> Option<f64> {
+        let recipe = self.ingredients_for(item)?;
+        Some(
+            recipe
+                .iter()
+                .map(|(ingredient, units)| units * self.ingredients.get(ingredient).unwrap_or(&0.0))
+                .sum(),
+        )
+    }
+
+    /// Ingredient -> units in one of this line's beverage, scaled to its size
+    /// (None without a recipe)
+    pub fn ingredients_for(&self, item: &OrderItem) -> Option<BTreeMap<String, f64>> {
+        let recipe = self
+            .recipes
+            .get(&item.beverage_name)
+            .or_else(|| self.recipes.get(&product_family(&item.beverage_name)))?;
+        let size = item.price_basis.map_or(1.0, |basis| basis.size.price_multiplier());
+        Some(
+            recipe
+                .iter()
+                .map(|(ingredient, units)| (ingredient.clone(), units * size))
+                .collect(),
+        )
+    }
+
+    /// Cost of goods of a whole order (lines without a recipe count as 0)
+    pub fn order_cost(&self, order: &Order) -> f64 {
+        order
+            .items
+            .iter()
+            .filter_map(|item| self.unit_cost(item).map(|cost| cost * item.quantity as f64))
+            .sum()
+    }
+
+    /// What the order leaves once its ingredients are paid for
+    pub fn order_margin(&self, order: &Order) -> f64 {
+        order.total_price.to_major() - self.order_cost(order)
+    }
+}
+
+impl Default for CostModel {
+    /// Typical small-shop costs for the three families on the menu
+    fn default() -> Self {
+        Self::new()
+            .with_ingredient("cup and lid", 0.12)
+            .with_ingredient("espresso shot", 0.25)
+            .with_ingredient("milk (100 ml)", 0.10)
+            .with_ingredient("tea bag", 0.20)
+            .with_ingredient("fruit portion", 0.45)
+            .with_ingredient("yogurt (100 ml)", 0.30)
+            .with_recipe(
+                "Coffee",
+                &[("espresso shot", 1.0), ("milk (100 ml)", 1.0), ("cup and lid", 1.0)],
+            )
+            .with_recipe(
+                "Coffee (+1 shot)",
+                &[("espresso shot", 2.0), ("milk (100 ml)", 1.0), ("cup and lid", 1.0)],
+            )
+            .with_recipe(
+                "Coffee (+2 shots)",
+                &[("espresso shot", 3.0), ("milk (100 ml)", 1.0), ("cup and lid", 1.0)],
+            )
+            .with_recipe("Tea", &[("tea bag", 1.0), ("cup and lid", 1.0)])
+            .with_recipe(
+                "Smoothie",
+                &[("fruit portion", 2.0), ("yogurt (100 ml)", 1.5), ("cup and lid", 1.0)],
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Customer, Money, PriceBasis, Size};
+
+    fn item(name: &str, size: Option<Size>, quantity: u8) -> OrderItem {
+        OrderItem {
+            beverage_name: name.to_string(),
+            beverage_description: name.to_string(),
+            price: Money::usd(4.0),
+            quantity,
+            prep_seconds: None,
+            price_basis: size.map(|size| PriceBasis {
+                size,
+                adjustment: 0.0,
+            }),
+            vat_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_recipe_by_name_then_family_scaled_by_size() {
+        let model = CostModel::default();
+
+        // 0.25 + 0.10 + 0.12
+        let medium = model.unit_cost(&item("Coffee", Some(Size::Medium), 1)).unwrap();
+        assert!((medium - 0.47).abs() < 1e-9);
+        let double = model.unit_cost(&item("Coffee (+2 shots)", None, 1)).unwrap();
+        assert!((double - 0.97).abs() < 1e-9);
+        // Family recipe, large
+        let tea = model.unit_cost(&item("Green Tea", Some(Size::Large), 1)).unwrap();
+        assert!((tea - 0.384).abs() < 1e-9);
+        assert_eq!(model.unit_cost(&item("Hot Chocolate", None, 1)), None);
+    }
+
+    #[test]
+    fn test_order_cost_and_unknown_ingredients() {
+        let model: CostModel = serde_json::from_str(
+            r#"{ "ingredients": { "tea bag": 0.5 },
+                 "recipes": { "Tea": { "tea bag": 1, "cup": 1 } } }"#,
+        )
+        .unwrap();
+        assert_eq!(model.unknown_ingredients(), ["cup"]);
+
+        let order = Order::new(
+            Customer::guest(),
+            vec![item("Black Tea", None, 2), item("Coffee", None, 1)],
+        );
+        assert_eq!(model.order_cost(&order), 1.0);
+        assert_eq!(model.order_margin(&order), 11.0);
+    }
+}
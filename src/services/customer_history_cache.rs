@@ -0,0 +1,191 @@
+// SOLID: CustomerHistoryCache - read-through cache of orders per customer
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The cache only remembers and forgets. It doesn't know how orders are loaded:
+// the caller passes a loader (usually a repository call) to `get_or_load`.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Because loading is a closure, the cache works in front of ANY repository,
+// and any service that reads a customer's history can share it.
+//
+// Eviction is LRU: when the cache is full, the customer looked up least
+// recently is dropped first.
+
+use crate::domain::Order;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Hit/miss counters, for tuning the capacity
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups served from the cache (0.0 when nothing was looked up yet)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+struct CacheState {
+    entries: HashMap<String, Vec<Order>>,
+    // Least recently used at the front
+    recency: VecDeque<String>,
+    stats: CacheStats,
+}
+
+/// LRU cache of order histories, keyed by customer email
+///
+/// Uses interior mutability so it can sit behind `&self` read methods.
+pub struct CustomerHistoryCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl CustomerHistoryCache {
+    /// Create a cache holding at most `capacity` customers
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                recency: VecDeque::new(),
+                stats: CacheStats::default(),
+            }),
+        }
+    }
+
+    /// Return the cached history, or load it and remember it
+    ///
+    /// Loader errors are passed through and nothing is cached.
+    pub fn get_or_load<E>(
+        &self,
+        email: &str,
+        load: impl FnOnce() -> Result<Vec<Order>, E>,
+    ) -> Result<Vec<Order>, E> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(orders) = state.entries.get(email).cloned() {
+            state.stats.hits += 1;
+            Self::touch(&mut state, email);
+            return Ok(orders);
+        }
+
+        state.stats.misses += 1;
+        let orders = load()?;
+
+        if state.entries.len() >= self.capacity
+            && let Some(oldest) = state.recency.pop_front()
+        {
+            state.entries.remove(&oldest);
+            state.stats.evictions += 1;
+        }
+
+        state.entries.insert(email.to_string(), orders.clone());
+        state.recency.push_back(email.to_string());
+
+        Ok(orders)
+    }
+
+    /// Forget a customer's history (call whenever one of their orders changes)
+    pub fn invalidate(&self, email: &str) {
+        let mut state = self.state.lock().unwrap();
+        if state.entries.remove(email).is_some() {
+            state.recency.retain(|key| key != email);
+        }
+    }
+
+    /// Forget everything (statistics are kept)
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.recency.clear();
+    }
+
+    /// Number of customers currently cached
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// True when no customer is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Current hit/miss counters
+    pub fn stats(&self) -> CacheStats {
+        self.state.lock().unwrap().stats
+    }
+
+    /// Move a key to the most-recently-used end
+    fn touch(state: &mut CacheState, email: &str) {
+        if let Some(position) = state.recency.iter().position(|key| key == email) {
+            let key = state.recency.remove(position).unwrap();
+            state.recency.push_back(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_empty() -> Result<Vec<Order>, ()> {
+        Ok(vec![])
+    }
+
+    #[test]
+    fn test_hit_after_miss() {
+        let cache = CustomerHistoryCache::new(10);
+
+        cache.get_or_load("alice@example.com", load_empty).unwrap();
+        cache.get_or_load("alice@example.com", load_empty).unwrap();
+
+        let stats = cache.stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 1);
+        assert!((stats.hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let cache = CustomerHistoryCache::new(2);
+
+        cache.get_or_load("a", load_empty).unwrap();
+        cache.get_or_load("b", load_empty).unwrap();
+        // Touch "a" so "b" becomes the least recently used
+        cache.get_or_load("a", load_empty).unwrap();
+        cache.get_or_load("c", load_empty).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+
+        // "a" is still cached, "b" was evicted
+        cache.get_or_load("a", load_empty).unwrap();
+        cache.get_or_load("b", load_empty).unwrap();
+        assert_eq!(cache.stats().hits, 2);
+        assert_eq!(cache.stats().misses, 4);
+    }
+
+    #[test]
+    fn test_invalidate_and_errors() {
+        let cache = CustomerHistoryCache::new(10);
+
+        cache.get_or_load("a", load_empty).unwrap();
+        cache.invalidate("a");
+        assert!(cache.is_empty());
+
+        // Failed loads are not cached
+        let result: Result<Vec<Order>, &str> = cache.get_or_load("a", || Err("down"));
+        assert!(result.is_err());
+        assert!(cache.is_empty());
+    }
+}
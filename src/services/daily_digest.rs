@@ -0,0 +1,174 @@
+// SOLID: DailyDigestJob - the day's figures, emailed to the owner
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// ReportingService computes the daily report; the presentation layer turns
+// it into HTML (the `render_html` function handed in); the Mailer port
+// delivers. This job only decides who gets which day, and makes sure
+// nobody gets it twice.
+//
+// IDEMPOTENCE:
+// Each recipient's copy has a key, `digest:<date>:<address>`, recorded in the
+// SentMailLog once the server accepted it. A rerun (cron fired twice, or a
+// run that failed for one recipient) only sends the copies still missing.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// SMTP or a recording mailer, a file or an in-memory log: all behind ports.
+
+use super::job_scheduler::{Job, JobError};
+use super::reporting_service::{DailyReport, ReportingService};
+use crate::ports::{Clock, EmailMessage, MailError, Mailer, OrderRepository, SentMailLog};
+
+/// Turns the report into the email's HTML (presentation::render_daily_digest)
+pub type DigestRenderer = fn(&DailyReport) -> String;
+
+/// Who got the digest on this run
+#[derive(Debug, Default)]
+pub struct DigestDelivery {
+    pub sent: Vec<String>,
+    /// Got it on an earlier run
+    pub skipped: Vec<String>,
+    pub failed: Vec<(String, MailError)>,
+}
+
+/// Sends one day's report to every recipient, once
+pub struct DailyDigestJob<M: Mailer, L: SentMailLog> {
+    mailer: M,
+    log: L,
+    recipients: Vec<String>,
+    render_html: DigestRenderer,
+}
+
+impl<M: Mailer, L: SentMailLog> DailyDigestJob<M, L> {
+    pub fn new(mailer: M, log: L, recipients: Vec<String>, render_html: DigestRenderer) -> Self {
+        Self {
+            mailer,
+            log,
+            recipients,
+            render_html,
+        }
+    }
+
+    /// Mail `report` to each recipient that doesn't have it yet
+    ///
+    /// One message per recipient, so one bad address doesn't hold up the others.
+    pub fn deliver(&mut self, report: &DailyReport) -> DigestDelivery {
+        let mut delivery = DigestDelivery::default();
+        let html = (self.render_html)(report);
+
+        for recipient in &self.recipients {
+            let key = format!("digest:{}:{}", report.date, recipient.to_lowercase());
+            match self.log.contains(&key) {
+                Ok(true) => {
+                    delivery.skipped.push(recipient.clone());
+                    continue;
+                }
+                Ok(false) => {}
+                // Without the log we can't tell: better late than twice
+                Err(e) => {
+                    delivery.failed.push((recipient.clone(), e));
+                    continue;
+                }
+            }
+
+            let message = EmailMessage {
+                to: vec![recipient.clone()],
+                subject: subject(report),
+                html: html.clone(),
+                text: text(report),
+            };
+            match self.mailer.send(&message).and_then(|_| self.log.record(&key)) {
+                Ok(()) => delivery.sent.push(recipient.clone()),
+                Err(e) => delivery.failed.push((recipient.clone(), e)),
+            }
+        }
+        delivery
+    }
+}
+
+fn subject(report: &DailyReport) -> String {
+    format!(
+        "Daily digest {}: {} orders, ${:.2}",
+        report.date, report.orders, report.revenue
+    )
+}
+
+/// The plain-text part, for mail clients that don't show HTML
+fn text(report: &DailyReport) -> String {
+    format!(
+        "Coffee shop, {}\n\nOrders: {}\nRevenue: ${:.2}\nAverage ticket: ${:.2}\n\
+         Cancelled: {}\nPast SLA: {}\n",
+        report.date,
+        report.orders,
+        report.revenue,
+        report.average_ticket,
+        report.cancelled,
+        report.sla_breaches
+    )
+}
+
+/// Scheduled at closing time: the digest of the day the clock is in
+impl<R, M, L> Job<ReportingService<R>> for DailyDigestJob<M, L>
+where
+    R: OrderRepository,
+    M: Mailer,
+    L: SentMailLog,
+{
+    fn run(
+        &mut self,
+        reporting: &mut ReportingService<R>,
+        clock: &dyn Clock,
+    ) -> Result<String, JobError> {
+        let report = reporting
+            .daily(clock.now().date_naive())
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+        let delivery = self.deliver(&report);
+
+        if let Some((recipient, e)) = delivery.failed.first() {
+            return Err(JobError::Failed(format!(
+                "{} of {} digest(s) not sent (first: {}: {})",
+                delivery.failed.len(),
+                self.recipients.len(),
+                recipient,
+                e
+            )));
+        }
+        Ok(format!(
+            "Digest for {} sent to {} recipient(s), {} already had it",
+            report.date,
+            delivery.sent.len(),
+            delivery.skipped.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{FixedClock, MemoryOrderRepository, MemorySentMailLog, RecordingMailer};
+    use chrono::Utc;
+
+    fn html(report: &DailyReport) -> String {
+        format!("<p>{} orders</p>", report.orders)
+    }
+
+    #[test]
+    fn test_reruns_do_not_send_twice() {
+        let mailer = RecordingMailer::new();
+        let recipients = vec!["owner@example.com".to_string(), "Manager@example.com".to_string()];
+        let log = MemorySentMailLog::new();
+        let mut job = DailyDigestJob::new(mailer.clone(), log, recipients, html);
+        let mut reporting = ReportingService::new(MemoryOrderRepository::new());
+        let clock = FixedClock::new(Utc::now());
+
+        let first = job.run(&mut reporting, &clock).unwrap();
+        assert!(first.contains("sent to 2 recipient(s), 0 already"), "{}", first);
+        let second = job.run(&mut reporting, &clock).unwrap();
+        assert!(second.contains("sent to 0 recipient(s), 2 already"), "{}", second);
+
+        let sent = mailer.sent();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].to, ["owner@example.com"]);
+        assert_eq!(sent[0].html, "<p>0 orders</p>");
+        assert!(sent[0].text.contains("Revenue: $0.00"));
+    }
+}
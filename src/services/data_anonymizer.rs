@@ -0,0 +1,216 @@
+// SOLID: DataAnonymizer - export orders with the personal data replaced
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// It only rewrites customer data and the processors' references (payment,
+// redirect token, refund and chargeback IDs), which lead back to the card.
+// Totals, items, statuses and timestamps are copied untouched, so reports on
+// the exported data give the same numbers.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Source and target are any OrderRepository: anonymize a JSON file into
+// another JSON file, or into memory for a classroom demo.
+//
+// The same real customer always maps to the same fake customer,
+// so "orders per customer" statistics survive the export.
+
+use crate::domain::{Customer, Order};
+use crate::ports::{OrderRepository, RepositoryError};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const FIRST_NAMES: [&str; 16] = [
+    "Alice", "Bruno", "Chloe", "Diego", "Emma", "Farid", "Grace", "Hugo",
+    "Ines", "Jonas", "Keiko", "Liam", "Maya", "Noah", "Olga", "Pablo",
+];
+
+const LAST_NAMES: [&str; 16] = [
+    "Martin", "Silva", "Nguyen", "Kowalski", "Dubois", "Rossi", "Schmidt", "Tanaka",
+    "Garcia", "Okafor", "Larsen", "Moreau", "Novak", "Haddad", "Fischer", "Costa",
+];
+
+/// Replaces names, emails, phones, birthdays and payment references with fake ones
+pub struct DataAnonymizer {
+    salt: u64,
+    // Real email (lowercased) -> fake customer
+    customers: HashMap<String, Customer>,
+}
+
+impl DataAnonymizer {
+    /// `salt` changes which fake names are picked; the same salt gives the same output
+    pub fn new(salt: u64) -> Self {
+        Self {
+            salt,
+            customers: HashMap::new(),
+        }
+    }
+
+    /// Anonymized copy of one order
+    pub fn anonymize_order(&mut self, order: &Order) -> Order {
+        let mut copy = order.clone();
+        copy.customer = self.fake_customer(&order.customer);
+        let payment_id = format!("ANON-PAY-{}", order.id.simple());
+        copy.payment_id = order.payment_id.as_ref().map(|_| payment_id.clone());
+        copy.payment_token = order
+            .payment_token
+            .as_ref()
+            .map(|_| format!("ANON-TOKEN-{}", order.id.simple()));
+        copy.refund_id = order
+            .refund_id
+            .as_ref()
+            .map(|_| format!("ANON-REFUND-{}", order.id.simple()));
+        // Still the order's payment, so the dispute can be matched to it
+        if let Some(chargeback) = &mut copy.chargeback {
+            chargeback.payment_id = payment_id;
+        }
+        copy
+    }
+
+    /// Copy every order of `source` into `target`, anonymized
+    ///
+    /// Returns how many orders were exported.
+    pub fn export<S: OrderRepository, T: OrderRepository>(
+        &mut self,
+        source: &S,
+        target: &mut T,
+    ) -> Result<usize, RepositoryError> {
+        let anonymized: Vec<Order> = source
+            .list_all()?
+            .iter()
+            .map(|order| self.anonymize_order(order))
+            .collect();
+
+        target.save_batch(&anonymized)?;
+        Ok(anonymized.len())
+    }
+
+    /// Number of distinct customers seen so far
+    pub fn customer_count(&self) -> usize {
+        self.customers.len()
+    }
+
+    fn fake_customer(&mut self, real: &Customer) -> Customer {
+        let key = real.email.trim().to_lowercase();
+        let index = self.customers.len();
+        let hash = fnv1a(&key) ^ self.salt;
+
+        self.customers
+            .entry(key)
+            .or_insert_with(|| {
+                let first = FIRST_NAMES[(hash % 16) as usize];
+                let last = LAST_NAMES[((hash >> 8) % 16) as usize];
+
+                Customer {
+                    id: Uuid::new_v4(),
+                    name: format!("{} {}", first, last),
+                    // The index keeps fake emails unique even when names collide
+                    email: format!(
+                        "{}.{}{}@example.com",
+                        first.to_lowercase(),
+                        last.to_lowercase(),
+                        index + 1
+                    ),
+                    // 555-01xx numbers are reserved for fiction
+                    phone: real
+                        .phone
+                        .as_ref()
+                        .map(|_| format!("555-01{:02}", index % 100)),
+                    // A birthday identifies people; a join date hardly does
+                    birthday: None,
+                    member_since: real.member_since,
+                }
+            })
+            .clone()
+    }
+}
+
+/// Small stable hash (std's hasher may change between Rust versions)
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Chargeback, Money, OrderItem};
+
+    fn make_order(name: &str, email: &str) -> Order {
+        let customer = Customer::new(
+            name.to_string(),
+            email.to_string(),
+            Some("+33 6 12 34 56 78".to_string()),
+        );
+
+        let items = vec![OrderItem {
+            beverage_name: "Coffee".to_string(),
+            beverage_description: "Medium Coffee".to_string(),
+            price: Money::usd(3.50),
+            quantity: 2,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        }];
+
+        let mut order = Order::new(customer, items);
+        order.mark_as_paid("CARD-4242".to_string()).unwrap();
+        order
+    }
+
+    #[test]
+    fn test_pii_replaced_totals_kept() {
+        let order = make_order("Jane Real", "jane@real.com");
+
+        let fake = DataAnonymizer::new(42).anonymize_order(&order);
+
+        assert_ne!(fake.customer.name, "Jane Real");
+        assert!(fake.customer.email.ends_with("@example.com"));
+        assert!(fake.customer.phone.as_deref().unwrap().starts_with("555-01"));
+        assert!(!fake.payment_id.as_deref().unwrap().contains("4242"));
+        assert_eq!(fake.id, order.id);
+        assert_eq!(fake.total_price, order.total_price);
+        assert_eq!(fake.created_at, order.created_at);
+
+        // A redirect payment, refunded, then charged back anyway
+        let mut order = make_order("Jane Real", "jane@real.com");
+        order.payment_token = Some("EC-4242".to_string());
+        order.mark_refunded("RE-4242".to_string()).unwrap();
+        let chargeback = Chargeback::new("CB-1", "CARD-4242", Money::usd(7.0), "fraudulent");
+        order.chargeback = Some(chargeback);
+
+        let fake = DataAnonymizer::new(42).anonymize_order(&order);
+        let fake_json = serde_json::to_string(&fake).unwrap();
+        assert!(!fake_json.contains("4242"), "{}", fake_json);
+        assert_eq!(fake.chargeback.unwrap().payment_id, fake.payment_id.unwrap());
+        assert!(fake.refund_id.is_some() && fake.payment_token.is_some());
+    }
+
+    #[test]
+    fn test_same_customer_same_fake() {
+        let mut anonymizer = DataAnonymizer::new(7);
+
+        let first = anonymizer.anonymize_order(&make_order("Jane", "jane@real.com"));
+        let second = anonymizer.anonymize_order(&make_order("Jane", "JANE@real.com"));
+        let other = anonymizer.anonymize_order(&make_order("Bob", "bob@real.com"));
+
+        assert_eq!(first.customer.email, second.customer.email);
+        assert_eq!(first.customer.id, second.customer.id);
+        assert_ne!(first.customer.email, other.customer.email);
+        assert_eq!(anonymizer.customer_count(), 2);
+    }
+
+    #[test]
+    fn test_export_between_repositories() {
+        let mut source = MemoryOrderRepository::new();
+        source.save(&make_order("Jane", "jane@real.com")).unwrap();
+        source.save(&make_order("Bob", "bob@real.com")).unwrap();
+        let mut target = MemoryOrderRepository::new();
+
+        let exported = DataAnonymizer::new(1).export(&source, &mut target).unwrap();
+
+        assert_eq!(exported, 2);
+        assert!(target.find_by_customer_email("jane@real.com").unwrap().is_empty());
+        assert_eq!(target.list_all().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,409 @@
+// SOLID: DayCloseService - the end-of-day close, in a fixed order
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Every step already belongs to someone else: OrderService cancels and
+// expires orders, ReportingService computes the figures, the archive is an
+// OrderRepository, flush jobs empty whatever was queued. This service only
+// runs them in the right order and collects one summary:
+//
+// 1. Stale orders: never-paid (Pending, AwaitingPayment) orders of the day are cancelled,
+//    Ready orders become no-shows (nobody collects after closing)
+// 2. Flush: registered jobs run once (webhook relays, outboxes...)
+// 3. Archive: the day's orders are copied to the archive repository
+// 4. Bundle: daily report, no-shows, payment mix, latency and the register
+//    reconciliation, computed from the archived copy, and the chargebacks
+//    received that day
+//
+// Paid and Preparing orders are left alone: they're paid for, the kitchen
+// finishes them. They're counted as still open.
+//
+// Sales figures are in the shop's currency (`with_currency`); the register
+// totals are kept per currency, so an order imported in francs shows up as
+// francs expected in the drawer instead of being added to the dollars.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// The archive is any OrderRepository (a JSON file per day in the CLI).
+// With several servers, `with_lock` makes them take turns through the
+// DistributedLock port: a second close of the same day, started while the
+// first is still running, is refused instead of racing it.
+
+use super::dispute_service::DisputeReport;
+use super::job_scheduler::{Job, JobRun};
+use super::no_show::{NoShowPolicy, NoShowReport};
+use super::order_service::{OrderService, OrderServiceError};
+use super::reporting_service::{
+    is_sale, payment_method, DailyReport, DateRange, LatencyReport, MixShare, ReportingService,
+};
+use crate::domain::{Currency, Money, Order, OrderStatus};
+use crate::ports::{
+    Clock, DistributedLock, LockError, Notifier, OrderRepository, PaymentProcessor,
+    RepositoryError,
+};
+use chrono::{Duration, NaiveDate};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+
+/// Errors that stop the close (flush job failures don't, they're reported)
+#[derive(Debug)]
+pub enum DayCloseError {
+    OrderFailed(OrderServiceError),
+    ArchiveFailed(RepositoryError),
+    /// Another instance is closing the same day right now
+    Locked(String),
+    LockFailed(LockError),
+}
+
+impl fmt::Display for DayCloseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayCloseError::OrderFailed(e) => write!(f, "Order update failed: {}", e),
+            DayCloseError::ArchiveFailed(e) => write!(f, "Archive failed: {}", e),
+            DayCloseError::Locked(msg) => write!(f, "Close already running: {}", msg),
+            DayCloseError::LockFailed(e) => write!(f, "Could not take the close lock: {}", e),
+        }
+    }
+}
+
+impl Error for DayCloseError {}
+
+/// Cash the system expects in the drawer, against what was counted
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegisterReconciliation {
+    /// One total per currency (empty when nothing was paid in cash)
+    pub expected_cash: Vec<Money>,
+    pub counted_cash: Option<Money>,
+    /// Counted minus what's expected in the counted currency (negative = missing cash)
+    pub variance: Option<Money>,
+    /// Everything not paid in cash (settled by the card processor), per currency
+    pub card_total: Vec<Money>,
+}
+
+impl RegisterReconciliation {
+    pub fn for_orders(orders: &[Order], counted_cash: Option<Money>) -> Self {
+        let (cash, card): (Vec<&Order>, Vec<&Order>) = orders
+            .iter()
+            .filter(|o| is_sale(o))
+            .partition(|o| payment_method(o.payment_id.as_deref().unwrap_or_default()) == "Cash");
+        let expected_cash = Money::sum_by_currency(cash.iter().map(|o| o.amount_charged()));
+        let variance = counted_cash.map(|counted| {
+            let expected = expected_cash.iter().find(|cash| cash.currency == counted.currency);
+            counted - expected.copied().unwrap_or(Money::zero(counted.currency))
+        });
+
+        Self {
+            expected_cash,
+            counted_cash,
+            variance,
+            card_total: Money::sum_by_currency(card.iter().map(|o| o.amount_charged())),
+        }
+    }
+
+    /// No count, or a count of exactly what's expected (and no cash expected
+    /// in a currency that wasn't counted)
+    pub fn balances(&self) -> bool {
+        self.variance.is_none_or(|variance| {
+            variance.is_zero()
+                && self.expected_cash.iter().all(|cash| cash.currency == variance.currency)
+        })
+    }
+}
+
+/// Everything the owner gets after closing
+#[derive(Debug, Clone, Serialize)]
+pub struct DayCloseBundle {
+    pub daily: DailyReport,
+    pub no_shows: NoShowReport,
+    pub payment_mix: Vec<MixShare>,
+    pub latency: LatencyReport,
+    pub reconciliation: RegisterReconciliation,
+    /// Chargebacks received that day (withheld from the card settlement)
+    pub disputes: DisputeReport,
+}
+
+/// What the close did
+#[derive(Debug)]
+pub struct DayCloseSummary {
+    pub date: NaiveDate,
+    pub cancelled: usize,
+    pub no_shows: usize,
+    /// Paid or Preparing at close
+    pub still_open: usize,
+    pub flushed: Vec<JobRun>,
+    pub archived: usize,
+    pub bundle: DayCloseBundle,
+}
+
+/// Longest a close may hold the lock if its instance dies halfway
+const CLOSE_LEASE_MINUTES: i64 = 15;
+
+/// Runs the end-of-day close
+pub struct DayCloseService<C> {
+    currency: Currency,
+    counted_cash: Option<Money>,
+    flush_jobs: Vec<(String, Box<dyn Job<C>>)>,
+    /// The shared lock and this instance's name in it
+    lock: Option<(Box<dyn DistributedLock>, String)>,
+}
+
+impl<C> DayCloseService<C> {
+    pub fn new() -> Self {
+        Self {
+            currency: Currency::USD,
+            counted_cash: None,
+            flush_jobs: Vec::new(),
+            lock: None,
+        }
+    }
+
+    /// Only one instance at a time may close a given day
+    pub fn with_lock(mut self, lock: impl DistributedLock + 'static, instance: &str) -> Self {
+        self.lock = Some((Box::new(lock), instance.to_string()));
+        self
+    }
+
+    /// The shop's currency, the daily figures are reported in (US dollars by default)
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// The cash counted in the drawer, to reconcile against sales
+    pub fn with_counted_cash(mut self, amount: Money) -> Self {
+        self.counted_cash = Some(amount);
+        self
+    }
+
+    /// Run `job` once during the close (e.g. flush an outbox)
+    pub fn with_flush(mut self, name: &str, job: impl Job<C> + 'static) -> Self {
+        self.flush_jobs.push((name.to_string(), Box::new(job)));
+        self
+    }
+}
+
+impl<C> Default for DayCloseService<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R, P, N> DayCloseService<OrderService<R, P, N>>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    /// Close `date`: settle stale orders, flush, archive, report
+    pub fn close(
+        &mut self,
+        service: &mut OrderService<R, P, N>,
+        clock: &dyn Clock,
+        date: NaiveDate,
+        archive: impl OrderRepository,
+    ) -> Result<DayCloseSummary, DayCloseError> {
+        let Some((lock, instance)) = &self.lock else {
+            return self.close_unlocked(service, clock, date, archive);
+        };
+
+        let (lease, instance) = (format!("day-close:{}", date), instance.clone());
+        let ttl = Duration::minutes(CLOSE_LEASE_MINUTES);
+        if !lock.try_acquire(&lease, &instance, ttl).map_err(DayCloseError::LockFailed)? {
+            return Err(DayCloseError::Locked(format!("{} is being closed elsewhere", date)));
+        }
+
+        let result = self.close_unlocked(service, clock, date, archive);
+        if let Some((lock, _)) = &self.lock
+            && let Err(e) = lock.release(&lease, &instance)
+        {
+            eprintln!("Warning: Close lock not released (it expires by itself): {}", e);
+        }
+        result
+    }
+
+    fn close_unlocked(
+        &mut self,
+        service: &mut OrderService<R, P, N>,
+        clock: &dyn Clock,
+        date: NaiveDate,
+        mut archive: impl OrderRepository,
+    ) -> Result<DayCloseSummary, DayCloseError> {
+        let day_orders = |service: &OrderService<R, P, N>| -> Result<Vec<Order>, DayCloseError> {
+            Ok(service
+                .list_all_orders()
+                .map_err(DayCloseError::OrderFailed)?
+                .into_iter()
+                .filter(|o| o.created_at.date_naive() == date)
+                .collect())
+        };
+
+        // 1. Stale orders
+        let never_paid: Vec<Order> = day_orders(service)?
+            .into_iter()
+            .filter(|o| matches!(o.status, OrderStatus::Pending | OrderStatus::AwaitingPayment))
+            .collect();
+        for order in &never_paid {
+            service.cancel_order(order.id).map_err(DayCloseError::OrderFailed)?;
+        }
+        let no_shows = service
+            .expire_no_shows(&NoShowPolicy::new(0), clock)
+            .map_err(DayCloseError::OrderFailed)?
+            .len();
+
+        // 2. Flush
+        let ran_at = clock.now();
+        let flushed = self
+            .flush_jobs
+            .iter_mut()
+            .map(|(name, job)| JobRun {
+                name: name.clone(),
+                ran_at,
+                outcome: job.run(service, clock),
+            })
+            .collect();
+
+        // 3. Archive (a second close of the same day overwrites the copy)
+        let orders = day_orders(service)?;
+        for order in &orders {
+            let stored = archive.find_by_id(order.id).map_err(DayCloseError::ArchiveFailed)?;
+            match stored {
+                Some(_) => archive.update(order),
+                None => archive.save(order),
+            }
+            .map_err(DayCloseError::ArchiveFailed)?;
+        }
+
+        // 4. Bundle (chargebacks come in for orders of any day)
+        let all_orders = service.list_all_orders().map_err(DayCloseError::OrderFailed)?;
+        let reports = ReportingService::new(archive).with_currency(self.currency);
+        let range = DateRange::new(date, date);
+        let bundle = DayCloseBundle {
+            daily: reports.daily(date).map_err(DayCloseError::ArchiveFailed)?,
+            no_shows: NoShowReport::for_day(&orders, ran_at.date_naive()),
+            payment_mix: reports.payment_mix(range).map_err(DayCloseError::ArchiveFailed)?,
+            latency: reports.latency(range).map_err(DayCloseError::ArchiveFailed)?,
+            reconciliation: RegisterReconciliation::for_orders(&orders, self.counted_cash),
+            disputes: DisputeReport::for_day(&all_orders, date),
+        };
+
+        Ok(DayCloseSummary {
+            date,
+            cancelled: never_paid.len(),
+            no_shows,
+            still_open: orders
+                .iter()
+                .filter(|o| matches!(o.status, OrderStatus::Paid | OrderStatus::Preparing))
+                .count(),
+            flushed,
+            archived: orders.len(),
+            bundle,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        CashPayment, ConsoleNotifier, FixedClock, MemoryLock, MemoryOrderRepository,
+        SharedOrderRepository, Verbosity,
+    };
+    use crate::domain::{Beverage, Coffee, Customer, OrderItem, Size};
+    use crate::services::JobError;
+    use std::io::Sink;
+
+    type Shared = SharedOrderRepository<MemoryOrderRepository>;
+    type TestService = OrderService<Shared, CashPayment, ConsoleNotifier<Sink>>;
+
+    fn coffee() -> Vec<Box<dyn Beverage>> {
+        vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })]
+    }
+
+    #[test]
+    fn test_close_settles_archives_and_reconciles() {
+        let notifier =
+            ConsoleNotifier::with_writer(std::io::sink()).with_verbosity(Verbosity::Quiet);
+        let mut orders = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let mut service: TestService = OrderService::new(orders.clone(), CashPayment, notifier);
+        let customer = || Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let mut ready = service.place_order(customer(), coffee()).unwrap();
+        ready.mark_as_preparing().unwrap();
+        orders.update(&ready).unwrap();
+        service.mark_order_ready(ready.id).unwrap();
+        service.place_order(customer(), coffee()).unwrap();
+        let pending = service.submit_order(customer(), coffee()).unwrap();
+
+        let clock = FixedClock::new(chrono::Utc::now() + chrono::Duration::minutes(1));
+        let date = ready.created_at.date_naive();
+        let archive = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let mut close = DayCloseService::new()
+            .with_counted_cash(Money::usd(6.50))
+            .with_flush("outbox", |_: &mut TestService, _: &dyn Clock| {
+                Ok::<_, JobError>("0 message(s) sent".to_string())
+            });
+
+        let summary = close.close(&mut service, &clock, date, archive.clone()).unwrap();
+
+        assert_eq!((summary.cancelled, summary.no_shows, summary.still_open), (1, 1, 1));
+        assert_eq!(service.get_order(pending.id).unwrap().status, OrderStatus::Cancelled);
+        assert_eq!(summary.flushed[0].outcome.as_deref().unwrap(), "0 message(s) sent");
+        assert_eq!(summary.archived, 3);
+        assert_eq!(archive.list_all().unwrap().len(), 3);
+        assert_eq!(summary.bundle.daily.orders, 2);
+        let reconciliation = &summary.bundle.reconciliation;
+        assert_eq!(reconciliation.expected_cash, vec![Money::usd(7.00)]);
+        assert_eq!(reconciliation.variance, Some(Money::usd(-0.50)));
+        assert!(!reconciliation.balances());
+    }
+
+    #[test]
+    fn test_sales_in_another_currency_are_totalled_apart() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut orders = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let mut service: TestService =
+            OrderService::new(orders.clone(), CashPayment, notifier);
+        let dollars = service.submit_order(Customer::named("Ada"), coffee()).unwrap();
+        let mut item = OrderItem::of(&Coffee::medium());
+        item.price = Money::new(400, Currency::CHF);
+        let mut francs = Order::new(Customer::named("Imported"), vec![item]);
+        francs.mark_as_paid("CASH-IMPORT-1".to_string()).unwrap();
+        orders.save(&francs).unwrap();
+
+        let clock = FixedClock::new(chrono::Utc::now());
+        let date = dollars.created_at.date_naive();
+        let archive = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let mut close = DayCloseService::new().with_counted_cash(Money::usd(0.0));
+        let summary = close.close(&mut service, &clock, date, archive).unwrap();
+
+        let daily = &summary.bundle.daily;
+        assert_eq!(daily.orders, 0);
+        assert_eq!(daily.other_currencies, vec![Money::new(400, Currency::CHF)]);
+        let reconciliation = &summary.bundle.reconciliation;
+        assert_eq!(reconciliation.expected_cash, vec![Money::new(400, Currency::CHF)]);
+        assert_eq!(reconciliation.variance, Some(Money::usd(0.0)));
+        assert!(!reconciliation.balances(), "the francs were never counted");
+    }
+
+    #[test]
+    fn test_a_day_is_closed_by_one_instance_at_a_time() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let orders = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let mut service: TestService = OrderService::new(orders, CashPayment, notifier);
+        let clock = FixedClock::new(chrono::Utc::now());
+        let date = clock.now().date_naive();
+        let archive = || SharedOrderRepository::new(MemoryOrderRepository::new());
+
+        let (lock, lease) = (MemoryLock::new(), format!("day-close:{}", date));
+        lock.try_acquire(&lease, "server-b", chrono::Duration::minutes(5)).unwrap();
+        let mut close = DayCloseService::new().with_lock(lock.clone(), "server-a");
+
+        let refused = close.close(&mut service, &clock, date, archive());
+        assert!(matches!(refused, Err(DayCloseError::Locked(_))));
+
+        lock.release(&lease, "server-b").unwrap();
+        close.close(&mut service, &clock, date, archive()).unwrap();
+        assert_eq!(lock.holder(&lease), None);
+    }
+}
@@ -0,0 +1,123 @@
+// SOLID: DeferredNotificationJob - customer notices held back by quiet hours,
+// sent once the window opens
+//
+// The QuietHoursNotifier parks them in a NotificationOutbox with the time
+// they may go out; this job, scheduled every few minutes, sends the due ones
+// through the real channel and takes them out of the outbox.
+//
+// A notice is removed only after the channel took it: if sending fails it
+// stays, and the next run tries again. A crash between the two means one
+// notice sent twice, never one lost.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Any Notifier, any outbox (in memory, a JSON file, shared with the
+// decorator): all ports.
+
+use super::job_scheduler::{Job, JobError};
+use crate::domain::{DeferredNotice, DeferredNotification};
+use crate::ports::{Clock, NotificationError, NotificationOutbox, Notifier, OutboxError};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// What one release did
+#[derive(Debug, Default)]
+pub struct ReleaseReport {
+    pub sent: Vec<Uuid>,
+    /// Still in the outbox, tried again on the next run
+    pub failed: Vec<(Uuid, NotificationError)>,
+}
+
+/// Sends the deferred notices whose quiet hours are over
+pub struct DeferredNotificationJob<N: Notifier, O: NotificationOutbox> {
+    notifier: N,
+    outbox: O,
+}
+
+impl<N: Notifier, O: NotificationOutbox> DeferredNotificationJob<N, O> {
+    pub fn new(notifier: N, outbox: O) -> Self {
+        Self { notifier, outbox }
+    }
+
+    pub fn outbox(&self) -> &O {
+        &self.outbox
+    }
+
+    /// Send everything due at `now`, earliest first
+    pub fn release(&mut self, now: DateTime<Utc>) -> Result<ReleaseReport, OutboxError> {
+        let mut report = ReleaseReport::default();
+        for deferred in self.outbox.due(now)? {
+            match send(&self.notifier, &deferred) {
+                Ok(()) => {
+                    self.outbox.remove(deferred.id)?;
+                    report.sent.push(deferred.id);
+                }
+                Err(e) => report.failed.push((deferred.id, e)),
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn send<N: Notifier>(
+    notifier: &N,
+    deferred: &DeferredNotification,
+) -> Result<(), NotificationError> {
+    match &deferred.notice {
+        DeferredNotice::OrderPlaced(notice) => notifier.notify_order_placed(notice),
+        DeferredNotice::OrderReady(notice) => notifier.notify_order_ready(notice),
+        DeferredNotice::OrderCancelled(notice) => notifier.notify_order_cancelled(notice),
+        DeferredNotice::OrderNoShow(notice) => notifier.notify_order_no_show(notice),
+        DeferredNotice::FeedbackRequested(notice) => notifier.notify_feedback_requested(notice),
+        DeferredNotice::RewardGranted(notice) => notifier.notify_reward_granted(notice),
+    }
+}
+
+/// Scheduled on any target: it only needs the clock
+impl<C, N: Notifier, O: NotificationOutbox> Job<C> for DeferredNotificationJob<N, O> {
+    fn run(&mut self, _target: &mut C, clock: &dyn Clock) -> Result<String, JobError> {
+        let report = self.release(clock.now()).map_err(|e| JobError::Failed(e.to_string()))?;
+        if let Some((id, e)) = report.failed.first() {
+            return Err(JobError::Failed(format!(
+                "{} deferred notice(s) not sent (first: {}: {})",
+                report.failed.len(),
+                id,
+                e
+            )));
+        }
+        Ok(format!("{} deferred notice(s) sent", report.sent.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        read_notification_records, FileNotifier, FixedClock, MemoryNotificationOutbox,
+        SharedNotificationOutbox,
+    };
+    use crate::domain::{Coffee, Customer, Order, OrderReadyNotice};
+    use chrono::Duration;
+
+    #[test]
+    fn test_due_notices_are_sent_and_removed() {
+        let now = Utc::now();
+        let mut outbox = SharedNotificationOutbox::new(MemoryNotificationOutbox::new());
+        let order = Order::single(Customer::named("Ada"), Coffee::medium());
+        let notice = DeferredNotice::OrderReady(OrderReadyNotice::from_order(&order));
+        let due = DeferredNotification::new(notice.clone(), now, now + Duration::hours(1));
+        outbox.defer(due).unwrap();
+        outbox.defer(DeferredNotification::new(notice, now, now + Duration::hours(9))).unwrap();
+
+        let path = std::env::temp_dir().join(format!("released-{}.jsonl", Uuid::new_v4()));
+        let mut job = DeferredNotificationJob::new(FileNotifier::new(path.clone()), outbox.clone());
+        let clock = FixedClock::new(now);
+        assert_eq!(job.run(&mut (), &clock).unwrap(), "0 deferred notice(s) sent");
+
+        clock.advance(Duration::hours(2));
+        assert_eq!(job.run(&mut (), &clock).unwrap(), "1 deferred notice(s) sent");
+        assert_eq!(outbox.pending().unwrap().len(), 1);
+        let records = read_notification_records(&path).unwrap();
+        assert_eq!((records.len(), records[0].event.as_str()), (1, "order.ready"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,311 @@
+// SOLID: DisputeService - chargebacks registered against the order they hit
+//
+// A gateway reports a chargeback by payment ID, days or weeks after the sale
+// (its webhook, or `chargeback` on the command line). This service finds the
+// order, marks it Disputed with the chargeback on it, and freezes what the
+// customer could still spend with the shop meanwhile: their store credit,
+// their unused reward coupons and their loyalty points. Whether the bank sides with the shop is
+// settled with the gateway, outside the system.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The order records the dispute (Order::dispute), the StoreCredit,
+// CouponStore and LoyaltyRepository ports hold what gets frozen, and DisputeReport puts a day's
+// chargebacks next to the close's settlement figures. This service only
+// connects them.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Any OrderRepository. The credit, coupon and loyalty stores are optional:
+// a shop without them only records the dispute.
+//
+// Gateways redeliver webhooks: a case already registered is reported as a
+// duplicate and nothing is frozen twice.
+
+use crate::domain::{Chargeback, Money, Order, OrderStatus};
+use crate::ports::{
+    CouponError, CouponStore, CreditError, LoyaltyError, LoyaltyRepository, OrderRepository,
+    RepositoryError, StoreCredit,
+};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Why a chargeback could not be registered
+#[derive(Debug)]
+pub enum DisputeError {
+    InvalidChargeback(String),
+    /// No order was paid with this payment ID
+    UnknownPayment(String),
+    /// The order was never paid, or is already disputed under another case
+    NotDisputable(OrderStatus),
+    StorageFailed(RepositoryError),
+    CreditFailed(CreditError),
+    CouponsFailed(CouponError),
+    LoyaltyFailed(LoyaltyError),
+}
+
+impl fmt::Display for DisputeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DisputeError::InvalidChargeback(msg) => write!(f, "Invalid chargeback: {}", msg),
+            DisputeError::UnknownPayment(id) => write!(f, "No order was paid with {}", id),
+            DisputeError::NotDisputable(status) => {
+                write!(f, "A {} order can't be disputed", status.to_string().to_lowercase())
+            }
+            DisputeError::StorageFailed(e) => write!(f, "Storage failed: {}", e),
+            DisputeError::CreditFailed(e) => write!(f, "{}", e),
+            DisputeError::CouponsFailed(e) => write!(f, "{}", e),
+            DisputeError::LoyaltyFailed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for DisputeError {}
+
+/// What registering a chargeback did
+#[derive(Debug, Clone)]
+pub struct RegisteredDispute {
+    pub order: Order,
+    /// The customer's store credit was frozen
+    pub credit_frozen: bool,
+    /// Unused reward coupons put on hold
+    pub coupons_frozen: usize,
+    /// The customer's loyalty points can't be spent (PointsPayment charges
+    /// the other processor instead)
+    pub loyalty_frozen: bool,
+    /// The case was already registered (a redelivered webhook): nothing changed
+    pub duplicate: bool,
+}
+
+/// Registers gateway chargebacks
+#[derive(Default)]
+pub struct DisputeService {
+    credit: Option<Box<dyn StoreCredit>>,
+    coupons: Option<Box<dyn CouponStore>>,
+    loyalty: Option<Box<dyn LoyaltyRepository>>,
+}
+
+impl DisputeService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Freeze the customer's store credit while the dispute is open
+    pub fn with_store_credit(mut self, credit: impl StoreCredit + 'static) -> Self {
+        self.credit = Some(Box::new(credit));
+        self
+    }
+
+    /// Put the customer's unused reward coupons on hold while the dispute is open
+    pub fn with_coupons(mut self, coupons: impl CouponStore + 'static) -> Self {
+        self.coupons = Some(Box::new(coupons));
+        self
+    }
+
+    /// Freeze the customer's loyalty points while the dispute is open
+    pub fn with_loyalty(mut self, accounts: impl LoyaltyRepository + 'static) -> Self {
+        self.loyalty = Some(Box::new(accounts));
+        self
+    }
+
+    /// Mark the order paid with `chargeback.payment_id` as Disputed, and freeze
+    pub fn register(
+        &mut self,
+        repository: &mut dyn OrderRepository,
+        chargeback: Chargeback,
+    ) -> Result<RegisteredDispute, DisputeError> {
+        if chargeback.amount.cents <= 0 {
+            return Err(DisputeError::InvalidChargeback(format!(
+                "amount {} is not positive",
+                chargeback.amount
+            )));
+        }
+
+        let mut order = repository
+            .list_all()
+            .map_err(DisputeError::StorageFailed)?
+            .into_iter()
+            .find(|o| o.payment_id.as_deref() == Some(chargeback.payment_id.as_str()))
+            .ok_or_else(|| DisputeError::UnknownPayment(chargeback.payment_id.clone()))?;
+        if chargeback.amount.currency != order.currency() {
+            return Err(DisputeError::InvalidChargeback(format!(
+                "amount {} for an order paid in {}",
+                chargeback.amount,
+                order.currency()
+            )));
+        }
+
+        if order.chargeback.as_ref().is_some_and(|c| c.case_id == chargeback.case_id) {
+            return Ok(RegisteredDispute {
+                order,
+                credit_frozen: false,
+                coupons_frozen: 0,
+                loyalty_frozen: false,
+                duplicate: true,
+            });
+        }
+
+        let case_id = chargeback.case_id.clone();
+        order
+            .dispute(chargeback)
+            .map_err(|e| DisputeError::NotDisputable(e.from))?;
+        repository.update(&order).map_err(DisputeError::StorageFailed)?;
+
+        let email = order.customer.email.clone();
+        let reason = format!("Chargeback {} on order {}", case_id, order.short_id());
+        let credit_frozen = match &mut self.credit {
+            Some(credit) => {
+                credit.freeze(&email, &reason).map_err(DisputeError::CreditFailed)?;
+                true
+            }
+            None => false,
+        };
+
+        let mut coupons_frozen = 0;
+        if let Some(coupons) = &mut self.coupons {
+            let open = coupons
+                .for_customer(&email)
+                .map_err(DisputeError::CouponsFailed)?
+                .into_iter()
+                .filter(|c| c.redeemed_at.is_none() && !c.frozen);
+            for mut coupon in open {
+                coupon.frozen = true;
+                coupons.save(coupon).map_err(DisputeError::CouponsFailed)?;
+                coupons_frozen += 1;
+            }
+        }
+
+        // A customer who never earned a point has nothing to freeze
+        let mut loyalty_frozen = false;
+        if let Some(accounts) = &mut self.loyalty
+            && let Some(mut account) = accounts.find(&email).map_err(DisputeError::LoyaltyFailed)?
+        {
+            account.frozen = true;
+            accounts.save(account).map_err(DisputeError::LoyaltyFailed)?;
+            loyalty_frozen = true;
+        }
+
+        Ok(RegisteredDispute {
+            order,
+            credit_frozen,
+            coupons_frozen,
+            loyalty_frozen,
+            duplicate: false,
+        })
+    }
+}
+
+/// One chargeback in the settlement figures
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DisputeLine {
+    pub order_id: Uuid,
+    pub case_id: String,
+    pub payment_id: String,
+    pub amount: Money,
+    pub reason: String,
+}
+
+/// Chargebacks received on one day: what the gateway withholds from its settlement
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DisputeReport {
+    pub date: NaiveDate,
+    pub disputes: Vec<DisputeLine>,
+    /// One total per currency charged back, in currency order
+    pub totals: Vec<Money>,
+}
+
+impl DisputeReport {
+    /// Build the report for `date` from any list of orders (all of them:
+    /// a chargeback arrives long after the sale)
+    pub fn for_day(orders: &[Order], date: NaiveDate) -> Self {
+        let mut received: Vec<(&Order, &Chargeback)> = orders
+            .iter()
+            .filter_map(|o| o.chargeback.as_ref().map(|c| (o, c)))
+            .filter(|(_, c)| c.received_at.date_naive() == date)
+            .collect();
+        received.sort_by_key(|(_, c)| c.received_at);
+
+        let disputes: Vec<DisputeLine> = received
+            .into_iter()
+            .map(|(order, c)| DisputeLine {
+                order_id: order.id,
+                case_id: c.case_id.clone(),
+                payment_id: c.payment_id.clone(),
+                amount: c.amount,
+                reason: c.reason.clone(),
+            })
+            .collect();
+        let totals = Money::sum_by_currency(disputes.iter().map(|d| d.amount));
+
+        Self {
+            date,
+            disputes,
+            totals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        MemoryCouponStore, MemoryLoyaltyRepository, MemoryOrderRepository, MemoryStoreCredit,
+        SharedLoyaltyRepository, SharedStoreCredit,
+    };
+    use crate::domain::{Coffee, Coupon, Currency, Customer, LoyaltyAccount, Occasion};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_chargeback_disputes_the_order_and_freezes_credit() {
+        let mut repository = MemoryOrderRepository::new();
+        let mut order = Order::single(Customer::named("Ada"), Coffee::medium());
+        order.mark_as_paid("CC-1".to_string()).unwrap();
+        repository.save(&order).unwrap();
+
+        let mut credit = SharedStoreCredit::new(MemoryStoreCredit::new());
+        credit.grant("ada@example.com", Money::usd(5.0), "Cold drink").unwrap();
+        let mut coupons = MemoryCouponStore::new();
+        let month = Duration::days(30);
+        let birthday = Coupon::new("ada@example.com", Occasion::Birthday, 50.0, Utc::now(), month);
+        coupons.save(birthday).unwrap();
+        let mut accounts = SharedLoyaltyRepository::new(MemoryLoyaltyRepository::new());
+        let mut ada = LoyaltyAccount::new("ada@example.com");
+        ada.earn(300);
+        accounts.save(ada).unwrap();
+        let mut disputes = DisputeService::new()
+            .with_store_credit(credit.clone())
+            .with_coupons(coupons)
+            .with_loyalty(accounts.clone());
+
+        let chargeback = Chargeback::new("CB-1", "CC-1", Money::usd(3.50), "fraudulent");
+        let registered = disputes.register(&mut repository, chargeback.clone()).unwrap();
+        assert_eq!(registered.order.status, OrderStatus::Disputed);
+        assert!(registered.credit_frozen);
+        assert_eq!(registered.coupons_frozen, 1);
+        assert!(registered.loyalty_frozen);
+        assert_eq!(credit.balance("ada@example.com").unwrap(), Money::cents(0));
+        let mut ada = accounts.find("ada@example.com").unwrap().unwrap();
+        assert_eq!((ada.spend(100), ada.points), (0, 300));
+        let stored = repository.find_by_id(order.id).unwrap().unwrap();
+        assert_eq!(stored.chargeback, Some(chargeback.clone()));
+
+        // The gateway redelivers the webhook
+        let again = disputes.register(&mut repository, chargeback).unwrap();
+        assert!(again.duplicate);
+        let unknown = Chargeback::new("CB-2", "CC-9", Money::usd(1.0), "");
+        assert!(matches!(
+            disputes.register(&mut repository, unknown),
+            Err(DisputeError::UnknownPayment(_))
+        ));
+        let francs = Chargeback::new("CB-3", "CC-1", Money::new(350, Currency::CHF), "");
+        assert!(matches!(
+            disputes.register(&mut repository, francs),
+            Err(DisputeError::InvalidChargeback(_))
+        ));
+
+        let report = DisputeReport::for_day(&[registered.order], Utc::now().date_naive());
+        assert_eq!(report.disputes.len(), 1);
+        assert_eq!(report.totals, [Money::usd(3.50)]);
+    }
+}
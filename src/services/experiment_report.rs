@@ -0,0 +1,213 @@
+// SOLID: ExperimentReport - compare conversion and ticket size across variants
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// ExposureLog collects who saw which variant (from the message bus).
+// ExperimentReport only does arithmetic over exposures and orders; it
+// doesn't know how customers were bucketed or what each variant offered.
+//
+// DEFINITIONS:
+// - Exposed: a distinct customer who was bucketed into the variant
+// - Converted: an exposed customer with at least one sale placed at or
+//   after their first exposure
+// - Average ticket: mean total of those sales
+
+use super::reporting_service::is_sale;
+use crate::bus::{ExperimentExposure, MessageBus, Subscription};
+use crate::domain::Order;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Remembers every exposure published on the bus
+pub struct ExposureLog {
+    subscription: Subscription<ExperimentExposure>,
+    exposures: Vec<ExperimentExposure>,
+}
+
+impl ExposureLog {
+    pub fn subscribe(bus: &MessageBus) -> Self {
+        Self {
+            subscription: bus.subscribe(),
+            exposures: Vec::new(),
+        }
+    }
+
+    /// Pick up exposures published since the last poll
+    pub fn poll(&mut self) {
+        self.exposures.extend(self.subscription.drain());
+    }
+
+    pub fn exposures(&self) -> &[ExperimentExposure] {
+        &self.exposures
+    }
+}
+
+/// Results for one variant
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariantResult {
+    pub variant: String,
+    pub exposed: usize,
+    pub converted: usize,
+    pub conversion_rate: f64,
+    pub orders: usize,
+    pub average_ticket: f64,
+}
+
+/// Side-by-side results for one experiment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExperimentReport {
+    pub experiment: String,
+    /// "control" first, then the other variants by name
+    pub variants: Vec<VariantResult>,
+}
+
+impl ExperimentReport {
+    pub fn compare(experiment: &str, exposures: &[ExperimentExposure], orders: &[Order]) -> Self {
+        // variant -> customer -> first exposure
+        let mut first_seen: HashMap<&str, HashMap<String, DateTime<Utc>>> = HashMap::new();
+        for exposure in exposures.iter().filter(|e| e.experiment == experiment) {
+            let seen = first_seen
+                .entry(exposure.variant.as_str())
+                .or_default()
+                .entry(exposure.customer_email.to_lowercase())
+                .or_insert(exposure.exposed_at);
+            *seen = (*seen).min(exposure.exposed_at);
+        }
+
+        let mut variants: Vec<VariantResult> = first_seen
+            .into_iter()
+            .map(|(variant, customers)| Self::variant_result(variant, &customers, orders))
+            .collect();
+        variants.sort_by(|a, b| {
+            (a.variant != "control", &a.variant).cmp(&(b.variant != "control", &b.variant))
+        });
+
+        Self {
+            experiment: experiment.to_string(),
+            variants,
+        }
+    }
+
+    fn variant_result(
+        variant: &str,
+        customers: &HashMap<String, DateTime<Utc>>,
+        orders: &[Order],
+    ) -> VariantResult {
+        let sales: Vec<&Order> = orders
+            .iter()
+            .filter(|o| is_sale(o))
+            .filter(|o| {
+                customers
+                    .get(&o.customer.email.to_lowercase())
+                    .is_some_and(|exposed_at| o.created_at >= *exposed_at)
+            })
+            .collect();
+
+        let mut buyers: Vec<String> = sales
+            .iter()
+            .map(|o| o.customer.email.to_lowercase())
+            .collect();
+        buyers.sort();
+        buyers.dedup();
+
+        let exposed = customers.len();
+        let converted = buyers.len();
+        let revenue = sales.iter().fold(0.0, |sum, o| sum + o.total_price.to_major());
+
+        VariantResult {
+            variant: variant.to_string(),
+            exposed,
+            converted,
+            conversion_rate: if exposed == 0 {
+                0.0
+            } else {
+                converted as f64 / exposed as f64
+            },
+            orders: sales.len(),
+            average_ticket: if sales.is_empty() {
+                0.0
+            } else {
+                revenue / sales.len() as f64
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Customer, Money, OrderItem};
+    use chrono::Duration;
+
+    fn exposure(variant: &str, email: &str, at: DateTime<Utc>) -> ExperimentExposure {
+        ExperimentExposure {
+            experiment: "tea-week".to_string(),
+            variant: variant.to_string(),
+            customer_email: email.to_string(),
+            exposed_at: at,
+        }
+    }
+
+    fn paid_order(email: &str, total: f64) -> Order {
+        let customer = Customer::new("Guest".to_string(), email.to_string(), None);
+        let mut order = Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: "Tea".to_string(),
+                beverage_description: "Tea (Medium)".to_string(),
+                price: Money::usd(total),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        order
+    }
+
+    #[test]
+    fn test_compare_variants() {
+        let before = Utc::now() - Duration::minutes(5);
+        let exposures = vec![
+            exposure("twenty-off", "bob@example.com", before),
+            exposure("control", "ada@example.com", before),
+            exposure("control", "ada@example.com", before),
+            exposure("control", "cy@example.com", before),
+            exposure("twenty-off", "dee@example.com", before),
+        ];
+        let orders = vec![
+            paid_order("ada@example.com", 3.00),
+            paid_order("bob@example.com", 2.00),
+            paid_order("bob@example.com", 4.00),
+            paid_order("dee@example.com", 3.00),
+            // Never exposed: not counted anywhere
+            paid_order("eve@example.com", 9.00),
+        ];
+
+        let report = ExperimentReport::compare("tea-week", &exposures, &orders);
+
+        assert_eq!(report.variants.len(), 2);
+        let control = &report.variants[0];
+        assert_eq!(control.variant, "control");
+        assert_eq!((control.exposed, control.converted, control.orders), (2, 1, 1));
+        assert_eq!(control.conversion_rate, 0.5);
+        assert_eq!(control.average_ticket, 3.00);
+        let treated = &report.variants[1];
+        assert_eq!((treated.exposed, treated.converted, treated.orders), (2, 2, 3));
+        assert_eq!(treated.conversion_rate, 1.0);
+        assert!((treated.average_ticket - 3.00).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_orders_before_exposure_do_not_convert() {
+        let order = paid_order("ada@example.com", 3.00);
+        let later = order.created_at + Duration::minutes(1);
+        let exposures = vec![exposure("control", "ada@example.com", later)];
+
+        let report = ExperimentReport::compare("tea-week", &exposures, &[order]);
+
+        assert_eq!(report.variants[0].converted, 0);
+        assert_eq!(report.variants[0].average_ticket, 0.0);
+    }
+}
@@ -0,0 +1,400 @@
+// SOLID: FeedbackService - "how was it?" after a collected order
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// OrderService doesn't know customers get asked. It publishes OrderCompleted
+// on the bus; this service subscribes and sends the rating link through
+// whatever Notifier it was given.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// It decides who may rate what and adds the ratings up. Storage is the
+// FeedbackRepository's, delivery the Notifier's, the page or CLI the
+// caller's: `submit` is the whole API.
+//
+// TOKENS:
+// A link carries `<order id>.<HMAC of the order id>`, keyed with the shop's
+// secret. Nothing is stored per link: the signature proves we issued it, and
+// the one-rating-per-order rule makes a forwarded link harmless.
+//
+// SCORE:
+// NPS on the 1-5 scale: 5 is a promoter, 4 passive, 1-3 a detractor, and the
+// score is % promoters - % detractors (-100 to 100).
+
+use super::reporting_service::DateRange;
+use crate::bus::{MessageBus, OrderCompleted, Subscription};
+use crate::domain::{Feedback, FeedbackRequestNotice, NoticeRecipient, RATING_RANGE};
+use crate::ports::{Clock, FeedbackError, FeedbackRepository, Notifier};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Longest comment accepted, in characters
+pub const MAX_COMMENT_CHARS: usize = 500;
+
+/// Bytes of the HMAC kept in a token (hex-encoded: twice as many characters)
+const SIGNATURE_BYTES: usize = 16;
+
+/// Why a rating was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedbackServiceError {
+    /// Malformed, or not signed with our secret
+    InvalidToken,
+    InvalidRating(u8),
+    CommentTooLong(usize),
+    AlreadyRated(Uuid),
+    StorageFailed(FeedbackError),
+}
+
+impl fmt::Display for FeedbackServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedbackServiceError::InvalidToken => write!(f, "This feedback link is not valid"),
+            FeedbackServiceError::InvalidRating(rating) => write!(
+                f,
+                "Rating must be {} to {}, got {}",
+                RATING_RANGE.start(),
+                RATING_RANGE.end(),
+                rating
+            ),
+            FeedbackServiceError::CommentTooLong(chars) => write!(
+                f,
+                "Comment is {} characters, at most {} are kept",
+                chars, MAX_COMMENT_CHARS
+            ),
+            FeedbackServiceError::AlreadyRated(id) => write!(f, "Order {} was already rated", id),
+            FeedbackServiceError::StorageFailed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for FeedbackServiceError {}
+
+impl From<FeedbackError> for FeedbackServiceError {
+    fn from(e: FeedbackError) -> Self {
+        FeedbackServiceError::StorageFailed(e)
+    }
+}
+
+/// The ratings of a period, added up
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackReport {
+    pub responses: usize,
+    /// 0.0 when there are no responses
+    pub average_rating: f64,
+    pub promoters: usize,
+    pub passives: usize,
+    pub detractors: usize,
+    /// -100 to 100, None when there are no responses
+    pub nps: Option<i32>,
+    /// (rating, count) for 1 to 5
+    pub distribution: Vec<(u8, usize)>,
+}
+
+impl FeedbackReport {
+    pub fn from_feedback(feedback: &[Feedback]) -> Self {
+        let responses = feedback.len();
+        let count = |wanted: fn(u8) -> bool| feedback.iter().filter(|f| wanted(f.rating)).count();
+        let promoters = count(|rating| rating == 5);
+        let passives = count(|rating| rating == 4);
+        let detractors = count(|rating| rating <= 3);
+
+        let total: u32 = feedback.iter().map(|f| f.rating as u32).sum();
+        let (average_rating, nps) = match responses {
+            0 => (0.0, None),
+            n => {
+                let net = (promoters as f64 - detractors as f64) / n as f64 * 100.0;
+                (total as f64 / n as f64, Some(net.round() as i32))
+            }
+        };
+
+        Self {
+            responses,
+            average_rating,
+            promoters,
+            passives,
+            detractors,
+            nps,
+            distribution: RATING_RANGE
+                .map(|rating| (rating, feedback.iter().filter(|f| f.rating == rating).count()))
+                .collect(),
+        }
+    }
+}
+
+/// Sends rating links for completed orders and collects the answers
+pub struct FeedbackService<R: FeedbackRepository, N: Notifier> {
+    repository: R,
+    notifier: N,
+    secret: String,
+    link_base: String,
+    completed: Option<Subscription<OrderCompleted>>,
+}
+
+impl<R: FeedbackRepository, N: Notifier> FeedbackService<R, N> {
+    /// `link_base` is the rating page; the token is appended as a last path segment
+    pub fn new(repository: R, notifier: N, secret: &str, link_base: &str) -> Self {
+        Self {
+            repository,
+            notifier,
+            secret: secret.to_string(),
+            link_base: link_base.trim_end_matches('/').to_string(),
+            completed: None,
+        }
+    }
+
+    /// Ask for feedback on every order completed from now on (see `poll`)
+    pub fn with_message_bus(mut self, bus: &MessageBus) -> Self {
+        self.completed = Some(bus.subscribe());
+        self
+    }
+
+    pub fn repository(&self) -> &R {
+        &self.repository
+    }
+
+    /// Send a rating link for each order completed since the last poll
+    ///
+    /// Returns how many requests went out. A failed one is logged and
+    /// dropped: nagging a customer later is worse than not asking.
+    pub fn poll(&mut self) -> usize {
+        let completed = match &self.completed {
+            Some(subscription) => subscription.drain(),
+            None => return 0,
+        };
+
+        let mut sent = 0;
+        for order in completed {
+            match self.notifier.notify_feedback_requested(&self.request_for(&order)) {
+                Ok(()) => sent += 1,
+                Err(e) => eprintln!("Warning: Failed to send feedback request: {}", e),
+            }
+        }
+        sent
+    }
+
+    /// The notice asking this order's customer for a rating
+    pub fn request_for(&self, order: &OrderCompleted) -> FeedbackRequestNotice {
+        FeedbackRequestNotice {
+            order_id: order.order_id,
+            recipient: NoticeRecipient {
+                name: order.customer_name.clone(),
+                email: order.customer_email.clone(),
+            },
+            link: self.link_for(order.order_id),
+            completed_at: order.completed_at,
+        }
+    }
+
+    pub fn link_for(&self, order_id: Uuid) -> String {
+        format!("{}/{}", self.link_base, self.token_for(order_id))
+    }
+
+    /// `<order id>.<signature>`, only valid for this order
+    pub fn token_for(&self, order_id: Uuid) -> String {
+        let id = order_id.simple().to_string();
+        let signature = &self.mac(&id).finalize().into_bytes()[..SIGNATURE_BYTES];
+        let hex: String = signature.iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("{}.{}", id, hex)
+    }
+
+    /// The order a token was issued for, if we issued it
+    pub fn verify_token(&self, token: &str) -> Option<Uuid> {
+        let (id, hex) = token.trim().split_once('.')?;
+        let order_id = Uuid::parse_str(id).ok()?;
+        let signature = decode_hex(hex)?;
+        if signature.len() != SIGNATURE_BYTES {
+            return None;
+        }
+        // Constant-time comparison: a timing leak would let the signature be guessed
+        self.mac(&order_id.simple().to_string())
+            .verify_truncated_left(&signature)
+            .ok()?;
+        Some(order_id)
+    }
+
+    /// Record a customer's rating (what the rating page, or the CLI, calls)
+    ///
+    /// One rating per order: a second one is refused, not merged.
+    pub fn submit(
+        &mut self,
+        token: &str,
+        rating: u8,
+        comment: Option<&str>,
+        clock: &dyn Clock,
+    ) -> Result<Feedback, FeedbackServiceError> {
+        let order_id = self.verify_token(token).ok_or(FeedbackServiceError::InvalidToken)?;
+        if !RATING_RANGE.contains(&rating) {
+            return Err(FeedbackServiceError::InvalidRating(rating));
+        }
+        let comment = comment.map(str::trim).filter(|c| !c.is_empty());
+        if let Some(chars) = comment.map(|c| c.chars().count())
+            && chars > MAX_COMMENT_CHARS
+        {
+            return Err(FeedbackServiceError::CommentTooLong(chars));
+        }
+        if self.repository.find(order_id)?.is_some() {
+            return Err(FeedbackServiceError::AlreadyRated(order_id));
+        }
+
+        let feedback = Feedback {
+            order_id,
+            rating,
+            comment: comment.map(str::to_string),
+            submitted_at: clock.now(),
+        };
+        self.repository.save(feedback.clone())?;
+        Ok(feedback)
+    }
+
+    /// Ratings submitted within `range` (all of them if None)
+    pub fn report(&self, range: Option<DateRange>) -> Result<FeedbackReport, FeedbackServiceError> {
+        let feedback: Vec<Feedback> = self
+            .repository
+            .list()?
+            .into_iter()
+            .filter(|f| range.is_none_or(|r| r.contains(f.submitted_at.date_naive())))
+            .collect();
+        Ok(FeedbackReport::from_feedback(&feedback))
+    }
+
+    fn mac(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts any key length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        CashPayment, ConsoleNotifier, FileNotifier, FixedClock, MemoryFeedbackRepository,
+        MemoryOrderRepository, SharedOrderRepository, read_notification_records,
+    };
+    use crate::ports::OrderRepository;
+    use crate::domain::{Coffee, Customer, Size};
+    use crate::services::OrderService;
+    use chrono::Utc;
+    use std::io;
+
+    fn service() -> FeedbackService<MemoryFeedbackRepository, ConsoleNotifier<io::Sink>> {
+        FeedbackService::new(
+            MemoryFeedbackRepository::new(),
+            ConsoleNotifier::with_writer(io::sink()),
+            "s3cret",
+            "https://shop.example/feedback/",
+        )
+    }
+
+    #[test]
+    fn test_completed_orders_get_a_rating_link() {
+        let bus = MessageBus::new();
+        let path = std::env::temp_dir().join(format!("feedback-{}.jsonl", Uuid::new_v4()));
+        let mut feedback = FeedbackService::new(
+            MemoryFeedbackRepository::new(),
+            FileNotifier::new(path.clone()),
+            "s3cret",
+            "https://shop.example/feedback",
+        )
+        .with_message_bus(&bus);
+        let mut repository = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let mut orders = OrderService::new(
+            repository.clone(),
+            CashPayment,
+            ConsoleNotifier::with_writer(io::sink()),
+        )
+        .with_message_bus(bus);
+
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let coffee = Coffee {
+            size: Size::Small,
+            extra_shots: 0,
+        };
+        let mut order = orders.place_order(customer, vec![Box::new(coffee)]).unwrap();
+        // Not ready yet: nothing to complete, nobody asked
+        assert!(orders.complete_order(order.id).is_err());
+        order.mark_as_preparing().unwrap();
+        repository.update(&order).unwrap();
+        orders.mark_order_ready(order.id).unwrap();
+        orders.complete_order(order.id).unwrap();
+        assert_eq!(feedback.poll(), 1);
+        assert_eq!(feedback.poll(), 0);
+
+        let records = read_notification_records(&path).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, "feedback.requested");
+        let link = feedback.link_for(order.id);
+        assert!(link.starts_with("https://shop.example/feedback/"));
+        assert_eq!(feedback.verify_token(link.rsplit('/').next().unwrap()), Some(order.id));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_submit_checks_token_rating_and_duplicates() {
+        let mut service = service();
+        let clock = FixedClock::new(Utc::now());
+        let order_id = Uuid::new_v4();
+        let token = service.token_for(order_id);
+
+        let forged = FeedbackService::new(
+            MemoryFeedbackRepository::new(),
+            ConsoleNotifier::with_writer(io::sink()),
+            "guess",
+            "https://shop.example/feedback",
+        )
+        .token_for(order_id);
+        assert_eq!(
+            service.submit(&forged, 5, None, &clock),
+            Err(FeedbackServiceError::InvalidToken)
+        );
+        assert_eq!(
+            service.submit(&token, 6, None, &clock),
+            Err(FeedbackServiceError::InvalidRating(6))
+        );
+        let long = "a".repeat(MAX_COMMENT_CHARS + 1);
+        assert!(matches!(
+            service.submit(&token, 5, Some(&long), &clock),
+            Err(FeedbackServiceError::CommentTooLong(_))
+        ));
+
+        let saved = service.submit(&token, 4, Some("  Lovely  "), &clock).unwrap();
+        assert_eq!(saved.comment.as_deref(), Some("Lovely"));
+        assert_eq!(
+            service.submit(&token, 1, None, &clock),
+            Err(FeedbackServiceError::AlreadyRated(order_id))
+        );
+    }
+
+    #[test]
+    fn test_report_scores_nps() {
+        let mut service = service();
+        let clock = FixedClock::new(Utc::now());
+        for rating in [5, 5, 5, 4, 2] {
+            let token = service.token_for(Uuid::new_v4());
+            service.submit(&token, rating, None, &clock).unwrap();
+        }
+
+        let report = service.report(None).unwrap();
+        assert_eq!(report.responses, 5);
+        assert_eq!((report.promoters, report.passives, report.detractors), (3, 1, 1));
+        assert_eq!(report.nps, Some(40));
+        assert!((report.average_rating - 4.2).abs() < 1e-9);
+        assert_eq!(report.distribution[4], (5, 3));
+
+        let yesterday = clock.now().date_naive().pred_opt().unwrap();
+        let empty = service.report(Some(DateRange::new(yesterday, yesterday))).unwrap();
+        assert_eq!((empty.responses, empty.nps), (0, None));
+    }
+}
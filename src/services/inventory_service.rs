@@ -0,0 +1,171 @@
+// SOLID: InventoryService - is there enough on the shelves for this order?
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// It turns an order's lines into ingredients (CostModel's recipes, scaled to
+// the size) and holds them against the stock levels: `check` before anything
+// is charged, `consume` once the order is paid. Where the levels are kept is
+// the InventoryRepository's business.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// OrderService only sees this service (`with_inventory`), and this service
+// only the InventoryRepository port: memory in tests, a JSON file in the shop.
+//
+// An ingredient nobody put on the shelves is not tracked, so it never runs
+// out: a shop can start by counting its milk and nothing else. Lines without
+// a recipe take nothing.
+
+use crate::domain::{OrderItem, Shortage, StockLevel};
+use crate::ports::{InventoryError, InventoryRepository};
+use crate::services::cost_model::CostModel;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// Why stock could not be checked or taken
+#[derive(Debug, Clone, PartialEq)]
+pub enum StockError {
+    /// The order needs more than the shop has, ingredient by ingredient
+    OutOfStock(Vec<Shortage>),
+    StorageFailed(InventoryError),
+}
+
+impl fmt::Display for StockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StockError::OutOfStock(shortages) => {
+                let list: Vec<String> = shortages.iter().map(|s| s.to_string()).collect();
+                write!(f, "Out of stock: {}", list.join(", "))
+            }
+            StockError::StorageFailed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for StockError {}
+
+impl From<InventoryError> for StockError {
+    fn from(e: InventoryError) -> Self {
+        StockError::StorageFailed(e)
+    }
+}
+
+/// Checks and takes ingredient stock for orders
+pub struct InventoryService {
+    repository: Box<dyn InventoryRepository>,
+    recipes: CostModel,
+}
+
+impl InventoryService {
+    /// Stock kept in `repository`, drinks made with the default recipes
+    pub fn new(repository: impl InventoryRepository + 'static) -> Self {
+        Self {
+            repository: Box::new(repository),
+            recipes: CostModel::default(),
+        }
+    }
+
+    /// Use the shop's own recipes (the ones its CostModel costs)
+    pub fn with_recipes(mut self, recipes: CostModel) -> Self {
+        self.recipes = recipes;
+        self
+    }
+
+    /// Ingredient -> units these lines take, all quantities included
+    pub fn needs(&self, items: &[OrderItem]) -> BTreeMap<String, f64> {
+        let mut needs = BTreeMap::new();
+        for item in items {
+            for (ingredient, units) in self.recipes.ingredients_for(item).unwrap_or_default() {
+                *needs.entry(ingredient).or_insert(0.0) += units * item.quantity as f64;
+            }
+        }
+        needs
+    }
+
+    /// Ok if every tracked ingredient these lines take is on hand
+    pub fn check(&self, items: &[OrderItem]) -> Result<(), StockError> {
+        let mut shortages = Vec::new();
+        for (ingredient, needed) in self.needs(items) {
+            if let Some(level) = self.repository.find(&ingredient)?
+                && level.on_hand < needed
+            {
+                shortages.push(Shortage {
+                    ingredient,
+                    needed,
+                    on_hand: level.on_hand,
+                });
+            }
+        }
+        if shortages.is_empty() {
+            Ok(())
+        } else {
+            Err(StockError::OutOfStock(shortages))
+        }
+    }
+
+    /// Take what these lines use off the shelves (tracked ingredients only)
+    pub fn consume(&mut self, items: &[OrderItem]) -> Result<(), StockError> {
+        for (ingredient, units) in self.needs(items) {
+            if let Some(mut level) = self.repository.find(&ingredient)? {
+                level.take(units);
+                self.repository.save(level)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add `units` of an ingredient (it becomes tracked if it wasn't)
+    pub fn restock(&mut self, ingredient: &str, units: f64) -> Result<StockLevel, StockError> {
+        let mut level = self
+            .repository
+            .find(ingredient)?
+            .unwrap_or_else(|| StockLevel::new(ingredient, 0.0));
+        level.restock(units);
+        self.repository.save(level.clone())?;
+        Ok(level)
+    }
+
+    /// Every tracked ingredient, by name
+    pub fn levels(&self) -> Result<Vec<StockLevel>, StockError> {
+        Ok(self.repository.list()?)
+    }
+
+    /// Ingredients at or below their reorder point
+    pub fn low_stock(&self) -> Result<Vec<StockLevel>, StockError> {
+        Ok(self.levels()?.into_iter().filter(StockLevel::is_low).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryInventoryRepository;
+    use crate::domain::{Coffee, Size, Tea, TeaVariety};
+
+    #[test]
+    fn test_check_consume_and_restock() {
+        let shelves = MemoryInventoryRepository::with_levels([
+            StockLevel::new("espresso shot", 3.0).with_reorder_at(1.0),
+            StockLevel::new("milk (100 ml)", 10.0),
+        ]);
+        let mut inventory = InventoryService::new(shelves);
+        let two_coffees = [OrderItem {
+            quantity: 2,
+            ..OrderItem::of(&Coffee::medium())
+        }];
+        let tea = [OrderItem::of(&Tea::new(TeaVariety::Green, Size::Large))];
+
+        assert_eq!(inventory.needs(&two_coffees)["espresso shot"], 2.0);
+        inventory.check(&two_coffees).unwrap();
+        inventory.consume(&two_coffees).unwrap();
+        assert_eq!(inventory.low_stock().unwrap()[0].ingredient, "espresso shot");
+
+        let err = inventory.check(&two_coffees).unwrap_err();
+        assert_eq!(err.to_string(), "Out of stock: espresso shot (2 needed, 1 on hand)");
+        // Tea bags and cups aren't tracked: never out
+        inventory.check(&tea).unwrap();
+
+        inventory.restock("espresso shot", 20.0).unwrap();
+        inventory.check(&two_coffees).unwrap();
+        assert_eq!(inventory.levels().unwrap()[1].on_hand, 8.0);
+    }
+}
@@ -0,0 +1,263 @@
+// SOLID: IssueService - complaints, from the counter to a remedy
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// It keeps the list of issues and runs their workflow:
+//
+// ```text
+// open ──resolve(Remake)──────> Resolved (the barista makes it again)
+//   ├───resolve(Refund(x))────> Resolved (RefundService: may wait for a manager)
+//   └───resolve(Credit(x))────> Resolved (StoreCredit: the customer's balance)
+// ```
+//
+// Moving money is not its job: refunds go through RefundService, with its
+// approval limit and permissions, and credit through the StoreCredit port.
+// An issue resolved with a large refund is resolved for the customer even
+// while the refund waits for approval; the pending request is its reference.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Any PaymentProcessor and manager channel (inside RefundService), any
+// StoreCredit.
+
+use super::authorization::{AuthorizationPolicy, Permission};
+use super::refund_service::{RefundError, RefundService};
+use crate::domain::{Issue, IssueKind, Order, RefundStatus, Remedy, StaffMember};
+use crate::ports::{CreditError, Notifier, PaymentProcessor, StoreCredit};
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Errors from the issue workflow
+#[derive(Debug)]
+pub enum IssueError {
+    IssueNotFound,
+    AlreadyResolved,
+    /// The order handed in is not the one the issue is about
+    WrongOrder,
+    NotAuthorized { staff: String, permission: Permission },
+    RefundFailed(RefundError),
+    CreditFailed(CreditError),
+}
+
+impl fmt::Display for IssueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IssueError::IssueNotFound => write!(f, "Issue not found"),
+            IssueError::AlreadyResolved => write!(f, "Issue was already resolved"),
+            IssueError::WrongOrder => write!(f, "This issue is about another order"),
+            IssueError::NotAuthorized { staff, permission } => {
+                write!(f, "{} is not allowed to {:?}", staff, permission)
+            }
+            IssueError::RefundFailed(e) => write!(f, "{}", e),
+            IssueError::CreditFailed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for IssueError {}
+
+/// Keeps complaints and carries out their remedies
+pub struct IssueService<P: PaymentProcessor, M: Notifier, C: StoreCredit> {
+    refunds: RefundService<P, M>,
+    credit: C,
+    authorization: AuthorizationPolicy,
+    issues: Vec<Issue>,
+}
+
+impl<P: PaymentProcessor, M: Notifier, C: StoreCredit> IssueService<P, M, C> {
+    pub fn new(refunds: RefundService<P, M>, credit: C) -> Self {
+        Self {
+            refunds,
+            credit,
+            authorization: AuthorizationPolicy::default(),
+            issues: Vec::new(),
+        }
+    }
+
+    /// Who may give credit (refund permissions are RefundService's own)
+    pub fn with_authorization(mut self, authorization: AuthorizationPolicy) -> Self {
+        self.authorization = authorization;
+        self
+    }
+
+    /// Write down what the customer says went wrong
+    pub fn open(
+        &mut self,
+        order: &Order,
+        kind: IssueKind,
+        description: &str,
+        reported_by: &StaffMember,
+    ) -> Issue {
+        let issue = Issue::new(order, kind, description, reported_by);
+        self.issues.push(issue.clone());
+        issue
+    }
+
+    /// Make it right, and close the issue
+    ///
+    /// A refund that goes through at once is added to the order's
+    /// `refunded_total`: save the order afterwards.
+    ///
+    /// If the remedy can't be carried out (not allowed, refund refused by
+    /// the processor...), the issue stays open so another one can be tried.
+    pub fn resolve(
+        &mut self,
+        id: Uuid,
+        order: &mut Order,
+        remedy: Remedy,
+        staff: &StaffMember,
+    ) -> Result<Issue, IssueError> {
+        let index = self.open_index(id)?;
+        if self.issues[index].order_id != order.id {
+            return Err(IssueError::WrongOrder);
+        }
+
+        let reason = format!("{:?}: {}", self.issues[index].kind, self.issues[index].description);
+        let reference = match &remedy {
+            Remedy::Remake => None,
+            Remedy::Refund(amount) => {
+                let request = self
+                    .refunds
+                    .request(order, *amount, &reason, staff)
+                    .map_err(IssueError::RefundFailed)?;
+                Some(match request.status {
+                    RefundStatus::Refunded => request.refund_id.unwrap_or_default(),
+                    _ => format!("refund request {} (pending approval)", request.id),
+                })
+            }
+            Remedy::Credit(amount) => {
+                if !self.authorization.allows(staff, Permission::GrantCredit) {
+                    return Err(IssueError::NotAuthorized {
+                        staff: staff.name.clone(),
+                        permission: Permission::GrantCredit,
+                    });
+                }
+                let customer = &self.issues[index].customer_email;
+                let grant = self
+                    .credit
+                    .grant(customer, *amount, &reason)
+                    .map_err(IssueError::CreditFailed)?;
+                Some(grant)
+            }
+        };
+
+        self.issues[index].resolve(remedy, staff, reference);
+        Ok(self.issues[index].clone())
+    }
+
+    /// Issues still waiting for a remedy, oldest first
+    pub fn open_issues(&self) -> Vec<&Issue> {
+        self.issues.iter().filter(|i| i.is_open()).collect()
+    }
+
+    pub fn for_order(&self, order_id: Uuid) -> Vec<&Issue> {
+        self.issues.iter().filter(|i| i.order_id == order_id).collect()
+    }
+
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    /// Where refund remedies waiting for a manager are approved
+    pub fn refunds_mut(&mut self) -> &mut RefundService<P, M> {
+        &mut self.refunds
+    }
+
+    pub fn credit(&self) -> &C {
+        &self.credit
+    }
+
+    fn open_index(&self, id: Uuid) -> Result<usize, IssueError> {
+        let index = self
+            .issues
+            .iter()
+            .position(|i| i.id == id)
+            .ok_or(IssueError::IssueNotFound)?;
+        if self.issues[index].is_open() {
+            Ok(index)
+        } else {
+            Err(IssueError::AlreadyResolved)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, MemoryStoreCredit};
+    use crate::domain::{Customer, IssueStatus, Money, OrderItem, Role};
+    use std::io;
+
+    type Service = IssueService<CashPayment, ConsoleNotifier<io::Sink>, MemoryStoreCredit>;
+
+    fn paid_order(total: f64) -> Order {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let mut order = Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: "Coffee".to_string(),
+                beverage_description: "Large latte".to_string(),
+                price: Money::usd(total),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        order
+    }
+
+    fn service() -> Service {
+        let refunds = RefundService::new(CashPayment, ConsoleNotifier::with_writer(io::sink()));
+        IssueService::new(refunds, MemoryStoreCredit::new())
+    }
+
+    #[test]
+    fn test_remedies_refund_and_credit() {
+        let mut issues = service();
+        let barista = StaffMember::new("Sam", Role::Barista);
+        let lead = StaffMember::new("Kim", Role::ShiftLead);
+        let mut order = paid_order(4.5);
+
+        let cold = issues.open(&order, IssueKind::ColdDrink, "Lukewarm", &barista);
+        let refund = Remedy::Refund(Money::usd(4.5));
+        let refunded = issues.resolve(cold.id, &mut order, refund, &barista).unwrap();
+        assert_eq!(refunded.status, IssueStatus::Resolved);
+        assert!(refunded.reference.unwrap().starts_with("CASH-REFUND-"));
+        assert_eq!(order.refunded_total, Money::usd(4.5));
+        assert!(matches!(
+            issues.resolve(cold.id, &mut order, Remedy::Remake, &barista),
+            Err(IssueError::AlreadyResolved)
+        ));
+
+        // Baristas may not give credit: the issue stays open for someone who can
+        let wrong = issues.open(&order, IssueKind::WrongDrink, "Asked for oat milk", &barista);
+        assert!(matches!(
+            issues.resolve(wrong.id, &mut order, Remedy::Credit(Money::usd(3.0)), &barista),
+            Err(IssueError::NotAuthorized { .. })
+        ));
+        assert_eq!(issues.open_issues().len(), 1);
+        issues.resolve(wrong.id, &mut order, Remedy::Credit(Money::usd(3.0)), &lead).unwrap();
+        assert_eq!(issues.credit().balance("Ada@Example.com").unwrap(), Money::usd(3.0));
+        assert!(issues.open_issues().is_empty());
+        assert_eq!(issues.for_order(order.id).len(), 2);
+    }
+
+    #[test]
+    fn test_large_refund_waits_for_a_manager() {
+        let mut issues = service();
+        let barista = StaffMember::new("Sam", Role::Barista);
+        let mut order = paid_order(45.0);
+        let mut other = paid_order(45.0);
+        let issue = issues.open(&order, IssueKind::MissingItem, "Half the tray", &barista);
+
+        assert!(matches!(
+            issues.resolve(issue.id, &mut other, Remedy::Remake, &barista),
+            Err(IssueError::WrongOrder)
+        ));
+        let refund = Remedy::Refund(Money::usd(30.0));
+        let resolved = issues.resolve(issue.id, &mut order, refund, &barista).unwrap();
+        assert!(resolved.reference.unwrap().contains("pending approval"));
+        assert_eq!(issues.refunds_mut().pending().len(), 1);
+    }
+}
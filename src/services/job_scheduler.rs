@@ -0,0 +1,498 @@
+// SOLID: JobScheduler - recurring background tasks
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. OPEN-CLOSED PRINCIPLE (OCP):
+//    The scheduler knows nothing about no-shows, reports or inventory.
+//    New recurring tasks are added by registering a Job, not by editing this file.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    "What time is it?" comes from the Clock port, so schedules can be
+//    tested by moving a FixedClock instead of sleeping.
+//
+// 3. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    The scheduler decides WHEN a job runs. Each job decides WHAT it does.
+//
+// The scheduler is generic over a context `C` (typically an OrderService).
+// The caller owns the context and lends it to `run_pending()`, so the same
+// scheduler can be driven from the CLI loop or from a long-running process.
+//
+// SEVERAL INSTANCES:
+// With several servers on one backend, every scheduler would run the nightly
+// report. A job registered with `register_singleton` takes a lease from the
+// DistributedLock port first, held until its next due time: the first
+// instance to get there runs it, the others skip that round.
+
+use crate::domain::{Money, SlaBreachNotice};
+use crate::ports::{Clock, DistributedLock, Notifier, OrderRepository, PaymentProcessor};
+use crate::services::no_show::NoShowPolicy;
+use crate::services::order_service::OrderService;
+use crate::services::sla_watchdog::SlaPolicy;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by a job run
+#[derive(Debug, Clone)]
+pub enum JobError {
+    Failed(String),
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JobError::Failed(msg) => write!(f, "Job failed: {}", msg),
+        }
+    }
+}
+
+impl Error for JobError {}
+
+/// When a job should run (a tiny subset of cron)
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Run at a fixed interval, starting with the first `run_pending()` call
+    Every(Duration),
+    /// Run once a day at the given UTC time
+    DailyAt(NaiveTime),
+}
+
+impl Schedule {
+    /// First due time for a job that has never run
+    fn first_due(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(_) => now,
+            Schedule::DailyAt(time) => {
+                let today = now.date_naive().and_time(*time).and_utc();
+                if today >= now {
+                    today
+                } else {
+                    today + Duration::days(1)
+                }
+            }
+        }
+    }
+
+    /// Next due time after a run that started at `ran_at`
+    fn next_after(&self, ran_at: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(interval) => ran_at + *interval,
+            Schedule::DailyAt(time) => {
+                ran_at.date_naive().and_time(*time).and_utc() + Duration::days(1)
+            }
+        }
+    }
+}
+
+/// A recurring task
+///
+/// Returns a short human-readable summary of what it did.
+/// Closures `FnMut(&mut C, &dyn Clock) -> Result<String, JobError>` are jobs too.
+pub trait Job<C> {
+    fn run(&mut self, ctx: &mut C, clock: &dyn Clock) -> Result<String, JobError>;
+}
+
+impl<C, F> Job<C> for F
+where
+    F: FnMut(&mut C, &dyn Clock) -> Result<String, JobError>,
+{
+    fn run(&mut self, ctx: &mut C, clock: &dyn Clock) -> Result<String, JobError> {
+        self(ctx, clock)
+    }
+}
+
+/// Outcome of one job execution
+#[derive(Debug)]
+pub struct JobRun {
+    pub name: String,
+    pub ran_at: DateTime<Utc>,
+    pub outcome: Result<String, JobError>,
+}
+
+struct ScheduledJob<C> {
+    name: String,
+    schedule: Schedule,
+    next_run: Option<DateTime<Utc>>,
+    /// Run by one instance only (see `with_lock`)
+    singleton: bool,
+    job: Box<dyn Job<C>>,
+}
+
+/// Lightweight cron-like scheduler
+///
+/// There is no background thread: the host calls `run_pending()` whenever it
+/// has a moment (every loop iteration, every tick of a timer...). Each job
+/// that is due runs once, then gets its next due time from its schedule.
+pub struct JobScheduler<C> {
+    jobs: Vec<ScheduledJob<C>>,
+    /// The shared lock and this instance's name in it
+    lock: Option<(Box<dyn DistributedLock>, String)>,
+}
+
+impl<C> JobScheduler<C> {
+    /// Create an empty scheduler
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            lock: None,
+        }
+    }
+
+    /// Coordinate singleton jobs with the other instances through `lock`
+    ///
+    /// `instance` names this process in the lock (host name, pod name...).
+    /// Without a lock, singleton jobs run like any other.
+    pub fn with_lock(mut self, lock: impl DistributedLock + 'static, instance: &str) -> Self {
+        self.lock = Some((Box::new(lock), instance.to_string()));
+        self
+    }
+
+    /// Register a job under a name
+    pub fn register(
+        &mut self,
+        name: &str,
+        schedule: Schedule,
+        job: impl Job<C> + 'static,
+    ) -> &mut Self {
+        self.push(name, schedule, false, job)
+    }
+
+    /// Register a job that only one instance runs each time it is due
+    pub fn register_singleton(
+        &mut self,
+        name: &str,
+        schedule: Schedule,
+        job: impl Job<C> + 'static,
+    ) -> &mut Self {
+        self.push(name, schedule, true, job)
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        schedule: Schedule,
+        singleton: bool,
+        job: impl Job<C> + 'static,
+    ) -> &mut Self {
+        self.jobs.push(ScheduledJob {
+            name: name.to_string(),
+            schedule,
+            next_run: None,
+            singleton,
+            job: Box::new(job),
+        });
+        self
+    }
+
+    /// Names of the registered jobs, in registration order
+    pub fn job_names(&self) -> Vec<&str> {
+        self.jobs.iter().map(|job| job.name.as_str()).collect()
+    }
+
+    /// Run every job that is due and report what happened
+    ///
+    /// A failing job doesn't stop the others; its error is in the returned runs
+    /// and it is retried at its next scheduled time. A singleton job another
+    /// instance took this round is not run and not reported.
+    pub fn run_pending(&mut self, ctx: &mut C, clock: &dyn Clock) -> Vec<JobRun> {
+        let now = clock.now();
+        let mut runs = Vec::new();
+
+        for scheduled in &mut self.jobs {
+            let due = *scheduled
+                .next_run
+                .get_or_insert_with(|| scheduled.schedule.first_due(now));
+
+            if due > now {
+                continue;
+            }
+
+            let next_run = scheduled.schedule.next_after(now);
+            scheduled.next_run = Some(next_run);
+
+            // Hold the lease until the next round, so a later instance skips this one
+            let outcome = match (&self.lock, scheduled.singleton) {
+                (Some((lock, instance)), true) => {
+                    let lease = format!("job:{}", scheduled.name);
+                    match lock.try_acquire(&lease, instance, next_run - now) {
+                        Ok(true) => scheduled.job.run(ctx, clock),
+                        Ok(false) => continue,
+                        Err(e) => Err(JobError::Failed(e.to_string())),
+                    }
+                }
+                _ => scheduled.job.run(ctx, clock),
+            };
+
+            runs.push(JobRun {
+                name: scheduled.name.clone(),
+                ran_at: now,
+                outcome,
+            });
+        }
+
+        runs
+    }
+}
+
+impl<C> Default for JobScheduler<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// BUILT-IN JOBS
+//
+// Jobs that work on an OrderService. Other recurring tasks can be registered
+// as closures without touching this file (OCP).
+// ============================================================================
+
+/// Flags ready orders that were never picked up
+pub struct NoShowExpiryJob {
+    policy: NoShowPolicy,
+}
+
+impl NoShowExpiryJob {
+    pub fn new(policy: NoShowPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<R, P, N> Job<OrderService<R, P, N>> for NoShowExpiryJob
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    fn run(
+        &mut self,
+        service: &mut OrderService<R, P, N>,
+        clock: &dyn Clock,
+    ) -> Result<String, JobError> {
+        let flagged = service
+            .expire_no_shows(&self.policy, clock)
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        Ok(format!("{} order(s) flagged as no-show", flagged.len()))
+    }
+}
+
+/// Tags orders stuck past the SLA and escalates them to a manager
+///
+/// `manager` is a notifier of its own (a ChatNotifier on the managers'
+/// channel, say), separate from the service's customer-facing one.
+pub struct SlaWatchdogJob<M: Notifier> {
+    policy: SlaPolicy,
+    manager: M,
+}
+
+impl<M: Notifier> SlaWatchdogJob<M> {
+    pub fn new(policy: SlaPolicy, manager: M) -> Self {
+        Self { policy, manager }
+    }
+}
+
+impl<R, P, N, M> Job<OrderService<R, P, N>> for SlaWatchdogJob<M>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+    M: Notifier,
+{
+    fn run(
+        &mut self,
+        service: &mut OrderService<R, P, N>,
+        clock: &dyn Clock,
+    ) -> Result<String, JobError> {
+        let now = clock.now();
+        let flagged = service
+            .flag_sla_breaches(&self.policy, clock)
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        for order in &flagged {
+            let notice = SlaBreachNotice::from_order(order, now);
+            if let Err(e) = self.manager.notify_sla_breach(&notice) {
+                eprintln!("Warning: Failed to send notification: {}", e);
+            }
+        }
+
+        Ok(format!("{} order(s) past SLA", flagged.len()))
+    }
+}
+
+/// Produces the no-show report for the current day
+pub struct NoShowReportJob;
+
+impl<R, P, N> Job<OrderService<R, P, N>> for NoShowReportJob
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    fn run(
+        &mut self,
+        service: &mut OrderService<R, P, N>,
+        clock: &dyn Clock,
+    ) -> Result<String, JobError> {
+        let report = service
+            .no_show_report(clock.now().date_naive())
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        let values: Vec<String> = report.total_value().iter().map(Money::to_string).collect();
+        Ok(format!(
+            "No-show report for {}: {} order(s), {} uncollected",
+            report.date,
+            report.count(),
+            if values.is_empty() { "nothing".to_string() } else { values.join(" + ") }
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        CashPayment, ConsoleNotifier, FixedClock, MemoryLock, MemoryOrderRepository, Verbosity,
+    };
+    use crate::domain::{Beverage, Coffee, Customer, Size};
+    use std::sync::Arc;
+
+    fn counting_job(counter: &mut u32, _clock: &dyn Clock) -> Result<String, JobError> {
+        *counter += 1;
+        Ok(format!("run #{}", counter))
+    }
+
+    #[test]
+    fn test_interval_job() {
+        let clock = FixedClock::new(Utc::now());
+        let mut scheduler = JobScheduler::new();
+        scheduler.register("count", Schedule::Every(Duration::minutes(5)), counting_job);
+
+        let mut counter = 0;
+
+        // First call runs immediately
+        assert_eq!(scheduler.run_pending(&mut counter, &clock).len(), 1);
+
+        // Not due yet
+        clock.advance(Duration::minutes(3));
+        assert!(scheduler.run_pending(&mut counter, &clock).is_empty());
+
+        // Due again
+        clock.advance(Duration::minutes(3));
+        assert_eq!(scheduler.run_pending(&mut counter, &clock).len(), 1);
+        assert_eq!(counter, 2);
+    }
+
+    #[test]
+    fn test_daily_job() {
+        let start = Utc::now().date_naive().and_hms_opt(8, 0, 0).unwrap().and_utc();
+        let clock = FixedClock::new(start);
+        let mut scheduler = JobScheduler::new();
+        let closing_time = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
+        scheduler.register("report", Schedule::DailyAt(closing_time), counting_job);
+
+        let mut counter = 0;
+        assert!(scheduler.run_pending(&mut counter, &clock).is_empty());
+
+        clock.advance(Duration::hours(10));
+        assert_eq!(scheduler.run_pending(&mut counter, &clock).len(), 1);
+
+        // Next run is tomorrow at 18:00
+        clock.advance(Duration::hours(12));
+        assert!(scheduler.run_pending(&mut counter, &clock).is_empty());
+        clock.advance(Duration::hours(12));
+        assert_eq!(scheduler.run_pending(&mut counter, &clock).len(), 1);
+    }
+
+    #[test]
+    fn test_failing_job_does_not_block_others() {
+        let clock = FixedClock::new(Utc::now());
+        let mut scheduler: JobScheduler<u32> = JobScheduler::new();
+        scheduler
+            .register(
+                "broken",
+                Schedule::Every(Duration::minutes(1)),
+                |_: &mut u32, _: &dyn Clock| Err(JobError::Failed("boom".to_string())),
+            )
+            .register("count", Schedule::Every(Duration::minutes(1)), counting_job);
+
+        let mut counter = 0;
+        let runs = scheduler.run_pending(&mut counter, &clock);
+
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].outcome.is_err());
+        assert!(runs[1].outcome.is_ok());
+        assert_eq!(scheduler.job_names(), vec!["broken", "count"]);
+    }
+
+    #[test]
+    fn test_singleton_job_runs_on_one_instance_per_round() {
+        let clock = Arc::new(FixedClock::new(Utc::now()));
+        let lock = MemoryLock::new().with_clock(clock.clone());
+        let mut instances: Vec<JobScheduler<u32>> = ["server-a", "server-b"]
+            .into_iter()
+            .map(|name| JobScheduler::new().with_lock(lock.clone(), name))
+            .collect();
+        for scheduler in &mut instances {
+            scheduler
+                .register_singleton("report", Schedule::Every(Duration::minutes(5)), counting_job)
+                .register("local", Schedule::Every(Duration::minutes(5)), counting_job);
+        }
+
+        let mut counters = [0, 0];
+        for (scheduler, counter) in instances.iter_mut().zip(&mut counters) {
+            scheduler.run_pending(counter, &*clock);
+        }
+        // Both ran "local", only server-a ran "report"
+        assert_eq!(counters, [2, 1]);
+        assert_eq!(lock.holder("job:report").as_deref(), Some("server-a"));
+
+        // server-b reached the next round first this time
+        clock.advance(Duration::minutes(5));
+        let runs = instances[1].run_pending(&mut counters[1], &*clock);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(instances[0].run_pending(&mut counters[0], &*clock).len(), 1);
+    }
+
+    #[test]
+    fn test_no_show_expiry_job() {
+        let clock = FixedClock::new(Utc::now());
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new());
+        let mut scheduler = JobScheduler::new();
+        scheduler.register(
+            "no-show expiry",
+            Schedule::Every(Duration::minutes(1)),
+            NoShowExpiryJob::new(NoShowPolicy::default()),
+        );
+
+        let runs = scheduler.run_pending(&mut service, &clock);
+        assert_eq!(
+            runs[0].outcome.as_ref().unwrap(),
+            "0 order(s) flagged as no-show"
+        );
+    }
+
+    #[test]
+    fn test_sla_watchdog_escalates_once() {
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new());
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Small,
+            extra_shots: 0,
+        })];
+        let order = service.place_order(customer, beverages).unwrap();
+
+        let manager = ConsoleNotifier::with_writer(Vec::new()).with_verbosity(Verbosity::Quiet);
+        let mut job = SlaWatchdogJob::new(SlaPolicy::new(5, 10), manager);
+        let clock = FixedClock::new(order.status_since() + Duration::minutes(6));
+
+        assert_eq!(job.run(&mut service, &clock).unwrap(), "1 order(s) past SLA");
+        assert_eq!(job.run(&mut service, &clock).unwrap(), "0 order(s) past SLA");
+        assert!(service.get_order(order.id).unwrap().breached_sla());
+
+        let escalations = String::from_utf8(job.manager.into_inner()).unwrap();
+        assert_eq!(escalations.matches("SLA Breach").count(), 1);
+    }
+}
@@ -0,0 +1,354 @@
+// SOLID: KioskService - the self-order kiosk's whole API
+//
+// INTERFACE SEGREGATION PRINCIPLE (ISP):
+// The tablet team needs four things: show the menu, fill a cart, pay, and
+// tell the customer their number. OrderService offers much more (submit,
+// cancel, no-shows, SLA...), none of which a kiosk should call. This facade
+// exposes only the kiosk's four steps.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// It wraps an OrderService, so storage, payment (usually CardPresentPayment)
+// and notifications are whatever the shop configured.
+//
+// HARD TO MISUSE:
+// - Items are chosen by menu ID, so a kiosk can't invent a beverage or a price
+// - `checkout` takes the cart by value: a paid cart can't be paid again
+// - There is no customer to fill in: every kiosk order is a guest order
+//
+// The screen can speak the customer's language: `with_translations` names
+// the menu and the cart in a locale (see MenuTranslations), while the order
+// itself keeps the English names reports and recipes match on.
+
+use super::order_service::{OrderService, OrderServiceError};
+use crate::domain::{
+    Beverage, Coffee, Customer, Locale, MenuTranslations, Money, OrderStatus, Size, Smoothie,
+    Tea, TeaVariety,
+};
+use crate::ports::{Notifier, OrderRepository, PaymentProcessor};
+use chrono::Duration;
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Errors a kiosk can show to the customer
+#[derive(Debug)]
+pub enum KioskError {
+    UnknownItem(String),
+    EmptyCart,
+    OrderFailed(OrderServiceError),
+}
+
+impl fmt::Display for KioskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KioskError::UnknownItem(id) => write!(f, "Not on the menu: {}", id),
+            KioskError::EmptyCart => write!(f, "The cart is empty"),
+            KioskError::OrderFailed(e) => write!(f, "Order failed: {}", e),
+        }
+    }
+}
+
+impl Error for KioskError {}
+
+/// How a menu item is made
+#[derive(Debug, Clone)]
+enum Recipe {
+    Coffee { extra_shots: u8 },
+    Tea { variety: TeaVariety },
+    Smoothie { fruits: &'static [&'static str] },
+}
+
+impl Recipe {
+    fn make(&self, size: Size) -> Box<dyn Beverage> {
+        match self {
+            Recipe::Coffee { extra_shots } => Box::new(Coffee {
+                size,
+                extra_shots: *extra_shots,
+            }),
+            Recipe::Tea { variety } => Box::new(Tea::new(variety.clone(), size)),
+            Recipe::Smoothie { fruits } => Box::new(Smoothie {
+                size,
+                fruits: fruits.iter().map(|f| f.to_string()).collect(),
+            }),
+        }
+    }
+}
+
+/// One button on the kiosk screen
+#[derive(Debug, Clone)]
+pub struct MenuItem {
+    pub id: &'static str,
+    /// In the kiosk's locale
+    pub name: String,
+    /// A line about the item, when its translation has one
+    pub description: Option<String>,
+    recipe: Recipe,
+}
+
+impl MenuItem {
+    fn new(id: &'static str, recipe: Recipe) -> Self {
+        let name = recipe.make(Size::Medium).name();
+        Self {
+            id,
+            name,
+            description: None,
+            recipe,
+        }
+    }
+
+    fn localize(&mut self, translations: &MenuTranslations, locale: &Locale) {
+        let english = self.recipe.make(Size::Medium).name();
+        self.name = translations.name(&english, locale);
+        self.description = translations.description(&english, locale).map(str::to_string);
+    }
+
+    /// Price in each size, for display
+    pub fn price(&self, size: Size) -> Money {
+        self.recipe.make(size).price()
+    }
+
+    /// The beverage made in `size`, for front ends ordering by item (the shell)
+    pub fn beverage(&self, size: Size) -> Box<dyn Beverage> {
+        self.recipe.make(size)
+    }
+}
+
+/// The kiosk menu (a fixed subset of what the barista can make)
+fn menu() -> Vec<MenuItem> {
+    vec![
+        MenuItem::new("coffee", Recipe::Coffee { extra_shots: 0 }),
+        MenuItem::new("double-coffee", Recipe::Coffee { extra_shots: 1 }),
+        MenuItem::new("green-tea", Recipe::Tea { variety: TeaVariety::Green }),
+        MenuItem::new("black-tea", Recipe::Tea { variety: TeaVariety::Black }),
+        MenuItem::new("berry-smoothie", Recipe::Smoothie {
+            fruits: &["Strawberry", "Blueberry"],
+        }),
+        MenuItem::new("tropical-smoothie", Recipe::Smoothie {
+            fruits: &["Mango", "Pineapple", "Banana"],
+        }),
+    ]
+}
+
+/// A line in the cart
+#[derive(Debug, Clone, PartialEq)]
+pub struct CartLine {
+    pub item_id: &'static str,
+    pub size: Size,
+    pub description: String,
+    pub price: Money,
+}
+
+/// What the customer has picked so far
+///
+/// Only KioskService can add to it, and only from the menu.
+#[derive(Debug, Default)]
+pub struct Cart {
+    lines: Vec<CartLine>,
+}
+
+impl Cart {
+    pub fn lines(&self) -> &[CartLine] {
+        &self.lines
+    }
+
+    pub fn total(&self) -> Money {
+        self.lines.iter().map(|line| line.price).sum()
+    }
+
+    /// Remove a line (the "x" next to it); out of range does nothing
+    pub fn remove(&mut self, index: usize) {
+        if index < self.lines.len() {
+            self.lines.remove(index);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+/// What the kiosk shows after payment
+#[derive(Debug, Clone, PartialEq)]
+pub struct KioskTicket {
+    /// Called out at the counter (1-999, then starts over)
+    pub order_number: u32,
+    pub order_id: Uuid,
+    pub total: Money,
+    /// Rough time until ready, from the drinks' prep times
+    pub ready_in: Duration,
+}
+
+/// Self-order kiosk facade over OrderService
+pub struct KioskService<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    orders: OrderService<R, P, N>,
+    menu: Vec<MenuItem>,
+    translations: MenuTranslations,
+    locale: Locale,
+    last_number: u32,
+}
+
+impl<R, P, N> KioskService<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    pub fn new(orders: OrderService<R, P, N>) -> Self {
+        Self {
+            orders,
+            menu: menu(),
+            translations: MenuTranslations::new(),
+            locale: Locale::default(),
+            last_number: 0,
+        }
+    }
+
+    /// Show the menu and the cart in `locale`, falling back to English
+    pub fn with_translations(mut self, translations: MenuTranslations, locale: Locale) -> Self {
+        for item in &mut self.menu {
+            item.localize(&translations, &locale);
+        }
+        self.translations = translations;
+        self.locale = locale;
+        self
+    }
+
+    /// Step 1: what can be ordered
+    pub fn menu(&self) -> &[MenuItem] {
+        &self.menu
+    }
+
+    /// Step 2: start a cart...
+    pub fn new_cart(&self) -> Cart {
+        Cart::default()
+    }
+
+    /// ...and add menu items to it
+    pub fn add_to_cart(&self, cart: &mut Cart, item_id: &str, size: Size) -> Result<(), KioskError> {
+        let item = self
+            .menu
+            .iter()
+            .find(|item| item.id == item_id)
+            .ok_or_else(|| KioskError::UnknownItem(item_id.to_string()))?;
+        let beverage = item.recipe.make(size);
+
+        cart.lines.push(CartLine {
+            item_id: item.id,
+            size,
+            description: self.translations.describe(
+                &beverage.name(),
+                &beverage.description(),
+                &self.locale,
+            ),
+            price: beverage.price(),
+        });
+        Ok(())
+    }
+
+    /// Step 3: pay, and get the number to listen for
+    pub fn checkout(&mut self, cart: Cart) -> Result<KioskTicket, KioskError> {
+        if cart.is_empty() {
+            return Err(KioskError::EmptyCart);
+        }
+
+        let beverages: Vec<Box<dyn Beverage>> = cart
+            .lines
+            .iter()
+            .map(|line| {
+                let item = self.menu.iter().find(|item| item.id == line.item_id);
+                // Lines can only come from add_to_cart, so the item exists
+                item.expect("cart line from the menu").recipe.make(line.size)
+            })
+            .collect();
+
+        let order = self
+            .orders
+            .place_order(Customer::guest(), beverages)
+            .map_err(KioskError::OrderFailed)?;
+
+        self.last_number = self.last_number % 999 + 1;
+        Ok(KioskTicket {
+            order_number: self.last_number,
+            order_id: order.id,
+            total: order.amount_charged(),
+            ready_in: order.prep_time_estimate(),
+        })
+    }
+
+    /// Step 4: the status screen ("Preparing", "Ready")
+    pub fn status(&self, ticket: &KioskTicket) -> Result<OrderStatus, KioskError> {
+        self.orders
+            .get_order(ticket.order_id)
+            .map(|order| order.status)
+            .map_err(KioskError::OrderFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CardPresentPayment, ConsoleNotifier, MemoryOrderRepository, Verbosity};
+
+    type TestKiosk = KioskService<MemoryOrderRepository, CardPresentPayment, ConsoleNotifier<Vec<u8>>>;
+
+    fn kiosk() -> TestKiosk {
+        let notifier = ConsoleNotifier::with_writer(Vec::new()).with_verbosity(Verbosity::Quiet);
+        KioskService::new(OrderService::new(
+            MemoryOrderRepository::new(),
+            CardPresentPayment::new("KIOSK-1"),
+            notifier,
+        ))
+    }
+
+    #[test]
+    fn test_menu_to_order_number() {
+        let mut kiosk = kiosk();
+        let mut cart = kiosk.new_cart();
+        kiosk.add_to_cart(&mut cart, "coffee", Size::Medium).unwrap();
+        kiosk.add_to_cart(&mut cart, "green-tea", Size::Small).unwrap();
+        let cart_total = cart.total();
+
+        let first = kiosk.checkout(cart).unwrap();
+        let mut again = kiosk.new_cart();
+        kiosk.add_to_cart(&mut again, "coffee", Size::Large).unwrap();
+        let second = kiosk.checkout(again).unwrap();
+
+        assert_eq!(first.total, cart_total);
+        assert_eq!((first.order_number, second.order_number), (1, 2));
+        assert!(first.ready_in > Duration::zero());
+        assert_eq!(kiosk.status(&first).unwrap(), OrderStatus::Paid);
+    }
+
+    #[test]
+    fn test_rejects_unknown_items_and_empty_carts() {
+        let mut kiosk = kiosk();
+        let mut cart = kiosk.new_cart();
+
+        let unknown = kiosk.add_to_cart(&mut cart, "unicorn-latte", Size::Medium);
+
+        assert!(matches!(unknown, Err(KioskError::UnknownItem(_))));
+        assert!(matches!(kiosk.checkout(cart), Err(KioskError::EmptyCart)));
+    }
+
+    #[test]
+    fn test_menu_and_cart_in_the_kiosk_locale() {
+        let translations = MenuTranslations::new()
+            .with_name("Green Tea", "de", "Grüntee")
+            .with_description("Green Tea", "de", "Sencha aus Japan")
+            .with_name("Small", "de", "Klein");
+        let kiosk = kiosk().with_translations(translations, Locale::parse("de-CH"));
+        let mut cart = kiosk.new_cart();
+        kiosk.add_to_cart(&mut cart, "green-tea", Size::Small).unwrap();
+
+        let tea = kiosk.menu().iter().find(|item| item.id == "green-tea").unwrap();
+        assert_eq!(tea.name, "Grüntee");
+        assert_eq!(tea.description.as_deref(), Some("Sencha aus Japan"));
+        assert_eq!(kiosk.menu()[0].name, "Coffee");
+        assert_eq!(cart.lines()[0].description, "Grüntee (Klein)");
+    }
+}
@@ -0,0 +1,194 @@
+// SOLID: Kitchen capacity - ready-time estimates and when to stop taking orders
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// - EtaEstimator answers "when will each ticket be ready?"
+// - CapacityPolicy answers "can we take one more order right now?"
+//
+// Both work on the KitchenDisplay's tickets and their prep times
+// (see PrepTimeModel). Neither touches storage or the order workflow:
+// the counter, a kiosk or an online ordering page asks them before
+// calling OrderService::place_order.
+//
+// The model is deliberately simple: tickets are made in board order, each
+// by the first barista who is free, and a ticket already on the board is
+// assumed not started yet (slightly pessimistic, never optimistic).
+//
+// The board only shows what has arrived. With a rate limit set
+// (`with_max_orders_per_minute`), `accepts_with` also reads LiveMetrics'
+// window: a rush still short of a long board stops being accepted early.
+
+use super::kitchen_display::KitchenTicket;
+use super::live_metrics::WindowStats;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Estimates ready times from the ticket board
+#[derive(Debug, Clone)]
+pub struct EtaEstimator {
+    baristas: usize,
+}
+
+impl EtaEstimator {
+    /// `baristas` working in parallel (at least one)
+    pub fn new(baristas: usize) -> Self {
+        Self {
+            baristas: baristas.max(1),
+        }
+    }
+
+    /// When each ticket should be ready, in board order
+    pub fn estimate(
+        &self,
+        tickets: &[KitchenTicket],
+        now: DateTime<Utc>,
+    ) -> Vec<(Uuid, DateTime<Utc>)> {
+        let mut free_at = vec![now; self.baristas];
+        tickets
+            .iter()
+            .map(|ticket| (ticket.order_id, Self::assign(&mut free_at, ticket.prep_time)))
+            .collect()
+    }
+
+    /// When a new order taking `prep_time` would be ready if placed now
+    pub fn estimate_next(
+        &self,
+        tickets: &[KitchenTicket],
+        prep_time: Duration,
+        now: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        let mut free_at = vec![now; self.baristas];
+        for ticket in tickets {
+            Self::assign(&mut free_at, ticket.prep_time);
+        }
+        Self::assign(&mut free_at, prep_time)
+    }
+
+    /// Give the work to the first free barista, return when it's done
+    fn assign(free_at: &mut [DateTime<Utc>], prep_time: Duration) -> DateTime<Utc> {
+        let barista = free_at.iter_mut().min().expect("at least one barista");
+        *barista += prep_time;
+        *barista
+    }
+}
+
+/// Stop taking orders when the wait would get too long
+///
+/// Owned by Operations, like NoShowPolicy: the limit is a number to tune,
+/// not logic to rewrite.
+#[derive(Debug, Clone)]
+pub struct CapacityPolicy {
+    max_wait: Duration,
+    max_orders_per_minute: Option<f64>,
+}
+
+impl CapacityPolicy {
+    /// Refuse orders that would be ready more than `minutes` from now
+    pub fn new(minutes: i64) -> Self {
+        Self {
+            max_wait: Duration::minutes(minutes),
+            max_orders_per_minute: None,
+        }
+    }
+
+    /// Also refuse orders while more than `rate` a minute are coming in (see `accepts_with`)
+    pub fn with_max_orders_per_minute(mut self, rate: f64) -> Self {
+        self.max_orders_per_minute = Some(rate);
+        self
+    }
+
+    pub fn max_wait(&self) -> Duration {
+        self.max_wait
+    }
+
+    /// Can an order taking `prep_time` still be accepted?
+    pub fn accepts(
+        &self,
+        estimator: &EtaEstimator,
+        tickets: &[KitchenTicket],
+        prep_time: Duration,
+        now: DateTime<Utc>,
+    ) -> bool {
+        estimator.estimate_next(tickets, prep_time, now) - now <= self.max_wait
+    }
+
+    /// `accepts`, and the recent arrival rate is within the limit (if one is set)
+    pub fn accepts_with(
+        &self,
+        estimator: &EtaEstimator,
+        tickets: &[KitchenTicket],
+        prep_time: Duration,
+        live: &WindowStats,
+    ) -> bool {
+        self.accepts(estimator, tickets, prep_time, live.at)
+            && self
+                .max_orders_per_minute
+                .is_none_or(|max| live.orders_per_minute() <= max)
+    }
+}
+
+impl Default for CapacityPolicy {
+    /// 20 minutes from order to pickup
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Money;
+
+    fn ticket(prep_minutes: i64, now: DateTime<Utc>) -> KitchenTicket {
+        KitchenTicket {
+            order_id: Uuid::new_v4(),
+            items: vec![("Coffee (Medium)".to_string(), 1)],
+            placed_at: now,
+            prep_time: Duration::minutes(prep_minutes),
+        }
+    }
+
+    #[test]
+    fn test_tickets_are_shared_between_baristas() {
+        let now = Utc::now();
+        let board = vec![ticket(4, now), ticket(2, now), ticket(3, now)];
+
+        let etas = EtaEstimator::new(2).estimate(&board, now);
+
+        assert_eq!(etas[0].1, now + Duration::minutes(4));
+        assert_eq!(etas[1].1, now + Duration::minutes(2));
+        // Second barista is free first
+        assert_eq!(etas[2].1, now + Duration::minutes(5));
+    }
+
+    #[test]
+    fn test_capacity_policy_refuses_long_waits() {
+        let now = Utc::now();
+        let board: Vec<KitchenTicket> = (0..6).map(|_| ticket(3, now)).collect();
+        let policy = CapacityPolicy::new(20);
+
+        // 18 minutes of work ahead for a single barista
+        assert!(policy.accepts(&EtaEstimator::new(1), &board, Duration::minutes(2), now));
+        assert!(!policy.accepts(&EtaEstimator::new(1), &board, Duration::minutes(3), now));
+        assert!(policy.accepts(&EtaEstimator::new(2), &board, Duration::minutes(3), now));
+    }
+
+    #[test]
+    fn test_capacity_policy_refuses_a_rush() {
+        let now = Utc::now();
+        let live = |orders| WindowStats {
+            at: now,
+            window: Duration::minutes(5),
+            orders,
+            revenue: Money::cents(0),
+            failures: 0,
+        };
+        let policy = CapacityPolicy::new(20).with_max_orders_per_minute(2.0);
+        let estimator = EtaEstimator::new(2);
+
+        // An empty board, but 15 orders in 5 minutes are on their way to it
+        assert!(policy.accepts_with(&estimator, &[], Duration::minutes(3), &live(10)));
+        assert!(!policy.accepts_with(&estimator, &[], Duration::minutes(3), &live(15)));
+        let unlimited = CapacityPolicy::new(20);
+        assert!(unlimited.accepts_with(&estimator, &[], Duration::minutes(3), &live(15)));
+    }
+}
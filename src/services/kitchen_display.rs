@@ -0,0 +1,140 @@
+// SOLID: KitchenDisplay - the barista's ticket board (KDS)
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// OrderService has no idea this exists. It publishes OrderPlaced / OrderReady
+// on the message bus, and the kitchen display subscribes to them.
+// Removing the KDS, or adding a second one, changes nothing upstream.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Owned by the baristas: it only keeps the list of drinks to make.
+//
+// Tickets are ordered by when they could be ready if started now
+// (placed_at + prep time), so a quick tea placed a few seconds after a
+// round of five smoothies isn't stuck behind all of them.
+
+use crate::bus::{MessageBus, OrderCancelled, OrderPlaced, OrderReady, Subscription};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// One order on the board
+#[derive(Debug, Clone, PartialEq)]
+pub struct KitchenTicket {
+    pub order_id: Uuid,
+    /// (beverage description, quantity) per line
+    pub items: Vec<(String, u8)>,
+    pub placed_at: DateTime<Utc>,
+    /// Estimated time to make every item
+    pub prep_time: Duration,
+}
+
+impl KitchenTicket {
+    /// Earliest the order can be ready: placed_at + prep time
+    pub fn due_at(&self) -> DateTime<Utc> {
+        self.placed_at + self.prep_time
+    }
+}
+
+/// Open tickets, earliest due first
+pub struct KitchenDisplay {
+    placed: Subscription<OrderPlaced>,
+    ready: Subscription<OrderReady>,
+    cancelled: Subscription<OrderCancelled>,
+    tickets: Vec<KitchenTicket>,
+}
+
+impl KitchenDisplay {
+    /// Subscribe to the order topics it needs
+    pub fn subscribe(bus: &MessageBus) -> Self {
+        Self {
+            placed: bus.subscribe(),
+            ready: bus.subscribe(),
+            cancelled: bus.subscribe(),
+            tickets: Vec::new(),
+        }
+    }
+
+    /// Apply every message received since the last poll
+    pub fn poll(&mut self) {
+        for placed in self.placed.drain() {
+            self.tickets.push(KitchenTicket {
+                order_id: placed.order_id,
+                items: placed.items,
+                placed_at: placed.placed_at,
+                prep_time: Duration::seconds(placed.prep_seconds as i64),
+            });
+        }
+
+        let done: Vec<Uuid> = self
+            .ready
+            .drain()
+            .into_iter()
+            .map(|ready| ready.order_id)
+            .chain(self.cancelled.drain().into_iter().map(|c| c.order_id))
+            .collect();
+        self.tickets.retain(|ticket| !done.contains(&ticket.order_id));
+
+        self.tickets.sort_by_key(|ticket| (ticket.due_at(), ticket.placed_at));
+    }
+
+    /// Tickets still to prepare
+    pub fn tickets(&self) -> &[KitchenTicket] {
+        &self.tickets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Money;
+
+    fn placed(order_id: Uuid) -> OrderPlaced {
+        OrderPlaced {
+            order_id,
+            customer_email: "test@example.com".to_string(),
+            items: vec![("Coffee (Medium)".to_string(), 1)],
+            total_price: Money::usd(3.50),
+            prep_seconds: 90,
+            placed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_tickets_follow_messages() {
+        let bus = MessageBus::new();
+        let mut kds = KitchenDisplay::subscribe(&bus);
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        bus.publish(placed(first));
+        bus.publish(placed(second));
+        kds.poll();
+        assert_eq!(kds.tickets().len(), 2);
+
+        bus.publish(OrderReady {
+            order_id: first,
+            ready_at: Utc::now(),
+        });
+        kds.poll();
+        assert_eq!(kds.tickets().len(), 1);
+        assert_eq!(kds.tickets()[0].order_id, second);
+    }
+
+    #[test]
+    fn test_quick_orders_come_first() {
+        let bus = MessageBus::new();
+        let mut kds = KitchenDisplay::subscribe(&bus);
+        let big = Uuid::new_v4();
+        let quick = Uuid::new_v4();
+
+        let mut smoothies = placed(big);
+        smoothies.prep_seconds = 600;
+        let mut tea = placed(quick);
+        tea.placed_at = smoothies.placed_at + Duration::seconds(30);
+
+        bus.publish(smoothies);
+        bus.publish(tea);
+        kds.poll();
+
+        assert_eq!(kds.tickets()[0].order_id, quick);
+    }
+}
@@ -0,0 +1,137 @@
+// SOLID: LeaderElection - one leader among redundant instances
+//
+// Several worker binaries can run side by side so that losing one loses
+// nothing: they all fulfill orders from the queue. Scheduled jobs must run
+// once though, so exactly one instance at a time, the leader, runs them.
+//
+// Election is a lease on the DistributedLock port (file, Redis...):
+// - every instance calls `tick` regularly (every `renew_every()`)
+// - whoever holds the lease renews it and stays leader
+// - the others keep trying; when the leader dies, its lease runs out and
+//   the next tick of another instance takes over
+//
+// A leader that cannot renew (lock backend unreachable, a long pause) must
+// not keep acting: `is_leader` turns false as soon as its own lease would
+// have run out, even before the next tick says so.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// The election knows the lock and clock ports, not Redis or the OS.
+
+use crate::ports::{Clock, DistributedLock, LockError};
+use chrono::{DateTime, Duration, Utc};
+
+/// What this instance is after a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Leader,
+    Follower,
+}
+
+/// Lease-based leader election for one group of instances
+pub struct LeaderElection {
+    lock: Box<dyn DistributedLock>,
+    /// Lock name shared by the group
+    lease_name: String,
+    instance: String,
+    lease: Duration,
+    /// End of our lease, while we believe we lead
+    leading_until: Option<DateTime<Utc>>,
+}
+
+impl LeaderElection {
+    /// `group` names what is being led ("worker"); `instance` names this process
+    pub fn new(lock: impl DistributedLock + 'static, group: &str, instance: &str) -> Self {
+        Self {
+            lock: Box::new(lock),
+            lease_name: format!("leader:{}", group),
+            instance: instance.to_string(),
+            lease: Duration::seconds(15),
+            leading_until: None,
+        }
+    }
+
+    /// How long a silent leader keeps the role (default 15s): the failover time
+    pub fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    /// How often to tick: a leader gets two more chances to renew before it loses the lease
+    pub fn renew_every(&self) -> Duration {
+        self.lease / 3
+    }
+
+    /// Renew or try to take the lease
+    ///
+    /// On a lock error this instance is a follower until a later tick
+    /// succeeds: better no leader for a moment than two.
+    pub fn tick(&mut self, clock: &dyn Clock) -> Result<Role, LockError> {
+        let now = clock.now();
+        match self.lock.try_acquire(&self.lease_name, &self.instance, self.lease) {
+            Ok(true) => {
+                self.leading_until = Some(now + self.lease);
+                Ok(Role::Leader)
+            }
+            Ok(false) => {
+                self.leading_until = None;
+                Ok(Role::Follower)
+            }
+            Err(e) => {
+                self.leading_until = None;
+                Err(e)
+            }
+        }
+    }
+
+    /// Leader, and our lease has not run out since the last tick
+    pub fn is_leader(&self, clock: &dyn Clock) -> bool {
+        self.leading_until.is_some_and(|until| clock.now() < until)
+    }
+
+    /// Hand the role over now (clean shutdown), instead of after the lease
+    pub fn step_down(&mut self) -> Result<(), LockError> {
+        if self.leading_until.take().is_some() {
+            self.lock.release(&self.lease_name, &self.instance)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{FixedClock, MemoryLock};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_follower_takes_over_when_the_leader_goes_silent() {
+        let clock = Arc::new(FixedClock::new(Utc::now()));
+        let lock = MemoryLock::new().with_clock(clock.clone());
+        let lease = Duration::seconds(15);
+        let mut a = LeaderElection::new(lock.clone(), "worker", "a").with_lease(lease);
+        let mut b = LeaderElection::new(lock.clone(), "worker", "b").with_lease(lease);
+
+        assert_eq!(a.tick(&*clock).unwrap(), Role::Leader);
+        assert_eq!(b.tick(&*clock).unwrap(), Role::Follower);
+
+        // a keeps renewing: it stays leader well past one lease
+        for _ in 0..5 {
+            clock.advance(a.renew_every());
+            assert_eq!(a.tick(&*clock).unwrap(), Role::Leader);
+            assert_eq!(b.tick(&*clock).unwrap(), Role::Follower);
+        }
+
+        // a stops ticking (frozen, partitioned...): b takes over after the lease
+        clock.advance(Duration::seconds(10));
+        assert_eq!(b.tick(&*clock).unwrap(), Role::Follower);
+        clock.advance(Duration::seconds(6));
+        assert!(!a.is_leader(&*clock));
+        assert_eq!(b.tick(&*clock).unwrap(), Role::Leader);
+        assert_eq!(a.tick(&*clock).unwrap(), Role::Follower);
+
+        // A clean shutdown hands over at once
+        b.step_down().unwrap();
+        assert_eq!(a.tick(&*clock).unwrap(), Role::Leader);
+        assert!(a.is_leader(&*clock) && !b.is_leader(&*clock));
+    }
+}
@@ -0,0 +1,223 @@
+// SOLID: LiveMetrics - orders, revenue and payment failures over the last few minutes
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Keeps a sliding window of recent events and turns it into rates: orders
+// per minute, revenue per minute, and the share of payments that failed.
+// ReportingService answers "how did last week go?"; this answers "how busy
+// are we right now?".
+//
+// Fed by the message bus (OrderPlaced, PaymentFailed) like KitchenDisplay,
+// or by hand with `record_sale` / `record_failure` (the `metrics` command
+// replays stored orders that way). Events drop out as they age past the
+// window, so memory is bounded by one window's traffic.
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Consumers read a WindowStats snapshot, never the events:
+// - SurgePricing::multiplier_with surges on arrivals, not only on the queue
+// - CapacityPolicy::accepts_with stops taking orders above a rate
+// - the interactive menu's header, and Prometheus gauges (render_live_prometheus)
+
+use crate::bus::{MessageBus, OrderPlaced, PaymentFailed, Subscription};
+use crate::domain::{Currency, Money};
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+
+/// What happened in the window ending at `at`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowStats {
+    pub at: DateTime<Utc>,
+    pub window: Duration,
+    /// Orders paid and placed
+    pub orders: usize,
+    /// Their totals added up, in the metrics' currency (other currencies are left out)
+    pub revenue: Money,
+    /// Payments declined or failed
+    pub failures: usize,
+}
+
+impl WindowStats {
+    pub fn orders_per_minute(&self) -> f64 {
+        self.orders as f64 / self.minutes()
+    }
+
+    pub fn revenue_per_minute(&self) -> Money {
+        self.revenue.scaled(1.0 / self.minutes())
+    }
+
+    /// Failed payments out of all payment attempts (0.0 when there were none)
+    pub fn failure_rate(&self) -> f64 {
+        let attempts = self.orders + self.failures;
+        if attempts == 0 {
+            0.0
+        } else {
+            self.failures as f64 / attempts as f64
+        }
+    }
+
+    fn minutes(&self) -> f64 {
+        (self.window.num_seconds() as f64 / 60.0).max(1.0 / 60.0)
+    }
+}
+
+impl fmt::Display for WindowStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Last {} min: {:.1} orders/min, {}/min, {:.0}% payments failed",
+            self.window.num_minutes(),
+            self.orders_per_minute(),
+            self.revenue_per_minute(),
+            self.failure_rate() * 100.0
+        )
+    }
+}
+
+/// Sliding window over recent sales and payment failures
+pub struct LiveMetrics {
+    window: Duration,
+    currency: Currency,
+    // None when fed by hand only
+    feeds: Option<(Subscription<OrderPlaced>, Subscription<PaymentFailed>)>,
+    // (placed at, total) of recent sales
+    sales: Vec<(DateTime<Utc>, Money)>,
+    failures: Vec<DateTime<Utc>>,
+}
+
+impl Default for LiveMetrics {
+    /// The last 5 minutes, fed by hand
+    fn default() -> Self {
+        Self {
+            window: Duration::minutes(5),
+            currency: Currency::USD,
+            feeds: None,
+            sales: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+}
+
+impl LiveMetrics {
+    /// Fed by hand with `record_sale` / `record_failure`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fed by every OrderPlaced and PaymentFailed published from now on
+    pub fn subscribe(bus: &MessageBus) -> Self {
+        Self {
+            feeds: Some((bus.subscribe(), bus.subscribe())),
+            ..Self::default()
+        }
+    }
+
+    /// Look back `window` instead of 5 minutes
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Count revenue in `currency` instead of US dollars
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn record_sale(&mut self, at: DateTime<Utc>, total: Money) {
+        self.sales.push((at, total));
+    }
+
+    pub fn record_failure(&mut self, at: DateTime<Utc>) {
+        self.failures.push(at);
+    }
+
+    /// Record every message received since the last poll
+    pub fn poll(&mut self) {
+        let Some((placed, failed)) = &self.feeds else {
+            return;
+        };
+        let sales = placed.drain().into_iter().map(|m| (m.placed_at, m.total_price));
+        self.sales.extend(sales);
+        self.failures.extend(failed.drain().into_iter().map(|m| m.failed_at));
+    }
+
+    /// The window ending at `now`; older events are forgotten
+    pub fn stats(&mut self, now: DateTime<Utc>) -> WindowStats {
+        let start = now - self.window;
+        self.sales.retain(|(at, _)| *at > start);
+        self.failures.retain(|at| *at > start);
+
+        let recent: Vec<Money> = self
+            .sales
+            .iter()
+            .filter(|(at, _)| *at <= now)
+            .map(|(_, total)| *total)
+            .collect();
+        WindowStats {
+            at: now,
+            window: self.window,
+            orders: recent.len(),
+            revenue: recent
+                .iter()
+                .filter(|total| total.currency == self.currency)
+                .fold(Money::zero(self.currency), |sum, total| sum + *total),
+            failures: self.failures.iter().filter(|at| **at <= now).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{ConsoleNotifier, CreditCardPayment, MemoryOrderRepository};
+    use crate::domain::{Coffee, Customer, OrderLine};
+    use crate::services::OrderService;
+
+    #[test]
+    fn test_rates_over_a_sliding_window() {
+        let now: DateTime<Utc> = "2026-10-15T09:00:00Z".parse().unwrap();
+        let ago = |minutes| now - Duration::minutes(minutes);
+        let mut live = LiveMetrics::new();
+        live.record_sale(ago(1), Money::usd(4.00));
+        live.record_sale(ago(4), Money::usd(6.00));
+        live.record_sale(ago(12), Money::usd(100.00));
+        live.record_failure(ago(2));
+
+        let stats = live.stats(now);
+        assert_eq!((stats.orders, stats.failures), (2, 1));
+        assert!((stats.orders_per_minute() - 0.4).abs() < 1e-9);
+        assert_eq!(stats.revenue_per_minute(), Money::usd(2.0));
+        assert!((stats.failure_rate() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            stats.to_string(),
+            "Last 5 min: 0.4 orders/min, 2.00 USD/min, 33% payments failed"
+        );
+
+        // Five minutes later everything has aged out
+        let quiet = live.stats(now + Duration::minutes(5));
+        assert_eq!((quiet.orders, quiet.failures, quiet.failure_rate()), (0, 0, 0.0));
+        assert!(live.sales.is_empty());
+    }
+
+    #[test]
+    fn test_fed_by_the_bus() {
+        let bus = MessageBus::new();
+        let mut live = LiveMetrics::subscribe(&bus).with_window(Duration::minutes(1));
+        let mut service = OrderService::new(
+            MemoryOrderRepository::new(),
+            CreditCardPayment::new("https://gw.example".to_string()),
+            ConsoleNotifier::new(),
+        )
+        .with_message_bus(bus);
+
+        service.place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())]).unwrap();
+        // 400 coffees are over the gateway's card limit: declined
+        let catering = (0..2).map(|_| OrderLine::new(Box::new(Coffee::medium()), 200)).collect();
+        assert!(service.place_order_lines(Customer::named("Grace"), catering).is_err());
+
+        live.poll();
+        let stats = live.stats(Utc::now());
+        assert_eq!((stats.orders, stats.failures), (1, 1));
+        assert_eq!(stats.revenue, Money::usd(3.50));
+        assert_eq!(stats.failure_rate(), 0.5);
+    }
+}
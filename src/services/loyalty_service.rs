@@ -0,0 +1,176 @@
+// SOLID: LoyaltyService - points for every paid order, discounts for regulars
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The loyalty rules and nothing else: how many points an order earns
+// (10 per dollar charged by default) and which tier a customer has reached
+// by counting their paid orders (Silver from 10 orders, 5% off; Gold from
+// 25, 10% off). PricingCalculator's every-10th-order rule stays a plain
+// calculation; this is the program that remembers who ordered what.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Accounts live behind the LoyaltyRepository port. OrderService only sees
+// this service (`with_loyalty`): it asks for the customer's tier when
+// pricing an order, and has each paid order accrue its points.
+//
+// Refunds take back what they had earned (`reverse`), and a refund in full
+// the order's place in the tier count too.
+//
+// Spending points is a payment: PointsPayment wraps the register's
+// processor and reads the same accounts (share them with a
+// SharedLoyaltyRepository).
+
+use crate::domain::{LoyaltyAccount, Money, Order};
+use crate::ports::{LoyaltyError, LoyaltyRepository};
+
+/// A level of the program, reached with `min_orders` paid orders
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoyaltyTier {
+    pub name: String,
+    pub min_orders: u32,
+    /// Taken off every beverage at checkout
+    pub discount_percent: f64,
+}
+
+impl LoyaltyTier {
+    pub fn new(name: &str, min_orders: u32, discount_percent: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            min_orders,
+            discount_percent: discount_percent.clamp(0.0, 100.0),
+        }
+    }
+}
+
+/// Accrues points and finds each customer's tier
+pub struct LoyaltyService {
+    accounts: Box<dyn LoyaltyRepository>,
+    points_per_dollar: u64,
+    // Sorted by min_orders
+    tiers: Vec<LoyaltyTier>,
+}
+
+impl LoyaltyService {
+    /// 10 points per dollar; Silver (10 orders, 5% off) and Gold (25 orders, 10% off)
+    pub fn new(accounts: impl LoyaltyRepository + 'static) -> Self {
+        Self {
+            accounts: Box::new(accounts),
+            points_per_dollar: 10,
+            tiers: vec![LoyaltyTier::new("Silver", 10, 5.0), LoyaltyTier::new("Gold", 25, 10.0)],
+        }
+    }
+
+    pub fn with_points_per_dollar(mut self, points: u64) -> Self {
+        self.points_per_dollar = points;
+        self
+    }
+
+    /// Replace the default tiers
+    pub fn with_tiers(mut self, mut tiers: Vec<LoyaltyTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.min_orders);
+        self.tiers = tiers;
+        self
+    }
+
+    /// The customer's account (empty if they never earned a point)
+    pub fn account(&self, customer_email: &str) -> Result<LoyaltyAccount, LoyaltyError> {
+        Ok(self
+            .accounts
+            .find(customer_email)?
+            .unwrap_or_else(|| LoyaltyAccount::new(customer_email)))
+    }
+
+    /// The highest tier the customer reached, None below the first one
+    pub fn tier(&self, customer_email: &str) -> Result<Option<&LoyaltyTier>, LoyaltyError> {
+        let orders = self.account(customer_email)?.orders;
+        Ok(self.tiers.iter().rev().find(|tier| orders >= tier.min_orders))
+    }
+
+    /// Points a paid order earns: `points_per_dollar` for each whole dollar charged,
+    /// not counting what loyalty points paid
+    pub fn points_for(&self, order: &Order) -> u64 {
+        self.points_on(order.less_paid_with_points(order.amount_charged()))
+    }
+
+    fn points_on(&self, amount: Money) -> u64 {
+        (amount.cents.max(0) as u64 / 100) * self.points_per_dollar
+    }
+
+    /// Count a paid order and credit its points; returns the updated account
+    pub fn accrue(&mut self, order: &Order) -> Result<LoyaltyAccount, LoyaltyError> {
+        let mut account = self.account(&order.customer.email)?;
+        account.earn(self.points_for(order));
+        self.accounts.save(account.clone())?;
+        Ok(account)
+    }
+
+    /// Take back the points `refunded` had earned, once it is on the order's
+    /// `refunded_total`; returns the updated account
+    ///
+    /// The points are those the order earns on what it keeps now, less those
+    /// it earned before this refund. A refund in full also stops counting the
+    /// order towards the customer's tier.
+    pub fn reverse(
+        &mut self,
+        order: &Order,
+        refunded: Money,
+    ) -> Result<LoyaltyAccount, LoyaltyError> {
+        let kept = order.left_to_refund();
+        let earned = |left: Money| self.points_on(order.less_paid_with_points(left));
+        let points = earned(kept + refunded) - earned(kept);
+        let mut account = self.account(&order.customer.email)?;
+        account.reverse(points, kept.is_zero());
+        self.accounts.save(account.clone())?;
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryLoyaltyRepository;
+    use crate::domain::{Coffee, Customer, OrderItem};
+
+    #[test]
+    fn test_accrual_and_tiers() {
+        let tiers = vec![LoyaltyTier::new("Gold", 3, 10.0), LoyaltyTier::new("Silver", 2, 5.0)];
+        let mut loyalty = LoyaltyService::new(MemoryLoyaltyRepository::new()).with_tiers(tiers);
+        let large_order = || {
+            let coffees = OrderItem {
+                quantity: 2,
+                ..OrderItem::of(&Coffee::medium())
+            };
+            Order::new(Customer::named("Ada"), vec![coffees])
+        };
+
+        // $7.00: 70 points
+        assert_eq!(loyalty.accrue(&large_order()).unwrap().points, 70);
+        assert_eq!(loyalty.tier("ada@example.com").unwrap(), None);
+        loyalty.accrue(&large_order()).unwrap();
+        assert_eq!(loyalty.tier("ADA@example.com").unwrap().unwrap().name, "Silver");
+        let account = loyalty.accrue(&large_order()).unwrap();
+        assert_eq!((account.orders, account.points), (3, 210));
+        assert_eq!(loyalty.tier("ada@example.com").unwrap().unwrap().discount_percent, 10.0);
+    }
+
+    #[test]
+    fn test_refunds_take_back_points_and_the_order() {
+        let mut loyalty = LoyaltyService::new(MemoryLoyaltyRepository::new());
+        let coffees = OrderItem {
+            quantity: 2,
+            ..OrderItem::of(&Coffee::medium())
+        };
+        let mut order = Order::new(Customer::named("Ada"), vec![coffees]);
+        loyalty.accrue(&Order::single(Customer::named("Ada"), Coffee::medium())).unwrap();
+        loyalty.accrue(&order).unwrap();
+
+        // $7.00 earned 70 points; refunding $2.50 leaves $4.50, worth 40
+        order.record_refund(Money::usd(2.5));
+        let account = loyalty.reverse(&order, Money::usd(2.5)).unwrap();
+        assert_eq!((account.orders, account.points, account.lifetime_points), (2, 70, 70));
+
+        // The rest: the order no longer counts
+        order.record_refund(Money::usd(4.5));
+        let account = loyalty.reverse(&order, Money::usd(4.5)).unwrap();
+        assert_eq!((account.orders, account.points, account.lifetime_points), (1, 30, 30));
+    }
+}
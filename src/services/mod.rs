@@ -8,11 +8,53 @@
 // 1. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
 //    Each service has ONE responsibility:
 //    - OrderService: manage order workflow
+//    - SharedOrderService: one OrderService used from many threads (an HTTP server's)
+//    - OrderIntake: queue submitted orders, refuse them (429) when workers fall behind
+//    - KioskService: the self-order kiosk's small API (menu, cart, pay, number)
 //    - PricingCalculator: calculate prices
+//    - SurgePricing: optional peak-hour surcharge (feature `surge-pricing`)
+//    - PriceBooks: effective-dated menu prices, and re-pricing past orders for audits
+//    - RoundingPolicy: round cash totals to the coins a country still uses
+//    - PaymentRules: surcharges and minimums per payment method (2% on cards from 5.00)
+//    - VatRates: the VAT rate included in each product's price, for receipts
+//    - PromotionEngine: apply discounts, for everyone or per experiment variant
+//    - NoShowPolicy: decide when a ready order was abandoned
+//    - SlaPolicy: decide when a paid or preparing order is stuck
+//    - JobScheduler: decide when recurring jobs run
+//    - LeaderElection: pick the one instance that runs them when several do
+//    - CustomerHistoryCache: remember recent customer order histories
+//    - KitchenDisplay: keep the barista's ticket board (fed by the message bus)
+//    - PickupBoard: keep the customers' preparing/ready screen (same bus)
+//    - PrepTimeModel: estimate how long each drink takes to make
+//    - EtaEstimator / CapacityPolicy: predict ready times, decide when to stop taking orders
+//    - LiveMetrics: orders/min, revenue/min and payment failure rate over the last minutes
+//    - AlertEngine: alert rules on those numbers, fired and resolved on the ops channel
+//    - RepositoryAuditor: check stored orders for broken invariants
+//    - RepositoryMigrator: copy orders between storage backends
+//    - OrderImporter: load historical or catered orders from CSV/JSON files
+//    - OrderQueryService: the order book as it stood at a past moment
+//    - OrderSearchService: find orders by customer or drink, indexed when the backend can
+//    - DataAnonymizer: export orders with personal data replaced
+//    - RefundService: refunds, with manager approval above a limit
+//    - DisputeService: gateway chargebacks, freezing the customer's credit and coupons
+//    - IssueService: complaints about an order, resolved by a remake, refund or credit
+//    - AuthorizationPolicy: decide which staff role may do what
+//    - DayCloseService: run the end-of-day close (settle, flush, archive, report)
+//    - ReportingService: compute sales reports (and margins, with a CostModel)
+//    - CostModel: ingredient costs and recipes (cost of goods)
+//    - InventoryService: ingredient stock, checked and taken as orders are placed
+//    - LoyaltyService: points for paid orders, tiered discounts at checkout
+//    - DailyDigestJob: email the day's report to the owner, once per recipient
+//    - CampaignService: mail an announcement to many customers, paced and resumable
+//    - DeferredNotificationJob: send the notices quiet hours held back, once the window opens
+//    - FeedbackService: ask for a rating after pickup, add the answers up (NPS)
+//    - RewardService: birthday and anniversary coupons, granted and redeemed
+//    - ExperimentReport: compare conversion and ticket size across variants
+//    - Accounting: book sales (and store credit, a liability) to the shop's chart of accounts
+//    - WebhookDispatcher: deliver order events to subscribed URLs
 //    
 //    If we added more services:
-//    - InventoryService: manage beverage inventory
-//    - ReportingService: generate reports
+//    - PurchasingService: reorder the ingredients that run low
 //    
 //    Each would have its own file and single responsibility.
 // 
@@ -20,9 +62,117 @@
 //    Services depend on ports (traits), not on adapters (implementations).
 //    This allows us to swap implementations without changing business logic.
 
+pub mod accounting;
+pub mod alerting;
+pub mod authorization;
+pub mod campaign_service;
+pub mod cash_rounding;
+pub mod cost_model;
+pub mod customer_history_cache;
+pub mod daily_digest;
+pub mod data_anonymizer;
+pub mod deferred_notifications;
+pub mod day_close;
+pub mod dispute_service;
+pub mod experiment_report;
+pub mod feedback_service;
+pub mod inventory_service;
+pub mod issue_service;
+pub mod job_scheduler;
+pub mod kiosk_service;
+pub mod kitchen_capacity;
+pub mod kitchen_display;
+pub mod leader_election;
+pub mod live_metrics;
+pub mod loyalty_service;
+pub mod no_show;
+pub mod order_importer;
+pub mod order_intake;
+pub mod order_query;
+pub mod order_search;
 pub mod order_service;
+pub mod payment_rules;
+pub mod pickup_board;
+pub mod prep_time;
+pub mod price_book;
+pub mod promotion_engine;
 pub mod pricing_calculator;
+pub mod refund_service;
+pub mod reporting_service;
+pub mod repository_auditor;
+pub mod repository_migrator;
+pub mod reward_service;
+pub mod shared_order_service;
+pub mod sla_watchdog;
+#[cfg(feature = "surge-pricing")]
+pub mod surge_pricing;
+pub mod vat;
+pub mod webhook_dispatcher;
 
 // Re-export for convenience
-pub use order_service::{OrderService, OrderServiceError};
+pub use accounting::{
+    apply_store_credit, credit_liability, journal_entries, store_credit_entries, AccountMapping,
+    CreditLiability, JournalEntry, Posting,
+};
+pub use alerting::{AlertEngine, AlertMetric, AlertRule, AlertSample};
+pub use authorization::{AuthorizationPolicy, Permission};
+pub use campaign_service::{
+    CampaignDelivery, CampaignProgress, CampaignRenderer, CampaignService,
+};
+pub use cash_rounding::RoundingPolicy;
+pub use cost_model::CostModel;
+pub use customer_history_cache::{CacheStats, CustomerHistoryCache};
+pub use daily_digest::{DailyDigestJob, DigestDelivery, DigestRenderer};
+pub use data_anonymizer::DataAnonymizer;
+pub use deferred_notifications::{DeferredNotificationJob, ReleaseReport};
+pub use day_close::{
+    DayCloseBundle, DayCloseError, DayCloseService, DayCloseSummary, RegisterReconciliation,
+};
+pub use dispute_service::{DisputeError, DisputeLine, DisputeReport, DisputeService, RegisteredDispute};
+pub use experiment_report::{ExperimentReport, ExposureLog, VariantResult};
+pub use feedback_service::{
+    FeedbackReport, FeedbackService, FeedbackServiceError, MAX_COMMENT_CHARS,
+};
+pub use inventory_service::{InventoryService, StockError};
+pub use issue_service::{IssueError, IssueService};
+pub use job_scheduler::{
+    Job, JobError, JobRun, JobScheduler, NoShowExpiryJob, NoShowReportJob, Schedule,
+    SlaWatchdogJob,
+};
+pub use kiosk_service::{Cart, CartLine, KioskError, KioskService, KioskTicket, MenuItem};
+pub use kitchen_capacity::{CapacityPolicy, EtaEstimator};
+pub use kitchen_display::{KitchenDisplay, KitchenTicket};
+pub use leader_election::{LeaderElection, Role};
+pub use live_metrics::{LiveMetrics, WindowStats};
+pub use loyalty_service::{LoyaltyService, LoyaltyTier};
+pub use no_show::{NoShowEntry, NoShowPolicy, NoShowReport};
+pub use order_importer::{
+    ImportError, ImportFormat, ImportItem, ImportRecord, ImportReport, ImportRow, OrderImporter,
+    RowError,
+};
+pub use order_intake::{IntakeError, OrderIntake};
+pub use order_query::{OrderBook, OrderQueryService};
+pub use order_search::OrderSearchService;
+pub use order_service::{OrderService, OrderServiceError, UnpaidOrders};
+pub use payment_rules::{MethodRule, PaymentRuleError, PaymentRules};
+pub use pickup_board::{board_from_orders, BoardChange, BoardStatus, PickupBoard};
+pub use prep_time::PrepTimeModel;
+pub use price_book::{BookPriced, PriceBook, PriceBookError, PriceBooks, RepricedLine, Repricing};
+pub use promotion_engine::{PercentOff, Promoted, Promotion, PromotionEngine};
 pub use pricing_calculator::PricingCalculator;
+pub use refund_service::{RefundError, RefundPolicy, RefundService};
+pub use reporting_service::{
+    DailyReport, DateRange, DayMargin, DayTotal, LatencyReport, LatencySummary, MarginLine,
+    MixShare, ProfitabilityReport, ReportingService, WeeklyReport,
+};
+pub use repository_auditor::{
+    AuditFinding, AuditReport, AuditRule, AuditorConfig, RepairAction, RepositoryAuditor,
+};
+pub use repository_migrator::{MigrationReport, RepositoryMigrator};
+pub use reward_service::{RewardError, RewardPolicy, RewardRun, RewardService};
+pub use shared_order_service::SharedOrderService;
+pub use sla_watchdog::SlaPolicy;
+#[cfg(feature = "surge-pricing")]
+pub use surge_pricing::{SurgePriced, SurgePricing};
+pub use vat::VatRates;
+pub use webhook_dispatcher::{sign_payload, WebhookDelivery, WebhookDispatcher, WebhookEvent, WebhookRelay};
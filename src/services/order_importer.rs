@@ -0,0 +1,496 @@
+// SOLID: OrderImporter - load historical or catered orders from a file
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// It turns rows into valid Orders and hands them to a repository in one
+// `save_batch`. Nothing is charged and nobody is notified: these orders were
+// paid (or invoiced) elsewhere, OrderService's workflow doesn't apply.
+//
+// Every row is checked on its own. A bad row is reported with its number and
+// the reason, the good rows are still imported.
+//
+// Two formats, same fields:
+//
+// ```text
+// JSON: [{"created_at": "...", "customer_name": "...", "customer_email": "...",
+//         "items": [{"name": "Coffee", "price": 3.5, "quantity": 2}]}, ...]
+// CSV:  created_at,customer_name,customer_email,item,price,quantity,status
+//       2026-03-02T08:15:00Z,Ada,ada@example.com,Coffee,3.50,2,Completed
+// ```
+//
+// A CSV row is an order with one line. Optional fields (both formats): `id`,
+// `customer_phone`, `quantity` (default 1), `description`, `status` (default
+// Completed), `payment_id` (default `IMPORT-...` for paid statuses), `total`,
+// `currency` (an ISO code the prices and total are in, default USD).
+//
+// The total is always computed from the items (LinePricing). A `total` in
+// the file is only checked against it (Order::expected_total): a row whose
+// total drifted from its items (a hand-edited price, say) is rejected.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// The target is any OrderRepository, "now" comes from a Clock.
+
+use crate::domain::{
+    Currency, Customer, LinePricing, Money, Order, OrderItem, OrderStatus, StatusChange,
+};
+use crate::ports::{Clock, OrderRepository, RepositoryError};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Errors that stop the whole import (rejected rows don't, they're reported)
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file isn't valid JSON, or the CSV header is missing a column
+    Unreadable(String),
+    StorageFailed(RepositoryError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Unreadable(msg) => write!(f, "Unreadable import file: {}", msg),
+            ImportError::StorageFailed(e) => write!(f, "Import failed: {}", e),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+/// How the import file is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+impl ImportFormat {
+    /// From the file extension (`.csv`, `.json`)
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(ImportFormat::Csv),
+            "json" => Some(ImportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// One order as written in the import file, before validation
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ImportRecord {
+    #[serde(default)]
+    pub id: Option<Uuid>,
+    /// RFC 3339, `YYYY-MM-DD HH:MM[:SS]` or `YYYY-MM-DD` (UTC)
+    pub created_at: String,
+    pub customer_name: String,
+    pub customer_email: String,
+    #[serde(default)]
+    pub customer_phone: Option<String>,
+    pub items: Vec<ImportItem>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub payment_id: Option<String>,
+    /// What the file says the order came to; checked against the items
+    #[serde(default)]
+    pub total: Option<f64>,
+    /// ISO code of the prices and total ("EUR"); USD when not given
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ImportItem {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub price: f64,
+    #[serde(default)]
+    pub quantity: Option<u32>,
+}
+
+/// A parsed row: its number (see RowError), and the record or why it couldn't be read
+pub type ImportRow = (usize, Result<ImportRecord, String>);
+
+/// A rejected row: its number in the file, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    /// CSV: line number (the header is line 1). JSON: position in the array, from 1.
+    pub row: usize,
+    pub message: String,
+}
+
+/// What an import did
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub rejected: Vec<RowError>,
+}
+
+/// Validates import rows and inserts the good ones
+#[derive(Debug, Clone, Default)]
+pub struct OrderImporter {
+    dry_run: bool,
+}
+
+impl OrderImporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate and report, but write nothing
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Parse `text` and import its valid rows into `repository`
+    pub fn import_str<R: OrderRepository>(
+        &self,
+        format: ImportFormat,
+        text: &str,
+        repository: &mut R,
+        clock: &dyn Clock,
+    ) -> Result<ImportReport, ImportError> {
+        let rows = match format {
+            ImportFormat::Csv => parse_csv(text)?,
+            ImportFormat::Json => parse_json(text)?,
+        };
+        self.import(rows, repository, clock)
+    }
+
+    /// Import already-parsed rows (`Err` rows are reported as they are)
+    pub fn import<R: OrderRepository>(
+        &self,
+        rows: Vec<ImportRow>,
+        repository: &mut R,
+        clock: &dyn Clock,
+    ) -> Result<ImportReport, ImportError> {
+        let now = clock.now();
+        let mut seen = HashSet::new();
+        let mut orders = Vec::new();
+        let mut rejected = Vec::new();
+
+        for (row, record) in rows {
+            let checked = record.and_then(|record| to_order(record, now));
+            let checked = checked.and_then(|order| {
+                let stored = repository
+                    .find_by_id(order.id)
+                    .map_err(|e| format!("could not check the id: {}", e))?;
+                if stored.is_some() || !seen.insert(order.id) {
+                    Err(format!("order {} already exists", order.id))
+                } else {
+                    Ok(order)
+                }
+            });
+            match checked {
+                Ok(order) => orders.push(order),
+                Err(message) => rejected.push(RowError { row, message }),
+            }
+        }
+
+        if !self.dry_run && !orders.is_empty() {
+            repository.save_batch(&orders).map_err(ImportError::StorageFailed)?;
+        }
+        Ok(ImportReport {
+            imported: orders.len(),
+            rejected,
+        })
+    }
+}
+
+/// One JSON array of ImportRecord
+pub fn parse_json(text: &str) -> Result<Vec<ImportRow>, ImportError> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_str(text).map_err(|e| ImportError::Unreadable(e.to_string()))?;
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let record = serde_json::from_value(value).map_err(|e| e.to_string());
+            (index + 1, record)
+        })
+        .collect())
+}
+
+/// A header line, then one order per line (quoted fields may hold commas, not newlines)
+pub fn parse_csv(text: &str) -> Result<Vec<ImportRow>, ImportError> {
+    let mut lines = text.lines().enumerate();
+    let header = lines
+        .next()
+        .map(|(_, line)| split_csv_line(line))
+        .ok_or_else(|| ImportError::Unreadable("the file is empty".to_string()))?;
+    let columns: HashMap<String, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.trim().to_ascii_lowercase(), index))
+        .collect();
+    for required in ["created_at", "customer_name", "customer_email", "item", "price"] {
+        if !columns.contains_key(required) {
+            return Err(ImportError::Unreadable(format!("missing CSV column '{}'", required)));
+        }
+    }
+
+    Ok(lines
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, csv_record(&columns, &split_csv_line(line))))
+        .collect())
+}
+
+fn csv_record(columns: &HashMap<String, usize>, fields: &[String]) -> Result<ImportRecord, String> {
+    // Missing trailing fields and empty ones both mean "not given"
+    let field = |name: &str| {
+        columns
+            .get(name)
+            .and_then(|&index| fields.get(index))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let required = |name: &str| field(name).ok_or_else(|| format!("{} is missing", name));
+
+    let price = required("price")?;
+    let price = price.parse().map_err(|_| format!("price '{}' is not a number", price))?;
+    let quantity = match field("quantity") {
+        Some(quantity) => Some(
+            quantity
+                .parse()
+                .map_err(|_| format!("quantity '{}' is not a whole number", quantity))?,
+        ),
+        None => None,
+    };
+    let total = match field("total") {
+        Some(total) => {
+            Some(total.parse().map_err(|_| format!("total '{}' is not a number", total))?)
+        }
+        None => None,
+    };
+    let id = match field("id") {
+        Some(id) => Some(Uuid::parse_str(&id).map_err(|_| format!("id '{}' is not a UUID", id))?),
+        None => None,
+    };
+
+    Ok(ImportRecord {
+        id,
+        created_at: required("created_at")?,
+        customer_name: required("customer_name")?,
+        customer_email: required("customer_email")?,
+        customer_phone: field("customer_phone"),
+        items: vec![ImportItem {
+            name: required("item")?,
+            description: field("description"),
+            price,
+            quantity,
+        }],
+        status: field("status"),
+        payment_id: field("payment_id"),
+        total,
+        currency: field("currency"),
+    })
+}
+
+/// Split on commas outside double quotes; `""` inside quotes is a quote
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+/// Validate one record and build the stored order
+fn to_order(record: ImportRecord, now: DateTime<Utc>) -> Result<Order, String> {
+    let created_at = parse_timestamp(&record.created_at)?;
+    if created_at > now {
+        return Err(format!("created_at {} is in the future", record.created_at));
+    }
+    if record.customer_name.trim().is_empty() {
+        return Err("customer_name is empty".to_string());
+    }
+    if !record.customer_email.contains('@') {
+        return Err(format!("customer_email '{}' is not an email", record.customer_email));
+    }
+    if record.items.is_empty() {
+        return Err("the order has no items".to_string());
+    }
+
+    let currency = match &record.currency {
+        Some(code) => code.parse::<Currency>()?,
+        None => Currency::USD,
+    };
+    let mut items = Vec::with_capacity(record.items.len());
+    for item in record.items {
+        if item.name.trim().is_empty() {
+            return Err("an item has no name".to_string());
+        }
+        if !item.price.is_finite() || item.price < 0.0 {
+            return Err(format!("price {} for {} is not valid", item.price, item.name));
+        }
+        let quantity = item.quantity.unwrap_or(1);
+        let quantity = u8::try_from(quantity)
+            .ok()
+            .filter(|&quantity| quantity > 0)
+            .ok_or_else(|| format!("quantity {} for {} is not 1-255", quantity, item.name))?;
+        items.push(OrderItem {
+            beverage_description: item.description.unwrap_or_else(|| item.name.clone()),
+            beverage_name: item.name,
+            price: Money::from_major(item.price, currency),
+            quantity,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        });
+    }
+
+    let status = match record.status.as_deref() {
+        Some(status) => parse_status(status)?,
+        None => OrderStatus::Completed,
+    };
+
+    let customer =
+        Customer::new(record.customer_name, record.customer_email, record.customer_phone);
+    let mut order = Order::new(customer, items);
+    if let Some(total) = record.total {
+        let expected = order.expected_total(&LinePricing);
+        if !total.is_finite() || Money::from_major(total, currency) != expected {
+            return Err(format!("total {} doesn't match the items ({})", total, expected));
+        }
+    }
+    order.id = record.id.unwrap_or(order.id);
+    order.created_at = created_at;
+    // Pending and Cancelled orders were never paid, unless the file says otherwise
+    order.payment_id = record.payment_id.or_else(|| {
+        (!matches!(status, OrderStatus::Pending | OrderStatus::Cancelled))
+            .then(|| format!("IMPORT-{}", order.id.simple()))
+    });
+    // The file only tells the final status, not when each step happened
+    order.status_history = vec![StatusChange {
+        status: OrderStatus::Pending,
+        at: created_at,
+    }];
+    if status != OrderStatus::Pending {
+        order.status_history.push(StatusChange {
+            status: status.clone(),
+            at: created_at,
+        });
+    }
+    order.status = status;
+    Ok(order)
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(at) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(at.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        .map_err(|_| format!("created_at '{}' is not a date", value))
+}
+
+fn parse_status(value: &str) -> Result<OrderStatus, String> {
+    let status: OrderStatus = value.parse()?;
+    // Waiting on a payment provider or a bank: not a state to import
+    match status {
+        OrderStatus::AwaitingPayment | OrderStatus::Disputed => {
+            Err(format!("status '{}' can't be imported", value))
+        }
+        status => Ok(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{FixedClock, MemoryOrderRepository};
+
+    fn clock() -> FixedClock {
+        FixedClock::new("2026-03-10T12:00:00Z".parse().unwrap())
+    }
+
+    #[test]
+    fn test_csv_import_reports_bad_rows_and_keeps_good_ones() {
+        let csv = "\
+created_at,customer_name,customer_email,item,price,quantity,status
+2026-03-02T08:15:00Z,Ada,ada@example.com,Coffee,3.50,2,Completed
+2026-03-02,\"Lovelace, Ada\",ada@example.com,Green Tea,3.00,,
+2026-03-02,Bob,not-an-email,Coffee,3.50,1,
+2026-04-01,Cy,cy@example.com,Coffee,3.50,1,
+2026-03-03,Di,di@example.com,Coffee,abc,1,
+2026-03-03,Ed,ed@example.com,Coffee,3.50,1,Lost";
+        let mut repository = MemoryOrderRepository::new();
+
+        let report = OrderImporter::new()
+            .import_str(ImportFormat::Csv, csv, &mut repository, &clock())
+            .unwrap();
+
+        assert_eq!(report.imported, 2);
+        let rows: Vec<usize> = report.rejected.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![4, 5, 6, 7]);
+        assert!(report.rejected[1].message.contains("future"));
+        let orders = repository.list_all().unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().all(|o| o.status == OrderStatus::Completed));
+        assert!(orders.iter().all(|o| o.payment_id.as_deref().unwrap().starts_with("IMPORT-")));
+        assert!(orders.iter().any(|o| o.customer.name == "Lovelace, Ada"));
+    }
+
+    #[test]
+    fn test_json_import_rejects_duplicates_and_dry_run_writes_nothing() {
+        let id = Uuid::new_v4();
+        let json = format!(
+            r#"[
+  {{"id": "{id}", "created_at": "2026-03-01 09:30", "customer_name": "Catering",
+   "customer_email": "events@example.com", "payment_id": "INVOICE-42", "total": 220.0,
+   "currency": "chf",
+   "items": [{{"name": "Coffee", "price": 3.5, "quantity": 40}},
+             {{"name": "Smoothie", "price": 4.0, "quantity": 20}}]}},
+  {{"id": "{id}", "created_at": "2026-03-01", "customer_name": "Again",
+   "customer_email": "again@example.com", "items": [{{"name": "Coffee", "price": 3.5}}]}},
+  {{"created_at": "2026-03-01", "customer_name": "No items"}},
+  {{"created_at": "2026-03-02", "customer_name": "Edited", "customer_email": "ed@example.com",
+   "items": [{{"name": "Coffee", "price": 3.5}}], "total": 5.0}}
+]"#
+        );
+        let mut repository = MemoryOrderRepository::new();
+
+        let dry = OrderImporter::new()
+            .dry_run(true)
+            .import_str(ImportFormat::Json, &json, &mut repository, &clock())
+            .unwrap();
+        assert_eq!(dry.imported, 1);
+        assert!(repository.list_all().unwrap().is_empty());
+
+        let report = OrderImporter::new()
+            .import_str(ImportFormat::Json, &json, &mut repository, &clock())
+            .unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.rejected.len(), 3);
+        assert!(report.rejected[0].message.contains("already exists"));
+        assert_eq!(report.rejected[2].message, "total 5 doesn't match the items (3.50 USD)");
+        let order = repository.find_by_id(id).unwrap().unwrap();
+        assert_eq!(order.total_price, Money::new(22_000, Currency::CHF));
+        assert_eq!(order.payment_id.as_deref(), Some("INVOICE-42"));
+    }
+}
@@ -0,0 +1,160 @@
+// SOLID: OrderIntake - accept orders only as fast as the workers can take them
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// OrderService saves the order, the queue decides whether there is room.
+// OrderIntake only ties the two together and says what the caller should
+// tell the customer: accepted, "busy, try again" (429) or "closed" (503).
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// It knows the OrderQueueProducer trait, not the channel behind it. Whether
+// a full queue blocks or refuses is the queue's policy (see
+// adapters::bounded_order_queue); intake handles both contracts.
+//
+// NO STRANDED ORDERS:
+// The order is saved (Pending) before it is queued, because workers load it
+// by ID. If the queue refuses it, the order is cancelled again, so nothing
+// stays Pending with no worker ever coming for it. Nobody was charged yet.
+
+use super::order_service::{OrderService, OrderServiceError};
+use crate::domain::{Beverage, Customer, Order};
+use crate::ports::{Notifier, OrderQueueProducer, OrderRepository, PaymentProcessor, QueueError};
+use std::error::Error;
+use std::fmt;
+
+/// Why an order was not accepted
+#[derive(Debug)]
+pub enum IntakeError {
+    /// The queue is full: the order was cancelled, the customer can retry
+    Busy(String),
+    /// No worker will ever read the queue: the order was cancelled
+    Unavailable(String),
+    /// The order itself was refused (empty, storage down, ...)
+    Rejected(OrderServiceError),
+}
+
+impl IntakeError {
+    /// The HTTP status an API in front of intake should answer with
+    pub fn status_code(&self) -> u16 {
+        match self {
+            IntakeError::Busy(_) => 429,
+            IntakeError::Unavailable(_) => 503,
+            IntakeError::Rejected(OrderServiceError::InvalidOrder(_)) => 400,
+            IntakeError::Rejected(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for IntakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntakeError::Busy(msg) => write!(f, "Too many orders, try again shortly: {}", msg),
+            IntakeError::Unavailable(msg) => write!(f, "Not taking orders: {}", msg),
+            IntakeError::Rejected(e) => write!(f, "Order rejected: {}", e),
+        }
+    }
+}
+
+impl Error for IntakeError {}
+
+/// Intake front of an asynchronous OrderService setup
+pub struct OrderIntake<R, P, N, Q>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+    Q: OrderQueueProducer,
+{
+    orders: OrderService<R, P, N>,
+    queue: Q,
+}
+
+impl<R, P, N, Q> OrderIntake<R, P, N, Q>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+    Q: OrderQueueProducer,
+{
+    pub fn new(orders: OrderService<R, P, N>, queue: Q) -> Self {
+        Self { orders, queue }
+    }
+
+    /// Save the order as Pending and queue it for a worker
+    pub fn accept(
+        &mut self,
+        customer: Customer,
+        beverages: Vec<Box<dyn Beverage>>,
+    ) -> Result<Order, IntakeError> {
+        let order = self
+            .orders
+            .submit_order(customer, beverages)
+            .map_err(IntakeError::Rejected)?;
+
+        let refused = match self.queue.enqueue(order.id) {
+            Ok(()) => return Ok(order),
+            Err(QueueError::Full(msg)) => IntakeError::Busy(msg),
+            Err(e) => IntakeError::Unavailable(e.to_string()),
+        };
+
+        if let Err(e) = self.orders.cancel_order(order.id) {
+            eprintln!("Warning: Order {} was not queued and is still pending: {}", order.id, e);
+        }
+        Err(refused)
+    }
+
+    /// The wrapped service (listing, reporting, ...)
+    pub fn orders(&self) -> &OrderService<R, P, N> {
+        &self.orders
+    }
+
+    /// Stop taking orders: the queue handle is dropped (so workers stop once
+    /// it is drained) and the service is handed back
+    pub fn close(self) -> OrderService<R, P, N> {
+        self.orders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        bounded_order_queue, CashPayment, ConsoleNotifier, FullQueuePolicy, MemoryOrderRepository,
+    };
+    use crate::domain::{Coffee, OrderStatus, Size};
+    use crate::ports::OrderQueueConsumer;
+    use std::io;
+
+    fn coffee() -> Vec<Box<dyn Beverage>> {
+        vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })]
+    }
+
+    fn customer() -> Customer {
+        Customer::new("Ada".to_string(), "ada@example.com".to_string(), None)
+    }
+
+    #[test]
+    fn test_full_queue_answers_429_and_cancels_the_order() {
+        let (producer, consumer) = bounded_order_queue(1, FullQueuePolicy::Shed);
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier);
+        let mut intake = OrderIntake::new(service, producer);
+
+        let accepted = intake.accept(customer(), coffee()).unwrap();
+        let error = intake.accept(customer(), coffee()).unwrap_err();
+
+        assert_eq!(error.status_code(), 429);
+        let orders = intake.orders().list_all_orders().unwrap();
+        let shed = orders.iter().find(|o| o.id != accepted.id).unwrap();
+        assert_eq!(shed.status, OrderStatus::Cancelled);
+
+        // Once a worker took the first one, there is room again
+        assert_eq!(consumer.dequeue().unwrap(), Some(accepted.id));
+        assert!(intake.accept(customer(), coffee()).is_ok());
+        drop(consumer);
+        assert_eq!(intake.accept(customer(), coffee()).unwrap_err().status_code(), 503);
+        assert_eq!(intake.accept(customer(), vec![]).unwrap_err().status_code(), 400);
+    }
+}
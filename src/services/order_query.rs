@@ -0,0 +1,143 @@
+// SOLID: OrderQueryService - the order book as it stood at a past moment
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Read-only, forensic questions: "what was pending at 9:05?", "which orders
+// were on the counter when the espresso machine broke?". Every order keeps
+// its status history, so the book at any moment is rebuilt from it
+// (Order::as_of): no event store, no snapshots to keep.
+//
+// Items and totals are as stored now: an order edited since shows its
+// current lines (AuditedRepository's log has the changes).
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Reads through the OrderRepository trait, like ReportingService.
+
+use crate::domain::{Order, OrderStatus};
+use crate::ports::{OrderRepository, RepositoryError};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Statuses in lifecycle order, for counts
+const LIFECYCLE: [OrderStatus; 10] = [
+    OrderStatus::Pending,
+    OrderStatus::AwaitingPayment,
+    OrderStatus::Paid,
+    OrderStatus::Preparing,
+    OrderStatus::Ready,
+    OrderStatus::Completed,
+    OrderStatus::NoShow,
+    OrderStatus::Cancelled,
+    OrderStatus::Refunded,
+    OrderStatus::Disputed,
+];
+
+/// Every order that existed at `at`, each in the state it was in then
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderBook {
+    pub at: DateTime<Utc>,
+    /// Oldest first
+    pub orders: Vec<Order>,
+}
+
+impl OrderBook {
+    /// Orders that were in `status` at that moment
+    pub fn with_status(&self, status: &OrderStatus) -> Vec<&Order> {
+        self.orders.iter().filter(|order| &order.status == status).collect()
+    }
+
+    /// Orders not handed over, cancelled or refunded yet: what the counter had to deal with
+    pub fn open(&self) -> Vec<&Order> {
+        self.orders
+            .iter()
+            .filter(|order| {
+                matches!(
+                    order.status,
+                    OrderStatus::Pending
+                        | OrderStatus::AwaitingPayment
+                        | OrderStatus::Paid
+                        | OrderStatus::Preparing
+                        | OrderStatus::Ready
+                )
+            })
+            .collect()
+    }
+
+    /// Orders per status, in lifecycle order (statuses with none left out)
+    pub fn counts(&self) -> Vec<(OrderStatus, usize)> {
+        LIFECYCLE
+            .into_iter()
+            .map(|status| {
+                let count = self.orders.iter().filter(|o| o.status == status).count();
+                (status, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+}
+
+/// Temporal queries over stored orders
+pub struct OrderQueryService<R: OrderRepository> {
+    repository: R,
+}
+
+impl<R: OrderRepository> OrderQueryService<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// The order book as it stood at `at`
+    pub fn as_of(&self, at: DateTime<Utc>) -> Result<OrderBook, RepositoryError> {
+        let mut orders: Vec<Order> = self
+            .repository
+            .list_all()?
+            .iter()
+            .filter_map(|order| order.as_of(at))
+            .collect();
+        orders.sort_by_key(|order| order.created_at);
+        Ok(OrderBook { at, orders })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer};
+    use chrono::Duration;
+
+    #[test]
+    fn test_what_was_pending_at_a_past_moment() {
+        let opening: DateTime<Utc> = "2026-10-15T09:00:00Z".parse().unwrap();
+        let at = |minutes| opening + Duration::minutes(minutes);
+        let placed = |name: &str, minute| {
+            let mut order = Order::single(Customer::named(name), Coffee::medium());
+            order.created_at = at(minute);
+            order.status_history[0].at = at(minute);
+            order
+        };
+
+        let mut ada = placed("Ada", 0);
+        ada.mark_as_paid("CASH-1".to_string()).unwrap();
+        ada.mark_as_preparing().unwrap();
+        ada.status_history[1].at = at(2);
+        ada.status_history[2].at = at(4);
+        let grace = placed("Grace", 3);
+        let late = placed("Linus", 30);
+
+        let mut repository = MemoryOrderRepository::new();
+        for order in [&ada, &grace, &late] {
+            repository.save(order).unwrap();
+        }
+        let service = OrderQueryService::new(repository);
+
+        let book = service.as_of(at(3)).unwrap();
+        assert_eq!(book.orders.len(), 2);
+        assert_eq!(book.with_status(&OrderStatus::Pending)[0].id, grace.id);
+        assert_eq!(book.counts(), vec![(OrderStatus::Pending, 1), (OrderStatus::Paid, 1)]);
+        assert_eq!(book.open().len(), 2);
+
+        let later = service.as_of(at(5)).unwrap();
+        assert_eq!(later.with_status(&OrderStatus::Preparing)[0].id, ada.id);
+        assert!(service.as_of(at(-1)).unwrap().orders.is_empty());
+    }
+}
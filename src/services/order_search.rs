@@ -0,0 +1,153 @@
+// SOLID: OrderSearchService - find orders by customer name or what was ordered
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Searching is not part of OrderRepository. A backend with its own text
+//    index (SQLite's FTS5 table) offers it through RepositoryCapabilities;
+//    every other backend says nothing and loses nothing.
+//
+// 2. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    The service names no adapter. It asks the repository for
+//    `full_text_search` and, when the answer is None, scans `list_all` itself
+//    with the same rule: every word of the query must start a word of the
+//    customer's name or of an item's name or description.
+//
+// The two paths agree on WHICH orders match, not on their order: the index
+// ranks by relevance, the scan lists the newest first. The index also folds
+// accents ("cafe" finds "Café"), the scan only case.
+
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+
+/// Text search over orders, through the backend's index when it has one
+pub struct OrderSearchService<R: OrderRepository + RepositoryCapabilities> {
+    repository: R,
+}
+
+impl<R: OrderRepository + RepositoryCapabilities> OrderSearchService<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Orders matching every word of `query` ("ada latte"); none for a query without words
+    pub fn search(&self, query: &str) -> Result<Vec<Order>, RepositoryError> {
+        if let Some(found) = self.repository.full_text_search(query) {
+            return found;
+        }
+        let words = words_of(query);
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut found: Vec<Order> = self
+            .repository
+            .list_all()?
+            .into_iter()
+            .filter(|order| {
+                let text = searchable_words(order);
+                words.iter().all(|word| text.iter().any(|t| t.starts_with(word.as_str())))
+            })
+            .collect();
+        found.sort_by_key(|order| std::cmp::Reverse(order.created_at));
+        Ok(found)
+    }
+
+    /// True when searches go through the backend's index, not a scan
+    pub fn indexed(&self) -> bool {
+        self.repository.full_text_search("").is_some()
+    }
+
+    pub fn repository(&self) -> &R {
+        &self.repository
+    }
+
+    pub fn repository_mut(&mut self) -> &mut R {
+        &mut self.repository
+    }
+}
+
+/// The words a search can match, split and lowercased as the index does
+fn searchable_words(order: &Order) -> Vec<String> {
+    let mut text = order.customer.name.clone();
+    for item in &order.items {
+        text.push(' ');
+        text.push_str(&item.beverage_name);
+        text.push(' ');
+        text.push_str(&item.beverage_description);
+    }
+    words_of(&text)
+}
+
+fn words_of(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer, Size, Smoothie};
+
+    fn shop_orders() -> Vec<Order> {
+        vec![
+            Order::single(Customer::named("Ada Lovelace"), Coffee::medium()),
+            Order::single(Customer::named("Alan Turing"), Smoothie::new(&["Mango"], Size::Large)),
+            Order::single(Customer::named("Grace Hopper"), Coffee::large().with_extra_shots(2)),
+        ]
+    }
+
+    fn names(found: &[Order]) -> Vec<String> {
+        let mut names: Vec<String> = found.iter().map(|o| o.customer.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_scans_when_the_backend_has_no_index() {
+        let mut repository = MemoryOrderRepository::new();
+        repository.save_batch(&shop_orders()).unwrap();
+        let search = OrderSearchService::new(repository);
+
+        assert!(!search.indexed());
+        assert_eq!(names(&search.search("coff").unwrap()), ["Ada Lovelace", "Grace Hopper"]);
+        assert_eq!(names(&search.search("MANGO al").unwrap()), ["Alan Turing"]);
+        assert_eq!(names(&search.search("coffee large").unwrap()), ["Grace Hopper"]);
+        assert!(search.search("ango").unwrap().is_empty());
+        assert!(search.search("  ").unwrap().is_empty());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_index_matches_the_scan_and_follows_writes() {
+        use crate::adapters::SqliteOrderRepository;
+
+        let orders = shop_orders();
+        let mut memory = MemoryOrderRepository::new();
+        let mut sqlite = SqliteOrderRepository::in_memory().unwrap();
+        memory.save_batch(&orders).unwrap();
+        sqlite.save_batch(&orders).unwrap();
+        let scan = OrderSearchService::new(memory);
+        let mut index = OrderSearchService::new(sqlite);
+
+        assert!(index.indexed());
+        for query in ["coff", "MANGO al", "coffee large", "ango", "ada \"", "hopper"] {
+            assert_eq!(
+                names(&index.search(query).unwrap()),
+                names(&scan.search(query).unwrap()),
+                "{}",
+                query
+            );
+        }
+
+        let mut renamed = orders[0].clone();
+        renamed.customer.name = "Ada Byron".to_string();
+        index.repository_mut().update(&renamed).unwrap();
+        index.repository_mut().delete(orders[2].id).unwrap();
+        assert_eq!(names(&index.search("byron").unwrap()), ["Ada Byron"]);
+        assert!(index.search("lovelace").unwrap().is_empty());
+        assert!(index.search("hopper").unwrap().is_empty());
+    }
+}
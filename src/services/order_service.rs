@@ -23,21 +23,58 @@
 //    This service depends on three small, focused interfaces
 //    Not on one giant "OrderManager" interface with 20 methods
 
-use crate::domain::{Beverage, Customer, Order, OrderItem, OrderStatus};
+use crate::bus::{
+    Message, MessageBus, OrderCancelled, OrderCompleted, OrderNoShow, OrderPlaced, OrderReady,
+    PaymentFailed,
+};
+use crate::domain::{
+    Beverage, Customer, DomainEvent, InvalidTransition, Locale, MenuTranslations, Money,
+    Order, OrderCancelledNotice, OrderItem, OrderLine, OrderNoShowNotice, OrderPlacedNotice,
+    OrderReadyNotice, OrderStatus,
+};
 use crate::ports::{
-    Notifier, NotificationError, OrderRepository, PaymentError, PaymentProcessor, RepositoryError,
+    Clock, EventPublisher, FiscalError, FiscalNumberGenerator, MetricsRecorder, Notifier,
+    NotificationError, OrderRepository, PaymentError, PaymentProcessor, PaymentRequest,
+    PendingPayment, RepositoryError, ORDER_PLACEMENT_SECONDS, ORDER_PREP_SECONDS,
 };
+use crate::services::customer_history_cache::{CacheStats, CustomerHistoryCache};
+use crate::services::inventory_service::{InventoryService, StockError};
+use crate::services::loyalty_service::LoyaltyService;
+use crate::services::no_show::{NoShowPolicy, NoShowReport};
+use crate::services::cash_rounding::RoundingPolicy;
+use crate::services::payment_rules::{PaymentRuleError, PaymentRules};
+use crate::services::prep_time::PrepTimeModel;
+use crate::services::price_book::PriceBooks;
+use crate::services::promotion_engine::Promoted;
+use crate::services::sla_watchdog::SlaPolicy;
+use crate::services::vat::VatRates;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 
 /// Errors that can occur during order processing
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum OrderServiceError {
     PaymentFailed(PaymentError),
     StorageFailed(RepositoryError),
     NotificationFailed(NotificationError),
+    /// Paid, but no fiscal receipt number could be issued (the payment is refunded)
+    FiscalNumberingFailed(FiscalError),
     OrderNotFound,
     InvalidOrder(String),
+    /// The payment method may not pay this total (below its minimum); nothing was charged
+    PaymentMethodRefused(PaymentRuleError),
+    /// Not enough of an ingredient for the order; nothing was charged
+    StockUnavailable(StockError),
+    /// The order's status doesn't allow this step (e.g. Ready while still Paid)
+    InvalidTransition(InvalidTransition),
+    /// Paid through another processor (its payment method): refund it through that one
+    RefundElsewhere(String),
+    /// A thread panicked while using the shared service (SharedOrderService):
+    /// its last order may be half done, so nothing more is accepted
+    ServicePoisoned(String),
 }
 
 impl fmt::Display for OrderServiceError {
@@ -46,14 +83,38 @@ impl fmt::Display for OrderServiceError {
             OrderServiceError::PaymentFailed(e) => write!(f, "Payment failed: {}", e),
             OrderServiceError::StorageFailed(e) => write!(f, "Storage failed: {}", e),
             OrderServiceError::NotificationFailed(e) => write!(f, "Notification failed: {}", e),
+            OrderServiceError::FiscalNumberingFailed(e) => write!(f, "{}", e),
             OrderServiceError::OrderNotFound => write!(f, "Order not found"),
             OrderServiceError::InvalidOrder(msg) => write!(f, "Invalid order: {}", msg),
+            OrderServiceError::PaymentMethodRefused(e) => {
+                write!(f, "Payment method refused: {}", e)
+            }
+            OrderServiceError::StockUnavailable(e) => write!(f, "{}", e),
+            OrderServiceError::InvalidTransition(e) => write!(f, "{}", e),
+            OrderServiceError::RefundElsewhere(method) => write!(
+                f,
+                "Order was paid by {}: cancel it through that processor to refund it",
+                method
+            ),
+            OrderServiceError::ServicePoisoned(msg) => {
+                write!(f, "Order service unavailable: {}", msg)
+            }
         }
     }
 }
 
 impl Error for OrderServiceError {}
 
+/// Whether the baristas see an order before it is paid (`place_unpaid_order`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnpaidOrders {
+    /// Only once settled: nothing is made before it's paid
+    #[default]
+    HoldUntilPaid,
+    /// Straight away, so the drink is ready when the customer comes to pay
+    Queue,
+}
+
 /// OrderService - Orchestrates the order workflow
 /// 
 /// SOLID PRINCIPLE: Dependency Inversion Principle (DIP)
@@ -93,6 +154,30 @@ where
     repository: R,
     payment_processor: P,
     notifier: N,
+    bus: Option<MessageBus>,
+    history_cache: Option<CustomerHistoryCache>,
+    metrics: Option<Box<dyn MetricsRecorder>>,
+    event_publishers: Vec<Box<dyn EventPublisher>>,
+    prep_times: PrepTimeModel,
+    price_books: Option<PriceBooks>,
+    payment_rules: PaymentRules,
+    rounding: RoundingPolicy,
+    vat_rates: Option<VatRates>,
+    fiscal: Option<FiscalRegistration>,
+    inventory: Option<InventoryService>,
+    loyalty: Option<LoyaltyService>,
+    translations: Option<(MenuTranslations, Locale)>,
+    unpaid_orders: UnpaidOrders,
+    clock: Option<Box<dyn Clock + Send>>,
+    // (order ID, cents refunded before) -> refund ID, for cancel refunds not
+    // stored yet: a cancel retried after a failed update reuses the refund
+    cancel_refunds: HashMap<(uuid::Uuid, i64), String>,
+}
+
+/// The shop's tax registration and its receipt counter
+struct FiscalRegistration {
+    tax_id: String,
+    numbers: Box<dyn FiscalNumberGenerator>,
 }
 
 impl<R, P, N> OrderService<R, P, N>
@@ -111,16 +196,164 @@ where
             repository,
             payment_processor,
             notifier,
+            bus: None,
+            history_cache: None,
+            metrics: None,
+            event_publishers: Vec::new(),
+            prep_times: PrepTimeModel::default(),
+            price_books: None,
+            payment_rules: PaymentRules::default(),
+            rounding: RoundingPolicy::default(),
+            vat_rates: None,
+            fiscal: None,
+            inventory: None,
+            loyalty: None,
+            translations: None,
+            unpaid_orders: UnpaidOrders::default(),
+            clock: None,
+            cancel_refunds: HashMap::new(),
         }
     }
 
+    /// Cache customer order histories (LRU, at most `capacity` customers)
+    ///
+    /// Histories are invalidated whenever one of the customer's orders changes.
+    pub fn with_history_cache(mut self, capacity: usize) -> Self {
+        self.history_cache = Some(CustomerHistoryCache::new(capacity));
+        self
+    }
+
+    /// Hit/miss counters of the history cache, if enabled
+    pub fn history_cache_stats(&self) -> Option<CacheStats> {
+        self.history_cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Publish order lifecycle messages on a bus
+    ///
+    /// SOLID (OCP): Subscribers (kitchen display, loyalty, inventory...) react
+    /// to these messages. Adding one never requires changing this service.
+    pub fn with_message_bus(mut self, bus: MessageBus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Report placement latency and prep duration to a metrics backend
+    pub fn with_metrics(mut self, metrics: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
+
+    /// Publish domain events (placed, paid, ready, cancelled) to `publisher` too
+    ///
+    /// For systems outside the process; call it once per publisher. A
+    /// publisher that fails is logged and skipped, the order stands.
+    pub fn with_event_publisher(mut self, publisher: impl EventPublisher + 'static) -> Self {
+        self.event_publishers.push(Box::new(publisher));
+        self
+    }
+
+    /// Estimate prep times with shop-specific overrides
+    pub fn with_prep_times(mut self, prep_times: PrepTimeModel) -> Self {
+        self.prep_times = prep_times;
+        self
+    }
+
+    /// Price orders from the price book in effect when they are placed
+    ///
+    /// The version used is recorded on the order, for audits (PriceBooks::reprice).
+    pub fn with_price_books(mut self, price_books: PriceBooks) -> Self {
+        self.price_books = Some(price_books);
+        self
+    }
+
+    /// Surcharges and minimums per payment method, e.g. 2% on cards from 5.00
+    ///
+    /// Checked before charging; the surcharge is recorded on the order (`surcharge`).
+    pub fn with_payment_rules(mut self, payment_rules: PaymentRules) -> Self {
+        self.payment_rules = payment_rules;
+        self
+    }
+
+    /// Round totals for the payment method, e.g. cash to 5 cents
+    ///
+    /// The difference is recorded on the order (`rounding_adjustment`).
+    pub fn with_rounding(mut self, rounding: RoundingPolicy) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Record on each line the VAT rate included in its price
+    pub fn with_vat_rates(mut self, vat_rates: VatRates) -> Self {
+        self.vat_rates = Some(vat_rates);
+        self
+    }
+
+    /// Issue fiscal receipts: each paid order gets the next number from
+    /// `numbers` and the shop's VAT registration `tax_id`
+    ///
+    /// Numbers are only drawn once the payment went through, so a declined
+    /// card leaves no gap in the sequence.
+    pub fn with_fiscal_receipts(
+        mut self,
+        tax_id: &str,
+        numbers: impl FiscalNumberGenerator + 'static,
+    ) -> Self {
+        self.fiscal = Some(FiscalRegistration {
+            tax_id: tax_id.to_string(),
+            numbers: Box::new(numbers),
+        });
+        self
+    }
+
+    /// Refuse orders the shop lacks the ingredients for, and take paid ones off the shelves
+    ///
+    /// Stock is checked when the order is built, before anything is charged,
+    /// and consumed once it is paid and saved.
+    pub fn with_inventory(mut self, inventory: InventoryService) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    /// Run a loyalty program: members' tier discount at checkout, points for paid orders
+    ///
+    /// The discount is a line on each beverage ("Gold member -10%"); points
+    /// accrue once the order is paid and saved.
+    pub fn with_loyalty(mut self, loyalty: LoyaltyService) -> Self {
+        self.loyalty = Some(loyalty);
+        self
+    }
+
+    /// Name the beverages in customer notices in `locale` ("Café (Moyen)")
+    ///
+    /// Orders keep the English names; only what the customer reads changes.
+    pub fn with_translations(mut self, translations: MenuTranslations, locale: Locale) -> Self {
+        self.translations = Some((translations, locale));
+        self
+    }
+
+    /// Put orders to be paid at the counter on the barista queue before they're paid
+    pub fn with_unpaid_orders(mut self, unpaid_orders: UnpaidOrders) -> Self {
+        self.unpaid_orders = unpaid_orders;
+        self
+    }
+
+    /// Take "now" from `clock` instead of the system's (a demo's FixedClock)
+    ///
+    /// It picks the price book in effect (happy hour) and dates new orders
+    /// and every status change.
+    pub fn with_clock(mut self, clock: impl Clock + Send + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
     /// Place a new order
     /// 
     /// This method orchestrates the entire order workflow:
     /// 1. Create the order
     /// 2. Process payment
-    /// 3. Save to storage
-    /// 4. Send notification
+    /// 3. Number the fiscal receipt, if the shop issues them
+    /// 4. Save to storage (the payment is refunded if this or 3 fails)
+    /// 5. Send notification
     /// 
     /// SOLID (SRP): Notice this method doesn't DO these things,
     /// it COORDINATES them. Each step is delegated to a specialized component.
@@ -133,52 +366,527 @@ where
         customer: Customer,
         beverages: Vec<Box<dyn Beverage>>,
     ) -> Result<Order, OrderServiceError> {
-        // Validate order
-        if beverages.is_empty() {
-            return Err(OrderServiceError::InvalidOrder(
-                "Order must contain at least one item".to_string(),
-            ));
-        }
-
-        // Create order items from beverages
-        let items: Vec<OrderItem> = beverages
-            .iter()
-            .map(|b| OrderItem {
-                beverage_name: b.name(),
-                beverage_description: b.description(),
-                price: b.price(),
-                quantity: 1,
-            })
-            .collect();
+        self.place_order_lines(customer, single_lines(beverages))
+    }
 
-        // Create the order
-        let mut order = Order::new(customer, items);
+    /// Place a new order with a quantity per beverage ("2 medium coffees")
+    ///
+    /// Same workflow as `place_order`; each line becomes one item, and the
+    /// total is price times quantity. A quantity of 0 is refused.
+    pub fn place_order_lines(
+        &mut self,
+        customer: Customer,
+        lines: Vec<OrderLine>,
+    ) -> Result<Order, OrderServiceError> {
+        let mut order = self.build_order(customer, lines)?;
+        self.apply_payment_rules(&mut order, &self.payment_processor)?;
+        self.apply_rounding(&mut order, &self.payment_processor);
 
         // SOLID (DIP): We're calling a trait method, not a concrete implementation
         // This could be CashPayment, CreditCardPayment, MobilePayment, or MockPayment
         // The service doesn't know or care!
-        let payment_id = self
+        let payment_id = match self
             .payment_processor
-            .process_payment(order.total_price)
-            .map_err(OrderServiceError::PaymentFailed)?;
+            .process_request(&PaymentRequest::for_order(&order))
+        {
+            Ok(payment_id) => payment_id,
+            Err(e) => {
+                self.publish(PaymentFailed {
+                    order_id: order.id,
+                    customer_email: order.customer.email.clone(),
+                    amount: order.total_price,
+                    reason: e.to_string(),
+                    failed_at: self.now(),
+                });
+                return Err(OrderServiceError::PaymentFailed(e));
+            }
+        };
 
         // Mark order as paid
-        order.mark_as_paid(payment_id);
+        let method = self.payment_processor.payment_method_name().to_string();
+        let points = self.paid_with_points(&self.payment_processor, &payment_id);
+        if let Err(e) = self.mark_paid(&mut order, &payment_id, method, points) {
+            refund_unsaved(&self.payment_processor, &order, &payment_id);
+            return Err(e);
+        }
 
         // SOLID (DIP): Again, trait method. Could be Memory, JSON, Postgres, etc.
+        // A charge must never outlive a failed save: nobody could find the
+        // order to hand over the drink or refund it later, so refund it now.
+        if let Err(e) = self.repository.save(&order) {
+            refund_unsaved(&self.payment_processor, &order, &payment_id);
+            return Err(OrderServiceError::StorageFailed(e));
+        }
+        self.invalidate_history(&order);
+        self.emit(DomainEvent::placed(&order));
+        self.emit(DomainEvent::paid(&order));
+        self.consume_stock(&order);
+        self.accrue_points(&order);
+
+        // SOLID (DIP): Trait method. Could be Console, Email, SMS, Push, etc.
+        // Note: We don't fail the order if notification fails - it's already paid and saved
+        let notice = self.placed_notice(&order);
+        if let Err(e) = self.notifier.notify_order_placed(&notice) {
+            eprintln!("Warning: Failed to send notification: {}", e);
+        }
+
+        self.publish(OrderPlaced::from_order(&order));
+        self.observe(ORDER_PLACEMENT_SECONDS, order.placement_latency());
+
+        Ok(order)
+    }
+
+    /// Accept an order without charging for it yet
+    ///
+    /// This is the intake half of asynchronous processing: the order is
+    /// validated and saved as Pending, then its ID is queued for a worker
+    /// which calls `process_submitted_order`.
+    pub fn submit_order(
+        &mut self,
+        customer: Customer,
+        beverages: Vec<Box<dyn Beverage>>,
+    ) -> Result<Order, OrderServiceError> {
+        let order = self.build_order(customer, single_lines(beverages))?;
+
         self.repository
             .save(&order)
             .map_err(OrderServiceError::StorageFailed)?;
+        self.invalidate_history(&order);
+        self.emit(DomainEvent::placed(&order));
 
-        // SOLID (DIP): Trait method. Could be Console, Email, SMS, Push, etc.
-        // Note: We don't fail the order if notification fails - it's already paid and saved
-        if let Err(e) = self.notifier.notify_order_placed(&order) {
+        Ok(order)
+    }
+
+    /// Fulfillment half of asynchronous processing
+    ///
+    /// Charges a Pending order, marks it paid and notifies the customer.
+    /// Orders that are no longer Pending are rejected, so delivering the
+    /// same ID twice never charges twice. Stock is checked again first: other
+    /// orders may have used it since this one was submitted.
+    pub fn process_submitted_order(&mut self, id: uuid::Uuid) -> Result<Order, OrderServiceError> {
+        let mut order = self.get_order(id)?;
+
+        if order.status != OrderStatus::Pending {
+            return Err(OrderServiceError::InvalidOrder(format!(
+                "Order {} is not pending (status: {})",
+                id, order.status
+            )));
+        }
+
+        self.check_stock(&order.items)?;
+        self.apply_payment_rules(&mut order, &self.payment_processor)?;
+        self.apply_rounding(&mut order, &self.payment_processor);
+        let payment_id = self
+            .payment_processor
+            .process_request(&PaymentRequest::for_order(&order))
+            .map_err(OrderServiceError::PaymentFailed)?;
+
+        self.record_payment(order, payment_id)
+    }
+
+    /// First half of a redirect payment (PayPal-style): nothing is charged yet
+    ///
+    /// The order is saved as AwaitingPayment with the provider's token. Send
+    /// the customer to the returned `approval_url`; when the provider sends
+    /// them back, call `confirm_payment`. Orders never confirmed are
+    /// cancelled like any other (`cancel_order`, or the day close).
+    pub fn initiate_order(
+        &mut self,
+        customer: Customer,
+        lines: Vec<OrderLine>,
+    ) -> Result<(Order, PendingPayment), OrderServiceError> {
+        let mut order = self.build_order(customer, lines)?;
+        self.apply_payment_rules(&mut order, &self.payment_processor)?;
+        self.apply_rounding(&mut order, &self.payment_processor);
+
+        let pending = self
+            .payment_processor
+            .initiate(&PaymentRequest::for_order(&order))
+            .map_err(OrderServiceError::PaymentFailed)?;
+        self.stamped(&mut order, |order| order.await_payment(pending.token.clone()))?;
+
+        self.repository
+            .save(&order)
+            .map_err(OrderServiceError::StorageFailed)?;
+        self.invalidate_history(&order);
+        self.emit(DomainEvent::placed(&order));
+
+        Ok((order, pending))
+    }
+
+    /// Second half of a redirect payment: take the money the customer approved
+    ///
+    /// `token` must be the one the order is waiting for, and the stock still
+    /// there (StockUnavailable otherwise, before anything is taken). Then the
+    /// order is paid, numbered and announced exactly like `place_order`'s.
+    pub fn confirm_payment(
+        &mut self,
+        id: uuid::Uuid,
+        token: &str,
+    ) -> Result<Order, OrderServiceError> {
+        let order = self.get_order(id)?;
+
+        if order.status != OrderStatus::AwaitingPayment {
+            return Err(OrderServiceError::InvalidOrder(format!(
+                "Order {} is not awaiting payment (status: {})",
+                id, order.status
+            )));
+        }
+        if order.payment_token.as_deref() != Some(token) {
+            return Err(OrderServiceError::InvalidOrder(format!(
+                "Token {} is not the one order {} awaits",
+                token, id
+            )));
+        }
+
+        self.check_stock(&order.items)?;
+        let payment_id = self
+            .payment_processor
+            .confirm(token)
+            .map_err(OrderServiceError::PaymentFailed)?;
+
+        self.record_payment(order, payment_id)
+    }
+
+    /// Place an order the customer pays at the counter, at pickup
+    ///
+    /// Nothing is charged: the order is saved as Pending and the customer is
+    /// told it was received. Whether the baristas start on it now or once it
+    /// is paid is the shop's policy (`with_unpaid_orders`). Call
+    /// `settle_order` when the customer pays; the day close cancels the rest.
+    pub fn place_unpaid_order(
+        &mut self,
+        customer: Customer,
+        lines: Vec<OrderLine>,
+    ) -> Result<Order, OrderServiceError> {
+        let order = self.build_order(customer, lines)?;
+
+        self.repository
+            .save(&order)
+            .map_err(OrderServiceError::StorageFailed)?;
+        self.invalidate_history(&order);
+        self.emit(DomainEvent::placed(&order));
+
+        let notice = self.placed_notice(&order);
+        if let Err(e) = self.notifier.notify_order_placed(&notice) {
+            eprintln!("Warning: Failed to send notification: {}", e);
+        }
+
+        if self.unpaid_orders == UnpaidOrders::Queue {
+            self.publish(OrderPlaced::from_order(&order));
+        }
+
+        Ok(order)
+    }
+
+    /// Take payment at the counter for an unpaid order
+    ///
+    /// `processor` is the counter's (the cash drawer, the card terminal), not
+    /// necessarily the service's own; its payment rules and cash rounding
+    /// apply, and a refund after a failed fiscal numbering goes through it.
+    /// The order remembers it: cancel it with `cancel_order_through`.
+    /// Orders that are no longer Pending are rejected, so settling twice
+    /// never charges twice, and so are orders the shelves can no longer make.
+    pub fn settle_order(
+        &mut self,
+        id: uuid::Uuid,
+        processor: &dyn PaymentProcessor,
+    ) -> Result<Order, OrderServiceError> {
+        let mut order = self.get_order(id)?;
+
+        if order.status != OrderStatus::Pending {
+            return Err(OrderServiceError::InvalidOrder(format!(
+                "Order {} is not awaiting payment at the counter (status: {})",
+                id, order.status
+            )));
+        }
+
+        self.check_stock(&order.items)?;
+        self.apply_payment_rules(&mut order, processor)?;
+        self.apply_rounding(&mut order, processor);
+        let payment_id = processor
+            .process_request(&PaymentRequest::for_order(&order))
+            .map_err(OrderServiceError::PaymentFailed)?;
+
+        let method = processor.payment_method_name().to_string();
+        let points = self.paid_with_points(processor, &payment_id);
+        if let Err(e) = self.mark_paid(&mut order, &payment_id, method, points) {
+            refund_unsaved(processor, &order, &payment_id);
+            return Err(e);
+        }
+
+        // As in place_order_lines: no charge outlives a failed save
+        if let Err(e) = self.repository.update(&order) {
+            refund_unsaved(processor, &order, &payment_id);
+            return Err(OrderServiceError::StorageFailed(e));
+        }
+        self.invalidate_history(&order);
+        self.emit(DomainEvent::paid(&order));
+        self.consume_stock(&order);
+        self.accrue_points(&order);
+
+        // Already on the barista queue if it went there unpaid
+        if self.unpaid_orders == UnpaidOrders::HoldUntilPaid {
+            self.publish(OrderPlaced::from_order(&order));
+        }
+
+        Ok(order)
+    }
+
+    /// A stored order was charged: number it, update it, announce it
+    ///
+    /// The charge is refunded if the order can't be numbered or updated.
+    fn record_payment(
+        &mut self,
+        mut order: Order,
+        payment_id: String,
+    ) -> Result<Order, OrderServiceError> {
+        let method = self.payment_processor.payment_method_name().to_string();
+        let points = self.paid_with_points(&self.payment_processor, &payment_id);
+        if let Err(e) = self.mark_paid(&mut order, &payment_id, method, points) {
+            refund_unsaved(&self.payment_processor, &order, &payment_id);
+            return Err(e);
+        }
+
+        if let Err(e) = self.repository.update(&order) {
+            refund_unsaved(&self.payment_processor, &order, &payment_id);
+            return Err(OrderServiceError::StorageFailed(e));
+        }
+        self.invalidate_history(&order);
+        self.emit(DomainEvent::paid(&order));
+        self.consume_stock(&order);
+        self.accrue_points(&order);
+
+        let notice = self.placed_notice(&order);
+        if let Err(e) = self.notifier.notify_order_placed(&notice) {
             eprintln!("Warning: Failed to send notification: {}", e);
         }
 
+        self.publish(OrderPlaced::from_order(&order));
+        self.observe(ORDER_PLACEMENT_SECONDS, order.placement_latency());
+
+        Ok(order)
+    }
+
+    /// Refuse the method about to be charged, or record its surcharge
+    fn apply_payment_rules(
+        &self,
+        order: &mut Order,
+        processor: &dyn PaymentProcessor,
+    ) -> Result<(), OrderServiceError> {
+        if self.payment_rules.is_empty() {
+            return Ok(());
+        }
+        let method = processor.payment_method_name();
+        order.surcharge = self
+            .payment_rules
+            .surcharge(method, order.total_price)
+            .map_err(OrderServiceError::PaymentMethodRefused)?;
+        Ok(())
+    }
+
+    /// Record the rounding of the total (and surcharge) for the method about to be charged
+    fn apply_rounding(&self, order: &mut Order, processor: &dyn PaymentProcessor) {
+        // Without a policy, don't ask the processor for its name at all
+        if self.rounding.increments.is_empty() {
+            return;
+        }
+        let method = processor.payment_method_name();
+        let due = order.total() + order.surcharge;
+        order.rounding_adjustment = self.rounding.adjustment(method, due);
+    }
+
+    /// What loyalty points paid of a charge, for the accrual
+    fn paid_with_points(
+        &self,
+        processor: &dyn PaymentProcessor,
+        payment_id: &str,
+    ) -> Option<Money> {
+        // Without a loyalty program, don't ask the processor at all
+        self.loyalty.as_ref()?;
+        processor.paid_with_points(payment_id)
+    }
+
+    /// The "order placed" notice, in the configured locale if there is one
+    fn placed_notice(&self, order: &Order) -> OrderPlacedNotice {
+        match &self.translations {
+            Some((translations, locale)) => {
+                OrderPlacedNotice::from_order_in(order, translations, locale)
+            }
+            None => OrderPlacedNotice::from_order(order),
+        }
+    }
+
+    /// Refuse items the shelves can't make (no-op without inventory)
+    ///
+    /// Checked when an order is built, and again before a stored order is
+    /// charged: other orders may have used the stock in between.
+    fn check_stock(&self, items: &[OrderItem]) -> Result<(), OrderServiceError> {
+        if let Some(inventory) = &self.inventory {
+            inventory
+                .check(items)
+                .map_err(OrderServiceError::StockUnavailable)?;
+        }
+        Ok(())
+    }
+
+    /// Take a paid order's ingredients off the shelves (no-op without inventory)
+    ///
+    /// The order is paid and saved already: a stock that can't be updated is
+    /// a warning, not a reason to fail it.
+    fn consume_stock(&mut self, order: &Order) {
+        if let Some(inventory) = &mut self.inventory
+            && let Err(e) = inventory.consume(&order.items)
+        {
+            eprintln!("Warning: Failed to update stock for order {}: {}", order.id, e);
+        }
+    }
+
+    /// Mark a charged order paid by `method` (`points` of it with loyalty points)
+    /// and number its receipt; the caller refunds on error
+    fn mark_paid(
+        &mut self,
+        order: &mut Order,
+        payment_id: &str,
+        method: String,
+        points: Option<Money>,
+    ) -> Result<(), OrderServiceError> {
+        self.stamped(order, |order| order.mark_as_paid(payment_id.to_string()))?;
+        order.payment_method = Some(method);
+        if let Some(points) = points {
+            order.paid_with_points = points;
+        }
+        self.issue_fiscal_number(order)
+    }
+
+    /// Credit a paid order's loyalty points (no-op without a loyalty program)
+    ///
+    /// Like stock, a warning if it fails: the order is paid and saved already.
+    fn accrue_points(&mut self, order: &Order) {
+        if let Some(loyalty) = &mut self.loyalty
+            && let Err(e) = loyalty.accrue(order)
+        {
+            eprintln!("Warning: Failed to credit points for order {}: {}", order.id, e);
+        }
+    }
+
+    /// This service's "now": its clock's (`with_clock`), or the system's
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.as_ref().map_or_else(Utc::now, |clock| clock.now())
+    }
+
+    /// Make a status change, dated by this service's clock
+    fn stamped(
+        &self,
+        order: &mut Order,
+        change: impl FnOnce(&mut Order) -> Result<(), InvalidTransition>,
+    ) -> Result<(), OrderServiceError> {
+        stamped_at(order, self.now(), change)
+    }
+
+    /// Take back the points a refund had earned (no-op without a loyalty program)
+    fn reverse_points(&mut self, order: &Order, refunded: Money) {
+        if let Some(loyalty) = &mut self.loyalty
+            && let Err(e) = loyalty.reverse(order, refunded)
+        {
+            eprintln!("Warning: Failed to take back points for order {}: {}", order.id, e);
+        }
+    }
+
+    /// Give a paid order the next fiscal receipt number (no-op without registration)
+    fn issue_fiscal_number(&mut self, order: &mut Order) -> Result<(), OrderServiceError> {
+        if let Some(fiscal) = &mut self.fiscal {
+            let number = fiscal
+                .numbers
+                .next_number()
+                .map_err(OrderServiceError::FiscalNumberingFailed)?;
+            order.fiscal_number = Some(number);
+            order.seller_tax_id = Some(fiscal.tax_id.clone());
+        }
+        Ok(())
+    }
+
+    /// Validate beverages and turn them into a Pending order
+    fn build_order(
+        &self,
+        customer: Customer,
+        lines: Vec<OrderLine>,
+    ) -> Result<Order, OrderServiceError> {
+        // Validate order
+        if lines.is_empty() {
+            return Err(OrderServiceError::InvalidOrder(
+                "Order must contain at least one item".to_string(),
+            ));
+        }
+        if let Some(line) = lines.iter().find(|line| line.quantity == 0) {
+            return Err(OrderServiceError::InvalidOrder(format!(
+                "Quantity of {} must be at least 1",
+                line.beverage.name()
+            )));
+        }
+
+        let book = self
+            .price_books
+            .as_ref()
+            .and_then(|books| books.effective_at(self.now()));
+        let lines: Vec<OrderLine> = match book {
+            Some(book) => lines
+                .into_iter()
+                .map(|line| OrderLine::new(book.apply(line.beverage), line.quantity))
+                .collect(),
+            None => lines,
+        };
+        let lines = self.apply_loyalty_discount(&customer.email, lines);
+
+        // Create order items from beverages
+        let items: Vec<OrderItem> = lines
+            .iter()
+            .map(|line| {
+                let b = line.beverage.as_ref();
+                OrderItem {
+                    quantity: line.quantity,
+                    prep_seconds: Some(self.prep_times.estimate(b).num_seconds() as u32),
+                    vat_rate: self.vat_rates.as_ref().map(|rates| rates.rate_for(&b.name())),
+                    ..OrderItem::of(b)
+                }
+            })
+            .collect();
+
+        self.check_stock(&items)?;
+
+        let mut order = Order::placed_at(customer, items, self.now());
+        order.price_book_version = book.map(|book| book.version);
         Ok(order)
     }
 
+    /// Take the customer's tier discount off each beverage (members only)
+    fn apply_loyalty_discount(
+        &self,
+        customer_email: &str,
+        lines: Vec<OrderLine>,
+    ) -> Vec<OrderLine> {
+        let Some(loyalty) = &self.loyalty else {
+            return lines;
+        };
+        let tier = match loyalty.tier(customer_email) {
+            Ok(Some(tier)) if tier.discount_percent > 0.0 => tier,
+            Ok(_) => return lines,
+            Err(e) => {
+                eprintln!("Warning: No loyalty discount, accounts unavailable: {}", e);
+                return lines;
+            }
+        };
+        let label = format!("{} member -{}%", tier.name, tier.discount_percent);
+        lines
+            .into_iter()
+            .map(|line| {
+                let discount = line.beverage.price().scaled(tier.discount_percent / 100.0);
+                let promoted = Promoted::new(line.beverage, label.clone(), discount);
+                OrderLine::new(Box::new(promoted), line.quantity)
+            })
+            .collect()
+    }
+
     /// Get an order by ID
     pub fn get_order(&self, id: uuid::Uuid) -> Result<Order, OrderServiceError> {
         self.repository
@@ -189,50 +897,180 @@ where
 
     /// List all orders for a customer
     pub fn list_customer_orders(&self, email: &str) -> Result<Vec<Order>, OrderServiceError> {
+        let load = || {
+            self.repository
+                .find_by_customer_email(email)
+                .map_err(OrderServiceError::StorageFailed)
+        };
+
+        match &self.history_cache {
+            Some(cache) => cache.get_or_load(email, load),
+            None => load(),
+        }
+    }
+
+    /// A barista started on a paid order
+    pub fn mark_order_preparing(&mut self, id: uuid::Uuid) -> Result<(), OrderServiceError> {
+        let mut order = self.get_order(id)?;
+
+        self.stamped(&mut order, Order::mark_as_preparing)?;
+
         self.repository
-            .find_by_customer_email(email)
-            .map_err(OrderServiceError::StorageFailed)
+            .update(&order)
+            .map_err(OrderServiceError::StorageFailed)?;
+        self.invalidate_history(&order);
+
+        Ok(())
     }
 
-    /// Mark order as ready and notify customer
+    /// Mark a preparing order as ready and notify customer
     pub fn mark_order_ready(&mut self, id: uuid::Uuid) -> Result<(), OrderServiceError> {
         let mut order = self.get_order(id)?;
 
-        order.mark_as_ready();
+        self.stamped(&mut order, Order::mark_as_ready)?;
 
         self.repository
             .update(&order)
             .map_err(OrderServiceError::StorageFailed)?;
+        self.invalidate_history(&order);
 
         // Send notification (don't fail if notification fails)
-        if let Err(e) = self.notifier.notify_order_ready(&order) {
+        let notice = OrderReadyNotice::from_order(&order);
+        if let Err(e) = self.notifier.notify_order_ready(&notice) {
             eprintln!("Warning: Failed to send notification: {}", e);
         }
 
+        self.publish(OrderReady {
+            order_id: order.id,
+            ready_at: order.status_since(),
+        });
+        self.emit(DomainEvent::ready(&order));
+        self.observe(ORDER_PREP_SECONDS, order.prep_duration());
+
+        Ok(())
+    }
+
+    /// Hand a ready order over to the customer
+    ///
+    /// Only Ready orders can be completed; anything else is left as it was
+    /// (InvalidTransition). Subscribers (FeedbackService) hear about it
+    /// through OrderCompleted.
+    pub fn complete_order(&mut self, id: uuid::Uuid) -> Result<(), OrderServiceError> {
+        let mut order = self.get_order(id)?;
+
+        self.stamped(&mut order, Order::mark_as_completed)?;
+
+        self.repository
+            .update(&order)
+            .map_err(OrderServiceError::StorageFailed)?;
+        self.invalidate_history(&order);
+
+        self.publish(OrderCompleted {
+            order_id: order.id,
+            customer_name: order.customer.name.clone(),
+            customer_email: order.customer.email.clone(),
+            completed_at: order.status_since(),
+        });
+
         Ok(())
     }
 
     /// Cancel an order
+    ///
+    /// A paid order not handed over yet (Paid, Preparing, Ready) gets back what
+    /// wasn't refunded already (`left_to_refund`) through this service's
+    /// processor and becomes Refunded, and the loyalty points that refund had
+    /// earned are taken back. One refunded in full before is just cancelled.
+    ///
+    /// The refund goes back through the processor that took the payment: an
+    /// order paid by another one (settled at the counter) is refused with
+    /// `RefundElsewhere`, and left as it was.
+    ///
+    /// If the refund went through but the order couldn't be stored, cancelling
+    /// again returns the same refund instead of asking the processor twice.
     pub fn cancel_order(&mut self, id: uuid::Uuid) -> Result<(), OrderServiceError> {
+        self.cancel(id, None)
+    }
+
+    /// Cancel an order paid through `processor` (the counter's, see `settle_order`)
+    pub fn cancel_order_through(
+        &mut self,
+        id: uuid::Uuid,
+        processor: &dyn PaymentProcessor,
+    ) -> Result<(), OrderServiceError> {
+        self.cancel(id, Some(processor))
+    }
+
+    /// Cancel, refunding through `counter`, or this service's processor when None
+    fn cancel(
+        &mut self,
+        id: uuid::Uuid,
+        counter: Option<&dyn PaymentProcessor>,
+    ) -> Result<(), OrderServiceError> {
         let mut order = self.get_order(id)?;
 
-        // Only allow cancelling if not completed
-        if order.status == OrderStatus::Completed {
-            return Err(OrderServiceError::InvalidOrder(
-                "Cannot cancel completed order".to_string(),
-            ));
+        // Completed orders can't be cancelled, nor charged back ones (the bank
+        // decides) or those already cancelled or refunded
+        if !order.status.can_transition_to(&OrderStatus::Cancelled) {
+            return Err(OrderServiceError::InvalidTransition(InvalidTransition {
+                order_id: order.id,
+                from: order.status,
+                to: OrderStatus::Cancelled,
+            }));
         }
 
-        order.cancel();
+        // A paid order is refunded before it is cancelled: if the refund fails,
+        // the order stays as it was
+        let left = order.left_to_refund();
+        let refund = order.payment_id.clone().filter(|_| order.is_refundable() && !left.is_zero());
+        let key = (order.id, order.refunded_total.cents);
+        let refunded = refund.is_some();
+        let refund_id = match refund {
+            Some(payment_id) => {
+                let processor = counter.unwrap_or(&self.payment_processor);
+                // Orders stored before the method was recorded refund as before
+                if let Some(method) = &order.payment_method
+                    && method != processor.payment_method_name()
+                {
+                    return Err(OrderServiceError::RefundElsewhere(method.clone()));
+                }
+                let refund_id = match self.cancel_refunds.get(&key) {
+                    Some(refund_id) => refund_id.clone(),
+                    None => processor
+                        .refund_with_key(&payment_id, left, &refund_key(&order))
+                        .map_err(OrderServiceError::PaymentFailed)?,
+                };
+                self.cancel_refunds.insert(key, refund_id.clone());
+                order.record_refund(left);
+                Some(refund_id)
+            }
+            None => None,
+        };
+        self.stamped(&mut order, |order| match refund_id {
+            Some(refund_id) => order.mark_refunded(refund_id),
+            None => order.cancel(),
+        })?;
 
         self.repository
             .update(&order)
             .map_err(OrderServiceError::StorageFailed)?;
+        self.cancel_refunds.remove(&key);
+        self.invalidate_history(&order);
+        if refunded {
+            self.reverse_points(&order, left);
+        }
 
-        if let Err(e) = self.notifier.notify_order_cancelled(&order) {
+        let notice = OrderCancelledNotice::from_order(&order);
+        if let Err(e) = self.notifier.notify_order_cancelled(&notice) {
             eprintln!("Warning: Failed to send notification: {}", e);
         }
 
+        self.publish(OrderCancelled {
+            order_id: order.id,
+            cancelled_at: order.status_since(),
+        });
+        self.emit(DomainEvent::cancelled(&order));
+
         Ok(())
     }
 
@@ -242,20 +1080,168 @@ where
             .list_all()
             .map_err(OrderServiceError::StorageFailed)
     }
-}
 
-// ============================================================================
-// KEY INSIGHT: How DIP Enables Testing
-// 
-// Because OrderService is generic over traits, we can easily test it:
-// 
-// #[test]
-// fn test_place_order() {
-//     let repository = MemoryOrderRepository::new();
-//     let payment = MockPaymentProcessor { should_fail: false };
-//     let notifier = MockNotifier;
-//     
-//     let mut service = OrderService::new(repository, payment, notifier);
+    /// Flag ready orders nobody picked up and notify their customers
+    ///
+    /// SOLID (SRP): The policy decides WHAT is expired, the clock says WHEN
+    /// "now" is. This method only coordinates: update storage, then notify.
+    ///
+    /// Returns the orders that were flagged during this run.
+    pub fn expire_no_shows(
+        &mut self,
+        policy: &NoShowPolicy,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Order>, OrderServiceError> {
+        let now = clock.now();
+        let mut flagged = Vec::new();
+
+        for mut order in self.list_all_orders()? {
+            if !policy.is_expired(&order, now) {
+                continue;
+            }
+
+            stamped_at(&mut order, now, Order::mark_as_no_show)?;
+
+            self.repository
+                .update(&order)
+                .map_err(OrderServiceError::StorageFailed)?;
+            self.invalidate_history(&order);
+
+            // Nothing goes back on the shelves: the ingredients were taken
+            // when the order was paid, and the drink was made.
+
+            let notice = OrderNoShowNotice::from_order(&order);
+            if let Err(e) = self.notifier.notify_order_no_show(&notice) {
+                eprintln!("Warning: Failed to send notification: {}", e);
+            }
+
+            self.publish(OrderNoShow {
+                order_id: order.id,
+                flagged_at: order.status_since(),
+            });
+
+            flagged.push(order);
+        }
+
+        Ok(flagged)
+    }
+
+    /// Tag orders stuck in Paid or Preparing past the SLA
+    ///
+    /// Returns the newly tagged orders. The customer is not notified: who
+    /// hears about a breach is the caller's choice (see SlaWatchdogJob).
+    pub fn flag_sla_breaches(
+        &mut self,
+        policy: &SlaPolicy,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Order>, OrderServiceError> {
+        let now = clock.now();
+        let mut flagged = Vec::new();
+
+        for mut order in self.list_all_orders()? {
+            if !policy.is_breached(&order, now) {
+                continue;
+            }
+
+            order.flag_sla_breach(now);
+
+            self.repository
+                .update(&order)
+                .map_err(OrderServiceError::StorageFailed)?;
+            self.invalidate_history(&order);
+
+            flagged.push(order);
+        }
+
+        Ok(flagged)
+    }
+
+    /// Send a duration to the metrics backend, if one is attached
+    fn observe(&self, metric: &'static str, duration: Option<Duration>) {
+        if let (Some(metrics), Some(duration)) = (&self.metrics, duration) {
+            metrics.observe(metric, duration.num_milliseconds() as f64 / 1000.0);
+        }
+    }
+
+    /// Drop the cached history of the order's customer
+    fn invalidate_history(&self, order: &Order) {
+        if let Some(cache) = &self.history_cache {
+            cache.invalidate(&order.customer.email);
+        }
+    }
+
+    /// Send a message to bus subscribers, if a bus is attached
+    fn publish<M: Message>(&self, message: M) {
+        if let Some(bus) = &self.bus {
+            bus.publish(message);
+        }
+    }
+
+    /// Hand a domain event to every publisher
+    fn emit(&self, event: DomainEvent) {
+        for publisher in &self.event_publishers {
+            if let Err(e) = publisher.publish(&event) {
+                eprintln!("Warning: Failed to publish {}: {}", event.name(), e);
+            }
+        }
+    }
+
+    /// Daily report of orders flagged as no-shows
+    pub fn no_show_report(&self, date: NaiveDate) -> Result<NoShowReport, OrderServiceError> {
+        let orders = self.list_all_orders()?;
+        Ok(NoShowReport::for_day(&orders, date))
+    }
+}
+
+/// Make a status change dated `at` (the domain dates it with the system clock)
+fn stamped_at(
+    order: &mut Order,
+    at: DateTime<Utc>,
+    change: impl FnOnce(&mut Order) -> Result<(), InvalidTransition>,
+) -> Result<(), OrderServiceError> {
+    let before = order.status_history.len();
+    change(order).map_err(OrderServiceError::InvalidTransition)?;
+    for stamp in &mut order.status_history[before..] {
+        stamp.at = at;
+    }
+    Ok(())
+}
+
+/// Idempotency key of the next refund on `order`: the same until that refund
+/// is recorded (`record_refund`), so a retry repeats it and the next one doesn't
+fn refund_key(order: &Order) -> String {
+    format!("refund-{}-{}", order.id, order.refunded_total.cents)
+}
+
+/// Give the money back for an order that was charged but can't be kept
+fn refund_unsaved(processor: &dyn PaymentProcessor, order: &Order, payment_id: &str) {
+    let refunded =
+        processor.refund_with_key(payment_id, order.amount_charged(), &refund_key(order));
+    if let Err(refund) = refunded {
+        eprintln!(
+            "Warning: Order {} was charged ({}) but not saved, refund failed: {}",
+            order.id, payment_id, refund
+        );
+    }
+}
+
+/// One of each beverage
+fn single_lines(beverages: Vec<Box<dyn Beverage>>) -> Vec<OrderLine> {
+    beverages.into_iter().map(OrderLine::single).collect()
+}
+
+// ============================================================================
+// KEY INSIGHT: How DIP Enables Testing
+// 
+// Because OrderService is generic over traits, we can easily test it:
+// 
+// #[test]
+// fn test_place_order() {
+//     let repository = MemoryOrderRepository::new();
+//     let payment = MockPaymentProcessor { should_fail: false };
+//     let notifier = MockNotifier;
+//     
+//     let mut service = OrderService::new(repository, payment, notifier);
 //     
 //     let customer = Customer::new(...);
 //     let beverages = vec![Box::new(Coffee { ... })];
@@ -276,7 +1262,7 @@ where
 // pub struct BitcoinPayment;
 // 
 // impl PaymentProcessor for BitcoinPayment {
-//     fn process_payment(&self, amount: f64) -> Result<String, PaymentError> {
+//     fn process_payment(&self, amount: Money) -> Result<String, PaymentError> {
 //         // Bitcoin payment logic
 //         Ok(format!("BTC-{}", Uuid::new_v4()))
 //     }
@@ -316,14 +1302,17 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Coffee, Size};
-    use crate::adapters::{MemoryOrderRepository, CashPayment, ConsoleNotifier};
+    use crate::domain::{Coffee, Money, Size};
+    use crate::adapters::{
+        CashPayment, ConsoleNotifier, FixedClock, InMemoryMetrics, MemoryOrderRepository,
+    };
+    use chrono::{Duration, Utc};
 
     #[test]
     fn test_place_order_success() {
         let repository = MemoryOrderRepository::new();
         let payment = CashPayment;
-        let notifier = ConsoleNotifier;
+        let notifier = ConsoleNotifier::new();
         let mut service = OrderService::new(repository, payment, notifier);
 
         let customer = Customer::new(
@@ -348,7 +1337,7 @@ mod tests {
     fn test_place_order_empty_fails() {
         let repository = MemoryOrderRepository::new();
         let payment = CashPayment;
-        let notifier = ConsoleNotifier;
+        let notifier = ConsoleNotifier::new();
         let mut service = OrderService::new(repository, payment, notifier);
 
         let customer = Customer::new(
@@ -361,4 +1350,869 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_place_order_lines_charges_each_quantity() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier);
+        let customer = Customer::named("Ada");
+
+        let lines = vec![
+            OrderLine::new(Box::new(Coffee::medium()), 2),
+            OrderLine::single(Box::new(Coffee::small())),
+        ];
+        let order = service.place_order_lines(customer.clone(), lines).unwrap();
+        assert_eq!(order.items.len(), 2);
+        assert_eq!(order.items[0].quantity, 2);
+        assert_eq!(order.total_price, Money::usd(2.0 * 3.50 + 2.80));
+
+        let none = vec![OrderLine::new(Box::new(Coffee::medium()), 0)];
+        let result = service.place_order_lines(customer, none);
+        assert!(matches!(result, Err(OrderServiceError::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_expire_no_shows() {
+        let repository = MemoryOrderRepository::new();
+        let mut service = OrderService::new(repository, CashPayment, ConsoleNotifier::new());
+
+        let customer = Customer::new(
+            "Test User".to_string(),
+            "test@example.com".to_string(),
+            None,
+        );
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+
+        let mut order = service.place_order(customer, beverages).unwrap();
+        order.mark_as_preparing().unwrap();
+        service.repository.update(&order).unwrap();
+        service.mark_order_ready(order.id).unwrap();
+
+        let policy = NoShowPolicy::new(15);
+
+        // 10 minutes later: still waiting at the counter
+        let clock = FixedClock::new(Utc::now() + Duration::minutes(10));
+        assert!(service.expire_no_shows(&policy, &clock).unwrap().is_empty());
+
+        // 20 minutes later: flagged
+        clock.advance(Duration::minutes(10));
+        let flagged = service.expire_no_shows(&policy, &clock).unwrap();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(service.get_order(order.id).unwrap().status, OrderStatus::NoShow);
+
+        let report = service.no_show_report(Utc::now().date_naive()).unwrap();
+        assert_eq!(report.count(), 1);
+    }
+
+    #[test]
+    fn test_orders_are_placed_and_moved_on_the_service_clock() {
+        let opening = Utc::now() - Duration::days(3);
+        let clock = std::sync::Arc::new(FixedClock::new(opening));
+        let mut service = OrderService::new(
+            MemoryOrderRepository::new(),
+            CashPayment,
+            ConsoleNotifier::with_writer(std::io::sink()),
+        )
+        .with_clock(std::sync::Arc::clone(&clock));
+
+        let order = service.place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())]);
+        let order = order.unwrap();
+        assert_eq!(order.created_at, opening);
+        assert!(order.status_history.iter().all(|change| change.at == opening));
+
+        clock.advance(Duration::minutes(5));
+        service.mark_order_preparing(order.id).unwrap();
+        let order = service.get_order(order.id).unwrap();
+        assert_eq!(order.status_since(), opening + Duration::minutes(5));
+    }
+
+    #[test]
+    fn test_submit_then_process() {
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new());
+
+        let customer = Customer::new(
+            "Test User".to_string(),
+            "test@example.com".to_string(),
+            None,
+        );
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+
+        let submitted = service.submit_order(customer, beverages).unwrap();
+        assert_eq!(submitted.status, OrderStatus::Pending);
+        assert!(submitted.payment_id.is_none());
+
+        let processed = service.process_submitted_order(submitted.id).unwrap();
+        assert_eq!(processed.status, OrderStatus::Paid);
+
+        // A second delivery of the same ID must not charge again
+        assert!(service.process_submitted_order(submitted.id).is_err());
+    }
+
+    #[test]
+    fn test_publishes_to_message_bus() {
+        use crate::services::KitchenDisplay;
+
+        let bus = MessageBus::new();
+        let mut kds = KitchenDisplay::subscribe(&bus);
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new())
+                .with_message_bus(bus);
+
+        let customer = Customer::new(
+            "Test User".to_string(),
+            "test@example.com".to_string(),
+            None,
+        );
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+        let order = service.place_order(customer, beverages).unwrap();
+
+        kds.poll();
+        assert_eq!(kds.tickets().len(), 1);
+
+        service.cancel_order(order.id).unwrap();
+        kds.poll();
+        assert!(kds.tickets().is_empty());
+    }
+
+    #[test]
+    fn test_emits_domain_events_to_every_publisher() {
+        use crate::adapters::InMemoryEventPublisher;
+
+        let (analytics, kitchen) = (InMemoryEventPublisher::new(), InMemoryEventPublisher::new());
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier)
+            .with_event_publisher(analytics.clone())
+            .with_event_publisher(kitchen.clone());
+
+        let served = service
+            .place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())])
+            .unwrap();
+        service.mark_order_preparing(served.id).unwrap();
+        service.mark_order_ready(served.id).unwrap();
+        let cancelled = service
+            .place_order(Customer::named("Bob"), vec![Box::new(Coffee::medium())])
+            .unwrap();
+        service.cancel_order(cancelled.id).unwrap();
+
+        assert_eq!(
+            analytics.names(),
+            [
+                "order.placed",
+                "order.paid",
+                "order.ready",
+                "order.placed",
+                "order.paid",
+                "order.cancelled"
+            ]
+        );
+        assert_eq!(kitchen.events(), analytics.events());
+        match &analytics.events()[1] {
+            DomainEvent::OrderPaid { amount, payment_id, .. } => {
+                assert_eq!(*amount, Money::usd(3.5));
+                assert_eq!(Some(payment_id), served.payment_id.as_ref());
+            }
+            other => panic!("expected OrderPaid, got {:?}", other),
+        }
+        assert!(matches!(
+            analytics.events()[5],
+            DomainEvent::OrderCancelled { refunded: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_history_cache_invalidated_by_new_orders() {
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new())
+                .with_history_cache(10);
+
+        let customer = Customer::new(
+            "Test User".to_string(),
+            "test@example.com".to_string(),
+            None,
+        );
+        let coffee = || -> Vec<Box<dyn Beverage>> {
+            vec![Box::new(Coffee {
+                size: Size::Medium,
+                extra_shots: 0,
+            })]
+        };
+
+        service.place_order(customer.clone(), coffee()).unwrap();
+        assert_eq!(service.list_customer_orders("test@example.com").unwrap().len(), 1);
+        assert_eq!(service.list_customer_orders("test@example.com").unwrap().len(), 1);
+
+        // A new order must not be hidden by the cache
+        service.place_order(customer, coffee()).unwrap();
+        assert_eq!(service.list_customer_orders("test@example.com").unwrap().len(), 2);
+
+        let stats = service.history_cache_stats().unwrap();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+    }
+
+    #[test]
+    fn test_processor_receives_order_context() {
+        use std::sync::{Arc, Mutex};
+
+        // Overrides process_request to capture what OrderService sends
+        struct RecordingProcessor(Arc<Mutex<Vec<PaymentRequest>>>);
+        impl PaymentProcessor for RecordingProcessor {
+            fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+                unreachable!("OrderService calls process_request")
+            }
+            fn process_request(&self, request: &PaymentRequest) -> Result<String, PaymentError> {
+                self.0.lock().unwrap().push(request.clone());
+                Ok("TEST-1".to_string())
+            }
+        }
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut service = OrderService::new(
+            MemoryOrderRepository::new(),
+            RecordingProcessor(requests.clone()),
+            ConsoleNotifier::new(),
+        );
+        let customer = Customer::new("Test User".to_string(), "test@example.com".to_string(), None);
+        let customer_id = customer.id;
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+
+        let order = service.place_order(customer, beverages).unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].order_id, order.id);
+        assert_eq!(requests[0].amount, order.total_price);
+        assert_eq!(requests[0].customer_ref, customer_id.to_string());
+        assert_eq!(requests[0].idempotency_key, format!("order-{}", order.id));
+    }
+
+    #[test]
+    fn test_redirect_payment_waits_for_confirmation() {
+        // The provider's side: one checkout, approved once the customer is back
+        struct Redirect;
+        impl PaymentProcessor for Redirect {
+            fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+                unreachable!("redirect payments are initiated, then confirmed")
+            }
+            fn initiate(&self, _request: &PaymentRequest) -> Result<PendingPayment, PaymentError> {
+                Ok(PendingPayment {
+                    token: "EC-1".to_string(),
+                    approval_url: "https://pay.example.com/approve?token=EC-1".to_string(),
+                })
+            }
+            fn confirm(&self, token: &str) -> Result<String, PaymentError> {
+                Ok(format!("PAYPAL-{}", token))
+            }
+        }
+
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), Redirect, notifier);
+        let lines = vec![OrderLine::single(Box::new(Coffee::medium()))];
+
+        let (order, pending) = service.initiate_order(Customer::named("Ada"), lines).unwrap();
+        assert_eq!(order.status, OrderStatus::AwaitingPayment);
+        assert_eq!(pending.approval_url, "https://pay.example.com/approve?token=EC-1");
+        let stored = service.get_order(order.id).unwrap();
+        assert_eq!(stored.payment_token.as_deref(), Some("EC-1"));
+        assert_eq!(stored.payment_id, None);
+
+        assert!(service.confirm_payment(order.id, "EC-2").is_err());
+        let paid = service.confirm_payment(order.id, &pending.token).unwrap();
+        assert_eq!(paid.status, OrderStatus::Paid);
+        assert_eq!(paid.payment_id.as_deref(), Some("PAYPAL-EC-1"));
+        assert!(matches!(
+            service.confirm_payment(order.id, &pending.token),
+            Err(OrderServiceError::InvalidOrder(_))
+        ));
+    }
+
+    #[test]
+    fn test_pay_at_counter_queues_by_policy() {
+        use crate::adapters::CreditCardPayment;
+        use crate::services::KitchenDisplay;
+
+        let bus = MessageBus::new();
+        let mut kds = KitchenDisplay::subscribe(&bus);
+        let card = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), card, notifier)
+            .with_message_bus(bus);
+        let lines = || vec![OrderLine::single(Box::new(Coffee::medium()))];
+
+        let held = service.place_unpaid_order(Customer::named("Ada"), lines()).unwrap();
+        assert_eq!(held.status, OrderStatus::Pending);
+        kds.poll();
+        assert!(kds.tickets().is_empty());
+        let paid = service.settle_order(held.id, &CashPayment).unwrap();
+        assert_eq!(paid.status, OrderStatus::Paid);
+        assert!(paid.payment_id.unwrap().starts_with("CASH"));
+        kds.poll();
+        assert_eq!(kds.tickets().len(), 1);
+
+        let mut service = service.with_unpaid_orders(UnpaidOrders::Queue);
+        let queued = service.place_unpaid_order(Customer::named("Bob"), lines()).unwrap();
+        kds.poll();
+        assert_eq!(kds.tickets().len(), 2);
+        service.settle_order(queued.id, &CashPayment).unwrap();
+        kds.poll();
+        assert_eq!(kds.tickets().len(), 2);
+        assert!(matches!(
+            service.settle_order(queued.id, &CashPayment),
+            Err(OrderServiceError::InvalidOrder(_))
+        ));
+    }
+
+    #[test]
+    fn test_counter_payments_are_refunded_at_the_counter() {
+        use crate::adapters::CreditCardPayment;
+
+        let card = CreditCardPayment::new("https://payment-gateway.example.com".to_string());
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), card, notifier);
+        let lines = vec![OrderLine::single(Box::new(Coffee::medium()))];
+        let order = service.place_unpaid_order(Customer::named("Ada"), lines).unwrap();
+        let paid = service.settle_order(order.id, &CashPayment).unwrap();
+        assert_eq!(paid.payment_method.as_deref(), Some("Cash"));
+
+        // Not through the card gateway: it never saw this payment
+        assert!(matches!(
+            service.cancel_order(order.id),
+            Err(OrderServiceError::RefundElsewhere(method)) if method == "Cash"
+        ));
+        assert_eq!(service.get_order(order.id).unwrap().status, OrderStatus::Paid);
+        service.cancel_order_through(order.id, &CashPayment).unwrap();
+        let refunded = service.get_order(order.id).unwrap();
+        assert_eq!(refunded.status, OrderStatus::Refunded);
+        assert!(refunded.refund_id.unwrap().starts_with("CASH-REFUND-"));
+    }
+
+    #[test]
+    fn test_failed_save_refunds_the_charge() {
+        use std::sync::{Arc, Mutex};
+
+        struct FullDisk;
+        impl OrderRepository for FullDisk {
+            fn save(&mut self, _order: &Order) -> Result<(), RepositoryError> {
+                Err(RepositoryError::SaveFailed("disk full".to_string()))
+            }
+            fn find_by_id(&self, _id: uuid::Uuid) -> Result<Option<Order>, RepositoryError> {
+                Ok(None)
+            }
+            fn find_by_customer_email(&self, _email: &str) -> Result<Vec<Order>, RepositoryError> {
+                Ok(Vec::new())
+            }
+            fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+                Ok(Vec::new())
+            }
+            fn update(&mut self, _order: &Order) -> Result<(), RepositoryError> {
+                Err(RepositoryError::SaveFailed("disk full".to_string()))
+            }
+            fn delete(&mut self, _id: uuid::Uuid) -> Result<bool, RepositoryError> {
+                Ok(false)
+            }
+        }
+
+        // payment ID -> refunded amount
+        struct RefundLog(Arc<Mutex<Vec<(String, Money)>>>);
+        impl PaymentProcessor for RefundLog {
+            fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+                Ok("TEST-1".to_string())
+            }
+            fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+                self.0.lock().unwrap().push((payment_id.to_string(), amount));
+                Ok("TEST-REFUND-1".to_string())
+            }
+        }
+
+        let refunds = Arc::new(Mutex::new(Vec::new()));
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(FullDisk, RefundLog(refunds.clone()), notifier);
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+
+        let result = service.place_order(customer, beverages);
+
+        assert!(matches!(result, Err(OrderServiceError::StorageFailed(_))));
+        assert_eq!(*refunds.lock().unwrap(), [("TEST-1".to_string(), Money::usd(3.50))]);
+    }
+
+    /// Saves new orders, but fails every update: a charge can't be recorded
+    struct UpdatesFail(MemoryOrderRepository);
+
+    impl OrderRepository for UpdatesFail {
+        fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+            self.0.save(order)
+        }
+        fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Order>, RepositoryError> {
+            self.0.find_by_id(id)
+        }
+        fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+            self.0.find_by_customer_email(email)
+        }
+        fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+            self.0.list_all()
+        }
+        fn update(&mut self, _order: &Order) -> Result<(), RepositoryError> {
+            Err(RepositoryError::SaveFailed("disk full".to_string()))
+        }
+        fn delete(&mut self, id: uuid::Uuid) -> Result<bool, RepositoryError> {
+            self.0.delete(id)
+        }
+    }
+
+    /// Charges (directly or through a redirect) and logs payment ID -> refunded amount
+    #[derive(Clone, Default)]
+    struct Refunds(std::sync::Arc<std::sync::Mutex<Vec<(String, Money)>>>);
+
+    impl Refunds {
+        fn taken(&self) -> Vec<(String, Money)> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl PaymentProcessor for Refunds {
+        fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+            Ok("TEST-1".to_string())
+        }
+        fn initiate(&self, _request: &PaymentRequest) -> Result<PendingPayment, PaymentError> {
+            Ok(PendingPayment {
+                token: "EC-1".to_string(),
+                approval_url: "https://pay.example.com/approve?token=EC-1".to_string(),
+            })
+        }
+        fn confirm(&self, token: &str) -> Result<String, PaymentError> {
+            Ok(format!("PAYPAL-{}", token))
+        }
+        fn refund(&self, payment_id: &str, amount: Money) -> Result<String, PaymentError> {
+            self.0.lock().unwrap().push((payment_id.to_string(), amount));
+            Ok("TEST-REFUND-1".to_string())
+        }
+    }
+
+    #[test]
+    fn test_failed_update_refunds_submitted_and_confirmed_charges() {
+        let refunds = Refunds::default();
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let repository = UpdatesFail(MemoryOrderRepository::new());
+        let mut service = OrderService::new(repository, refunds.clone(), notifier);
+
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee::medium())];
+        let submitted = service.submit_order(Customer::named("Ada"), beverages).unwrap();
+        let result = service.process_submitted_order(submitted.id);
+        assert!(matches!(result, Err(OrderServiceError::StorageFailed(_))));
+        assert_eq!(refunds.taken(), [("TEST-1".to_string(), Money::usd(3.50))]);
+
+        let lines = vec![OrderLine::single(Box::new(Coffee::medium()))];
+        let (order, pending) = service.initiate_order(Customer::named("Bob"), lines).unwrap();
+        let result = service.confirm_payment(order.id, &pending.token);
+        assert!(matches!(result, Err(OrderServiceError::StorageFailed(_))));
+        assert_eq!(refunds.taken()[1], ("PAYPAL-EC-1".to_string(), Money::usd(3.50)));
+    }
+
+    #[test]
+    fn test_failed_update_refunds_a_counter_payment_through_the_counter() {
+        let own = Refunds::default();
+        let counter = Refunds::default();
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let repository = UpdatesFail(MemoryOrderRepository::new());
+        let mut service = OrderService::new(repository, own.clone(), notifier);
+        let lines = vec![OrderLine::single(Box::new(Coffee::medium()))];
+
+        let order = service.place_unpaid_order(Customer::named("Ada"), lines).unwrap();
+        let result = service.settle_order(order.id, &counter);
+
+        assert!(matches!(result, Err(OrderServiceError::StorageFailed(_))));
+        assert_eq!(counter.taken(), [("TEST-1".to_string(), Money::usd(3.50))]);
+        assert!(own.taken().is_empty());
+    }
+
+    #[test]
+    fn test_cancelling_refunds_only_what_is_left() {
+        let refunds = Refunds::default();
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), refunds.clone(), notifier);
+        let coffee = || -> Vec<Box<dyn Beverage>> { vec![Box::new(Coffee::medium())] };
+
+        // 1.00 of the 3.50 given back already (a complaint, say)
+        let mut partly = service.place_order(Customer::named("Ada"), coffee()).unwrap();
+        partly.record_refund(Money::usd(1.0));
+        service.repository.update(&partly).unwrap();
+        service.cancel_order(partly.id).unwrap();
+        let refunded = service.get_order(partly.id).unwrap();
+        assert_eq!(refunded.status, OrderStatus::Refunded);
+        assert_eq!(refunded.refunded_total, Money::usd(3.50));
+        assert_eq!(refunds.taken(), [("TEST-1".to_string(), Money::usd(2.50))]);
+
+        // All of it given back already: nothing more to refund
+        let mut fully = service.place_order(Customer::named("Bob"), coffee()).unwrap();
+        fully.record_refund(Money::usd(3.50));
+        service.repository.update(&fully).unwrap();
+        service.cancel_order(fully.id).unwrap();
+        assert_eq!(service.get_order(fully.id).unwrap().status, OrderStatus::Cancelled);
+        assert_eq!(refunds.taken().len(), 1);
+    }
+
+    #[test]
+    fn test_cancel_retried_after_a_failed_update_refunds_once() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        /// Fails updates while the flag is set
+        struct Flaky(MemoryOrderRepository, Arc<AtomicBool>);
+        impl OrderRepository for Flaky {
+            fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+                self.0.save(order)
+            }
+            fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Order>, RepositoryError> {
+                self.0.find_by_id(id)
+            }
+            fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+                self.0.find_by_customer_email(email)
+            }
+            fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+                self.0.list_all()
+            }
+            fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+                if self.1.load(Ordering::SeqCst) {
+                    return Err(RepositoryError::SaveFailed("disk full".to_string()));
+                }
+                self.0.update(order)
+            }
+            fn delete(&mut self, id: uuid::Uuid) -> Result<bool, RepositoryError> {
+                self.0.delete(id)
+            }
+        }
+
+        let (refunds, disk_full) = (Refunds::default(), Arc::new(AtomicBool::new(false)));
+        let repository = Flaky(MemoryOrderRepository::new(), disk_full.clone());
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(repository, refunds.clone(), notifier);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee::medium())];
+        let order = service.place_order(Customer::named("Ada"), beverages).unwrap();
+
+        disk_full.store(true, Ordering::SeqCst);
+        let failed = service.cancel_order(order.id);
+        assert!(matches!(failed, Err(OrderServiceError::StorageFailed(_))));
+        disk_full.store(false, Ordering::SeqCst);
+        service.cancel_order(order.id).unwrap();
+
+        assert_eq!(refunds.taken(), [("TEST-1".to_string(), Money::usd(3.50))]);
+        let refunded = service.get_order(order.id).unwrap();
+        assert_eq!(refunded.refund_id.as_deref(), Some("TEST-REFUND-1"));
+        assert_eq!(refunded.refunded_total, Money::usd(3.50));
+    }
+
+    #[test]
+    fn test_cancelling_a_paid_order_refunds_it() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier);
+        let coffee = || -> Vec<Box<dyn Beverage>> { vec![Box::new(Coffee::medium())] };
+
+        let paid = service.place_order(Customer::named("Ada"), coffee()).unwrap();
+        service.cancel_order(paid.id).unwrap();
+        let refunded = service.get_order(paid.id).unwrap();
+        assert_eq!(refunded.status, OrderStatus::Refunded);
+        assert!(refunded.refund_id.unwrap().starts_with("CASH-REFUND-"));
+        assert!(service.cancel_order(paid.id).is_err());
+
+        let unpaid = vec![OrderLine::single(Box::new(Coffee::medium()))];
+        let unpaid = service.place_unpaid_order(Customer::named("Ada"), unpaid).unwrap();
+        service.cancel_order(unpaid.id).unwrap();
+        let cancelled = service.get_order(unpaid.id).unwrap();
+        assert_eq!((cancelled.status, cancelled.refund_id), (OrderStatus::Cancelled, None));
+
+        // A processor that can't refund: the order stays paid
+        struct NoRefunds;
+        impl PaymentProcessor for NoRefunds {
+            fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+                Ok("TEST-1".to_string())
+            }
+        }
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), NoRefunds, notifier);
+        let paid = service.place_order(Customer::named("Ada"), coffee()).unwrap();
+        assert!(matches!(
+            service.cancel_order(paid.id),
+            Err(OrderServiceError::PaymentFailed(_))
+        ));
+        assert_eq!(service.get_order(paid.id).unwrap().status, OrderStatus::Paid);
+    }
+
+    #[test]
+    fn test_latencies_reach_the_metrics_port() {
+        let metrics = InMemoryMetrics::new();
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new())
+                .with_metrics(metrics.clone());
+
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Small,
+            extra_shots: 0,
+        })];
+        let mut order = service.place_order(customer, beverages).unwrap();
+        order.mark_as_preparing().unwrap();
+        service.repository.update(&order).unwrap();
+        service.mark_order_ready(order.id).unwrap();
+
+        assert_eq!(metrics.samples(ORDER_PLACEMENT_SECONDS).len(), 1);
+        assert_eq!(metrics.samples(ORDER_PREP_SECONDS).len(), 1);
+        assert!(metrics.samples(ORDER_PREP_SECONDS)[0] >= 0.0);
+    }
+
+    #[test]
+    fn test_items_carry_prep_estimates() {
+        let prep_times = PrepTimeModel::new().with_base("Coffee", 60);
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new())
+                .with_prep_times(prep_times);
+
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 1,
+        })];
+        let order = service.place_order(customer, beverages).unwrap();
+
+        // Overridden base (60 s) + one extra shot (20 s)
+        assert_eq!(order.items[0].prep_seconds, Some(80));
+        assert_eq!(order.prep_time_estimate(), Duration::seconds(80));
+    }
+
+    #[test]
+    fn test_fiscal_receipts_are_numbered_in_sequence() {
+        use crate::adapters::MemoryFiscalNumbers;
+
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new())
+                .with_vat_rates(VatRates::new(0.081))
+                .with_fiscal_receipts(
+                    "CHE-123.456.789 MWST",
+                    MemoryFiscalNumbers::continuing_from(7),
+                );
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let coffee = || -> Vec<Box<dyn Beverage>> {
+            vec![Box::new(Coffee {
+                size: Size::Medium,
+                extra_shots: 0,
+            })]
+        };
+
+        let first = service.place_order(customer.clone(), coffee()).unwrap();
+        let submitted = service.submit_order(customer, coffee()).unwrap();
+        assert_eq!(submitted.fiscal_number, None);
+        let second = service.process_submitted_order(submitted.id).unwrap();
+
+        assert_eq!(first.fiscal_number, Some(8));
+        assert_eq!(second.fiscal_number, Some(9));
+        assert_eq!(service.get_order(second.id).unwrap().fiscal_number, Some(9));
+        assert_eq!(first.seller_tax_id.as_deref(), Some("CHE-123.456.789 MWST"));
+        assert_eq!(first.items[0].vat_rate, Some(0.081));
+    }
+
+    #[test]
+    fn test_failed_fiscal_numbering_keeps_no_order() {
+        struct Unreachable;
+        impl FiscalNumberGenerator for Unreachable {
+            fn next_number(&mut self) -> Result<u64, FiscalError> {
+                Err(FiscalError::CounterFailed("fiscal device offline".to_string()))
+            }
+        }
+
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new())
+                .with_fiscal_receipts("CHE-123.456.789 MWST", Unreachable);
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+
+        let result = service.place_order(customer, beverages);
+
+        assert!(matches!(result, Err(OrderServiceError::FiscalNumberingFailed(_))));
+        assert!(service.list_all_orders().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_orders_take_stock_and_are_refused_without_it() {
+        use crate::adapters::MemoryInventoryRepository;
+        use crate::domain::{StockLevel, Tea, TeaVariety};
+
+        let espresso = StockLevel::new("espresso shot", 3.0);
+        let shelves = MemoryInventoryRepository::with_levels([espresso]);
+        let mut service = OrderService::new(
+            MemoryOrderRepository::new(),
+            CashPayment,
+            ConsoleNotifier::with_writer(std::io::sink()),
+        )
+        .with_inventory(InventoryService::new(shelves));
+        let two_coffees = || vec![OrderLine::new(Box::new(Coffee::medium()), 2)];
+
+        let unpaid = service.place_unpaid_order(Customer::named("Alan"), two_coffees()).unwrap();
+        service.place_order_lines(Customer::named("Ada"), two_coffees()).unwrap();
+        let refused = service.place_order_lines(Customer::named("Grace"), two_coffees());
+
+        let Err(OrderServiceError::StockUnavailable(e)) = refused else {
+            panic!("expected StockUnavailable, got {:?}", refused);
+        };
+        assert_eq!(e.to_string(), "Out of stock: espresso shot (2 needed, 1 on hand)");
+        assert_eq!(service.list_all_orders().unwrap().len(), 2);
+        // Ada's order took the shots Alan's was placed with: it isn't charged
+        assert!(matches!(
+            service.settle_order(unpaid.id, &CashPayment),
+            Err(OrderServiceError::StockUnavailable(_))
+        ));
+        assert_eq!(service.get_order(unpaid.id).unwrap().status, OrderStatus::Pending);
+        // Tea takes no espresso
+        let tea = Tea::new(TeaVariety::Green, Size::Medium);
+        let tea = vec![OrderLine::single(Box::new(tea))];
+        service.place_order_lines(Customer::named("Grace"), tea).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_transitions_are_surfaced() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier);
+        let coffee: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee::medium())];
+        let order = service.place_order(Customer::named("Ada"), coffee).unwrap();
+
+        // Paid, not preparing yet: it can't be ready
+        let result = service.mark_order_ready(order.id);
+        let Err(OrderServiceError::InvalidTransition(e)) = result else {
+            panic!("expected InvalidTransition, got {:?}", result);
+        };
+        assert_eq!((e.from, e.to), (OrderStatus::Paid, OrderStatus::Ready));
+        assert_eq!(service.get_order(order.id).unwrap().status, OrderStatus::Paid);
+
+        service.mark_order_preparing(order.id).unwrap();
+        service.mark_order_ready(order.id).unwrap();
+        service.complete_order(order.id).unwrap();
+        assert!(matches!(
+            service.cancel_order(order.id),
+            Err(OrderServiceError::InvalidTransition(_))
+        ));
+        assert_eq!(service.get_order(order.id).unwrap().status, OrderStatus::Completed);
+    }
+
+    #[test]
+    fn test_loyalty_members_get_their_discount_and_points() {
+        use crate::adapters::{MemoryLoyaltyRepository, SharedLoyaltyRepository};
+        use crate::ports::LoyaltyRepository;
+        use crate::services::LoyaltyTier;
+
+        let accounts = SharedLoyaltyRepository::new(MemoryLoyaltyRepository::new());
+        let loyalty = LoyaltyService::new(accounts.clone())
+            .with_tiers(vec![LoyaltyTier::new("Silver", 1, 10.0)]);
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier)
+            .with_loyalty(loyalty);
+        let two_coffees = || vec![OrderLine::new(Box::new(Coffee::medium()), 2)];
+
+        // First order at full price: $7.00, 70 points
+        let first = service.place_order_lines(Customer::named("Ada"), two_coffees()).unwrap();
+        assert_eq!(first.total_price, Money::usd(7.00));
+        // Now Silver: 10% off each coffee
+        let second = service.place_order_lines(Customer::named("Ada"), two_coffees()).unwrap();
+        assert_eq!(second.total_price, Money::usd(6.30));
+
+        let account = accounts.find("ada@example.com").unwrap().unwrap();
+        assert_eq!((account.orders, account.points), (2, 130));
+
+        // Cancelling refunds the $6.30 and takes its 60 points and the order back
+        service.cancel_order(second.id).unwrap();
+        let account = accounts.find("ada@example.com").unwrap().unwrap();
+        assert_eq!((account.orders, account.points), (1, 70));
+    }
+
+    #[test]
+    fn test_the_part_paid_with_points_earns_no_points() {
+        use crate::adapters::{MemoryLoyaltyRepository, PointsPayment, SharedLoyaltyRepository};
+        use crate::domain::LoyaltyAccount;
+        use crate::ports::LoyaltyRepository;
+
+        let mut accounts = SharedLoyaltyRepository::new(MemoryLoyaltyRepository::new());
+        let mut ada = LoyaltyAccount::new("ada@example.com");
+        ada.earn(500);
+        accounts.save(ada).unwrap();
+        let payment = PointsPayment::new(accounts.clone(), CashPayment);
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), payment, notifier)
+            .with_loyalty(LoyaltyService::new(accounts.clone()));
+        let two_coffees = vec![OrderLine::new(Box::new(Coffee::medium()), 2)];
+
+        // $7.00: the 500 points pay $5.00, cash the other $2.00, which earns 20 points
+        let order = service.place_order_lines(Customer::named("Ada"), two_coffees).unwrap();
+        assert_eq!(order.paid_with_points, Money::usd(5.00));
+        let account = accounts.find("ada@example.com").unwrap().unwrap();
+        assert_eq!(account.points, 20);
+
+        // Cancelling gives the 500 points back and takes the 20 earned
+        service.cancel_order(order.id).unwrap();
+        let account = accounts.find("ada@example.com").unwrap().unwrap();
+        assert_eq!(account.points, 500);
+    }
+
+    #[test]
+    fn test_placed_notice_names_beverages_in_the_configured_locale() {
+        use crate::adapters::{read_notification_records, FileNotifier};
+
+        let path = std::env::temp_dir().join(format!("notices-{}.jsonl", uuid::Uuid::new_v4()));
+        let translations = MenuTranslations::new()
+            .with_name("Coffee", "fr", "Café")
+            .with_name("Medium", "fr", "Moyen");
+        let notifier = FileNotifier::new(path.clone());
+        let mut service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier)
+            .with_translations(translations, Locale::parse("fr_CH.UTF-8"));
+
+        let order = service.place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())]);
+
+        // The order itself keeps the English name
+        assert_eq!(order.unwrap().items[0].beverage_description, "Coffee (Medium)");
+        let records = read_notification_records(&path).unwrap();
+        assert_eq!(records[0].notice["items"][0]["description"], "Café (Moyen)");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "mocks")]
+    #[test]
+    fn test_place_order_with_mockall_mocks() {
+        use crate::ports::{MockNotifier, MockPaymentProcessor};
+
+        let mut payment = MockPaymentProcessor::new();
+        payment
+            .expect_process_request()
+            .times(1)
+            .returning(|request| Ok(format!("MOCK-{:.2}", request.amount.to_major())));
+        payment.expect_payment_method_name().return_const("Mock".to_string());
+        let mut notifier = MockNotifier::new();
+        notifier.expect_notify_order_placed().times(1).returning(|_| Ok(()));
+        let mut service = OrderService::new(MemoryOrderRepository::new(), payment, notifier);
+
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })];
+        let order = service.place_order(customer, beverages).unwrap();
+
+        assert_eq!(order.payment_id.as_deref(), Some("MOCK-3.50"));
+        assert_eq!(order.payment_method.as_deref(), Some("Mock"));
+    }
 }
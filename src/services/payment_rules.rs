@@ -0,0 +1,195 @@
+// SOLID: PaymentRules - what each payment method costs and accepts
+//
+// Card fees are a percentage of the sale, so many shops pass them on
+// (a 2% card surcharge) and refuse cards for small amounts (a $5 minimum).
+// Below a dollar, only cash is worth taking at all.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Like RoundingPolicy, the rules don't touch menu prices or order totals:
+// they say whether a method may pay a total, and how much it adds.
+// OrderService checks them before charging and records the surcharge on the
+// order (`surcharge`); the receipt itemizes it and Accounting books it to its
+// own account.
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Methods are matched by `PaymentProcessor::payment_method_name`, and the
+// rules deserialize from JSON, so a shop changes them by configuration alone.
+
+use crate::domain::Money;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// The method every amount may be paid with, however small
+const CASH: &str = "Cash";
+
+/// Surcharge and minimum of one payment method
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MethodRule {
+    /// Share of the total added to the charge (0.02 = 2%)
+    pub surcharge_rate: f64,
+    /// Smallest total this method may pay
+    pub minimum: Option<f64>,
+}
+
+/// Rules per payment method
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaymentRules {
+    /// Payment method name ("Credit Card") -> its rule
+    pub methods: HashMap<String, MethodRule>,
+    /// Totals below this are cash only, whatever the method's own rule
+    pub cash_only_below: Option<f64>,
+}
+
+/// Why a method may not pay a total
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaymentRuleError {
+    BelowMinimum {
+        method: String,
+        minimum: Money,
+        total: Money,
+    },
+}
+
+impl fmt::Display for PaymentRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaymentRuleError::BelowMinimum {
+                method,
+                minimum,
+                total,
+            } => write!(
+                f,
+                "{} is accepted from {} (total: {})",
+                method, minimum, total
+            ),
+        }
+    }
+}
+
+impl Error for PaymentRuleError {}
+
+impl PaymentRules {
+    /// No surcharge or minimum for any method
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 2% surcharge and a 5.00 minimum on cards, cash only under 1.00
+    pub fn typical() -> Self {
+        Self::new()
+            .with_surcharge("Credit Card", 0.02)
+            .with_minimum("Credit Card", 5.0)
+            .with_surcharge("Card (terminal)", 0.02)
+            .with_minimum("Card (terminal)", 5.0)
+            .with_cash_only_below(1.0)
+    }
+
+    /// Add `rate` of the total to charges paid with `payment_method`
+    pub fn with_surcharge(mut self, payment_method: &str, rate: f64) -> Self {
+        self.methods.entry(payment_method.to_string()).or_default().surcharge_rate = rate;
+        self
+    }
+
+    /// Refuse `payment_method` for totals below `minimum`
+    pub fn with_minimum(mut self, payment_method: &str, minimum: f64) -> Self {
+        self.methods.entry(payment_method.to_string()).or_default().minimum = Some(minimum);
+        self
+    }
+
+    /// Refuse every method but cash for totals below `amount`
+    pub fn with_cash_only_below(mut self, amount: f64) -> Self {
+        self.cash_only_below = Some(amount);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty() && self.cash_only_below.is_none()
+    }
+
+    /// The surcharge on `total` paid with `payment_method`, if it may pay it
+    ///
+    /// Rounded to the cent, like cash rounding. Minimums are in the total's currency.
+    pub fn surcharge(
+        &self,
+        payment_method: &str,
+        total: Money,
+    ) -> Result<Money, PaymentRuleError> {
+        let rule = self.methods.get(payment_method);
+        let cash_only = self.cash_only_below.filter(|_| payment_method != CASH);
+        let minimum = rule.and_then(|r| r.minimum).into_iter().chain(cash_only).reduce(f64::max);
+        let minimum = minimum.map(|m| Money::from_major(m, total.currency));
+        if let Some(minimum) = minimum.filter(|m| total < *m) {
+            return Err(PaymentRuleError::BelowMinimum {
+                method: payment_method.to_string(),
+                minimum,
+                total,
+            });
+        }
+        let rate = rule.map_or(0.0, |r| r.surcharge_rate);
+        Ok(total.scaled(rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, CreditCardPayment, MemoryOrderRepository};
+    use crate::domain::{Beverage, Coffee, Customer};
+    use crate::services::{OrderService, OrderServiceError};
+    use std::io;
+
+    #[test]
+    fn test_rules_from_json() {
+        let rules: PaymentRules = serde_json::from_str(
+            r#"{
+                "methods": { "Credit Card": { "surcharge_rate": 0.02, "minimum": 5.0 } },
+                "cash_only_below": 1.0
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(rules.surcharge("Credit Card", Money::usd(12.40)), Ok(Money::cents(25)));
+        assert_eq!(rules.surcharge("Cash", Money::usd(0.50)), Ok(Money::cents(0)));
+        assert!(matches!(
+            rules.surcharge("Credit Card", Money::usd(4.99)),
+            Err(PaymentRuleError::BelowMinimum { minimum, .. }) if minimum == Money::usd(5.0)
+        ));
+        assert!(rules.surcharge("Store Credit", Money::usd(0.80)).is_err());
+        assert_eq!(rules.surcharge("Store Credit", Money::usd(3.00)), Ok(Money::cents(0)));
+    }
+
+    #[test]
+    fn test_order_service_refuses_before_charging_and_records_the_surcharge() {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        // Small coffee 2.80, a large one with a shot 5.10
+        let small = || -> Box<dyn Beverage> { Box::new(Coffee::small()) };
+        let large = || -> Box<dyn Beverage> { Box::new(Coffee::large().with_extra_shots(1)) };
+        let mut card = OrderService::new(
+            MemoryOrderRepository::new(),
+            CreditCardPayment::new("https://payment-gateway.example.com".to_string()),
+            ConsoleNotifier::with_writer(io::sink()),
+        )
+        .with_payment_rules(PaymentRules::typical());
+
+        let refused = card.place_order(customer.clone(), vec![small()]);
+        assert!(matches!(refused, Err(OrderServiceError::PaymentMethodRefused(_))));
+        assert!(card.list_all_orders().unwrap().is_empty());
+
+        let order = card.place_order(customer.clone(), vec![large()]).unwrap();
+        assert_eq!(order.surcharge, Money::usd(0.10));
+        assert_eq!(order.amount_charged(), Money::usd(5.20));
+
+        let mut cash = OrderService::new(
+            MemoryOrderRepository::new(),
+            CashPayment,
+            ConsoleNotifier::with_writer(io::sink()),
+        )
+        .with_payment_rules(PaymentRules::typical());
+        let order = cash.place_order(customer, vec![small()]).unwrap();
+        assert!(order.surcharge.is_zero());
+    }
+}
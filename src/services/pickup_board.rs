@@ -0,0 +1,173 @@
+// SOLID: PickupBoard - the customers' "preparing / ready" screen
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Like the kitchen display, a bus subscriber OrderService has never heard of.
+// It follows OrderPlaced -> OrderReady, and drops an order when it is
+// cancelled or flagged as a no-show.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Owned by front of house: it knows what a waiting customer may see (a short
+// pickup code, never an email address) and reports each change as a
+// `BoardChange`, so a screen can be kept live by pushing only what moved.
+// How changes reach a browser (Server-Sent Events...) is not its concern.
+//
+// A screen without a bus (a static page rewritten by cron) gets the same
+// entries from the stored orders with `board_from_orders`.
+
+use crate::bus::{MessageBus, OrderCancelled, OrderNoShow, OrderPlaced, OrderReady, Subscription};
+use crate::domain::{Order, OrderStatus};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Where an order is shown, or `Gone` once it leaves the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardStatus {
+    Preparing,
+    Ready,
+    Gone,
+}
+
+/// One order moving on the board
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoardChange {
+    pub order_id: Uuid,
+    /// What the customer looks for: the first 4 characters of the order ID
+    pub code: String,
+    pub status: BoardStatus,
+    pub at: DateTime<Utc>,
+}
+
+/// Orders preparing or waiting at the counter, in the order they got there
+pub struct PickupBoard {
+    placed: Subscription<OrderPlaced>,
+    ready: Subscription<OrderReady>,
+    cancelled: Subscription<OrderCancelled>,
+    no_show: Subscription<OrderNoShow>,
+    shown: Vec<BoardChange>,
+}
+
+impl PickupBoard {
+    /// Subscribe to the order topics it needs
+    pub fn subscribe(bus: &MessageBus) -> Self {
+        Self {
+            placed: bus.subscribe(),
+            ready: bus.subscribe(),
+            cancelled: bus.subscribe(),
+            no_show: bus.subscribe(),
+            shown: Vec::new(),
+        }
+    }
+
+    /// Apply every message received since the last poll; return what changed
+    pub fn poll(&mut self) -> Vec<BoardChange> {
+        let mut changes = Vec::new();
+
+        for placed in self.placed.drain() {
+            changes.push(self.show(placed.order_id, BoardStatus::Preparing, placed.placed_at));
+        }
+        for ready in self.ready.drain() {
+            changes.push(self.show(ready.order_id, BoardStatus::Ready, ready.ready_at));
+        }
+
+        let gone = self
+            .cancelled
+            .drain()
+            .into_iter()
+            .map(|c| (c.order_id, c.cancelled_at))
+            .chain(self.no_show.drain().into_iter().map(|n| (n.order_id, n.flagged_at)));
+        for (order_id, at) in gone {
+            if self.shown.iter().any(|entry| entry.order_id == order_id) {
+                self.shown.retain(|entry| entry.order_id != order_id);
+                changes.push(change(order_id, BoardStatus::Gone, at));
+            }
+        }
+
+        changes
+    }
+
+    /// Everything on the board now: what a screen that just connected needs
+    pub fn snapshot(&self) -> &[BoardChange] {
+        &self.shown
+    }
+
+    fn show(&mut self, order_id: Uuid, status: BoardStatus, at: DateTime<Utc>) -> BoardChange {
+        let entry = change(order_id, status, at);
+        self.shown.retain(|shown| shown.order_id != order_id);
+        self.shown.push(entry.clone());
+        entry
+    }
+}
+
+/// The board as the stored orders show it: paid or preparing, then ready
+pub fn board_from_orders(orders: &[Order]) -> Vec<BoardChange> {
+    let mut entries: Vec<BoardChange> = orders
+        .iter()
+        .filter_map(|order| {
+            let status = match order.status {
+                OrderStatus::Paid | OrderStatus::Preparing => BoardStatus::Preparing,
+                OrderStatus::Ready => BoardStatus::Ready,
+                _ => return None,
+            };
+            Some(change(order.id, status, order.status_since()))
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.at);
+    entries
+}
+
+fn change(order_id: Uuid, status: BoardStatus, at: DateTime<Utc>) -> BoardChange {
+    BoardChange {
+        order_id,
+        code: order_id.simple().to_string()[..4].to_uppercase(),
+        status,
+        at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Money;
+
+    fn placed(order_id: Uuid) -> OrderPlaced {
+        OrderPlaced {
+            order_id,
+            customer_email: "test@example.com".to_string(),
+            items: vec![("Coffee (Medium)".to_string(), 1)],
+            total_price: Money::usd(3.50),
+            prep_seconds: 90,
+            placed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_board_reports_each_move() {
+        let bus = MessageBus::new();
+        let mut board = PickupBoard::subscribe(&bus);
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        bus.publish(placed(first));
+        bus.publish(placed(second));
+        let changes = board.poll();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].code, first.simple().to_string()[..4].to_uppercase());
+        assert!(!changes[0].code.contains('@'));
+
+        bus.publish(OrderReady {
+            order_id: first,
+            ready_at: Utc::now(),
+        });
+        bus.publish(OrderCancelled {
+            order_id: second,
+            cancelled_at: Utc::now(),
+        });
+        let statuses: Vec<_> = board.poll().iter().map(|c| c.status).collect();
+        assert_eq!(statuses, [BoardStatus::Ready, BoardStatus::Gone]);
+
+        assert_eq!(board.snapshot().len(), 1);
+        assert_eq!(board.snapshot()[0].status, BoardStatus::Ready);
+        assert!(board.poll().is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+// SOLID: PrepTimeModel - how long each drink takes, with shop-specific overrides
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Every beverage knows its own prep time (Beverage::prep_time_estimate).
+// A shop whose espresso machine is slower, or whose smoothies are pre-cut,
+// overrides the base time per product family in configuration (usually
+// loaded from JSON, like AccountMapping), without touching any beverage type.
+//
+// Customizations and size still apply on top of an overridden base, so
+// "+2 shots" costs the same extra time everywhere.
+//
+// The estimates feed the kitchen: ticket ordering (KitchenDisplay),
+// ready-time estimates and the capacity policy (kitchen_capacity.rs).
+
+use super::reporting_service::product_family;
+use crate::domain::Beverage;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Prep time estimates, optionally overridden per product family
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrepTimeModel {
+    /// Product family ("Coffee", "Tea", "Smoothie") -> seconds for a medium,
+    /// before customizations
+    pub base_seconds: HashMap<String, u32>,
+}
+
+impl PrepTimeModel {
+    /// No overrides: every beverage uses its own estimate
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the base time of one product family
+    pub fn with_base(mut self, product_family: &str, seconds: u32) -> Self {
+        self.base_seconds.insert(product_family.to_string(), seconds);
+        self
+    }
+
+    /// How long this beverage takes here
+    pub fn estimate(&self, beverage: &dyn Beverage) -> Duration {
+        let base = self
+            .base_seconds
+            .get(&product_family(&beverage.name()))
+            .map(|seconds| Duration::seconds(*seconds as i64))
+            .unwrap_or_else(|| beverage.base_prep_time());
+
+        beverage
+            .size()
+            .scale_prep_time(base + beverage.prep_adjustment())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Coffee, Size, Tea, TeaVariety};
+
+    #[test]
+    fn test_without_overrides_uses_the_beverage_estimate() {
+        let coffee = Coffee {
+            size: Size::Small,
+            extra_shots: 1,
+        };
+
+        assert_eq!(PrepTimeModel::new().estimate(&coffee), coffee.prep_time_estimate());
+    }
+
+    #[test]
+    fn test_override_keeps_customizations_and_size() {
+        let model: PrepTimeModel =
+            serde_json::from_str(r#"{ "base_seconds": { "Coffee": 60 } }"#).unwrap();
+        let coffee = Coffee {
+            size: Size::Large,
+            extra_shots: 2,
+        };
+        let tea = Tea {
+            size: Size::Medium,
+            variety: TeaVariety::Green,
+        };
+
+        // (60 + 2 * 20) * 1.25
+        assert_eq!(model.estimate(&coffee), Duration::seconds(125));
+        assert_eq!(model.estimate(&tea), tea.prep_time_estimate());
+    }
+}
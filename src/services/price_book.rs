@@ -0,0 +1,368 @@
+// SOLID: PriceBooks - effective-dated menu prices, and re-pricing for audits
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Beverages keep their own list prices. A price book overrides the base
+// price per product family ("Coffee", "Tea", "Smoothie"), the way
+// PrepTimeModel overrides prep times; customizations and size still apply
+// on top, so "+2 shots" costs the same extra under every book. BookPriced
+// wraps a beverage like SurgePriced does: OrderService charges
+// `beverage.price()` as always.
+//
+// EFFECTIVE DATES:
+// A price change is published ahead of time, as a new version with the
+// moment it takes effect; orders are priced from the version effective
+// when they are placed. Versions are append-only (each one takes effect
+// after the previous one), so the book behind a past order never changes
+// and the order can be re-priced for an audit long after the menu moved on.
+//
+// ACTOR: Accounting owns the books, like PricingCalculator.
+
+use super::reporting_service::product_family;
+use crate::domain::{Beverage, Currency, Money, Order, PriceLine, Size};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Errors from publishing a price book
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceBookError {
+    /// Versions take effect in order: history is never rewritten
+    NotAfterLatest(DateTime<Utc>),
+    InvalidPrice(String),
+}
+
+impl fmt::Display for PriceBookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriceBookError::NotAfterLatest(latest) => write!(
+                f,
+                "A new price book must take effect after the latest one ({})",
+                latest.format("%Y-%m-%d %H:%M")
+            ),
+            PriceBookError::InvalidPrice(family) => write!(f, "Invalid price for {}", family),
+        }
+    }
+}
+
+impl Error for PriceBookError {}
+
+/// One version of the menu's prices
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceBook {
+    pub version: u32,
+    pub effective_from: DateTime<Utc>,
+    /// Product family -> base price of a medium, before customizations, in
+    /// the beverage's currency (families left out keep the beverage's own price)
+    pub base_prices: BTreeMap<String, f64>,
+}
+
+impl PriceBook {
+    /// Base price of this beverage's family here, if the book lists it
+    pub fn base_price(&self, family: &str) -> Option<f64> {
+        self.base_prices.get(family).copied()
+    }
+
+    /// Price the beverage from this book (unchanged if its family isn't listed)
+    pub fn apply(&self, beverage: Box<dyn Beverage>) -> Box<dyn Beverage> {
+        match self.base_price(&product_family(&beverage.name())) {
+            Some(base) => Box::new(BookPriced::new(beverage, base)),
+            None => beverage,
+        }
+    }
+}
+
+/// Every version of the price book, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceBooks {
+    versions: Vec<PriceBook>,
+}
+
+impl PriceBooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next version; returns its number
+    pub fn publish(
+        &mut self,
+        effective_from: DateTime<Utc>,
+        base_prices: BTreeMap<String, f64>,
+    ) -> Result<u32, PriceBookError> {
+        if let Some(latest) = self.versions.last()
+            && effective_from <= latest.effective_from
+        {
+            return Err(PriceBookError::NotAfterLatest(latest.effective_from));
+        }
+        if let Some((family, _)) = base_prices.iter().find(|(_, p)| !p.is_finite() || **p < 0.0) {
+            return Err(PriceBookError::InvalidPrice(family.clone()));
+        }
+
+        let version = self.versions.len() as u32 + 1;
+        self.versions.push(PriceBook {
+            version,
+            effective_from,
+            base_prices,
+        });
+        Ok(version)
+    }
+
+    pub fn versions(&self) -> &[PriceBook] {
+        &self.versions
+    }
+
+    /// The version in effect at `at` (None before the first one)
+    pub fn effective_at(&self, at: DateTime<Utc>) -> Option<&PriceBook> {
+        self.versions.iter().rev().find(|book| book.effective_from <= at)
+    }
+
+    /// What each line of `order` costs under the book effective when it was placed
+    ///
+    /// Only the book is applied: a surcharge or a promotion on the order
+    /// shows up as a difference, which is what an auditor wants to see.
+    pub fn reprice(&self, order: &Order) -> Repricing {
+        let book = self.effective_at(order.created_at);
+        let lines = order
+            .items
+            .iter()
+            .map(|item| {
+                let base = book.and_then(|b| b.base_price(&product_family(&item.beverage_name)));
+                let expected = match (base, item.price_basis) {
+                    (Some(base), Some(basis)) => Some(Money::from_major(
+                        (base + basis.adjustment) * basis.size.price_multiplier(),
+                        item.price.currency,
+                    )),
+                    _ => None,
+                };
+                RepricedLine {
+                    beverage_name: item.beverage_name.clone(),
+                    charged: item.price,
+                    expected,
+                }
+            })
+            .collect();
+
+        Repricing {
+            order_id: order.id,
+            book_version: book.map(|b| b.version),
+            recorded_version: order.price_book_version,
+            lines,
+        }
+    }
+}
+
+/// One order line, as charged and as its price book says
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepricedLine {
+    pub beverage_name: String,
+    pub charged: Money,
+    /// None when it can't be re-priced: no book then, family not in the
+    /// book, or a line stored without its price basis
+    pub expected: Option<Money>,
+}
+
+/// An order re-priced against the book effective when it was placed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repricing {
+    pub order_id: Uuid,
+    /// The book effective at the order's creation
+    pub book_version: Option<u32>,
+    /// The book the order says it was priced from
+    pub recorded_version: Option<u32>,
+    pub lines: Vec<RepricedLine>,
+}
+
+impl Repricing {
+    /// Lines charged differently from the book (more than `tolerance`, in major units, apart)
+    pub fn differences(&self, tolerance: f64) -> Vec<&RepricedLine> {
+        self.lines
+            .iter()
+            .filter(|line| {
+                matches!(line.expected, Some(e) if (e - line.charged).to_major().abs() > tolerance)
+            })
+            .collect()
+    }
+
+    /// Same book as recorded, and every re-priceable line charged as the book says
+    pub fn is_consistent(&self, tolerance: f64) -> bool {
+        self.book_version == self.recorded_version && self.differences(tolerance).is_empty()
+    }
+}
+
+/// A beverage sold at a price book's base price
+///
+/// The book replaces the list base price; customizations, size, and any
+/// surcharge or discount the beverage already carries are kept.
+#[derive(Debug)]
+pub struct BookPriced {
+    inner: Box<dyn Beverage>,
+    base: f64,
+}
+
+impl BookPriced {
+    pub fn new(inner: Box<dyn Beverage>, base: f64) -> Self {
+        Self { inner, base }
+    }
+
+    /// Book price minus list price, after size
+    fn difference(&self) -> Money {
+        let list = self.inner.base_price() - self.inner.price_adjustment();
+        let difference = (self.base - list) * self.inner.size().price_multiplier();
+        Money::from_major(difference, self.inner.currency())
+    }
+}
+
+impl Beverage for BookPriced {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn base_price(&self) -> f64 {
+        self.base + self.inner.price_adjustment()
+    }
+
+    fn price_adjustment(&self) -> f64 {
+        self.inner.price_adjustment()
+    }
+
+    fn size(&self) -> Size {
+        self.inner.size()
+    }
+
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    fn price(&self) -> Money {
+        self.inner.price() + self.difference()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    /// The beverage's own line changes; there is no "price book" line
+    fn price_lines(&self) -> Vec<PriceLine> {
+        let mut lines = self.inner.price_lines();
+        if let Some(first) = lines.first_mut() {
+            first.amount += self.difference();
+        }
+        lines
+    }
+
+    fn base_prep_time(&self) -> Duration {
+        self.inner.base_prep_time()
+    }
+
+    fn prep_adjustment(&self) -> Duration {
+        self.inner.prep_adjustment()
+    }
+
+    fn prep_time_estimate(&self) -> Duration {
+        self.inner.prep_time_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, MemoryOrderRepository};
+    use crate::domain::{Coffee, Customer, Tea, TeaVariety};
+    use crate::services::{OrderService, Promoted};
+    use chrono::TimeZone;
+    use std::io;
+
+    fn at(month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn prices(entries: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        entries.iter().map(|(family, price)| (family.to_string(), *price)).collect()
+    }
+
+    fn books() -> PriceBooks {
+        let mut books = PriceBooks::new();
+        books.publish(at(1, 1), prices(&[("Coffee", 3.50)])).unwrap();
+        books.publish(at(6, 1), prices(&[("Coffee", 4.00)])).unwrap();
+        books
+    }
+
+    #[test]
+    fn test_versions_resolve_by_date_and_never_go_back() {
+        let mut books = books();
+
+        assert!(books.effective_at(at(1, 1) - Duration::seconds(1)).is_none());
+        assert_eq!(books.effective_at(at(5, 31)).unwrap().version, 1);
+        assert_eq!(books.effective_at(at(6, 1)).unwrap().version, 2);
+        assert_eq!(
+            books.publish(at(3, 1), prices(&[("Coffee", 3.75)])),
+            Err(PriceBookError::NotAfterLatest(at(6, 1)))
+        );
+        assert_eq!(
+            books.publish(at(9, 1), prices(&[("Tea", -1.0)])),
+            Err(PriceBookError::InvalidPrice("Tea".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_book_price_keeps_customizations_size_and_discounts() {
+        let book = books().effective_at(at(7, 1)).unwrap().clone();
+        let double = book.apply(Box::new(Coffee {
+            size: Size::Large,
+            extra_shots: 2,
+        }));
+        // (4.00 + 2 * 0.75) * 1.2
+        assert_eq!(double.price(), Money::usd(6.60));
+        assert_eq!(double.price_lines()[0].amount, Money::usd(6.60));
+
+        let tea = Tea {
+            size: Size::Medium,
+            variety: TeaVariety::Green,
+        };
+        assert_eq!(book.apply(Box::new(tea)).price(), Money::usd(2.50));
+
+        let promoted = Promoted::new(
+            Box::new(Coffee {
+                size: Size::Medium,
+                extra_shots: 0,
+            }),
+            "Happy hour -10%".to_string(),
+            Money::usd(0.35),
+        );
+        let priced = book.apply(Box::new(promoted));
+        // 3.50 - 0.35 off, then +0.50 from the book
+        assert_eq!(priced.price(), Money::usd(3.65));
+        let itemized: Money = priced.price_lines().iter().map(|line| line.amount).sum();
+        assert_eq!(itemized, priced.price());
+    }
+
+    #[test]
+    fn test_orders_are_priced_and_repriced_from_their_book() {
+        let mut service = OrderService::new(
+            MemoryOrderRepository::new(),
+            CashPayment,
+            ConsoleNotifier::with_writer(io::sink()),
+        )
+        .with_price_books(books());
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let coffee = Coffee {
+            size: Size::Small,
+            extra_shots: 1,
+        };
+
+        let mut order = service.place_order(customer, vec![Box::new(coffee)]).unwrap();
+        assert_eq!(order.price_book_version, Some(2));
+        // (4.00 + 0.75) * 0.8
+        assert_eq!(order.total_price, Money::usd(3.80));
+        assert!(books().reprice(&order).is_consistent(0.005));
+
+        // The same order placed in March was priced from version 1
+        order.created_at = at(3, 1);
+        let repricing = books().reprice(&order);
+        assert_eq!(repricing.book_version, Some(1));
+        assert!(!repricing.is_consistent(0.005));
+        assert_eq!(repricing.differences(0.005)[0].expected, Some(Money::usd(3.40)));
+    }
+}
@@ -14,7 +14,7 @@
 // ACTOR: The Accounting department owns this module.
 // If Accounting wants to change pricing rules, they change this file and nothing else.
 
-use crate::domain::Beverage;
+use crate::domain::{Beverage, Money, OrderItem, OrderPricing};
 
 /// Pricing calculator
 /// 
@@ -51,7 +51,7 @@ impl PricingCalculator {
     /// This method encapsulates the pricing logic.
     /// If we need to add complexity (discounts, loyalty points, happy hour),
     /// we change THIS METHOD, not the callers.
-    pub fn calculate_beverage_price(&self, beverage: &dyn Beverage) -> f64 {
+    pub fn calculate_beverage_price(&self, beverage: &dyn Beverage) -> Money {
         beverage.price()
     }
 
@@ -67,13 +67,13 @@ impl PricingCalculator {
     /// - It takes a list of beverages (generic)
     /// - It has no side effects (pure calculation)
     /// - It doesn't save, notify, or process payments
-    pub fn calculate_total(&self, beverages: &[&dyn Beverage]) -> f64 {
-        let subtotal: f64 = beverages
+    pub fn calculate_total(&self, beverages: &[&dyn Beverage]) -> Money {
+        let subtotal: Money = beverages
             .iter()
             .map(|b| self.calculate_beverage_price(*b))
             .sum();
 
-        self.calculate_price_with_tax(subtotal)
+        subtotal.scaled(1.0 + self.tax_rate)
     }
 
     /// Apply a discount
@@ -89,8 +89,10 @@ impl PricingCalculator {
     /// Example: every 10th order gets 10% off
     /// This is a separate method because it's a separate pricing rule.
     /// If this rule changes, we change this method only.
+    ///
+    /// The shop's loyalty program (points, tiers) is LoyaltyService.
     pub fn calculate_loyalty_discount(&self, order_count: u32) -> f64 {
-        if order_count > 0 && order_count % 10 == 0 {
+        if order_count > 0 && order_count.is_multiple_of(10) {
             10.0 // 10% off
         } else {
             0.0
@@ -98,6 +100,17 @@ impl PricingCalculator {
     }
 }
 
+/// Stored items priced before tax: the total is their sum plus tax
+///
+/// With a tax rate of 0 this is LinePricing, the shop's own rule
+/// (VAT is included in its prices).
+impl OrderPricing for PricingCalculator {
+    fn total(&self, items: &[OrderItem]) -> Money {
+        let subtotal: Money = items.iter().map(OrderItem::line_total).sum();
+        subtotal.scaled(1.0 + self.tax_rate)
+    }
+}
+
 // ============================================================================
 // WHY SRP MATTERS: A Counter-Example
 // 
@@ -169,7 +182,7 @@ mod tests {
         let price = calculator.calculate_beverage_price(&coffee);
         
         // Coffee base: 3.50, Medium: 1.0 multiplier
-        assert_eq!(price, 3.50);
+        assert_eq!(price, Money::usd(3.50));
     }
 
     #[test]
@@ -205,6 +218,6 @@ mod tests {
         // Coffee2: 4.25 * 1.0 = 4.25
         // Subtotal: 7.05
         // With 10% tax: 7.755
-        assert!((total - 7.755).abs() < 0.01);
+        assert!((total.to_major() - 7.755).abs() < 0.01);
     }
 }
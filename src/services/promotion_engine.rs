@@ -0,0 +1,339 @@
+// SOLID: PromotionEngine - discounts for everyone, or per experiment variant
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// A promotion is any type implementing Promotion. The engine wraps each
+// beverage in Promoted (a Beverage itself), exactly like SurgePriced, so
+// OrderService charges the discounted price without knowing promotions exist.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Variant assignment goes through the Experiment port. Each time the engine
+// consults an experiment it publishes an ExperimentExposure on the bus, so
+// ExperimentReport can compare variants without the engine knowing about it.
+//
+// One promotion per beverage: when several apply, the customer gets the
+// biggest discount. Promotions never stack.
+
+use super::reporting_service::product_family;
+use crate::bus::{ExperimentExposure, MessageBus};
+use crate::domain::{Beverage, Currency, Money, PriceLine, Size};
+use crate::ports::Experiment;
+use chrono::{Duration, Utc};
+
+/// A discount rule
+pub trait Promotion: std::fmt::Debug {
+    /// What the receipt says ("Tea week -10%")
+    fn label(&self) -> String;
+
+    /// Amount off this beverage (zero when the promotion doesn't apply)
+    fn discount(&self, beverage: &dyn Beverage) -> Money;
+}
+
+/// A percentage off, on every beverage or on one product family
+#[derive(Debug, Clone)]
+pub struct PercentOff {
+    label: String,
+    percent: f64,
+    family: Option<String>,
+}
+
+impl PercentOff {
+    pub fn new(label: &str, percent: f64) -> Self {
+        Self {
+            label: label.to_string(),
+            percent: percent.clamp(0.0, 100.0),
+            family: None,
+        }
+    }
+
+    /// Only for one product family ("Coffee", "Tea", "Smoothie")
+    pub fn on(mut self, product_family: &str) -> Self {
+        self.family = Some(product_family.to_string());
+        self
+    }
+}
+
+impl Promotion for PercentOff {
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    fn discount(&self, beverage: &dyn Beverage) -> Money {
+        let applies = match &self.family {
+            Some(family) => product_family(&beverage.name()) == *family,
+            None => true,
+        };
+        if applies {
+            beverage.price().scaled(self.percent / 100.0)
+        } else {
+            Money::zero(beverage.currency())
+        }
+    }
+}
+
+/// A beverage sold with a promotion applied
+#[derive(Debug)]
+pub struct Promoted {
+    inner: Box<dyn Beverage>,
+    label: String,
+    discount: Money,
+}
+
+impl Promoted {
+    pub fn new(inner: Box<dyn Beverage>, label: String, discount: Money) -> Self {
+        let discount = discount.max(Money::zero(discount.currency)).min(inner.price());
+        Self {
+            inner,
+            label,
+            discount,
+        }
+    }
+
+    pub fn discount(&self) -> Money {
+        self.discount
+    }
+}
+
+impl Beverage for Promoted {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn base_price(&self) -> f64 {
+        self.inner.base_price()
+    }
+
+    fn price_adjustment(&self) -> f64 {
+        self.inner.price_adjustment()
+    }
+
+    fn size(&self) -> Size {
+        self.inner.size()
+    }
+
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    fn price(&self) -> Money {
+        self.inner.price() - self.discount
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn price_lines(&self) -> Vec<PriceLine> {
+        let mut lines = self.inner.price_lines();
+        lines.push(PriceLine {
+            label: self.label.clone(),
+            amount: -self.discount,
+        });
+        lines
+    }
+
+    fn base_prep_time(&self) -> Duration {
+        self.inner.base_prep_time()
+    }
+
+    fn prep_adjustment(&self) -> Duration {
+        self.inner.prep_adjustment()
+    }
+
+    fn prep_time_estimate(&self) -> Duration {
+        self.inner.prep_time_estimate()
+    }
+}
+
+/// A promotion only customers in one experiment variant get
+#[derive(Debug)]
+struct VariantPromotion {
+    experiment: String,
+    variant: String,
+    promotion: Box<dyn Promotion>,
+}
+
+/// Applies promotions to a customer's beverages before the order is placed
+#[derive(Default)]
+pub struct PromotionEngine {
+    promotions: Vec<Box<dyn Promotion>>,
+    experiments: Vec<Box<dyn Experiment>>,
+    variant_promotions: Vec<VariantPromotion>,
+    bus: Option<MessageBus>,
+}
+
+impl PromotionEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish an ExperimentExposure each time a customer is bucketed
+    pub fn with_message_bus(mut self, bus: MessageBus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// A promotion for every customer
+    pub fn promotion(mut self, promotion: impl Promotion + 'static) -> Self {
+        self.promotions.push(Box::new(promotion));
+        self
+    }
+
+    /// Run an experiment (customers are exposed to it on every `apply`)
+    pub fn experiment(mut self, experiment: impl Experiment + 'static) -> Self {
+        self.experiments.push(Box::new(experiment));
+        self
+    }
+
+    /// A promotion for customers in `variant` of `experiment`
+    ///
+    /// Never applies if no experiment with that name is registered.
+    pub fn variant_promotion(
+        mut self,
+        experiment: &str,
+        variant: &str,
+        promotion: impl Promotion + 'static,
+    ) -> Self {
+        self.variant_promotions.push(VariantPromotion {
+            experiment: experiment.to_string(),
+            variant: variant.to_string(),
+            promotion: Box::new(promotion),
+        });
+        self
+    }
+
+    /// The variant a customer is in, without recording an exposure
+    pub fn variant_for(&self, experiment: &str, customer_email: &str) -> Option<String> {
+        self.experiments
+            .iter()
+            .find(|e| e.name() == experiment)
+            .map(|e| e.variant_for(customer_email))
+    }
+
+    /// Wrap each beverage with the best promotion this customer is entitled to
+    pub fn apply(
+        &self,
+        customer_email: &str,
+        beverages: Vec<Box<dyn Beverage>>,
+    ) -> Vec<Box<dyn Beverage>> {
+        let variants: Vec<(&str, String)> = self
+            .experiments
+            .iter()
+            .map(|experiment| {
+                let variant = experiment.variant_for(customer_email);
+                self.expose(experiment.name(), &variant, customer_email);
+                (experiment.name(), variant)
+            })
+            .collect();
+
+        let active: Vec<&dyn Promotion> = self
+            .promotions
+            .iter()
+            .map(|p| p.as_ref())
+            .chain(
+                self.variant_promotions
+                    .iter()
+                    .filter(|vp| {
+                        variants
+                            .iter()
+                            .any(|(name, variant)| *name == vp.experiment && *variant == vp.variant)
+                    })
+                    .map(|vp| vp.promotion.as_ref()),
+            )
+            .collect();
+
+        beverages
+            .into_iter()
+            .map(|beverage| Self::best_deal(beverage, &active))
+            .collect()
+    }
+
+    fn best_deal(beverage: Box<dyn Beverage>, promotions: &[&dyn Promotion]) -> Box<dyn Beverage> {
+        let best = promotions
+            .iter()
+            .map(|promotion| (promotion, promotion.discount(beverage.as_ref())))
+            .filter(|(_, discount)| discount.cents > 0)
+            .max_by_key(|(_, discount)| discount.cents);
+
+        match best {
+            Some((promotion, discount)) => {
+                Box::new(Promoted::new(beverage, promotion.label(), discount))
+            }
+            None => beverage,
+        }
+    }
+
+    fn expose(&self, experiment: &str, variant: &str, customer_email: &str) {
+        if let Some(bus) = &self.bus {
+            bus.publish(ExperimentExposure {
+                experiment: experiment.to_string(),
+                variant: variant.to_string(),
+                customer_email: customer_email.to_string(),
+                exposed_at: Utc::now(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::HashedExperiment;
+    use crate::domain::{Coffee, Tea, TeaVariety};
+
+    fn basket() -> Vec<Box<dyn Beverage>> {
+        vec![
+            Box::new(Coffee {
+                size: Size::Medium,
+                extra_shots: 0,
+            }),
+            Box::new(Tea {
+                size: Size::Medium,
+                variety: TeaVariety::Green,
+            }),
+        ]
+    }
+
+    /// A customer email that lands in `variant`
+    fn customer_in(engine: &PromotionEngine, variant: &str) -> String {
+        (0..)
+            .map(|i| format!("c{}@example.com", i))
+            .find(|email| engine.variant_for("tea-week", email).as_deref() == Some(variant))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_best_promotion_wins_and_is_itemized() {
+        let engine = PromotionEngine::new()
+            .promotion(PercentOff::new("Happy hour -10%", 10.0))
+            .promotion(PercentOff::new("Tea week -20%", 20.0).on("Tea"));
+
+        let promoted = engine.apply("ada@example.com", basket());
+
+        assert_eq!(promoted[0].price(), Money::usd(3.15));
+        assert_eq!(promoted[1].price(), Money::usd(2.00));
+        let tea_lines = promoted[1].price_lines();
+        assert_eq!(tea_lines[1].label, "Tea week -20%");
+        assert_eq!(tea_lines[1].amount, Money::usd(-0.5));
+    }
+
+    #[test]
+    fn test_variant_promotions_and_exposures() {
+        let bus = MessageBus::new();
+        let exposures = bus.subscribe::<ExperimentExposure>();
+        let engine = PromotionEngine::new()
+            .with_message_bus(bus.clone())
+            .experiment(HashedExperiment::ab("tea-week", "twenty-off"))
+            .variant_promotion("tea-week", "twenty-off", PercentOff::new("-20%", 20.0).on("Tea"));
+
+        let control = customer_in(&engine, "control");
+        let treated = customer_in(&engine, "twenty-off");
+
+        assert_eq!(engine.apply(&control, basket())[1].price(), Money::usd(2.50));
+        assert_eq!(engine.apply(&treated, basket())[1].price(), Money::usd(2.00));
+
+        let seen = exposures.drain();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[1].variant, "twenty-off");
+        assert_eq!(seen[1].customer_email, treated);
+    }
+}
@@ -0,0 +1,382 @@
+// SOLID: RefundService - refunds, with manager approval above a limit
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// RefundPolicy says WHEN approval is needed (the amount), AuthorizationPolicy
+// says WHO may request and approve, the PaymentProcessor moves the money and
+// the managers' Notifier tells them something is waiting. This service only
+// runs the workflow:
+//
+// ```text
+// request ──(≤ limit, or the requester may approve)──> Refunded
+//    └──(> limit)──> PendingApproval ──approve──> Refunded
+//                           └──────────reject───> Rejected
+// ```
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Any PaymentProcessor that implements `refund`, any Notifier as the manager
+// channel (ChatNotifier on the managers' Slack, ConsoleNotifier in the demo).
+//
+// With a loyalty program (`with_loyalty`), each refund takes back the
+// points it had earned.
+//
+// Manager override: a manager asking for a refund is its own approver, so
+// their requests never wait, whatever the amount.
+
+use super::authorization::{AuthorizationPolicy, Permission};
+use super::loyalty_service::LoyaltyService;
+use crate::domain::{Money, Order, RefundPendingNotice, RefundRequest, StaffMember};
+use crate::ports::{Notifier, PaymentError, PaymentProcessor};
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+
+/// Errors from the refund workflow
+#[derive(Debug)]
+pub enum RefundError {
+    NotAuthorized { staff: String, permission: Permission },
+    InvalidAmount(String),
+    OrderNotPaid,
+    /// The order handed in is not the one the request is about
+    WrongOrder,
+    RequestNotFound,
+    NotPending,
+    PaymentFailed(PaymentError),
+}
+
+impl fmt::Display for RefundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RefundError::NotAuthorized { staff, permission } => {
+                write!(f, "{} is not allowed to {:?}", staff, permission)
+            }
+            RefundError::InvalidAmount(msg) => write!(f, "Invalid amount: {}", msg),
+            RefundError::OrderNotPaid => write!(f, "Order was never paid"),
+            RefundError::WrongOrder => write!(f, "This refund is for another order"),
+            RefundError::RequestNotFound => write!(f, "Refund request not found"),
+            RefundError::NotPending => write!(f, "Refund request was already decided"),
+            RefundError::PaymentFailed(e) => write!(f, "Refund failed: {}", e),
+        }
+    }
+}
+
+impl Error for RefundError {}
+
+/// Refunds above this amount need a manager's approval
+#[derive(Debug, Clone)]
+pub struct RefundPolicy {
+    approval_threshold: Money,
+}
+
+impl RefundPolicy {
+    pub fn new(approval_threshold: Money) -> Self {
+        Self {
+            approval_threshold: approval_threshold.max(Money::zero(approval_threshold.currency)),
+        }
+    }
+
+    /// Above the threshold, or in another currency (no rate to compare with)
+    pub fn needs_approval(&self, amount: Money) -> bool {
+        amount.currency != self.approval_threshold.currency || amount > self.approval_threshold
+    }
+}
+
+impl Default for RefundPolicy {
+    /// Anything above $20
+    fn default() -> Self {
+        Self::new(Money::usd(20.0))
+    }
+}
+
+/// Runs refund requests from the register to the payment processor
+pub struct RefundService<P: PaymentProcessor, M: Notifier> {
+    payment_processor: P,
+    managers: M,
+    policy: RefundPolicy,
+    authorization: AuthorizationPolicy,
+    requests: Vec<RefundRequest>,
+    loyalty: Option<LoyaltyService>,
+}
+
+impl<P: PaymentProcessor, M: Notifier> RefundService<P, M> {
+    /// `managers` is the channel pending approvals are announced on
+    pub fn new(payment_processor: P, managers: M) -> Self {
+        Self {
+            payment_processor,
+            managers,
+            policy: RefundPolicy::default(),
+            authorization: AuthorizationPolicy::default(),
+            requests: Vec::new(),
+            loyalty: None,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: RefundPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_authorization(mut self, authorization: AuthorizationPolicy) -> Self {
+        self.authorization = authorization;
+        self
+    }
+
+    /// Take back the loyalty points each refund had earned (share the accounts
+    /// with OrderService's program through a SharedLoyaltyRepository)
+    pub fn with_loyalty(mut self, loyalty: LoyaltyService) -> Self {
+        self.loyalty = Some(loyalty);
+        self
+    }
+
+    /// Ask for money back on a paid order
+    ///
+    /// Small refunds (and a manager's own) go through at once and are added
+    /// to the order's `refunded_total`; save the order afterwards. Larger ones
+    /// are stored as PendingApproval and announced to the managers.
+    pub fn request(
+        &mut self,
+        order: &mut Order,
+        amount: Money,
+        reason: &str,
+        requested_by: &StaffMember,
+    ) -> Result<RefundRequest, RefundError> {
+        self.authorize(requested_by, Permission::RequestRefund)?;
+
+        let remaining = order.left_to_refund() - self.pending_for(order);
+        if amount.currency != remaining.currency || amount.cents <= 0 || amount > remaining {
+            return Err(RefundError::InvalidAmount(format!(
+                "{} requested, {} refundable",
+                amount,
+                remaining.max(Money::zero(remaining.currency))
+            )));
+        }
+        let mut request = RefundRequest::new(order, amount, reason, requested_by)
+            .ok_or(RefundError::OrderNotPaid)?;
+
+        let self_approved = self.authorization.allows(requested_by, Permission::ApproveRefund);
+        if self.policy.needs_approval(amount) && !self_approved {
+            let notice = RefundPendingNotice::from_request(&request, order);
+            if let Err(e) = self.managers.notify_refund_pending(&notice) {
+                eprintln!("Warning: Failed to send notification: {}", e);
+            }
+        } else {
+            self.execute(&mut request, order, requested_by)?;
+        }
+
+        self.requests.push(request.clone());
+        Ok(request)
+    }
+
+    /// A manager says yes: the money goes back, and onto the order's `refunded_total`
+    ///
+    /// If the payment processor fails, the request stays pending so it can
+    /// be approved again.
+    pub fn approve(
+        &mut self,
+        id: Uuid,
+        order: &mut Order,
+        approver: &StaffMember,
+    ) -> Result<RefundRequest, RefundError> {
+        self.authorize(approver, Permission::ApproveRefund)?;
+        let index = self.pending_index(id)?;
+        if self.requests[index].order_id != order.id {
+            return Err(RefundError::WrongOrder);
+        }
+
+        let mut request = self.requests[index].clone();
+        self.execute(&mut request, order, approver)?;
+        self.requests[index] = request.clone();
+        Ok(request)
+    }
+
+    /// A manager says no
+    pub fn reject(
+        &mut self,
+        id: Uuid,
+        approver: &StaffMember,
+        reason: &str,
+    ) -> Result<RefundRequest, RefundError> {
+        self.authorize(approver, Permission::ApproveRefund)?;
+        let index = self.pending_index(id)?;
+
+        self.requests[index].reject(approver, reason);
+        Ok(self.requests[index].clone())
+    }
+
+    /// Requests waiting for a manager, oldest first
+    pub fn pending(&self) -> Vec<&RefundRequest> {
+        self.requests.iter().filter(|r| r.is_pending()).collect()
+    }
+
+    pub fn requests(&self) -> &[RefundRequest] {
+        &self.requests
+    }
+
+    fn authorize(&self, staff: &StaffMember, permission: Permission) -> Result<(), RefundError> {
+        if self.authorization.allows(staff, permission) {
+            Ok(())
+        } else {
+            Err(RefundError::NotAuthorized {
+                staff: staff.name.clone(),
+                permission,
+            })
+        }
+    }
+
+    /// Still waiting for a manager on this order (what was refunded is on the order)
+    fn pending_for(&self, order: &Order) -> Money {
+        self.requests
+            .iter()
+            .filter(|r| r.order_id == order.id && r.is_pending())
+            .fold(Money::zero(order.currency()), |sum, r| sum + r.amount)
+    }
+
+    fn pending_index(&self, id: Uuid) -> Result<usize, RefundError> {
+        let index = self
+            .requests
+            .iter()
+            .position(|r| r.id == id)
+            .ok_or(RefundError::RequestNotFound)?;
+        if self.requests[index].is_pending() {
+            Ok(index)
+        } else {
+            Err(RefundError::NotPending)
+        }
+    }
+
+    fn execute(
+        &mut self,
+        request: &mut RefundRequest,
+        order: &mut Order,
+        decided_by: &StaffMember,
+    ) -> Result<(), RefundError> {
+        let refund_id = self
+            .payment_processor
+            .refund_with_key(
+                &request.payment_id,
+                request.amount,
+                &format!("refund-{}", request.id),
+            )
+            .map_err(RefundError::PaymentFailed)?;
+        order.record_refund(request.amount);
+        request.mark_refunded(decided_by, refund_id);
+        // The money is back already: a warning, like OrderService's accrual
+        if let Some(loyalty) = &mut self.loyalty
+            && let Err(e) = loyalty.reverse(order, request.amount)
+        {
+            eprintln!("Warning: Failed to take back points for order {}: {}", order.id, e);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{read_notification_records, CashPayment, FileNotifier};
+    use crate::domain::{Customer, OrderItem, RefundStatus, Role};
+    use std::path::PathBuf;
+
+    fn paid_order(total: f64) -> Order {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let mut order = Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: "Smoothie".to_string(),
+                beverage_description: "Catering tray".to_string(),
+                price: Money::usd(total),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        );
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        order
+    }
+
+    /// Refunds above $20 need approval; manager notifications go to a temp file
+    fn service() -> (RefundService<CashPayment, FileNotifier>, PathBuf) {
+        let log = std::env::temp_dir().join(format!("refunds-{}.jsonl", Uuid::new_v4()));
+        let service = RefundService::new(CashPayment, FileNotifier::new(log.clone()))
+            .with_policy(RefundPolicy::new(Money::usd(20.0)));
+        (service, log)
+    }
+
+    #[test]
+    fn test_small_refunds_go_through_large_ones_wait() {
+        let (mut refunds, log) = service();
+        let barista = StaffMember::new("Sam", Role::Barista);
+        let mut order = paid_order(50.0);
+
+        let small = refunds.request(&mut order, Money::usd(5.0), "cold drink", &barista).unwrap();
+        let large = refunds.request(&mut order, Money::usd(30.0), "wrong tray", &barista).unwrap();
+
+        assert_eq!(small.status, RefundStatus::Refunded);
+        assert!(small.refund_id.as_deref().unwrap().starts_with("CASH-REFUND-"));
+        assert_eq!(order.refunded_total, Money::usd(5.0));
+        assert_eq!(large.status, RefundStatus::PendingApproval);
+        assert_eq!(refunds.pending().len(), 1);
+        let records = read_notification_records(&log).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, "refund.pending");
+
+        // 5 refunded + 30 pending: only 15 left to ask for
+        assert!(matches!(
+            refunds.request(&mut order, Money::usd(20.0), "again", &barista),
+            Err(RefundError::InvalidAmount(_))
+        ));
+        let _ = std::fs::remove_file(log);
+    }
+
+    #[test]
+    fn test_only_managers_decide() {
+        let (mut refunds, log) = service();
+        let barista = StaffMember::new("Sam", Role::Barista);
+        let manager = StaffMember::new("Max", Role::Manager);
+        let mut order = paid_order(50.0);
+        let pending =
+            refunds.request(&mut order, Money::usd(30.0), "wrong tray", &barista).unwrap();
+
+        assert!(matches!(
+            refunds.approve(pending.id, &mut order, &barista),
+            Err(RefundError::NotAuthorized { .. })
+        ));
+        let mut other = paid_order(50.0);
+        assert!(matches!(
+            refunds.approve(pending.id, &mut other, &manager),
+            Err(RefundError::WrongOrder)
+        ));
+        let approved = refunds.approve(pending.id, &mut order, &manager).unwrap();
+        assert_eq!(approved.status, RefundStatus::Refunded);
+        assert_eq!(order.left_to_refund(), Money::usd(20.0));
+        assert_eq!(approved.decided_by.as_deref(), Some("Max"));
+        assert!(matches!(
+            refunds.reject(pending.id, &manager, "too late"),
+            Err(RefundError::NotPending)
+        ));
+
+        // With a loyalty program (`with_loyalty`), each refund takes back the
+// points it had earned.
+//
+// Manager override: their own large refund doesn't wait
+        let own = refunds.request(&mut order, Money::usd(20.0), "goodwill", &manager).unwrap();
+        assert_eq!(own.status, RefundStatus::Refunded);
+        let _ = std::fs::remove_file(log);
+    }
+
+    #[test]
+    fn test_rejected_refund_frees_the_amount() {
+        let (mut refunds, log) = service();
+        let barista = StaffMember::new("Sam", Role::Barista);
+        let manager = StaffMember::new("Max", Role::Manager);
+        let mut order = paid_order(50.0);
+        let pending = refunds.request(&mut order, Money::usd(50.0), "all of it", &barista).unwrap();
+
+        let rejected = refunds.reject(pending.id, &manager, "drink was fine").unwrap();
+
+        assert_eq!(rejected.status, RefundStatus::Rejected);
+        assert_eq!(rejected.rejection_reason.as_deref(), Some("drink was fine"));
+        assert!(refunds.request(&mut order, Money::usd(10.0), "partial", &barista).is_ok());
+        let _ = std::fs::remove_file(log);
+    }
+}
@@ -0,0 +1,616 @@
+// SOLID: ReportingService - sales figures computed from stored orders
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Owned by the shop owner / Accounting. It only COMPUTES numbers.
+// Turning them into charts, JSON or CSV is the presentation layer's job,
+// so a new output format never touches this file.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Reads through the OrderRepository trait: reports work the same on a
+// JSON file, the in-memory store or seeded demo data.
+//
+// Revenue only counts SALES: orders that were paid and not cancelled.
+// Figures are in one currency (US dollars unless `with_currency` says
+// otherwise): francs added to dollars mean nothing, so sales in other
+// currencies are left out, and the daily report lists their totals apart.
+// Costs come from a CostModel (ingredients and recipes), passed in by the
+// caller: the same orders can be costed with last month's or this month's prices.
+
+use super::cost_model::CostModel;
+use crate::domain::{Currency, Money, Order, OrderStatus};
+use crate::ports::{OrderRepository, RepositoryError};
+use chrono::{Datelike, Duration, NaiveDate, Timelike};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Inclusive range of days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(from: NaiveDate, to: NaiveDate) -> Self {
+        Self { from, to }
+    }
+
+    /// Monday to Sunday of the week containing `date`
+    pub fn week_of(date: NaiveDate) -> Self {
+        let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        Self::new(monday, monday + Duration::days(6))
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.from && date <= self.to
+    }
+
+    /// Every day of the range, in order
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.from.iter_days().take_while(|day| day <= &self.to)
+    }
+}
+
+/// One day of sales
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    /// Paid, non-cancelled orders
+    pub orders: usize,
+    pub cancelled: usize,
+    /// Orders that got stuck past the SLA at some point (see SlaPolicy)
+    pub sla_breaches: usize,
+    pub revenue: f64,
+    pub average_ticket: f64,
+    /// Sales per opening hour (hour of day, orders)
+    pub hourly: Vec<(u32, usize)>,
+    /// Sales in other currencies, not counted above (one total per currency)
+    pub other_currencies: Vec<Money>,
+}
+
+/// Totals for one day, used inside multi-day reports
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayTotal {
+    pub date: NaiveDate,
+    pub orders: usize,
+    pub revenue: f64,
+}
+
+/// Seven days of sales
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeeklyReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: Vec<DayTotal>,
+    pub orders: usize,
+    pub revenue: f64,
+}
+
+/// How much of the business one category represents
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MixShare {
+    pub name: String,
+    /// Items sold (product mix) or orders paid (payment mix)
+    pub count: usize,
+    pub revenue: f64,
+    /// Share of the revenue, 0.0 to 1.0
+    pub share: f64,
+}
+
+/// Distribution of one duration, in seconds
+///
+/// Percentiles use the nearest-rank method: p95 is a value that was actually
+/// observed, with 95% of the samples at or below it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub samples: usize,
+    pub sum: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl LatencySummary {
+    /// Summarize raw observations (all zeros when there are none)
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        Self {
+            samples: samples.len(),
+            sum: total(samples.iter().copied()),
+            p50: percentile(&samples, 50.0),
+            p95: percentile(&samples, 95.0),
+            p99: percentile(&samples, 99.0),
+            max: samples.last().copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// How long orders took to place and to prepare over a range of days
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Creation to payment
+    pub placement: LatencySummary,
+    /// Preparing to Ready
+    pub preparation: LatencySummary,
+}
+
+/// Revenue against cost of goods for one beverage
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MarginLine {
+    /// Beverage name as sold ("Coffee (+1 shot)", "Green Tea")
+    pub name: String,
+    pub items: usize,
+    pub revenue: f64,
+    pub cost: f64,
+    pub margin: f64,
+    /// Margin as a share of revenue, 0.0 to 1.0
+    pub margin_rate: f64,
+}
+
+/// Revenue against cost of goods for one day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayMargin {
+    pub date: NaiveDate,
+    pub orders: usize,
+    pub revenue: f64,
+    pub cost: f64,
+    pub margin: f64,
+}
+
+/// Margins over a range of days, per beverage and per day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfitabilityReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Biggest margin first
+    pub beverages: Vec<MarginLine>,
+    pub days: Vec<DayMargin>,
+    pub revenue: f64,
+    pub cost: f64,
+    pub margin: f64,
+    pub margin_rate: f64,
+    /// Items sold without a recipe, counted at zero cost
+    pub uncosted_items: usize,
+}
+
+/// Computes reports from any repository
+pub struct ReportingService<R: OrderRepository> {
+    repository: R,
+    currency: Currency,
+}
+
+impl<R: OrderRepository> ReportingService<R> {
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository,
+            currency: Currency::USD,
+        }
+    }
+
+    /// Report the sales made in `currency` (US dollars by default)
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Sales of one day
+    pub fn daily(&self, date: NaiveDate) -> Result<DailyReport, RepositoryError> {
+        let orders = self.orders_in(DateRange::new(date, date))?;
+        let sales: Vec<&Order> =
+            orders.iter().map(Arc::as_ref).filter(|o| self.is_sale(o)).collect();
+
+        let mut hours: BTreeMap<u32, usize> = BTreeMap::new();
+        for order in &sales {
+            *hours.entry(order.created_at.hour()).or_default() += 1;
+        }
+
+        let revenue = total(sales.iter().map(|o| o.total_price.to_major()));
+
+        Ok(DailyReport {
+            date,
+            orders: sales.len(),
+            cancelled: orders
+                .iter()
+                .filter(|o| matches!(o.status, OrderStatus::Cancelled | OrderStatus::Refunded))
+                .count(),
+            sla_breaches: orders.iter().filter(|o| o.breached_sla()).count(),
+            revenue,
+            average_ticket: if sales.is_empty() {
+                0.0
+            } else {
+                revenue / sales.len() as f64
+            },
+            hourly: hours.into_iter().collect(),
+            other_currencies: Money::sum_by_currency(
+                orders
+                    .iter()
+                    .filter(|o| is_sale(o) && o.currency() != self.currency)
+                    .map(|o| o.total_price),
+            ),
+        })
+    }
+
+    /// Day-by-day sales of the week (Monday to Sunday) containing `date`
+    pub fn weekly(&self, date: NaiveDate) -> Result<WeeklyReport, RepositoryError> {
+        let range = DateRange::week_of(date);
+        let orders = self.orders_in(range)?;
+
+        let days: Vec<DayTotal> = range
+            .days()
+            .map(|day| {
+                let sales: Vec<&Order> = orders
+                    .iter()
+                    .map(Arc::as_ref)
+                    .filter(|o| o.created_at.date_naive() == day && self.is_sale(o))
+                    .collect();
+                DayTotal {
+                    date: day,
+                    orders: sales.len(),
+                    revenue: total(sales.iter().map(|o| o.total_price.to_major())),
+                }
+            })
+            .collect();
+
+        Ok(WeeklyReport {
+            from: range.from,
+            to: range.to,
+            orders: days.iter().map(|d| d.orders).sum(),
+            revenue: total(days.iter().map(|d| d.revenue)),
+            days,
+        })
+    }
+
+    /// Items sold per product family (Coffee, Tea, Smoothie...), best sellers first
+    pub fn product_mix(&self, range: DateRange) -> Result<Vec<MixShare>, RepositoryError> {
+        let orders = self.orders_in(range)?;
+        let lines = orders
+            .iter()
+            .filter(|o| self.is_sale(o))
+            .flat_map(|o| o.items.iter())
+            .map(|item| {
+                (
+                    product_family(&item.beverage_name),
+                    item.quantity as usize,
+                    item.line_total().to_major(),
+                )
+            });
+
+        Ok(shares(lines))
+    }
+
+    /// Orders per payment method, biggest first
+    ///
+    /// The method is read from the payment ID prefix (CASH-..., CC-...).
+    pub fn payment_mix(&self, range: DateRange) -> Result<Vec<MixShare>, RepositoryError> {
+        let orders = self.orders_in(range)?;
+        let payments = orders.iter().filter(|o| self.is_sale(o)).map(|o| {
+            (
+                payment_method(o.payment_id.as_deref().unwrap_or_default()),
+                1,
+                o.total_price.to_major(),
+            )
+        });
+
+        Ok(shares(payments))
+    }
+
+    /// Placement and preparation percentiles for orders created in `range`
+    ///
+    /// Computed from each order's status history, so it covers every stored
+    /// order, not just those placed since the process started.
+    pub fn latency(&self, range: DateRange) -> Result<LatencyReport, RepositoryError> {
+        let orders = self.orders_in(range)?;
+        let seconds = |duration: Duration| duration.num_milliseconds() as f64 / 1000.0;
+
+        Ok(LatencyReport {
+            from: range.from,
+            to: range.to,
+            placement: LatencySummary::from_samples(
+                orders
+                    .iter()
+                    .filter_map(|o| o.placement_latency())
+                    .map(seconds)
+                    .collect(),
+            ),
+            preparation: LatencySummary::from_samples(
+                orders.iter().filter_map(|o| o.prep_duration()).map(seconds).collect(),
+            ),
+        })
+    }
+
+    /// Cost of goods and margin of the sales in `range`
+    pub fn profitability(
+        &self,
+        range: DateRange,
+        costs: &CostModel,
+    ) -> Result<ProfitabilityReport, RepositoryError> {
+        let orders = self.orders_in(range)?;
+        let sales: Vec<&Order> =
+            orders.iter().map(Arc::as_ref).filter(|o| self.is_sale(o)).collect();
+
+        let mut beverages: BTreeMap<String, (usize, f64, f64)> = BTreeMap::new();
+        let mut uncosted_items = 0;
+        for item in sales.iter().flat_map(|o| o.items.iter()) {
+            let quantity = item.quantity as usize;
+            let cost = match costs.unit_cost(item) {
+                Some(unit) => unit * item.quantity as f64,
+                None => {
+                    uncosted_items += quantity;
+                    0.0
+                }
+            };
+            let line = beverages.entry(item.beverage_name.clone()).or_default();
+            line.0 += quantity;
+            line.1 += item.line_total().to_major();
+            line.2 += cost;
+        }
+        let mut beverages: Vec<MarginLine> = beverages
+            .into_iter()
+            .map(|(name, (items, revenue, cost))| MarginLine {
+                name,
+                items,
+                revenue,
+                cost,
+                margin: revenue - cost,
+                margin_rate: rate(revenue - cost, revenue),
+            })
+            .collect();
+        beverages.sort_by(|a, b| b.margin.total_cmp(&a.margin));
+
+        let days: Vec<DayMargin> = range
+            .days()
+            .map(|day| {
+                let sold: Vec<&&Order> =
+                    sales.iter().filter(|o| o.created_at.date_naive() == day).collect();
+                let revenue = total(sold.iter().map(|o| o.total_price.to_major()));
+                let cost = total(sold.iter().map(|o| costs.order_cost(o)));
+                DayMargin {
+                    date: day,
+                    orders: sold.len(),
+                    revenue,
+                    cost,
+                    margin: revenue - cost,
+                }
+            })
+            .collect();
+
+        let revenue = total(days.iter().map(|d| d.revenue));
+        let cost = total(days.iter().map(|d| d.cost));
+        Ok(ProfitabilityReport {
+            from: range.from,
+            to: range.to,
+            beverages,
+            days,
+            revenue,
+            cost,
+            margin: revenue - cost,
+            margin_rate: rate(revenue - cost, revenue),
+            uncosted_items,
+        })
+    }
+
+    /// A sale in the report's currency
+    fn is_sale(&self, order: &Order) -> bool {
+        is_sale(order) && order.currency() == self.currency
+    }
+
+    // Shared handles: a report reads orders, it never needs its own copies
+    fn orders_in(&self, range: DateRange) -> Result<Vec<Arc<Order>>, RepositoryError> {
+        Ok(self
+            .repository
+            .list_all_shared()?
+            .into_iter()
+            .filter(|o| range.contains(o.created_at.date_naive()))
+            .collect())
+    }
+}
+
+/// Paid and not cancelled (nor refunded)
+pub(crate) fn is_sale(order: &Order) -> bool {
+    order.payment_id.is_some()
+        && !matches!(order.status, OrderStatus::Cancelled | OrderStatus::Refunded)
+}
+
+/// "Coffee (+2 shots)" -> "Coffee", "Green Tea" -> "Tea", "Smoothie (Mango)" -> "Smoothie"
+pub(crate) fn product_family(beverage_name: &str) -> String {
+    let family = if beverage_name.starts_with("Coffee") {
+        "Coffee"
+    } else if beverage_name.ends_with("Tea") {
+        "Tea"
+    } else if beverage_name.starts_with("Smoothie") {
+        "Smoothie"
+    } else {
+        beverage_name
+    };
+    family.to_string()
+}
+
+/// "CASH-..." -> "Cash", "CC-..." -> "Credit Card", "CP-..." -> "Card (terminal)",
+/// anything else -> its prefix
+pub(crate) fn payment_method(payment_id: &str) -> String {
+    match payment_id.split('-').next().unwrap_or_default() {
+        "CASH" => "Cash".to_string(),
+        "CC" => "Credit Card".to_string(),
+        "CP" => "Card (terminal)".to_string(),
+        "WALLET" => "Store Credit".to_string(),
+        "PAYPAL" => "PayPal".to_string(),
+        "" => "Unknown".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Sum of amounts; 0.0 (not -0.0, which `Sum` gives for no items) when empty
+fn total(amounts: impl Iterator<Item = f64>) -> f64 {
+    amounts.fold(0.0, |sum, amount| sum + amount)
+}
+
+/// `part / whole`, 0.0 when there is no whole
+fn rate(part: f64, whole: f64) -> f64 {
+    if whole > 0.0 { part / whole } else { 0.0 }
+}
+
+/// Nearest-rank percentile of sorted samples (0.0 when empty)
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Group (name, count, revenue) rows and compute revenue shares
+fn shares(rows: impl Iterator<Item = (String, usize, f64)>) -> Vec<MixShare> {
+    let mut groups: BTreeMap<String, (usize, f64)> = BTreeMap::new();
+    for (name, count, revenue) in rows {
+        let group = groups.entry(name).or_default();
+        group.0 += count;
+        group.1 += revenue;
+    }
+
+    let total: f64 = groups.values().map(|(_, revenue)| revenue).sum();
+    let mut shares: Vec<MixShare> = groups
+        .into_iter()
+        .map(|(name, (count, revenue))| MixShare {
+            name,
+            count,
+            revenue,
+            share: if total > 0.0 { revenue / total } else { 0.0 },
+        })
+        .collect();
+
+    shares.sort_by(|a, b| b.revenue.total_cmp(&a.revenue));
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Customer, Money, OrderItem};
+    use chrono::TimeZone;
+
+    fn make_order(name: &str, price: f64, payment_id: Option<&str>, day: u32) -> Order {
+        let customer = Customer::new("Test".to_string(), "test@example.com".to_string(), None);
+        let items = vec![OrderItem {
+            beverage_name: name.to_string(),
+            beverage_description: format!("Medium {}", name),
+            price: Money::usd(price),
+            quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        }];
+
+        let mut order = Order::new(customer, items);
+        order.created_at = chrono::Utc.with_ymd_and_hms(2024, 3, day, 8, 30, 0).unwrap();
+        if let Some(id) = payment_id {
+            order.mark_as_paid(id.to_string()).unwrap();
+        }
+        order
+    }
+
+    fn service() -> ReportingService<MemoryOrderRepository> {
+        let mut repo = MemoryOrderRepository::new();
+        // Monday 4 March 2024
+        repo.save(&make_order("Coffee", 3.50, Some("CASH-1"), 4)).unwrap();
+        repo.save(&make_order("Green Tea", 2.50, Some("CC-1"), 4)).unwrap();
+        repo.save(&make_order("Coffee (+1 shot)", 4.25, Some("CC-2"), 5)).unwrap();
+        // Never paid: not a sale
+        repo.save(&make_order("Coffee", 3.50, None, 4)).unwrap();
+        ReportingService::new(repo)
+    }
+
+    fn march(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_daily_report() {
+        let report = service().daily(march(4)).unwrap();
+
+        assert_eq!(report.orders, 2);
+        assert!((report.revenue - 6.00).abs() < 0.001);
+        assert!((report.average_ticket - 3.00).abs() < 0.001);
+        assert_eq!(report.hourly, vec![(8, 2)]);
+    }
+
+    #[test]
+    fn test_weekly_report() {
+        let report = service().weekly(march(6)).unwrap();
+
+        assert_eq!(report.from, march(4));
+        assert_eq!(report.days.len(), 7);
+        assert_eq!(report.days[1].orders, 1);
+        assert_eq!(report.orders, 3);
+    }
+
+    #[test]
+    fn test_product_and_payment_mix() {
+        let range = DateRange::week_of(march(4));
+
+        let products = service().product_mix(range).unwrap();
+        assert_eq!(products[0].name, "Coffee");
+        assert_eq!(products[0].count, 2);
+
+        let payments = service().payment_mix(range).unwrap();
+        assert_eq!(payments[0].name, "Credit Card");
+        let total_share: f64 = payments.iter().map(|p| p.share).sum();
+        assert!((total_share - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_profitability_per_beverage_and_day() {
+        let costs = CostModel::new()
+            .with_ingredient("espresso shot", 0.50)
+            .with_ingredient("tea bag", 0.25)
+            .with_recipe("Coffee", &[("espresso shot", 1.0)])
+            .with_recipe("Coffee (+1 shot)", &[("espresso shot", 2.0)]);
+        let report = service().profitability(DateRange::week_of(march(4)), &costs).unwrap();
+
+        // 3.50 - 0.50 for the coffee, 4.25 - 1.00 for the double
+        assert_eq!(report.beverages[0].name, "Coffee (+1 shot)");
+        assert!((report.beverages[0].margin - 3.25).abs() < 1e-9);
+        assert!((report.beverages[1].margin_rate - 3.0 / 3.5).abs() < 1e-9);
+        // The tea has no recipe
+        assert_eq!(report.uncosted_items, 1);
+        assert_eq!(report.days.len(), 7);
+        assert!((report.days[0].cost - 0.50).abs() < 1e-9);
+        assert!((report.days[1].margin - 3.25).abs() < 1e-9);
+        assert!((report.revenue - 10.25).abs() < 1e-9);
+        assert!((report.margin - 8.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_percentiles_use_nearest_rank() {
+        let samples: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let summary = LatencySummary::from_samples(samples);
+
+        assert_eq!(summary.samples, 100);
+        assert_eq!((summary.p50, summary.p95, summary.p99, summary.max), (50.0, 95.0, 99.0, 100.0));
+        assert_eq!(LatencySummary::from_samples(vec![]).p99, 0.0);
+    }
+
+    #[test]
+    fn test_latency_report_reads_status_history() {
+        let mut order = make_order("Coffee", 3.50, None, 4);
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        order.mark_as_preparing().unwrap();
+        order.mark_as_ready().unwrap();
+        let created = order.created_at;
+        for (change, minutes) in order.status_history.iter_mut().zip([0, 1, 2, 5]) {
+            change.at = created + Duration::minutes(minutes);
+        }
+        let mut repo = MemoryOrderRepository::new();
+        repo.save(&order).unwrap();
+
+        let report = ReportingService::new(repo).latency(DateRange::week_of(march(4))).unwrap();
+
+        assert_eq!(report.placement.p50, 60.0);
+        assert_eq!(report.preparation.samples, 1);
+        assert_eq!(report.preparation.p99, 180.0);
+    }
+}
@@ -0,0 +1,495 @@
+// SOLID: RepositoryAuditor - consistency checks over any storage backend
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    The auditor only knows the OrderRepository trait. It checks a JSON file,
+//    an in-memory store or a future database exactly the same way.
+//
+// 2. SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+//    Auditing (finding problems) and repairing (fixing them) are separate steps.
+//    `audit()` never writes. `apply()` only performs the actions in a plan
+//    the caller has looked at.
+//
+// 3. OPEN-CLOSED PRINCIPLE (OCP):
+//    Each invariant is an AuditRule. Turning rules on/off is configuration,
+//    not a code change.
+//
+// Typical use: someone hand-edited orders.json and we want to know
+// what they broke before the application trips over it.
+//
+// Totals are recomputed with an OrderPricing (LinePricing unless
+// `with_pricing` says otherwise), the same rule Order::new applies, and
+// status histories are checked against OrderStatus::can_transition_to, the
+// lifecycle Order's own transitions enforce.
+
+use crate::domain::{LinePricing, Money, Order, OrderPricing, OrderStatus};
+use crate::ports::{OrderRepository, RepositoryError};
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Invariants the auditor knows how to check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditRule {
+    /// total_price equals what the pricing makes of the items
+    TotalMatchesItems,
+    /// status history follows legal transitions and ends in the current status
+    LegalStatus,
+    /// every order that was paid carries a payment_id
+    PaymentIdPresent,
+    /// customer records are complete and consistent across orders
+    NoOrphanedCustomers,
+}
+
+impl AuditRule {
+    pub const ALL: [AuditRule; 4] = [
+        AuditRule::TotalMatchesItems,
+        AuditRule::LegalStatus,
+        AuditRule::PaymentIdPresent,
+        AuditRule::NoOrphanedCustomers,
+    ];
+}
+
+/// Which rules to run
+#[derive(Debug, Clone)]
+pub struct AuditorConfig {
+    pub rules: Vec<AuditRule>,
+    /// Allowed difference when comparing money amounts
+    pub tolerance: f64,
+}
+
+impl Default for AuditorConfig {
+    fn default() -> Self {
+        Self {
+            rules: AuditRule::ALL.to_vec(),
+            tolerance: 0.005,
+        }
+    }
+}
+
+/// One broken invariant
+#[derive(Debug, Clone)]
+pub struct AuditFinding {
+    pub order_id: Uuid,
+    pub rule: AuditRule,
+    pub message: String,
+}
+
+/// What to do about a finding
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairAction {
+    /// Replace total_price by the total recomputed from the items
+    RecomputeTotal { order_id: Uuid, from: Money, to: Money },
+    /// Append the current status to the history so they agree again
+    SyncStatusHistory { order_id: Uuid },
+    /// Needs a human: the auditor can't guess the right value
+    ManualReview { order_id: Uuid, reason: String },
+}
+
+impl RepairAction {
+    /// Can `apply()` perform this action on its own?
+    pub fn is_automatic(&self) -> bool {
+        !matches!(self, RepairAction::ManualReview { .. })
+    }
+}
+
+/// Result of an audit run
+#[derive(Debug, Clone)]
+pub struct AuditReport {
+    pub orders_checked: usize,
+    pub findings: Vec<AuditFinding>,
+    pub repair_plan: Vec<RepairAction>,
+}
+
+impl AuditReport {
+    /// No invariant was broken
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Validates repository invariants and repairs what can be repaired
+pub struct RepositoryAuditor {
+    config: AuditorConfig,
+    pricing: Box<dyn OrderPricing>,
+}
+
+impl RepositoryAuditor {
+    pub fn new(config: AuditorConfig) -> Self {
+        Self {
+            config,
+            pricing: Box::new(LinePricing),
+        }
+    }
+
+    /// Recompute totals with `pricing` instead of LinePricing
+    pub fn with_pricing(mut self, pricing: impl OrderPricing + 'static) -> Self {
+        self.pricing = Box::new(pricing);
+        self
+    }
+
+    /// Check every order in the repository (read-only)
+    pub fn audit<R: OrderRepository>(&self, repository: &R) -> Result<AuditReport, RepositoryError> {
+        let orders = repository.list_all()?;
+        Ok(self.audit_orders(&orders))
+    }
+
+    /// Check a list of orders (read-only)
+    pub fn audit_orders(&self, orders: &[Order]) -> AuditReport {
+        let mut findings = Vec::new();
+        let mut repair_plan = Vec::new();
+
+        for rule in &self.config.rules {
+            match rule {
+                AuditRule::TotalMatchesItems => {
+                    self.check_totals(orders, &mut findings, &mut repair_plan)
+                }
+                AuditRule::LegalStatus => {
+                    Self::check_statuses(orders, &mut findings, &mut repair_plan)
+                }
+                AuditRule::PaymentIdPresent => {
+                    Self::check_payment_ids(orders, &mut findings, &mut repair_plan)
+                }
+                AuditRule::NoOrphanedCustomers => {
+                    Self::check_customers(orders, &mut findings, &mut repair_plan)
+                }
+            }
+        }
+
+        AuditReport {
+            orders_checked: orders.len(),
+            findings,
+            repair_plan,
+        }
+    }
+
+    /// Perform the automatic actions of a plan
+    ///
+    /// Manual-review actions are skipped. Returns how many actions were applied.
+    pub fn apply<R: OrderRepository>(
+        &self,
+        plan: &[RepairAction],
+        repository: &mut R,
+    ) -> Result<usize, RepositoryError> {
+        let mut applied = 0;
+
+        for action in plan.iter().filter(|action| action.is_automatic()) {
+            let order_id = match action {
+                RepairAction::RecomputeTotal { order_id, .. }
+                | RepairAction::SyncStatusHistory { order_id } => *order_id,
+                RepairAction::ManualReview { .. } => continue,
+            };
+
+            let Some(mut order) = repository.find_by_id(order_id)? else {
+                continue;
+            };
+
+            match action {
+                RepairAction::RecomputeTotal { .. } => {
+                    order.recompute_total(self.pricing.as_ref());
+                }
+                RepairAction::SyncStatusHistory { .. } => {
+                    order.status_history.push(crate::domain::StatusChange {
+                        status: order.status.clone(),
+                        at: Utc::now(),
+                    });
+                }
+                RepairAction::ManualReview { .. } => unreachable!(),
+            }
+
+            repository.update(&order)?;
+            applied += 1;
+        }
+
+        Ok(applied)
+    }
+
+    fn check_totals(
+        &self,
+        orders: &[Order],
+        findings: &mut Vec<AuditFinding>,
+        plan: &mut Vec<RepairAction>,
+    ) {
+        for order in orders {
+            let expected = order.expected_total(self.pricing.as_ref());
+            let drift = expected.checked_sub(order.total_price).map(|d| d.to_major().abs());
+
+            if drift.is_none_or(|drift| drift > self.config.tolerance) {
+                findings.push(AuditFinding {
+                    order_id: order.id,
+                    rule: AuditRule::TotalMatchesItems,
+                    message: format!(
+                        "total is {} but items add up to {}",
+                        order.total_price, expected
+                    ),
+                });
+                plan.push(RepairAction::RecomputeTotal {
+                    order_id: order.id,
+                    from: order.total_price,
+                    to: expected,
+                });
+            }
+        }
+    }
+
+    fn check_statuses(
+        orders: &[Order],
+        findings: &mut Vec<AuditFinding>,
+        plan: &mut Vec<RepairAction>,
+    ) {
+        for order in orders {
+            // Orders saved before status history existed have nothing to check
+            let Some(last) = order.status_history.last() else {
+                continue;
+            };
+
+            let illegal = order
+                .status_history
+                .windows(2)
+                .find(|pair| !pair[0].status.can_transition_to(&pair[1].status));
+
+            if let Some(pair) = illegal {
+                findings.push(AuditFinding {
+                    order_id: order.id,
+                    rule: AuditRule::LegalStatus,
+                    message: format!(
+                        "illegal transition {:?} -> {:?}",
+                        pair[0].status, pair[1].status
+                    ),
+                });
+                plan.push(RepairAction::ManualReview {
+                    order_id: order.id,
+                    reason: "status history contains an illegal transition".to_string(),
+                });
+            } else if last.status != order.status {
+                findings.push(AuditFinding {
+                    order_id: order.id,
+                    rule: AuditRule::LegalStatus,
+                    message: format!(
+                        "status is {} but history ends in {}",
+                        order.status, last.status
+                    ),
+                });
+
+                if last.status.can_transition_to(&order.status) {
+                    plan.push(RepairAction::SyncStatusHistory { order_id: order.id });
+                } else {
+                    plan.push(RepairAction::ManualReview {
+                        order_id: order.id,
+                        reason: format!(
+                            "{:?} can't follow {:?}",
+                            order.status, last.status
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_payment_ids(
+        orders: &[Order],
+        findings: &mut Vec<AuditFinding>,
+        plan: &mut Vec<RepairAction>,
+    ) {
+        for order in orders {
+            let requires_payment = matches!(
+                order.status,
+                OrderStatus::Paid
+                    | OrderStatus::Preparing
+                    | OrderStatus::Ready
+                    | OrderStatus::Completed
+                    | OrderStatus::NoShow
+                    | OrderStatus::Disputed
+                    | OrderStatus::Refunded
+            );
+
+            let missing = order
+                .payment_id
+                .as_deref()
+                .is_none_or(|id| id.trim().is_empty());
+
+            if requires_payment && missing {
+                findings.push(AuditFinding {
+                    order_id: order.id,
+                    rule: AuditRule::PaymentIdPresent,
+                    message: format!("{} order has no payment_id", order.status),
+                });
+                plan.push(RepairAction::ManualReview {
+                    order_id: order.id,
+                    reason: "find the payment in the processor's records".to_string(),
+                });
+            }
+        }
+    }
+
+    fn check_customers(
+        orders: &[Order],
+        findings: &mut Vec<AuditFinding>,
+        plan: &mut Vec<RepairAction>,
+    ) {
+        // First email seen for each customer id
+        let mut emails: HashMap<Uuid, &str> = HashMap::new();
+
+        for order in orders {
+            let customer = &order.customer;
+
+            let problem = if customer.name.trim().is_empty() || customer.email.trim().is_empty() {
+                Some("customer record has no name or email".to_string())
+            } else {
+                match emails.get(&customer.id) {
+                    Some(email) if *email != customer.email => Some(format!(
+                        "customer {} appears as both {} and {}",
+                        customer.id, email, customer.email
+                    )),
+                    Some(_) => None,
+                    None => {
+                        emails.insert(customer.id, &customer.email);
+                        None
+                    }
+                }
+            };
+
+            if let Some(message) = problem {
+                findings.push(AuditFinding {
+                    order_id: order.id,
+                    rule: AuditRule::NoOrphanedCustomers,
+                    message: message.clone(),
+                });
+                plan.push(RepairAction::ManualReview {
+                    order_id: order.id,
+                    reason: message,
+                });
+            }
+        }
+    }
+}
+
+impl Default for RepositoryAuditor {
+    fn default() -> Self {
+        Self::new(AuditorConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Customer, OrderItem};
+    use crate::services::PricingCalculator;
+
+    fn make_paid_order() -> Order {
+        let customer = Customer::new(
+            "Test User".to_string(),
+            "test@example.com".to_string(),
+            None,
+        );
+
+        let items = vec![OrderItem {
+            beverage_name: "Coffee".to_string(),
+            beverage_description: "Medium Coffee".to_string(),
+            price: Money::usd(3.50),
+            quantity: 2,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        }];
+
+        let mut order = Order::new(customer, items);
+        order.mark_as_paid("PAY-123".to_string()).unwrap();
+        order
+    }
+
+    #[test]
+    fn test_clean_repository() {
+        let mut repo = MemoryOrderRepository::new();
+        repo.save(&make_paid_order()).unwrap();
+
+        let report = RepositoryAuditor::default().audit(&repo).unwrap();
+
+        assert!(report.is_clean());
+        assert_eq!(report.orders_checked, 1);
+    }
+
+    #[test]
+    fn test_hand_edited_total_is_repaired() {
+        let mut repo = MemoryOrderRepository::new();
+        let mut order = make_paid_order();
+        order.total_price = Money::usd(1.00);
+        repo.save(&order).unwrap();
+
+        let auditor = RepositoryAuditor::default();
+        let report = auditor.audit(&repo).unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].rule, AuditRule::TotalMatchesItems);
+
+        let applied = auditor.apply(&report.repair_plan, &mut repo).unwrap();
+        assert_eq!(applied, 1);
+        assert_eq!(repo.find_by_id(order.id).unwrap().unwrap().total_price, Money::usd(7.00));
+        assert!(auditor.audit(&repo).unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_totals_follow_the_configured_pricing() {
+        let order = make_paid_order();
+        let auditor = RepositoryAuditor::default().with_pricing(PricingCalculator::new(0.10));
+
+        let report = auditor.audit_orders(std::slice::from_ref(&order));
+        assert_eq!(
+            report.repair_plan,
+            vec![RepairAction::RecomputeTotal {
+                order_id: order.id,
+                from: Money::usd(7.00),
+                to: Money::usd(7.70)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_missing_payment_id_needs_review() {
+        let mut order = make_paid_order();
+        order.payment_id = None;
+
+        let report = RepositoryAuditor::default().audit_orders(&[order]);
+
+        assert_eq!(report.findings[0].rule, AuditRule::PaymentIdPresent);
+        assert!(!report.repair_plan[0].is_automatic());
+    }
+
+    #[test]
+    fn test_status_out_of_sync_with_history() {
+        let mut order = make_paid_order();
+        // Hand edit: "status": "Preparing" without touching the history
+        order.status = OrderStatus::Preparing;
+
+        let auditor = RepositoryAuditor::default();
+        let report = auditor.audit_orders(std::slice::from_ref(&order));
+        assert_eq!(
+            report.repair_plan,
+            vec![RepairAction::SyncStatusHistory { order_id: order.id }]
+        );
+
+        // Skipping from Pending straight to Ready is illegal
+        let mut skipped = make_paid_order();
+        skipped.status_history.pop();
+        skipped.status = OrderStatus::Ready;
+        let report = auditor.audit_orders(&[skipped]);
+        assert!(!report.repair_plan[0].is_automatic());
+    }
+
+    #[test]
+    fn test_inconsistent_customer() {
+        let first = make_paid_order();
+        let mut second = make_paid_order();
+        second.customer = first.customer.clone();
+        second.customer.email = "someone-else@example.com".to_string();
+
+        let config = AuditorConfig {
+            rules: vec![AuditRule::NoOrphanedCustomers],
+            ..AuditorConfig::default()
+        };
+        let report = RepositoryAuditor::new(config).audit_orders(&[first, second]);
+
+        assert_eq!(report.findings.len(), 1);
+    }
+}
@@ -0,0 +1,170 @@
+// SOLID: RepositoryMigrator - copy every order from one backend to another
+//
+// PRINCIPLES DEMONSTRATED:
+//
+// 1. DEPENDENCY INVERSION PRINCIPLE (DIP):
+//    Source and target are any OrderRepository: memory to JSON, JSON to a
+//    future database, and so on. The migrator names no adapter.
+//
+// 2. INTERFACE SEGREGATION PRINCIPLE (ISP):
+//    Only the TARGET must implement RepositoryCapabilities. The migrator asks
+//    it how to write instead of assuming the weakest possible backend:
+//    - transactional targets get `save_batch` calls of up to max_batch_size orders
+//    - other targets get one order per call, so a failure leaves a known prefix
+//      copied and `copied` in the error message is exact
+//
+// Conflicts are checked before anything is written: an ID that already exists
+// in the target is either skipped (`skip_existing`) or aborts the migration
+// with nothing copied.
+
+use crate::domain::Order;
+use crate::ports::{OrderRepository, RepositoryCapabilities, RepositoryError};
+
+/// What a migration did
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+    pub copied: usize,
+    /// Orders already present in the target (only with `skip_existing`)
+    pub skipped: usize,
+    /// Number of `save_batch` calls
+    pub batches: usize,
+}
+
+/// Copies orders between repositories, adapting to the target's capabilities
+#[derive(Debug, Clone, Default)]
+pub struct RepositoryMigrator {
+    skip_existing: bool,
+}
+
+impl RepositoryMigrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip orders the target already has instead of failing
+    pub fn skip_existing(mut self, skip: bool) -> Self {
+        self.skip_existing = skip;
+        self
+    }
+
+    /// Copy every order from `source` into `target`, oldest first
+    pub fn migrate<S, T>(&self, source: &S, target: &mut T) -> Result<MigrationReport, RepositoryError>
+    where
+        S: OrderRepository,
+        T: OrderRepository + RepositoryCapabilities,
+    {
+        let mut orders = source.list_all()?;
+        orders.sort_by_key(|order| order.created_at);
+
+        let mut pending: Vec<Order> = Vec::with_capacity(orders.len());
+        let mut skipped = 0;
+        for order in orders {
+            if target.find_by_id(order.id)?.is_none() {
+                pending.push(order);
+            } else if self.skip_existing {
+                skipped += 1;
+            } else {
+                return Err(RepositoryError::AlreadyExists(format!(
+                    "Order {} already exists in the target (nothing was copied)",
+                    order.id
+                )));
+            }
+        }
+
+        let batch_size = if target.supports_transactions() {
+            target.max_batch_size().unwrap_or(pending.len()).max(1)
+        } else {
+            1
+        };
+
+        let mut report = MigrationReport {
+            copied: 0,
+            skipped,
+            batches: 0,
+        };
+        for batch in pending.chunks(batch_size) {
+            target.save_batch(batch).map_err(|e| {
+                RepositoryError::SaveFailed(format!("{} (after copying {} orders)", e, report.copied))
+            })?;
+            report.copied += batch.len();
+            report.batches += 1;
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MemoryOrderRepository;
+    use crate::domain::{Coffee, Customer};
+
+    fn make_order() -> Order {
+        Order::single(Customer::named("Ada"), Coffee::medium())
+    }
+
+    /// A target without transactions and a small batch limit
+    struct PlainRepository(MemoryOrderRepository);
+
+    impl OrderRepository for PlainRepository {
+        fn save(&mut self, order: &Order) -> Result<(), RepositoryError> {
+            self.0.save(order)
+        }
+        fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<Order>, RepositoryError> {
+            self.0.find_by_id(id)
+        }
+        fn find_by_customer_email(&self, email: &str) -> Result<Vec<Order>, RepositoryError> {
+            self.0.find_by_customer_email(email)
+        }
+        fn list_all(&self) -> Result<Vec<Order>, RepositoryError> {
+            self.0.list_all()
+        }
+        fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+            self.0.update(order)
+        }
+        fn delete(&mut self, id: uuid::Uuid) -> Result<bool, RepositoryError> {
+            self.0.delete(id)
+        }
+    }
+
+    impl RepositoryCapabilities for PlainRepository {}
+
+    fn source_with(count: usize) -> MemoryOrderRepository {
+        let mut source = MemoryOrderRepository::new();
+        for _ in 0..count {
+            source.save(&make_order()).unwrap();
+        }
+        source
+    }
+
+    #[test]
+    fn test_batch_size_follows_target_capabilities() {
+        let source = source_with(3);
+
+        let mut transactional = MemoryOrderRepository::new();
+        let report = RepositoryMigrator::new().migrate(&source, &mut transactional).unwrap();
+        assert_eq!((report.copied, report.batches), (3, 1));
+
+        let mut plain = PlainRepository(MemoryOrderRepository::new());
+        let report = RepositoryMigrator::new().migrate(&source, &mut plain).unwrap();
+        assert_eq!((report.copied, report.batches), (3, 3));
+    }
+
+    #[test]
+    fn test_conflicts_abort_or_skip() {
+        let source = source_with(2);
+        let mut target = MemoryOrderRepository::new();
+        target.save(&source.list_all().unwrap()[0]).unwrap();
+
+        assert!(RepositoryMigrator::new().migrate(&source, &mut target).is_err());
+        assert_eq!(target.count(), 1);
+
+        let report = RepositoryMigrator::new()
+            .skip_existing(true)
+            .migrate(&source, &mut target)
+            .unwrap();
+        assert_eq!((report.copied, report.skipped), (1, 1));
+        assert_eq!(target.count(), 2);
+    }
+}
@@ -0,0 +1,353 @@
+// SOLID: RewardService - birthday and anniversary coupons
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Occasion (domain) decides which days are special for a customer, the
+// CouponStore keeps coupons, the Notifier tells the customer. This service
+// ties them together once a day, and checks coupons at the counter.
+//
+// IDEMPOTENCE:
+// A customer gets at most one coupon per occasion per year: the store is
+// checked before granting, so a rerun of the job grants nothing new. A
+// coupon is saved before the customer is told; if telling fails, the coupon
+// still stands (the code can be looked up) and is not granted again.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// "Today" comes from the Clock port, coupons from any CouponStore, and the
+// message goes through any Notifier.
+//
+// There is no customer table: customers are taken from their orders, the
+// most recent order's copy of a customer being the up-to-date one.
+//
+// Scheduled daily (`Job` on OrderService), early enough that the message
+// arrives before the customer's morning coffee.
+
+use super::job_scheduler::{Job, JobError};
+use super::order_service::OrderService;
+use super::promotion_engine::PercentOff;
+use crate::domain::{Coupon, Customer, Occasion, Order, RewardGrantedNotice};
+use crate::ports::{Clock, CouponError, CouponStore, Notifier, OrderRepository, PaymentProcessor};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Errors from redeeming a coupon
+#[derive(Debug)]
+pub enum RewardError {
+    UnknownCoupon,
+    /// Coupons are personal: this one belongs to another customer
+    NotYours,
+    Expired,
+    AlreadyRedeemed,
+    /// Held back while one of the customer's payments is disputed
+    Frozen,
+    StorageFailed(CouponError),
+}
+
+impl fmt::Display for RewardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RewardError::UnknownCoupon => write!(f, "No coupon with this code"),
+            RewardError::NotYours => write!(f, "This coupon belongs to another customer"),
+            RewardError::Expired => write!(f, "This coupon has expired"),
+            RewardError::AlreadyRedeemed => write!(f, "This coupon was already used"),
+            RewardError::Frozen => write!(f, "This coupon is on hold (a disputed payment)"),
+            RewardError::StorageFailed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for RewardError {}
+
+impl From<CouponError> for RewardError {
+    fn from(e: CouponError) -> Self {
+        RewardError::StorageFailed(e)
+    }
+}
+
+/// How generous each occasion is
+///
+/// Owned by Marketing, like NoShowPolicy is by Operations.
+#[derive(Debug, Clone)]
+pub struct RewardPolicy {
+    birthday_percent_off: f64,
+    anniversary_percent_off: f64,
+    valid_for: Duration,
+}
+
+impl RewardPolicy {
+    pub fn new(birthday_percent_off: f64, anniversary_percent_off: f64, valid_days: i64) -> Self {
+        Self {
+            birthday_percent_off,
+            anniversary_percent_off,
+            valid_for: Duration::days(valid_days),
+        }
+    }
+
+    pub fn percent_off(&self, occasion: Occasion) -> f64 {
+        match occasion {
+            Occasion::Birthday => self.birthday_percent_off,
+            Occasion::Anniversary { .. } => self.anniversary_percent_off,
+        }
+    }
+}
+
+impl Default for RewardPolicy {
+    /// Half off on birthdays, 20% on anniversaries, a month to use them
+    fn default() -> Self {
+        Self::new(50.0, 20.0, 30)
+    }
+}
+
+/// What one run granted
+#[derive(Debug, Default)]
+pub struct RewardRun {
+    pub granted: Vec<Coupon>,
+    /// Occasions today whose coupon was granted on an earlier run
+    pub already_granted: usize,
+}
+
+/// Grants reward coupons on the day, and redeems them
+pub struct RewardService<S: CouponStore, N: Notifier> {
+    coupons: S,
+    notifier: N,
+    policy: RewardPolicy,
+}
+
+impl<S: CouponStore, N: Notifier> RewardService<S, N> {
+    pub fn new(coupons: S, notifier: N) -> Self {
+        Self {
+            coupons,
+            notifier,
+            policy: RewardPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: RewardPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Grant the coupons of every occasion falling on the clock's day
+    ///
+    /// Guests are skipped: there is nobody to send a coupon to.
+    pub fn grant_due(
+        &mut self,
+        orders: &[Order],
+        clock: &dyn Clock,
+    ) -> Result<RewardRun, CouponError> {
+        let now = clock.now();
+        let today = now.date_naive();
+        let mut run = RewardRun::default();
+
+        for customer in latest_customers(orders) {
+            for occasion in Occasion::falling_on(customer, today) {
+                let granted_before = self
+                    .coupons
+                    .for_customer(&customer.email)?
+                    .iter()
+                    .any(|c| c.occasion == occasion && c.year == today.year());
+                if granted_before {
+                    run.already_granted += 1;
+                    continue;
+                }
+
+                let coupon = self.grant(customer, occasion, now)?;
+                run.granted.push(coupon);
+            }
+        }
+        Ok(run)
+    }
+
+    fn grant(
+        &mut self,
+        customer: &Customer,
+        occasion: Occasion,
+        now: DateTime<Utc>,
+    ) -> Result<Coupon, CouponError> {
+        let percent_off = self.policy.percent_off(occasion);
+        let valid_for = self.policy.valid_for;
+        let coupon = Coupon::new(&customer.email, occasion, percent_off, now, valid_for);
+        self.coupons.save(coupon.clone())?;
+
+        let notice = RewardGrantedNotice::from_coupon(&coupon, customer);
+        if let Err(e) = self.notifier.notify_reward_granted(&notice) {
+            eprintln!("Warning: Failed to send reward coupon {}: {}", coupon.code, e);
+        }
+        Ok(coupon)
+    }
+
+    /// Use a coupon for `customer_email`'s order
+    ///
+    /// Returns the discount to add to the order's PromotionEngine. The
+    /// coupon is spent here, so the same code can't discount two orders.
+    pub fn redeem(
+        &mut self,
+        code: &str,
+        customer_email: &str,
+        clock: &dyn Clock,
+    ) -> Result<PercentOff, RewardError> {
+        let mut coupon = self.coupons.find(code)?.ok_or(RewardError::UnknownCoupon)?;
+        if !coupon.customer_email.eq_ignore_ascii_case(customer_email) {
+            return Err(RewardError::NotYours);
+        }
+        if coupon.redeemed_at.is_some() {
+            return Err(RewardError::AlreadyRedeemed);
+        }
+        if coupon.frozen {
+            return Err(RewardError::Frozen);
+        }
+        if !coupon.redeem(clock.now()) {
+            return Err(RewardError::Expired);
+        }
+        self.coupons.save(coupon.clone())?;
+
+        let label = format!("{} -{:.0}%", coupon.occasion.label(), coupon.percent_off);
+        Ok(PercentOff::new(&label, coupon.percent_off))
+    }
+
+    pub fn coupons(&self) -> &S {
+        &self.coupons
+    }
+}
+
+/// Each customer's most recent copy, guests left out
+pub(crate) fn latest_customers(orders: &[Order]) -> Vec<&Customer> {
+    let mut latest: HashMap<String, &Order> = HashMap::new();
+    for order in orders.iter().filter(|o| !o.customer.is_guest()) {
+        let key = order.customer.email.to_lowercase();
+        match latest.get(&key) {
+            Some(seen) if seen.created_at >= order.created_at => {}
+            _ => {
+                latest.insert(key, order);
+            }
+        }
+    }
+
+    let mut customers: Vec<&Customer> = latest.into_values().map(|o| &o.customer).collect();
+    customers.sort_by(|a, b| a.email.cmp(&b.email));
+    customers
+}
+
+impl<R, P, N, S, M> Job<OrderService<R, P, N>> for RewardService<S, M>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+    S: CouponStore,
+    M: Notifier,
+{
+    fn run(
+        &mut self,
+        service: &mut OrderService<R, P, N>,
+        clock: &dyn Clock,
+    ) -> Result<String, JobError> {
+        let orders = service.list_all_orders().map_err(|e| JobError::Failed(e.to_string()))?;
+        let run = self
+            .grant_due(&orders, clock)
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        Ok(format!(
+            "{} reward coupon(s) granted, {} already granted today",
+            run.granted.len(),
+            run.already_granted
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        read_notification_records, CashPayment, ConsoleNotifier, FileNotifier, FixedClock,
+        MemoryCouponStore, MemoryOrderRepository,
+    };
+    use crate::domain::{Money, OrderItem};
+    use crate::ports::RepositoryError;
+    use crate::services::promotion_engine::Promotion;
+    use chrono::{NaiveDate, TimeZone};
+    use std::io;
+    use uuid::Uuid;
+
+    fn order_for(customer: Customer) -> Order {
+        Order::new(
+            customer,
+            vec![OrderItem {
+                beverage_name: "Coffee".to_string(),
+                beverage_description: "Large latte".to_string(),
+                price: Money::usd(4.5),
+                quantity: 1,
+                prep_seconds: None,
+                price_basis: None,
+                vat_rate: None,
+            }],
+        )
+    }
+
+    fn ada() -> Customer {
+        Customer::new("Ada".to_string(), "ada@example.com".to_string(), None)
+            .with_birthday(NaiveDate::from_ymd_opt(1990, 3, 14).unwrap())
+            .with_member_since(NaiveDate::from_ymd_opt(2024, 3, 14).unwrap())
+    }
+
+    #[test]
+    fn test_job_grants_once_per_occasion_and_tells_the_customer() -> Result<(), RepositoryError> {
+        let path = std::env::temp_dir().join(format!("rewards-{}.jsonl", Uuid::new_v4()));
+        let mut repository = MemoryOrderRepository::new();
+        repository.save(&order_for(ada()))?;
+        repository.save(&order_for(Customer::guest()))?;
+        let bob = Customer::new("Bob".to_string(), "bob@example.com".to_string(), None);
+        repository.save(&order_for(bob))?;
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let mut service = OrderService::new(repository, CashPayment, notifier);
+
+        let notifier = FileNotifier::new(path.clone());
+        let mut rewards = RewardService::new(MemoryCouponStore::new(), notifier);
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 3, 14, 7, 0, 0).unwrap());
+        let first = rewards.run(&mut service, &clock).unwrap();
+        assert_eq!(first, "2 reward coupon(s) granted, 0 already granted today");
+        let second = rewards.run(&mut service, &clock).unwrap();
+        assert_eq!(second, "0 reward coupon(s) granted, 2 already granted today");
+
+        let coupons = rewards.coupons().for_customer("ada@example.com").unwrap();
+        assert_eq!(coupons[0].percent_off, 50.0);
+        assert_eq!(coupons[1].occasion, Occasion::Anniversary { years: 2 });
+        let records = read_notification_records(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].event, "reward.granted");
+        assert_eq!(records[0].order_id, coupons[0].id);
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_coupons_are_personal_and_single_use() {
+        let notifier = ConsoleNotifier::with_writer(io::sink());
+        let mut rewards = RewardService::new(MemoryCouponStore::new(), notifier);
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 3, 14, 7, 0, 0).unwrap());
+        let run = rewards.grant_due(&[order_for(ada())], &clock).unwrap();
+        let code = run.granted[0].code.clone();
+
+        assert!(matches!(
+            rewards.redeem(&code, "bob@example.com", &clock),
+            Err(RewardError::NotYours)
+        ));
+        let offer = rewards.redeem(&code, "ADA@example.com", &clock).unwrap();
+        assert_eq!(offer.label(), "Happy birthday -50%");
+        assert!(matches!(
+            rewards.redeem(&code, "ada@example.com", &clock),
+            Err(RewardError::AlreadyRedeemed)
+        ));
+
+        let late = FixedClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 7, 0, 0).unwrap());
+        let anniversary = run.granted[1].code.clone();
+        assert!(matches!(
+            rewards.redeem(&anniversary, "ada@example.com", &late),
+            Err(RewardError::Expired)
+        ));
+        assert!(matches!(
+            rewards.redeem("GIFT-NOPE", "ada@example.com", &clock),
+            Err(RewardError::UnknownCoupon)
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+// SOLID: SharedOrderService - one OrderService, any number of threads
+//
+// OrderService takes `&mut self` to place an order: it saves, charges, takes
+// stock and numbers receipts, and those steps must not interleave between
+// two orders. This handle puts the service behind a lock and offers the
+// same workflow on `&self`, so an HTTP handler, a kiosk and a worker thread
+// can each hold a clone of it.
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Like SharedOrderRepository, sharing is added around the service rather
+// than taught to it: OrderService and its adapters are unchanged, and the
+// ports only ask that implementations can move to another thread (`Send`).
+//
+// Calls are serialized: an order is placed start to finish (payment and
+// notification included) before the next begins, which keeps stock counts
+// and fiscal numbers exact. Reads wait for a placement in progress, unless
+// `with_reads` gives them the repository itself (a clone of the service's
+// SharedOrderRepository): then a lookup never waits on a payment gateway.
+//
+// A call that panics leaves its order half done: every call after it fails
+// with OrderServiceError::ServicePoisoned (503 from the API).
+
+use crate::domain::{Beverage, Customer, Order, OrderLine};
+use crate::ports::{Notifier, OrderRepository, PaymentProcessor};
+use crate::services::order_service::{OrderService, OrderServiceError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Thread-safe, cloneable handle over one OrderService
+pub struct SharedOrderService<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    inner: Arc<Mutex<OrderService<R, P, N>>>,
+    // Where reads go without the service's lock (None: through the service)
+    reads: Option<Arc<dyn OrderRepository + Sync>>,
+}
+
+impl<R, P, N> SharedOrderService<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    /// Share a configured service (builders first, then wrap it)
+    pub fn new(service: OrderService<R, P, N>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(service)),
+            reads: None,
+        }
+    }
+
+    /// Answer `get_order` and the lists from `repository`, without waiting for
+    /// the call in progress (it must hold the same orders as the service's)
+    pub fn with_reads(mut self, repository: impl OrderRepository + Sync + 'static) -> Self {
+        self.reads = Some(Arc::new(repository));
+        self
+    }
+
+    /// Run `f` with the service to itself, for the calls not mirrored here
+    pub fn with<T>(
+        &self,
+        f: impl FnOnce(&mut OrderService<R, P, N>) -> Result<T, OrderServiceError>,
+    ) -> Result<T, OrderServiceError> {
+        f(&mut *self.lock()?)
+    }
+
+    pub fn place_order(
+        &self,
+        customer: Customer,
+        beverages: Vec<Box<dyn Beverage>>,
+    ) -> Result<Order, OrderServiceError> {
+        self.lock()?.place_order(customer, beverages)
+    }
+
+    pub fn place_order_lines(
+        &self,
+        customer: Customer,
+        lines: Vec<OrderLine>,
+    ) -> Result<Order, OrderServiceError> {
+        self.lock()?.place_order_lines(customer, lines)
+    }
+
+    pub fn get_order(&self, id: Uuid) -> Result<Order, OrderServiceError> {
+        match &self.reads {
+            Some(reads) => reads
+                .find_by_id(id)
+                .map_err(OrderServiceError::StorageFailed)?
+                .ok_or(OrderServiceError::OrderNotFound),
+            None => self.lock()?.get_order(id),
+        }
+    }
+
+    pub fn list_customer_orders(&self, email: &str) -> Result<Vec<Order>, OrderServiceError> {
+        match &self.reads {
+            Some(reads) => reads
+                .find_by_customer_email(email)
+                .map_err(OrderServiceError::StorageFailed),
+            None => self.lock()?.list_customer_orders(email),
+        }
+    }
+
+    pub fn list_all_orders(&self) -> Result<Vec<Order>, OrderServiceError> {
+        match &self.reads {
+            Some(reads) => reads.list_all().map_err(OrderServiceError::StorageFailed),
+            None => self.lock()?.list_all_orders(),
+        }
+    }
+
+    pub fn mark_order_preparing(&self, id: Uuid) -> Result<(), OrderServiceError> {
+        self.lock()?.mark_order_preparing(id)
+    }
+
+    pub fn mark_order_ready(&self, id: Uuid) -> Result<(), OrderServiceError> {
+        self.lock()?.mark_order_ready(id)
+    }
+
+    pub fn complete_order(&self, id: Uuid) -> Result<(), OrderServiceError> {
+        self.lock()?.complete_order(id)
+    }
+
+    pub fn cancel_order(&self, id: Uuid) -> Result<(), OrderServiceError> {
+        self.lock()?.cancel_order(id)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, OrderService<R, P, N>>, OrderServiceError> {
+        self.inner
+            .lock()
+            .map_err(|e| OrderServiceError::ServicePoisoned(e.to_string()))
+    }
+}
+
+// Manual impl: cloning the handle must not require the adapters to be Clone
+impl<R, P, N> Clone for SharedOrderService<R, P, N>
+where
+    R: OrderRepository,
+    P: PaymentProcessor,
+    N: Notifier,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            reads: self.reads.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{
+        CashPayment, ConsoleNotifier, MemoryInventoryRepository, MemoryOrderRepository,
+    };
+    use crate::domain::{Coffee, Money, StockLevel};
+    use crate::services::InventoryService;
+    use std::thread;
+
+    #[test]
+    fn test_orders_placed_from_many_threads_all_land() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let shared = SharedOrderService::new(OrderService::new(
+            MemoryOrderRepository::new(),
+            CashPayment,
+            notifier,
+        ));
+
+        let workers: Vec<_> = (0..8)
+            .map(|worker| {
+                let service = shared.clone();
+                thread::spawn(move || {
+                    let customer = Customer::named(&format!("Guest{}", worker));
+                    for _ in 0..25 {
+                        let order = service
+                            .place_order(customer.clone(), vec![Box::new(Coffee::medium())])
+                            .unwrap();
+                        assert!(service.get_order(order.id).is_ok());
+                    }
+                    let email = customer.email;
+                    service.list_customer_orders(&email).unwrap().len()
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            assert_eq!(worker.join().unwrap(), 25);
+        }
+        let all = shared.list_all_orders().unwrap();
+        assert_eq!(all.len(), 200);
+        assert_eq!(all.iter().map(|order| order.total_price).sum::<Money>(), Money::usd(700.0));
+    }
+
+    #[test]
+    fn test_stock_is_never_oversold_under_contention() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let service = OrderService::new(MemoryOrderRepository::new(), CashPayment, notifier)
+            .with_inventory(InventoryService::new(MemoryInventoryRepository::with_levels([
+                StockLevel::new("espresso shot", 30.0),
+            ])));
+        let shared = SharedOrderService::new(service);
+
+        let workers: Vec<_> = (0..6)
+            .map(|worker| {
+                let service = shared.clone();
+                thread::spawn(move || {
+                    let customer = Customer::named(&format!("Guest{}", worker));
+                    (0..10)
+                        .filter(|_| {
+                            service
+                                .place_order(customer.clone(), vec![Box::new(Coffee::medium())])
+                                .is_ok()
+                        })
+                        .count()
+                })
+            })
+            .collect();
+
+        let placed: usize = workers.into_iter().map(|w| w.join().unwrap()).sum();
+        assert_eq!(shared.list_all_orders().unwrap().len(), placed);
+        // One shot per medium coffee: exactly 30 sold, never 31
+        assert_eq!(placed, 30);
+    }
+
+    #[test]
+    fn test_reads_do_not_wait_for_a_placement_in_progress() {
+        use crate::adapters::SharedOrderRepository;
+        use crate::ports::PaymentError;
+        use std::sync::mpsc::{channel, Receiver, Sender};
+
+        /// Tells when it's charging, then waits to be let through
+        struct SlowGateway(Sender<()>, Mutex<Receiver<()>>);
+        impl PaymentProcessor for SlowGateway {
+            fn process_payment(&self, _amount: Money) -> Result<String, PaymentError> {
+                self.0.send(()).unwrap();
+                self.1.lock().unwrap().recv().unwrap();
+                Ok(format!("SLOW-{}", Uuid::new_v4()))
+            }
+        }
+
+        let ((charging, started), (go, waiting)) = (channel(), channel());
+        let orders = SharedOrderRepository::new(MemoryOrderRepository::new());
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let gateway = SlowGateway(charging, Mutex::new(waiting));
+        let shared =
+            SharedOrderService::new(OrderService::new(orders.clone(), gateway, notifier))
+                .with_reads(orders);
+
+        let service = shared.clone();
+        let placing = thread::spawn(move || {
+            service.place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())])
+        });
+        started.recv().unwrap();
+
+        // Answered while the gateway still holds the placement
+        assert!(shared.list_all_orders().unwrap().is_empty());
+        assert!(matches!(shared.get_order(Uuid::new_v4()), Err(OrderServiceError::OrderNotFound)));
+
+        go.send(()).unwrap();
+        let order = placing.join().unwrap().unwrap();
+        assert_eq!(shared.get_order(order.id).unwrap().id, order.id);
+    }
+
+    #[test]
+    fn test_a_panic_mid_order_stops_the_service() {
+        let notifier = ConsoleNotifier::with_writer(std::io::sink());
+        let shared = SharedOrderService::new(OrderService::new(
+            MemoryOrderRepository::new(),
+            CashPayment,
+            notifier,
+        ));
+
+        let service = shared.clone();
+        let crashed = thread::spawn(move || service.with(|_| -> Result<(), _> { panic!("crash") }));
+        assert!(crashed.join().is_err());
+
+        let refused = shared.place_order(Customer::named("Ada"), vec![Box::new(Coffee::medium())]);
+        assert!(matches!(refused, Err(OrderServiceError::ServicePoisoned(_))), "{:?}", refused);
+    }
+}
@@ -0,0 +1,105 @@
+// SOLID: SlaPolicy - how long an order may sit in Paid or Preparing
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// The policy answers ONE question: "is this order stuck past its SLA?"
+// OrderService::flag_sla_breaches tags the orders, and SlaWatchdogJob
+// (job_scheduler.rs) escalates them to the manager's channel.
+//
+// An order is only reported once per status: a Paid order flagged at 6
+// minutes is not flagged again at 7, but is watched afresh once Preparing.
+
+use crate::domain::{Order, OrderStatus};
+use chrono::{DateTime, Duration, Utc};
+
+/// Time limits for orders waiting on the shop (not on the customer)
+#[derive(Debug, Clone)]
+pub struct SlaPolicy {
+    paid_timeout: Duration,
+    preparing_timeout: Duration,
+}
+
+impl SlaPolicy {
+    /// Flag orders paid for more than `paid_minutes` without being started,
+    /// or in preparation for more than `preparing_minutes`
+    pub fn new(paid_minutes: i64, preparing_minutes: i64) -> Self {
+        Self {
+            paid_timeout: Duration::minutes(paid_minutes),
+            preparing_timeout: Duration::minutes(preparing_minutes),
+        }
+    }
+
+    /// The limit for orders in `status` (None if the SLA doesn't watch it)
+    pub fn threshold(&self, status: &OrderStatus) -> Option<Duration> {
+        match status {
+            OrderStatus::Paid => Some(self.paid_timeout),
+            OrderStatus::Preparing => Some(self.preparing_timeout),
+            _ => None,
+        }
+    }
+
+    /// Is this order past its limit and not yet flagged for it?
+    pub fn is_breached(&self, order: &Order, now: DateTime<Utc>) -> bool {
+        match self.threshold(&order.status) {
+            Some(limit) => now - order.status_since() > limit && !order.is_sla_breach_flagged(),
+            None => false,
+        }
+    }
+}
+
+impl Default for SlaPolicy {
+    /// 5 minutes to start an order, 10 to make it
+    fn default() -> Self {
+        Self::new(5, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Customer, Money, OrderItem};
+
+    fn make_paid_order() -> Order {
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let items = vec![OrderItem {
+            beverage_name: "Coffee".to_string(),
+            beverage_description: "Medium Coffee".to_string(),
+            price: Money::usd(3.50),
+            quantity: 1,
+            prep_seconds: None,
+            price_basis: None,
+            vat_rate: None,
+        }];
+        let mut order = Order::new(customer, items);
+        order.mark_as_paid("CASH-1".to_string()).unwrap();
+        order
+    }
+
+    #[test]
+    fn test_thresholds_per_status() {
+        let policy = SlaPolicy::new(5, 10);
+        let mut order = make_paid_order();
+        let since = order.status_since();
+
+        assert!(!policy.is_breached(&order, since + Duration::minutes(4)));
+        assert!(policy.is_breached(&order, since + Duration::minutes(6)));
+
+        order.mark_as_preparing().unwrap();
+        let since = order.status_since();
+        assert!(!policy.is_breached(&order, since + Duration::minutes(6)));
+        assert!(policy.is_breached(&order, since + Duration::minutes(11)));
+    }
+
+    #[test]
+    fn test_flagged_or_unwatched_orders_are_not_breached() {
+        let policy = SlaPolicy::default();
+        let mut order = make_paid_order();
+        let later = order.status_since() + Duration::hours(1);
+
+        order.flag_sla_breach(later);
+        assert!(!policy.is_breached(&order, later));
+
+        order.mark_as_preparing().unwrap();
+        order.mark_as_ready().unwrap();
+        assert!(!policy.is_breached(&order, order.status_since() + Duration::hours(1)));
+    }
+}
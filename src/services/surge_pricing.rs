@@ -0,0 +1,241 @@
+// SOLID: SurgePricing - optional peak-hour surcharge (feature `surge-pricing`)
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// Surge pricing is controversial, so it is a separate, opt-in rule rather
+// than an `if` inside OrderService. SurgePriced wraps any Beverage and
+// raises its price; OrderService charges `beverage.price()` as always and
+// never learns that a surcharge exists. Turning the feature off removes
+// this file from the build and changes nothing else.
+//
+// LISKOV SUBSTITUTION PRINCIPLE (LSP):
+// A SurgePriced coffee is still a coffee: same name, size, description and
+// prep time. Only the price differs, and `price_lines()` says by how much,
+// so receipts itemize the surcharge instead of hiding it in the total.
+//
+// The multiplier is bounded: it grows with the queue depth (orders waiting
+// for the kitchen) but never goes below 1.0 or above the configured cap.
+// With `with_free_rate`, arrivals count too: `multiplier_with` reads
+// LiveMetrics' window and adds each order a minute above the free rate to
+// the depth, so prices rise with a rush before the queue shows it.
+
+use super::live_metrics::WindowStats;
+use crate::domain::{Beverage, Currency, Money, PriceLine, Size};
+use chrono::Duration;
+
+/// Multiplier as a function of queue depth
+///
+/// Owned by the shop owner: thresholds are configuration, not code.
+#[derive(Debug, Clone)]
+pub struct SurgePricing {
+    free_depth: usize,
+    step: f64,
+    max_multiplier: f64,
+    free_rate: Option<f64>,
+}
+
+impl SurgePricing {
+    /// No surcharge up to `free_depth` queued orders, then `step` more for
+    /// each order beyond, never above `max_multiplier`
+    pub fn new(free_depth: usize, step: f64, max_multiplier: f64) -> Self {
+        Self {
+            free_depth,
+            step: step.max(0.0),
+            max_multiplier: max_multiplier.max(1.0),
+            free_rate: None,
+        }
+    }
+
+    /// Count each order a minute above `orders_per_minute` as one more queued order
+    pub fn with_free_rate(mut self, orders_per_minute: f64) -> Self {
+        self.free_rate = Some(orders_per_minute.max(0.0));
+        self
+    }
+
+    /// Price multiplier for the current queue depth (1.0 = no surcharge)
+    pub fn multiplier(&self, queue_depth: usize) -> f64 {
+        let extra = queue_depth.saturating_sub(self.free_depth) as f64;
+        (1.0 + extra * self.step).min(self.max_multiplier)
+    }
+
+    /// Price multiplier for the queue depth and the recent arrival rate
+    ///
+    /// Without a free rate, the same as `multiplier(queue_depth)`.
+    pub fn multiplier_with(&self, queue_depth: usize, live: &WindowStats) -> f64 {
+        let rush = self
+            .free_rate
+            .map(|free| (live.orders_per_minute() - free).max(0.0).ceil() as usize)
+            .unwrap_or(0);
+        self.multiplier(queue_depth + rush)
+    }
+
+    /// Wrap the beverage if the queue is long enough to surge
+    pub fn apply(&self, beverage: Box<dyn Beverage>, queue_depth: usize) -> Box<dyn Beverage> {
+        Self::surge(beverage, self.multiplier(queue_depth))
+    }
+
+    /// Wrap the beverage if the queue or the rush is big enough to surge
+    pub fn apply_with(
+        &self,
+        beverage: Box<dyn Beverage>,
+        queue_depth: usize,
+        live: &WindowStats,
+    ) -> Box<dyn Beverage> {
+        Self::surge(beverage, self.multiplier_with(queue_depth, live))
+    }
+
+    fn surge(beverage: Box<dyn Beverage>, multiplier: f64) -> Box<dyn Beverage> {
+        if multiplier > 1.0 {
+            Box::new(SurgePriced::new(beverage, multiplier))
+        } else {
+            beverage
+        }
+    }
+}
+
+impl Default for SurgePricing {
+    /// +5% per order beyond 5 in the queue, at most +25%
+    fn default() -> Self {
+        Self::new(5, 0.05, 1.25)
+    }
+}
+
+/// A beverage sold with a peak-hour surcharge
+#[derive(Debug)]
+pub struct SurgePriced {
+    inner: Box<dyn Beverage>,
+    multiplier: f64,
+}
+
+impl SurgePriced {
+    pub fn new(inner: Box<dyn Beverage>, multiplier: f64) -> Self {
+        Self { inner, multiplier }
+    }
+
+    /// The amount added on top of the normal price, rounded to the cent
+    pub fn surcharge(&self) -> Money {
+        self.inner.price().scaled(self.multiplier - 1.0)
+    }
+}
+
+impl Beverage for SurgePriced {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn base_price(&self) -> f64 {
+        self.inner.base_price()
+    }
+
+    fn price_adjustment(&self) -> f64 {
+        self.inner.price_adjustment()
+    }
+
+    fn size(&self) -> Size {
+        self.inner.size()
+    }
+
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    fn price(&self) -> Money {
+        self.inner.price() + self.surcharge()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn price_lines(&self) -> Vec<PriceLine> {
+        let mut lines = self.inner.price_lines();
+        lines.push(PriceLine {
+            label: format!("Peak-hour surcharge (x{:.2})", self.multiplier),
+            amount: self.surcharge(),
+        });
+        lines
+    }
+
+    fn base_prep_time(&self) -> Duration {
+        self.inner.base_prep_time()
+    }
+
+    fn prep_adjustment(&self) -> Duration {
+        self.inner.prep_adjustment()
+    }
+
+    fn prep_time_estimate(&self) -> Duration {
+        self.inner.prep_time_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{CashPayment, ConsoleNotifier, MemoryOrderRepository};
+    use crate::domain::{Coffee, Customer};
+    use crate::services::OrderService;
+
+    fn coffee() -> Box<dyn Beverage> {
+        Box::new(Coffee {
+            size: Size::Medium,
+            extra_shots: 0,
+        })
+    }
+
+    #[test]
+    fn test_multiplier_is_bounded() {
+        let surge = SurgePricing::new(5, 0.05, 1.25);
+
+        assert_eq!(surge.multiplier(0), 1.0);
+        assert_eq!(surge.multiplier(5), 1.0);
+        assert!((surge.multiplier(7) - 1.10).abs() < 1e-9);
+        assert_eq!(surge.multiplier(100), 1.25);
+    }
+
+    #[test]
+    fn test_a_rush_surges_before_the_queue_fills() {
+        let live = WindowStats {
+            at: chrono::Utc::now(),
+            window: Duration::minutes(5),
+            orders: 20,
+            revenue: Money::usd(80.0),
+            failures: 0,
+        };
+        let surge = SurgePricing::new(5, 0.05, 1.25);
+
+        // 4 orders a minute, 1.5 of them above the free rate: two more "queued"
+        assert_eq!(surge.multiplier_with(5, &live), 1.0);
+        let rushed = surge.with_free_rate(2.5);
+        assert!((rushed.multiplier_with(5, &live) - 1.10).abs() < 1e-9);
+        assert_eq!(rushed.apply_with(coffee(), 5, &live).price_lines().len(), 2);
+    }
+
+    #[test]
+    fn test_surcharge_is_itemized() {
+        let surged = SurgePricing::new(0, 0.10, 1.5).apply(coffee(), 2);
+        let lines = surged.price_lines();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].amount, Money::usd(3.50));
+        assert_eq!(lines[1].label, "Peak-hour surcharge (x1.20)");
+        assert_eq!(lines[1].amount, Money::usd(0.70));
+        let itemized: Money = lines.iter().map(|line| line.amount).sum();
+        assert_eq!(itemized, surged.price());
+
+        // A quiet queue leaves the beverage alone
+        assert_eq!(SurgePricing::default().apply(coffee(), 0).price_lines().len(), 1);
+    }
+
+    #[test]
+    fn test_order_service_charges_the_surcharge_unchanged() {
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new());
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+
+        let beverage = SurgePricing::new(0, 0.10, 1.5).apply(coffee(), 1);
+        let order = service.place_order(customer, vec![beverage]).unwrap();
+
+        assert_eq!(order.total_price, Money::usd(3.85));
+        assert_eq!(order.items[0].beverage_description, "Coffee (Medium)");
+    }
+}
@@ -0,0 +1,67 @@
+// SOLID: VatRates - which VAT rate is included in each product's price
+//
+// Menu prices include VAT. Most jurisdictions tax food and drink at more
+// than one rate (a reduced rate for some products), and a receipt must show
+// the VAT per rate, so the rate is recorded on each order line when the
+// order is built (OrderItem::vat_rate) and the receipt adds them up
+// (Order::vat_breakdown). A later rate change never alters a past receipt.
+//
+// SINGLE RESPONSIBILITY PRINCIPLE (SRP):
+// Rates change with the law, not with the beverages: they are configuration,
+// by product family like PrepTimeModel and PriceBooks, usually loaded from
+// JSON. Prices don't move when a rate does; only the breakdown does.
+
+use super::reporting_service::product_family;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Standard VAT rate, and the product families taxed at another one
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VatRates {
+    /// Rate of everything not listed, e.g. 0.081 for 8.1%
+    pub standard: f64,
+    /// Product family -> its rate
+    pub families: BTreeMap<String, f64>,
+}
+
+impl VatRates {
+    /// Every product at the same rate
+    pub fn new(standard: f64) -> Self {
+        Self {
+            standard,
+            families: BTreeMap::new(),
+        }
+    }
+
+    /// Tax one product family ("Smoothie") at its own rate
+    pub fn with_rate(mut self, family: &str, rate: f64) -> Self {
+        self.families.insert(family.to_string(), rate);
+        self
+    }
+
+    /// Rate included in the price of this beverage
+    pub fn rate_for(&self, beverage_name: &str) -> f64 {
+        self.families
+            .get(&product_family(beverage_name))
+            .copied()
+            .unwrap_or(self.standard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_rate_or_standard() {
+        let rates = VatRates::new(0.081).with_rate("Smoothie", 0.026);
+
+        assert_eq!(rates.rate_for("Coffee (+1 shot)"), 0.081);
+        assert_eq!(rates.rate_for("Smoothie (Mango)"), 0.026);
+
+        let json: VatRates = serde_json::from_str(r#"{ "families": { "Tea": 0.07 } }"#).unwrap();
+        assert_eq!(json.rate_for("Green Tea"), 0.07);
+        assert_eq!(json.rate_for("Coffee"), 0.0);
+    }
+}
@@ -0,0 +1,301 @@
+// SOLID: WebhookDispatcher - fan order events out to every matching subscription
+//
+// OPEN-CLOSED PRINCIPLE (OCP):
+// A new integration is a new subscription (data), not new code.
+// The relay listens on the message bus, so OrderService doesn't change either.
+//
+// DEPENDENCY INVERSION PRINCIPLE (DIP):
+// Subscriptions come from a WebhookSubscriptionStore, requests go through a
+// WebhookTransport. JSON file or database, ureq or a test double: same dispatcher.
+//
+// Each delivery is signed: `X-Webhook-Signature: sha256=<hex HMAC of the body>`,
+// keyed with the subscription's secret, so receivers can reject forgeries.
+//
+// The body is written by a PayloadSerializer: the one named by the
+// subscription's `format`, else the first one registered, else plain JSON.
+//
+// Note: there is no persistent outbox yet, so events are relayed straight from
+// the in-process bus and a failed delivery is reported, not retried.
+
+use crate::bus::{Message, MessageBus, OrderCancelled, OrderNoShow, OrderPlaced, OrderReady, Subscription};
+pub use crate::domain::WebhookEvent;
+use crate::ports::{PayloadSerializer, WebhookError, WebhookSubscriptionStore, WebhookTransport};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+impl WebhookEvent {
+    /// Wrap a bus message (the domain type knows nothing of the bus)
+    pub fn from_message<M: Message + Serialize>(message: &M) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            topic: M::TOPIC.to_string(),
+            occurred_at: Utc::now(),
+            data: serde_json::to_value(message).unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+/// Outcome of one event for one subscription
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookDelivery {
+    pub subscription_id: Uuid,
+    pub url: String,
+    pub outcome: Result<u16, WebhookError>,
+}
+
+/// Sends events to the subscriptions that want them
+pub struct WebhookDispatcher<S: WebhookSubscriptionStore, T: WebhookTransport> {
+    store: S,
+    transport: T,
+    serializers: Vec<Box<dyn PayloadSerializer>>,
+}
+
+impl<S: WebhookSubscriptionStore, T: WebhookTransport> WebhookDispatcher<S, T> {
+    /// Delivers plain JSON until serializers are registered
+    pub fn new(store: S, transport: T) -> Self {
+        Self {
+            store,
+            transport,
+            serializers: Vec::new(),
+        }
+    }
+
+    /// Offer another wire format; the first one registered is the default
+    pub fn with_serializer(mut self, serializer: impl PayloadSerializer + 'static) -> Self {
+        self.serializers.push(Box::new(serializer));
+        self
+    }
+
+    /// The store, for managing subscriptions
+    pub fn store_mut(&mut self) -> &mut S {
+        &mut self.store
+    }
+
+    /// Deliver one event to every active subscription whose filter matches
+    ///
+    /// One failing receiver doesn't stop the others; check each delivery's outcome.
+    /// A subscription asking for a format nobody registered fails its delivery.
+    pub fn dispatch(&self, event: &WebhookEvent) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        let deliveries = self
+            .store
+            .list()?
+            .into_iter()
+            .filter(|subscription| subscription.wants(&event.topic))
+            .map(|subscription| {
+                let outcome = self
+                    .encode(event, subscription.format.as_deref())
+                    .and_then(|(content_type, body)| {
+                        let headers = [
+                            ("Content-Type", content_type),
+                            ("X-Webhook-Event", event.topic.clone()),
+                            ("X-Webhook-Id", event.id.to_string()),
+                            ("X-Webhook-Signature", sign_payload(&subscription.secret, &body)),
+                        ];
+                        self.transport.post(&subscription.url, &headers, &body)
+                    });
+                WebhookDelivery {
+                    subscription_id: subscription.id,
+                    outcome,
+                    url: subscription.url,
+                }
+            })
+            .collect();
+
+        Ok(deliveries)
+    }
+
+    /// Content-Type and body of `event` in `format` (None: the default format)
+    fn encode(
+        &self,
+        event: &WebhookEvent,
+        format: Option<&str>,
+    ) -> Result<(String, String), WebhookError> {
+        let serializer = match format {
+            Some(name) => self.serializers.iter().find(|s| s.format() == name),
+            None => self.serializers.first(),
+        };
+        let failed =
+            |e: String| WebhookError::DeliveryFailed(format!("Failed to serialize event: {}", e));
+
+        match (serializer, format) {
+            (Some(serializer), _) => {
+                let body = serializer.serialize(event).map_err(|e| failed(e.to_string()))?;
+                Ok((serializer.content_type().to_string(), body))
+            }
+            (None, None | Some("json")) => {
+                let body = serde_json::to_string(event).map_err(|e| failed(e.to_string()))?;
+                Ok(("application/json".to_string(), body))
+            }
+            (None, Some(name)) => Err(WebhookError::DeliveryFailed(format!(
+                "No serializer for format '{}'",
+                name
+            ))),
+        }
+    }
+}
+
+/// `sha256=<hex>` HMAC of `body` keyed with `secret`
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body.as_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Turns order messages from the bus into webhook events
+pub struct WebhookRelay {
+    placed: Subscription<OrderPlaced>,
+    ready: Subscription<OrderReady>,
+    cancelled: Subscription<OrderCancelled>,
+    no_show: Subscription<OrderNoShow>,
+}
+
+impl WebhookRelay {
+    /// Subscribe to every order topic
+    pub fn subscribe(bus: &MessageBus) -> Self {
+        Self {
+            placed: bus.subscribe(),
+            ready: bus.subscribe(),
+            cancelled: bus.subscribe(),
+            no_show: bus.subscribe(),
+        }
+    }
+
+    /// Events received since the last call
+    pub fn collect(&self) -> Vec<WebhookEvent> {
+        let mut events: Vec<WebhookEvent> = Vec::new();
+        events.extend(self.placed.drain().iter().map(WebhookEvent::from_message));
+        events.extend(self.ready.drain().iter().map(WebhookEvent::from_message));
+        events.extend(self.cancelled.drain().iter().map(WebhookEvent::from_message));
+        events.extend(self.no_show.drain().iter().map(WebhookEvent::from_message));
+        events
+    }
+
+    /// Collect and dispatch everything received since the last call
+    pub fn forward<S: WebhookSubscriptionStore, T: WebhookTransport>(
+        &self,
+        dispatcher: &WebhookDispatcher<S, T>,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        let mut deliveries = Vec::new();
+        for event in self.collect() {
+            deliveries.extend(dispatcher.dispatch(&event)?);
+        }
+        Ok(deliveries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{MemoryWebhookStore, RecordingWebhookTransport};
+    use crate::domain::WebhookSubscription;
+
+    fn subscription(url: &str, events: &[&str]) -> WebhookSubscription {
+        WebhookSubscription::new(
+            url.to_string(),
+            "s3cret".to_string(),
+            events.iter().map(|e| e.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_fan_out_to_matching_subscriptions() {
+        let transport = RecordingWebhookTransport::new();
+        let mut dispatcher = WebhookDispatcher::new(MemoryWebhookStore::new(), transport.clone());
+        dispatcher.store_mut().add(subscription("https://a.example/hook", &["order.*"])).unwrap();
+        dispatcher.store_mut().add(subscription("https://b.example/hook", &["order.ready"])).unwrap();
+
+        let bus = MessageBus::new();
+        let relay = WebhookRelay::subscribe(&bus);
+        bus.publish(OrderCancelled {
+            order_id: Uuid::new_v4(),
+            cancelled_at: Utc::now(),
+        });
+
+        let deliveries = relay.forward(&dispatcher).unwrap();
+
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].url, "https://a.example/hook");
+        let request = &transport.requests()[0];
+        assert_eq!(request.header("X-Webhook-Event"), Some("order.cancelled"));
+        assert!(request.body.contains("\"cancelled_at\""));
+    }
+
+    #[test]
+    fn test_signature_matches_body() {
+        let transport = RecordingWebhookTransport::new();
+        let mut dispatcher = WebhookDispatcher::new(MemoryWebhookStore::new(), transport.clone());
+        dispatcher.store_mut().add(subscription("https://a.example/hook", &[])).unwrap();
+
+        let event = WebhookEvent::from_message(&OrderReady {
+            order_id: Uuid::new_v4(),
+            ready_at: Utc::now(),
+        });
+        dispatcher.dispatch(&event).unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.header("X-Webhook-Signature"), Some(sign_payload("s3cret", &request.body).as_str()));
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign_payload("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_each_subscription_gets_its_format() {
+        use crate::adapters::{CloudEventsPayload, JsonPayload, XmlPayload};
+
+        let transport = RecordingWebhookTransport::new();
+        let mut dispatcher = WebhookDispatcher::new(MemoryWebhookStore::new(), transport.clone())
+            .with_serializer(JsonPayload)
+            .with_serializer(XmlPayload)
+            .with_serializer(CloudEventsPayload::new("/shops/geneva"));
+        let store = dispatcher.store_mut();
+        store.add(subscription("https://a.example/hook", &[])).unwrap();
+        store.add(subscription("https://erp.example/in", &[]).with_format("xml")).unwrap();
+        store.add(subscription("https://fn.example/", &[]).with_format("cloudevents")).unwrap();
+        store.add(subscription("https://b.example/hook", &[]).with_format("yaml")).unwrap();
+
+        let event = WebhookEvent::from_message(&OrderReady {
+            order_id: Uuid::new_v4(),
+            ready_at: Utc::now(),
+        });
+        let deliveries = dispatcher.dispatch(&event).unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].header("Content-Type"), Some("application/json"));
+        assert_eq!(requests[1].header("Content-Type"), Some("application/xml"));
+        assert!(requests[1].body.contains("<topic>order.ready</topic>"));
+        assert_eq!(requests[2].header("Content-Type"), Some("application/cloudevents+json"));
+        assert!(requests[2].body.contains("\"specversion\":\"1.0\""));
+        // Signed as sent, whatever the format
+        assert_eq!(
+            requests[1].header("X-Webhook-Signature"),
+            Some(sign_payload("s3cret", &requests[1].body).as_str())
+        );
+        assert!(matches!(deliveries[3].outcome, Err(WebhookError::DeliveryFailed(_))));
+    }
+
+    #[test]
+    fn test_failed_delivery_is_reported() {
+        let mut dispatcher =
+            WebhookDispatcher::new(MemoryWebhookStore::new(), RecordingWebhookTransport::answering(500));
+        dispatcher.store_mut().add(subscription("https://a.example/hook", &[])).unwrap();
+
+        let event = WebhookEvent::from_message(&OrderReady {
+            order_id: Uuid::new_v4(),
+            ready_at: Utc::now(),
+        });
+        let deliveries = dispatcher.dispatch(&event).unwrap();
+
+        assert_eq!(deliveries[0].outcome, Err(WebhookError::Rejected(500)));
+    }
+}
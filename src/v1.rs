@@ -0,0 +1,58 @@
+// Stable public API, version 1
+//
+// The modules behind it (domain, ports, services, adapters...) keep moving:
+// types are split, renamed, or change modules as the shop grows. Courses,
+// slides and exercises written against the crate should import from here
+// instead, `use coffee_shop_solid::v1::*;`, and keep compiling:
+//
+// - nothing is removed from or renamed in v1; a breaking change means a v2
+//   next to it
+// - what is expected to grow is `#[non_exhaustive]`: new OrderStatus values,
+//   new error variants and new Order/OrderItem fields are additive, so match
+//   with a `_` arm and build orders with Order::new / OrderService
+//
+// Only the core of the SOLID demo is here: the order, the three ports the
+// OrderService depends on (with their errors and notices), the service
+// itself, and the adapters used in the examples. Everything else is usable
+// from its module, without the guarantee.
+
+pub use crate::domain::{
+    Beverage, Coffee, Customer, Order, OrderItem, OrderStatus, Size, Smoothie, StatusChange, Tea,
+};
+pub use crate::domain::{BeverageError, Currency, InvalidTransition, Money, TeaVariety};
+pub use crate::domain::{
+    OrderCancelledNotice, OrderNoShowNotice, OrderPlacedNotice, OrderReadyNotice,
+};
+pub use crate::ports::{
+    NotificationError, Notifier, OrderRepository, PaymentError, PaymentProcessor, PaymentRequest,
+    RepositoryError,
+};
+pub use crate::services::{OrderService, OrderServiceError, PricingCalculator};
+pub use crate::adapters::{
+    CashPayment, ConsoleNotifier, CreditCardPayment, JsonOrderRepository, MemoryOrderRepository,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The quick start, written against v1 only
+    #[test]
+    fn test_quick_start_uses_v1_only() {
+        let mut service =
+            OrderService::new(MemoryOrderRepository::new(), CashPayment, ConsoleNotifier::new());
+        let customer = Customer::new("Ada".to_string(), "ada@example.com".to_string(), None);
+        let beverages: Vec<Box<dyn Beverage>> = vec![Box::new(Coffee {
+            size: Size::Large,
+            extra_shots: 1,
+        })];
+
+        let order = service.place_order(customer, beverages).unwrap();
+
+        assert_eq!(order.status, OrderStatus::Paid);
+        assert!(matches!(
+            service.get_order(uuid::Uuid::new_v4()),
+            Err(OrderServiceError::OrderNotFound)
+        ));
+    }
+}